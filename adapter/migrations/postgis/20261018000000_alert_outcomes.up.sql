@@ -0,0 +1,14 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS alert_outcomes (
+    program INTEGER NOT NULL,
+    alert_id INTEGER NOT NULL,
+    operator VARCHAR(255) NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    start_time TIMESTAMPTZ NOT NULL,
+    outcome INTEGER NOT NULL,
+    spot_reference VARCHAR(255),
+    spot_time TIMESTAMPTZ,
+    PRIMARY KEY(program, alert_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_outcomes_operator ON alert_outcomes (operator, start_time DESC);
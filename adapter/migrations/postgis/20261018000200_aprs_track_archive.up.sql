@@ -0,0 +1,15 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS aprs_track_archive (
+    archive_id BIGSERIAL PRIMARY KEY,
+    callsign VARCHAR(255) NOT NULL,
+    ssid INTEGER NOT NULL,
+    summit VARCHAR(255),
+    start_time TIMESTAMP NOT NULL,
+    end_time TIMESTAMP NOT NULL,
+    arrival_time TIMESTAMP,
+    points JSONB NOT NULL,
+    archived_at TIMESTAMPTZ NOT NULL,
+    UNIQUE(callsign, ssid, start_time)
+);
+
+CREATE INDEX IF NOT EXISTS idx_aprs_track_archive_callsign ON aprs_track_archive (callsign, start_time DESC);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE aprs_log DROP COLUMN IF EXISTS payload;
+ALTER TABLE aprs_log DROP COLUMN IF EXISTS packet_type;
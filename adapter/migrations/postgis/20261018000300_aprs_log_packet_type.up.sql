@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE aprs_log ADD COLUMN packet_type INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE aprs_log ADD COLUMN payload TEXT;
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE aprs_log DROP COLUMN IF EXISTS altitude;
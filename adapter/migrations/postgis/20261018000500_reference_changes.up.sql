@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS reference_changes (
+    change_id BIGSERIAL PRIMARY KEY,
+    program INTEGER NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    kind INTEGER NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    changed_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reference_changes_unique ON reference_changes (program, reference, kind, COALESCE(old_value, ''), COALESCE(new_value, ''));
+CREATE INDEX IF NOT EXISTS idx_reference_changes_changed_at ON reference_changes (program, changed_at);
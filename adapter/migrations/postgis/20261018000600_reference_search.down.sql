@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_pota_references_romaji_trgm;
+DROP INDEX IF EXISTS idx_pota_references_location_trgm;
+DROP INDEX IF EXISTS idx_pota_references_name_trgm;
+DROP INDEX IF EXISTS idx_sota_references_romaji_trgm;
+DROP INDEX IF EXISTS idx_sota_references_city_trgm;
+DROP INDEX IF EXISTS idx_sota_references_name_trgm;
+DROP FUNCTION IF EXISTS fold_romaji(TEXT);
@@ -0,0 +1,16 @@
+-- Add up migration script here
+-- リファレンス名の全文検索（pg_trgm）
+-- romaji は名称（英字）を common::kana::fold_romaji と同じ規則で畳んだもの
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE OR REPLACE FUNCTION fold_romaji(name TEXT) RETURNS TEXT AS $$
+    SELECT replace(replace(replace(replace(replace(replace(replace(lower(name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u')
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE INDEX IF NOT EXISTS idx_sota_references_name_trgm ON sota_references USING GIN (summit_name gin_trgm_ops, summit_name_j gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_sota_references_city_trgm ON sota_references USING GIN (city gin_trgm_ops, city_j gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_sota_references_romaji_trgm ON sota_references USING GIN (fold_romaji(summit_name) gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS idx_pota_references_name_trgm ON pota_references USING GIN (park_name gin_trgm_ops, park_name_j gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_pota_references_location_trgm ON pota_references USING GIN (park_location gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_pota_references_romaji_trgm ON pota_references USING GIN (fold_romaji(park_name) gin_trgm_ops);
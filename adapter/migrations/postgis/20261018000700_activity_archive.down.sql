@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS spot_archive;
+DROP TABLE IF EXISTS alert_archive;
@@ -0,0 +1,6 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_spots_band;
+ALTER TABLE spots DROP COLUMN IF EXISTS spotters;
+ALTER TABLE spots DROP COLUMN IF EXISTS canonical_mode;
+ALTER TABLE spots DROP COLUMN IF EXISTS band;
+ALTER TABLE spots DROP COLUMN IF EXISTS frequency_hz;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS session_files;
+DROP TABLE IF EXISTS session_qsos;
+DROP TABLE IF EXISTS activation_sessions;
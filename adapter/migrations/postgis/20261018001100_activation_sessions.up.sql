@@ -0,0 +1,35 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS activation_sessions (
+    session_id BIGSERIAL PRIMARY KEY,
+    user_id VARCHAR(255) NOT NULL,
+    program INTEGER NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    callsign VARCHAR(255) NOT NULL,
+    operator VARCHAR(255) NOT NULL,
+    status INTEGER NOT NULL,
+    opened_at TIMESTAMPTZ NOT NULL,
+    closed_at TIMESTAMPTZ,
+    qualified_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_activation_sessions_user_id ON activation_sessions (user_id, opened_at);
+
+CREATE TABLE IF NOT EXISTS session_qsos (
+    session_id BIGINT NOT NULL,
+    client_id VARCHAR(255) NOT NULL,
+    callsign VARCHAR(255) NOT NULL,
+    band VARCHAR(32) NOT NULL,
+    mode VARCHAR(32) NOT NULL,
+    qso_time TIMESTAMPTZ NOT NULL,
+    record TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (session_id, client_id)
+);
+
+CREATE TABLE IF NOT EXISTS session_files (
+    session_id BIGINT NOT NULL,
+    filename VARCHAR(255) NOT NULL,
+    content BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (session_id, filename)
+);
@@ -0,0 +1,5 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS sota_activation_zones (
+    summit_code VARCHAR(255) NOT NULL PRIMARY KEY,
+    geometry TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE aprs_log ADD COLUMN altitude REAL;
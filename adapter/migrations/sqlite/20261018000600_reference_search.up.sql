@@ -0,0 +1,46 @@
+-- Add up migration script here
+-- リファレンス名の全文検索インデックス（trigram）
+-- romaji は名称（英字）を common::kana::fold_romaji と同じ規則で畳んだもの
+CREATE VIRTUAL TABLE IF NOT EXISTS sota_references_fts USING fts5(
+    code, name, name_j, location, romaji, tokenize = 'trigram'
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS pota_references_fts USING fts5(
+    code, name, name_j, location, romaji, tokenize = 'trigram'
+);
+
+INSERT INTO sota_references_fts (rowid, code, name, name_j, location, romaji)
+    SELECT s.rowid, s.summit_code, s.summit_name, IFNULL(s.summit_name_j, ''), IFNULL(s.city, '') || ' ' || IFNULL(s.city_j, ''), replace(replace(replace(replace(replace(replace(replace(lower(s.summit_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u') FROM sota_references AS s;
+
+INSERT INTO pota_references_fts (rowid, code, name, name_j, location, romaji)
+    SELECT p.rowid, IFNULL(p.pota_code, '') || ' ' || IFNULL(p.wwff_code, ''), p.park_name, p.park_name_j, p.park_location, replace(replace(replace(replace(replace(replace(replace(lower(p.park_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u') FROM pota_references AS p;
+
+CREATE TRIGGER IF NOT EXISTS sota_references_fts_insert AFTER INSERT ON sota_references BEGIN
+    INSERT INTO sota_references_fts (rowid, code, name, name_j, location, romaji)
+        VALUES (NEW.rowid, NEW.summit_code, NEW.summit_name, IFNULL(NEW.summit_name_j, ''), IFNULL(NEW.city, '') || ' ' || IFNULL(NEW.city_j, ''), replace(replace(replace(replace(replace(replace(replace(lower(NEW.summit_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u'));
+END;
+
+CREATE TRIGGER IF NOT EXISTS sota_references_fts_delete AFTER DELETE ON sota_references BEGIN
+    DELETE FROM sota_references_fts WHERE rowid = OLD.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS sota_references_fts_update AFTER UPDATE OF summit_code, summit_name, summit_name_j, city, city_j ON sota_references BEGIN
+    DELETE FROM sota_references_fts WHERE rowid = OLD.rowid;
+    INSERT INTO sota_references_fts (rowid, code, name, name_j, location, romaji)
+        VALUES (NEW.rowid, NEW.summit_code, NEW.summit_name, IFNULL(NEW.summit_name_j, ''), IFNULL(NEW.city, '') || ' ' || IFNULL(NEW.city_j, ''), replace(replace(replace(replace(replace(replace(replace(lower(NEW.summit_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u'));
+END;
+
+CREATE TRIGGER IF NOT EXISTS pota_references_fts_insert AFTER INSERT ON pota_references BEGIN
+    INSERT INTO pota_references_fts (rowid, code, name, name_j, location, romaji)
+        VALUES (NEW.rowid, IFNULL(NEW.pota_code, '') || ' ' || IFNULL(NEW.wwff_code, ''), NEW.park_name, NEW.park_name_j, NEW.park_location, replace(replace(replace(replace(replace(replace(replace(lower(NEW.park_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u'));
+END;
+
+CREATE TRIGGER IF NOT EXISTS pota_references_fts_delete AFTER DELETE ON pota_references BEGIN
+    DELETE FROM pota_references_fts WHERE rowid = OLD.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS pota_references_fts_update AFTER UPDATE OF pota_code, wwff_code, park_name, park_name_j, park_location ON pota_references BEGIN
+    DELETE FROM pota_references_fts WHERE rowid = OLD.rowid;
+    INSERT INTO pota_references_fts (rowid, code, name, name_j, location, romaji)
+        VALUES (NEW.rowid, IFNULL(NEW.pota_code, '') || ' ' || IFNULL(NEW.wwff_code, ''), NEW.park_name, NEW.park_name_j, NEW.park_location, replace(replace(replace(replace(replace(replace(replace(lower(NEW.park_name), ' ', ''), '-', ''), '.', ''), '''', ''), 'ou', 'o'), 'oo', 'o'), 'uu', 'u'));
+END;
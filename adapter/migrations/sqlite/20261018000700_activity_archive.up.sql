@@ -0,0 +1,46 @@
+-- Add up migration script here
+-- month (YYYYMM) を先頭にしたキーで月ごとに区切って保存する
+CREATE TABLE IF NOT EXISTS spot_archive (
+    month INTEGER NOT NULL,
+    program INTEGER NOT NULL,
+    activation_date DATE NOT NULL,
+    operator VARCHAR(255) NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    band VARCHAR(16) NOT NULL,
+    mode VARCHAR(16) NOT NULL,
+    activator VARCHAR(255) NOT NULL,
+    association VARCHAR(16) NOT NULL,
+    reference_detail VARCHAR(255) NOT NULL,
+    frequency VARCHAR(255) NOT NULL,
+    first_spot DATETIME NOT NULL,
+    last_spot DATETIME NOT NULL,
+    spot_count INTEGER NOT NULL,
+    PRIMARY KEY(month, program, activation_date, operator, reference, band, mode)
+) WITHOUT ROWID;
+
+CREATE INDEX IF NOT EXISTS idx_spot_archive_operator ON spot_archive (operator, month);
+CREATE INDEX IF NOT EXISTS idx_spot_archive_reference ON spot_archive (reference, month);
+CREATE INDEX IF NOT EXISTS idx_spot_archive_association ON spot_archive (program, association, month);
+
+CREATE TABLE IF NOT EXISTS alert_archive (
+    month INTEGER NOT NULL,
+    program INTEGER NOT NULL,
+    alert_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    association VARCHAR(16) NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    reference_detail VARCHAR(255) NOT NULL,
+    "location" VARCHAR(255) NOT NULL,
+    activator VARCHAR(255) NOT NULL,
+    activator_name VARCHAR(255),
+    operator VARCHAR(255) NOT NULL,
+    start_time DATETIME NOT NULL,
+    end_time DATETIME,
+    frequencies VARCHAR(255) NOT NULL,
+    comment VARCHAR(255),
+    poster VARCHAR(255),
+    PRIMARY KEY(month, program, alert_id)
+) WITHOUT ROWID;
+
+CREATE INDEX IF NOT EXISTS idx_alert_archive_operator ON alert_archive (operator, month);
+CREATE INDEX IF NOT EXISTS idx_alert_archive_reference ON alert_archive (reference, month);
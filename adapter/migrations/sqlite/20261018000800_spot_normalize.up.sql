@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE spots ADD COLUMN frequency_hz BIGINT;
+ALTER TABLE spots ADD COLUMN band VARCHAR(16);
+ALTER TABLE spots ADD COLUMN canonical_mode VARCHAR(32);
+-- 重複スポットのスポッター（カンマ区切り）
+ALTER TABLE spots ADD COLUMN spotters TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_spots_band ON spots (band, spot_time DESC);
@@ -0,0 +1,17 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS relay_posts (
+    post_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id VARCHAR(255) NOT NULL,
+    kind INTEGER NOT NULL,
+    program INTEGER NOT NULL,
+    reference VARCHAR(255) NOT NULL,
+    activator VARCHAR(255) NOT NULL,
+    local_id INTEGER NOT NULL,
+    status INTEGER NOT NULL,
+    upstream_id VARCHAR(255),
+    error TEXT,
+    created_at DATETIME NOT NULL,
+    relayed_at DATETIME
+);
+
+CREATE INDEX IF NOT EXISTS idx_relay_posts_user_id ON relay_posts (user_id, created_at);
@@ -1,66 +1,85 @@
-use anyhow::{anyhow, Result};
-use aprs_message::{AprsCallsign, AprsData, AprsIS};
+use anyhow::Result;
+use aprs_message::AprsCallsign;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Component;
+use std::path::Path;
 use std::sync::Arc;
 
 use common::config::AppConfig;
 use common::error::{AppError, AppResult};
-use common::http::{with_retry, RetryConfig};
+use domain::model::aprs::AprsPacket;
 use domain::repository::aprs::AprsRepositry;
 
-pub async fn connect_aprsis_with(cfg: &AppConfig) -> Result<AprsIS> {
-    connect_aprsis(&cfg.aprs_host, &cfg.aprs_user, &cfg.aprs_password).await
-}
+use crate::aprs_is::AprsIsClient;
+use crate::aprs_replay::{AprsRecorder, AprsReplay, PacketRecord};
 
-async fn connect_aprsis(host: &str, user: &str, password: &str) -> Result<AprsIS> {
-    let host = host.to_string();
-    let user = user.to_string();
-    let password = password.to_string();
+/// APRSパケットの入出力先
+#[derive(Clone)]
+pub enum AprsClient {
+    /// APRS-IS接続（recorder があれば受信パケットを記録する）
+    Live {
+        aprs: Arc<AprsIsClient>,
+        recorder: Option<Arc<AprsRecorder>>,
+    },
+    /// 記録ファイルの再生
+    Replay(Arc<AprsReplay>),
+}
 
-    with_retry("APRS-IS connect", &RetryConfig::default(), || {
-        let h = host.clone();
-        let u = user.clone();
-        let p = password.clone();
-        async move {
-            AprsIS::connect(&h, &u, &p)
-                .await
-                .map_err(|e| anyhow!("{}", e))
+pub async fn connect_aprsis_with(cfg: &AppConfig) -> Result<AprsClient> {
+    let aprs = AprsIsClient::connect(&cfg.aprs_host, &cfg.aprs_user, &cfg.aprs_password).await?;
+    let recorder = match &cfg.aprs_record_path {
+        Some(path) => {
+            tracing::info!("Recording APRS packets to {}", path);
+            Some(Arc::new(AprsRecorder::create(Path::new(path))?))
         }
+        None => None,
+    };
+    Ok(AprsClient::Live {
+        aprs: Arc::new(aprs),
+        recorder,
     })
-    .await
-    .ok_or_else(|| anyhow!("Failed to connect to APRS-IS {} after retries", host))
 }
 
 #[derive(Component)]
 #[shaku(interface = AprsRepositry)]
 pub struct AprsRepositryImpl {
-    aprs: Arc<AprsIS>,
+    aprs: AprsClient,
 }
 
 #[async_trait]
 impl AprsRepositry for AprsRepositryImpl {
     async fn write_message(&self, addressee: &AprsCallsign, message: &str) -> AppResult<()> {
-        self.aprs
-            .write_message(addressee, message)
+        let aprs = match &self.aprs {
+            AprsClient::Live { aprs, .. } => aprs,
+            AprsClient::Replay(replay) => return replay.write_message(addressee, message).await,
+        };
+        aprs.write_message(addressee, message)
             .await
             .map_err(|_| AppError::APRSError)?;
         Ok(())
     }
 
     async fn set_filter(&self, filter: String) -> AppResult<()> {
-        self.aprs
-            .set_filter(filter)
+        let aprs = match &self.aprs {
+            AprsClient::Live { aprs, .. } => aprs,
+            AprsClient::Replay(replay) => return replay.set_filter(filter).await,
+        };
+        aprs.set_filter(filter)
             .await
             .map_err(|_| AppError::APRSError)?;
         Ok(())
     }
 
-    async fn set_buddy_list(&self, _buddy: Vec<String>) -> AppResult<()> {
+    async fn set_buddy_list(&self, buddy: Vec<String>) -> AppResult<()> {
+        let aprs = match &self.aprs {
+            AprsClient::Live { aprs, .. } => aprs,
+            AprsClient::Replay(replay) => return replay.set_buddy_list(buddy).await,
+        };
         // b/ buddy filter と f/ friend filter はサーバー側の実装不具合で動作しない。
         // r/ レンジ + t/ タイプフィルターで代替し、コールサインフィルタリングはアプリ側で行う。
-        self.aprs
-            .set_filter("r/36/137/1500 t/p/m".to_string())
+        // タイプは位置・メッセージに加えてオブジェクト・アイテム・気象・テレメトリも受ける。
+        aprs.set_filter("r/36/137/1500 t/pmoiwt".to_string())
             .await
             .map_err(|e| {
                 tracing::warn!("APRS set_filter failed: {e}");
@@ -69,10 +88,94 @@ impl AprsRepositry for AprsRepositryImpl {
         Ok(())
     }
 
-    async fn get_aprs_packet(&self) -> AppResult<AprsData> {
-        self.aprs.read_packet().await.map_err(|e| {
-            tracing::warn!("APRS read_packet failed: {e}");
-            AppError::APRSError
-        })
+    async fn get_aprs_packet(&self) -> AppResult<(DateTime<Utc>, AprsPacket)> {
+        let (aprs, recorder) = match &self.aprs {
+            AprsClient::Live { aprs, recorder } => (aprs, recorder),
+            AprsClient::Replay(replay) => return replay.get_aprs_packet().await,
+        };
+        // 解析できない種類のパケット（Mic-E・ステータス等）は読み飛ばす
+        loop {
+            let line = aprs.read_line().await.map_err(|e| {
+                tracing::warn!("APRS read_line failed: {e}");
+                AppError::APRSError
+            })?;
+            match line.parse::<AprsPacket>() {
+                Ok(packet) => {
+                    if let Some(recorder) = recorder {
+                        recorder.record(PacketRecord::Raw { line });
+                    }
+                    return Ok((Utc::now(), packet));
+                }
+                Err(e) => tracing::trace!("APRS packet skipped: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_live_packets_are_parsed_from_raw_lines() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let host = listener.local_addr().unwrap().to_string();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let login = lines.next_line().await.unwrap().unwrap();
+            writer
+                .write_all(
+                    concat!(
+                        "# logresp JL1NIE-10 verified, server T2TEST\r\n",
+                        "JA1ABC-7>APRS,TCPIP*,qAC,T2TEST:;JA/TK-001*111111z3540.00N/13945.00E[QRV 7.032 CW\r\n",
+                        "JA1ABC-7>APRS,TCPIP*:`(_fn\"Oj/\r\n",
+                        "JA1ABC-7>APRS,TCPIP*:!3540.00N/13945.00E>/A=004921\r\n",
+                        "JA1ABC-7>APRS,TCPIP*::JL1NIE-10:KEEP{12\r\n",
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .unwrap();
+            let ack = lines.next_line().await.unwrap().unwrap();
+            (login, ack)
+        });
+
+        let aprs = AprsIsClient::connect(&host, "JL1NIE-10", "12345")
+            .await
+            .unwrap();
+        let repo = AprsRepositryImpl {
+            aprs: AprsClient::Live {
+                aprs: Arc::new(aprs),
+                recorder: None,
+            },
+        };
+
+        match repo.get_aprs_packet().await.unwrap().1 {
+            AprsPacket::Object { callsign, object } => {
+                assert_eq!(callsign.ssid, Some(7));
+                assert_eq!(object.name, "JA/TK-001");
+                assert_eq!(object.comment, "QRV 7.032 CW");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        // Mic-Eは読み飛ばし、高度付きの位置が届く
+        match repo.get_aprs_packet().await.unwrap().1 {
+            AprsPacket::Position { altitude, .. } => {
+                assert!((altitude.unwrap() - 1500.0).abs() < 0.1);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match repo.get_aprs_packet().await.unwrap().1 {
+            AprsPacket::Message { message, .. } => assert_eq!(message, "KEEP"),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let (login, ack) = server.await.unwrap();
+        assert!(login.starts_with("user JL1NIE-10 pass 12345 "), "{}", login);
+        assert_eq!(ack, "JL1NIE-10>APRS,TCPIP*::JA1ABC-7 :ack12");
     }
 }
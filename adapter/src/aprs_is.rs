@@ -0,0 +1,324 @@
+//! APRS-ISクライアント
+//!
+//! 受信した行は TNC2形式のまま返し、パケットの解析は `AprsPacket` の `FromStr` に任せる。
+//! 送信するメッセージは改行と67文字ごとに区切り、1行ずつ番号付きのパケットにする。
+//! 切断やタイムアウトのときは再接続し、最後に設定したフィルタを設定し直す。
+
+use anyhow::{anyhow, Result};
+use aprs_message::AprsCallsign;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex as AsyncMutex;
+use tokio::time::{timeout, Duration};
+
+use common::http::{with_retry, RetryConfig};
+use domain::model::aprs::format_callsign;
+
+/// サーバーは20秒ごとにコメント行を送るので、これだけ何も届かなければ切断とみなす（秒）
+const READ_TIMEOUT_SECS: u64 = 120;
+
+/// メッセージ本文の最大長（APRS仕様）
+const MAX_MESSAGE_LEN: usize = 67;
+
+/// メッセージ番号の上限（5桁まで）
+const MAX_MSGNO: u32 = 99999;
+
+/// 自局あてのメッセージの種類
+#[derive(Debug, PartialEq)]
+enum Incoming {
+    /// 自局あてではない、または番号なしのメッセージ
+    Other,
+    /// 番号付きのメッセージ（ackを返す）
+    Numbered { from: String, id: String },
+    /// 自局が送ったメッセージへのack/rej
+    Ack,
+}
+
+/// TNC2形式の行が自局あてのメッセージか調べる
+fn classify_message(line: &str, own: &str) -> Incoming {
+    let Some((header, payload)) = line.split_once(':') else {
+        return Incoming::Other;
+    };
+    let Some(payload) = payload.strip_prefix(':') else {
+        return Incoming::Other;
+    };
+    let (Some(addressee), Some(text)) = (payload.get(..9), payload.get(9..)) else {
+        return Incoming::Other;
+    };
+    let Some(text) = text.strip_prefix(':') else {
+        return Incoming::Other;
+    };
+    if !addressee.trim().eq_ignore_ascii_case(own) {
+        return Incoming::Other;
+    }
+    if text.starts_with("ack") || text.starts_with("rej") {
+        return Incoming::Ack;
+    }
+    // `{MM}AA` 形式（reply-ack）の場合は `}` の前まで
+    let id = text
+        .rsplit_once('{')
+        .map(|(_, id)| id.split('}').next().unwrap_or_default());
+    match (header.split_once('>'), id) {
+        (Some((from, _)), Some(id))
+            if (1..=5).contains(&id.len()) && id.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            Incoming::Numbered {
+                from: from.to_string(),
+                id: id.to_string(),
+            }
+        }
+        _ => Incoming::Other,
+    }
+}
+
+/// 自局から送るメッセージの行
+fn message_line(from: &str, addressee: &str, message: &str) -> String {
+    format!("{}>APRS,TCPIP*::{:<9}:{}", from, addressee, message)
+}
+
+/// 送信するメッセージを番号付きのパケットの行に分ける
+///
+/// 本文は改行ごとに分け、さらに67文字ごとに区切る。
+/// 制御文字とメッセージ本文に使えない `|` `~` `{` は取り除く。
+fn message_lines(from: &str, addressee: &str, message: &str, msgno: &AtomicU32) -> Vec<String> {
+    message
+        .split(['\r', '\n'])
+        .map(|l| {
+            l.chars()
+                .filter(|c| !c.is_control() && !matches!(c, '|' | '~' | '{'))
+                .collect::<Vec<_>>()
+        })
+        .filter(|l| l.iter().any(|c| !c.is_whitespace()))
+        .flat_map(|l| {
+            l.chunks(MAX_MESSAGE_LEN)
+                .map(|c| c.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .map(|text| {
+            let id = msgno.fetch_add(1, Ordering::Relaxed) % MAX_MSGNO + 1;
+            message_line(from, addressee, &format!("{}{{{}", text, id))
+        })
+        .collect()
+}
+
+pub struct AprsIsClient {
+    host: String,
+    user: String,
+    password: String,
+    /// 再接続時に設定し直すフィルタ
+    filter: Mutex<Option<String>>,
+    reader: AsyncMutex<Option<BufReader<OwnedReadHalf>>>,
+    writer: AsyncMutex<Option<OwnedWriteHalf>>,
+    /// 次に送るメッセージの番号
+    msgno: AtomicU32,
+}
+
+impl AprsIsClient {
+    pub async fn connect(host: &str, user: &str, password: &str) -> Result<Self> {
+        let client = Self {
+            host: host.to_string(),
+            user: user.to_uppercase(),
+            password: password.to_string(),
+            filter: Mutex::new(None),
+            reader: AsyncMutex::new(None),
+            writer: AsyncMutex::new(None),
+            msgno: AtomicU32::new(0),
+        };
+        let (reader, writer) = client.open_with_retry().await?;
+        *client.reader.lock().await = Some(reader);
+        *client.writer.lock().await = Some(writer);
+        Ok(client)
+    }
+
+    /// 接続してログインする（フィルタを設定済みならログイン時に指定する）
+    async fn open(&self) -> Result<(BufReader<OwnedReadHalf>, OwnedWriteHalf)> {
+        let mut login = format!(
+            "user {} pass {} vers SotaApp {}",
+            self.user,
+            self.password,
+            env!("CARGO_PKG_VERSION")
+        );
+        if let Some(filter) = self.filter.lock().ok().and_then(|f| f.clone()) {
+            login.push_str(&format!(" filter {}", filter));
+        }
+        let stream = TcpStream::connect(&self.host).await?;
+        let (reader, mut writer) = stream.into_split();
+        writer
+            .write_all(format!("{}\r\n", login).as_bytes())
+            .await?;
+        Ok((BufReader::new(reader), writer))
+    }
+
+    async fn open_with_retry(&self) -> Result<(BufReader<OwnedReadHalf>, OwnedWriteHalf)> {
+        with_retry("APRS-IS connect", &RetryConfig::default(), || self.open())
+            .await
+            .ok_or_else(|| anyhow!("Failed to connect to APRS-IS {} after retries", self.host))
+    }
+
+    async fn send(&self, line: &str) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        let w = writer
+            .as_mut()
+            .ok_or_else(|| anyhow!("APRS-IS not connected"))?;
+        if let Err(e) = w.write_all(format!("{}\r\n", line).as_bytes()).await {
+            *writer = None;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    pub async fn set_filter(&self, filter: String) -> Result<()> {
+        if let Ok(mut f) = self.filter.lock() {
+            *f = Some(filter.clone());
+        }
+        self.send(&format!("#filter {}", filter)).await
+    }
+
+    pub async fn write_message(&self, addressee: &AprsCallsign, message: &str) -> Result<()> {
+        for line in message_lines(
+            &self.user,
+            &format_callsign(addressee),
+            message,
+            &self.msgno,
+        ) {
+            self.send(&line).await?;
+        }
+        Ok(())
+    }
+
+    /// 次のパケットの行を返す
+    ///
+    /// サーバーのコメント行（`#`）と自局へのack/rejは返さず、番号付きのメッセージにはackを返す。
+    pub async fn read_line(&self) -> Result<String> {
+        let mut reader = self.reader.lock().await;
+        let mut buf = Vec::new();
+        loop {
+            let received = match reader.as_mut() {
+                Some(r) => {
+                    buf.clear();
+                    match timeout(
+                        Duration::from_secs(READ_TIMEOUT_SECS),
+                        r.read_until(b'\n', &mut buf),
+                    )
+                    .await
+                    {
+                        Ok(Ok(n)) if n > 0 => true,
+                        Ok(Ok(_)) => {
+                            tracing::warn!("APRS-IS connection closed");
+                            false
+                        }
+                        Ok(Err(e)) => {
+                            tracing::warn!("APRS-IS read failed: {e}");
+                            false
+                        }
+                        Err(_) => {
+                            tracing::warn!("APRS-IS no data for {}s", READ_TIMEOUT_SECS);
+                            false
+                        }
+                    }
+                }
+                None => false,
+            };
+            if !received {
+                *reader = None;
+                let (r, w) = self.open_with_retry().await?;
+                *reader = Some(r);
+                *self.writer.lock().await = Some(w);
+                continue;
+            }
+
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match classify_message(line, &self.user) {
+                Incoming::Ack => continue,
+                Incoming::Numbered { from, id } => {
+                    if let Err(e) = self
+                        .send(&message_line(&self.user, &from, &format!("ack{}", id)))
+                        .await
+                    {
+                        tracing::warn!("APRS-IS ack to {} failed: {e}", from);
+                    }
+                }
+                Incoming::Other => {}
+            }
+            return Ok(line.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_message() {
+        let own = "JL1NIE-10";
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS,TCPIP*::JL1NIE-10:KEEP{12", own),
+            Incoming::Numbered {
+                from: "JA1ABC-7".to_string(),
+                id: "12".to_string()
+            }
+        );
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS::JL1NIE-10:QRV{AB}3C", own),
+            Incoming::Numbered {
+                from: "JA1ABC-7".to_string(),
+                id: "AB".to_string()
+            }
+        );
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS::JL1NIE-10:ack3", own),
+            Incoming::Ack
+        );
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS::JL1NIE-10:KEEP", own),
+            Incoming::Other
+        );
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS::JA2XYZ   :KEEP{12", own),
+            Incoming::Other
+        );
+        assert_eq!(
+            classify_message("JA1ABC-7>APRS:!3540.00N/13945.00E>", own),
+            Incoming::Other
+        );
+    }
+
+    #[test]
+    fn test_message_lines_split() {
+        let own = "JA1ABC-7";
+        let msgno = AtomicU32::new(0);
+        let long = "A".repeat(70);
+        let message = format!(
+            "Welcome to JA/KN-006. Mt.Hoge 1234m 8pts.\r\n\n{}\nx|y~z{{1",
+            long
+        );
+        let lines = message_lines("JL1NIE-10", own, &message, &msgno);
+        assert_eq!(
+            lines,
+            vec![
+                "JL1NIE-10>APRS,TCPIP*::JA1ABC-7 :Welcome to JA/KN-006. Mt.Hoge 1234m 8pts.{1",
+                format!("JL1NIE-10>APRS,TCPIP*::JA1ABC-7 :{}{{2", "A".repeat(67)).as_str(),
+                "JL1NIE-10>APRS,TCPIP*::JA1ABC-7 :AAA{3",
+                "JL1NIE-10>APRS,TCPIP*::JA1ABC-7 :xyz1{4",
+            ]
+        );
+        for (i, line) in lines.iter().enumerate() {
+            assert!(!line.contains(['\r', '\n']));
+            assert_eq!(
+                classify_message(line, own),
+                Incoming::Numbered {
+                    from: "JL1NIE-10".to_string(),
+                    id: (i + 1).to_string()
+                }
+            );
+        }
+    }
+}
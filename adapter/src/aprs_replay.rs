@@ -0,0 +1,434 @@
+//! APRSパケットの記録と再生
+//!
+//! 受信パケットを JSON Lines で記録し、記録ファイルを元の時間間隔（または倍速）で
+//! 再生する。再生時に送信されたメッセージは APRS-IS へは送らずに保持する。
+//! ライブ受信したパケットは TNC2形式の生パケット（`"type":"raw"`）のまま記録する。
+
+use aprs_message::AprsCallsign;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::time::Duration;
+
+use common::error::{AppError, AppResult};
+use domain::model::aprs::{format_callsign, parse_callsign, AprsPacket};
+use domain::repository::aprs::AprsRepositry;
+
+/// 記録ファイルの1行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedPacket {
+    pub time: DateTime<Utc>,
+    #[serde(flatten)]
+    pub packet: PacketRecord,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PacketRecord {
+    Position {
+        from: String,
+        latitude: f64,
+        longitude: f64,
+        /// 高度（m）。活動範囲の判定で標高差に使う
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        altitude: Option<f64>,
+    },
+    Message {
+        from: String,
+        addressee: String,
+        message: String,
+    },
+    /// TNC2形式の生パケット（オブジェクト・気象・テレメトリ等）
+    Raw { line: String },
+}
+
+impl TryFrom<PacketRecord> for AprsPacket {
+    type Error = AppError;
+
+    fn try_from(record: PacketRecord) -> AppResult<Self> {
+        match record {
+            PacketRecord::Position {
+                from,
+                latitude,
+                longitude,
+                altitude,
+            } => Ok(AprsPacket::Position {
+                callsign: parse_callsign(&from),
+                latitude,
+                longitude,
+                altitude,
+            }),
+            PacketRecord::Message {
+                from,
+                addressee,
+                message,
+            } => Ok(AprsPacket::Message {
+                callsign: parse_callsign(&from),
+                addressee,
+                message,
+            }),
+            PacketRecord::Raw { line } => line.parse(),
+        }
+    }
+}
+
+/// 記録ファイルを読み込む（空行と `#` で始まる行は無視）
+pub fn read_recording(path: &Path) -> AppResult<Vec<RecordedPacket>> {
+    let file =
+        File::open(path).map_err(|e| AppError::IoError(format!("{}: {}", path.display(), e)))?;
+
+    let mut packets = Vec::new();
+    for (lineno, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| AppError::IoError(e.to_string()))?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |e: &dyn std::fmt::Display| {
+            AppError::UnprocessableEntity(format!("{}:{}: {}", path.display(), lineno + 1, e))
+        };
+        let packet: RecordedPacket = serde_json::from_str(line).map_err(|e| error(&e))?;
+        if let PacketRecord::Raw { line } = &packet.packet {
+            line.parse::<AprsPacket>().map_err(|e| error(&e))?;
+        }
+        packets.push(packet);
+    }
+    Ok(packets)
+}
+
+/// 記録中のメッセージ以外のパケット送信元（SSIDなし）
+pub fn position_callsigns(packets: &[RecordedPacket]) -> HashSet<String> {
+    packets
+        .iter()
+        .filter_map(|p| match AprsPacket::try_from(p.packet.clone()) {
+            Ok(AprsPacket::Message { .. }) | Err(_) => None,
+            Ok(packet) => Some(packet.callsign().callsign.clone()),
+        })
+        .collect()
+}
+
+/// 受信パケットをファイルに追記する
+pub struct AprsRecorder {
+    file: Mutex<File>,
+}
+
+impl AprsRecorder {
+    pub fn create(path: &Path) -> AppResult<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| AppError::IoError(format!("{}: {}", path.display(), e)))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// 記録に失敗してもパケット処理は継続する
+    pub fn record(&self, packet: PacketRecord) {
+        let record = RecordedPacket {
+            time: Utc::now(),
+            packet,
+        };
+        let result = serde_json::to_string(&record)
+            .map_err(|e| e.to_string())
+            .and_then(|line| {
+                let mut file = self.file.lock().map_err(|e| e.to_string())?;
+                writeln!(file, "{}", line).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            tracing::warn!("APRS record failed: {}", e);
+        }
+    }
+}
+
+/// 再生中に送信されたメッセージ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SentMessage {
+    /// 送信のきっかけになったパケットの記録時刻
+    pub time: DateTime<Utc>,
+    pub addressee: String,
+    pub message: String,
+}
+
+/// 記録ファイルを再生する AprsRepositry
+///
+/// `speed` は再生倍率。1.0 で記録時と同じ間隔、0 以下なら待たずに再生する。
+pub struct AprsReplay {
+    packets: Mutex<VecDeque<RecordedPacket>>,
+    speed: f64,
+    current: Mutex<Option<DateTime<Utc>>>,
+    sent: Mutex<Vec<SentMessage>>,
+}
+
+impl AprsReplay {
+    pub fn new(packets: Vec<RecordedPacket>, speed: f64) -> Self {
+        Self {
+            packets: Mutex::new(packets.into()),
+            speed,
+            current: Mutex::new(None),
+            sent: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn open(path: &Path, speed: f64) -> AppResult<Self> {
+        Ok(Self::new(read_recording(path)?, speed))
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.packets.lock().map(|p| p.len()).unwrap_or(0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    pub fn sent_messages(&self) -> Vec<SentMessage> {
+        self.sent.lock().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// 直前のパケットとの記録時刻の差を再生倍率で縮めた待ち時間
+    fn delay(&self, prev: Option<DateTime<Utc>>, next: DateTime<Utc>) -> Option<Duration> {
+        if self.speed <= 0.0 {
+            return None;
+        }
+        let elapsed = (next - prev?).to_std().ok()?;
+        Some(elapsed.div_f64(self.speed))
+    }
+}
+
+#[async_trait]
+impl AprsRepositry for AprsReplay {
+    async fn write_message(&self, addressee: &AprsCallsign, message: &str) -> AppResult<()> {
+        let time = self
+            .current
+            .lock()
+            .map_err(|_| AppError::APRSError)?
+            .unwrap_or_else(Utc::now);
+        tracing::info!(
+            "APRS replay message to {}: {}",
+            format_callsign(addressee),
+            message
+        );
+        self.sent
+            .lock()
+            .map_err(|_| AppError::APRSError)?
+            .push(SentMessage {
+                time,
+                addressee: format_callsign(addressee),
+                message: message.to_string(),
+            });
+        Ok(())
+    }
+
+    async fn set_buddy_list(&self, _buddy: Vec<String>) -> AppResult<()> {
+        Ok(())
+    }
+
+    async fn set_filter(&self, _filter: String) -> AppResult<()> {
+        Ok(())
+    }
+
+    async fn get_aprs_packet(&self) -> AppResult<(DateTime<Utc>, AprsPacket)> {
+        let next = self
+            .packets
+            .lock()
+            .map_err(|_| AppError::APRSError)?
+            .pop_front()
+            .ok_or_else(|| AppError::EntityNotFound("APRS recording finished".to_string()))?;
+
+        let prev = self
+            .current
+            .lock()
+            .map_err(|_| AppError::APRSError)?
+            .replace(next.time);
+        if let Some(delay) = self.delay(prev, next.time) {
+            tokio::time::sleep(delay).await;
+        }
+
+        Ok((next.time, next.packet.try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn t(sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 3, 0, 0, sec).unwrap()
+    }
+
+    fn position(time: DateTime<Utc>, from: &str) -> RecordedPacket {
+        RecordedPacket {
+            time,
+            packet: PacketRecord::Position {
+                from: from.to_string(),
+                latitude: 35.0,
+                longitude: 139.0,
+                altitude: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_record_format() {
+        let line = r#"{"time":"2026-05-03T00:00:01Z","type":"message","from":"JA1ABC-7","addressee":"JL1NIE-10","message":"DX"}"#;
+        let packet: RecordedPacket = serde_json::from_str(line).unwrap();
+        assert_eq!(packet.time, t(1));
+
+        match AprsPacket::try_from(packet.packet.clone()).unwrap() {
+            AprsPacket::Message {
+                callsign, message, ..
+            } => {
+                assert_eq!(callsign.callsign, "JA1ABC");
+                assert_eq!(callsign.ssid, Some(7));
+                assert_eq!(message, "DX");
+            }
+            _ => panic!("expected message"),
+        }
+        assert_eq!(serde_json::to_string(&packet).unwrap(), line);
+    }
+
+    #[test]
+    fn test_recorder_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aprs.jsonl");
+
+        let recorder = AprsRecorder::create(&path).unwrap();
+        recorder.record(PacketRecord::Position {
+            from: "JA1ABC-9".to_string(),
+            latitude: 35.5,
+            longitude: 139.5,
+            altitude: Some(1500.0),
+        });
+        recorder.record(PacketRecord::Raw {
+            line: "JA1ABC>APRS,TCPIP*::JL1NIE-10:JA".to_string(),
+        });
+
+        let packets = read_recording(&path).unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(
+            packets[0].packet,
+            PacketRecord::Position {
+                from: "JA1ABC-9".to_string(),
+                latitude: 35.5,
+                longitude: 139.5,
+                altitude: Some(1500.0),
+            }
+        );
+        // 再生した位置にも高度が残る（山頂到着の判定に使う）
+        assert!(matches!(
+            AprsPacket::try_from(packets[0].packet.clone()),
+            Ok(AprsPacket::Position { altitude: Some(alt), .. }) if alt == 1500.0
+        ));
+        assert!(matches!(
+            AprsPacket::try_from(packets[1].packet.clone()),
+            Ok(AprsPacket::Message { message, .. }) if message == "JA"
+        ));
+        assert_eq!(
+            position_callsigns(&packets),
+            HashSet::from(["JA1ABC".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_read_recording_reports_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.jsonl");
+        std::fs::write(
+            &path,
+            "# comment\n\n{\"time\":\"2026-05-03T00:00:00Z\",\"type\":\"unknown\"}\n",
+        )
+        .unwrap();
+
+        match read_recording(&path) {
+            Err(AppError::UnprocessableEntity(msg)) => assert!(msg.contains(":3:"), "{}", msg),
+            other => panic!("unexpected {:?}", other.map(|p| p.len())),
+        }
+    }
+
+    #[test]
+    fn test_raw_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("raw.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"time":"2026-05-03T00:00:00Z","type":"raw","line":"JA1ABC-13>APRS:_05030000c220s004t041"}"#,
+                "\n",
+                r#"{"time":"2026-05-03T00:00:01Z","type":"raw","line":"JA1ABC-7>APRS:unknown"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        match read_recording(&path) {
+            Err(AppError::UnprocessableEntity(msg)) => assert!(msg.contains(":2:"), "{}", msg),
+            other => panic!("unexpected {:?}", other.map(|p| p.len())),
+        }
+
+        let line = r#"{"time":"2026-05-03T00:00:00Z","type":"raw","line":"JA1ABC-13>APRS:_05030000c220s004t041"}"#;
+        let packet: RecordedPacket = serde_json::from_str(line).unwrap();
+        assert_eq!(
+            position_callsigns(std::slice::from_ref(&packet)),
+            HashSet::from(["JA1ABC".to_string()])
+        );
+        match AprsPacket::try_from(packet.packet).unwrap() {
+            AprsPacket::Weather {
+                callsign, report, ..
+            } => {
+                assert_eq!(callsign.ssid, Some(13));
+                assert_eq!(report.wind_direction, Some(220));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_captures_messages() {
+        let replay = AprsReplay::new(
+            vec![position(t(0), "JA1ABC-7"), position(t(30), "JA1ABC-7")],
+            0.0,
+        );
+
+        replay.get_aprs_packet().await.unwrap();
+        let (time, _) = replay.get_aprs_packet().await.unwrap();
+        assert_eq!(time, t(30));
+        replay
+            .write_message(&parse_callsign("JA1ABC-7"), "Welcome")
+            .await
+            .unwrap();
+
+        assert!(replay.is_finished());
+        assert!(replay.get_aprs_packet().await.is_err());
+        assert_eq!(
+            replay.sent_messages(),
+            vec![SentMessage {
+                time: t(30),
+                addressee: "JA1ABC-7".to_string(),
+                message: "Welcome".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_replay_accelerated_timing() {
+        // 記録上2秒間隔を100倍速で再生すると20ms待つ
+        let replay = AprsReplay::new(
+            vec![position(t(0), "JA1ABC"), position(t(2), "JA1ABC")],
+            100.0,
+        );
+
+        let start = std::time::Instant::now();
+        replay.get_aprs_packet().await.unwrap();
+        assert!(start.elapsed() < Duration::from_millis(20));
+        replay.get_aprs_packet().await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+}
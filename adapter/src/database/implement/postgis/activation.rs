@@ -3,14 +3,19 @@ use shaku::Component;
 use sqlx::PgConnection;
 
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
 
-use domain::model::activation::{Alert, Spot};
+use domain::model::activation::{Alert, AlertOutcome, Spot};
+use domain::model::archive::{
+    ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
 use domain::model::event::{DeleteAct, FindAct};
 use domain::repository::activation::ActivationRepositry;
 
+use super::archive;
 use super::querybuilder::findact_query_builder;
 use crate::database::connect::ConnectionPool;
-use crate::database::model::activation::{AlertRow, SpotRow};
+use crate::database::model::activation::{AlertOutcomeRow, AlertRow, SpotRow};
 
 #[derive(Component)]
 #[shaku(interface = ActivationRepositry)]
@@ -65,8 +70,8 @@ impl ActivationRepositryImpl {
     async fn update_spot_impl(&self, s: SpotRow, db: &mut PgConnection) -> AppResult<()> {
         sqlx::query!(
             r#"
-                INSERT INTO spots (program, spot_id, reference, reference_detail, activator, activator_name, operator, spot_time, frequency, mode, spotter,comment) 
-                VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                INSERT INTO spots (program, spot_id, reference, reference_detail, activator, activator_name, operator, spot_time, frequency, mode, spotter,comment, frequency_hz, band, canonical_mode, spotters) 
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
                 ON CONFLICT (program, spot_id ) DO UPDATE             
                 SET program = EXCLUDED.program,
                     spot_id = EXCLUDED.spot_id,
@@ -79,7 +84,11 @@ impl ActivationRepositryImpl {
                     frequency = EXCLUDED.frequency,
                     mode = EXCLUDED.mode,
                     spotter = EXCLUDED.spotter,
-                    comment = EXCLUDED.comment
+                    comment = EXCLUDED.comment,
+                    frequency_hz = EXCLUDED.frequency_hz,
+                    band = EXCLUDED.band,
+                    canonical_mode = EXCLUDED.canonical_mode,
+                    spotters = EXCLUDED.spotters
             "#,
             s.program.as_i32(),
             s.spot_id,
@@ -93,6 +102,44 @@ impl ActivationRepositryImpl {
             s.mode,
             s.spotter,
             s.comment,
+            s.frequency_hz,
+            s.band,
+            s.canonical_mode,
+            s.spotters,
+        )
+        .execute(db)
+        .await
+        .map_err(db_error("activation operation postgis"))?;
+        Ok(())
+    }
+
+    async fn update_outcome_impl(
+        &self,
+        o: AlertOutcomeRow,
+        db: &mut PgConnection,
+    ) -> AppResult<()> {
+        // 確定した結果（Pending以外）は書き換えない（スポットが削除された後の再判定で不在にしない）
+        sqlx::query!(
+            r#"
+                INSERT INTO alert_outcomes (program, alert_id, operator, reference, start_time, outcome, spot_reference, spot_time)
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (program, alert_id) DO UPDATE
+                SET operator = EXCLUDED.operator,
+                    reference = EXCLUDED.reference,
+                    start_time = EXCLUDED.start_time,
+                    outcome = EXCLUDED.outcome,
+                    spot_reference = EXCLUDED.spot_reference,
+                    spot_time = EXCLUDED.spot_time
+                WHERE alert_outcomes.outcome = 0
+            "#,
+            o.program.as_i32(),
+            o.alert_id,
+            o.operator,
+            o.reference,
+            o.start_time,
+            o.outcome.as_i32(),
+            o.spot_reference,
+            o.spot_time,
         )
         .execute(db)
         .await
@@ -176,7 +223,11 @@ impl ActivationRepositryImpl {
                 frequency,
                 mode,
                 spotter,
-                comment
+                comment,
+                frequency_hz,
+                band,
+                canonical_mode,
+                spotters
             FROM spots WHERE "#
             .to_string();
 
@@ -190,11 +241,37 @@ impl ActivationRepositryImpl {
 
         Ok(rows.into_iter().map(Spot::from).collect())
     }
+
+    async fn select_outcomes_by_condition(&self, query: &str) -> AppResult<Vec<AlertOutcome>> {
+        let mut select = r#"
+            SELECT
+                program,
+                alert_id,
+                operator,
+                reference,
+                start_time,
+                outcome,
+                spot_reference,
+                spot_time
+            FROM alert_outcomes WHERE "#
+            .to_string();
+
+        select.push_str(query);
+
+        let sql_query = sqlx::query_as::<_, AlertOutcomeRow>(&select);
+        let rows: Vec<AlertOutcomeRow> = sql_query
+            .fetch_all(self.pool.inner_ref())
+            .await
+            .map_err(db_error("activation operation postgis"))?;
+
+        Ok(rows.into_iter().map(AlertOutcome::from).collect())
+    }
 }
 
 #[async_trait]
 impl ActivationRepositry for ActivationRepositryImpl {
     async fn update_alerts(&self, alerts: Vec<Alert>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_alerts");
         let mut tx = self
             .pool
             .inner_ref()
@@ -212,6 +289,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn update_spots(&self, spots: Vec<Spot>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_spots");
         let mut tx = self
             .pool
             .inner_ref()
@@ -228,6 +306,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
         Ok(())
     }
     async fn delete_alerts(&self, query: DeleteAct) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "delete_alerts");
         let mut tx = self
             .pool
             .inner_ref()
@@ -243,6 +322,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn delete_spots(&self, query: DeleteAct) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "delete_spots");
         let mut tx = self
             .pool
             .inner_ref()
@@ -258,14 +338,72 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn find_alerts(&self, event: &FindAct) -> AppResult<Vec<Alert>> {
+        let _timer = QueryTimer::new("activation", "find_alerts");
         let query = findact_query_builder(true, event);
         let results = self.select_alerts_by_condition(&query).await?;
         Ok(results)
     }
 
     async fn find_spots(&self, event: &FindAct) -> AppResult<Vec<Spot>> {
+        let _timer = QueryTimer::new("activation", "find_spots");
         let query = findact_query_builder(false, event);
         let results = self.select_spots_by_condition(&query).await?;
         Ok(results)
     }
+
+    async fn update_outcomes(&self, outcomes: Vec<AlertOutcome>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_outcomes");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("activation transaction postgis"))?;
+
+        for o in outcomes {
+            self.update_outcome_impl(AlertOutcomeRow::from(o), &mut tx)
+                .await?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("activation transaction postgis"))?;
+        Ok(())
+    }
+
+    async fn find_outcomes(&self, event: &FindAct) -> AppResult<Vec<AlertOutcome>> {
+        let _timer = QueryTimer::new("activation", "find_outcomes");
+        let query = findact_query_builder(true, event);
+        let results = self.select_outcomes_by_condition(&query).await?;
+        Ok(results)
+    }
+
+    async fn archive_alerts(&self, query: DeleteAct) -> AppResult<u64> {
+        let _timer = QueryTimer::new("activation", "archive_alerts");
+        archive::archive_alerts(&self.pool, query.before).await
+    }
+
+    async fn archive_spots(&self, query: DeleteAct) -> AppResult<u64> {
+        let _timer = QueryTimer::new("activation", "archive_spots");
+        archive::archive_spots(&self.pool, query.before).await
+    }
+
+    async fn find_alert_archive(&self, query: &FindArchive) -> AppResult<Vec<AlertArchive>> {
+        let _timer = QueryTimer::new("activation", "find_alert_archive");
+        archive::find_alert_archive(&self.pool, query).await
+    }
+
+    async fn find_spot_archive(&self, query: &FindArchive) -> AppResult<Vec<SpotArchive>> {
+        let _timer = QueryTimer::new("activation", "find_spot_archive");
+        archive::find_spot_archive(&self.pool, query).await
+    }
+
+    async fn reference_year_stats(&self, query: &FindArchive) -> AppResult<Vec<ReferenceYearStat>> {
+        let _timer = QueryTimer::new("activation", "reference_year_stats");
+        archive::reference_year_stats(&self.pool, query).await
+    }
+
+    async fn activator_stats(&self, query: &FindArchive) -> AppResult<Vec<ActivatorStat>> {
+        let _timer = QueryTimer::new("activation", "activator_stats");
+        archive::activator_stats(&self.pool, query).await
+    }
 }
@@ -1,14 +1,15 @@
 use aprs_message::AprsCallsign;
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use serde_json::error::Category;
 use shaku::Component;
 use sqlx::SqliteConnection;
 
 use crate::database::connect::ConnectionPool;
-use crate::database::model::aprslog::AprsLogRow;
+use crate::database::model::aprslog::{AprsLogRow, AprsTrackArchiveRow};
 use common::error::{db_error, tx_error, AppResult};
-use domain::model::aprslog::AprsLog;
+use common::metrics::QueryTimer;
+use domain::model::aprslog::{AprsLog, AprsSegment, AprsTrackArchive};
 use domain::repository::aprs::AprsLogRepository;
 
 #[derive(Component)]
@@ -30,7 +31,10 @@ impl AprsLogRepositoryImpl {
                     distance,
                     state,
                     longitude,
-                    latitude
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
                 FROM aprs_log WHERE callsign = $1
             "#,
             callsign
@@ -54,7 +58,10 @@ impl AprsLogRepositoryImpl {
                     distance,
                     state,
                     longitude,
-                    latitude
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
                 FROM aprs_log WHERE time = $1
             "#,
             after
@@ -77,8 +84,11 @@ impl AprsLogRepositoryImpl {
                     distance,
                     state,
                     longitude,
-                    latitude
-                ) VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
+                ) VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
             log.time,
             log.callsign,
@@ -88,6 +98,9 @@ impl AprsLogRepositoryImpl {
             log.state,
             log.longitude,
             log.latitude,
+            log.altitude,
+            log.packet_type,
+            log.payload,
         )
         .execute(db)
         .await
@@ -96,6 +109,73 @@ impl AprsLogRepositoryImpl {
         Ok(())
     }
 
+    async fn upsert_archive(&self, row: AprsTrackArchiveRow) -> AppResult<i64> {
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO aprs_track_archive (
+                    callsign,
+                    ssid,
+                    summit,
+                    start_time,
+                    end_time,
+                    arrival_time,
+                    points,
+                    archived_at
+                ) VALUES($1, $2, $3, $4, $5, $6, $7::jsonb, $8)
+                ON CONFLICT(callsign, ssid, start_time) DO UPDATE SET
+                    summit = EXCLUDED.summit,
+                    end_time = EXCLUDED.end_time,
+                    arrival_time = EXCLUDED.arrival_time,
+                    points = EXCLUDED.points,
+                    archived_at = EXCLUDED.archived_at
+                RETURNING archive_id
+            "#,
+            row.callsign,
+            row.ssid,
+            row.summit,
+            row.start_time,
+            row.end_time,
+            row.arrival_time,
+            row.points,
+            row.archived_at,
+        )
+        .fetch_one(self.pool.inner_ref())
+        .await
+        .map_err(db_error("aprs_track_archive operation postgis"))?;
+        Ok(result.archive_id)
+    }
+
+    async fn select_archives(
+        &self,
+        callsign: Option<&str>,
+        archive_id: Option<i64>,
+    ) -> AppResult<Vec<AprsTrackArchiveRow>> {
+        let result = sqlx::query_as!(
+            AprsTrackArchiveRow,
+            r#"
+                SELECT
+                    archive_id,
+                    callsign,
+                    ssid,
+                    summit,
+                    start_time,
+                    end_time,
+                    arrival_time,
+                    points::text AS "points!",
+                    archived_at
+                FROM aprs_track_archive
+                WHERE ($1::text IS NULL OR callsign = $1) AND ($2::bigint IS NULL OR archive_id = $2)
+                ORDER BY start_time DESC
+            "#,
+            callsign,
+            archive_id
+        )
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("aprs_track_archive operation postgis"))?;
+        Ok(result)
+    }
+
     async fn delete(&self, before: &NaiveDateTime, db: &mut SqliteConnection) -> AppResult<()> {
         sqlx::query!(
             r#"
@@ -113,6 +193,7 @@ impl AprsLogRepositoryImpl {
 #[async_trait]
 impl AprsLogRepository for AprsLogRepositoryImpl {
     async fn get_aprs_log_by_callsign(&self, callsign: &AprsCallsign) -> AppResult<Vec<AprsLog>> {
+        let _timer = QueryTimer::new("aprs_log", "get_aprs_log_by_callsign");
         let callsign = callsign.callsign;
         let result = self.select_by_call(&callsign).await?;
         let mut logs = Vec::new();
@@ -124,6 +205,7 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
     }
 
     async fn get_aprs_log_by_time(&self, after: &NaiveDateTime) -> AppResult<Vec<AprsLog>> {
+        let _timer = QueryTimer::new("aprs_log", "get_aprs_log_by_time");
         let result = self.select_by_time(after).await?;
         let mut logs = Vec::new();
         for log in result {
@@ -134,6 +216,7 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
     }
 
     async fn insert_aprs_log(&self, aprs_log: AprsLog) -> AppResult<()> {
+        let _timer = QueryTimer::new("aprs_log", "insert_aprs_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -150,6 +233,7 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
     }
 
     async fn delete_aprs_log(&self, before: &NaiveDateTime) -> AppResult<()> {
+        let _timer = QueryTimer::new("aprs_log", "delete_aprs_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -164,4 +248,29 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
 
         Ok(())
     }
+
+    async fn archive_track(&self, segment: AprsSegment) -> AppResult<i64> {
+        let _timer = QueryTimer::new("aprs_track_archive", "archive_track");
+        let row = AprsTrackArchiveRow::from_segment(segment, Utc::now().naive_utc())?;
+        self.upsert_archive(row).await
+    }
+
+    async fn find_track_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchive>> {
+        let _timer = QueryTimer::new("aprs_track_archive", "find_track_archives");
+        self.select_archives(Some(callsign), None)
+            .await?
+            .into_iter()
+            .map(AprsTrackArchive::try_from)
+            .collect()
+    }
+
+    async fn find_track_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchive>> {
+        let _timer = QueryTimer::new("aprs_track_archive", "find_track_archive");
+        self.select_archives(None, Some(archive_id))
+            .await?
+            .into_iter()
+            .next()
+            .map(AprsTrackArchive::try_from)
+            .transpose()
+    }
 }
@@ -0,0 +1,376 @@
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use sqlx::query_builder::QueryBuilder;
+use sqlx::{PgConnection, Postgres};
+
+use common::error::{db_error, tx_error, AppResult};
+use domain::model::activation::{Alert, Spot};
+use domain::model::archive::{
+    compact_spots, ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
+
+use crate::database::connect::ConnectionPool;
+use crate::database::model::activation::{AlertRow, SpotRow};
+use crate::database::model::archive::{
+    ActivatorStatRow, AlertArchiveRow, ReferenceYearStatRow, SpotArchiveRow,
+};
+
+/// 月のパーティションがなければ作成する
+async fn ensure_partition(table: &str, month: i32, db: &mut PgConnection) -> AppResult<()> {
+    let next = if month % 100 == 12 {
+        (month / 100 + 1) * 100 + 1
+    } else {
+        month + 1
+    };
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {table}_{month} PARTITION OF {table} FOR VALUES FROM ({month}) TO ({next})"
+    );
+    sqlx::query(&sql)
+        .execute(db)
+        .await
+        .map_err(db_error("create archive partition"))?;
+    Ok(())
+}
+
+async fn upsert_spot(s: SpotArchiveRow, db: &mut PgConnection) -> AppResult<()> {
+    sqlx::query(
+        r#"
+            INSERT INTO spot_archive (month, program, activation_date, operator, reference, band, mode,
+                                      activator, association, reference_detail, frequency, first_spot, last_spot, spot_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (month, program, activation_date, operator, reference, band, mode) DO UPDATE
+            SET activator = CASE WHEN EXCLUDED.last_spot > spot_archive.last_spot
+                                 THEN EXCLUDED.activator ELSE spot_archive.activator END,
+                reference_detail = CASE WHEN EXCLUDED.last_spot > spot_archive.last_spot
+                                        THEN EXCLUDED.reference_detail ELSE spot_archive.reference_detail END,
+                frequency = CASE WHEN EXCLUDED.last_spot > spot_archive.last_spot
+                                 THEN EXCLUDED.frequency ELSE spot_archive.frequency END,
+                first_spot = LEAST(spot_archive.first_spot, EXCLUDED.first_spot),
+                last_spot = GREATEST(spot_archive.last_spot, EXCLUDED.last_spot),
+                spot_count = spot_archive.spot_count + EXCLUDED.spot_count
+        "#,
+    )
+    .bind(s.month)
+    .bind(s.program.as_i32())
+    .bind(s.activation_date)
+    .bind(s.operator)
+    .bind(s.reference)
+    .bind(s.band)
+    .bind(s.mode)
+    .bind(s.activator)
+    .bind(s.association)
+    .bind(s.reference_detail)
+    .bind(s.frequency)
+    .bind(s.first_spot)
+    .bind(s.last_spot)
+    .bind(s.spot_count)
+    .execute(db)
+    .await
+    .map_err(db_error("insert/update spot_archive"))?;
+    Ok(())
+}
+
+async fn upsert_alert(a: AlertArchiveRow, db: &mut PgConnection) -> AppResult<()> {
+    sqlx::query(
+        r#"
+            INSERT INTO alert_archive (month, program, alert_id, user_id, association, reference, reference_detail,
+                                       "location", activator, activator_name, operator, start_time, end_time,
+                                       frequencies, comment, poster)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+            ON CONFLICT (month, program, alert_id) DO UPDATE
+            SET user_id = EXCLUDED.user_id,
+                association = EXCLUDED.association,
+                reference = EXCLUDED.reference,
+                reference_detail = EXCLUDED.reference_detail,
+                "location" = EXCLUDED."location",
+                activator = EXCLUDED.activator,
+                activator_name = EXCLUDED.activator_name,
+                operator = EXCLUDED.operator,
+                start_time = EXCLUDED.start_time,
+                end_time = EXCLUDED.end_time,
+                frequencies = EXCLUDED.frequencies,
+                comment = EXCLUDED.comment,
+                poster = EXCLUDED.poster
+        "#,
+    )
+    .bind(a.month)
+    .bind(a.program.as_i32())
+    .bind(a.alert_id)
+    .bind(a.user_id)
+    .bind(a.association)
+    .bind(a.reference)
+    .bind(a.reference_detail)
+    .bind(a.location)
+    .bind(a.activator)
+    .bind(a.activator_name)
+    .bind(a.operator)
+    .bind(a.start_time)
+    .bind(a.end_time)
+    .bind(a.frequencies)
+    .bind(a.comment)
+    .bind(a.poster)
+    .execute(db)
+    .await
+    .map_err(db_error("insert/update alert_archive"))?;
+    Ok(())
+}
+
+/// 期限切れのスポットをまとめてアーカイブへ移す（保存と削除は同じトランザクション）
+pub async fn archive_spots(pool: &ConnectionPool, before: DateTime<Utc>) -> AppResult<u64> {
+    let mut tx = pool
+        .inner_ref()
+        .begin()
+        .await
+        .map_err(tx_error("begin archive_spots"))?;
+
+    let rows = sqlx::query_as::<_, SpotRow>(
+        r#"
+            SELECT program, spot_id, reference, reference_detail, activator, activator_name,
+                   operator, spot_time, frequency, mode, spotter, comment,
+                   frequency_hz, band, canonical_mode, spotters
+            FROM spots
+            WHERE spot_time < $1
+        "#,
+    )
+    .bind(before)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error("fetch expired spots"))?;
+    let spots: Vec<Spot> = rows.into_iter().map(Spot::from).collect();
+
+    let compacted = compact_spots(&spots);
+    let months: BTreeSet<i32> = compacted.iter().map(|s| s.month).collect();
+    for month in months {
+        ensure_partition("spot_archive", month, &mut tx).await?;
+    }
+    for s in compacted {
+        upsert_spot(SpotArchiveRow::from(s), &mut tx).await?;
+    }
+
+    sqlx::query("DELETE FROM spots WHERE spot_time < $1")
+        .bind(before)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error("delete old spots"))?;
+
+    tx.commit()
+        .await
+        .map_err(tx_error("commit archive_spots"))?;
+    Ok(spots.len() as u64)
+}
+
+/// 期限切れのアラートをアーカイブへ移す（保存と削除は同じトランザクション）
+pub async fn archive_alerts(pool: &ConnectionPool, before: DateTime<Utc>) -> AppResult<u64> {
+    let mut tx = pool
+        .inner_ref()
+        .begin()
+        .await
+        .map_err(tx_error("begin archive_alerts"))?;
+
+    let rows = sqlx::query_as::<_, AlertRow>(
+        r#"
+            SELECT program, alert_id, user_id, reference, reference_detail, location, activator,
+                   activator_name, operator, start_time, end_time, frequencies, comment, poster
+            FROM alerts
+            WHERE start_time < $1
+        "#,
+    )
+    .bind(before)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_error("fetch expired alerts"))?;
+    let count = rows.len() as u64;
+
+    let archived: Vec<AlertArchive> = rows
+        .into_iter()
+        .map(|row| AlertArchive::from(Alert::from(row)))
+        .collect();
+    let months: BTreeSet<i32> = archived.iter().map(|a| a.month).collect();
+    for month in months {
+        ensure_partition("alert_archive", month, &mut tx).await?;
+    }
+    for a in archived {
+        upsert_alert(AlertArchiveRow::from(a), &mut tx).await?;
+    }
+
+    sqlx::query("DELETE FROM alerts WHERE start_time < $1")
+        .bind(before)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error("delete old alerts"))?;
+
+    tx.commit()
+        .await
+        .map_err(tx_error("commit archive_alerts"))?;
+    Ok(count)
+}
+
+/// 検索条件をWHERE句に追加する（月で先に絞り込む）
+fn push_filters<'a>(
+    builder: &mut QueryBuilder<'a, Postgres>,
+    r: &'a FindArchive,
+    time_column: &str,
+    is_spot: bool,
+) {
+    let (from_month, to_month) = r.months();
+    if let Some(month) = from_month {
+        builder.push(" month >= ");
+        builder.push_bind(month);
+        builder.push(" AND ");
+    }
+    if let Some(month) = to_month {
+        builder.push(" month <= ");
+        builder.push_bind(month);
+        builder.push(" AND ");
+    }
+    if let Some(program) = &r.program {
+        builder.push(" program = ");
+        builder.push_bind(program.as_i32());
+        builder.push(" AND ");
+    }
+    if let Some(operator) = &r.operator {
+        builder.push(" operator = ");
+        builder.push_bind(operator.as_str());
+        builder.push(" AND ");
+    }
+    if let Some(reference) = &r.reference {
+        builder.push(" reference = ");
+        builder.push_bind(reference.as_str());
+        builder.push(" AND ");
+    }
+    if let Some(association) = &r.association {
+        builder.push(" association = ");
+        builder.push_bind(association.as_str());
+        builder.push(" AND ");
+    }
+    if is_spot {
+        if let Some(band) = &r.band {
+            builder.push(" band = ");
+            builder.push_bind(band.as_str());
+            builder.push(" AND ");
+        }
+        if let Some(mode) = &r.mode {
+            builder.push(" mode = ");
+            builder.push_bind(mode.as_str());
+            builder.push(" AND ");
+        }
+    }
+    if let Some(after) = r.after {
+        builder.push(format!(" {} >= ", time_column));
+        builder.push_bind(after);
+        builder.push(" AND ");
+    }
+    if let Some(before) = r.before {
+        builder.push(format!(" {} <= ", time_column));
+        builder.push_bind(before);
+        builder.push(" AND ");
+    }
+    builder.push(" TRUE ");
+}
+
+fn push_paging<'a>(builder: &mut QueryBuilder<'a, Postgres>, r: &'a FindArchive) {
+    if let Some(limit) = r.limit {
+        builder.push(" LIMIT ");
+        builder.push_bind(limit);
+    }
+    if let Some(offset) = r.offset {
+        builder.push(" OFFSET ");
+        builder.push_bind(offset);
+    }
+}
+
+pub async fn find_spot_archive(
+    pool: &ConnectionPool,
+    query: &FindArchive,
+) -> AppResult<Vec<SpotArchive>> {
+    let mut builder = QueryBuilder::new(
+        r#"
+            SELECT month, program, activation_date, operator, reference, band, mode, activator,
+                   association, reference_detail, frequency, first_spot, last_spot, spot_count
+            FROM spot_archive WHERE "#,
+    );
+    push_filters(&mut builder, query, "first_spot", true);
+    builder.push(" ORDER BY first_spot DESC ");
+    push_paging(&mut builder, query);
+
+    let rows: Vec<SpotArchiveRow> = builder
+        .build_query_as::<SpotArchiveRow>()
+        .fetch_all(pool.inner_ref())
+        .await
+        .map_err(db_error("fetch spot_archive"))?;
+    Ok(rows.into_iter().map(SpotArchive::from).collect())
+}
+
+pub async fn find_alert_archive(
+    pool: &ConnectionPool,
+    query: &FindArchive,
+) -> AppResult<Vec<AlertArchive>> {
+    let mut builder = QueryBuilder::new(
+        r#"
+            SELECT month, association, program, alert_id, user_id, reference, reference_detail,
+                   location, activator, activator_name, operator, start_time, end_time,
+                   frequencies, comment, poster
+            FROM alert_archive WHERE "#,
+    );
+    push_filters(&mut builder, query, "start_time", false);
+    builder.push(" ORDER BY start_time DESC ");
+    push_paging(&mut builder, query);
+
+    let rows: Vec<AlertArchiveRow> = builder
+        .build_query_as::<AlertArchiveRow>()
+        .fetch_all(pool.inner_ref())
+        .await
+        .map_err(db_error("fetch alert_archive"))?;
+    Ok(rows.into_iter().map(AlertArchive::from).collect())
+}
+
+/// リファレンスごと・年ごとのアクティベーション数（運用者・日ごとに1回と数える）
+pub async fn reference_year_stats(
+    pool: &ConnectionPool,
+    query: &FindArchive,
+) -> AppResult<Vec<ReferenceYearStat>> {
+    let mut builder = QueryBuilder::new(
+        r#"
+            SELECT program, reference, month / 100 AS year,
+                   COUNT(DISTINCT operator || '|' || activation_date) AS activations,
+                   COUNT(DISTINCT operator) AS activators
+            FROM spot_archive WHERE "#,
+    );
+    push_filters(&mut builder, query, "first_spot", true);
+    builder.push(
+        " GROUP BY program, reference, month / 100 ORDER BY year DESC, activations DESC, reference ",
+    );
+    push_paging(&mut builder, query);
+
+    let rows: Vec<ReferenceYearStatRow> = builder
+        .build_query_as::<ReferenceYearStatRow>()
+        .fetch_all(pool.inner_ref())
+        .await
+        .map_err(db_error("fetch reference_year_stats"))?;
+    Ok(rows.into_iter().map(ReferenceYearStat::from).collect())
+}
+
+/// アクティベーターごとのアクティベーション数（リファレンス・日ごとに1回と数える）
+pub async fn activator_stats(
+    pool: &ConnectionPool,
+    query: &FindArchive,
+) -> AppResult<Vec<ActivatorStat>> {
+    let mut builder = QueryBuilder::new(
+        r#"
+            SELECT operator,
+                   COUNT(DISTINCT reference || '|' || activation_date) AS activations,
+                   COUNT(DISTINCT reference) AS reference_count
+            FROM spot_archive WHERE "#,
+    );
+    push_filters(&mut builder, query, "first_spot", true);
+    builder.push(" GROUP BY operator ORDER BY activations DESC, reference_count DESC, operator ");
+    push_paging(&mut builder, query);
+
+    let rows: Vec<ActivatorStatRow> = builder
+        .build_query_as::<ActivatorStatRow>()
+        .fetch_all(pool.inner_ref())
+        .await
+        .map_err(db_error("fetch activator_stats"))?;
+    Ok(rows.into_iter().map(ActivatorStat::from).collect())
+}
@@ -1,14 +1,18 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use domain::model::id::LogId;
 use shaku::Component;
 use sqlx::PgConnection;
 
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
 
+use domain::model::changelog::ReferenceChange;
 use domain::model::event::{DeleteLog, DeleteRef, FindRef, PagenatedResult};
 use domain::model::pota::{ParkCode, PotaActLog, PotaHuntLog, PotaRefLog, PotaReference};
 use domain::model::AwardProgram::POTA;
 
+use super::changelog;
 use super::querybuilder::findref_query_builder;
 use crate::database::connect::ConnectionPool;
 use crate::database::model::pota::{PotaLogRow, PotaRefLogRow, PotaReferenceRow};
@@ -321,7 +325,18 @@ impl POTARepositoryImpl {
 
 #[async_trait]
 impl PotaRepository for PotaRepositoryImpl {
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "record_changes");
+        changelog::record_changes(&self.pool, changes).await
+    }
+
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>> {
+        let _timer = QueryTimer::new("pota", "find_changes");
+        changelog::find_changes(&self.pool, POTA, since).await
+    }
+
     async fn find_reference(&self, event: &FindRef) -> AppResult<Vec<PotaRefLog>> {
+        let _timer = QueryTimer::new("pota", "find_reference");
         let log_id = event.log_id;
         let query = findref_query_builder(POTA, event);
         let results = self.select_by_condition(log_id, &query).await?;
@@ -330,6 +345,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn create_reference(&self, references: Vec<PotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "create_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -350,6 +366,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn show_reference(&self, event: &FindRef) -> AppResult<PotaReference> {
+        let _timer = QueryTimer::new("pota", "show_reference");
         let query = findref_query_builder(POTA, event);
         let result = self.select(&query).await?;
         Ok(result.into())
@@ -359,6 +376,7 @@ impl PotaRepository for PotaRepositoryImpl {
         &self,
         event: &FindRef,
     ) -> AppResult<PagenatedResult<PotaReference>> {
+        let _timer = QueryTimer::new("pota", "show_all_references");
         let limit = event.limit.unwrap_or(10);
         let offset = event.offset.unwrap_or(0);
         let query = findref_query_builder(POTA, event);
@@ -372,6 +390,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn update_reference(&self, references: Vec<PotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "update_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -388,6 +407,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn delete_reference(&self, query: DeleteRef<ParkCode>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "delete_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -405,6 +425,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn upload_activator_log(&self, logs: Vec<PotaActLog>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "upload_activator_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -424,6 +445,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn upload_hunter_log(&self, logs: Vec<PotaHuntLog>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "upload_hunter_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -443,6 +465,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn delete_log(&self, query: DeleteLog) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "delete_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -1,4 +1,6 @@
+use common::kana::SearchKeys;
 use domain::model::event::{FindAct, FindRef};
+use domain::model::locator::PREFECTURES;
 use domain::model::AwardProgram::{self, POTA, SOTA, WWFF};
 use sqlx::{Postgres, QueryBuilder};
 
@@ -85,6 +87,14 @@ fn build_sota_conditions<'a>(builder: &mut QueryBuilder<'a, Postgres>, r: &'a Fi
             builder.push(") AND ");
         }
     }
+
+    if let Some(date) = r.valid_on() {
+        builder.push("valid_from <= ");
+        builder.push_bind(date);
+        builder.push(" AND valid_to >= ");
+        builder.push_bind(date);
+        builder.push(" AND ");
+    }
     builder.push("TRUE ");
 
     if r.min_elev.is_some() {
@@ -197,10 +207,17 @@ pub fn build_activation_query<'a>(
             builder.push_bind(after);
             builder.push(" ORDER BY start_time ASC ");
         }
-    } else if let Some(after) = r.issued_after {
-        builder.push("spot_time >= ");
-        builder.push_bind(after);
-        builder.push(" ORDER BY spot_time DESC ");
+    } else {
+        if let Some(band) = &r.band {
+            builder.push("band = ");
+            builder.push_bind(band.as_str());
+            builder.push(" AND ");
+        }
+        if let Some(after) = r.issued_after {
+            builder.push("spot_time >= ");
+            builder.push_bind(after);
+            builder.push(" ORDER BY spot_time DESC ");
+        }
     }
 
     if let Some(limit) = &r.limit {
@@ -221,6 +238,11 @@ pub fn build_activation_query<'a>(
 #[deprecated(note = "Use build_sota_ref_query or build_pota_ref_query instead")]
 pub fn findref_query_builder(mode: AwardProgram, r: &FindRef) -> String {
     let mut query: String = String::new();
+    let search = r
+        .name
+        .as_deref()
+        .map(SearchKeys::new)
+        .filter(|k| !k.is_empty());
 
     if r.sota_code.is_some() && mode == SOTA {
         query.push_str(&format!(
@@ -238,20 +260,13 @@ pub fn findref_query_builder(mode: AwardProgram, r: &FindRef) -> String {
             r.wwff_code.clone().unwrap()
         ))
     } else {
-        if let Some(name) = &r.name {
-            if r.is_sota() && mode == SOTA {
-                let name = format!("'%{}%'", name);
-                query.push_str(&format!(
-                    "(summit_code ILIKE {} OR summit_name LIKE {} OR summit_name_j LIKE {}) AND ",
-                    name, name, name
-                ));
-            } else {
-                let name = format!("'%{}%'", name);
-                query.push_str(&format!(
-                "(p.pota_code ILIKE {} OR p.wwff_code ILIKE {} OR p.park_name LIKE {} OR p.park_name_j LIKE {}) AND ",
-                name, name, name, name
+        if let Some(keys) = &search {
+            let (names, others, romaji) = search_columns(r, &mode);
+            let columns: Vec<&str> = names.iter().chain(others).copied().collect();
+            query.push_str(&format!(
+                "{} AND ",
+                search_terms(keys, &columns, romaji, |v| format!("%{}%", v))
             ));
-            }
         }
 
         if let Some(min_elev) = &r.min_elev {
@@ -266,6 +281,51 @@ pub fn findref_query_builder(mode: AwardProgram, r: &FindRef) -> String {
             }
         }
 
+        let sota = r.is_sota() && mode == SOTA;
+
+        if let Some(grid) = &r.grid {
+            let column = if sota { "maidenhead" } else { "p.maidenhead" };
+            query.push_str(&format!(
+                "({} ILIKE {}) AND ",
+                column,
+                quote(&format!("{}%", grid))
+            ));
+        }
+
+        for (column, code) in [("jcc_code", &r.jcc_code), ("jcg_code", &r.jcg_code)] {
+            if let Some(code) = code {
+                // city_jに都道府県名が無い場合は地域名で都道府県を確認する
+                let location = if sota {
+                    format!(
+                        "(city_j LIKE m.prefecture || '%' || m.municipality || '%' OR (city_j LIKE m.municipality || '%' AND region_name = {}))",
+                        prefecture_region_sql("m.prefecture")
+                    )
+                } else {
+                    "p.park_location LIKE '%' || m.municipality || '%'".to_string()
+                };
+                query.push_str(&format!(
+                    "EXISTS (SELECT 1 FROM municipality_century_codes AS m WHERE m.{} = {} AND {}) AND ",
+                    column,
+                    quote(code),
+                    location
+                ));
+            }
+        }
+
+        if let Some(pref) = &r.prefecture {
+            if sota {
+                query.push_str(&format!(
+                    "(city_j LIKE '%{}%' OR region_name = '{}' OR association_name LIKE '%{}%') AND ",
+                    pref.name, pref.romaji, pref.romaji
+                ));
+            } else {
+                query.push_str(&format!(
+                    "(p.park_locid LIKE '%{}%' OR p.park_location LIKE '%{}%') AND ",
+                    pref.code, pref.name
+                ));
+            }
+        }
+
         if let Some(bbox) = &r.bbox {
             query.push_str(&format!(
                 "(ST_Within(coordinates, ST_MakeEnvelope({}, {}, {}, {}, 4326))) AND ",
@@ -279,20 +339,41 @@ pub fn findref_query_builder(mode: AwardProgram, r: &FindRef) -> String {
                 lon, lat, dist
             ));
         }
+
+        if let Some(route) = &r.route {
+            query.push_str(&format!(
+                "(ST_DWithin(coordinates,ST_GeogFromText('{}'),{})) AND ",
+                route.to_wkt(),
+                route.width
+            ));
+        }
+    }
+
+    if mode == SOTA {
+        if let Some(date) = r.valid_on() {
+            query.push_str(&format!(
+                "(valid_from <= '{}' AND valid_to >= '{}') AND ",
+                date, date
+            ));
+        }
     }
     query.push_str("TRUE ");
 
+    let rank = search
+        .map(|keys| search_rank(&keys, r, &mode))
+        .unwrap_or_default();
+
     if r.is_sota() && mode == SOTA {
         if r.min_elev.is_some() {
-            query.push_str("ORDER BY alt_m DESC ");
+            query.push_str(&format!("ORDER BY {}alt_m DESC ", rank));
         } else {
-            query.push_str("ORDER BY summit_code ");
+            query.push_str(&format!("ORDER BY {}summit_code ", rank));
         }
     } else if r.is_pota() && (mode == POTA || mode == WWFF) {
         if r.min_area.is_some() {
-            query.push_str("ORDER BY p.park_area DESC ");
+            query.push_str(&format!("ORDER BY {}p.park_area DESC ", rank));
         } else {
-            query.push_str("ORDER BY p.pota_code ");
+            query.push_str(&format!("ORDER BY {}p.pota_code ", rank));
         }
     }
 
@@ -306,6 +387,81 @@ pub fn findref_query_builder(mode: AwardProgram, r: &FindRef) -> String {
     query
 }
 
+/// 名称検索の対象列（名称、コード・所在地、ローマ字の式）
+fn search_columns(
+    r: &FindRef,
+    mode: &AwardProgram,
+) -> (
+    &'static [&'static str],
+    &'static [&'static str],
+    &'static str,
+) {
+    if r.is_sota() && *mode == SOTA {
+        (
+            &["summit_name", "summit_name_j"],
+            &["summit_code", "city", "city_j"],
+            "fold_romaji(summit_name)",
+        )
+    } else {
+        (
+            &["p.park_name", "p.park_name_j"],
+            &["p.pota_code", "p.wwff_code", "p.park_location"],
+            "fold_romaji(p.park_name)",
+        )
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// 検索キーのいずれかが列に一致する条件（ローマ字の式はローマ字のキーと照合）
+fn search_terms(
+    keys: &SearchKeys,
+    columns: &[&str],
+    romaji: &str,
+    pattern: impl Fn(&str) -> String,
+) -> String {
+    let mut terms = vec!["FALSE".to_string()];
+    for column in columns {
+        for v in &keys.text {
+            terms.push(format!("{} ILIKE {}", column, quote(&pattern(v))));
+        }
+    }
+    for v in &keys.romaji {
+        terms.push(format!("{} LIKE {}", romaji, quote(&pattern(v))));
+    }
+    format!("({})", terms.join(" OR "))
+}
+
+/// 名称検索の関連度（完全一致、前方一致、名称の部分一致の順、同順位はpg_trgmの類似度）
+fn search_rank(keys: &SearchKeys, r: &FindRef, mode: &AwardProgram) -> String {
+    let (names, others, romaji) = search_columns(r, mode);
+    let exact_columns: Vec<&str> = names.iter().chain(&others[..1]).copied().collect();
+
+    let mut similarity: Vec<String> = names
+        .iter()
+        .flat_map(|c| {
+            keys.text
+                .iter()
+                .map(move |v| format!("similarity({}, {})", c, quote(v)))
+        })
+        .collect();
+    similarity.extend(
+        keys.romaji
+            .iter()
+            .map(|v| format!("similarity({}, {})", romaji, quote(v))),
+    );
+
+    format!(
+        "CASE WHEN {} THEN 0 WHEN {} THEN 1 WHEN {} THEN 2 ELSE 3 END, GREATEST({}) DESC, ",
+        search_terms(keys, &exact_columns, romaji, |v| v.to_string()),
+        search_terms(keys, &exact_columns, romaji, |v| format!("{}%", v)),
+        search_terms(keys, names, romaji, |v| format!("%{}%", v)),
+        similarity.join(", ")
+    )
+}
+
 #[deprecated(note = "Use build_activation_query instead")]
 pub fn findact_query_builder(is_alert: bool, r: &FindAct) -> String {
     let mut query: String = String::new();
@@ -321,11 +477,16 @@ pub fn findact_query_builder(is_alert: bool, r: &FindAct) -> String {
                 after
             ));
         }
-    } else if let Some(after) = r.issued_after {
-        query.push_str(&format!(
-            "spot_time >= '{}' ORDER BY spot_time DESC ",
-            after
-        ));
+    } else {
+        if let Some(band) = &r.band {
+            query.push_str(&format!("band = '{}' AND ", band.replace('\'', "''")));
+        }
+        if let Some(after) = r.issued_after {
+            query.push_str(&format!(
+                "spot_time >= '{}' ORDER BY spot_time DESC ",
+                after
+            ));
+        }
     }
 
     if let Some(limit) = &r.limit {
@@ -337,3 +498,12 @@ pub fn findact_query_builder(is_alert: bool, r: &FindAct) -> String {
     }
     query
 }
+
+/// 都道府県名を地域名(ローマ字)に変換するSQL式
+fn prefecture_region_sql(column: &str) -> String {
+    let cases: Vec<String> = PREFECTURES
+        .iter()
+        .map(|p| format!("WHEN '{}' THEN '{}'", p.name, p.romaji))
+        .collect();
+    format!("CASE {} {} END", column, cases.join(" "))
+}
@@ -1,15 +1,19 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Component;
 use sqlx::PgConnection;
 
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
+use domain::model::changelog::ReferenceChange;
 use domain::model::event::{DeleteRef, FindRef, PagenatedResult};
-use domain::model::sota::{SotaReference, SummitCode};
+use domain::model::sota::{ActivationZone, SotaReference, SummitCode};
 use domain::model::AwardProgram::SOTA;
 
+use super::changelog;
 use super::querybuilder::findref_query_builder;
 use crate::database::connect::ConnectionPool;
-use crate::database::model::sota::SotaReferenceRow;
+use crate::database::model::sota::{ActivationZoneRow, SotaReferenceRow};
 use domain::repository::sota::SotaRepository;
 
 #[derive(Component)]
@@ -307,6 +311,23 @@ impl SotaRepositoryImpl {
         Ok((total, rows))
     }
 
+    async fn upsert_zone(&self, z: ActivationZoneRow, db: &mut PgConnection) -> AppResult<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO sota_activation_zones (summit_code, geometry)
+                VALUES ($1, $2)
+                ON CONFLICT (summit_code) DO UPDATE
+                SET geometry = EXCLUDED.geometry
+            "#,
+            z.summit_code,
+            z.geometry,
+        )
+        .execute(db)
+        .await
+        .map_err(db_error("upsert sota_activation_zones postgis"))?;
+        Ok(())
+    }
+
     async fn select_by_condition(&self, query: &str) -> AppResult<Vec<SotaReferenceRow>> {
         let mut select = r#"
             SELECT
@@ -347,7 +368,18 @@ impl SotaRepositoryImpl {
 
 #[async_trait]
 impl SotaRepository for SotaRepositoryImpl {
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "record_changes");
+        changelog::record_changes(&self.pool, changes).await
+    }
+
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>> {
+        let _timer = QueryTimer::new("sota", "find_changes");
+        changelog::find_changes(&self.pool, SOTA, since).await
+    }
+
     async fn create_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "create_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -367,7 +399,30 @@ impl SotaRepository for SotaRepositoryImpl {
         Ok(())
     }
 
+    async fn replace_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "replace_reference");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin replace_reference sota postgis"))?;
+
+        self.delete_all(&mut tx).await?;
+        for r in references.into_iter().enumerate() {
+            self.create(SotaReferenceRow::from(r.1), &mut tx).await?;
+            if r.0 % 500 == 0 {
+                tracing::info!("insert sota {} rescords", r.0);
+            }
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit replace_reference sota postgis"))?;
+        Ok(())
+    }
+
     async fn show_reference(&self, event: &FindRef) -> AppResult<SOTAReference> {
+        let _timer = QueryTimer::new("sota", "show_reference");
         let query = findref_query_builder(SOTA, event);
         let result = self.select(&query).await?;
         Ok(result.into())
@@ -377,6 +432,7 @@ impl SotaRepository for SotaRepositoryImpl {
         &self,
         event: &FindRef,
     ) -> AppResult<PagenatedResult<SotaReference>> {
+        let _timer = QueryTimer::new("sota", "show_all_references");
         let limit = event.limit.unwrap_or(10);
         let offset = event.offset.unwrap_or(0);
         let query = findref_query_builder(SOTA, event);
@@ -390,6 +446,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn update_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "update_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -409,6 +466,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn upsert_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "upsert_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -430,6 +488,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn delete_reference(&self, query: DeleteRef<SummitCode>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "delete_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -446,7 +505,45 @@ impl SotaRepository for SotaRepositoryImpl {
         Ok(())
     }
 
+    async fn upsert_activation_zones(&self, zones: Vec<ActivationZone>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "upsert_activation_zones");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin upsert_activation_zones postgis"))?;
+
+        for z in zones {
+            self.upsert_zone(ActivationZoneRow::from(z), &mut tx)
+                .await?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit upsert_activation_zones postgis"))?;
+        Ok(())
+    }
+
+    async fn find_activation_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZone>> {
+        let _timer = QueryTimer::new("sota", "find_activation_zone");
+        let row = sqlx::query_as!(
+            ActivationZoneRow,
+            r#"
+                SELECT summit_code, geometry
+                FROM sota_activation_zones
+                WHERE summit_code = $1
+            "#,
+            summit_code,
+        )
+        .fetch_optional(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch sota_activation_zones postgis"))?;
+
+        row.map(ActivationZone::try_from).transpose()
+    }
+
     async fn find_reference(&self, event: &FindRef) -> AppResult<Vec<SotaReference>> {
+        let _timer = QueryTimer::new("sota", "find_reference");
         let query = findref_query_builder(SOTA, event);
         let results = self.select_by_condition(&query).await?;
         let results = results.into_iter().map(SotaReference::from).collect();
@@ -3,14 +3,19 @@ use shaku::Component;
 use sqlx::SqliteConnection;
 
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
 
-use domain::model::activation::{Alert, Spot};
+use domain::model::activation::{Alert, AlertOutcome, Spot};
+use domain::model::archive::{
+    ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
 use domain::model::event::{DeleteAct, FindAct};
 use domain::repository::activation::ActivationRepositry;
 
+use super::archive;
 use super::querybuilder::findact_query_builder;
 use crate::database::connect::ConnectionPool;
-use crate::database::model::activation::{AlertRow, SpotRow};
+use crate::database::model::activation::{AlertOutcomeRow, AlertRow, SpotRow};
 
 #[derive(Component)]
 #[shaku(interface = ActivationRepositry)]
@@ -67,8 +72,8 @@ impl ActivationRepositryImpl {
         let program = s.program.as_i32();
         sqlx::query!(
             r#"
-                INSERT INTO spots (program, spot_id, reference, reference_detail, activator, activator_name, operator, spot_time, frequency, mode, spotter,comment) 
-                VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                INSERT INTO spots (program, spot_id, reference, reference_detail, activator, activator_name, operator, spot_time, frequency, mode, spotter,comment, frequency_hz, band, canonical_mode, spotters) 
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
                 ON CONFLICT (program, spot_id ) DO UPDATE             
                 SET program = EXCLUDED.program,
                     spot_id = EXCLUDED.spot_id,
@@ -81,7 +86,11 @@ impl ActivationRepositryImpl {
                     frequency = EXCLUDED.frequency,
                     mode = EXCLUDED.mode,
                     spotter = EXCLUDED.spotter,
-                    comment = EXCLUDED.comment
+                    comment = EXCLUDED.comment,
+                    frequency_hz = EXCLUDED.frequency_hz,
+                    band = EXCLUDED.band,
+                    canonical_mode = EXCLUDED.canonical_mode,
+                    spotters = EXCLUDED.spotters
             "#,
             program,
             s.spot_id,
@@ -95,6 +104,10 @@ impl ActivationRepositryImpl {
             s.mode,
             s.spotter,
             s.comment,
+            s.frequency_hz,
+            s.band,
+            s.canonical_mode,
+            s.spotters,
         )
         .execute(db)
         .await
@@ -102,6 +115,42 @@ impl ActivationRepositryImpl {
         Ok(())
     }
 
+    async fn update_outcome_impl(
+        &self,
+        o: AlertOutcomeRow,
+        db: &mut SqliteConnection,
+    ) -> AppResult<()> {
+        let program = o.program.as_i32();
+        let outcome = o.outcome.as_i32();
+        // 確定した結果（Pending以外）は書き換えない（スポットが削除された後の再判定で不在にしない）
+        sqlx::query!(
+            r#"
+                INSERT INTO alert_outcomes (program, alert_id, operator, reference, start_time, outcome, spot_reference, spot_time)
+                VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (program, alert_id) DO UPDATE
+                SET operator = EXCLUDED.operator,
+                    reference = EXCLUDED.reference,
+                    start_time = EXCLUDED.start_time,
+                    outcome = EXCLUDED.outcome,
+                    spot_reference = EXCLUDED.spot_reference,
+                    spot_time = EXCLUDED.spot_time
+                WHERE alert_outcomes.outcome = 0
+            "#,
+            program,
+            o.alert_id,
+            o.operator,
+            o.reference,
+            o.start_time,
+            outcome,
+            o.spot_reference,
+            o.spot_time,
+        )
+        .execute(db)
+        .await
+        .map_err(db_error("insert/update alert outcome"))?;
+        Ok(())
+    }
+
     async fn delete_alerts_impl(&self, d: DeleteAct, db: &mut SqliteConnection) -> AppResult<()> {
         let before = d.before;
         sqlx::query!(
@@ -176,7 +225,11 @@ impl ActivationRepositryImpl {
                 frequency,
                 mode,
                 spotter,
-                comment
+                comment,
+                frequency_hz,
+                band,
+                canonical_mode,
+                spotters
             FROM spots WHERE "#;
 
         let mut builder = findact_query_builder(false, select, query);
@@ -189,11 +242,36 @@ impl ActivationRepositryImpl {
 
         Ok(rows.into_iter().map(Spot::from).collect())
     }
+
+    async fn select_outcomes_by_condition(&self, query: &FindAct) -> AppResult<Vec<AlertOutcome>> {
+        let select = r#"
+            SELECT
+                program,
+                alert_id,
+                operator,
+                reference,
+                start_time,
+                outcome,
+                spot_reference,
+                spot_time
+            FROM alert_outcomes WHERE "#;
+
+        let mut builder = findact_query_builder(true, select, query);
+        let sql_query = builder.build_query_as::<AlertOutcomeRow>();
+
+        let rows: Vec<AlertOutcomeRow> = sql_query
+            .fetch_all(self.pool.inner_ref())
+            .await
+            .map_err(db_error("fetch alert outcomes"))?;
+
+        Ok(rows.into_iter().map(AlertOutcome::from).collect())
+    }
 }
 
 #[async_trait]
 impl ActivationRepositry for ActivationRepositryImpl {
     async fn update_alerts(&self, alerts: Vec<Alert>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_alerts");
         let mut tx = self
             .pool
             .inner_ref()
@@ -211,6 +289,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn update_spots(&self, spots: Vec<Spot>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_spots");
         let mut tx = self
             .pool
             .inner_ref()
@@ -225,6 +304,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
         Ok(())
     }
     async fn delete_alerts(&self, query: DeleteAct) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "delete_alerts");
         let mut tx = self
             .pool
             .inner_ref()
@@ -240,6 +320,7 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn delete_spots(&self, query: DeleteAct) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "delete_spots");
         let mut tx = self
             .pool
             .inner_ref()
@@ -253,12 +334,143 @@ impl ActivationRepositry for ActivationRepositryImpl {
     }
 
     async fn find_alerts(&self, event: &FindAct) -> AppResult<Vec<Alert>> {
+        let _timer = QueryTimer::new("activation", "find_alerts");
         let results = self.select_alerts_by_condition(event).await?;
         Ok(results)
     }
 
     async fn find_spots(&self, event: &FindAct) -> AppResult<Vec<Spot>> {
+        let _timer = QueryTimer::new("activation", "find_spots");
         let results = self.select_spots_by_condition(event).await?;
         Ok(results)
     }
+
+    async fn update_outcomes(&self, outcomes: Vec<AlertOutcome>) -> AppResult<()> {
+        let _timer = QueryTimer::new("activation", "update_outcomes");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin update_outcomes"))?;
+
+        for o in outcomes {
+            self.update_outcome_impl(AlertOutcomeRow::from(o), &mut tx)
+                .await?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit update_outcomes"))?;
+        Ok(())
+    }
+
+    async fn find_outcomes(&self, event: &FindAct) -> AppResult<Vec<AlertOutcome>> {
+        let _timer = QueryTimer::new("activation", "find_outcomes");
+        let results = self.select_outcomes_by_condition(event).await?;
+        Ok(results)
+    }
+
+    async fn archive_alerts(&self, query: DeleteAct) -> AppResult<u64> {
+        let _timer = QueryTimer::new("activation", "archive_alerts");
+        archive::archive_alerts(&self.pool, query.before).await
+    }
+
+    async fn archive_spots(&self, query: DeleteAct) -> AppResult<u64> {
+        let _timer = QueryTimer::new("activation", "archive_spots");
+        archive::archive_spots(&self.pool, query.before).await
+    }
+
+    async fn find_alert_archive(&self, query: &FindArchive) -> AppResult<Vec<AlertArchive>> {
+        let _timer = QueryTimer::new("activation", "find_alert_archive");
+        archive::find_alert_archive(&self.pool, query).await
+    }
+
+    async fn find_spot_archive(&self, query: &FindArchive) -> AppResult<Vec<SpotArchive>> {
+        let _timer = QueryTimer::new("activation", "find_spot_archive");
+        archive::find_spot_archive(&self.pool, query).await
+    }
+
+    async fn reference_year_stats(&self, query: &FindArchive) -> AppResult<Vec<ReferenceYearStat>> {
+        let _timer = QueryTimer::new("activation", "reference_year_stats");
+        archive::reference_year_stats(&self.pool, query).await
+    }
+
+    async fn activator_stats(&self, query: &FindArchive) -> AppResult<Vec<ActivatorStat>> {
+        let _timer = QueryTimer::new("activation", "activator_stats");
+        archive::activator_stats(&self.pool, query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeDelta, TimeZone, Utc};
+    use domain::model::activation::ActivationOutcome;
+    use domain::model::event::FindActBuilder;
+    use domain::model::AwardProgram;
+    use sqlx::migrate::Migrator;
+    use sqlx::sqlite::SqlitePool;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    async fn setup_repo() -> (ActivationRepositryImpl, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        std::fs::File::create(&db_path).expect("Failed to create db file");
+
+        let pool = SqlitePool::connect(&format!("sqlite:{}", db_path.display()))
+            .await
+            .expect("Failed to connect to test db");
+        let migrator = Migrator::new(Path::new("migrations/sqlite"))
+            .await
+            .expect("Failed to load migrations");
+        migrator.run(&pool).await.expect("Failed to run migrations");
+
+        let repo = ActivationRepositryImpl {
+            pool: ConnectionPool::new(pool),
+        };
+        (repo, temp_dir)
+    }
+
+    fn outcome(
+        outcome: ActivationOutcome,
+        spot_time: Option<DateTime<Utc>>,
+        start_time: DateTime<Utc>,
+    ) -> AlertOutcome {
+        AlertOutcome {
+            program: AwardProgram::SOTA,
+            alert_id: 1,
+            operator: "JA1ABC".to_string(),
+            reference: "JA/TK-001".to_string(),
+            start_time,
+            outcome,
+            spot_reference: spot_time.map(|_| "JA/TK-001".to_string()),
+            spot_time,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_finalized_outcome_is_frozen() {
+        let (repo, _temp_dir) = setup_repo().await;
+        let start = Utc.with_ymd_and_hms(2026, 5, 3, 0, 0, 0).unwrap();
+        let spotted = start + TimeDelta::minutes(5);
+
+        repo.update_outcomes(vec![outcome(
+            ActivationOutcome::OnTime,
+            Some(spotted),
+            start,
+        )])
+        .await
+        .unwrap();
+        // スポットが削除された後の再判定では不在になるが、確定済みの結果は変えない
+        repo.update_outcomes(vec![outcome(ActivationOutcome::NoShow, None, start)])
+            .await
+            .unwrap();
+
+        let query = FindActBuilder::default().sota().build();
+        let outcomes = repo.find_outcomes(&query).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].outcome, ActivationOutcome::OnTime);
+        assert_eq!(outcomes[0].spot_time, Some(spotted));
+    }
 }
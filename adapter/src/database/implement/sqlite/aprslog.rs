@@ -1,13 +1,17 @@
 use aprs_message::AprsCallsign;
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use shaku::Component;
 use sqlx::SqliteConnection;
 
 use crate::database::connect::ConnectionPool;
-use crate::database::model::aprslog::AprsLogRow;
+use crate::database::model::aprslog::{AprsLogRow, AprsTrackArchiveRow};
 use common::error::{db_error, tx_error, AppResult};
-use domain::model::{aprslog::AprsLog, event::FindAprs};
+use common::metrics::QueryTimer;
+use domain::model::{
+    aprslog::{AprsLog, AprsSegment, AprsTrackArchive},
+    event::FindAprs,
+};
 use domain::repository::aprs::AprsLogRepository;
 
 #[derive(Component)]
@@ -17,7 +21,12 @@ pub struct AprsLogRepositoryImpl {
 }
 
 impl AprsLogRepositoryImpl {
-    async fn select_by_callsign(&self, callsign: &AprsCallsign) -> AppResult<Vec<AprsLogRow>> {
+    async fn select_by_callsign(
+        &self,
+        callsign: &AprsCallsign,
+        after: Option<NaiveDateTime>,
+        before: Option<NaiveDateTime>,
+    ) -> AppResult<Vec<AprsLogRow>> {
         let result = if let Some(ssid) = &callsign.ssid {
             sqlx::query_as!(
                 AprsLogRow,
@@ -31,12 +40,18 @@ impl AprsLogRepositoryImpl {
                     state,
                     message,
                     longitude,
-                    latitude
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
                 FROM aprs_log WHERE callsign = $1 AND ssid = $2
+                    AND ($3 IS NULL OR time > $3) AND ($4 IS NULL OR time < $4)
                 ORDER BY time DESC
             "#,
                 callsign.callsign,
-                ssid
+                ssid,
+                after,
+                before
             )
             .fetch_all(self.pool.inner_ref())
             .await
@@ -54,11 +69,17 @@ impl AprsLogRepositoryImpl {
                     state,
                     message,
                     longitude,
-                    latitude
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
                 FROM aprs_log WHERE callsign = $1
+                    AND ($2 IS NULL OR time > $2) AND ($3 IS NULL OR time < $3)
                 ORDER BY time DESC
             "#,
-                callsign.callsign
+                callsign.callsign,
+                after,
+                before
             )
             .fetch_all(self.pool.inner_ref())
             .await
@@ -72,6 +93,7 @@ impl AprsLogRepositoryImpl {
         &self,
         region: &String,
         after: &NaiveDateTime,
+        before: Option<NaiveDateTime>,
     ) -> AppResult<Vec<AprsLogRow>> {
         let result = sqlx::query_as!(
             AprsLogRow,
@@ -85,12 +107,17 @@ impl AprsLogRepositoryImpl {
                     state,
                     message,
                     longitude,
-                    latitude
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
                 FROM aprs_log WHERE time > $1 AND destination LIKE $2
+                    AND ($3 IS NULL OR time < $3)
                 ORDER BY time DESC
             "#,
             after,
-            region
+            region,
+            before
         )
         .fetch_all(self.pool.inner_ref())
         .await
@@ -111,8 +138,11 @@ impl AprsLogRepositoryImpl {
                     state,
                     message,
                     longitude,
-                    latitude
-                ) VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    latitude,
+                    altitude,
+                    packet_type,
+                    payload
+                ) VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
             log.time,
             log.callsign,
@@ -123,6 +153,9 @@ impl AprsLogRepositoryImpl {
             log.message,
             log.longitude,
             log.latitude,
+            log.altitude,
+            log.packet_type,
+            log.payload,
         )
         .execute(db)
         .await
@@ -130,6 +163,91 @@ impl AprsLogRepositoryImpl {
         Ok(())
     }
 
+    async fn upsert_archive(&self, row: AprsTrackArchiveRow) -> AppResult<i64> {
+        let result = sqlx::query!(
+            r#"
+                INSERT INTO aprs_track_archive (
+                    callsign,
+                    ssid,
+                    summit,
+                    start_time,
+                    end_time,
+                    arrival_time,
+                    points,
+                    archived_at
+                ) VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT(callsign, ssid, start_time) DO UPDATE SET
+                    summit = EXCLUDED.summit,
+                    end_time = EXCLUDED.end_time,
+                    arrival_time = EXCLUDED.arrival_time,
+                    points = EXCLUDED.points,
+                    archived_at = EXCLUDED.archived_at
+                RETURNING archive_id AS "archive_id!: i64"
+            "#,
+            row.callsign,
+            row.ssid,
+            row.summit,
+            row.start_time,
+            row.end_time,
+            row.arrival_time,
+            row.points,
+            row.archived_at,
+        )
+        .fetch_one(self.pool.inner_ref())
+        .await
+        .map_err(db_error("upsert aprs_track_archive"))?;
+        Ok(result.archive_id)
+    }
+
+    async fn select_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchiveRow>> {
+        let result = sqlx::query_as!(
+            AprsTrackArchiveRow,
+            r#"
+                SELECT
+                    archive_id AS "archive_id!: i64",
+                    callsign,
+                    ssid,
+                    summit,
+                    start_time,
+                    end_time,
+                    arrival_time,
+                    points,
+                    archived_at
+                FROM aprs_track_archive WHERE callsign = $1
+                ORDER BY start_time DESC
+            "#,
+            callsign
+        )
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch aprs_track_archive by callsign"))?;
+        Ok(result)
+    }
+
+    async fn select_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchiveRow>> {
+        let result = sqlx::query_as!(
+            AprsTrackArchiveRow,
+            r#"
+                SELECT
+                    archive_id AS "archive_id!: i64",
+                    callsign,
+                    ssid,
+                    summit,
+                    start_time,
+                    end_time,
+                    arrival_time,
+                    points,
+                    archived_at
+                FROM aprs_track_archive WHERE archive_id = $1
+            "#,
+            archive_id
+        )
+        .fetch_optional(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch aprs_track_archive"))?;
+        Ok(result)
+    }
+
     async fn delete(&self, before: &NaiveDateTime, db: &mut SqliteConnection) -> AppResult<()> {
         sqlx::query!(
             r#"
@@ -147,15 +265,18 @@ impl AprsLogRepositoryImpl {
 #[async_trait]
 impl AprsLogRepository for AprsLogRepositoryImpl {
     async fn find_aprs_log(&self, query: &FindAprs) -> AppResult<Vec<AprsLog>> {
+        let _timer = QueryTimer::new("aprs_log", "find_aprs_log");
+        let before = query.before.map(|t| t.naive_utc());
         let result = if let Some(ref callsign) = query.callsign {
-            self.select_by_callsign(callsign).await?
+            let after = query.after.map(|t| t.naive_utc());
+            self.select_by_callsign(callsign, after, before).await?
         } else {
             let after = query.after.unwrap_or_default();
 
             let mut reference = query.reference.clone().unwrap_or_default();
             reference.push('%');
 
-            self.select_by_reference_time(&reference, &after.naive_utc())
+            self.select_by_reference_time(&reference, &after.naive_utc(), before)
                 .await?
         };
 
@@ -165,6 +286,7 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
     }
 
     async fn insert_aprs_log(&self, aprs_log: AprsLog) -> AppResult<()> {
+        let _timer = QueryTimer::new("aprs_log", "insert_aprs_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -181,6 +303,7 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
     }
 
     async fn delete_aprs_log(&self, before: &NaiveDateTime) -> AppResult<()> {
+        let _timer = QueryTimer::new("aprs_log", "delete_aprs_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -195,4 +318,210 @@ impl AprsLogRepository for AprsLogRepositoryImpl {
 
         Ok(())
     }
+
+    async fn archive_track(&self, segment: AprsSegment) -> AppResult<i64> {
+        let _timer = QueryTimer::new("aprs_track_archive", "archive_track");
+        let row = AprsTrackArchiveRow::from_segment(segment, Utc::now().naive_utc())?;
+        self.upsert_archive(row).await
+    }
+
+    async fn find_track_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchive>> {
+        let _timer = QueryTimer::new("aprs_track_archive", "find_track_archives");
+        self.select_archives(callsign)
+            .await?
+            .into_iter()
+            .map(AprsTrackArchive::try_from)
+            .collect()
+    }
+
+    async fn find_track_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchive>> {
+        let _timer = QueryTimer::new("aprs_track_archive", "find_track_archive");
+        self.select_archive(archive_id)
+            .await?
+            .map(AprsTrackArchive::try_from)
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+    use domain::model::aprs::{AprsPacket, WeatherReport};
+    use domain::model::aprslog::{AprsLogDetail, AprsState};
+    use sqlx::migrate::Migrator;
+    use sqlx::sqlite::SqlitePool;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    async fn setup_repo() -> (AprsLogRepositoryImpl, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test.db");
+        std::fs::File::create(&db_path).expect("Failed to create db file");
+
+        let pool = SqlitePool::connect(&format!("sqlite:{}", db_path.display()))
+            .await
+            .expect("Failed to connect to test db");
+        let migrator = Migrator::new(Path::new("migrations/sqlite"))
+            .await
+            .expect("Failed to load migrations");
+        migrator.run(&pool).await.expect("Failed to run migrations");
+
+        let repo = AprsLogRepositoryImpl {
+            pool: ConnectionPool::new(pool),
+        };
+        (repo, temp_dir)
+    }
+
+    fn make_log(minutes: i64, on_summit: bool) -> AprsLog {
+        let time = Utc
+            .with_ymd_and_hms(2026, 5, 3, 0, 0, 0)
+            .unwrap()
+            .naive_utc()
+            + TimeDelta::minutes(minutes);
+        let state = if on_summit {
+            AprsState::OnSummit {
+                time,
+                distance: 5.0,
+                message: "Welcome".to_string(),
+            }
+        } else {
+            AprsState::Climbing {
+                time,
+                distance: 500.0,
+            }
+        };
+        AprsLog {
+            callsign: AprsCallsign {
+                callsign: "JA1ABC".to_string(),
+                ssid: Some(7),
+            },
+            destination: Some("JA/TK-001".to_string()),
+            state,
+            longitude: 139.0,
+            latitude: 35.0 + minutes as f64 * 0.0001,
+            altitude: None,
+            detail: AprsLogDetail::Position,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_aprs_log_time_window() {
+        let (repo, _temp_dir) = setup_repo().await;
+        for m in [0, 10, 20, 30] {
+            repo.insert_aprs_log(make_log(m, false)).await.unwrap();
+        }
+
+        let base = Utc.with_ymd_and_hms(2026, 5, 3, 0, 0, 0).unwrap();
+        let query = FindAprs {
+            callsign: Some(AprsCallsign {
+                callsign: "JA1ABC".to_string(),
+                ssid: None,
+            }),
+            after: Some(base + TimeDelta::minutes(5)),
+            before: Some(base + TimeDelta::minutes(25)),
+            ..Default::default()
+        };
+        let logs = repo.find_aprs_log(&query).await.unwrap();
+        assert_eq!(logs.len(), 2);
+
+        let query = FindAprs {
+            reference: Some("JA/TK".to_string()),
+            after: Some(base + TimeDelta::minutes(5)),
+            ..Default::default()
+        };
+        assert_eq!(repo.find_aprs_log(&query).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_archive_track_roundtrip() {
+        let (repo, _temp_dir) = setup_repo().await;
+        let logs = vec![make_log(0, false), make_log(10, false), make_log(20, true)];
+        let mut segments = AprsSegment::split(logs, TimeDelta::hours(3));
+        let segment = segments.remove(0);
+
+        let id = repo.archive_track(segment.clone()).await.unwrap();
+        // 同じアクティベーションを再保存しても1件のまま
+        let id2 = repo.archive_track(segment.clone()).await.unwrap();
+        assert_eq!(id, id2);
+
+        let archives = repo.find_track_archives("JA1ABC").await.unwrap();
+        assert_eq!(archives.len(), 1);
+
+        let archive = repo.find_track_archive(id).await.unwrap().unwrap();
+        assert_eq!(archive.segment.summit.as_deref(), Some("JA/TK-001"));
+        assert_eq!(archive.segment.callsign.ssid, Some(7));
+        assert_eq!(archive.segment.arrival, segment.arrival);
+        assert_eq!(archive.segment.points, segment.points);
+
+        assert!(repo.find_track_archive(id + 1).await.unwrap().is_none());
+        assert!(repo.find_track_archives("JA2XYZ").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_log_detail_roundtrip() {
+        let (repo, _temp_dir) = setup_repo().await;
+        let weather = WeatherReport {
+            wind_direction: Some(220),
+            temperature: Some(41),
+            ..Default::default()
+        };
+        let mut log = make_log(0, true);
+        log.detail = AprsLogDetail::Weather(weather.clone());
+        repo.insert_aprs_log(log).await.unwrap();
+        let mut log = make_log(10, true);
+        log.detail = AprsLogDetail::Object("JA/TK-001".to_string());
+        repo.insert_aprs_log(log).await.unwrap();
+
+        let query = FindAprs {
+            callsign: Some(AprsCallsign {
+                callsign: "JA1ABC".to_string(),
+                ssid: Some(7),
+            }),
+            ..Default::default()
+        };
+        let logs = repo.find_aprs_log(&query).await.unwrap();
+        assert_eq!(
+            logs[0].detail,
+            AprsLogDetail::Object("JA/TK-001".to_string())
+        );
+        assert_eq!(logs[1].detail, AprsLogDetail::Weather(weather));
+    }
+
+    #[tokio::test]
+    async fn test_live_position_altitude_roundtrip() {
+        let (repo, _temp_dir) = setup_repo().await;
+        // APRS-ISから受信した行の高度（/A= フィート）がAprsLogまで残る
+        let packet: AprsPacket = "JA1ABC-7>APRS,TCPIP*,qAC,T2TEST:!3540.00N/13945.00E>/A=004921"
+            .parse()
+            .unwrap();
+        let AprsPacket::Position {
+            callsign,
+            latitude,
+            longitude,
+            altitude,
+        } = packet
+        else {
+            panic!("expected position");
+        };
+        let log = AprsLog {
+            callsign,
+            latitude,
+            longitude,
+            altitude,
+            ..make_log(0, false)
+        };
+        repo.insert_aprs_log(log).await.unwrap();
+
+        let query = FindAprs {
+            callsign: Some(AprsCallsign {
+                callsign: "JA1ABC".to_string(),
+                ssid: Some(7),
+            }),
+            ..Default::default()
+        };
+        let logs = repo.find_aprs_log(&query).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert!((logs[0].altitude.unwrap() - 1500.0).abs() < 0.1);
+    }
 }
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqliteConnection;
+
+use common::error::{db_error, tx_error, AppResult};
+use domain::model::changelog::ReferenceChange;
+use domain::model::AwardProgram;
+
+use crate::database::connect::ConnectionPool;
+use crate::database::model::changelog::ReferenceChangeRow;
+
+async fn insert_change(c: ReferenceChangeRow, db: &mut SqliteConnection) -> AppResult<()> {
+    let program = c.program.as_i32();
+    let kind = c.kind.as_i32();
+    sqlx::query!(
+        r#"
+            INSERT INTO reference_changes (program, reference, kind, old_value, new_value, changed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT DO NOTHING
+        "#,
+        program,
+        c.reference,
+        kind,
+        c.old_value,
+        c.new_value,
+        c.changed_at,
+    )
+    .execute(db)
+    .await
+    .map_err(db_error("insert reference_changes"))?;
+    Ok(())
+}
+
+/// 変更履歴を保存する（同じ内容の変更は最初の1件だけ残す）
+pub async fn record_changes(pool: &ConnectionPool, changes: Vec<ReferenceChange>) -> AppResult<()> {
+    let mut tx = pool
+        .inner_ref()
+        .begin()
+        .await
+        .map_err(tx_error("begin record_changes"))?;
+
+    for c in changes {
+        insert_change(ReferenceChangeRow::from(c), &mut tx).await?;
+    }
+    tx.commit()
+        .await
+        .map_err(tx_error("commit record_changes"))?;
+    Ok(())
+}
+
+pub async fn find_changes(
+    pool: &ConnectionPool,
+    program: AwardProgram,
+    since: DateTime<Utc>,
+) -> AppResult<Vec<ReferenceChange>> {
+    let rows = sqlx::query_as::<_, ReferenceChangeRow>(
+        r#"
+            SELECT program, reference, kind, old_value, new_value, changed_at
+            FROM reference_changes
+            WHERE program = $1 AND changed_at >= $2
+            ORDER BY changed_at, change_id
+        "#,
+    )
+    .bind(program.as_i32())
+    .bind(since)
+    .fetch_all(pool.inner_ref())
+    .await
+    .map_err(db_error("fetch reference_changes"))?;
+    Ok(rows.into_iter().map(ReferenceChange::from).collect())
+}
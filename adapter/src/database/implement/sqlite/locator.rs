@@ -108,6 +108,15 @@ impl LocatorRepositry for LocatorRepositryImpl {
         Ok(result)
     }
 
+    async fn find_all_muni_codes(&self) -> AppResult<Vec<i32>> {
+        let codes =
+            sqlx::query_scalar::<_, i32>("SELECT muni_code FROM municipality_century_codes")
+                .fetch_all(self.pool.inner_ref())
+                .await
+                .map_err(db_error("fetch all muni_code"))?;
+        Ok(codes)
+    }
+
     async fn find_mapcode(&self, lon: f64, lat: f64) -> AppResult<String> {
         let client = http::client();
         let response = client
@@ -1,7 +1,12 @@
 pub mod activation;
 pub mod aprslog;
+pub mod archive;
+pub mod changelog;
 pub mod healthcheck;
 pub mod locator;
 pub mod pota_reference;
 pub mod querybuilder;
+pub mod relay;
+pub mod session;
 pub mod sota_reference;
+pub mod watch;
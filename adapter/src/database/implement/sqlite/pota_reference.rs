@@ -1,11 +1,13 @@
 use async_trait::async_trait;
-use chrono::{Days, NaiveDateTime, Utc};
+use chrono::{DateTime, Days, NaiveDateTime, Utc};
 use shaku::Component;
 use sqlx::{query_as, SqliteConnection, SqlitePool};
 use std::time::{Duration, Instant};
 
 use common::config::AppConfig;
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
+use domain::model::changelog::ReferenceChange;
 use domain::model::event::{DeleteLog, DeleteRef, FindRef, FindRefBuilder, PagenatedResult};
 use domain::model::id::{LogId, UserId};
 use domain::model::pota::{
@@ -15,6 +17,7 @@ use domain::model::pota::{
 use domain::model::AwardProgram::POTA;
 use domain::repository::pota::PotaRepository;
 
+use super::changelog;
 use super::querybuilder::findref_query_builder;
 use crate::database::connect::ConnectionPool;
 use crate::database::model::pota::{
@@ -524,7 +527,7 @@ impl PotaRepositoryImpl {
 
     async fn count_by_condition(&self, query: &FindRef) -> AppResult<i64> {
         let select = r#"
-            SELECT COUNT(*) FROM pota_references WHERE "#;
+            SELECT COUNT(*) FROM pota_references AS p WHERE "#;
 
         let mut builder = findref_query_builder(POTA, None, select, query);
         let sql_query = builder.build_query_scalar::<i64>();
@@ -594,11 +597,23 @@ impl PotaRepositoryImpl {
 
 #[async_trait]
 impl PotaRepository for PotaRepositoryImpl {
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "record_changes");
+        changelog::record_changes(&self.pool, changes).await
+    }
+
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>> {
+        let _timer = QueryTimer::new("pota", "find_changes");
+        changelog::find_changes(&self.pool, POTA, since).await
+    }
+
     async fn count_reference(&self, event: &FindRef) -> AppResult<i64> {
+        let _timer = QueryTimer::new("pota", "count_reference");
         Ok(self.count_by_condition(event).await?)
     }
 
     async fn find_reference(&self, event: &FindRef) -> AppResult<Vec<PotaRefLog>> {
+        let _timer = QueryTimer::new("pota", "find_reference");
         let log_id = event.log_id;
         let results = self.select_by_condition(log_id, event).await?;
         let results = results.into_iter().map(PotaRefLog::from).collect();
@@ -606,6 +621,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn create_reference(&self, references: Vec<PotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "create_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -627,6 +643,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn show_reference(&self, event: &FindRef) -> AppResult<PotaReference> {
+        let _timer = QueryTimer::new("pota", "show_reference");
         let result = self.select(event).await?;
         Ok(result.into())
     }
@@ -635,6 +652,7 @@ impl PotaRepository for PotaRepositoryImpl {
         &self,
         event: &FindRef,
     ) -> AppResult<PagenatedResult<PotaReference>> {
+        let _timer = QueryTimer::new("pota", "show_all_references");
         let limit = event.limit.unwrap_or(10);
         let offset = event.offset.unwrap_or(0);
         let (total, results) = self.select_pagenated(event).await?;
@@ -647,6 +665,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn update_reference(&self, references: Vec<PotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "update_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -663,6 +682,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn delete_reference(&self, query: DeleteRef<ParkCode>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "delete_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -680,6 +700,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn upload_activator_log(&self, logs: Vec<PotaActLog>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "upload_activator_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -699,6 +720,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn upload_hunter_log(&self, logs: Vec<PotaHuntLog>) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "upload_hunter_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -718,6 +740,7 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn delete_log(&self, query: DeleteLog) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "delete_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -732,10 +755,12 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn log_statistics(&self) -> AppResult<PotaLogStat> {
+        let _timer = QueryTimer::new("pota", "log_statistics");
         self.log_stat().await
     }
 
     async fn migrate_legacy_log(&self, dbname: String) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "migrate_legacy_log");
         let res = self.migrate_legacy(&dbname).await;
         if res.is_err() {
             tracing::error!("Legacy DB:{} migration failed.", dbname)
@@ -748,11 +773,13 @@ impl PotaRepository for PotaRepositoryImpl {
     }
 
     async fn find_logid(&self, query: LogId) -> AppResult<PotaLogHist> {
+        let _timer = QueryTimer::new("pota", "find_logid");
         let result = self.select_logid(query).await?;
         Ok(result.into())
     }
 
     async fn update_logid(&self, log: PotaLogHist) -> AppResult<()> {
+        let _timer = QueryTimer::new("pota", "update_logid");
         let mut tx = self
             .pool
             .inner_ref()
@@ -1,8 +1,10 @@
 use domain::model::id::LogId;
 use sqlx::query_builder::QueryBuilder;
 
+use common::kana::SearchKeys;
 use common::utils::calculate_bounding_box;
 use domain::model::event::{CenterRadius, FindAct, FindLog, FindRef};
+use domain::model::locator::PREFECTURES;
 use domain::model::AwardProgram::{self, POTA, SOTA, WWFF};
 use sqlx::Sqlite;
 
@@ -13,6 +15,10 @@ pub fn findref_query_builder<'a>(
     r: &'a FindRef,
 ) -> QueryBuilder<'a, Sqlite> {
     let mut builder = QueryBuilder::new(query);
+    let search = match (&r.name, &r.sota_code, &r.pota_code, &r.wwff_code) {
+        (Some(name), None, None, None) => Some(SearchKeys::new(name)).filter(|k| !k.is_empty()),
+        _ => None,
+    };
 
     if let Some(logid) = logid {
         builder.push_bind(logid.raw());
@@ -38,26 +44,11 @@ pub fn findref_query_builder<'a>(
             builder.push(" ) AND ");
         }
     } else {
-        if let Some(name) = &r.name {
-            if r.is_sota() && mode == SOTA {
-                builder.push(" (summit_code LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" OR summit_name LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" OR summit_name_j LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" ) AND ");
-            } else {
-                builder.push(" (p.pota_code LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" OR p.wwff_code LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" OR p.park_name LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" OR p.park_name_j LIKE ");
-                builder.push_bind(format!("%{}%", name));
-                builder.push(" ) AND ");
-            }
+        if let Some(keys) = &search {
+            let (rowid, fts) = fts_table(r, &mode);
+            builder.push(format!(" {} IN (SELECT rowid FROM {} WHERE ", rowid, fts));
+            push_search_terms(&mut builder, keys, &FTS_ALL_COLUMNS, |v| format!("%{}%", v));
+            builder.push(" ) AND ");
         }
 
         if let Some(min_elev) = r.min_elev {
@@ -76,6 +67,55 @@ pub fn findref_query_builder<'a>(
             }
         }
 
+        let sota = r.is_sota() && mode == SOTA;
+
+        if let Some(grid) = &r.grid {
+            builder.push(if sota {
+                " (maidenhead LIKE "
+            } else {
+                " (p.maidenhead LIKE "
+            });
+            builder.push_bind(format!("{}%", grid));
+            builder.push(" ) AND ");
+        }
+
+        for (column, code) in [("jcc_code", &r.jcc_code), ("jcg_code", &r.jcg_code)] {
+            if let Some(code) = code {
+                builder.push(format!(
+                    " EXISTS (SELECT 1 FROM municipality_century_codes AS m WHERE m.{} = ",
+                    column
+                ));
+                builder.push_bind(code.as_str());
+                if sota {
+                    // city_jに都道府県名が無い場合は地域名で都道府県を確認する
+                    builder.push(format!(
+                        " AND (city_j LIKE m.prefecture || '%' || m.municipality || '%' OR (city_j LIKE m.municipality || '%' AND region_name = {}))) AND ",
+                        prefecture_region_sql("m.prefecture")
+                    ));
+                } else {
+                    builder.push(" AND p.park_location LIKE '%' || m.municipality || '%') AND ");
+                }
+            }
+        }
+
+        if let Some(pref) = &r.prefecture {
+            if sota {
+                builder.push(" (city_j LIKE ");
+                builder.push_bind(format!("%{}%", pref.name));
+                builder.push(" OR region_name = ");
+                builder.push_bind(pref.romaji);
+                builder.push(" OR association_name LIKE ");
+                builder.push_bind(format!("%{}%", pref.romaji));
+                builder.push(" ) AND ");
+            } else {
+                builder.push(" (p.park_locid LIKE ");
+                builder.push_bind(format!("%{}%", pref.code));
+                builder.push(" OR p.park_location LIKE ");
+                builder.push_bind(format!("%{}%", pref.name));
+                builder.push(" ) AND ");
+            }
+        }
+
         if let Some(bbox) = &r.bbox {
             builder.push(" (longitude BETWEEN ");
             builder.push_bind(bbox.min_lon);
@@ -101,20 +141,57 @@ pub fn findref_query_builder<'a>(
             builder.push_bind(max_lat);
             builder.push(" ) AND ");
         }
+
+        if let Some(route) = &r.route {
+            builder.push(" (");
+            for (i, bbox) in route.bounding_boxes().into_iter().enumerate() {
+                if i > 0 {
+                    builder.push(" OR ");
+                }
+                builder.push("(longitude BETWEEN ");
+                builder.push_bind(bbox.min_lon);
+                builder.push(" AND ");
+                builder.push_bind(bbox.max_lon);
+                builder.push(" AND latitude BETWEEN ");
+                builder.push_bind(bbox.min_lat);
+                builder.push(" AND ");
+                builder.push_bind(bbox.max_lat);
+                builder.push(")");
+            }
+            builder.push(" ) AND ");
+        }
+    }
+
+    if mode == SOTA {
+        if let Some(date) = r.valid_on() {
+            builder.push(" (valid_from <= ");
+            builder.push_bind(date);
+            builder.push(" AND valid_to >= ");
+            builder.push_bind(date);
+            builder.push(" ) AND ");
+        }
     }
     builder.push(" TRUE ");
 
     if r.is_sota() && mode == SOTA {
+        builder.push(" ORDER BY ");
+        if let Some(keys) = &search {
+            push_search_rank(&mut builder, r, &mode, keys);
+        }
         if r.min_elev.is_some() {
-            builder.push(" ORDER BY alt_m DESC ");
+            builder.push(" alt_m DESC ");
         } else {
-            builder.push(" ORDER BY summit_code ");
+            builder.push(" summit_code ");
         }
     } else if r.is_pota() && (mode == POTA || mode == WWFF) {
+        builder.push(" ORDER BY ");
+        if let Some(keys) = &search {
+            push_search_rank(&mut builder, r, &mode, keys);
+        }
         if r.min_area.is_some() {
-            builder.push(" ORDER BY p.park_area DESC ");
+            builder.push(" p.park_area DESC ");
         } else {
-            builder.push(" ORDER BY p.pota_code ");
+            builder.push(" p.pota_code ");
         }
     }
 
@@ -131,6 +208,61 @@ pub fn findref_query_builder<'a>(
     builder
 }
 
+const FTS_ALL_COLUMNS: [&str; 4] = ["code", "name", "name_j", "location"];
+const FTS_NAME_COLUMNS: [&str; 3] = ["code", "name", "name_j"];
+
+/// 名称検索に使う全文検索テーブルと、それに対応する検索元の行
+fn fts_table(r: &FindRef, mode: &AwardProgram) -> (&'static str, &'static str) {
+    if r.is_sota() && *mode == SOTA {
+        ("sota_references.rowid", "sota_references_fts")
+    } else {
+        ("p.rowid", "pota_references_fts")
+    }
+}
+
+/// 検索キーのいずれかが列に一致する条件（romaji列はローマ字のキーと照合）
+fn push_search_terms(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    keys: &SearchKeys,
+    columns: &[&str],
+    pattern: impl Fn(&str) -> String,
+) {
+    builder.push("(FALSE");
+    for column in columns {
+        for v in &keys.text {
+            builder.push(format!(" OR {} LIKE ", column));
+            builder.push_bind(pattern(v));
+        }
+    }
+    for v in &keys.romaji {
+        builder.push(" OR romaji LIKE ");
+        builder.push_bind(pattern(v));
+    }
+    builder.push(")");
+}
+
+/// 名称検索の関連度（完全一致、前方一致、名称の部分一致、所在地などの一致の順）
+fn push_search_rank(
+    builder: &mut QueryBuilder<'_, Sqlite>,
+    r: &FindRef,
+    mode: &AwardProgram,
+    keys: &SearchKeys,
+) {
+    let (rowid, fts) = fts_table(r, mode);
+    builder.push(" (SELECT CASE WHEN ");
+    push_search_terms(builder, keys, &FTS_NAME_COLUMNS, |v| v.to_string());
+    builder.push(" THEN 0 WHEN ");
+    push_search_terms(builder, keys, &FTS_NAME_COLUMNS, |v| format!("{}%", v));
+    builder.push(" THEN 1 WHEN ");
+    push_search_terms(builder, keys, &FTS_NAME_COLUMNS[1..], |v| {
+        format!("%{}%", v)
+    });
+    builder.push(format!(
+        " THEN 2 ELSE 3 END FROM {} WHERE rowid = {}), ",
+        fts, rowid
+    ));
+}
+
 pub fn findact_query_builder<'a>(
     is_alert: bool,
     query: &str,
@@ -158,6 +290,11 @@ pub fn findact_query_builder<'a>(
         }
         builder.push("TRUE ORDER BY start_time ASC ");
     } else {
+        if let Some(band) = &r.band {
+            builder.push(" band = ");
+            builder.push_bind(band.as_str());
+            builder.push(" AND ");
+        }
         if let Some(after) = r.issued_after {
             builder.push(" spot_time >= ");
             builder.push_bind(after);
@@ -182,12 +319,20 @@ pub fn findact_query_builder<'a>(
 pub fn findlog_query_builder<'a>(query: &str, r: &FindLog) -> QueryBuilder<'a, Sqlite> {
     let mut builder = QueryBuilder::new(query);
 
-    if r.activation {
+    if r.summit_chase {
+        builder.push(" his_summit_code IS NOT NULL AND ");
+    } else if r.activation {
         builder.push(" my_summit_code IS NOT NULL AND ");
     } else {
         builder.push(" my_summit_code IS NULL AND ");
     }
 
+    if let Some(user_id) = &r.user_id {
+        builder.push(" user_id = ");
+        builder.push_bind(user_id.clone().raw());
+        builder.push(" AND ");
+    }
+
     if let Some(after) = r.after {
         builder.push(" time >= ");
         builder.push_bind(after);
@@ -204,3 +349,12 @@ pub fn findlog_query_builder<'a>(query: &str, r: &FindLog) -> QueryBuilder<'a, S
 
     builder
 }
+
+/// 都道府県名を地域名(ローマ字)に変換するSQL式
+fn prefecture_region_sql(column: &str) -> String {
+    let cases: Vec<String> = PREFECTURES
+        .iter()
+        .map(|p| format!("WHEN '{}' THEN '{}'", p.name, p.romaji))
+        .collect();
+    format!("CASE {} {} END", column, cases.join(" "))
+}
@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use shaku::Component;
+
+use common::error::{db_error, AppError, AppResult};
+use domain::model::id::UserId;
+use domain::model::relay::RelayPost;
+use domain::repository::relay::RelayPostRepository;
+
+use crate::database::connect::ConnectionPool;
+use crate::database::model::relay::RelayPostRow;
+
+const POST_COLUMNS: &str = r#"
+    SELECT post_id, user_id, kind, program, reference, activator, local_id,
+           status, upstream_id, error, created_at, relayed_at
+    FROM relay_posts
+"#;
+
+#[derive(Component)]
+#[shaku(interface = RelayPostRepository)]
+pub struct RelayPostRepositoryImpl {
+    pool: ConnectionPool,
+}
+
+#[async_trait]
+impl RelayPostRepository for RelayPostRepositoryImpl {
+    async fn insert_post(&self, post: RelayPost) -> AppResult<i64> {
+        let p = RelayPostRow::from(post);
+        let (post_id,): (i64,) = sqlx::query_as(
+            r#"
+                INSERT INTO relay_posts (user_id, kind, program, reference, activator, local_id,
+                                         status, upstream_id, error, created_at, relayed_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                RETURNING post_id
+            "#,
+        )
+        .bind(p.user_id)
+        .bind(p.kind.as_i32())
+        .bind(p.program.as_i32())
+        .bind(p.reference)
+        .bind(p.activator)
+        .bind(p.local_id)
+        .bind(p.status.as_i32())
+        .bind(p.upstream_id)
+        .bind(p.error)
+        .bind(p.created_at)
+        .bind(p.relayed_at)
+        .fetch_one(self.pool.inner_ref())
+        .await
+        .map_err(db_error("insert relay_posts"))?;
+        Ok(post_id)
+    }
+
+    async fn update_post(&self, post: RelayPost) -> AppResult<()> {
+        let p = RelayPostRow::from(post);
+        let result = sqlx::query(
+            r#"
+                UPDATE relay_posts
+                SET local_id = $2, status = $3, upstream_id = $4, error = $5, relayed_at = $6
+                WHERE post_id = $1
+            "#,
+        )
+        .bind(p.post_id)
+        .bind(p.local_id)
+        .bind(p.status.as_i32())
+        .bind(p.upstream_id)
+        .bind(p.error)
+        .bind(p.relayed_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("update relay_posts"))?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NoRowsAffectedError(format!(
+                "relay_post {}",
+                p.post_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn find_post(&self, post_id: i64) -> AppResult<Option<RelayPost>> {
+        let row =
+            sqlx::query_as::<_, RelayPostRow>(&format!("{} WHERE post_id = $1", POST_COLUMNS))
+                .bind(post_id)
+                .fetch_optional(self.pool.inner_ref())
+                .await
+                .map_err(db_error("fetch relay_posts"))?;
+        Ok(row.map(RelayPost::from))
+    }
+
+    async fn find_posts(&self, user_id: &UserId, limit: i64) -> AppResult<Vec<RelayPost>> {
+        let rows = sqlx::query_as::<_, RelayPostRow>(&format!(
+            "{} WHERE user_id = $1 ORDER BY created_at DESC, post_id DESC LIMIT $2",
+            POST_COLUMNS
+        ))
+        .bind(user_id.clone())
+        .bind(limit)
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch relay_posts"))?;
+        Ok(rows.into_iter().map(RelayPost::from).collect())
+    }
+}
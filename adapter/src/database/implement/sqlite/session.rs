@@ -0,0 +1,219 @@
+use async_trait::async_trait;
+use shaku::Component;
+
+use common::error::{db_error, tx_error, AppError, AppResult};
+use domain::model::id::UserId;
+use domain::model::session::{ActivationSession, SessionFile, SessionQso};
+use domain::repository::session::SessionRepository;
+
+use crate::database::connect::ConnectionPool;
+use crate::database::model::session::{ActivationSessionRow, SessionFileRow, SessionQsoRow};
+
+const SESSION_COLUMNS: &str = r#"
+    SELECT session_id, user_id, program, reference, callsign, operator,
+           status, opened_at, closed_at, qualified_at
+    FROM activation_sessions
+"#;
+
+const QSO_COLUMNS: &str = r#"
+    SELECT session_id, client_id, callsign, band, mode, qso_time, record, created_at
+    FROM session_qsos
+"#;
+
+#[derive(Component)]
+#[shaku(interface = SessionRepository)]
+pub struct SessionRepositoryImpl {
+    pool: ConnectionPool,
+}
+
+#[async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn create_session(&self, session: ActivationSession) -> AppResult<i64> {
+        let s = ActivationSessionRow::from(session);
+        let (session_id,): (i64,) = sqlx::query_as(
+            r#"
+                INSERT INTO activation_sessions (user_id, program, reference, callsign, operator,
+                                                 status, opened_at, closed_at, qualified_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING session_id
+            "#,
+        )
+        .bind(s.user_id)
+        .bind(s.program.as_i32())
+        .bind(s.reference)
+        .bind(s.callsign)
+        .bind(s.operator)
+        .bind(s.status.as_i32())
+        .bind(s.opened_at)
+        .bind(s.closed_at)
+        .bind(s.qualified_at)
+        .fetch_one(self.pool.inner_ref())
+        .await
+        .map_err(db_error("insert activation_sessions"))?;
+        Ok(session_id)
+    }
+
+    async fn update_session(&self, session: ActivationSession) -> AppResult<()> {
+        let s = ActivationSessionRow::from(session);
+        let result = sqlx::query(
+            r#"
+                UPDATE activation_sessions
+                SET status = $2, closed_at = $3, qualified_at = $4
+                WHERE session_id = $1
+            "#,
+        )
+        .bind(s.session_id)
+        .bind(s.status.as_i32())
+        .bind(s.closed_at)
+        .bind(s.qualified_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("update activation_sessions"))?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NoRowsAffectedError(format!(
+                "activation_session {}",
+                s.session_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn delete_session(&self, session_id: i64) -> AppResult<()> {
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin delete_session"))?;
+        for table in ["session_files", "session_qsos", "activation_sessions"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE session_id = $1", table))
+                .bind(session_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(db_error("delete activation_sessions"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit delete_session"))?;
+        Ok(())
+    }
+
+    async fn find_session(&self, session_id: i64) -> AppResult<Option<ActivationSession>> {
+        let row = sqlx::query_as::<_, ActivationSessionRow>(&format!(
+            "{} WHERE session_id = $1",
+            SESSION_COLUMNS
+        ))
+        .bind(session_id)
+        .fetch_optional(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch activation_sessions"))?;
+        Ok(row.map(ActivationSession::from))
+    }
+
+    async fn find_sessions(&self, user_id: &UserId) -> AppResult<Vec<ActivationSession>> {
+        let rows = sqlx::query_as::<_, ActivationSessionRow>(&format!(
+            "{} WHERE user_id = $1 ORDER BY opened_at DESC, session_id DESC",
+            SESSION_COLUMNS
+        ))
+        .bind(user_id.clone())
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch activation_sessions"))?;
+        Ok(rows.into_iter().map(ActivationSession::from).collect())
+    }
+
+    async fn insert_qso(&self, qso: SessionQso) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+                INSERT INTO session_qsos (session_id, client_id, callsign, band, mode,
+                                          qso_time, record, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (session_id, client_id) DO NOTHING
+            "#,
+        )
+        .bind(qso.session_id)
+        .bind(qso.client_id)
+        .bind(qso.callsign)
+        .bind(qso.band)
+        .bind(qso.mode)
+        .bind(qso.qso_time)
+        .bind(qso.record)
+        .bind(qso.created_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("insert session_qsos"))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_qsos(&self, session_id: i64) -> AppResult<Vec<SessionQso>> {
+        let rows = sqlx::query_as::<_, SessionQsoRow>(&format!(
+            "{} WHERE session_id = $1 ORDER BY qso_time, created_at",
+            QSO_COLUMNS
+        ))
+        .bind(session_id)
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch session_qsos"))?;
+        Ok(rows.into_iter().map(SessionQso::from).collect())
+    }
+
+    async fn replace_files(&self, session_id: i64, files: Vec<SessionFile>) -> AppResult<()> {
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin replace_files"))?;
+        sqlx::query("DELETE FROM session_files WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error("delete session_files"))?;
+        for f in files {
+            sqlx::query(
+                r#"
+                    INSERT INTO session_files (session_id, filename, content, created_at)
+                    VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(session_id)
+            .bind(f.filename)
+            .bind(f.content)
+            .bind(f.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error("insert session_files"))?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit replace_files"))?;
+        Ok(())
+    }
+
+    async fn find_file_names(&self, session_id: i64) -> AppResult<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT filename FROM session_files WHERE session_id = $1 ORDER BY filename",
+        )
+        .bind(session_id)
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch session_files"))?;
+        Ok(rows.into_iter().map(|(f,)| f).collect())
+    }
+
+    async fn find_file(&self, session_id: i64, filename: &str) -> AppResult<Option<SessionFile>> {
+        let row = sqlx::query_as::<_, SessionFileRow>(
+            r#"
+                SELECT session_id, filename, content, created_at
+                FROM session_files
+                WHERE session_id = $1 AND filename = $2
+            "#,
+        )
+        .bind(session_id)
+        .bind(filename)
+        .fetch_optional(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch session_files"))?;
+        Ok(row.map(SessionFile::from))
+    }
+}
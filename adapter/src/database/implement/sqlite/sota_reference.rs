@@ -1,18 +1,22 @@
 use std::cmp::Ordering;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use common::utils::calculate_distance;
 use shaku::Component;
 use sqlx::SqliteConnection;
 
 use common::error::{db_error, row_not_found, tx_error, AppResult};
+use common::metrics::QueryTimer;
+use domain::model::changelog::ReferenceChange;
 use domain::model::event::{DeleteLog, DeleteRef, FindLog, FindRef, PagenatedResult};
-use domain::model::sota::{SotaLog, SotaReference, SummitCode};
+use domain::model::sota::{ActivationZone, SotaLog, SotaReference, SummitCode};
 use domain::model::AwardProgram::SOTA;
 
+use super::changelog;
 use super::querybuilder::{findlog_query_builder, findref_query_builder};
 use crate::database::connect::ConnectionPool;
-use crate::database::model::sota::{SotaLogRow, SotaReferenceRow};
+use crate::database::model::sota::{ActivationZoneRow, SotaLogRow, SotaReferenceRow};
 
 use domain::repository::sota::SotaRepository;
 
@@ -247,6 +251,39 @@ impl SotaRepositoryImpl {
         Ok(())
     }
 
+    async fn upsert_zone(&self, z: ActivationZoneRow, db: &mut SqliteConnection) -> AppResult<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO sota_activation_zones (summit_code, geometry)
+                VALUES ($1, $2)
+                ON CONFLICT (summit_code) DO UPDATE
+                SET geometry = EXCLUDED.geometry
+            "#,
+            z.summit_code,
+            z.geometry,
+        )
+        .execute(db)
+        .await
+        .map_err(db_error("upsert sota_activation_zones"))?;
+        Ok(())
+    }
+
+    async fn select_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZoneRow>> {
+        let row = sqlx::query_as!(
+            ActivationZoneRow,
+            r#"
+                SELECT summit_code, geometry
+                FROM sota_activation_zones
+                WHERE summit_code = $1
+            "#,
+            summit_code,
+        )
+        .fetch_optional(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch sota_activation_zones"))?;
+        Ok(row)
+    }
+
     async fn delete(&self, ref_id: SummitCode, db: &mut SqliteConnection) -> AppResult<()> {
         let ref_id = ref_id.inner_ref();
         sqlx::query!(
@@ -447,7 +484,18 @@ impl SotaRepositoryImpl {
 
 #[async_trait]
 impl SotaRepository for SotaRepositoryImpl {
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "record_changes");
+        changelog::record_changes(&self.pool, changes).await
+    }
+
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>> {
+        let _timer = QueryTimer::new("sota", "find_changes");
+        changelog::find_changes(&self.pool, SOTA, since).await
+    }
+
     async fn create_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "create_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -468,7 +516,31 @@ impl SotaRepository for SotaRepositoryImpl {
         Ok(())
     }
 
+    async fn replace_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "replace_reference");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin replace_reference sota"))?;
+
+        self.delete_all(&mut tx).await?;
+        let len = references.len();
+        for r in references.into_iter().enumerate() {
+            self.create(SotaReferenceRow::from(r.1), &mut tx).await?;
+            if r.0 % 10000 == 0 {
+                tracing::info!("insert sota with {}/{} rescords", r.0, len);
+            }
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit replace_reference sota"))?;
+        Ok(())
+    }
+
     async fn show_reference(&self, event: &FindRef) -> AppResult<SotaReference> {
+        let _timer = QueryTimer::new("sota", "show_reference");
         let result = self.select(event).await?;
         Ok(result.into())
     }
@@ -477,6 +549,7 @@ impl SotaRepository for SotaRepositoryImpl {
         &self,
         event: &FindRef,
     ) -> AppResult<PagenatedResult<SotaReference>> {
+        let _timer = QueryTimer::new("sota", "show_all_references");
         let limit = event.limit.unwrap_or(10);
         let offset = event.offset.unwrap_or(0);
         let (total, results) = self.select_pagenated(event).await?;
@@ -489,6 +562,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn update_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "update_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -508,6 +582,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn upsert_reference(&self, references: Vec<SotaReference>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "upsert_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -526,6 +601,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn delete_reference(&self, query: DeleteRef<SummitCode>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "delete_reference");
         let mut tx = self
             .pool
             .inner_ref()
@@ -542,11 +618,40 @@ impl SotaRepository for SotaRepositoryImpl {
         Ok(())
     }
 
+    async fn upsert_activation_zones(&self, zones: Vec<ActivationZone>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "upsert_activation_zones");
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin upsert_activation_zones"))?;
+
+        for z in zones {
+            self.upsert_zone(ActivationZoneRow::from(z), &mut tx)
+                .await?;
+        }
+        tx.commit()
+            .await
+            .map_err(tx_error("commit upsert_activation_zones"))?;
+        Ok(())
+    }
+
+    async fn find_activation_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZone>> {
+        let _timer = QueryTimer::new("sota", "find_activation_zone");
+        self.select_zone(summit_code)
+            .await?
+            .map(ActivationZone::try_from)
+            .transpose()
+    }
+
     async fn count_reference(&self, event: &FindRef) -> AppResult<i64> {
+        let _timer = QueryTimer::new("sota", "count_reference");
         Ok(self.count_by_condition(event).await?)
     }
 
     async fn find_reference(&self, event: &FindRef) -> AppResult<Vec<SotaReference>> {
+        let _timer = QueryTimer::new("sota", "find_reference");
         let mut results = self.select_by_condition(event).await?;
 
         if let Some(center) = &event.center {
@@ -574,6 +679,7 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn upload_log(&self, logs: Vec<SotaLog>) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "upload_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -594,11 +700,13 @@ impl SotaRepository for SotaRepositoryImpl {
     }
 
     async fn find_log(&self, query: &FindLog) -> AppResult<Vec<SotaLog>> {
+        let _timer = QueryTimer::new("sota", "find_log");
         let results = self.select_log_by_condition(query).await?;
         Ok(results.into_iter().map(SotaLog::from).collect())
     }
 
     async fn delete_log(&self, query: DeleteLog) -> AppResult<()> {
+        let _timer = QueryTimer::new("sota", "delete_log");
         let mut tx = self
             .pool
             .inner_ref()
@@ -620,6 +728,8 @@ mod tests {
     use super::*;
     use chrono::NaiveDate;
     use domain::model::event::FindRefBuilder;
+    use domain::model::locator::Prefecture;
+    use domain::model::route::RouteCorridor;
     use sqlx::migrate::Migrator;
     use sqlx::sqlite::SqlitePool;
     use std::path::Path;
@@ -724,6 +834,242 @@ mod tests {
         assert_eq!(count, 3);
     }
 
+    #[tokio::test]
+    async fn test_search_reference_japanese() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let summit = |code: &str, name: &str, name_j: &str, city_j: &str| {
+            let mut r = make_test_reference(code, name);
+            r.summit_name_j = Some(name_j.to_string());
+            r.city_j = Some(city_j.to_string());
+            r
+        };
+        repo.create_reference(vec![
+            summit("JA/SO-001", "Fuji-san", "富士山", "静岡県富士宮市"),
+            summit("JA/YN-002", "Fuji", "富士", "山梨県"),
+            summit("JA/KN-003", "Komafuji", "駒富士", "神奈川県"),
+            summit("JA/TK-004", "Takao-san", "高尾山", "八王子市"),
+        ])
+        .await
+        .expect("Failed to create references");
+
+        let search = |name: &str| {
+            let query = FindRefBuilder::default()
+                .sota()
+                .name(name.to_string())
+                .build();
+            let repo = &repo;
+            async move {
+                repo.find_reference(&query)
+                    .await
+                    .expect("Failed to find")
+                    .into_iter()
+                    .map(|r| r.summit_code)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // かなはローマ字読みで照合し、完全一致・前方一致・部分一致の順に並ぶ
+        assert_eq!(
+            search("ふじ").await,
+            vec!["JA/YN-002", "JA/SO-001", "JA/KN-003"]
+        );
+        assert_eq!(search("ﾀｶｵｻﾝ").await, vec!["JA/TK-004"]);
+        assert_eq!(search("fujisan").await, vec!["JA/SO-001"]);
+        assert_eq!(
+            search("富士").await,
+            vec!["JA/YN-002", "JA/SO-001", "JA/KN-003"]
+        );
+        // 所在地も検索対象
+        assert_eq!(search("八王子").await, vec!["JA/TK-004"]);
+        assert_eq!(search("JA/TK").await, vec!["JA/TK-004"]);
+
+        // 名称の変更が検索インデックスに反映される
+        let mut renamed = summit("JA/TK-004", "Jinba-san", "陣馬山", "八王子市");
+        renamed.points = 2;
+        repo.update_reference(vec![renamed]).await.unwrap();
+        assert!(search("たかお").await.is_empty());
+        assert_eq!(search("じんば").await, vec!["JA/TK-004"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_reference_by_grid_jcc_prefecture() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO municipality_century_codes(muni_code, prefecture, municipality, jcc_code) VALUES (13201, '東京都', '八王子市', '1004')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let summit = |code: &str, grid: &str, region: &str, city_j: &str| {
+            let mut r = make_test_reference(code, "Mt. Test");
+            r.maidenhead = grid.to_string();
+            r.region_name = region.to_string();
+            r.city_j = Some(city_j.to_string());
+            r
+        };
+        repo.create_reference(vec![
+            summit("JA/TK-004", "PM95wp", "Tokyo", "東京都八王子市"),
+            summit("JA/KN-003", "PM95qk", "Kanagawa", "神奈川県相模原市"),
+            summit("JA/NI-001", "PM97aa", "Niigata", "新潟県魚沼市"),
+        ])
+        .await
+        .expect("Failed to create references");
+
+        let find = |query: FindRef| {
+            let repo = &repo;
+            async move {
+                repo.find_reference(&query)
+                    .await
+                    .expect("Failed to find")
+                    .into_iter()
+                    .map(|r| r.summit_code)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let query = FindRefBuilder::default().sota().grid("PM95".to_string());
+        assert_eq!(find(query.build()).await, vec!["JA/KN-003", "JA/TK-004"]);
+        let query = FindRefBuilder::default().sota().grid("pm95wp".to_string());
+        assert_eq!(find(query.build()).await, vec!["JA/TK-004"]);
+
+        let query = FindRefBuilder::default()
+            .sota()
+            .jcc_code("1004".to_string());
+        assert_eq!(find(query.build()).await, vec!["JA/TK-004"]);
+        let query = FindRefBuilder::default()
+            .sota()
+            .jcc_code("9999".to_string());
+        assert!(find(query.build()).await.is_empty());
+
+        let kanagawa = Prefecture::resolve("JP-14").unwrap();
+        let query = FindRefBuilder::default().sota().prefecture(kanagawa);
+        assert_eq!(find(query.build()).await, vec!["JA/KN-003"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_reference_by_jcc_same_municipality_name() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        sqlx::query(
+            "INSERT INTO municipality_century_codes(muni_code, prefecture, municipality, jcc_code) VALUES (13206, '東京都', '府中市', '1009'), (34208, '広島県', '府中市', '3508')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let summit = |code: &str, region: &str, city_j: &str| {
+            let mut r = make_test_reference(code, "Mt. Test");
+            r.region_name = region.to_string();
+            r.city_j = Some(city_j.to_string());
+            r
+        };
+        repo.create_reference(vec![
+            summit("JA/TK-010", "Tokyo", "府中市"),
+            summit("JA/HS-020", "Hiroshima", "広島県府中市"),
+            summit("JA/HS-021", "Hiroshima", "府中市"),
+        ])
+        .await
+        .expect("Failed to create references");
+
+        let find = |jcc: &str| {
+            let repo = &repo;
+            let query = FindRefBuilder::default().sota().jcc_code(jcc.to_string());
+            async move {
+                let mut codes = repo
+                    .find_reference(&query.build())
+                    .await
+                    .expect("Failed to find")
+                    .into_iter()
+                    .map(|r| r.summit_code)
+                    .collect::<Vec<_>>();
+                codes.sort();
+                codes
+            }
+        };
+
+        // 同名の市町村でも他の都道府県のJCCには一致しない
+        assert_eq!(find("1009").await, vec!["JA/TK-010"]);
+        assert_eq!(find("3508").await, vec!["JA/HS-020", "JA/HS-021"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_reference_along_route() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let summit = |code: &str, lon: f64, lat: f64| {
+            let mut r = make_test_reference(code, "Mt. Test");
+            r.longitude = lon;
+            r.latitude = lat;
+            r
+        };
+        repo.create_reference(vec![
+            summit("JA/TK-001", 139.1, 35.005),
+            summit("JA/TK-002", 139.1, 35.05),
+            summit("JA/TK-003", 139.3, 35.0),
+        ])
+        .await
+        .expect("Failed to create references");
+
+        let route = RouteCorridor::new(vec![(139.0, 35.0), (139.2, 35.0)], 1000.0).unwrap();
+        let query = FindRefBuilder::default().sota().route(route).build();
+        let result = repo.find_reference(&query).await.expect("Failed to find");
+        let codes: Vec<_> = result.iter().map(|r| r.summit_code.as_str()).collect();
+        assert_eq!(codes, vec!["JA/TK-001"]);
+    }
+
+    #[tokio::test]
+    async fn test_find_retired_reference_as_of() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let mut retired = make_test_reference("JA/TK-002", "Mt. Retired");
+        retired.valid_to = NaiveDate::from_ymd_opt(2022, 3, 31).unwrap();
+        repo.create_reference(vec![make_test_reference("JA/TK-001", "Mt. Test"), retired])
+            .await
+            .expect("Failed to create references");
+
+        // 既定では今日有効なサミットのみ
+        let query = FindRefBuilder::default().sota().build();
+        let result = repo.find_reference(&query).await.expect("Failed to find");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].summit_code, "JA/TK-001");
+
+        // 有効期間内の日付を指定すると期限切れのサミットも見つかる
+        let query = FindRefBuilder::default()
+            .sota()
+            .as_of(NaiveDate::from_ymd_opt(2021, 6, 1).unwrap())
+            .build();
+        let result = repo.find_reference(&query).await.expect("Failed to find");
+        assert_eq!(result.len(), 2);
+
+        let query = FindRefBuilder::default().sota().include_retired().build();
+        assert_eq!(repo.count_reference(&query).await.unwrap(), 2);
+
+        // コード指定は有効期間で絞り込まない
+        let query = FindRefBuilder::default()
+            .sota()
+            .sota_code("JA/TK-002".to_string())
+            .build();
+        let result = repo.find_reference(&query).await.expect("Failed to find");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].summit_name, "Mt. Retired");
+    }
+
     #[tokio::test]
     async fn test_update_reference() {
         let (pool, _temp_dir) = setup_test_db().await;
@@ -865,4 +1211,93 @@ mod tests {
         assert_eq!(result.offset, 0);
         assert_eq!(result.results.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_upsert_and_find_activation_zone() {
+        let (pool, _temp_dir) = setup_test_db().await;
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let square = |size: f64| ActivationZone {
+            summit_code: "JA/TK-001".to_string(),
+            polygons: vec![vec![
+                (139.0, 35.0),
+                (139.0 + size, 35.0),
+                (139.0 + size, 35.0 + size),
+                (139.0, 35.0 + size),
+            ]],
+        };
+
+        repo.upsert_activation_zones(vec![square(0.01)])
+            .await
+            .expect("Failed to upsert zone");
+        repo.upsert_activation_zones(vec![square(0.02)])
+            .await
+            .expect("Failed to upsert zone again");
+
+        let zone = repo
+            .find_activation_zone("JA/TK-001")
+            .await
+            .expect("Failed to find zone")
+            .expect("zone not found");
+        assert_eq!(zone, square(0.02));
+
+        let none = repo
+            .find_activation_zone("JA/TK-002")
+            .await
+            .expect("Failed to find zone");
+        assert!(none.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_record_and_find_changes() {
+        use chrono::{TimeZone, Utc};
+        use domain::model::changelog::{ReferenceChange, ReferenceChangeKind};
+        use domain::model::AwardProgram;
+
+        let (pool, _temp_dir) = setup_test_db().await;
+        let repo = SotaRepositoryImpl {
+            pool: crate::database::connect::ConnectionPool::new(pool),
+        };
+
+        let change = |kind, old: Option<&str>, new: Option<&str>, day| ReferenceChange {
+            program: AwardProgram::SOTA,
+            reference: "JA/TK-001".to_string(),
+            kind,
+            old_value: old.map(str::to_string),
+            new_value: new.map(str::to_string),
+            changed_at: Utc.with_ymd_and_hms(2026, 10, day, 0, 0, 0).unwrap(),
+        };
+
+        repo.record_changes(vec![
+            change(ReferenceChangeKind::Added, None, Some("Mt. Test"), 1),
+            change(ReferenceChangeKind::PointsChanged, Some("4"), Some("6"), 2),
+        ])
+        .await
+        .expect("Failed to record changes");
+        // 次回の取り込みで同じ変更が検出されても重複させない
+        repo.record_changes(vec![change(
+            ReferenceChangeKind::PointsChanged,
+            Some("4"),
+            Some("6"),
+            3,
+        )])
+        .await
+        .expect("Failed to record changes again");
+
+        let all = repo
+            .find_changes(Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap())
+            .await
+            .expect("Failed to find changes");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].kind, ReferenceChangeKind::PointsChanged);
+        assert_eq!(all[1].changed_at.date_naive().to_string(), "2026-10-02");
+
+        let recent = repo
+            .find_changes(Utc.with_ymd_and_hms(2026, 10, 2, 0, 0, 0).unwrap())
+            .await
+            .expect("Failed to find changes");
+        assert_eq!(recent.len(), 1);
+    }
 }
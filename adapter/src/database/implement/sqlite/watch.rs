@@ -0,0 +1,237 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Component;
+
+use common::error::{db_error, tx_error, AppError, AppResult};
+use domain::model::id::UserId;
+use domain::model::watch::{DeliveryStatus, Watchlist, WebhookDelivery};
+use domain::repository::watch::WatchRepository;
+
+use crate::database::connect::ConnectionPool;
+use crate::database::model::watch::{WatchlistRow, WebhookDeliveryRow};
+
+const WATCH_COLUMNS: &str = r#"
+    SELECT watch_id, user_id, name, callsigns, patterns, programs, bands,
+           webhook_url, secret, enabled, created_at, updated_at
+    FROM watchlists
+"#;
+
+const DELIVERY_COLUMNS: &str = r#"
+    SELECT delivery_id, watch_id, event_key, event, payload, status, attempts,
+           status_code, error, created_at, next_attempt_at, delivered_at
+    FROM webhook_deliveries
+"#;
+
+#[derive(Component)]
+#[shaku(interface = WatchRepository)]
+pub struct WatchRepositoryImpl {
+    pool: ConnectionPool,
+}
+
+#[async_trait]
+impl WatchRepository for WatchRepositoryImpl {
+    async fn create_watch(&self, watch: Watchlist) -> AppResult<i64> {
+        let w = WatchlistRow::from(watch);
+        let (watch_id,): (i64,) = sqlx::query_as(
+            r#"
+                INSERT INTO watchlists (user_id, name, callsigns, patterns, programs, bands,
+                                        webhook_url, secret, enabled, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                RETURNING watch_id
+            "#,
+        )
+        .bind(w.user_id)
+        .bind(w.name)
+        .bind(w.callsigns)
+        .bind(w.patterns)
+        .bind(w.programs)
+        .bind(w.bands)
+        .bind(w.webhook_url)
+        .bind(w.secret)
+        .bind(w.enabled)
+        .bind(w.created_at)
+        .bind(w.updated_at)
+        .fetch_one(self.pool.inner_ref())
+        .await
+        .map_err(db_error("insert watchlists"))?;
+        Ok(watch_id)
+    }
+
+    async fn update_watch(&self, watch: Watchlist) -> AppResult<()> {
+        let w = WatchlistRow::from(watch);
+        let result = sqlx::query(
+            r#"
+                UPDATE watchlists
+                SET name = $2, callsigns = $3, patterns = $4, programs = $5, bands = $6,
+                    webhook_url = $7, secret = $8, enabled = $9, updated_at = $10
+                WHERE watch_id = $1
+            "#,
+        )
+        .bind(w.watch_id)
+        .bind(w.name)
+        .bind(w.callsigns)
+        .bind(w.patterns)
+        .bind(w.programs)
+        .bind(w.bands)
+        .bind(w.webhook_url)
+        .bind(w.secret)
+        .bind(w.enabled)
+        .bind(w.updated_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("update watchlists"))?;
+        if result.rows_affected() == 0 {
+            return Err(AppError::NoRowsAffectedError(format!(
+                "watchlist {}",
+                w.watch_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn delete_watch(&self, watch_id: i64) -> AppResult<()> {
+        let mut tx = self
+            .pool
+            .inner_ref()
+            .begin()
+            .await
+            .map_err(tx_error("begin delete_watch"))?;
+        sqlx::query("DELETE FROM webhook_deliveries WHERE watch_id = $1")
+            .bind(watch_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error("delete webhook_deliveries"))?;
+        sqlx::query("DELETE FROM watchlists WHERE watch_id = $1")
+            .bind(watch_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error("delete watchlists"))?;
+        tx.commit().await.map_err(tx_error("commit delete_watch"))?;
+        Ok(())
+    }
+
+    async fn find_watch(&self, watch_id: i64) -> AppResult<Option<Watchlist>> {
+        let row =
+            sqlx::query_as::<_, WatchlistRow>(&format!("{} WHERE watch_id = $1", WATCH_COLUMNS))
+                .bind(watch_id)
+                .fetch_optional(self.pool.inner_ref())
+                .await
+                .map_err(db_error("fetch watchlists"))?;
+        Ok(row.map(Watchlist::from))
+    }
+
+    async fn find_watches(&self, user_id: &UserId) -> AppResult<Vec<Watchlist>> {
+        let rows = sqlx::query_as::<_, WatchlistRow>(&format!(
+            "{} WHERE user_id = $1 ORDER BY watch_id",
+            WATCH_COLUMNS
+        ))
+        .bind(user_id.clone())
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch watchlists"))?;
+        Ok(rows.into_iter().map(Watchlist::from).collect())
+    }
+
+    async fn find_enabled_watches(&self) -> AppResult<Vec<Watchlist>> {
+        let rows = sqlx::query_as::<_, WatchlistRow>(&format!(
+            "{} WHERE enabled = TRUE ORDER BY watch_id",
+            WATCH_COLUMNS
+        ))
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch enabled watchlists"))?;
+        Ok(rows.into_iter().map(Watchlist::from).collect())
+    }
+
+    async fn insert_delivery(&self, delivery: WebhookDelivery) -> AppResult<bool> {
+        let d = WebhookDeliveryRow::from(delivery);
+        let result = sqlx::query(
+            r#"
+                INSERT INTO webhook_deliveries (watch_id, event_key, event, payload, status, attempts,
+                                                status_code, error, created_at, next_attempt_at, delivered_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (watch_id, event_key) DO NOTHING
+            "#,
+        )
+        .bind(d.watch_id)
+        .bind(d.event_key)
+        .bind(d.event)
+        .bind(d.payload)
+        .bind(d.status.as_i32())
+        .bind(d.attempts)
+        .bind(d.status_code)
+        .bind(d.error)
+        .bind(d.created_at)
+        .bind(d.next_attempt_at)
+        .bind(d.delivered_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("insert webhook_deliveries"))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(&format!(
+            "{} WHERE status = $1 AND next_attempt_at <= $2 ORDER BY next_attempt_at LIMIT $3",
+            DELIVERY_COLUMNS
+        ))
+        .bind(DeliveryStatus::Pending.as_i32())
+        .bind(now)
+        .bind(limit)
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch due webhook_deliveries"))?;
+        Ok(rows.into_iter().map(WebhookDelivery::from).collect())
+    }
+
+    async fn update_delivery(&self, delivery: WebhookDelivery) -> AppResult<()> {
+        let d = WebhookDeliveryRow::from(delivery);
+        sqlx::query(
+            r#"
+                UPDATE webhook_deliveries
+                SET status = $2, attempts = $3, status_code = $4, error = $5,
+                    next_attempt_at = $6, delivered_at = $7
+                WHERE delivery_id = $1
+            "#,
+        )
+        .bind(d.delivery_id)
+        .bind(d.status.as_i32())
+        .bind(d.attempts)
+        .bind(d.status_code)
+        .bind(d.error)
+        .bind(d.next_attempt_at)
+        .bind(d.delivered_at)
+        .execute(self.pool.inner_ref())
+        .await
+        .map_err(db_error("update webhook_deliveries"))?;
+        Ok(())
+    }
+
+    async fn find_deliveries(&self, watch_id: i64, limit: i64) -> AppResult<Vec<WebhookDelivery>> {
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(&format!(
+            "{} WHERE watch_id = $1 ORDER BY created_at DESC, delivery_id DESC LIMIT $2",
+            DELIVERY_COLUMNS
+        ))
+        .bind(watch_id)
+        .bind(limit)
+        .fetch_all(self.pool.inner_ref())
+        .await
+        .map_err(db_error("fetch webhook_deliveries"))?;
+        Ok(rows.into_iter().map(WebhookDelivery::from).collect())
+    }
+
+    async fn delete_deliveries(&self, before: DateTime<Utc>) -> AppResult<u64> {
+        let result =
+            sqlx::query("DELETE FROM webhook_deliveries WHERE status <> $1 AND created_at < $2")
+                .bind(DeliveryStatus::Pending.as_i32())
+                .bind(before)
+                .execute(self.pool.inner_ref())
+                .await
+                .map_err(db_error("delete webhook_deliveries"))?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use domain::model::activation::{Alert, Spot};
+use domain::model::activation::{ActivationOutcome, Alert, AlertOutcome, Spot};
 use domain::model::AwardProgram;
 use sqlx::FromRow;
 
@@ -109,6 +109,11 @@ pub struct SpotRow {
     pub mode: String,
     pub spotter: String,
     pub comment: Option<String>,
+    pub frequency_hz: Option<i64>,
+    pub band: Option<String>,
+    pub canonical_mode: Option<String>,
+    /// カンマ区切り
+    pub spotters: Option<String>,
 }
 
 impl From<Spot> for SpotRow {
@@ -126,6 +131,10 @@ impl From<Spot> for SpotRow {
             mode,
             spotter,
             comment,
+            frequency_hz,
+            band,
+            canonical_mode,
+            spotters,
         } = value;
         Self {
             program,
@@ -140,6 +149,10 @@ impl From<Spot> for SpotRow {
             mode,
             spotter,
             comment,
+            frequency_hz,
+            band,
+            canonical_mode,
+            spotters: (!spotters.is_empty()).then(|| spotters.join(",")),
         }
     }
 }
@@ -159,7 +172,15 @@ impl From<SpotRow> for Spot {
             mode,
             spotter,
             comment,
+            frequency_hz,
+            band,
+            canonical_mode,
+            spotters,
         } = value;
+        let spotters = match spotters {
+            Some(s) if !s.is_empty() => s.split(',').map(str::to_string).collect(),
+            _ => vec![spotter.clone()],
+        };
         Self {
             program,
             spot_id,
@@ -173,6 +194,72 @@ impl From<SpotRow> for Spot {
             mode,
             spotter,
             comment,
+            frequency_hz,
+            band,
+            canonical_mode,
+            spotters,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct AlertOutcomeRow {
+    pub program: AwardProgram,
+    pub alert_id: i32,
+    pub operator: String,
+    pub reference: String,
+    pub start_time: DateTime<Utc>,
+    pub outcome: ActivationOutcome,
+    pub spot_reference: Option<String>,
+    pub spot_time: Option<DateTime<Utc>>,
+}
+
+impl From<AlertOutcome> for AlertOutcomeRow {
+    fn from(value: AlertOutcome) -> Self {
+        let AlertOutcome {
+            program,
+            alert_id,
+            operator,
+            reference,
+            start_time,
+            outcome,
+            spot_reference,
+            spot_time,
+        } = value;
+        Self {
+            program,
+            alert_id,
+            operator,
+            reference,
+            start_time,
+            outcome,
+            spot_reference,
+            spot_time,
+        }
+    }
+}
+
+impl From<AlertOutcomeRow> for AlertOutcome {
+    fn from(value: AlertOutcomeRow) -> Self {
+        let AlertOutcomeRow {
+            program,
+            alert_id,
+            operator,
+            reference,
+            start_time,
+            outcome,
+            spot_reference,
+            spot_time,
+        } = value;
+        Self {
+            program,
+            alert_id,
+            operator,
+            reference,
+            start_time,
+            outcome,
+            spot_reference,
+            spot_time,
         }
     }
 }
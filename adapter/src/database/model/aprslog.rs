@@ -1,6 +1,9 @@
 use aprs_message::AprsCallsign;
 use chrono::NaiveDateTime;
-use domain::model::aprslog::{AprsLog, AprsState};
+use common::error::{AppError, AppResult};
+use domain::model::aprslog::{
+    AprsLog, AprsLogDetail, AprsSegment, AprsState, AprsTrackArchive, AprsTrackPoint,
+};
 
 #[derive(Debug, sqlx::FromRow)]
 pub struct AprsLogRow {
@@ -13,6 +16,11 @@ pub struct AprsLogRow {
     pub message: Option<String>,
     pub longitude: f64,
     pub latitude: f64,
+    pub altitude: Option<f64>,
+    /// 0:位置 1:オブジェクト 2:アイテム 3:気象 4:テレメトリ
+    pub packet_type: i64,
+    /// オブジェクト名、または気象・テレメトリのJSON
+    pub payload: Option<String>,
 }
 
 impl From<AprsLogRow> for AprsLog {
@@ -50,6 +58,14 @@ impl From<AprsLogRow> for AprsLog {
         } else {
             Some(aprs_log.ssid as u32)
         };
+        let payload = aprs_log.payload.unwrap_or_default();
+        let detail = match aprs_log.packet_type {
+            1 => AprsLogDetail::Object(payload),
+            2 => AprsLogDetail::Item(payload),
+            3 => AprsLogDetail::Weather(serde_json::from_str(&payload).unwrap_or_default()),
+            4 => AprsLogDetail::Telemetry(serde_json::from_str(&payload).unwrap_or_default()),
+            _ => AprsLogDetail::Position,
+        };
         AprsLog {
             callsign: AprsCallsign {
                 callsign: aprs_log.callsign,
@@ -59,6 +75,8 @@ impl From<AprsLogRow> for AprsLog {
             state,
             longitude: aprs_log.longitude,
             latitude: aprs_log.latitude,
+            altitude: aprs_log.altitude,
+            detail,
         }
     }
 }
@@ -81,6 +99,13 @@ impl From<AprsLog> for AprsLogRow {
             } => (4, time, distance, Some(message)),
             AprsState::Descending { time, distance } => (5, time, distance, None),
         };
+        let (packet_type, payload) = match aprs_log.detail {
+            AprsLogDetail::Position => (0, None),
+            AprsLogDetail::Object(name) => (1, Some(name)),
+            AprsLogDetail::Item(name) => (2, Some(name)),
+            AprsLogDetail::Weather(report) => (3, serde_json::to_string(&report).ok()),
+            AprsLogDetail::Telemetry(telemetry) => (4, serde_json::to_string(&telemetry).ok()),
+        };
         AprsLogRow {
             time,
             callsign: aprs_log.callsign.callsign,
@@ -91,6 +116,83 @@ impl From<AprsLog> for AprsLogRow {
             message,
             longitude: aprs_log.longitude,
             latitude: aprs_log.latitude,
+            altitude: aprs_log.altitude,
+            packet_type,
+            payload,
         }
     }
 }
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct AprsTrackArchiveRow {
+    pub archive_id: i64,
+    pub callsign: String,
+    pub ssid: i64,
+    pub summit: Option<String>,
+    pub start_time: NaiveDateTime,
+    pub end_time: NaiveDateTime,
+    pub arrival_time: Option<NaiveDateTime>,
+    /// [[時刻, 緯度, 経度], ...] のJSON
+    pub points: String,
+    pub archived_at: NaiveDateTime,
+}
+
+impl AprsTrackArchiveRow {
+    pub fn from_segment(segment: AprsSegment, archived_at: NaiveDateTime) -> AppResult<Self> {
+        let (Some(start_time), Some(end_time)) = (segment.start_time(), segment.end_time()) else {
+            return Err(AppError::UnprocessableEntity(
+                "トラックに位置がありません".to_string(),
+            ));
+        };
+        let points: Vec<(NaiveDateTime, f64, f64)> = segment
+            .points
+            .iter()
+            .map(|p| (p.time, p.latitude, p.longitude))
+            .collect();
+        Ok(Self {
+            archive_id: 0,
+            callsign: segment.callsign.callsign,
+            ssid: segment.callsign.ssid.unwrap_or(0) as i64,
+            summit: segment.summit,
+            start_time,
+            end_time,
+            arrival_time: segment.arrival,
+            points: serde_json::to_string(&points).map_err(AppError::JsonError)?,
+            archived_at,
+        })
+    }
+}
+
+impl TryFrom<AprsTrackArchiveRow> for AprsTrackArchive {
+    type Error = AppError;
+
+    fn try_from(row: AprsTrackArchiveRow) -> Result<Self, Self::Error> {
+        let points: Vec<(NaiveDateTime, f64, f64)> =
+            serde_json::from_str(&row.points).map_err(AppError::JsonError)?;
+        let ssid = if row.ssid == 0 {
+            None
+        } else {
+            Some(row.ssid as u32)
+        };
+        Ok(AprsTrackArchive {
+            archive_id: row.archive_id,
+            archived_at: row.archived_at.and_utc(),
+            segment: AprsSegment {
+                callsign: AprsCallsign {
+                    callsign: row.callsign,
+                    ssid,
+                },
+                summit: row.summit,
+                arrival: row.arrival_time,
+                points: points
+                    .into_iter()
+                    .map(|(time, latitude, longitude)| AprsTrackPoint {
+                        time,
+                        latitude,
+                        longitude,
+                    })
+                    .collect(),
+            },
+        })
+    }
+}
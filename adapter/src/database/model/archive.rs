@@ -0,0 +1,208 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use domain::model::activation::Alert;
+use domain::model::archive::{ActivatorStat, AlertArchive, ReferenceYearStat, SpotArchive};
+use domain::model::AwardProgram;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct SpotArchiveRow {
+    pub month: i32,
+    pub program: AwardProgram,
+    pub activation_date: NaiveDate,
+    pub operator: String,
+    pub reference: String,
+    pub band: String,
+    pub mode: String,
+    pub activator: String,
+    pub association: String,
+    pub reference_detail: String,
+    pub frequency: String,
+    pub first_spot: DateTime<Utc>,
+    pub last_spot: DateTime<Utc>,
+    pub spot_count: i32,
+}
+
+impl From<SpotArchive> for SpotArchiveRow {
+    fn from(value: SpotArchive) -> Self {
+        let SpotArchive {
+            program,
+            month,
+            activation_date,
+            operator,
+            reference,
+            band,
+            mode,
+            activator,
+            association,
+            reference_detail,
+            frequency,
+            first_spot,
+            last_spot,
+            spot_count,
+        } = value;
+        Self {
+            month,
+            program,
+            activation_date,
+            operator,
+            reference,
+            band,
+            mode,
+            activator,
+            association,
+            reference_detail,
+            frequency,
+            first_spot,
+            last_spot,
+            spot_count,
+        }
+    }
+}
+
+impl From<SpotArchiveRow> for SpotArchive {
+    fn from(value: SpotArchiveRow) -> Self {
+        let SpotArchiveRow {
+            month,
+            program,
+            activation_date,
+            operator,
+            reference,
+            band,
+            mode,
+            activator,
+            association,
+            reference_detail,
+            frequency,
+            first_spot,
+            last_spot,
+            spot_count,
+        } = value;
+        Self {
+            program,
+            month,
+            activation_date,
+            operator,
+            reference,
+            band,
+            mode,
+            activator,
+            association,
+            reference_detail,
+            frequency,
+            first_spot,
+            last_spot,
+            spot_count,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct AlertArchiveRow {
+    pub month: i32,
+    pub association: String,
+    pub program: AwardProgram,
+    pub alert_id: i32,
+    pub user_id: i32,
+    pub reference: String,
+    pub reference_detail: String,
+    pub location: String,
+    pub activator: String,
+    pub activator_name: Option<String>,
+    pub operator: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub frequencies: String,
+    pub comment: Option<String>,
+    pub poster: Option<String>,
+}
+
+impl From<AlertArchive> for AlertArchiveRow {
+    fn from(value: AlertArchive) -> Self {
+        let AlertArchive {
+            month,
+            association,
+            alert,
+        } = value;
+        Self {
+            month,
+            association,
+            program: alert.program,
+            alert_id: alert.alert_id,
+            user_id: alert.user_id,
+            reference: alert.reference,
+            reference_detail: alert.reference_detail,
+            location: alert.location,
+            activator: alert.activator,
+            activator_name: alert.activator_name,
+            operator: alert.operator,
+            start_time: alert.start_time,
+            end_time: alert.end_time,
+            frequencies: alert.frequencies,
+            comment: alert.comment,
+            poster: alert.poster,
+        }
+    }
+}
+
+impl From<AlertArchiveRow> for AlertArchive {
+    fn from(value: AlertArchiveRow) -> Self {
+        Self {
+            month: value.month,
+            association: value.association,
+            alert: Alert {
+                program: value.program,
+                alert_id: value.alert_id,
+                user_id: value.user_id,
+                reference: value.reference,
+                reference_detail: value.reference_detail,
+                location: value.location,
+                activator: value.activator,
+                activator_name: value.activator_name,
+                operator: value.operator,
+                start_time: value.start_time,
+                end_time: value.end_time,
+                frequencies: value.frequencies,
+                comment: value.comment,
+                poster: value.poster,
+            },
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ReferenceYearStatRow {
+    pub program: AwardProgram,
+    pub reference: String,
+    pub year: i32,
+    pub activations: i64,
+    pub activators: i64,
+}
+
+impl From<ReferenceYearStatRow> for ReferenceYearStat {
+    fn from(value: ReferenceYearStatRow) -> Self {
+        Self {
+            program: value.program,
+            reference: value.reference,
+            year: value.year,
+            activations: value.activations,
+            activators: value.activators,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct ActivatorStatRow {
+    pub operator: String,
+    pub activations: i64,
+    pub reference_count: i64,
+}
+
+impl From<ActivatorStatRow> for ActivatorStat {
+    fn from(value: ActivatorStatRow) -> Self {
+        Self {
+            operator: value.operator,
+            activations: value.activations,
+            references: value.reference_count,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use domain::model::changelog::{ReferenceChange, ReferenceChangeKind};
+use domain::model::AwardProgram;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct ReferenceChangeRow {
+    pub program: AwardProgram,
+    pub reference: String,
+    pub kind: ReferenceChangeKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl From<ReferenceChange> for ReferenceChangeRow {
+    fn from(value: ReferenceChange) -> Self {
+        let ReferenceChange {
+            program,
+            reference,
+            kind,
+            old_value,
+            new_value,
+            changed_at,
+        } = value;
+        Self {
+            program,
+            reference,
+            kind,
+            old_value,
+            new_value,
+            changed_at,
+        }
+    }
+}
+
+impl From<ReferenceChangeRow> for ReferenceChange {
+    fn from(value: ReferenceChangeRow) -> Self {
+        let ReferenceChangeRow {
+            program,
+            reference,
+            kind,
+            old_value,
+            new_value,
+            changed_at,
+        } = value;
+        Self {
+            program,
+            reference,
+            kind,
+            old_value,
+            new_value,
+            changed_at,
+        }
+    }
+}
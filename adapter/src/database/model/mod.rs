@@ -1,5 +1,10 @@
 pub mod activation;
 pub mod aprslog;
+pub mod archive;
+pub mod changelog;
 pub mod locator;
 pub mod pota;
+pub mod relay;
+pub mod session;
 pub mod sota;
+pub mod watch;
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use domain::model::id::UserId;
+use domain::model::relay::{PostKind, RelayPost, RelayStatus};
+use domain::model::AwardProgram;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct RelayPostRow {
+    pub post_id: i64,
+    pub user_id: UserId,
+    pub kind: PostKind,
+    pub program: AwardProgram,
+    pub reference: String,
+    pub activator: String,
+    pub local_id: i32,
+    pub status: RelayStatus,
+    pub upstream_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub relayed_at: Option<DateTime<Utc>>,
+}
+
+impl From<RelayPost> for RelayPostRow {
+    fn from(value: RelayPost) -> Self {
+        let RelayPost {
+            post_id,
+            user_id,
+            kind,
+            program,
+            reference,
+            activator,
+            local_id,
+            status,
+            upstream_id,
+            error,
+            created_at,
+            relayed_at,
+        } = value;
+        Self {
+            post_id,
+            user_id,
+            kind,
+            program,
+            reference,
+            activator,
+            local_id,
+            status,
+            upstream_id,
+            error,
+            created_at,
+            relayed_at,
+        }
+    }
+}
+
+impl From<RelayPostRow> for RelayPost {
+    fn from(value: RelayPostRow) -> Self {
+        let RelayPostRow {
+            post_id,
+            user_id,
+            kind,
+            program,
+            reference,
+            activator,
+            local_id,
+            status,
+            upstream_id,
+            error,
+            created_at,
+            relayed_at,
+        } = value;
+        Self {
+            post_id,
+            user_id,
+            kind,
+            program,
+            reference,
+            activator,
+            local_id,
+            status,
+            upstream_id,
+            error,
+            created_at,
+            relayed_at,
+        }
+    }
+}
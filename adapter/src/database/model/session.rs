@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use domain::model::id::UserId;
+use domain::model::session::{ActivationSession, SessionFile, SessionQso, SessionStatus};
+use domain::model::AwardProgram;
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow)]
+pub struct ActivationSessionRow {
+    pub session_id: i64,
+    pub user_id: UserId,
+    pub program: AwardProgram,
+    pub reference: String,
+    pub callsign: String,
+    pub operator: String,
+    pub status: SessionStatus,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub qualified_at: Option<DateTime<Utc>>,
+}
+
+impl From<ActivationSession> for ActivationSessionRow {
+    fn from(value: ActivationSession) -> Self {
+        let ActivationSession {
+            session_id,
+            user_id,
+            program,
+            reference,
+            callsign,
+            operator,
+            status,
+            opened_at,
+            closed_at,
+            qualified_at,
+        } = value;
+        Self {
+            session_id,
+            user_id,
+            program,
+            reference,
+            callsign,
+            operator,
+            status,
+            opened_at,
+            closed_at,
+            qualified_at,
+        }
+    }
+}
+
+impl From<ActivationSessionRow> for ActivationSession {
+    fn from(value: ActivationSessionRow) -> Self {
+        let ActivationSessionRow {
+            session_id,
+            user_id,
+            program,
+            reference,
+            callsign,
+            operator,
+            status,
+            opened_at,
+            closed_at,
+            qualified_at,
+        } = value;
+        Self {
+            session_id,
+            user_id,
+            program,
+            reference,
+            callsign,
+            operator,
+            status,
+            opened_at,
+            closed_at,
+            qualified_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SessionQsoRow {
+    pub session_id: i64,
+    pub client_id: String,
+    pub callsign: String,
+    pub band: String,
+    pub mode: String,
+    pub qso_time: DateTime<Utc>,
+    pub record: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SessionQsoRow> for SessionQso {
+    fn from(value: SessionQsoRow) -> Self {
+        let SessionQsoRow {
+            session_id,
+            client_id,
+            callsign,
+            band,
+            mode,
+            qso_time,
+            record,
+            created_at,
+        } = value;
+        Self {
+            session_id,
+            client_id,
+            callsign,
+            band,
+            mode,
+            qso_time,
+            record,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SessionFileRow {
+    pub session_id: i64,
+    pub filename: String,
+    pub content: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SessionFileRow> for SessionFile {
+    fn from(value: SessionFileRow) -> Self {
+        let SessionFileRow {
+            session_id,
+            filename,
+            content,
+            created_at,
+        } = value;
+        Self {
+            session_id,
+            filename,
+            content,
+            created_at,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use common::error::AppError;
 use domain::model::id::UserId;
-use domain::model::sota::{SotaLog, SotaReference};
+use domain::model::sota::{ActivationZone, SotaLog, SotaReference};
 use sqlx::FromRow;
 
 #[derive(Debug, FromRow)]
@@ -129,6 +130,32 @@ impl From<SotaReferenceRow> for SotaReference {
     }
 }
 
+#[derive(Debug, FromRow)]
+pub struct ActivationZoneRow {
+    pub summit_code: String,
+    pub geometry: String,
+}
+
+impl From<ActivationZone> for ActivationZoneRow {
+    fn from(z: ActivationZone) -> Self {
+        Self {
+            geometry: z.geometry().to_string(),
+            summit_code: z.summit_code,
+        }
+    }
+}
+
+impl TryFrom<ActivationZoneRow> for ActivationZone {
+    type Error = AppError;
+
+    fn try_from(z: ActivationZoneRow) -> Result<Self, Self::Error> {
+        let geometry = serde_json::from_str(&z.geometry).map_err(AppError::JsonError)?;
+        ActivationZone::from_geometry(&z.summit_code, &geometry).ok_or_else(|| {
+            AppError::ConversionEntityError(format!("invalid activation zone {}", z.summit_code))
+        })
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub struct SotaLogRow {
     pub user_id: String,
@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use domain::model::id::UserId;
+use domain::model::watch::{DeliveryStatus, Watchlist, WebhookDelivery};
+use domain::model::AwardProgram;
+use sqlx::FromRow;
+
+fn join(values: &[String]) -> String {
+    values.join(",")
+}
+
+fn split(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[derive(Debug, FromRow)]
+pub struct WatchlistRow {
+    pub watch_id: i64,
+    pub user_id: UserId,
+    pub name: String,
+    /// カンマ区切り
+    pub callsigns: String,
+    /// カンマ区切り
+    pub patterns: String,
+    /// プログラム番号のカンマ区切り
+    pub programs: String,
+    /// カンマ区切り
+    pub bands: String,
+    pub webhook_url: String,
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Watchlist> for WatchlistRow {
+    fn from(value: Watchlist) -> Self {
+        let Watchlist {
+            watch_id,
+            user_id,
+            name,
+            callsigns,
+            patterns,
+            programs,
+            bands,
+            webhook_url,
+            secret,
+            enabled,
+            created_at,
+            updated_at,
+        } = value;
+        Self {
+            watch_id,
+            user_id,
+            name,
+            callsigns: join(&callsigns),
+            patterns: join(&patterns),
+            programs: programs
+                .iter()
+                .map(|p| p.as_i32().to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+            bands: join(&bands),
+            webhook_url,
+            secret,
+            enabled,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+impl From<WatchlistRow> for Watchlist {
+    fn from(value: WatchlistRow) -> Self {
+        let WatchlistRow {
+            watch_id,
+            user_id,
+            name,
+            callsigns,
+            patterns,
+            programs,
+            bands,
+            webhook_url,
+            secret,
+            enabled,
+            created_at,
+            updated_at,
+        } = value;
+        Self {
+            watch_id,
+            user_id,
+            name,
+            callsigns: split(&callsigns),
+            patterns: split(&patterns),
+            programs: split(&programs)
+                .iter()
+                .filter_map(|p| p.parse::<i32>().ok())
+                .filter(|p| (0..=2).contains(p))
+                .map(AwardProgram::from)
+                .collect(),
+            bands: split(&bands),
+            webhook_url,
+            secret,
+            enabled,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct WebhookDeliveryRow {
+    pub delivery_id: i64,
+    pub watch_id: i64,
+    pub event_key: String,
+    pub event: String,
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryRow {
+    fn from(value: WebhookDelivery) -> Self {
+        let WebhookDelivery {
+            delivery_id,
+            watch_id,
+            event_key,
+            event,
+            payload,
+            status,
+            attempts,
+            status_code,
+            error,
+            created_at,
+            next_attempt_at,
+            delivered_at,
+        } = value;
+        Self {
+            delivery_id,
+            watch_id,
+            event_key,
+            event,
+            payload,
+            status,
+            attempts,
+            status_code,
+            error,
+            created_at,
+            next_attempt_at,
+            delivered_at,
+        }
+    }
+}
+
+impl From<WebhookDeliveryRow> for WebhookDelivery {
+    fn from(value: WebhookDeliveryRow) -> Self {
+        let WebhookDeliveryRow {
+            delivery_id,
+            watch_id,
+            event_key,
+            event,
+            payload,
+            status,
+            attempts,
+            status_code,
+            error,
+            created_at,
+            next_attempt_at,
+            delivered_at,
+        } = value;
+        Self {
+            delivery_id,
+            watch_id,
+            event_key,
+            event,
+            payload,
+            status,
+            attempts,
+            status_code,
+            error,
+            created_at,
+            next_attempt_at,
+            delivered_at,
+        }
+    }
+}
@@ -4,14 +4,15 @@ use chrono::NaiveDate;
 use common::config::AppConfig;
 use common::error::{AppError, AppResult};
 use common::http::{self, with_retry, RetryConfig};
+use common::jobs::JobRegistry;
+use common::metrics::{self, GEOMAG_LAST_UPDATE_TIMESTAMP, GEOMAG_UPDATE_ERRORS_TOTAL};
 use domain::{model::geomag::GeomagIndex, repository::geomag::GeoMagRepositry};
 use shaku::Component;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tokio_cron_scheduler::{Job, JobScheduler};
 
-pub async fn connect_geomag_with(cfg: &AppConfig) -> Result<GeoMag> {
-    let geomag = GeoMag::new(cfg).await?;
+pub async fn connect_geomag_with(cfg: &AppConfig, jobs: &JobRegistry) -> Result<GeoMag> {
+    let geomag = GeoMag::new(cfg, jobs).await?;
     Ok(geomag)
 }
 
@@ -21,7 +22,7 @@ pub struct GeoMag {
 }
 
 impl GeoMag {
-    pub async fn new(config: &AppConfig) -> Result<Self> {
+    pub async fn new(config: &AppConfig, jobs: &JobRegistry) -> Result<Self> {
         let endpoint = config.geomag_endpoint.clone();
         let schedule = config.geomag_update_schedule.clone();
 
@@ -38,27 +39,29 @@ impl GeoMag {
         })
         .await;
 
-        let sched = JobScheduler::new().await?;
-        sched
-            .add(
-                Job::new_async(&schedule, move |_uuid, _l| {
-                    let endpoint = endpoint.clone();
-                    let geomag = geomag_clone.clone();
-                    Box::pin(async move {
-                        if let Err(e) = Self::update(endpoint.as_str(), geomag.clone()).await {
-                            tracing::error!("Geomag update error: {}", e);
-                        }
-                    })
-                })
-                .unwrap_or_else(|_| panic!("Bad cron format: {}", &schedule)),
-            )
-            .await?;
-
-        sched.start().await?;
+        jobs.add_cron("geomag", &schedule, move || {
+            let endpoint = endpoint.clone();
+            let geomag = geomag_clone.clone();
+            async move {
+                let result = Self::update(endpoint.as_str(), geomag).await;
+                if result.is_err() {
+                    metrics::inc_counter(GEOMAG_UPDATE_ERRORS_TOTAL, &[]);
+                }
+                result
+            }
+        })
+        .await?;
 
         Ok(Self { geomag })
     }
 
+    /// 更新ジョブを登録しない（APRS記録の再生など外部取得が不要な場合）
+    pub fn detached() -> Self {
+        Self {
+            geomag: Arc::new(Mutex::new(None)),
+        }
+    }
+
     pub async fn get_geomag(&self) -> AppResult<Option<GeomagIndex>> {
         let geomag = self.geomag.lock().await;
         Ok(geomag.clone())
@@ -120,6 +123,11 @@ impl GeoMag {
             tracing::info!("Update GeomagIndex {:?}", &new_index);
 
             *index = Some(new_index);
+            metrics::set_gauge(
+                GEOMAG_LAST_UPDATE_TIMESTAMP,
+                &[],
+                chrono::Utc::now().timestamp() as f64,
+            );
 
             Ok(())
         } else {
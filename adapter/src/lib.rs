@@ -1,4 +1,8 @@
 pub mod aprs;
+pub mod aprs_is;
+pub mod aprs_replay;
 pub mod database;
 pub mod geomag;
 pub mod minikvs;
+pub mod relay;
+pub mod webhook;
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use common::config::AppConfig;
+use common::error::{AppError, AppResult};
+use common::http;
+use domain::model::activation::{Alert, Spot};
+use domain::model::relay::RelayOutcome;
+use domain::repository::relay::UpstreamRelay;
+use serde_json::{json, Value};
+use shaku::Component;
+use std::time::Duration;
+
+/// 1回の中継のタイムアウト（秒）
+const RELAY_TIMEOUT_SECS: u64 = 15;
+
+/// 拒否されたときに記録する応答本文の長さ
+const RELAY_MESSAGE_MAX_CHARS: usize = 200;
+
+pub fn spot_payload(spot: &Spot) -> Value {
+    let program: String = spot.program.clone().into();
+    json!({
+        "program": program,
+        "reference": spot.reference,
+        "activator": spot.activator,
+        "frequency": spot.frequency,
+        "mode": spot.mode,
+        "spotter": spot.spotter,
+        "comment": spot.comment,
+        "spotTime": spot.spot_time.to_rfc3339(),
+    })
+}
+
+pub fn alert_payload(alert: &Alert) -> Value {
+    let program: String = alert.program.clone().into();
+    json!({
+        "program": program,
+        "reference": alert.reference,
+        "activator": alert.activator,
+        "startTime": alert.start_time.to_rfc3339(),
+        "endTime": alert.end_time.map(|t| t.to_rfc3339()),
+        "frequencies": alert.frequencies,
+        "comment": alert.comment,
+        "poster": alert.poster,
+    })
+}
+
+/// 応答本文のJSONにある `id` をアップストリーム側のIDとして取り出す
+fn upstream_id(body: &str) -> Option<String> {
+    match serde_json::from_str::<Value>(body).ok()?.get("id")? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// JSONを中継先へPOSTする（中継先がなければ `Disabled`）
+pub async fn relay_to(
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+    payload: &Value,
+) -> AppResult<RelayOutcome> {
+    let Some(endpoint) = endpoint.filter(|e| !e.is_empty()) else {
+        return Ok(RelayOutcome::Disabled);
+    };
+    let mut req = http::client()
+        .post(endpoint)
+        .timeout(Duration::from_secs(RELAY_TIMEOUT_SECS))
+        .json(payload);
+    if let Some(key) = api_key {
+        req = req.bearer_auth(key);
+    }
+    let res = req.send().await.map_err(AppError::PostError)?;
+    let status = res.status();
+    let body = res.text().await.unwrap_or_default();
+    if status.is_success() {
+        Ok(RelayOutcome::Relayed {
+            upstream_id: upstream_id(&body),
+        })
+    } else {
+        Ok(RelayOutcome::Rejected {
+            status_code: status.as_u16(),
+            message: body.chars().take(RELAY_MESSAGE_MAX_CHARS).collect(),
+        })
+    }
+}
+
+#[derive(Component)]
+#[shaku(interface = UpstreamRelay)]
+pub struct HttpRelayImpl {
+    config: AppConfig,
+}
+
+#[async_trait]
+impl UpstreamRelay for HttpRelayImpl {
+    async fn relay_spot(&self, spot: &Spot) -> AppResult<RelayOutcome> {
+        relay_to(
+            self.config.relay_spot_endpoint.as_deref(),
+            self.config.relay_api_key.as_deref(),
+            &spot_payload(spot),
+        )
+        .await
+    }
+
+    async fn relay_alert(&self, alert: &Alert) -> AppResult<RelayOutcome> {
+        relay_to(
+            self.config.relay_alert_endpoint.as_deref(),
+            self.config.relay_api_key.as_deref(),
+            &alert_payload(alert),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// 1回だけリクエストを受けて決まった応答を返すローカルの中継先
+    async fn stand_in(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/spots", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&buf).to_string();
+                if let Some(end) = text.find("\r\n\r\n") {
+                    let length = text
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if buf.len() >= end + 4 + length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&buf).to_string()
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn test_relay_to_stand_in() {
+        let (url, handle) = stand_in("201 Created", r#"{"id":123}"#).await;
+        let payload = json!({"reference": "JA/KN-006"});
+        let outcome = relay_to(Some(url.as_str()), Some("secret"), &payload)
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            RelayOutcome::Relayed {
+                upstream_id: Some("123".to_string())
+            }
+        );
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("POST /spots"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("authorization: bearer secret"));
+        assert!(request.contains("JA/KN-006"));
+    }
+
+    #[tokio::test]
+    async fn test_relay_to_rejected() {
+        let (url, _handle) = stand_in("400 Bad Request", "unknown summit").await;
+        let outcome = relay_to(Some(url.as_str()), None, &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            RelayOutcome::Rejected {
+                status_code: 400,
+                message: "unknown summit".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_relay_to_disabled() {
+        let outcome = relay_to(None, None, &json!({})).await.unwrap();
+        assert_eq!(outcome, RelayOutcome::Disabled);
+        let outcome = relay_to(Some(""), None, &json!({})).await.unwrap();
+        assert_eq!(outcome, RelayOutcome::Disabled);
+    }
+
+    #[test]
+    fn test_upstream_id() {
+        assert_eq!(upstream_id(r#"{"id":"abc"}"#).as_deref(), Some("abc"));
+        assert_eq!(upstream_id(r#"{"id":42}"#).as_deref(), Some("42"));
+        assert_eq!(upstream_id("ok"), None);
+    }
+}
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use common::error::{AppError, AppResult};
+use common::http::header;
+use domain::repository::watch::WebhookRepository;
+use hmac::{Hmac, Mac};
+use reqwest::{redirect, Client, Url};
+use sha2::Sha256;
+use shaku::Component;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// 署名ヘッダー（値は "sha256=<HMAC-SHA256の16進>"）
+pub const SIGNATURE_HEADER: &str = "X-SotaApp-Signature";
+
+/// 1回の送信のタイムアウト（秒）
+const WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// 本文のHMAC-SHA256署名（16進）
+pub fn sign(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 外部の宛先として送ってよいアドレスか（ループバック・プライベート・リンクローカルなどを除く）
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_ipv4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_ipv4(v4),
+            None => {
+                let head = v6.segments()[0];
+                !(v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    // ユニークローカル（fc00::/7）
+                    || head & 0xfe00 == 0xfc00
+                    // リンクローカル（fe80::/10）
+                    || head & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // リンクローカルにはクラウドのメタデータ（169.254.169.254）も含まれる
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        // キャリアグレードNAT（100.64.0.0/10）
+        || (a == 100 && b & 0xc0 == 64))
+}
+
+/// Webhookの宛先URLを確かめ、接続先のアドレスに解決する
+///
+/// httpsのURLだけを受け付け、解決したアドレスに内部のアドレスが1つでもあれば拒否する。
+async fn resolve_webhook_url(url: &str) -> AppResult<(Url, Vec<SocketAddr>)> {
+    let invalid = |msg: &str| AppError::UnprocessableEntity(msg.to_string());
+    let not_https = || invalid("webhookUrlはhttpsのURLで指定してください");
+    let parsed = Url::parse(url).map_err(|_| not_https())?;
+    if parsed.scheme() != "https" {
+        return Err(not_https());
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| not_https())?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|_| invalid("webhookUrlのホスト名を解決できません"))?
+            .collect(),
+    };
+    if addrs.is_empty() || !addrs.iter().all(|a| is_public_ip(a.ip())) {
+        return Err(invalid(
+            "webhookUrlに内部ネットワークのアドレスは指定できません",
+        ));
+    }
+    Ok((parsed, addrs))
+}
+
+#[derive(Component)]
+#[shaku(interface = WebhookRepository)]
+pub struct WebhookRepositoryImpl {}
+
+#[async_trait]
+impl WebhookRepository for WebhookRepositoryImpl {
+    async fn check_url(&self, url: &str) -> AppResult<()> {
+        resolve_webhook_url(url).await.map(|_| ())
+    }
+
+    async fn post(&self, url: &str, secret: &str, payload: &str) -> AppResult<u16> {
+        // 登録後にDNSが書き換えられることもあるので送信のたびに確かめ、
+        // 確かめたアドレスに固定して接続する（リダイレクトはたどらない）
+        let (url, addrs) = resolve_webhook_url(url).await?;
+        let client = Client::builder()
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECS))
+            .redirect(redirect::Policy::none())
+            .resolve_to_addrs(url.host_str().unwrap_or_default(), &addrs)
+            .build()
+            .map_err(AppError::PostError)?;
+        let res = client
+            .post(url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(
+                SIGNATURE_HEADER,
+                format!("sha256={}", sign(secret, payload)),
+            )
+            .body(payload.to_string())
+            .send()
+            .await
+            .map_err(AppError::PostError)?;
+        Ok(res.status().as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign("key", "The quick brown fox jumps over the lazy dog"),
+            "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "0.0.0.0",
+            "100.64.0.1",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_webhook_url() {
+        for url in [
+            "http://93.184.216.34/hook",
+            "https://127.0.0.1/hook",
+            "https://169.254.169.254/latest/meta-data",
+            "https://[::1]:8443/hook",
+            "https://localhost/hook",
+            "not a url",
+        ] {
+            assert!(resolve_webhook_url(url).await.is_err(), "{}", url);
+        }
+        let (url, addrs) = resolve_webhook_url("https://93.184.216.34:8443/hook")
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/hook");
+        assert_eq!(addrs, vec!["93.184.216.34:8443".parse().unwrap()]);
+    }
+}
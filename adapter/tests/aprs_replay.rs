@@ -0,0 +1,62 @@
+/// APRS記録ファイルの再生テスト
+///
+/// `APRS_RECORD_PATH` で記録したファイルを test_data に置けば、
+/// 同じパケット列を何度でも再生して確認できる。
+///
+/// 実行方法:
+///   cargo test --package adapter --test aprs_replay
+use adapter::aprs_replay::{position_callsigns, read_recording, AprsReplay};
+use domain::model::aprs::AprsPacket;
+use domain::repository::aprs::AprsRepositry;
+use std::collections::HashSet;
+use std::path::Path;
+
+const SAMPLE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../test_data/aprs_replay_sample.jsonl"
+);
+
+#[test]
+fn sample_recording_is_valid() {
+    let packets = read_recording(Path::new(SAMPLE)).unwrap();
+    assert_eq!(packets.len(), 8);
+    assert!(packets.windows(2).all(|w| w[0].time <= w[1].time));
+    assert_eq!(
+        position_callsigns(&packets),
+        HashSet::from(["JA1ABC".to_string(), "JA2XYZ".to_string()])
+    );
+}
+
+#[tokio::test]
+async fn replay_preserves_packet_order() {
+    let replay = AprsReplay::open(Path::new(SAMPLE), 0.0).unwrap();
+
+    let packets = read_recording(Path::new(SAMPLE)).unwrap();
+    let mut kinds = Vec::new();
+    let mut times = Vec::new();
+    while !replay.is_finished() {
+        let (time, packet) = replay.get_aprs_packet().await.unwrap();
+        times.push(time);
+        match packet {
+            AprsPacket::Message { message, .. } => kinds.push(format!("message:{}", message)),
+            packet => kinds.push(format!("{}:{}", packet.kind(), packet.callsign().callsign)),
+        }
+    }
+
+    assert_eq!(
+        kinds,
+        vec![
+            "position:JA1ABC",
+            "position:JA1ABC",
+            "position:JA1ABC",
+            "position:JA1ABC",
+            "object:JA1ABC",
+            "message:JA",
+            "weather:JA1ABC",
+            "position:JA2XYZ"
+        ]
+    );
+    // 再生したパケットの時刻は記録時刻
+    assert_eq!(times, packets.iter().map(|p| p.time).collect::<Vec<_>>());
+    assert!(replay.sent_messages().is_empty());
+}
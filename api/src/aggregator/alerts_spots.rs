@@ -4,6 +4,9 @@ use std::sync::Arc;
 use common::config::AppConfig;
 use common::error::{AppError, AppResult};
 use common::http;
+use common::metrics::{
+    self, ALERTS_CONVERTED_TOTAL, ALERTS_FETCHED_TOTAL, SPOTS_CONVERTED_TOTAL, SPOTS_FETCHED_TOTAL,
+};
 use domain::model::activation::{Alert, Spot};
 use registry::AppRegistry;
 use service::services::AdminPeriodicService;
@@ -43,6 +46,12 @@ pub async fn update_alerts(config: &AppConfig, registry: &Arc<AppRegistry>) -> A
                     converted.len(),
                     total
                 );
+                metrics::add_counter(ALERTS_FETCHED_TOTAL, &[("source", "sota")], total as u64);
+                metrics::add_counter(
+                    ALERTS_CONVERTED_TOTAL,
+                    &[("source", "sota")],
+                    converted.len() as u64,
+                );
                 converted
             }
         },
@@ -74,6 +83,12 @@ pub async fn update_alerts(config: &AppConfig, registry: &Arc<AppRegistry>) -> A
                     converted.len(),
                     total
                 );
+                metrics::add_counter(ALERTS_FETCHED_TOTAL, &[("source", "pota")], total as u64);
+                metrics::add_counter(
+                    ALERTS_CONVERTED_TOTAL,
+                    &[("source", "pota")],
+                    converted.len() as u64,
+                );
                 requests.extend(converted);
             }
         },
@@ -99,10 +114,17 @@ pub async fn update_spots(config: &AppConfig, registry: &Arc<AppRegistry>) -> Ap
         .await
         .map_err(AppError::GetError)?;
 
+    let total = response.len();
     let mut requests: Vec<Spot> = response
         .into_iter()
         .filter_map(|ss| AppResult::<Spot>::from(ss).ok())
         .collect();
+    metrics::add_counter(SPOTS_FETCHED_TOTAL, &[("source", "sota")], total as u64);
+    metrics::add_counter(
+        SPOTS_CONVERTED_TOTAL,
+        &[("source", "sota")],
+        requests.len() as u64,
+    );
 
     let endpoint = config.pota_spot_endpoint.clone();
     let response = client
@@ -114,10 +136,17 @@ pub async fn update_spots(config: &AppConfig, registry: &Arc<AppRegistry>) -> Ap
         .await
         .map_err(AppError::GetError)?;
 
+    let total = response.len();
     let requests_pota: Vec<Spot> = response
         .into_iter()
         .filter_map(|ss| AppResult::<Spot>::from(ss).ok())
         .collect();
+    metrics::add_counter(SPOTS_FETCHED_TOTAL, &[("source", "pota")], total as u64);
+    metrics::add_counter(
+        SPOTS_CONVERTED_TOTAL,
+        &[("source", "pota")],
+        requests_pota.len() as u64,
+    );
 
     requests.extend(requests_pota);
 
@@ -3,14 +3,53 @@ use domain::repository::aprs::AprsRepositry;
 use registry::AppRegistry;
 use service::services::AdminPeriodicService;
 use shaku::HasComponent;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 pub async fn process_incoming_packet(registry: &Arc<AppRegistry>) -> AppResult<()> {
     let service: &dyn AdminPeriodicService = registry.resolve_ref();
     let aprs_repo: &dyn AprsRepositry = registry.resolve_ref();
+    process_packet_with(service, aprs_repo).await
+}
 
-    let packet = aprs_repo.get_aprs_packet().await?;
-    service.aprs_packet_received(packet).await?;
+/// 次のパケットを受信時刻（再生では記録時刻）とともにサービスへ渡す
+async fn process_packet_with(
+    service: &dyn AdminPeriodicService,
+    aprs_repo: &dyn AprsRepositry,
+) -> AppResult<()> {
+    let (now, packet) = aprs_repo.get_aprs_packet().await?;
+    service.aprs_packet_received(packet, now).await?;
 
     Ok(())
 }
+
+/// 記録再生用: バディリストを設定して `count` パケットを処理する
+///
+/// 個々のパケットのエラーはライブ受信と同様にログに出して処理を続け、エラー数を返す。
+pub async fn replay_packets(
+    registry: &Arc<AppRegistry>,
+    buddy: HashSet<String>,
+    count: usize,
+) -> usize {
+    let service: &dyn AdminPeriodicService = registry.resolve_ref();
+    let aprs_repo: &dyn AprsRepositry = registry.resolve_ref();
+    replay_packets_with(service, aprs_repo, buddy, count).await
+}
+
+pub async fn replay_packets_with(
+    service: &dyn AdminPeriodicService,
+    aprs_repo: &dyn AprsRepositry,
+    buddy: HashSet<String>,
+    count: usize,
+) -> usize {
+    service.set_buddy_callsigns(buddy);
+
+    let mut errors = 0;
+    for _ in 0..count {
+        if let Err(e) = process_packet_with(service, aprs_repo).await {
+            tracing::error!("APRS Error {:?}", e);
+            errors += 1;
+        }
+    }
+    errors
+}
@@ -2,11 +2,16 @@ use futures_util::StreamExt;
 use shaku::HasComponent;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
 use common::error::AppResult;
 use common::http;
+use common::metrics::{
+    self, REFERENCE_LIST_LAST_SUCCESS_TIMESTAMP, REFERENCE_LIST_UPDATED,
+    REFERENCE_LIST_UPDATE_DURATION_SECONDS,
+};
 use common::{config::AppConfig, error::AppError};
 use registry::AppRegistry;
 use service::services::AdminPeriodicService;
@@ -14,6 +19,22 @@ use service::services::AdminPeriodicService;
 const SOTA_CSV_PATH: &str = "/tmp/summits.csv";
 const POTA_CSV_PATH: &str = "/tmp/parks.csv";
 
+/// リスト更新の所要時間と件数を記録
+fn record_list_update(list: &str, start: Instant, count: usize) {
+    let labels = [("list", list)];
+    metrics::set_gauge(
+        REFERENCE_LIST_UPDATE_DURATION_SECONDS,
+        &labels,
+        start.elapsed().as_secs_f64(),
+    );
+    metrics::set_gauge(REFERENCE_LIST_UPDATED, &labels, count as f64);
+    metrics::set_gauge(
+        REFERENCE_LIST_LAST_SUCCESS_TIMESTAMP,
+        &labels,
+        chrono::Utc::now().timestamp() as f64,
+    );
+}
+
 /// HTTPレスポンスをストリームでファイルに保存（メモリ効率向上）
 async fn download_to_file(url: &str, path: &str, user_agent: Option<&str>) -> AppResult<()> {
     let client = http::client();
@@ -52,6 +73,7 @@ async fn download_to_file(url: &str, path: &str, user_agent: Option<&str>) -> Ap
 pub async fn update_summit_list(config: AppConfig, registry: Arc<AppRegistry>) -> AppResult<()> {
     let service: &dyn AdminPeriodicService = registry.resolve_ref();
     let endpoint = config.sota_summitlist_endpoint.clone();
+    let start = Instant::now();
 
     tracing::info!("Downloading summit list from {}", endpoint);
     download_to_file(&endpoint, SOTA_CSV_PATH, None).await?;
@@ -63,6 +85,7 @@ pub async fn update_summit_list(config: AppConfig, registry: Arc<AppRegistry>) -
 
     // 一時ファイル削除
     let _ = tokio::fs::remove_file(SOTA_CSV_PATH).await;
+    record_list_update("sota", start, count);
 
     tracing::info!(
         "Summit list updated successfully. {} summits updated.",
@@ -74,6 +97,7 @@ pub async fn update_summit_list(config: AppConfig, registry: Arc<AppRegistry>) -
 pub async fn update_park_list(config: AppConfig, registry: Arc<AppRegistry>) -> AppResult<()> {
     let service: &dyn AdminPeriodicService = registry.resolve_ref();
     let endpoint = config.pota_parklist_endpoint.clone();
+    let start = Instant::now();
 
     let user_agent = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/108.0.0.0 Safari/537.36";
 
@@ -87,6 +111,7 @@ pub async fn update_park_list(config: AppConfig, registry: Arc<AppRegistry>) ->
 
     // 一時ファイル削除
     let _ = tokio::fs::remove_file(POTA_CSV_PATH).await;
+    record_list_update("pota", start, count);
 
     tracing::info!("Park list updated successfully. {} parks updated.", count);
     Ok(())
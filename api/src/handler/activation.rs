@@ -1,29 +1,111 @@
 use aprs_message::AprsCallsign;
-use axum::{routing::get, Json, Router};
+use axum::{
+    body::Body,
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
 use chrono::{Duration, Utc};
-use common::error::{AppError, AppResult};
+use common::error::{AppError, AppResult, ErrorResponse};
+use common::utils::call_to_operator;
+use firebase_auth_sdk::FireAuth;
+use serde::Deserialize;
 use serde_json::Value;
 use shaku_axum::Inject;
+use std::collections::HashMap;
+use utoipa::{IntoParams, OpenApi};
 
-use domain::model::event::{FindActBuilder, FindAprs};
+use domain::model::activation::OutcomeSummary;
+use domain::model::event::{FindAct, FindActBuilder, FindAprs, GroupBy};
+use domain::model::id::UserId;
+use domain::model::needed::Needed;
+use domain::model::AwardProgram;
 
 use domain::repository::minikvs::KvsRepositry;
 use registry::{AppRegistry, AppState};
-use service::services::UserService;
+use service::implement::activity_chart::generate_activity_svg;
+use service::services::{RelayService, UserService};
+
+use super::auth::with_auth;
 
 use crate::model::{
     activation::ActivationView,
-    alerts::AlertView,
-    aprslog::{AprsLogView, Track, Tracks},
-    param::{GetParam, ValidatedQuery},
-    spots::SpotView,
+    activity::{ActivityCellView, ActivityStatsView, ACTIVITY_DEFAULT_HOURS},
+    alerts::{AlertOutcomeView, AlertView, ReliabilityView},
+    aprslog::{archive_to_gpx, AprsLogView, Segment, Segments, Track, Tracks},
+    feed::{alerts_to_ical, spots_to_atom, FEED_MAX_ENTRIES},
+    param::{parse_program, GetParam, ValidatedQuery},
+    relay::{
+        build_alert, build_spot, AlertPostRequest, RelayPostView, SpotPostRequest,
+        POSTS_DEFAULT_LIMIT,
+    },
+    spots::{NeededView, SpotView},
+    FromModel, IntoDto,
 };
 
+/// アクティベーション API
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        show_all_alerts,
+        show_alerts_ical,
+        show_spots_atom,
+        show_sota_alerts,
+        show_pota_alerts,
+        show_all_spots,
+        show_sota_spots,
+        show_pota_spots,
+        show_aprs_log,
+        show_aprs_track,
+        show_aprs_activations,
+        show_track_archives,
+        download_track_archive,
+        show_reliability,
+        show_activity_stats,
+        show_activity_chart
+    ),
+    components(schemas(
+        SpotView,
+        AlertView,
+        NeededView,
+        AlertOutcomeView,
+        ReliabilityView,
+        ActivityStatsView,
+        ActivityCellView,
+        AprsLogView,
+        Tracks,
+        Track,
+        Segments,
+        Segment,
+    )),
+    tags((name = "activation", description = "スポット・アラート・APRSトラックAPI"))
+)]
+pub struct ActivationApi;
+
+/// スポット・アラート投稿 API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(post_spot, post_alert, list_posts),
+    components(schemas(SpotPostRequest, AlertPostRequest, RelayPostView, ErrorResponse)),
+    tags((name = "activation", description = "スポット・アラート・APRSトラックAPI"))
+)]
+pub struct ActivationPostApi;
+
 /// キャッシュTTL定数
 const CACHE_TTL_SPOTS: i64 = 30;
 const CACHE_TTL_ALERTS: i64 = 180;
 const CACHE_TTL_TRACK: i64 = 60;
 
+/// スポット集計のSVGの幅（ピクセル）
+const ACTIVITY_CHART_WIDTH: u32 = 900;
+
+/// 履行状況の集計期間デフォルト（90日）
+const RELIABILITY_DEFAULT_HOURS: i64 = 24 * 90;
+/// 履行状況に含める直近の結果件数
+const RELIABILITY_RECENT: usize = 20;
+
 /// パラメータからFindActBuilderにグルーピングとフィルタを適用
 fn apply_common_filters(
     param: &GetParam,
@@ -56,14 +138,57 @@ fn apply_common_filters(
         query = query.pattern(pat);
     }
 
+    // バンドフィルタ（スポットのみ）
+    if let Some(band) = &param.band {
+        query = query.band(band);
+    }
+
     // ログIDフィルタ
     if let Some(log_id) = &param.log_id {
         query = query.log_id(log_id);
     }
 
+    // 未交信フラグの判定に使うSOTAログの利用者
+    if let Some(user_id) = &param.user_id {
+        query = query.user_id(UserId::from(user_id.clone()));
+    }
+
     query
 }
 
+fn needed_first(param: &GetParam) -> bool {
+    param.sort.as_deref() == Some("needed")
+}
+
+/// グループをキー順に並べる
+///
+/// needed_firstなら、グループ内を未交信フラグの優先度順にし、
+/// グループも最も優先度の高い要素の順（同じならキー順）にする。
+fn order_groups<T>(
+    groups: HashMap<GroupBy, Vec<T>>,
+    needed_first: bool,
+    needed: impl Fn(&T) -> Option<Needed>,
+) -> Vec<(GroupBy, Vec<T>)> {
+    let priority = |v: &T| needed(v).map_or(u8::MAX, |n| n.priority());
+    let key = |g: &GroupBy| match g {
+        GroupBy::Callsign(k) | GroupBy::Reference(k) => k.clone(),
+    };
+
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    if needed_first {
+        for (_, values) in groups.iter_mut() {
+            values.sort_by_key(|v| priority(v));
+        }
+        groups.sort_by_cached_key(|(g, values)| {
+            let best = values.iter().map(priority).min().unwrap_or(u8::MAX);
+            (best, key(g))
+        });
+    } else {
+        groups.sort_by_cached_key(|(g, _)| key(g));
+    }
+    groups
+}
+
 async fn show_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -78,15 +203,17 @@ async fn show_spots(
     let query = apply_common_filters(&param, query, 3).build();
 
     let result = user_service.find_spots(query).await?;
-    let mut spots: Vec<_> = result
+    let result = order_groups(result, needed_first(&param), |s| s.needed);
+    let spots: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(SpotView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(SpotView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
 
-    spots.sort_by(|a, b| a.key.cmp(&b.key));
-
     let value =
         serde_json::to_value(spots).map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
     kvs_repo
@@ -110,15 +237,17 @@ async fn show_alerts(
     let query = apply_common_filters(&param, query, 24).build();
 
     let result = user_service.find_alerts(query).await?;
-    let mut alerts: Vec<_> = result
+    let result = order_groups(result, needed_first(&param), |a| a.needed);
+    let alerts: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(AlertView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(AlertView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
 
-    alerts.sort_by(|a, b| a.key.cmp(&b.key));
-
     let value =
         serde_json::to_value(alerts).map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
     kvs_repo
@@ -132,6 +261,17 @@ async fn show_alerts(
     Ok(Json(value))
 }
 
+/// SOTAスポット
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/spots/sota",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<SpotView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_sota_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -141,6 +281,17 @@ async fn show_sota_spots(
     show_spots(user_service, kvs_repo, param, query).await
 }
 
+/// POTAスポット
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/spots/pota",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<SpotView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_pota_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -150,6 +301,17 @@ async fn show_pota_spots(
     show_spots(user_service, kvs_repo, param, query).await
 }
 
+/// SOTA/POTAスポット（by_call/by_refでグループ化、sort=neededで未交信順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/spots",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<SpotView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_all_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -159,6 +321,17 @@ async fn show_all_spots(
     show_spots(user_service, kvs_repo, param, query).await
 }
 
+/// SOTAアラート
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/alerts/sota",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<AlertView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_sota_alerts(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -168,6 +341,17 @@ async fn show_sota_alerts(
     show_alerts(user_service, kvs_repo, param, query).await
 }
 
+/// POTAアラート
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/alerts/pota",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<AlertView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_pota_alerts(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -177,6 +361,17 @@ async fn show_pota_alerts(
     show_alerts(user_service, kvs_repo, param, query).await
 }
 
+/// SOTA/POTAアラート（by_call/by_refでグループ化、sort=neededで未交信順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/alerts",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<AlertView>]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
 async fn show_all_alerts(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -186,37 +381,179 @@ async fn show_all_alerts(
     show_alerts(user_service, kvs_repo, param, query).await
 }
 
+/// フィード用の検索条件（programで絞り込み、ほかは/alerts・/spotsと同じ）
+fn feed_query(param: &GetParam, default_hours: i64) -> AppResult<FindAct> {
+    let mut query = FindActBuilder::default();
+    if let Some(program) = &param.program {
+        query = match parse_program(program)? {
+            AwardProgram::SOTA => query.sota(),
+            _ => query.pota(),
+        };
+    }
+    Ok(apply_common_filters(param, query, default_hours).build())
+}
+
+/// by_call/by_refで絞り込んだコールサイン・リファレンス（"null"はグループ化のみ）
+fn feed_target(param: &GetParam) -> Option<(&'static str, String)> {
+    let valid = |v: &String| !v.starts_with("null");
+    if let Some(callsign) = param.by_call.as_ref().filter(|v| valid(v)) {
+        Some(("callsign", callsign.to_uppercase()))
+    } else {
+        param
+            .by_ref
+            .as_ref()
+            .filter(|v| valid(v))
+            .map(|r| ("reference", r.to_uppercase()))
+    }
+}
+
+fn text_response(content_type: &str, body: String) -> AppResult<Response> {
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?
+        .into_response())
+}
+
+/// アラートのiCalendar（/activation/alertsと同じパラメータ、programでも絞り込める）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/alerts.ics",
+    params(GetParam),
+    responses(
+        (status = 200, description = "iCalendar", content_type = "text/calendar", body = String),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
+async fn show_alerts_ical(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Response> {
+    let key = format!("ics:{}", param.to_key());
+    if let Some(Value::String(ics)) = kvs_repo.get(&key).await {
+        return text_response("text/calendar; charset=utf-8", ics);
+    }
+
+    let result = user_service.find_alerts(feed_query(&param, 24)?).await?;
+    let mut alerts: Vec<_> = result
+        .into_values()
+        .flatten()
+        .map(|log| log.alert)
+        .collect();
+    alerts.sort_by_key(|a| (a.start_time, a.alert_id));
+
+    let ics = alerts_to_ical(&alerts, Utc::now());
+    kvs_repo
+        .set(
+            key,
+            Value::String(ics.clone()),
+            Some(Duration::seconds(CACHE_TTL_ALERTS)),
+        )
+        .await;
+    text_response("text/calendar; charset=utf-8", ics)
+}
+
+/// 最近のスポットのAtomフィード（by_callでコールサイン、by_refでリファレンスごと）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/spots.atom",
+    params(GetParam),
+    responses(
+        (status = 200, description = "Atomフィード", content_type = "application/atom+xml", body = String),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "activation"
+)]
+async fn show_spots_atom(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Response> {
+    let key = format!("atom:{}", param.to_key());
+    if let Some(Value::String(atom)) = kvs_repo.get(&key).await {
+        return text_response("application/atom+xml; charset=utf-8", atom);
+    }
+
+    let result = user_service.find_spots(feed_query(&param, 3)?).await?;
+    let mut spots: Vec<_> = result.into_values().flatten().map(|log| log.spot).collect();
+    spots.sort_by_key(|s| std::cmp::Reverse((s.spot_time, s.spot_id)));
+    spots.truncate(FEED_MAX_ENTRIES);
+
+    let (title, feed_key) = match feed_target(&param) {
+        Some((kind, target)) => (format!("Spots: {}", target), format!("{}:{}", kind, target)),
+        None => ("SOTA/POTA spots".to_string(), "all".to_string()),
+    };
+    let atom = spots_to_atom(&title, &feed_key, &spots, Utc::now());
+    kvs_repo
+        .set(
+            key,
+            Value::String(atom.clone()),
+            Some(Duration::seconds(CACHE_TTL_SPOTS)),
+        )
+        .await;
+    text_response("application/atom+xml; charset=utf-8", atom)
+}
+
+/// after/before を優先し、なければ hours_ago（既定 default_hours）から検索開始時刻を決める
+fn aprs_window(param: &GetParam, default_hours: Option<i64>) -> FindAprs {
+    let after = param.after.or_else(|| {
+        param
+            .hours_ago
+            .or(default_hours)
+            .map(|h| Utc::now() - Duration::hours(h))
+    });
+    FindAprs {
+        after,
+        before: param.before,
+        ..Default::default()
+    }
+}
+
+/// APRSログ（by_call または pat_ref）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/aprs/log",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [AprsLogView]),
+    ),
+    tag = "activation"
+)]
 async fn show_aprs_log(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
 ) -> AppResult<Json<Vec<AprsLogView>>> {
-    let mut request = FindAprs {
-        reference: None,
-        callsign: None,
-        after: None,
-    };
+    let mut request = aprs_window(&param, None);
 
     if let Some(callsign) = param.by_call {
         request.callsign = Some(AprsCallsign::from(callsign));
-    } else {
-        if let Some(ago) = param.hours_ago {
-            request.after = Some(Utc::now() - Duration::hours(ago));
-        }
-        if let Some(pat) = param.pat_ref {
-            request.reference = Some(pat);
-        }
+    } else if let Some(pat) = param.pat_ref {
+        request.reference = Some(pat);
     }
 
     let result = user_service
         .find_aprs_log(request)
         .await?
         .into_iter()
-        .map(AprsLogView::from)
+        .map(AprsLogView::from_model)
         .collect::<Vec<_>>();
 
     Ok(Json(result))
 }
 
+/// サミットに向かう局のAPRSトラック
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/aprs/track",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = Tracks),
+    ),
+    tag = "activation"
+)]
 async fn show_aprs_track(
     user_service: Inject<AppRegistry, dyn UserService>,
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
@@ -228,13 +565,14 @@ async fn show_aprs_track(
     };
 
     let request = FindAprs {
-        reference: param.pat_ref,
-        callsign: None,
-        after: Some(Utc::now() - Duration::hours(param.hours_ago.unwrap_or(24))),
+        reference: param.pat_ref.clone(),
+        ..aprs_window(&param, Some(24))
     };
 
-    let tracks = user_service.get_aprs_track(request).await?;
-    let tracks = tracks.into_iter().map(Track::from).collect();
+    let tracks = user_service
+        .get_aprs_track(request, param.tolerance)
+        .await?;
+    let tracks = tracks.into_iter().map(Track::from_model).collect();
     let value = Tracks { tracks };
     let value =
         serde_json::to_value(value).map_err(|e| AppError::ConversionEntityError(e.to_string()))?;
@@ -246,16 +584,314 @@ async fn show_aprs_track(
     Ok(Json(value))
 }
 
-pub fn build_activation_routers() -> Router<AppState> {
-    let routers = Router::new()
+/// アクティベーションごとのトラック（by_call または pat_ref）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/aprs/activations",
+    params(GetParam),
+    responses(
+        (status = 200, description = "GeoJSON FeatureCollection", body = Segments),
+    ),
+    tag = "activation"
+)]
+async fn show_aprs_activations(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Segments>> {
+    let mut request = aprs_window(&param, Some(24));
+    if let Some(callsign) = param.by_call {
+        request.callsign = Some(AprsCallsign::from(callsign.to_uppercase()));
+    } else {
+        request.reference = param.pat_ref;
+    }
+
+    let segments = user_service
+        .get_aprs_segments(request, param.tolerance)
+        .await?;
+    Ok(Json(Segments::new(
+        segments.into_iter().map(Segment::from_model).collect(),
+    )))
+}
+
+/// 保存したトラックの一覧
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/aprs/archive/{callsign}",
+    params(("callsign" = String, Path, description = "コールサイン")),
+    responses(
+        (status = 200, description = "GeoJSON FeatureCollection", body = Segments),
+    ),
+    tag = "activation"
+)]
+async fn show_track_archives(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(callsign): Path<String>,
+) -> AppResult<Json<Segments>> {
+    let archives = user_service
+        .find_track_archives(&callsign.to_uppercase())
+        .await?;
+    Ok(Json(Segments::new(
+        archives.into_iter().map(Segment::from_model).collect(),
+    )))
+}
+
+/// 保存したトラックをGPXでダウンロード
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/aprs/archive/{callsign}/{archive_id}",
+    params(
+        ("callsign" = String, Path, description = "コールサイン"),
+        ("archive_id" = i64, Path, description = "トラックID")
+    ),
+    responses(
+        (status = 200, description = "GPXファイル", content_type = "application/gpx+xml", body = String),
+        (status = 404, description = "トラックがない"),
+    ),
+    tag = "activation"
+)]
+async fn download_track_archive(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path((callsign, archive_id)): Path<(String, i64)>,
+) -> AppResult<Response> {
+    let callsign = callsign.to_uppercase();
+    let archive = user_service
+        .find_track_archive(archive_id)
+        .await?
+        .filter(|a| a.segment.callsign.callsign == callsign)
+        .ok_or_else(|| AppError::EntityNotFound(format!("track {} {}", callsign, archive_id)))?;
+
+    let filename = format!("{}_{}.gpx", callsign, archive_id);
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/gpx+xml")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(Body::from(archive_to_gpx(&archive)))
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?
+        .into_response())
+}
+
+/// スポット集計の検索条件（hours_ago時間以内、既定24時間）
+fn activity_query(param: &GetParam) -> AppResult<FindAct> {
+    let mut query = FindActBuilder::default();
+    if let Some(program) = &param.program {
+        query = match parse_program(program)? {
+            AwardProgram::SOTA => query.sota(),
+            _ => query.pota(),
+        };
+    }
+    let hours = param.hours_ago.unwrap_or(ACTIVITY_DEFAULT_HOURS);
+    query = query.issued_after(Utc::now() - Duration::hours(hours));
+    if let Some(pat) = &param.pat_ref {
+        query = query.pattern(pat);
+    }
+    Ok(query.build())
+}
+
+/// バンド・モード・時間帯（UTC）ごとのスポット数（program/hours_ago/pat_ref、geomag=trueでK指数つき）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/stats",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = ActivityStatsView),
+    ),
+    tag = "activation"
+)]
+async fn show_activity_stats(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<ActivityStatsView>> {
+    let stats = user_service.activity_stats(activity_query(&param)?).await?;
+    let mut view = ActivityStatsView::from_model(stats);
+    if param.geomag.unwrap_or(false) {
+        view.k_index = user_service
+            .get_geomagnetic()
+            .await?
+            .map(|g| g.k_index.into_iter().map(|k| k as i32).collect());
+    }
+    Ok(Json(view))
+}
+
+/// スポット集計のSVGヒートマップ（横軸UTCの時、縦軸バンド）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/stats/svg",
+    params(GetParam),
+    responses(
+        (status = 200, description = "SVGヒートマップ", body = String, content_type = "image/svg+xml"),
+    ),
+    tag = "activation"
+)]
+async fn show_activity_chart(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Response> {
+    let stats = user_service.activity_stats(activity_query(&param)?).await?;
+    let geomag = if param.geomag.unwrap_or(false) {
+        user_service.get_geomagnetic().await?
+    } else {
+        None
+    };
+    let svg = generate_activity_svg(&stats, geomag.as_ref(), ACTIVITY_CHART_WIDTH)
+        .map_err(AppError::ConversionEntityError)?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "image/svg+xml")
+        .body(Body::from(svg))
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?
+        .into_response())
+}
+
+/// アラート履行状況（hours_ago時間以内、既定90日）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/reliability/{callsign}",
+    params(("callsign" = String, Path, description = "コールサイン"), GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = ReliabilityView),
+    ),
+    tag = "activation"
+)]
+async fn show_reliability(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(callsign): Path<String>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<ReliabilityView>> {
+    let operator = call_to_operator(&callsign.to_uppercase());
+    let hours = param.hours_ago.unwrap_or(RELIABILITY_DEFAULT_HOURS);
+    let query = FindActBuilder::default()
+        .operator(&operator)
+        .issued_after(Utc::now() - Duration::hours(hours))
+        .build();
+
+    let outcomes = user_service.find_alert_outcomes(query).await?;
+    let summary = OutcomeSummary::from_outcomes(&operator, &outcomes);
+    let recent: Vec<_> = outcomes
+        .into_iter()
+        .rev()
+        .take(RELIABILITY_RECENT)
+        .collect();
+
+    Ok(Json(ReliabilityView::from_model((summary, recent))))
+}
+
+/// 投稿一覧の取得パラメータ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PostQuery {
+    /// 取得件数（既定50、最大500）
+    pub limit: Option<i64>,
+}
+
+/// スポットの投稿
+///
+/// 参照先をローカルのリファレンス一覧で確かめて保存し、設定された中継先へ送る。
+/// 中継に失敗しても保存したスポットは残り、結果は `status` で返す。
+#[utoipa::path(
+    post,
+    path = "/api/v2/activation/spots",
+    request_body = SpotPostRequest,
+    responses(
+        (status = 201, description = "保存成功（中継結果を含む）", body = RelayPostView),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "リファレンス・周波数が不正", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "activation"
+)]
+async fn post_spot(
+    relay_service: Inject<AppRegistry, dyn RelayService>,
+    Extension(user_id): Extension<UserId>,
+    Json(req): Json<SpotPostRequest>,
+) -> AppResult<(StatusCode, Json<RelayPostView>)> {
+    let post = relay_service.post_spot(user_id, build_spot(req)?).await?;
+    Ok((StatusCode::CREATED, Json(post.into_dto())))
+}
+
+/// アラートの投稿
+///
+/// 参照先をローカルのリファレンス一覧で確かめて保存し、設定された中継先へ送る。
+/// 中継に失敗しても保存したアラートは残り、結果は `status` で返す。
+#[utoipa::path(
+    post,
+    path = "/api/v2/activation/alerts",
+    request_body = AlertPostRequest,
+    responses(
+        (status = 201, description = "保存成功（中継結果を含む）", body = RelayPostView),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "リファレンス・時刻が不正", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "activation"
+)]
+async fn post_alert(
+    relay_service: Inject<AppRegistry, dyn RelayService>,
+    Extension(user_id): Extension<UserId>,
+    Json(req): Json<AlertPostRequest>,
+) -> AppResult<(StatusCode, Json<RelayPostView>)> {
+    let post = relay_service.post_alert(user_id, build_alert(req)?).await?;
+    Ok((StatusCode::CREATED, Json(post.into_dto())))
+}
+
+/// 自分の投稿と中継結果（新しい順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/activation/posts",
+    params(PostQuery),
+    responses(
+        (status = 200, description = "取得成功", body = Vec<RelayPostView>),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "activation"
+)]
+async fn list_posts(
+    relay_service: Inject<AppRegistry, dyn RelayService>,
+    Extension(user_id): Extension<UserId>,
+    Query(query): Query<PostQuery>,
+) -> AppResult<Json<Vec<RelayPostView>>> {
+    let limit = query.limit.unwrap_or(POSTS_DEFAULT_LIMIT).clamp(1, 500);
+    let posts = relay_service.find_posts(user_id, limit).await?;
+    Ok(Json(
+        posts.into_iter().map(RelayPostView::from_model).collect(),
+    ))
+}
+
+pub fn build_activation_routers(auth: &FireAuth) -> Router<AppState> {
+    let protected = with_auth(
+        Router::new()
+            .route("/spots", post(post_spot))
+            .route("/alerts", post(post_alert))
+            .route("/posts", get(list_posts)),
+        auth,
+    );
+
+    let public = Router::new()
         .route("/alerts", get(show_all_alerts))
+        .route("/alerts.ics", get(show_alerts_ical))
+        .route("/spots.atom", get(show_spots_atom))
         .route("/alerts/sota", get(show_sota_alerts))
         .route("/alerts/pota", get(show_pota_alerts))
         .route("/spots", get(show_all_spots))
         .route("/spots/sota", get(show_sota_spots))
         .route("/spots/pota", get(show_pota_spots))
         .route("/aprs/log", get(show_aprs_log))
-        .route("/aprs/track", get(show_aprs_track));
+        .route("/aprs/track", get(show_aprs_track))
+        .route("/aprs/activations", get(show_aprs_activations))
+        .route("/aprs/archive/{callsign}", get(show_track_archives))
+        .route(
+            "/aprs/archive/{callsign}/{archive_id}",
+            get(download_track_archive),
+        )
+        .route("/reliability/{callsign}", get(show_reliability))
+        .route("/stats", get(show_activity_stats))
+        .route("/stats/svg", get(show_activity_chart));
+
+    let routers = Router::new().merge(protected).merge(public);
+
     Router::new().nest("/activation", routers)
 }
 
@@ -497,4 +1133,80 @@ mod tests {
             Some(domain::model::AwardProgram::SOTA)
         ));
     }
+
+    // =====================================================
+    // order_groups テスト
+    // =====================================================
+
+    #[test]
+    fn test_order_groups_needed_first() {
+        let needed = |priority: u8| match priority {
+            0 => Some(Needed {
+                new_reference: true,
+                ..Default::default()
+            }),
+            1 => Some(Needed {
+                new_band: Some(true),
+                ..Default::default()
+            }),
+            _ => Some(Needed::default()),
+        };
+        let group = |k: &str| GroupBy::Callsign(Some(k.to_string()));
+        let groups = || {
+            HashMap::from([
+                (group("JA1AAA"), vec![3u8, 3]),
+                (group("JA1BBB"), vec![3, 1]),
+                (group("JA1CCC"), vec![0]),
+            ])
+        };
+
+        let keys = |g: &[(GroupBy, Vec<u8>)]| -> Vec<String> {
+            g.iter()
+                .map(|(k, _)| match k {
+                    GroupBy::Callsign(k) | GroupBy::Reference(k) => k.clone().unwrap(),
+                })
+                .collect()
+        };
+
+        let ordered = order_groups(groups(), false, |p| needed(*p));
+        assert_eq!(keys(&ordered), vec!["JA1AAA", "JA1BBB", "JA1CCC"]);
+
+        let ordered = order_groups(groups(), true, |p| needed(*p));
+        assert_eq!(keys(&ordered), vec!["JA1CCC", "JA1BBB", "JA1AAA"]);
+        assert_eq!(ordered[1].1, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_apply_common_filters_user_id() {
+        let param = GetParam {
+            user_id: Some("user-1".to_string()),
+            sort: Some("needed".to_string()),
+            ..Default::default()
+        };
+
+        let query = apply_common_filters(&param, FindActBuilder::default(), 3).build();
+
+        assert_eq!(query.user_id, Some(UserId::from("user-1".to_string())));
+        assert!(needed_first(&param));
+    }
+
+    #[test]
+    fn test_activity_query() {
+        let param = GetParam {
+            program: Some("POTA".to_string()),
+            pat_ref: Some("^JA-".to_string()),
+            ..Default::default()
+        };
+        let query = activity_query(&param).unwrap();
+        assert_eq!(query.program, Some(domain::model::AwardProgram::POTA));
+        assert_eq!(query.pattern.as_deref(), Some("^JA-"));
+        let after = query.issued_after.unwrap();
+        assert!(after <= Utc::now() - Duration::hours(ACTIVITY_DEFAULT_HOURS - 1));
+
+        let param = GetParam {
+            program: Some("iota".to_string()),
+            ..Default::default()
+        };
+        assert!(activity_query(&param).is_err());
+    }
 }
@@ -1,24 +1,48 @@
 //! 管理コンソールハンドラー
 //!
-//! システム状態の表示とグレースフルリブート機能
+//! システム状態の表示、定期ジョブの管理、カントリーファイルの更新とグレースフルリブート機能
 
 use axum::{
-    extract::State,
+    extract::{Multipart, Path, State},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
 };
+use common::dxcc::{self, CtyDat};
+use common::error::{AppError, AppResult, ErrorResponse};
+use common::jobs::JobStatus;
+use dto::admin::SystemMetrics;
 use firebase_auth_sdk::FireAuth;
 use registry::AppState;
-use serde::Serialize;
 use shaku_axum::Inject;
 use std::time::Instant;
+use utoipa::OpenApi;
 
 use super::auth::with_auth;
+use super::multipart::{extract_text_file, UploadFile};
+use crate::model::dxcc::CtyStatusView;
 use registry::AppRegistry;
 use service::services::AdminService;
 
+/// 管理 API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_metrics,
+        restart_server,
+        list_jobs,
+        run_job,
+        pause_job,
+        resume_job,
+        get_cty_status,
+        upload_cty
+    ),
+    components(schemas(SystemMetrics, JobStatus, CtyStatusView, UploadFile, ErrorResponse)),
+    tags((name = "admin", description = "管理コンソールAPI"))
+)]
+pub struct AdminApi;
+
 /// アプリ起動時刻（グローバル）
 static START_TIME: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
 
@@ -27,22 +51,24 @@ pub fn init_start_time() {
     let _ = START_TIME.get_or_init(Instant::now);
 }
 
-/// システムメトリクス
-#[derive(Debug, Serialize)]
-pub struct SystemMetrics {
-    /// アップタイム（秒）
-    pub uptime_secs: u64,
-    /// メモリ使用量（バイト）
-    pub memory_used_bytes: Option<u64>,
-    /// メモリ使用量（MB）
-    pub memory_used_mb: Option<f64>,
-    /// データベース状態
-    pub db_status: String,
+/// 起動からの経過秒数
+pub(crate) fn uptime_secs() -> u64 {
+    START_TIME.get().map(|t| t.elapsed().as_secs()).unwrap_or(0)
 }
 
 /// メトリクス取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/admin/metrics",
+    responses(
+        (status = 200, description = "取得成功", body = SystemMetrics),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
 async fn get_metrics(admin_service: Inject<AppRegistry, dyn AdminService>) -> impl IntoResponse {
-    let uptime = START_TIME.get().map(|t| t.elapsed().as_secs()).unwrap_or(0);
+    let uptime = uptime_secs();
 
     // メモリ使用量を取得（Linux /proc/self/statm）
     let (memory_bytes, memory_mb) = get_memory_usage();
@@ -64,7 +90,7 @@ async fn get_metrics(admin_service: Inject<AppRegistry, dyn AdminService>) -> im
 }
 
 /// メモリ使用量を取得
-fn get_memory_usage() -> (Option<u64>, Option<f64>) {
+pub(crate) fn get_memory_usage() -> (Option<u64>, Option<f64>) {
     // Linux: /proc/self/statm から RSS を読み取る
     if let Ok(content) = std::fs::read_to_string("/proc/self/statm") {
         let parts: Vec<&str> = content.split_whitespace().collect();
@@ -81,6 +107,16 @@ fn get_memory_usage() -> (Option<u64>, Option<f64>) {
 }
 
 /// グレースフルリブート
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/restart",
+    responses(
+        (status = 200, description = "再起動を開始", body = Object),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
 async fn restart_server(State(state): State<AppState>) -> impl IntoResponse {
     tracing::info!("Admin requested graceful restart");
 
@@ -104,11 +140,153 @@ async fn restart_server(State(state): State<AppState>) -> impl IntoResponse {
     )
 }
 
+/// 定期ジョブ一覧
+#[utoipa::path(
+    get,
+    path = "/api/v2/admin/jobs",
+    responses(
+        (status = 200, description = "取得成功", body = [JobStatus]),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn list_jobs(State(state): State<AppState>) -> Json<Vec<JobStatus>> {
+    Json(state.jobs.list().await)
+}
+
+/// 定期ジョブを即時実行
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/jobs/{name}/run",
+    params(("name" = String, Path, description = "ジョブ名")),
+    responses(
+        (status = 202, description = "実行を受け付けた", body = JobStatus),
+        (status = 404, description = "ジョブがない", body = ErrorResponse),
+        (status = 422, description = "すでに実行中", body = ErrorResponse),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn run_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<(StatusCode, Json<JobStatus>)> {
+    let status = state.jobs.trigger(&name).await?;
+    Ok((StatusCode::ACCEPTED, Json(status)))
+}
+
+/// 定期ジョブを一時停止
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/jobs/{name}/pause",
+    params(("name" = String, Path, description = "ジョブ名")),
+    responses(
+        (status = 200, description = "一時停止した", body = JobStatus),
+        (status = 404, description = "ジョブがない", body = ErrorResponse),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn pause_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<Json<JobStatus>> {
+    Ok(Json(state.jobs.set_paused(&name, true).await?))
+}
+
+/// 定期ジョブを再開
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/jobs/{name}/resume",
+    params(("name" = String, Path, description = "ジョブ名")),
+    responses(
+        (status = 200, description = "再開した", body = JobStatus),
+        (status = 404, description = "ジョブがない", body = ErrorResponse),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn resume_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> AppResult<Json<JobStatus>> {
+    Ok(Json(state.jobs.set_paused(&name, false).await?))
+}
+
+fn cty_status(path: &str) -> CtyStatusView {
+    let cty = dxcc::current();
+    CtyStatusView {
+        loaded: cty.is_some(),
+        entities: cty.as_ref().map_or(0, |c| c.entity_count()),
+        prefixes: cty.as_ref().map_or(0, |c| c.prefix_count()),
+        path: path.to_string(),
+    }
+}
+
+/// カントリーファイル（DXCC判定用）の読み込み状況
+#[utoipa::path(
+    get,
+    path = "/api/v2/admin/cty",
+    responses(
+        (status = 200, description = "取得成功", body = CtyStatusView),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn get_cty_status(State(state): State<AppState>) -> Json<CtyStatusView> {
+    Json(cty_status(&state.config.cty_dat_path))
+}
+
+/// カントリーファイル（cty.dat形式）のアップロード
+///
+/// 読み込めたファイルだけを`CTY_DAT_PATH`に保存し、以後のDXCC判定に使う。
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/cty",
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "カントリーファイル（cty.dat）"),
+    responses(
+        (status = 200, description = "更新成功", body = CtyStatusView),
+        (status = 422, description = "cty.dat形式ではない", body = ErrorResponse),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "admin"
+)]
+async fn upload_cty(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> AppResult<Json<CtyStatusView>> {
+    let text = extract_text_file(&mut multipart).await?;
+    let cty = CtyDat::parse(&text)?;
+
+    let path = std::path::Path::new(&state.config.cty_dat_path);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| AppError::IoError(format!("ディレクトリの作成に失敗: {}", e)))?;
+    }
+    std::fs::write(path, &text)
+        .map_err(|e| AppError::IoError(format!("ファイルの保存に失敗: {}", e)))?;
+
+    tracing::info!("cty.dat updated: {} entities", cty.entity_count());
+    dxcc::install(cty);
+    Ok(Json(cty_status(&state.config.cty_dat_path)))
+}
+
 /// 管理ルーター作成
 pub fn build_admin_routers(auth: &FireAuth) -> Router<AppState> {
     let router = Router::new()
         .route("/metrics", get(get_metrics))
-        .route("/restart", post(restart_server));
+        .route("/restart", post(restart_server))
+        .route("/jobs", get(list_jobs))
+        .route("/jobs/{name}/run", post(run_job))
+        .route("/jobs/{name}/pause", post(pause_job))
+        .route("/jobs/{name}/resume", post(resume_job))
+        .route("/cty", get(get_cty_status).post(upload_cty));
 
     // 認証ミドルウェアを適用
     let protected = with_auth(router, auth);
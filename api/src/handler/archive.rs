@@ -0,0 +1,137 @@
+use axum::{extract::Path, routing::get, Json, Router};
+use shaku_axum::Inject;
+use utoipa::OpenApi;
+
+use common::error::AppResult;
+use registry::{AppRegistry, AppState};
+use service::services::UserService;
+
+use crate::model::{
+    alerts::AlertView,
+    archive::{build_archive_query, ActivatorStatView, ReferenceYearStatView, SpotArchiveView},
+    param::{parse_program, GetParam, ValidatedQuery},
+    FromModel,
+};
+
+/// スポット・アラートのアーカイブAPI
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        show_spot_archive,
+        show_alert_archive,
+        show_reference_year_stats,
+        show_activator_stats
+    ),
+    components(schemas(SpotArchiveView, ReferenceYearStatView, ActivatorStatView)),
+    tags((name = "archive", description = "期限切れスポット・アラートの履歴検索・集計API"))
+)]
+pub struct ArchiveApi;
+
+/// アーカイブ済みスポット（by_call/by_ref/association/band/mode/after/before/yearで絞り込み）
+#[utoipa::path(
+    get,
+    path = "/api/v2/archive/{program}/spots",
+    params(("program" = String, Path, description = "sota または pota"), GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [SpotArchiveView]),
+    ),
+    tag = "archive"
+)]
+async fn show_spot_archive(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(program): Path<String>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<SpotArchiveView>>> {
+    let query = build_archive_query(parse_program(&program)?, &param);
+    let spots = user_service.find_spot_archive(query).await?;
+    Ok(Json(
+        spots.into_iter().map(SpotArchiveView::from_model).collect(),
+    ))
+}
+
+/// アーカイブ済みアラート
+#[utoipa::path(
+    get,
+    path = "/api/v2/archive/{program}/alerts",
+    params(("program" = String, Path, description = "sota または pota"), GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [AlertView]),
+    ),
+    tag = "archive"
+)]
+async fn show_alert_archive(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(program): Path<String>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<AlertView>>> {
+    let query = build_archive_query(parse_program(&program)?, &param);
+    let alerts = user_service.find_alert_archive(query).await?;
+    Ok(Json(
+        alerts
+            .into_iter()
+            .map(|a| AlertView::from_model(a.alert))
+            .collect(),
+    ))
+}
+
+/// リファレンスごと・年ごとのアクティベーション数
+#[utoipa::path(
+    get,
+    path = "/api/v2/archive/{program}/stats/references",
+    params(("program" = String, Path, description = "sota または pota"), GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ReferenceYearStatView]),
+    ),
+    tag = "archive"
+)]
+async fn show_reference_year_stats(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(program): Path<String>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<ReferenceYearStatView>>> {
+    let query = build_archive_query(parse_program(&program)?, &param);
+    let stats = user_service.reference_year_stats(query).await?;
+    Ok(Json(
+        stats
+            .into_iter()
+            .map(ReferenceYearStatView::from_model)
+            .collect(),
+    ))
+}
+
+/// アクティベーターのランキング（associationで協会ごと）
+#[utoipa::path(
+    get,
+    path = "/api/v2/archive/{program}/stats/activators",
+    params(("program" = String, Path, description = "sota または pota"), GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivatorStatView]),
+    ),
+    tag = "archive"
+)]
+async fn show_activator_stats(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(program): Path<String>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<ActivatorStatView>>> {
+    let query = build_archive_query(parse_program(&program)?, &param);
+    let stats = user_service.activator_stats(query).await?;
+    Ok(Json(
+        stats
+            .into_iter()
+            .map(ActivatorStatView::from_model)
+            .collect(),
+    ))
+}
+
+pub fn build_archive_routers() -> Router<AppState> {
+    let routers = Router::new()
+        .route("/{program}/spots", get(show_spot_archive))
+        .route("/{program}/alerts", get(show_alert_archive))
+        .route(
+            "/{program}/stats/references",
+            get(show_reference_year_stats),
+        )
+        .route("/{program}/stats/activators", get(show_activator_stats));
+    Router::new().nest("/archive", routers)
+}
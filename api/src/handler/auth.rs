@@ -7,11 +7,21 @@ use axum::response::IntoResponse;
 use axum::Json;
 use axum::{routing::post, Router};
 use firebase_auth_sdk::FireAuth;
+use utoipa::OpenApi;
 
 use crate::model::auth::AuthRequest;
 use domain::model::id::UserId;
 use registry::AppState;
 
+/// 認証 API
+#[derive(OpenApi)]
+#[openapi(
+    paths(sign_in),
+    components(schemas(AuthRequest)),
+    tags((name = "auth", description = "認証API"))
+)]
+pub struct AuthApi;
+
 /// 認証ミドルウェアをルーターに適用
 pub fn with_auth<S: Clone + Send + Sync + 'static>(
     router: Router<S>,
@@ -43,6 +53,17 @@ pub async fn auth_middle(
     Ok(next.run(req).await)
 }
 
+/// メールアドレスとパスワードでサインインし、AuthorizationヘッダーでIDトークンを返す
+#[utoipa::path(
+    post,
+    path = "/api/v2/auth/signin",
+    request_body = AuthRequest,
+    responses(
+        (status = 200, description = "サインイン成功（AuthorizationヘッダーにIDトークン）", body = String),
+        (status = 401, description = "認証失敗", body = String),
+    ),
+    tag = "auth"
+)]
 pub async fn sign_in(
     State(auth_service): State<FireAuth>,
     Json(creds_request): Json<AuthRequest>,
@@ -14,11 +14,28 @@ use firebase_auth_sdk::FireAuth;
 use registry::AppState;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use utoipa::{OpenApi, ToSchema};
 
 use super::auth::with_auth;
 
+/// アワード証明書 管理API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_template_status, upload_template, get_config, update_config),
+    components(schemas(
+        TemplateType,
+        TemplateStatus,
+        TemplateUpload,
+        UpdateConfigRequest,
+        TemplateConfigUpdate,
+        AwardTemplateConfig
+    )),
+    tags((name = "award_admin", description = "アワード証明書テンプレート管理API"))
+)]
+pub struct AwardAdminApi;
+
 /// テンプレートの種類
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TemplateType {
     Activator,
@@ -35,7 +52,7 @@ impl TemplateType {
 }
 
 /// テンプレートステータスレスポンス
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateStatus {
     pub activator_available: bool,
@@ -43,7 +60,7 @@ pub struct TemplateStatus {
 }
 
 /// 設定更新リクエスト
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateConfigRequest {
     pub activator: Option<TemplateConfigUpdate>,
@@ -52,7 +69,7 @@ pub struct UpdateConfigRequest {
 }
 
 /// 単一テンプレートの設定更新
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TemplateConfigUpdate {
     pub callsign_x: Option<f32>,
@@ -72,6 +89,15 @@ pub struct TemplateConfigUpdate {
     pub issue_date_centered: Option<bool>,
 }
 
+/// テンプレート画像アップロード（OpenAPI用）
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct TemplateUpload {
+    /// JPG/PNG画像
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
+
 /// テンプレート画像が存在するかチェック（JPG/PNG対応）
 fn template_exists(template_dir: &std::path::Path, base_name: &str) -> bool {
     for ext in &["png", "jpg", "jpeg"] {
@@ -83,6 +109,16 @@ fn template_exists(template_dir: &std::path::Path, base_name: &str) -> bool {
 }
 
 /// テンプレートのステータス取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/admin/award/templates/status",
+    responses(
+        (status = 200, description = "取得成功", body = TemplateStatus),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "award_admin"
+)]
 async fn get_template_status(State(state): State<AppState>) -> Json<TemplateStatus> {
     let template_dir = PathBuf::from(&state.config.award_template_dir);
 
@@ -93,6 +129,17 @@ async fn get_template_status(State(state): State<AppState>) -> Json<TemplateStat
 }
 
 /// 設定取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/admin/award/config",
+    responses(
+        (status = 200, description = "取得成功", body = AwardTemplateConfig),
+        (status = 500, description = "設定の読み込みに失敗", body = Object),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "award_admin"
+)]
 async fn get_config(State(state): State<AppState>) -> impl IntoResponse {
     let config_path = PathBuf::from(&state.config.award_config_path);
 
@@ -109,6 +156,18 @@ async fn get_config(State(state): State<AppState>) -> impl IntoResponse {
 }
 
 /// 設定更新
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/award/config",
+    request_body = UpdateConfigRequest,
+    responses(
+        (status = 200, description = "更新後の設定", body = AwardTemplateConfig),
+        (status = 500, description = "設定の読み書きに失敗", body = Object),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "award_admin"
+)]
 async fn update_config(
     State(state): State<AppState>,
     Json(req): Json<UpdateConfigRequest>,
@@ -226,6 +285,19 @@ fn detect_image_format(data: &[u8]) -> Option<&'static str> {
 }
 
 /// テンプレートアップロード（JPG/PNG画像）
+#[utoipa::path(
+    post,
+    path = "/api/v2/admin/award/templates/{template_type}",
+    params(("template_type" = TemplateType, Path, description = "テンプレートの種類")),
+    request_body(content = TemplateUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "アップロード成功", body = Object),
+        (status = 400, description = "画像がない、または形式が不正", body = Object),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "award_admin"
+)]
 async fn upload_template(
     State(state): State<AppState>,
     Path(template_type): Path<TemplateType>,
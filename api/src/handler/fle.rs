@@ -12,6 +12,16 @@ use axum::{
 use registry::AppState;
 use serde::{Deserialize, Serialize};
 use service::implement::fle::{compile_fle, generate_fle_output, FleCompileResult};
+use utoipa::{OpenApi, ToSchema};
+
+/// FLE API
+#[derive(OpenApi)]
+#[openapi(
+    paths(compile_handler, generate_handler),
+    components(schemas(FleCompileFormRequest, FleCompileResponse, FleGenerateForm)),
+    tags((name = "fle", description = "Fast Log Entry変換API"))
+)]
+pub struct FleApi;
 
 /// FLEルーターを作成
 pub fn fle_router() -> Router<AppState> {
@@ -21,7 +31,7 @@ pub fn fle_router() -> Router<AppState> {
 }
 
 /// FLEコンパイルリクエスト (フォーム形式 - フロントエンド互換)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct FleCompileFormRequest {
     /// コマンド (interp) - 互換性のため受け取るが使用しない
     #[serde(default)]
@@ -31,8 +41,16 @@ struct FleCompileFormRequest {
     arg: String,
 }
 
+/// FLE生成フォーム（OpenAPI用、`text`/`file`でも可）
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct FleGenerateForm {
+    /// FLE形式テキスト
+    edittext: String,
+}
+
 /// FLEコンパイルレスポンス
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct FleCompileResponse {
     status: String,
     #[serde(rename = "logtype")]
@@ -136,6 +154,15 @@ impl From<FleCompileResult> for FleCompileResponse {
 /// FLEコンパイルハンドラー (JSON応答)
 ///
 /// FLE形式テキストをパースし、QSOレコードとステータスを返す
+#[utoipa::path(
+    post,
+    path = "/api/v2/fle/compile",
+    request_body(content = FleCompileFormRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "コンパイル結果（エラー時もstatusで返す）", body = FleCompileResponse),
+    ),
+    tag = "fle"
+)]
 async fn compile_handler(Form(req): Form<FleCompileFormRequest>) -> Response {
     let result = compile_fle(&req.arg);
     let response: FleCompileResponse = result.into();
@@ -146,6 +173,16 @@ async fn compile_handler(Form(req): Form<FleCompileFormRequest>) -> Response {
 /// FLE生成ハンドラー (ZIP応答)
 ///
 /// FLE形式テキストからSOTA CSV/POTA ADIF/HAMLOG CSV等を生成しZIPで返す
+#[utoipa::path(
+    post,
+    path = "/api/v2/fle/generate",
+    request_body(content = FleGenerateForm, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "生成したログ一式のZIP", body = Vec<u8>, content_type = "application/zip"),
+        (status = 400, description = "テキストがない", body = Object),
+    ),
+    tag = "fle"
+)]
 async fn generate_handler(mut multipart: Multipart) -> Response {
     let mut text_content: Option<String> = None;
 
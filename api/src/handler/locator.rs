@@ -1,15 +1,18 @@
 use axum::{
-    extract::Multipart,
+    extract::{Multipart, Query},
+    http::StatusCode,
     routing::{get, post},
     Json, Router,
 };
 use firebase_auth_sdk::FireAuth;
 use shaku_axum::Inject;
+use utoipa::OpenApi;
 
-use crate::model::import::ImportResult;
+use crate::model::import::{import_response, ImportParam, ImportReportView, ImportResult};
 use crate::model::{
     locator::{CenturyCodeView, MapcodeView},
     param::{GetParam, ValidatedQuery},
+    IntoDto,
 };
 use common::error::AppResult;
 use common::utils::maidenhead;
@@ -18,29 +21,85 @@ use service::model::locator::UploadMuniCSV;
 use service::services::{AdminService, UserService};
 
 use super::auth::with_auth;
-use super::multipart::extract_text_file;
+use super::multipart::{extract_text_file, UploadFile};
 
+/// ロケーター API（認証不要）
+#[derive(OpenApi)]
+#[openapi(
+    paths(find_century_code, find_map_code),
+    components(schemas(CenturyCodeView, MapcodeView)),
+    tags((name = "locator", description = "JCC/JCG・グリッドロケーター・マップコードAPI"))
+)]
+pub struct LocatorApi;
+
+/// ロケーター 管理API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(import_muni_csv),
+    components(schemas(ImportResult, ImportReportView, UploadFile)),
+    tags((name = "locator", description = "JCC/JCG・グリッドロケーター・マップコードAPI"))
+)]
+pub struct LocatorAdminApi;
+
+/// 市区町村コード・JCC/JCGコード表のインポート
+#[utoipa::path(
+    post,
+    path = "/api/v2/locator/jcc-jcg/import",
+    params(ImportParam),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "市区町村・JCC/JCGコード表（CSV）"),
+    responses(
+        (status = 200, description = "インポート成功（dry_runでは検証結果）", body = ImportResult),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "検証エラーのためインポートしなかった", body = ImportResult),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "locator"
+)]
 async fn import_muni_csv(
     admin_service: Inject<AppRegistry, dyn AdminService>,
+    Query(param): Query<ImportParam>,
     mut multipart: Multipart,
-) -> AppResult<Json<ImportResult>> {
+) -> AppResult<(StatusCode, Json<ImportResult>)> {
     let data = extract_text_file(&mut multipart).await?;
     let reqs = UploadMuniCSV { data };
-    let count = admin_service.import_muni_century_list(reqs).await?;
-    Ok(Json(ImportResult::success(count as u32, 0)))
+    let report = admin_service
+        .import_muni_century_list(reqs, param.dry_run)
+        .await?;
+    Ok(import_response(report))
 }
 
+/// 市区町村コード（muni_code）からJCC/JCGコード、lon/latからグリッドロケーター
+#[utoipa::path(
+    get,
+    path = "/api/v2/locator/jcc-jcg",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = CenturyCodeView),
+        (status = 404, description = "市区町村コードがない"),
+    ),
+    tag = "locator"
+)]
 async fn find_century_code(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
 ) -> AppResult<Json<CenturyCodeView>> {
     let muni_code: i32 = param.muni_code.unwrap_or_default();
     let (lon, lat) = (param.lon.unwrap_or_default(), param.lat.unwrap_or_default());
-    let mut res: CenturyCodeView = user_service.find_century_code(muni_code).await?.into();
+    let mut res: CenturyCodeView = user_service.find_century_code(muni_code).await?.into_dto();
     res.maidenhead = maidenhead(lon, lat);
     Ok(Json(res))
 }
 
+/// lon/latのマップコード
+#[utoipa::path(
+    get,
+    path = "/api/v2/locator/mapcode",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = MapcodeView),
+    ),
+    tag = "locator"
+)]
 async fn find_map_code(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -15,6 +15,16 @@ use service::implement::logconv::{
     convert_to_adif, convert_to_sota_activator, create_zip, decode_adif, decode_auto, parse_adif,
     ConversionOptions, ConversionResult, QsoRecord,
 };
+use utoipa::{OpenApi, ToSchema};
+
+/// ログ変換 API
+#[derive(OpenApi)]
+#[openapi(
+    paths(hamlog_handler, pota_handler),
+    components(schemas(LogconvForm, LogconvResponse)),
+    tags((name = "logconv", description = "HAMLOG/ADIFログ変換API"))
+)]
+pub struct LogconvApi;
 
 /// Logconvルーターを作成
 pub fn logconv_router() -> Router<AppState> {
@@ -79,8 +89,19 @@ impl From<ConversionOptionsRequest> for ConversionOptions {
     }
 }
 
+/// 変換フォーム（OpenAPI用）
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct LogconvForm {
+    /// ログファイル
+    #[schema(value_type = String, format = Binary)]
+    file: Vec<u8>,
+    /// 変換オプション（JSON文字列、`arg`でも可）
+    options: String,
+}
+
 /// レスポンス
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 struct LogconvResponse {
     status: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -94,6 +115,19 @@ struct LogconvResponse {
 }
 
 /// HAMLOGフォーマット変換ハンドラー
+#[utoipa::path(
+    post,
+    path = "/api/v2/logconv/hamlog",
+    request_body(content = LogconvForm, content_type = "multipart/form-data", description = "HAMLOG CSVと変換オプション"),
+    responses(
+        (status = 200, description = "変換結果のZIP（対象リファレンスがない場合はJSON）", content(
+            (Vec<u8> = "application/zip"),
+            (LogconvResponse = "application/json"),
+        )),
+        (status = 400, description = "入力不正", body = LogconvResponse),
+    ),
+    tag = "logconv"
+)]
 async fn hamlog_handler(mut multipart: Multipart) -> Response {
     let mut file_content: Option<Vec<u8>> = None;
     let mut options_json: Option<String> = None;
@@ -316,6 +350,16 @@ async fn hamlog_handler(mut multipart: Multipart) -> Response {
 }
 
 /// POTA ADIF変換ハンドラー (JSON応答版)
+#[utoipa::path(
+    post,
+    path = "/api/v2/logconv/pota",
+    request_body(content = LogconvForm, content_type = "multipart/form-data", description = "HAMLOG CSVまたはADIFと変換オプション"),
+    responses(
+        (status = 200, description = "変換結果（ADIFファイル内容を含む）", body = Object),
+        (status = 400, description = "入力不正", body = LogconvResponse),
+    ),
+    tag = "logconv"
+)]
 async fn pota_handler(mut multipart: Multipart) -> Response {
     let mut file_content: Option<Vec<u8>> = None;
     let mut options_json: Option<String> = None;
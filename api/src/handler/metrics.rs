@@ -0,0 +1,130 @@
+//! Prometheus メトリクスハンドラー
+//!
+//! ルートごとのリクエスト計測ミドルウェアと `/metrics` エンドポイント
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use shaku_axum::Inject;
+use std::time::Instant;
+use utoipa::OpenApi;
+
+use common::metrics::{
+    self, DATABASE_UP, GEOMAG_INDEX_AGE_SECONDS, HTTP_REQUESTS_TOTAL,
+    HTTP_REQUEST_DURATION_SECONDS, MEMORY_RSS_BYTES, UPTIME_SECONDS,
+};
+use registry::{AppRegistry, AppState};
+use service::services::{AdminService, UserService};
+
+use super::admin::{get_memory_usage, uptime_secs};
+
+/// メトリクス API
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_metrics),
+    tags((name = "metrics", description = "Prometheus メトリクス"))
+)]
+pub struct MetricsApi;
+
+/// リクエスト数と処理時間をルート単位で記録するミドルウェア
+pub async fn track_metrics(req: Request, next: Next) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = req.method().to_string();
+    let start = Instant::now();
+
+    let response = next.run(req).await;
+
+    let status = response.status().as_u16().to_string();
+    metrics::inc_counter(
+        HTTP_REQUESTS_TOTAL,
+        &[("method", &method), ("route", &route), ("status", &status)],
+    );
+    metrics::observe(
+        HTTP_REQUEST_DURATION_SECONDS,
+        &[("method", &method), ("route", &route)],
+        start.elapsed().as_secs_f64(),
+    );
+    response
+}
+
+/// Prometheus テキスト形式でメトリクスを出力
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    operation_id = "prometheus_metrics",
+    responses(
+        (status = 200, description = "Prometheus テキスト形式", body = String, content_type = "text/plain"),
+    ),
+    tag = "metrics"
+)]
+async fn get_metrics(
+    admin_service: Inject<AppRegistry, dyn AdminService>,
+    user_service: Inject<AppRegistry, dyn UserService>,
+) -> impl IntoResponse {
+    metrics::set_gauge(UPTIME_SECONDS, &[], uptime_secs() as f64);
+
+    if let (Some(bytes), _) = get_memory_usage() {
+        metrics::set_gauge(MEMORY_RSS_BYTES, &[], bytes as f64);
+    }
+
+    let db_up = admin_service.health_check().await.unwrap_or(false);
+    metrics::set_gauge(DATABASE_UP, &[], if db_up { 1.0 } else { 0.0 });
+
+    if let Ok(Some(geomag)) = user_service.get_geomagnetic().await {
+        if let Some(start) = geomag.date.and_hms_opt(0, 0, 0) {
+            let age = Utc::now().naive_utc() - start;
+            metrics::set_gauge(GEOMAG_INDEX_AGE_SECONDS, &[], age.num_seconds() as f64);
+        }
+    }
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::render(),
+    )
+}
+
+/// メトリクスルーター作成
+pub fn build_metrics_routers() -> Router<AppState> {
+    Router::new().route("/metrics", get(get_metrics))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use axum::middleware;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_track_metrics_records_matched_route() {
+        let app = Router::new()
+            .route("/spots/{id}", get(|| async { StatusCode::OK }))
+            .route_layer(middleware::from_fn(track_metrics));
+
+        let request = Request::builder()
+            .uri("/spots/42")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let text = metrics::render();
+        assert!(text.contains(
+            "sotaapp_http_requests_total{method=\"GET\",route=\"/spots/{id}\",status=\"200\"}"
+        ));
+        assert!(text.contains(
+            "sotaapp_http_request_duration_seconds_count{method=\"GET\",route=\"/spots/{id}\"}"
+        ));
+    }
+}
@@ -1,15 +1,19 @@
 pub mod activation;
 pub mod admin;
+pub mod archive;
 pub mod auth;
 pub mod award_admin;
 pub mod fle;
 pub mod health;
 pub mod locator;
 pub mod logconv;
+pub mod metrics;
 pub mod multipart;
 pub mod pota;
 pub mod propagation;
 pub mod search;
+pub mod session;
 pub mod sota;
 pub mod v2;
+pub mod watch;
 pub mod wspr;
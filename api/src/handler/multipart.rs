@@ -2,6 +2,15 @@
 
 use axum::extract::Multipart;
 use common::error::{AppError, AppResult};
+use utoipa::ToSchema;
+
+/// アップロードするファイル（OpenAPI用、最初のフィールドを読む）
+#[derive(ToSchema)]
+#[allow(dead_code)]
+pub struct UploadFile {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+}
 
 /// マルチパートからUTF-8文字列データを抽出する
 pub async fn extract_text_file(multipart: &mut Multipart) -> AppResult<String> {
@@ -6,27 +6,32 @@ use axum::{
 };
 // Note: Query is still used for log_migrate which has different param type
 use chrono::{Duration, Utc};
-use common::error::{AppError, AppResult};
+use common::error::{AppError, AppResult, ErrorResponse};
 use fastrand;
 use firebase_auth_sdk::FireAuth;
 use serde_json::{json, Value};
 use shaku_axum::Inject;
 use std::str::FromStr;
+use utoipa::OpenApi;
 
-use crate::model::import::ImportResult;
+use crate::model::changelog::{ReferenceChangeView, CHANGES_DEFAULT_DAYS};
+use crate::model::import::{import_response, ImportParam, ImportReportView, ImportResult};
 use crate::model::pota::{
-    PagenatedResponse, PotaLogHistView, PotaLogStatView, PotaRefLogView, PotaRefView,
-    UpdateRefRequest,
+    build_references, PagenatedResponse, PotaLogHistView, PotaLogStatView, PotaRefLogView,
+    PotaRefView, UpdateRefRequest,
 };
 use crate::model::{
     activation::ActivationView,
     alerts::AlertView,
     param::{build_findref_query, GetParam, ValidatedQuery},
+    solar::{sun_view, SunParam, SunView},
     spots::SpotView,
+    FromModel, IntoDto,
 };
 use domain::model::{
     event::{DeleteRef, FindActBuilder, FindRefBuilder},
     id::LogId,
+    AwardProgram,
 };
 use domain::{
     model::pota::ParkCode, repository::minikvs::KvsRepositry, repository::pota::PotaRepository,
@@ -36,28 +41,120 @@ use service::model::pota::{UploadPOTALog, UploadPOTAReference};
 use service::services::{AdminService, PotaLogService, UserService};
 
 use super::auth::with_auth;
-use super::multipart::extract_text_file;
-
+use super::multipart::{extract_text_file, UploadFile};
+
+/// POTA API（認証不要）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        upload_pota_log,
+        get_pota_logid,
+        delete_pota_log,
+        reqeust_shareid,
+        obtain_shareid,
+        log_stat,
+        show_pota_spots,
+        show_pota_alerts,
+        show_park_changes,
+        show_all_pota_reference,
+        find_pota_reference,
+        show_pota_reference
+    ),
+    components(schemas(
+        PotaRefView,
+        SunView,
+        PotaRefLogView,
+        PotaLogHistView,
+        PotaLogStatView,
+        crate::model::pota::PotaLogStatEntView,
+        ReferenceChangeView,
+        SpotView,
+        AlertView,
+        UploadFile,
+    )),
+    tags((name = "pota", description = "POTA パーク・ログ・スポット・アラートAPI"))
+)]
+pub struct PotaApi;
+
+/// POTA 管理API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        import_pota_reference_ja,
+        update_pota_reference,
+        delete_pota_reference,
+        log_migrate
+    ),
+    components(schemas(UpdateRefRequest, ImportResult, ImportReportView, UploadFile)),
+    tags((name = "pota", description = "POTA パーク・ログ・スポット・アラートAPI"))
+)]
+pub struct PotaAdminApi;
+
+/// パークの更新
+#[utoipa::path(
+    put,
+    path = "/api/v2/pota/parks/{park_code}",
+    params(("park_code" = String, Path, description = "パークコード（例: JA-0001）")),
+    request_body = UpdateRefRequest,
+    responses(
+        (status = 201, description = "更新成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "pota"
+)]
 async fn update_pota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Json(req): Json<UpdateRefRequest>,
 ) -> AppResult<StatusCode> {
     admin_service
-        .update_pota_reference(req.into())
+        .update_pota_reference(build_references(req))
         .await
         .map(|_| StatusCode::CREATED)
 }
 
+/// 日本のパークリストのインポート
+#[utoipa::path(
+    post,
+    path = "/api/v2/pota/import",
+    params(ImportParam),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "POTA/WWFFパークリスト（CSV）"),
+    responses(
+        (status = 200, description = "インポート成功（dry_runでは検証結果）", body = ImportResult),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "検証エラーのためインポートしなかった", body = ImportResult),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "pota"
+)]
 async fn import_pota_reference_ja(
     admin_service: Inject<AppRegistry, dyn AdminService>,
+    Query(param): Query<ImportParam>,
     mut multipart: Multipart,
-) -> AppResult<Json<ImportResult>> {
+) -> AppResult<(StatusCode, Json<ImportResult>)> {
     let data = extract_text_file(&mut multipart).await?;
     let reqs = UploadPOTAReference { data };
-    let count = admin_service.import_pota_park_list_ja(reqs).await?;
-    Ok(Json(ImportResult::success(count as u32, 0)))
+    let report = admin_service
+        .import_pota_park_list_ja(reqs, param.dry_run)
+        .await?;
+    Ok(import_response(report))
 }
 
+/// POTAログ（アクティベータ/ハンター）のアップロード
+#[utoipa::path(
+    post,
+    path = "/api/v2/pota/log/{act_id}/{hntr_id}",
+    params(
+        ("act_id" = String, Path, description = "アクティベータログID"),
+        ("hntr_id" = String, Path, description = "ハンターログID")
+    ),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "POTA公式サイトのログCSV"),
+    responses(
+        (status = 200, description = "アップロード成功", body = PotaLogHistView),
+        (status = 422, description = "ログを読み込めない"),
+    ),
+    tag = "pota"
+)]
 async fn upload_pota_log(
     pota_log_service: Inject<AppRegistry, dyn PotaLogService>,
     Path((activator_logid, hunter_logid)): Path<(String, String)>,
@@ -70,18 +167,39 @@ async fn upload_pota_log(
         data,
     };
     let loguser = pota_log_service.upload_pota_log(reqs).await?;
-    Ok(Json(loguser.into()))
+    Ok(Json(loguser.into_dto()))
 }
 
+/// ログIDの情報
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/log/{log_id}",
+    params(("log_id" = String, Path, description = "ログID（UUID）")),
+    responses(
+        (status = 200, description = "取得成功", body = PotaLogHistView),
+        (status = 404, description = "ログがない"),
+    ),
+    tag = "pota"
+)]
 async fn get_pota_logid(
     pota_log_service: Inject<AppRegistry, dyn PotaLogService>,
     Path(log_id): Path<String>,
 ) -> AppResult<Json<PotaLogHistView>> {
     let log_id = LogId::from_str(&log_id)?;
     let loguser = pota_log_service.find_logid(log_id).await?;
-    Ok(Json(loguser.into()))
+    Ok(Json(loguser.into_dto()))
 }
 
+/// POTAログの削除
+#[utoipa::path(
+    delete,
+    path = "/api/v2/pota/log/{log_id}",
+    params(("log_id" = String, Path, description = "ログID（UUID）")),
+    responses(
+        (status = 200, description = "削除成功"),
+    ),
+    tag = "pota"
+)]
 async fn delete_pota_log(
     pota_log_service: Inject<AppRegistry, dyn PotaLogService>,
     Path(log_id): Path<String>,
@@ -94,6 +212,18 @@ async fn delete_pota_log(
         .map(|_| StatusCode::OK)
 }
 
+/// パークの削除
+#[utoipa::path(
+    delete,
+    path = "/api/v2/pota/parks/{park_code}",
+    params(("park_code" = String, Path, description = "パークコード（例: JA-0001）")),
+    responses(
+        (status = 200, description = "削除成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "pota"
+)]
 async fn delete_pota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Path(park_code): Path<String>,
@@ -106,9 +236,22 @@ async fn delete_pota_reference(
         .map(|_| StatusCode::OK)
 }
 
+/// パークの取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/parks/{park_code}",
+    params(("park_code" = String, Path, description = "パークコード（例: JA-0001）"), SunParam),
+    responses(
+        (status = 200, description = "取得成功（日の出・日の入りを含む）", body = PotaRefView),
+        (status = 404, description = "パークがない"),
+        (status = 422, description = "日付が不正", body = ErrorResponse),
+    ),
+    tag = "pota"
+)]
 async fn show_pota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Path(park_code): Path<String>,
+    Query(param): Query<SunParam>,
 ) -> AppResult<Json<PotaRefView>> {
     let query = FindRefBuilder::default()
         .pota()
@@ -116,10 +259,22 @@ async fn show_pota_reference(
         .build();
 
     let result = admin_service.show_pota_reference(query).await?;
+    let mut view = PotaRefView::from_model(result);
+    view.sun = Some(sun_view(&param, view.latitude, view.longitude)?);
 
-    Ok(Json(result.into()))
+    Ok(Json(view))
 }
 
+/// パーク一覧（limit/offsetでページング）
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/parks",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = PagenatedResponse<PotaRefView>),
+    ),
+    tag = "pota"
+)]
 async fn show_all_pota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -136,9 +291,20 @@ async fn show_all_pota_reference(
         .show_all_pota_references(query.build())
         .await?;
 
-    Ok(Json(result.into()))
+    Ok(Json(result.into_dto()))
 }
 
+/// パーク検索（log_id指定でハンターログの交信数つき）
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/parks/search",
+    params(GetParam),
+    responses(
+        (status = 200, description = "検索成功", body = [PotaRefLogView]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "pota"
+)]
 async fn find_pota_reference(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -154,12 +320,23 @@ async fn find_pota_reference(
         .pota
         .unwrap_or(vec![])
         .into_iter()
-        .map(PotaRefLogView::from)
+        .map(PotaRefLogView::from_model)
         .collect();
 
     Ok(Json(res))
 }
 
+/// POTAスポット（hours_ago時間以内）
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/spots",
+    operation_id = "pota_spots",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<SpotView>]),
+    ),
+    tag = "pota"
+)]
 async fn show_pota_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -176,13 +353,27 @@ async fn show_pota_spots(
     let spots: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(SpotView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(SpotView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
 
     Ok(Json(spots))
 }
 
+/// POTAアラート（hours_ago時間以内）
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/alerts",
+    operation_id = "pota_alerts",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<AlertView>]),
+    ),
+    tag = "pota"
+)]
 async fn show_pota_alerts(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -199,13 +390,29 @@ async fn show_pota_alerts(
     let alerts: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(AlertView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(AlertView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
 
     Ok(Json(alerts))
 }
 
+/// ログ共有ID（4桁、30分有効）の発行
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/log-share/{act_id}/{hntr_id}",
+    params(
+        ("act_id" = String, Path, description = "アクティベータログID"),
+        ("hntr_id" = String, Path, description = "ハンターログID")
+    ),
+    responses(
+        (status = 200, description = "share_id, activator_logid, hunter_logid", body = Object),
+    ),
+    tag = "pota"
+)]
 async fn reqeust_shareid(
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
     Path((act_id, hntr_id)): Path<(String, String)>,
@@ -229,6 +436,17 @@ async fn reqeust_shareid(
     Ok(Json(value))
 }
 
+/// ログ共有IDからログIDを取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/log-share/{share_id}",
+    params(("share_id" = String, Path, description = "ログ共有ID")),
+    responses(
+        (status = 200, description = "share_id, activator_logid, hunter_logid", body = Object),
+        (status = 404, description = "無効なshare_id"),
+    ),
+    tag = "pota"
+)]
 async fn obtain_shareid(
     kvs_repo: Inject<AppRegistry, dyn KvsRepositry>,
     Path(share_id): Path<String>,
@@ -240,13 +458,35 @@ async fn obtain_shareid(
     }
 }
 
+/// アップロードされたログの統計
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/log-stat",
+    responses(
+        (status = 200, description = "取得成功", body = PotaLogStatView),
+    ),
+    tag = "pota"
+)]
 async fn log_stat(
     pota_repo: Inject<AppRegistry, dyn PotaRepository>,
 ) -> AppResult<Json<PotaLogStatView>> {
     let stat = pota_repo.log_statistics().await?;
-    Ok(Json(stat.into()))
+    Ok(Json(stat.into_dto()))
 }
 
+/// 旧システムのログDBの移行
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/log-migrate",
+    params(GetParam),
+    responses(
+        (status = 200, description = "移行成功"),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "nameが指定されていない"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "pota"
+)]
 async fn log_migrate(
     pota_repo: Inject<AppRegistry, dyn PotaRepository>,
     Query(param): Query<GetParam>,
@@ -260,6 +500,34 @@ async fn log_migrate(
     Ok(StatusCode::NOT_FOUND)
 }
 
+/// パークリスト更新による変更履歴（since未指定なら直近30日）
+#[utoipa::path(
+    get,
+    path = "/api/v2/pota/changes",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ReferenceChangeView]),
+    ),
+    tag = "pota"
+)]
+async fn show_park_changes(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<ReferenceChangeView>>> {
+    let since = param
+        .since
+        .unwrap_or_else(|| Utc::now() - Duration::days(CHANGES_DEFAULT_DAYS));
+    let changes = user_service
+        .find_reference_changes(AwardProgram::POTA, since)
+        .await?;
+    Ok(Json(
+        changes
+            .into_iter()
+            .map(ReferenceChangeView::from_model)
+            .collect(),
+    ))
+}
+
 pub fn build_pota_routers(auth: &FireAuth) -> Router<AppState> {
     let protected = with_auth(
         Router::new()
@@ -279,6 +547,7 @@ pub fn build_pota_routers(auth: &FireAuth) -> Router<AppState> {
         .route("/log-stat", get(log_stat))
         .route("/spots", get(show_pota_spots))
         .route("/alerts", get(show_pota_alerts))
+        .route("/changes", get(show_park_changes))
         .route("/parks", get(show_all_pota_reference))
         .route("/parks/search", get(find_pota_reference))
         .route("/parks/{park_code}", get(show_pota_reference));
@@ -1,22 +1,85 @@
-use axum::{routing::get, Json, Router};
+use axum::{extract::Query, routing::get, Json, Router};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
 use shaku_axum::Inject;
+use utoipa::{IntoParams, OpenApi};
 
 use crate::model::geomag::GeomagView;
-use common::error::{AppError, AppResult};
+use crate::model::IntoDto;
+use common::error::{AppError, AppResult, ErrorResponse};
 use registry::{AppRegistry, AppState};
+use service::implement::solar::grayline_geojson;
 use service::services::UserService;
 
+/// 伝搬 API
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_geomag, get_grayline),
+    components(schemas(GeomagView, ErrorResponse)),
+    tags((name = "propagation", description = "地磁気指数・グレイラインAPI"))
+)]
+pub struct PropagationApi;
+
+/// 最新の地磁気指数（A/K指数）
+#[utoipa::path(
+    get,
+    path = "/api/v2/propagation/geomag",
+    responses(
+        (status = 200, description = "取得成功", body = GeomagView),
+        (status = 404, description = "地磁気データがまだない"),
+    ),
+    tag = "propagation"
+)]
 async fn get_geomag(
     user_service: Inject<AppRegistry, dyn UserService>,
 ) -> AppResult<Json<GeomagView>> {
     let result = user_service.get_geomagnetic().await?;
     if let Some(result) = result {
-        return Ok(Json(result.into()));
+        return Ok(Json(result.into_dto()));
     }
     Err(AppError::EntityNotFound("GeoMag Error".to_string()))
 }
 
+/// グレイラインの時刻パラメータ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct GraylineQuery {
+    /// 時刻（RFC3339、省略時は現在）
+    pub time: Option<String>,
+}
+
+/// 昼夜の境界（グレイライン）をGeoJSONで返す
+///
+/// ターミネーター（LineString）、夜側の領域（Polygon）、太陽直下点（Point）のFeatureCollection。
+#[utoipa::path(
+    get,
+    path = "/api/v2/propagation/grayline",
+    params(GraylineQuery),
+    responses(
+        (status = 200, description = "GeoJSON FeatureCollection", body = Object),
+        (status = 422, description = "時刻が不正", body = ErrorResponse),
+    ),
+    tag = "propagation"
+)]
+async fn get_grayline(Query(query): Query<GraylineQuery>) -> AppResult<Json<serde_json::Value>> {
+    let time = match query
+        .time
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        Some(t) => DateTime::parse_from_rfc3339(t)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|_| {
+                AppError::UnprocessableEntity(format!("timeはRFC3339で指定してください: {}", t))
+            })?,
+        None => Utc::now(),
+    };
+    Ok(Json(grayline_geojson(time)))
+}
+
 pub fn build_propagation_routers() -> Router<AppState> {
-    let routers = Router::new().route("/geomag", get(get_geomag));
+    let routers = Router::new()
+        .route("/geomag", get(get_geomag))
+        .route("/grayline", get(get_grayline));
     Router::new().nest("/propagation", routers)
 }
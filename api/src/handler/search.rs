@@ -1,24 +1,43 @@
-use axum::{routing::get, Json, Router};
+use axum::{
+    extract::Path,
+    routing::{get, post},
+    Json, Router,
+};
 use shaku_axum::Inject;
 use utoipa::OpenApi;
 
+use crate::model::dxcc::DxccView;
 use crate::model::param::{build_findref_query, GetParam, ValidatedQuery};
-use crate::model::search::{SearchBriefResponse, SearchFullResponse, SearchResponse};
-use common::error::AppResult;
+use crate::model::search::{
+    RouteSearchResponse, SearchBriefResponse, SearchFullResponse, SearchResponse,
+};
+use crate::model::IntoDto;
+use common::dxcc;
+use common::error::{AppError, AppResult};
 use domain::model::event::{FindRefBuilder, FindResult};
+use domain::model::route::RouteCorridor;
 use registry::{AppRegistry, AppState};
 use service::services::UserService;
 
 /// Search API
 #[derive(OpenApi)]
 #[openapi(
-    paths(search_reference, search_reference_full, search_reference_breif),
+    paths(
+        search_reference,
+        search_reference_full,
+        search_reference_breif,
+        search_route,
+        search_dxcc
+    ),
     components(schemas(
         GetParam,
         SearchResponse,
         SearchFullResponse,
         SearchBriefResponse,
+        RouteSearchResponse,
+        DxccView,
         crate::model::search::SearchBriefData,
+        crate::model::search::RouteCandidate,
         crate::model::sota::SotaSearchView,
         crate::model::sota::SotaRefView,
         crate::model::pota::PotaSearchView,
@@ -57,7 +76,7 @@ async fn search_reference(
     ValidatedQuery(param): ValidatedQuery<GetParam>,
 ) -> AppResult<Json<SearchResponse>> {
     let results = search(user_service, param).await?;
-    Ok(Json(results.into()))
+    Ok(Json(results.into_dto()))
 }
 
 /// SOTA/POTAリファレンス検索（詳細）
@@ -76,7 +95,7 @@ async fn search_reference_full(
     ValidatedQuery(param): ValidatedQuery<GetParam>,
 ) -> AppResult<Json<SearchFullResponse>> {
     let results = search(user_service, param).await?;
-    Ok(Json(results.into()))
+    Ok(Json(results.into_dto()))
 }
 
 /// SOTA/POTAリファレンス検索（簡易）
@@ -106,17 +125,70 @@ async fn search_reference_breif(
     };
 
     if count < maxcount {
-        res = search(user_service, param).await?.into();
+        res = search(user_service, param).await?.into_dto();
     }
 
     Ok(Json(res))
 }
 
+/// 経路沿いのSOTA/POTAリファレンス検索
+///
+/// 本文にGPX（トラックまたはルート）かGeoJSON（LineString）を送る。
+/// 経路からwidth km以内のリファレンスを、始点からの経路沿い距離の順に返す。
+#[utoipa::path(
+    post,
+    path = "/api/v2/search/route",
+    params(GetParam),
+    request_body(content = String, description = "GPXまたはGeoJSON"),
+    responses(
+        (status = 200, description = "検索成功", body = RouteSearchResponse),
+        (status = 400, description = "無効なパラメータ"),
+        (status = 422, description = "経路を読み込めない"),
+    ),
+    tag = "search"
+)]
+async fn search_route(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+    body: String,
+) -> AppResult<Json<RouteSearchResponse>> {
+    let width = param.width.unwrap_or(1.0) * 1000.0;
+    let route = RouteCorridor::parse(&body, width)?;
+
+    let query = FindRefBuilder::default().sota().pota().route(route);
+    let mut query = build_findref_query(param, query)?;
+    query.limit = query.limit.map_or(Some(500), |v| Some(v.min(500)));
+
+    let hits = user_service.find_references_along_route(query).await?;
+    Ok(Json(hits.into_dto()))
+}
+
+/// コールサインのDXCCエンティティ・ゾーン
+///
+/// 管理画面からアップロードしたカントリーファイル（cty.dat）で判定する。
+#[utoipa::path(
+    get,
+    path = "/api/v2/search/dxcc/{callsign}",
+    params(("callsign" = String, Path, description = "コールサイン（JA1ABC/3、KH0/JA1ABCなども可）")),
+    responses(
+        (status = 200, description = "判定成功", body = DxccView),
+        (status = 404, description = "判定できない、またはカントリーファイル未登録"),
+    ),
+    tag = "search"
+)]
+async fn search_dxcc(Path(callsign): Path<String>) -> AppResult<Json<DxccView>> {
+    dxcc::lookup(&callsign)
+        .map(|d| Json(d.into_dto()))
+        .ok_or_else(|| AppError::EntityNotFound(format!("DXCC entity for {}", callsign)))
+}
+
 pub fn build_search_routers() -> Router<AppState> {
     let routers = Router::new()
         .route("/", get(search_reference))
         .route("/full", get(search_reference_full))
-        .route("/brief", get(search_reference_breif));
+        .route("/brief", get(search_reference_breif))
+        .route("/route", post(search_route))
+        .route("/dxcc/{callsign}", get(search_dxcc));
 
     Router::new().nest("/search", routers)
 }
@@ -0,0 +1,276 @@
+//! アクティベーションセッションハンドラー
+//!
+//! 運用中のQSOの逐次登録と、終了時に作るログファイルのダウンロード
+
+use axum::{
+    body::Body,
+    extract::Path,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use firebase_auth_sdk::FireAuth;
+use shaku_axum::Inject;
+use utoipa::OpenApi;
+
+use common::error::{AppError, AppResult, ErrorResponse};
+use domain::model::id::UserId;
+use registry::{AppRegistry, AppState};
+use service::services::SessionService;
+
+use super::auth::with_auth;
+use crate::model::session::{
+    build_qsos, build_session, ClientQsoRequest, QsoPostRequest, QsoPostView, SessionQsoView,
+    SessionRequest, SessionView,
+};
+use crate::model::{FromModel, IntoDto};
+
+/// アクティベーションセッション API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        list_sessions,
+        open_session,
+        show_session,
+        delete_session,
+        post_qsos,
+        list_qsos,
+        close_session,
+        download_file
+    ),
+    components(schemas(
+        SessionRequest,
+        ClientQsoRequest,
+        QsoPostRequest,
+        SessionView,
+        QsoPostView,
+        SessionQsoView,
+        ErrorResponse
+    )),
+    tags((name = "session", description = "アクティベーションセッションAPI"))
+)]
+pub struct SessionApi;
+
+/// セッションの一覧（新しい順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/session",
+    responses(
+        (status = 200, description = "取得成功", body = Vec<SessionView>),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn list_sessions(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+) -> AppResult<Json<Vec<SessionView>>> {
+    let sessions = session_service.find_sessions(user_id).await?;
+    Ok(Json(
+        sessions.into_iter().map(SessionView::from_model).collect(),
+    ))
+}
+
+/// セッションの開始
+#[utoipa::path(
+    post,
+    path = "/api/v2/session",
+    request_body = SessionRequest,
+    responses(
+        (status = 201, description = "開始", body = SessionView),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "プログラム・リファレンスが不正", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn open_session(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Json(req): Json<SessionRequest>,
+) -> AppResult<(StatusCode, Json<SessionView>)> {
+    let state = session_service
+        .open_session(build_session(req, user_id)?)
+        .await?;
+    Ok((StatusCode::CREATED, Json(state.into_dto())))
+}
+
+/// セッションと成立状況
+#[utoipa::path(
+    get,
+    path = "/api/v2/session/{session_id}",
+    params(("session_id" = i64, Path, description = "セッションID")),
+    responses(
+        (status = 200, description = "取得成功", body = SessionView),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "セッションがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn show_session(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path(session_id): Path<i64>,
+) -> AppResult<Json<SessionView>> {
+    let state = session_service.find_session(user_id, session_id).await?;
+    Ok(Json(state.into_dto()))
+}
+
+/// セッションの削除（QSO・ログファイルも削除する）
+#[utoipa::path(
+    delete,
+    path = "/api/v2/session/{session_id}",
+    params(("session_id" = i64, Path, description = "セッションID")),
+    responses(
+        (status = 200, description = "削除成功"),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "セッションがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn delete_session(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path(session_id): Path<i64>,
+) -> AppResult<StatusCode> {
+    session_service
+        .delete_session(user_id, session_id)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// QSOの登録
+///
+/// 同じclientIdのQSOは1件として扱うので、通信が切れたときはそのまま再送してよい。
+/// 終了したセッションには登録済みのQSOの再送だけを受け付ける。
+#[utoipa::path(
+    post,
+    path = "/api/v2/session/{session_id}/qsos",
+    params(("session_id" = i64, Path, description = "セッションID")),
+    request_body = QsoPostRequest,
+    responses(
+        (status = 200, description = "登録成功", body = QsoPostView),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "セッションがない", body = ErrorResponse),
+        (status = 422, description = "QSOが不正・セッションが終了済み", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn post_qsos(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path(session_id): Path<i64>,
+    Json(req): Json<QsoPostRequest>,
+) -> AppResult<Json<QsoPostView>> {
+    let report = session_service
+        .add_qsos(user_id, session_id, build_qsos(req)?)
+        .await?;
+    Ok(Json(report.into_dto()))
+}
+
+/// 登録済みのQSO（交信時刻順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/session/{session_id}/qsos",
+    params(("session_id" = i64, Path, description = "セッションID")),
+    responses(
+        (status = 200, description = "取得成功", body = Vec<SessionQsoView>),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "セッションがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn list_qsos(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path(session_id): Path<i64>,
+) -> AppResult<Json<Vec<SessionQsoView>>> {
+    let qsos = session_service.find_qsos(user_id, session_id).await?;
+    Ok(Json(
+        qsos.into_iter().map(SessionQsoView::from_model).collect(),
+    ))
+}
+
+/// セッションの終了（ログファイルを作る。終了後に呼ぶと作り直す）
+#[utoipa::path(
+    post,
+    path = "/api/v2/session/{session_id}/close",
+    params(("session_id" = i64, Path, description = "セッションID")),
+    responses(
+        (status = 200, description = "終了", body = SessionView),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "セッションがない", body = ErrorResponse),
+        (status = 422, description = "QSOがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn close_session(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path(session_id): Path<i64>,
+) -> AppResult<Json<SessionView>> {
+    let state = session_service.close_session(user_id, session_id).await?;
+    Ok(Json(state.into_dto()))
+}
+
+fn content_type(filename: &str) -> &'static str {
+    match filename.rsplit('.').next() {
+        Some("zip") => "application/zip",
+        Some("csv") => "text/csv",
+        _ => "text/plain",
+    }
+}
+
+/// ログファイルのダウンロード
+#[utoipa::path(
+    get,
+    path = "/api/v2/session/{session_id}/files/{filename}",
+    params(
+        ("session_id" = i64, Path, description = "セッションID"),
+        ("filename" = String, Path, description = "ファイル名")
+    ),
+    responses(
+        (status = 200, description = "ログファイル", content_type = "application/octet-stream", body = String),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "ファイルがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "session"
+)]
+async fn download_file(
+    session_service: Inject<AppRegistry, dyn SessionService>,
+    Extension(user_id): Extension<UserId>,
+    Path((session_id, filename)): Path<(i64, String)>,
+) -> AppResult<Response> {
+    let file = session_service
+        .find_file(user_id, session_id, filename)
+        .await?;
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type(&file.filename))
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file.filename),
+        )
+        .body(Body::from(file.content))
+        .map_err(|e| AppError::ConversionEntityError(e.to_string()))?
+        .into_response())
+}
+
+pub fn build_session_routers(auth: &FireAuth) -> Router<AppState> {
+    let router = Router::new()
+        .route("/", get(list_sessions).post(open_session))
+        .route("/{session_id}", get(show_session).delete(delete_session))
+        .route("/{session_id}/qsos", get(list_qsos).post(post_qsos))
+        .route("/{session_id}/close", post(close_session))
+        .route("/{session_id}/files/{filename}", get(download_file));
+
+    Router::new().nest("/session", with_auth(router, auth))
+}
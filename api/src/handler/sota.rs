@@ -9,17 +9,21 @@ use axum::{
 use chrono::{Duration, Utc};
 use firebase_auth_sdk::FireAuth;
 use shaku_axum::Inject;
+use utoipa::{IntoParams, OpenApi};
 
 use common::award_config::AwardTemplateConfig;
-use common::error::AppResult;
+use common::error::{AppError, AppResult, ErrorResponse};
 use domain::model::sota::SummitCode;
 use domain::model::{
     event::{DeleteRef, FindActBuilder, FindRefBuilder},
     id::UserId,
+    AwardProgram,
 };
 use registry::{AppRegistry, AppState};
 use service::implement::award_pdf::{AwardPdfGenerator, AwardType, CertificateInfo};
-use service::model::sota::{UploadSOTALog, UploadSOTASummit, UploadSOTASummitOpt};
+use service::model::sota::{
+    UploadActivationZones, UploadSOTALog, UploadSOTASummit, UploadSOTASummitOpt,
+};
 use service::services::{AdminService, SotaLogService, UserService};
 use std::path::PathBuf;
 
@@ -27,38 +31,134 @@ use crate::model::award::{
     ActivatorAwardResult, AwardJudgmentResult, ChaserAwardResult, JudgmentMode, LogType,
     SummitActivation, SummitChase,
 };
-use crate::model::import::ImportResult;
-use crate::model::sota::{PagenatedResponse, SotaRefView, UpdateRefRequest};
+use crate::model::changelog::{ReferenceChangeView, CHANGES_DEFAULT_DAYS};
+use crate::model::import::{import_response, ImportParam, ImportReportView, ImportResult};
+use crate::model::sota::{build_references, PagenatedResponse, SotaRefView, UpdateRefRequest};
 use crate::model::{
     activation::ActivationView,
     alerts::AlertView,
     param::{build_findref_query, GetParam, ValidatedQuery},
+    solar::{sun_view, SunParam, SunView},
     spots::SpotView,
+    FromModel, IntoDto,
 };
 
 use super::auth::with_auth;
-use super::multipart::extract_text_file;
-
+use super::multipart::{extract_text_file, UploadFile};
+
+/// SOTA API（認証不要）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        show_sota_spots,
+        show_sota_alerts,
+        show_summit_changes,
+        show_all_sota_reference,
+        show_sota_reference,
+        show_activation_zone,
+        search_sota_reference,
+        judge_10th_anniversary_award,
+        generate_award_pdf
+    ),
+    components(schemas(
+        SotaRefView,
+        SunView,
+        ReferenceChangeView,
+        SpotView,
+        AlertView,
+        crate::model::spots::NeededView,
+        AwardJudgmentResult,
+        ActivatorAwardResult,
+        ChaserAwardResult,
+        SummitActivation,
+        SummitChase,
+        LogType,
+        JudgmentMode,
+    )),
+    tags((name = "sota", description = "SOTA サミット・スポット・アラート・アワードAPI"))
+)]
+pub struct SotaApi;
+
+/// SOTA 管理API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        import_summit_list,
+        import_sota_opt_reference,
+        import_activation_zones,
+        update_summit_list,
+        upload_log,
+        delete_log,
+        update_sota_reference,
+        delete_sota_reference
+    ),
+    components(schemas(UpdateRefRequest, ImportResult, ImportReportView, UploadFile)),
+    tags((name = "sota", description = "SOTA サミット・スポット・アラート・アワードAPI"))
+)]
+pub struct SotaAdminApi;
+
+/// サミットの更新
+#[utoipa::path(
+    put,
+    path = "/api/v2/sota/summits/{summit_code}",
+    params(("summit_code" = String, Path, description = "サミットコード（例: JA/TK-001）")),
+    request_body = UpdateRefRequest,
+    responses(
+        (status = 201, description = "更新成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn update_sota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Json(req): Json<UpdateRefRequest>,
 ) -> AppResult<StatusCode> {
     admin_service
-        .update_sota_reference(req.into())
+        .update_sota_reference(build_references(req))
         .await
         .map(|_| StatusCode::CREATED)
 }
 
+/// サミットリストのインポート
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/import",
+    params(ImportParam),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "SOTAサミットリスト（CSV）"),
+    responses(
+        (status = 200, description = "インポート成功（dry_runでは検証結果）", body = ImportResult),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "検証エラーのためインポートしなかった", body = ImportResult),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn import_summit_list(
     admin_service: Inject<AppRegistry, dyn AdminService>,
+    Query(param): Query<ImportParam>,
     mut multipart: Multipart,
-) -> AppResult<Json<ImportResult>> {
+) -> AppResult<(StatusCode, Json<ImportResult>)> {
     let data = extract_text_file(&mut multipart).await?;
     let reqs = UploadSOTASummit { data };
-    let count = admin_service.import_summit_list(reqs).await?;
-    Ok(Json(ImportResult::success(count as u32, 0)))
+    let report = admin_service
+        .import_summit_list(reqs, param.dry_run)
+        .await?;
+    Ok(import_response(report))
 }
 
+/// サミットリストの差分更新
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/update",
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "SOTAサミットリスト（CSV）"),
+    responses(
+        (status = 200, description = "更新成功", body = ImportResult),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn update_summit_list(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     mut multipart: Multipart,
@@ -69,16 +169,67 @@ async fn update_summit_list(
     Ok(Json(ImportResult::success(count as u32, 0)))
 }
 
+/// 日本のサミットの補足情報（日本語名など）のインポート
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/import/ja",
+    params(ImportParam),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "サミット補足情報（CSV）"),
+    responses(
+        (status = 200, description = "インポート成功（dry_runでは検証結果）", body = ImportResult),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "検証エラーのためインポートしなかった", body = ImportResult),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn import_sota_opt_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
+    Query(param): Query<ImportParam>,
     mut multipart: Multipart,
-) -> AppResult<Json<ImportResult>> {
+) -> AppResult<(StatusCode, Json<ImportResult>)> {
     let data = extract_text_file(&mut multipart).await?;
     let reqs = UploadSOTASummitOpt { data };
-    let count = admin_service.import_summit_opt_list(reqs).await?;
+    let report = admin_service
+        .import_summit_opt_list(reqs, param.dry_run)
+        .await?;
+    Ok(import_response(report))
+}
+
+/// アクティベーションゾーンのインポート
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/zones/import",
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "properties.summit_codeを持つGeoJSON FeatureCollection"),
+    responses(
+        (status = 200, description = "インポート成功", body = ImportResult),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
+async fn import_activation_zones(
+    admin_service: Inject<AppRegistry, dyn AdminService>,
+    mut multipart: Multipart,
+) -> AppResult<Json<ImportResult>> {
+    let data = extract_text_file(&mut multipart).await?;
+    let reqs = UploadActivationZones { data };
+    let count = admin_service.import_activation_zones(reqs).await?;
     Ok(Json(ImportResult::success(count as u32, 0)))
 }
 
+/// SOTAログのアップロード
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/log",
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "SOTA CSVログ"),
+    responses(
+        (status = 201, description = "アップロード成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn upload_log(
     sota_log_service: Inject<AppRegistry, dyn SotaLogService>,
     Extension(user_id): Extension<UserId>,
@@ -92,6 +243,17 @@ async fn upload_log(
         .map(|_| StatusCode::CREATED)
 }
 
+/// SOTAログの削除
+#[utoipa::path(
+    delete,
+    path = "/api/v2/sota/log",
+    responses(
+        (status = 200, description = "削除成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn delete_log(
     sota_log_service: Inject<AppRegistry, dyn SotaLogService>,
     Extension(user_id): Extension<UserId>,
@@ -102,6 +264,18 @@ async fn delete_log(
         .map(|_| StatusCode::OK)
 }
 
+/// サミットの削除
+#[utoipa::path(
+    delete,
+    path = "/api/v2/sota/summits/{summit_code}",
+    params(("summit_code" = String, Path, description = "サミットコード（例: JA/TK-001）")),
+    responses(
+        (status = 200, description = "削除成功"),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "sota"
+)]
 async fn delete_sota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Path(summit_code): Path<String>,
@@ -113,18 +287,97 @@ async fn delete_sota_reference(
         .map(|_| StatusCode::OK)
 }
 
+/// サミットの取得
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/summits/{summit_code}",
+    params(("summit_code" = String, Path, description = "サミットコード（例: JA/TK-001）"), SunParam),
+    responses(
+        (status = 200, description = "取得成功（日の出・日の入りを含む）", body = SotaRefView),
+        (status = 404, description = "サミットがない"),
+        (status = 422, description = "日付が不正", body = ErrorResponse),
+    ),
+    tag = "sota"
+)]
 async fn show_sota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     Path(summit_code): Path<String>,
+    Query(param): Query<SunParam>,
 ) -> AppResult<Json<SotaRefView>> {
     let query = FindRefBuilder::default()
         .sota()
         .sota_code(summit_code)
         .build();
     let result = admin_service.show_sota_reference(query).await?;
-    Ok(Json(result.into()))
+    let mut view = SotaRefView::from_model(result);
+    view.sun = Some(sun_view(&param, view.latitude, view.longitude)?);
+    Ok(Json(view))
+}
+
+/// アクティベーションゾーンをGeoJSON Featureで返す
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/summits/{summit_code}/zone",
+    params(("summit_code" = String, Path, description = "サミットコード（例: JA/TK-001）")),
+    responses(
+        (status = 200, description = "GeoJSON Feature", body = Object),
+        (status = 404, description = "ゾーンが登録されていない"),
+    ),
+    tag = "sota"
+)]
+async fn show_activation_zone(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    Path(summit_code): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let zone = user_service
+        .find_activation_zone(&summit_code)
+        .await?
+        .ok_or_else(|| AppError::EntityNotFound(format!("activation zone {}", summit_code)))?;
+    Ok(Json(serde_json::json!({
+        "type": "Feature",
+        "properties": { "summit_code": zone.summit_code },
+        "geometry": zone.geometry(),
+    })))
 }
 
+/// サミットリスト更新による変更履歴（since未指定なら直近30日）
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/changes",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ReferenceChangeView]),
+    ),
+    tag = "sota"
+)]
+async fn show_summit_changes(
+    user_service: Inject<AppRegistry, dyn UserService>,
+    ValidatedQuery(param): ValidatedQuery<GetParam>,
+) -> AppResult<Json<Vec<ReferenceChangeView>>> {
+    let since = param
+        .since
+        .unwrap_or_else(|| Utc::now() - Duration::days(CHANGES_DEFAULT_DAYS));
+    let changes = user_service
+        .find_reference_changes(AwardProgram::SOTA, since)
+        .await?;
+    Ok(Json(
+        changes
+            .into_iter()
+            .map(ReferenceChangeView::from_model)
+            .collect(),
+    ))
+}
+
+/// サミット一覧（limit/offsetでページング）
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/summits",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = PagenatedResponse<SotaRefView>),
+    ),
+    tag = "sota"
+)]
 async fn show_all_sota_reference(
     admin_service: Inject<AppRegistry, dyn AdminService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -139,9 +392,20 @@ async fn show_all_sota_reference(
     let result = admin_service
         .show_all_sota_references(query.build())
         .await?;
-    Ok(Json(result.into()))
+    Ok(Json(result.into_dto()))
 }
 
+/// サミット検索
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/summits/search",
+    params(GetParam),
+    responses(
+        (status = 200, description = "検索成功", body = [SotaRefView]),
+        (status = 400, description = "無効なパラメータ"),
+    ),
+    tag = "sota"
+)]
 async fn search_sota_reference(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -157,11 +421,22 @@ async fn search_sota_reference(
         .sota
         .unwrap_or(vec![])
         .into_iter()
-        .map(SotaRefView::from)
+        .map(SotaRefView::from_model)
         .collect();
     Ok(Json(res))
 }
 
+/// SOTAスポット（hours_ago時間以内）
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/spots",
+    operation_id = "sota_spots",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<SpotView>]),
+    ),
+    tag = "sota"
+)]
 async fn show_sota_spots(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -175,12 +450,26 @@ async fn show_sota_spots(
     let spots: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(SpotView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(SpotView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
     Ok(Json(spots))
 }
 
+/// SOTAアラート（hours_ago時間以内）
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/alerts",
+    operation_id = "sota_alerts",
+    params(GetParam),
+    responses(
+        (status = 200, description = "取得成功", body = [ActivationView<AlertView>]),
+    ),
+    tag = "sota"
+)]
 async fn show_sota_alerts(
     user_service: Inject<AppRegistry, dyn UserService>,
     ValidatedQuery(param): ValidatedQuery<GetParam>,
@@ -194,14 +483,18 @@ async fn show_sota_alerts(
     let alerts: Vec<_> = result
         .into_iter()
         .map(|(k, v)| {
-            ActivationView::from((k, v.into_iter().map(AlertView::from).collect::<Vec<_>>()))
+            ActivationView::from_model((
+                k,
+                v.into_iter().map(AlertView::from_model).collect::<Vec<_>>(),
+            ))
         })
         .collect();
     Ok(Json(alerts))
 }
 
 /// アワード判定クエリパラメータ
-#[derive(Debug, serde::Deserialize, Default)]
+#[derive(Debug, serde::Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AwardJudgeQuery {
     /// 判定モード: strict（デフォルト）または lenient
     #[serde(default)]
@@ -210,6 +503,17 @@ pub struct AwardJudgeQuery {
 
 /// SOTA日本支部設立10周年記念アワード判定エンドポイント
 /// CSVをアップロードしてin-memoryで判定、結果を返す（DBに保存しない）
+#[utoipa::path(
+    post,
+    path = "/api/v2/sota/award/10th-anniversary/judge",
+    params(AwardJudgeQuery),
+    request_body(content = UploadFile, content_type = "multipart/form-data", description = "SOTA CSVログ"),
+    responses(
+        (status = 200, description = "判定結果", body = AwardJudgmentResult),
+        (status = 422, description = "ログを読み込めない"),
+    ),
+    tag = "sota"
+)]
 async fn judge_10th_anniversary_award(
     State(state): State<AppState>,
     sota_log_service: Inject<AppRegistry, dyn SotaLogService>,
@@ -231,7 +535,9 @@ async fn judge_10th_anniversary_award(
     let data = extract_text_file(&mut multipart).await?;
 
     // in-memoryで判定（モード指定）
-    let result = sota_log_service.judge_10th_anniversary_award(&data, service_mode)?;
+    let result = sota_log_service
+        .judge_10th_anniversary_award(&data, service_mode)
+        .await?;
 
     // サービス層のログタイプをAPI層の型に変換
     let log_type = match result.log_type {
@@ -272,8 +578,11 @@ async fn judge_10th_anniversary_award(
                     summit_code: s.summit_code,
                     unique_stations: s.unique_stations,
                     qualified: s.qualified,
+                    points: s.points,
                 })
                 .collect(),
+            s2s_summits: a.s2s_summits,
+            s2s_points: a.s2s_points,
         }),
         chaser: result.chaser.map(|c| ChaserAwardResult {
             achieved: c.achieved,
@@ -284,6 +593,7 @@ async fn judge_10th_anniversary_award(
                     summit_code: s.summit_code,
                     unique_activators: s.unique_activators,
                     activators: s.activators,
+                    points: s.points,
                 })
                 .collect(),
         }),
@@ -295,7 +605,8 @@ async fn judge_10th_anniversary_award(
 }
 
 /// PDF証明書生成リクエスト
-#[derive(Debug, serde::Deserialize)]
+#[derive(Debug, serde::Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GeneratePdfQuery {
     /// アワード種別: activator または chaser
     pub award_type: String,
@@ -310,6 +621,17 @@ pub struct GeneratePdfQuery {
 }
 
 /// PDF証明書生成エンドポイント
+#[utoipa::path(
+    get,
+    path = "/api/v2/sota/award/10th-anniversary/certificate",
+    params(GeneratePdfQuery),
+    responses(
+        (status = 200, description = "PDF証明書", content_type = "application/pdf"),
+        (status = 400, description = "無効なアワード種別"),
+        (status = 404, description = "テンプレートが設定されていない"),
+    ),
+    tag = "sota"
+)]
 async fn generate_award_pdf(
     State(state): State<AppState>,
     Query(query): Query<GeneratePdfQuery>,
@@ -426,6 +748,7 @@ pub fn build_sota_routers(auth: &FireAuth) -> Router<AppState> {
         Router::new()
             .route("/import", post(import_summit_list))
             .route("/import/ja", post(import_sota_opt_reference))
+            .route("/zones/import", post(import_activation_zones))
             .route("/log", post(upload_log))
             .route("/log", delete(delete_log))
             .route("/update", post(update_summit_list))
@@ -437,8 +760,10 @@ pub fn build_sota_routers(auth: &FireAuth) -> Router<AppState> {
     let public = Router::new()
         .route("/spots", get(show_sota_spots))
         .route("/alerts", get(show_sota_alerts))
+        .route("/changes", get(show_summit_changes))
         .route("/summits", get(show_all_sota_reference))
         .route("/summits/{summit_code}", get(show_sota_reference))
+        .route("/summits/{summit_code}/zone", get(show_activation_zone))
         .route("/summits/search", get(search_sota_reference))
         .route(
             "/award/10th-anniversary/judge",
@@ -1,13 +1,16 @@
 use axum::extract::DefaultBodyLimit;
+use axum::middleware;
 use axum::Router;
 use firebase_auth_sdk::FireAuth;
 use registry::AppState;
 
 use super::{
-    activation::build_activation_routers, admin::build_admin_routers, auth::build_auth_routers,
+    activation::build_activation_routers, admin::build_admin_routers,
+    archive::build_archive_routers, auth::build_auth_routers,
     award_admin::build_award_admin_routers, fle::fle_router, health::build_health_chek_routers,
-    locator::build_locator_routers, logconv::logconv_router, pota::build_pota_routers,
-    propagation::build_propagation_routers, search::build_search_routers, sota::build_sota_routers,
+    locator::build_locator_routers, logconv::logconv_router, metrics::track_metrics,
+    pota::build_pota_routers, propagation::build_propagation_routers, search::build_search_routers,
+    session::build_session_routers, sota::build_sota_routers, watch::build_watch_routers,
     wspr::wspr_router,
 };
 
@@ -19,13 +22,17 @@ pub fn routes(auth: FireAuth) -> Router<AppState> {
         .merge(build_locator_routers(&auth))
         .merge(build_propagation_routers())
         .merge(build_search_routers())
-        .merge(build_activation_routers())
+        .merge(build_activation_routers(&auth))
+        .merge(build_archive_routers())
         .merge(build_auth_routers(&auth))
         .merge(build_admin_routers(&auth))
         .merge(build_award_admin_routers(&auth))
+        .merge(build_watch_routers(&auth))
+        .merge(build_session_routers(&auth))
         .nest("/wspr", wspr_router())
         .nest("/logconv", logconv_router())
         .nest("/fle", fle_router())
+        .route_layer(middleware::from_fn(track_metrics))
         .layer(DefaultBodyLimit::max(1024 * 1024 * 64));
 
     Router::new().nest("/api/v2", router)
@@ -0,0 +1,180 @@
+//! ウォッチリストハンドラー
+//!
+//! 利用者ごとのウォッチリストの登録・更新・削除とWebhook配信記録の参照
+
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    routing::{get, put},
+    Extension, Json, Router,
+};
+use firebase_auth_sdk::FireAuth;
+use serde::Deserialize;
+use shaku_axum::Inject;
+use utoipa::{IntoParams, OpenApi};
+
+use common::error::{AppResult, ErrorResponse};
+use domain::model::id::UserId;
+use registry::{AppRegistry, AppState};
+use service::services::WatchService;
+
+use super::auth::with_auth;
+use crate::model::watch::{
+    build_watch, WatchRequest, WatchView, WebhookDeliveryView, DELIVERIES_DEFAULT_LIMIT,
+};
+use crate::model::{FromModel, IntoDto};
+
+/// ウォッチリスト API（要認証）
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_watches, create_watch, update_watch, delete_watch, list_deliveries),
+    components(schemas(WatchRequest, WatchView, WebhookDeliveryView, ErrorResponse)),
+    tags((name = "watch", description = "ウォッチリストとWebhook通知API"))
+)]
+pub struct WatchApi;
+
+/// 配信記録の取得パラメータ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DeliveryQuery {
+    /// 取得件数（既定100、最大1000）
+    pub limit: Option<i64>,
+}
+
+/// ウォッチリストの一覧
+#[utoipa::path(
+    get,
+    path = "/api/v2/watch",
+    responses(
+        (status = 200, description = "取得成功", body = Vec<WatchView>),
+        (status = 401, description = "未認証"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "watch"
+)]
+async fn list_watches(
+    watch_service: Inject<AppRegistry, dyn WatchService>,
+    Extension(user_id): Extension<UserId>,
+) -> AppResult<Json<Vec<WatchView>>> {
+    let watches = watch_service.find_watches(user_id).await?;
+    Ok(Json(
+        watches.into_iter().map(WatchView::from_model).collect(),
+    ))
+}
+
+/// ウォッチリストの登録
+#[utoipa::path(
+    post,
+    path = "/api/v2/watch",
+    request_body = WatchRequest,
+    responses(
+        (status = 201, description = "登録成功", body = WatchView),
+        (status = 401, description = "未認証"),
+        (status = 422, description = "条件・URLが不正", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "watch"
+)]
+async fn create_watch(
+    watch_service: Inject<AppRegistry, dyn WatchService>,
+    Extension(user_id): Extension<UserId>,
+    Json(req): Json<WatchRequest>,
+) -> AppResult<(StatusCode, Json<WatchView>)> {
+    let watch = watch_service
+        .create_watch(build_watch(req, user_id, 0)?)
+        .await?;
+    Ok((StatusCode::CREATED, Json(watch.into_dto())))
+}
+
+/// ウォッチリストの更新
+#[utoipa::path(
+    put,
+    path = "/api/v2/watch/{watch_id}",
+    params(("watch_id" = i64, Path, description = "ウォッチID")),
+    request_body = WatchRequest,
+    responses(
+        (status = 200, description = "更新成功", body = WatchView),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "ウォッチがない", body = ErrorResponse),
+        (status = 422, description = "条件・URLが不正", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "watch"
+)]
+async fn update_watch(
+    watch_service: Inject<AppRegistry, dyn WatchService>,
+    Extension(user_id): Extension<UserId>,
+    Path(watch_id): Path<i64>,
+    Json(req): Json<WatchRequest>,
+) -> AppResult<Json<WatchView>> {
+    let watch = watch_service
+        .update_watch(build_watch(req, user_id, watch_id)?)
+        .await?;
+    Ok(Json(watch.into_dto()))
+}
+
+/// ウォッチリストの削除（配信記録も削除する）
+#[utoipa::path(
+    delete,
+    path = "/api/v2/watch/{watch_id}",
+    params(("watch_id" = i64, Path, description = "ウォッチID")),
+    responses(
+        (status = 200, description = "削除成功"),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "ウォッチがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "watch"
+)]
+async fn delete_watch(
+    watch_service: Inject<AppRegistry, dyn WatchService>,
+    Extension(user_id): Extension<UserId>,
+    Path(watch_id): Path<i64>,
+) -> AppResult<StatusCode> {
+    watch_service
+        .delete_watch(user_id, watch_id)
+        .await
+        .map(|_| StatusCode::OK)
+}
+
+/// Webhook配信記録（新しい順）
+#[utoipa::path(
+    get,
+    path = "/api/v2/watch/{watch_id}/deliveries",
+    params(("watch_id" = i64, Path, description = "ウォッチID"), DeliveryQuery),
+    responses(
+        (status = 200, description = "取得成功", body = Vec<WebhookDeliveryView>),
+        (status = 401, description = "未認証"),
+        (status = 404, description = "ウォッチがない", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "watch"
+)]
+async fn list_deliveries(
+    watch_service: Inject<AppRegistry, dyn WatchService>,
+    Extension(user_id): Extension<UserId>,
+    Path(watch_id): Path<i64>,
+    Query(query): Query<DeliveryQuery>,
+) -> AppResult<Json<Vec<WebhookDeliveryView>>> {
+    let limit = query
+        .limit
+        .unwrap_or(DELIVERIES_DEFAULT_LIMIT)
+        .clamp(1, 1000);
+    let deliveries = watch_service
+        .find_deliveries(user_id, watch_id, limit)
+        .await?;
+    Ok(Json(
+        deliveries
+            .into_iter()
+            .map(WebhookDeliveryView::from_model)
+            .collect(),
+    ))
+}
+
+pub fn build_watch_routers(auth: &FireAuth) -> Router<AppState> {
+    let router = Router::new()
+        .route("/", get(list_watches).post(create_watch))
+        .route("/{watch_id}", put(update_watch).delete(delete_watch))
+        .route("/{watch_id}/deliveries", get(list_deliveries));
+
+    Router::new().nest("/watch", with_auth(router, auth))
+}
@@ -12,6 +12,16 @@ use axum::{
 use registry::AppState;
 use serde::Deserialize;
 use service::implement::wspr_service::{generate_wspr_svg, WsprRequest};
+use utoipa::{OpenApi, ToSchema};
+
+/// WSPR API
+#[derive(OpenApi)]
+#[openapi(
+    paths(wspr_svg_handler),
+    components(schemas(WsprFormRequest)),
+    tags((name = "wspr", description = "WSPRスポットグラフAPI"))
+)]
+pub struct WsprApi;
 
 /// WSPRルーターを作成
 pub fn wspr_router() -> Router<AppState> {
@@ -19,14 +29,25 @@ pub fn wspr_router() -> Router<AppState> {
 }
 
 /// フォームリクエスト
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 struct WsprFormRequest {
+    /// WSPRスポット（JSON文字列）
     arg: String,
 }
 
 /// WSPRスポットからSVGを生成
 ///
 /// フォームで`arg`パラメータにJSONを受け取る
+#[utoipa::path(
+    post,
+    path = "/api/v2/wspr/svg",
+    request_body(content = WsprFormRequest, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = 200, description = "SVGグラフ", body = String, content_type = "image/svg+xml"),
+        (status = 400, description = "JSONが不正", body = Object),
+    ),
+    tag = "wspr"
+)]
 async fn wspr_svg_handler(Form(form): Form<WsprFormRequest>) -> Response {
     // JSONをパース
     let request: WsprRequest = match serde_json::from_str(&form.arg) {
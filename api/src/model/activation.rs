@@ -1,20 +1,11 @@
-use serde::Serialize;
-use typeshare::typeshare;
-use utoipa::ToSchema;
-
 use domain::model::event::GroupBy;
 
-/// アクティベーションビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct ActivationView<T> {
-    pub key: Option<String>,
-    values: Vec<T>,
-}
+use crate::model::FromModel;
+
+pub use dto::activation::ActivationView;
 
-impl<T> From<(GroupBy, Vec<T>)> for ActivationView<T> {
-    fn from(g: (GroupBy, Vec<T>)) -> Self {
+impl<T> FromModel<(GroupBy, Vec<T>)> for ActivationView<T> {
+    fn from_model(g: (GroupBy, Vec<T>)) -> Self {
         match g.0 {
             GroupBy::Callsign(callsign) => Self {
                 key: callsign,
@@ -31,13 +22,14 @@ impl<T> From<(GroupBy, Vec<T>)> for ActivationView<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
 
     #[test]
     fn test_activation_view_from_callsign_with_value() {
         let group = GroupBy::Callsign(Some("JA1ABC".to_string()));
         let values = vec![1, 2, 3];
 
-        let view: ActivationView<i32> = (group, values).into();
+        let view: ActivationView<i32> = (group, values).into_dto();
 
         assert_eq!(view.key, Some("JA1ABC".to_string()));
         assert_eq!(view.values.len(), 3);
@@ -49,7 +41,7 @@ mod tests {
         let group = GroupBy::Callsign(None);
         let values = vec!["a", "b"];
 
-        let view: ActivationView<&str> = (group, values).into();
+        let view: ActivationView<&str> = (group, values).into_dto();
 
         assert!(view.key.is_none());
         assert_eq!(view.values.len(), 2);
@@ -60,7 +52,7 @@ mod tests {
         let group = GroupBy::Reference(Some("JA/TK-001".to_string()));
         let values = vec![10.5, 20.5];
 
-        let view: ActivationView<f64> = (group, values).into();
+        let view: ActivationView<f64> = (group, values).into_dto();
 
         assert_eq!(view.key, Some("JA/TK-001".to_string()));
         assert_eq!(view.values.len(), 2);
@@ -71,7 +63,7 @@ mod tests {
         let group = GroupBy::Reference(None);
         let values: Vec<String> = vec![];
 
-        let view: ActivationView<String> = (group, values).into();
+        let view: ActivationView<String> = (group, values).into_dto();
 
         assert!(view.key.is_none());
         assert!(view.values.is_empty());
@@ -82,7 +74,7 @@ mod tests {
         let group = GroupBy::Callsign(Some("JA1XYZ".to_string()));
         let values = vec!["spot1".to_string(), "spot2".to_string()];
 
-        let view: ActivationView<String> = (group, values).into();
+        let view: ActivationView<String> = (group, values).into_dto();
         let json = serde_json::to_string(&view).unwrap();
 
         // camelCase形式で出力される
@@ -95,7 +87,7 @@ mod tests {
         let group = GroupBy::Callsign(None);
         let values = vec![42];
 
-        let view: ActivationView<i32> = (group, values).into();
+        let view: ActivationView<i32> = (group, values).into_dto();
         let json = serde_json::to_string(&view).unwrap();
 
         assert!(json.contains("\"key\":null"));
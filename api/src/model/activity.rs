@@ -0,0 +1,37 @@
+use domain::model::activity::{ActivityCell, ActivityStats};
+
+use crate::model::FromModel;
+
+pub use dto::activity::{ActivityCellView, ActivityStatsView};
+
+/// 集計期間の既定値（時間）
+pub const ACTIVITY_DEFAULT_HOURS: i64 = 24;
+
+impl FromModel<ActivityCell> for ActivityCellView {
+    fn from_model(c: ActivityCell) -> Self {
+        Self {
+            program: c.program.into(),
+            band: c.band,
+            mode: c.mode,
+            hour: c.hour,
+            count: c.count,
+        }
+    }
+}
+
+impl FromModel<ActivityStats> for ActivityStatsView {
+    fn from_model(s: ActivityStats) -> Self {
+        Self {
+            from: s.from.to_rfc3339(),
+            to: s.to.to_rfc3339(),
+            total: s.total,
+            unknown_band: s.unknown_band,
+            cells: s
+                .cells
+                .into_iter()
+                .map(ActivityCellView::from_model)
+                .collect(),
+            k_index: None,
+        }
+    }
+}
@@ -1,14 +1,18 @@
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
-use serde::{Deserialize, Serialize};
-use typeshare::typeshare;
-use utoipa::ToSchema;
+use serde::Deserialize;
 
 use common::error::{AppError, AppResult};
 use common::utils::call_to_operator;
 
-use domain::model::activation::Alert;
+use domain::model::activation::{Alert, AlertLog, AlertOutcome, OutcomeSummary, SpotLog};
 use domain::model::AwardProgram;
 
+use crate::model::dxcc::DxccView;
+use crate::model::spots::{needed_view, SpotView};
+use crate::model::FromModel;
+
+pub use dto::alerts::{AlertOutcomeView, AlertView, ReliabilityView};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SotaAlert {
@@ -103,28 +107,8 @@ impl From<PotaAlert> for AppResult<Alert> {
     }
 }
 
-/// アラートビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-pub struct AlertView {
-    pub program: String,
-    pub alert_id: i32,
-    pub user_id: i32,
-    pub reference: String,
-    pub reference_detail: String,
-    pub location: String,
-    pub activator: String,
-    pub operator: String,
-    pub activator_name: Option<String>,
-    pub start_time: String,
-    pub end_time: Option<String>,
-    pub frequencies: String,
-    pub comment: Option<String>,
-    pub poster: Option<String>,
-}
-
-impl From<Alert> for AlertView {
-    fn from(a: Alert) -> Self {
+impl FromModel<Alert> for AlertView {
+    fn from_model(a: Alert) -> Self {
         Self {
             program: a.program.into(),
             alert_id: a.alert_id,
@@ -140,6 +124,58 @@ impl From<Alert> for AlertView {
             frequencies: a.frequencies,
             comment: a.comment,
             poster: a.poster,
+            outcome: None,
+            spot: None,
+            eta: None,
+            needed: None,
+            dxcc: None,
+        }
+    }
+}
+
+impl FromModel<AlertLog> for AlertView {
+    fn from_model(log: AlertLog) -> Self {
+        Self {
+            outcome: Some(log.outcome.as_str().to_string()),
+            spot: log
+                .spot
+                .map(|s| SpotView::from_model(SpotLog::new(s, None))),
+            eta: log.eta.map(|t| t.to_rfc3339()),
+            needed: log.needed.map(|n| needed_view(&log.alert.program, n)),
+            dxcc: log.dxcc.map(DxccView::from_model),
+            ..AlertView::from_model(log.alert)
+        }
+    }
+}
+
+impl FromModel<AlertOutcome> for AlertOutcomeView {
+    fn from_model(o: AlertOutcome) -> Self {
+        Self {
+            program: o.program.into(),
+            alert_id: o.alert_id,
+            reference: o.reference,
+            start_time: o.start_time.to_rfc3339(),
+            outcome: o.outcome.as_str().to_string(),
+            spot_reference: o.spot_reference,
+            spot_time: o.spot_time.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+impl FromModel<(OutcomeSummary, Vec<AlertOutcome>)> for ReliabilityView {
+    fn from_model((summary, recent): (OutcomeSummary, Vec<AlertOutcome>)) -> Self {
+        Self {
+            reliability: summary.reliability(),
+            operator: summary.operator,
+            total: summary.total,
+            on_time: summary.on_time,
+            late: summary.late,
+            different_reference: summary.different_reference,
+            no_show: summary.no_show,
+            recent: recent
+                .into_iter()
+                .map(AlertOutcomeView::from_model)
+                .collect(),
         }
     }
 }
@@ -147,6 +183,7 @@ impl From<Alert> for AlertView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::TimeZone;
 
     fn create_test_alert(program: AwardProgram) -> Alert {
@@ -175,7 +212,7 @@ mod tests {
     #[test]
     fn test_alert_view_from_sota_alert() {
         let alert = create_test_alert(AwardProgram::SOTA);
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         assert_eq!(view.program, "SOTA");
         assert_eq!(view.alert_id, 12345);
@@ -194,7 +231,7 @@ mod tests {
     #[test]
     fn test_alert_view_from_pota_alert() {
         let alert = create_test_alert(AwardProgram::POTA);
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         assert_eq!(view.program, "POTA");
     }
@@ -202,7 +239,7 @@ mod tests {
     #[test]
     fn test_alert_view_start_time_format() {
         let alert = create_test_alert(AwardProgram::SOTA);
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         // RFC3339形式で出力される
         assert!(view.start_time.contains("2024-06-15"));
@@ -212,7 +249,7 @@ mod tests {
     #[test]
     fn test_alert_view_end_time_some() {
         let alert = create_test_alert(AwardProgram::SOTA);
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         assert!(view.end_time.is_some());
         assert!(view.end_time.as_ref().unwrap().contains("2024-06-15"));
@@ -223,7 +260,7 @@ mod tests {
     fn test_alert_view_end_time_none() {
         let mut alert = create_test_alert(AwardProgram::SOTA);
         alert.end_time = None;
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         assert!(view.end_time.is_none());
     }
@@ -235,11 +272,68 @@ mod tests {
         alert.comment = None;
         alert.poster = None;
 
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         assert!(view.activator_name.is_none());
         assert!(view.comment.is_none());
         assert!(view.poster.is_none());
+        assert!(view.outcome.is_none());
+        assert!(view.spot.is_none());
+    }
+
+    #[test]
+    fn test_alert_view_from_alert_log() {
+        use domain::model::activation::{make_test_spot, ActivationOutcome, Spot};
+
+        let alert = create_test_alert(AwardProgram::SOTA);
+        let spot = Spot {
+            operator: "JA1ABC".to_string(),
+            spot_time: Utc.with_ymd_and_hms(2024, 6, 15, 9, 10, 0).unwrap(),
+            ..make_test_spot("JA1ABC/P", "JA/TK-001")
+        };
+        let log = AlertLog {
+            alert,
+            outcome: ActivationOutcome::OnTime,
+            spot: Some(spot),
+            eta: None,
+            needed: None,
+            dxcc: None,
+        };
+        let view: AlertView = log.into_dto();
+
+        assert_eq!(view.alert_id, 12345);
+        assert_eq!(view.outcome.as_deref(), Some("on_time"));
+        assert_eq!(view.spot.unwrap().reference, "JA/TK-001");
+    }
+
+    #[test]
+    fn test_reliability_view() {
+        use domain::model::activation::ActivationOutcome;
+
+        let outcome = |alert_id, outcome| AlertOutcome {
+            program: AwardProgram::SOTA,
+            alert_id,
+            operator: "JA1ABC".to_string(),
+            reference: "JA/TK-001".to_string(),
+            start_time: Utc.with_ymd_and_hms(2024, 6, 15, 9, 0, 0).unwrap(),
+            outcome,
+            spot_reference: None,
+            spot_time: None,
+        };
+        let outcomes = vec![
+            outcome(1, ActivationOutcome::OnTime),
+            outcome(2, ActivationOutcome::Late),
+            outcome(3, ActivationOutcome::NoShow),
+            outcome(4, ActivationOutcome::DifferentReference),
+        ];
+        let summary = OutcomeSummary::from_outcomes("JA1ABC", &outcomes);
+        let view = ReliabilityView::from_model((summary, outcomes));
+
+        assert_eq!(view.total, 4);
+        assert_eq!(view.on_time, 1);
+        assert_eq!(view.no_show, 1);
+        assert_eq!(view.reliability, Some(0.5));
+        assert_eq!(view.recent[2].outcome, "no_show");
     }
 
     // =====================================================
@@ -368,7 +462,7 @@ mod tests {
     #[test]
     fn test_alert_view_json_serialization() {
         let alert = create_test_alert(AwardProgram::SOTA);
-        let view: AlertView = alert.into();
+        let view: AlertView = alert.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -1,30 +1,25 @@
-use serde::Serialize;
-use typeshare::typeshare;
-
-use domain::model::aprslog::{AprsLog, AprsState, AprsTrack};
-
-#[derive(Debug, Serialize)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct AprsLogView {
-    pub time: String,
-    pub callsign: String,
-    pub ssid: i32,
-    pub destination: String,
-    pub state: String,
-    pub distance: f64,
-    pub longitude: f64,
-    pub latitude: f64,
-}
+use std::fmt::Write;
+
+use domain::model::aprs::WeatherReport;
+use domain::model::aprslog::{AprsLog, AprsSegment, AprsState, AprsTrack, AprsTrackArchive};
+
+use crate::model::FromModel;
 
-impl From<AprsLog> for AprsLogView {
-    fn from(l: AprsLog) -> AprsLogView {
+pub use dto::aprslog::{
+    AprsLogView, Geometry, Properties, Segment, SegmentProperties, Segments, Track, Tracks,
+    WeatherView,
+};
+
+impl FromModel<AprsLog> for AprsLogView {
+    fn from_model(l: AprsLog) -> AprsLogView {
         let AprsLog {
             callsign,
             destination,
             state,
             longitude,
             latitude,
+            detail,
+            ..
         } = l;
         let (time, state, distance) = match state {
             AprsState::Travelling { time } => (time, "Travelling", 0.0),
@@ -43,46 +38,13 @@ impl From<AprsLog> for AprsLogView {
             distance,
             longitude,
             latitude,
+            packet: detail.name().to_string(),
         }
     }
 }
 
-#[derive(Serialize, Debug)]
-pub struct Tracks {
-    pub tracks: Vec<Track>,
-}
-
-#[derive(Serialize, Debug)]
-pub struct Track {
-    #[serde(rename = "type")]
-    type_: String,
-    geometry: Geometry,
-    properties: Properties,
-}
-
-#[derive(Serialize, Debug)]
-struct Geometry {
-    #[serde(rename = "type")]
-    type_: String,
-    coordinates: Vec<Vec<f64>>,
-}
-
-#[derive(Serialize, Debug)]
-struct Properties {
-    callsign: String,
-    ssid: Option<String>,
-    lastseen: String,
-    distance: i32,
-    summit: String,
-    spot_summit: Option<String>,
-    spot_time: Option<String>,
-    spot_freq: Option<String>,
-    spot_mode: Option<String>,
-    spot_comment: Option<String>,
-}
-
-impl From<AprsTrack> for Track {
-    fn from(aprs: AprsTrack) -> Self {
+impl FromModel<AprsTrack> for Track {
+    fn from_model(aprs: AprsTrack) -> Self {
         Track {
             type_: "Feature".to_string(),
             geometry: Geometry {
@@ -104,7 +66,106 @@ impl From<AprsTrack> for Track {
                 spot_freq: aprs.spot_freq,
                 spot_mode: aprs.spot_mode,
                 spot_comment: aprs.spot_comment,
+                weather: aprs.weather.map(WeatherView::from_model),
+                eta: aprs.eta.map(|t| t.to_rfc3339()),
+            },
+        }
+    }
+}
+
+impl FromModel<AprsSegment> for Segment {
+    fn from_model(seg: AprsSegment) -> Self {
+        let rfc3339 = |t: chrono::NaiveDateTime| t.and_utc().to_rfc3339();
+        Segment {
+            type_: "Feature".to_string(),
+            properties: SegmentProperties {
+                callsign: seg.callsign.callsign.clone(),
+                ssid: seg.callsign.ssid.map(|s| s.to_string()),
+                summit: seg.summit.clone(),
+                arrival: seg.arrival.map(rfc3339),
+                start_time: seg.start_time().map(rfc3339),
+                end_time: seg.end_time().map(rfc3339),
+                times: seg.points.iter().map(|p| rfc3339(p.time)).collect(),
+                archive_id: None,
+                archived_at: None,
             },
+            geometry: Geometry {
+                type_: "LineString".to_string(),
+                coordinates: seg
+                    .points
+                    .into_iter()
+                    .map(|p| vec![p.longitude, p.latitude])
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl FromModel<AprsTrackArchive> for Segment {
+    fn from_model(archive: AprsTrackArchive) -> Self {
+        let mut segment = Segment::from_model(archive.segment);
+        segment.properties.archive_id = Some(archive.archive_id);
+        segment.properties.archived_at = Some(archive.archived_at.to_rfc3339());
+        segment
+    }
+}
+
+pub(crate) fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 保存したトラックをGPXに変換する
+pub fn archive_to_gpx(archive: &AprsTrackArchive) -> String {
+    let seg = &archive.segment;
+    let callsign = match seg.callsign.ssid {
+        Some(ssid) => format!("{}-{}", seg.callsign.callsign, ssid),
+        None => seg.callsign.callsign.clone(),
+    };
+    let name = escape_xml(&format!(
+        "{} {}",
+        callsign,
+        seg.summit.as_deref().unwrap_or_default()
+    ));
+
+    let mut gpx = String::new();
+    let _ = writeln!(gpx, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        gpx,
+        r#"<gpx version="1.1" creator="sotaapp2" xmlns="http://www.topografix.com/GPX/1/1">"#
+    );
+    let _ = writeln!(
+        gpx,
+        "  <trk>\n    <name>{}</name>\n    <trkseg>",
+        name.trim()
+    );
+    for p in &seg.points {
+        let _ = writeln!(
+            gpx,
+            r#"      <trkpt lat="{:.6}" lon="{:.6}"><time>{}</time></trkpt>"#,
+            p.latitude,
+            p.longitude,
+            p.time.and_utc().format("%Y-%m-%dT%H:%M:%SZ")
+        );
+    }
+    let _ = writeln!(gpx, "    </trkseg>\n  </trk>\n</gpx>");
+    gpx
+}
+
+impl FromModel<WeatherReport> for WeatherView {
+    fn from_model(w: WeatherReport) -> Self {
+        Self {
+            wind_direction: w.wind_direction,
+            wind_speed: w.wind_speed,
+            wind_gust: w.wind_gust,
+            temperature: w.temperature,
+            rain_1h: w.rain_1h,
+            rain_24h: w.rain_24h,
+            rain_midnight: w.rain_midnight,
+            humidity: w.humidity,
+            pressure: w.pressure,
         }
     }
 }
@@ -112,8 +173,10 @@ impl From<AprsTrack> for Track {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use aprs_message::AprsCallsign;
     use chrono::{NaiveDateTime, TimeZone, Utc};
+    use domain::model::aprslog::AprsLogDetail;
 
     fn make_test_time() -> NaiveDateTime {
         NaiveDateTime::parse_from_str("2024-06-15 10:30:00", "%Y-%m-%d %H:%M:%S").unwrap()
@@ -140,9 +203,11 @@ mod tests {
             },
             longitude: 139.2438,
             latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.callsign, "JA1ABC");
         assert_eq!(view.ssid, 7);
@@ -164,9 +229,11 @@ mod tests {
             },
             longitude: 139.0,
             latitude: 35.0,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.ssid, 0); // None -> default 0
         assert_eq!(view.destination, ""); // None -> empty string
@@ -185,9 +252,11 @@ mod tests {
             },
             longitude: 139.5,
             latitude: 35.5,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.state, "Climbing");
         assert_eq!(view.distance, 800.0);
@@ -205,9 +274,11 @@ mod tests {
             },
             longitude: 139.2438,
             latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.state, "NearSummit");
         assert_eq!(view.distance, 150.0);
@@ -225,9 +296,11 @@ mod tests {
             },
             longitude: 139.2438,
             latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.state, "OnSummit");
         assert_eq!(view.distance, 50.0);
@@ -244,9 +317,11 @@ mod tests {
             },
             longitude: 139.2438,
             latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert_eq!(view.state, "Descending");
         assert_eq!(view.distance, 300.0);
@@ -262,9 +337,11 @@ mod tests {
             },
             longitude: 139.0,
             latitude: 35.0,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
 
         assert!(view.time.contains("2024-06-15"));
         assert!(view.time.contains("10:30:00"));
@@ -287,9 +364,11 @@ mod tests {
             spot_freq: Some("14.285".to_string()),
             spot_mode: Some("SSB".to_string()),
             spot_comment: Some("CQ CQ".to_string()),
+            weather: None,
+            eta: None,
         };
 
-        let view: Track = track.into();
+        let view: Track = track.into_dto();
 
         assert_eq!(view.type_, "Feature");
         assert_eq!(view.geometry.type_, "LineString");
@@ -317,9 +396,11 @@ mod tests {
             spot_freq: None,
             spot_mode: None,
             spot_comment: None,
+            weather: None,
+            eta: None,
         };
 
-        let view: Track = track.into();
+        let view: Track = track.into_dto();
 
         assert!(view.properties.ssid.is_none());
         assert_eq!(view.properties.distance, 0);
@@ -341,9 +422,11 @@ mod tests {
             spot_freq: None,
             spot_mode: None,
             spot_comment: None,
+            weather: None,
+            eta: None,
         };
 
-        let view: Track = track.into();
+        let view: Track = track.into_dto();
 
         // RFC3339形式
         assert!(view.properties.lastseen.contains("2024-06-15"));
@@ -367,6 +450,8 @@ mod tests {
             spot_freq: None,
             spot_mode: None,
             spot_comment: None,
+            weather: None,
+            eta: None,
         };
 
         let track2 = AprsTrack {
@@ -380,10 +465,12 @@ mod tests {
             spot_freq: None,
             spot_mode: None,
             spot_comment: None,
+            weather: None,
+            eta: None,
         };
 
         let tracks = Tracks {
-            tracks: vec![track1.into(), track2.into()],
+            tracks: vec![track1.into_dto(), track2.into_dto()],
         };
 
         assert_eq!(tracks.tracks.len(), 2);
@@ -407,9 +494,11 @@ mod tests {
             },
             longitude: 139.2438,
             latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Position,
         };
 
-        let view: AprsLogView = log.into();
+        let view: AprsLogView = log.into_dto();
         let json = serde_json::to_string(&view).unwrap();
 
         // camelCase形式で出力される
@@ -431,9 +520,11 @@ mod tests {
             spot_freq: None,
             spot_mode: None,
             spot_comment: None,
+            weather: None,
+            eta: None,
         };
 
-        let view: Track = track.into();
+        let view: Track = track.into_dto();
         let json = serde_json::to_string(&view).unwrap();
 
         // GeoJSON形式
@@ -441,4 +532,97 @@ mod tests {
         assert!(json.contains("\"type\":\"LineString\""));
         assert!(json.contains("\"coordinates\":[[139.0,35.0]]"));
     }
+
+    #[test]
+    fn test_weather_attached_to_track() {
+        let weather = WeatherReport {
+            temperature: Some(41),
+            humidity: Some(85),
+            ..Default::default()
+        };
+        let log = AprsLog {
+            callsign: create_test_aprs_callsign("JA1ABC", Some(7)),
+            destination: Some("JA/TK-001".to_string()),
+            state: AprsState::OnSummit {
+                time: make_test_time(),
+                distance: 50.0,
+                message: String::new(),
+            },
+            longitude: 139.2438,
+            latitude: 35.6251,
+            altitude: None,
+            detail: AprsLogDetail::Weather(weather.clone()),
+        };
+        let view: AprsLogView = log.into_dto();
+        assert_eq!(view.packet, "weather");
+
+        let track = AprsTrack {
+            coordinates: vec![(139.0, 35.0)],
+            callsign: create_test_aprs_callsign("JA1ABC", Some(7)),
+            lastseen: Utc.with_ymd_and_hms(2024, 6, 15, 10, 30, 0).unwrap(),
+            distance: Some(100.0),
+            summit: Some("JA/TK-001".to_string()),
+            spot_summit: None,
+            spot_time: None,
+            spot_freq: None,
+            spot_mode: None,
+            spot_comment: None,
+            weather: Some(weather),
+            eta: None,
+        };
+        let json = serde_json::to_string(&Track::from_model(track)).unwrap();
+        assert!(json.contains("\"humidity\":85"));
+        assert!(json.contains("\"temperature\":41"));
+    }
+
+    fn make_archive() -> AprsTrackArchive {
+        let points = (0..3)
+            .map(|i| domain::model::aprslog::AprsTrackPoint {
+                time: make_test_time() + chrono::TimeDelta::minutes(i * 10),
+                latitude: 35.0 + i as f64 * 0.001,
+                longitude: 139.0,
+            })
+            .collect();
+        AprsTrackArchive {
+            archive_id: 12,
+            archived_at: Utc.with_ymd_and_hms(2024, 6, 16, 0, 0, 0).unwrap(),
+            segment: AprsSegment {
+                callsign: create_test_aprs_callsign("JA1ABC", Some(7)),
+                summit: Some("JA/TK-001".to_string()),
+                arrival: Some(make_test_time() + chrono::TimeDelta::minutes(20)),
+                points,
+            },
+        }
+    }
+
+    #[test]
+    fn test_segment_geojson_lon_lat() {
+        let view = Segments::new(vec![Segment::from_model(make_archive())]);
+        let json = serde_json::to_value(&view).unwrap();
+
+        assert_eq!(json["type"], "FeatureCollection");
+        let feature = &json["features"][0];
+        assert_eq!(feature["geometry"]["coordinates"][0][0], 139.0);
+        assert_eq!(feature["geometry"]["coordinates"][0][1], 35.0);
+        assert_eq!(feature["properties"]["summit"], "JA/TK-001");
+        assert_eq!(feature["properties"]["archive_id"], 12);
+        assert_eq!(
+            feature["properties"]["arrival"],
+            "2024-06-15T10:50:00+00:00"
+        );
+        assert_eq!(feature["properties"]["times"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_archive_to_gpx() {
+        let gpx = archive_to_gpx(&make_archive());
+
+        assert!(gpx.starts_with("<?xml"));
+        assert!(gpx.contains("<name>JA1ABC-7 JA/TK-001</name>"));
+        assert!(gpx.contains(
+            r#"<trkpt lat="35.001000" lon="139.000000"><time>2024-06-15T10:40:00Z</time></trkpt>"#
+        ));
+        assert_eq!(gpx.matches("<trkpt").count(), 3);
+        assert!(gpx.trim_end().ends_with("</gpx>"));
+    }
 }
@@ -0,0 +1,135 @@
+use common::utils::call_to_operator;
+use domain::model::archive::{
+    ActivatorStat, FindArchive, FindArchiveBuilder, ReferenceYearStat, SpotArchive,
+};
+use domain::model::AwardProgram;
+use service::implement::spot_normalize::canonical_mode;
+
+use crate::model::param::GetParam;
+use crate::model::FromModel;
+
+pub use dto::archive::{ActivatorStatView, ReferenceYearStatView, SpotArchiveView};
+
+/// アーカイブ検索の件数の既定値と上限
+pub const ARCHIVE_DEFAULT_LIMIT: i32 = 100;
+pub const ARCHIVE_MAX_LIMIT: i32 = 1000;
+
+/// パラメータからアーカイブの検索条件を作る
+///
+/// by_callは運用者、by_refはリファレンスの完全一致。yearを指定するとafter/beforeより優先する。
+pub fn build_archive_query(program: AwardProgram, param: &GetParam) -> FindArchive {
+    let mut query = FindArchiveBuilder::default().program(program);
+
+    if let Some(callsign) = &param.by_call {
+        query = query.operator(&call_to_operator(&callsign.to_uppercase()));
+    }
+    if let Some(reference) = &param.by_ref {
+        query = query.reference(reference);
+    }
+    if let Some(association) = &param.association {
+        query = query.association(association);
+    }
+    if let Some(band) = &param.band {
+        query = query.band(band);
+    }
+    // モードはスポットの正規化と同じ表記にそろえる（USB/LSBはSSB、FT4はMFSKなど）
+    if let Some(mode) = param.mode.as_deref().and_then(canonical_mode) {
+        query = query.mode(&mode);
+    }
+    if let Some(year) = param.year {
+        query = query.year(year);
+    } else {
+        if let Some(after) = param.after {
+            query = query.after(after);
+        }
+        if let Some(before) = param.before {
+            query = query.before(before);
+        }
+    }
+
+    let limit = param
+        .limit
+        .unwrap_or(ARCHIVE_DEFAULT_LIMIT)
+        .min(ARCHIVE_MAX_LIMIT);
+    query = query.limit(limit);
+    if let Some(offset) = param.offset {
+        query = query.offset(offset);
+    }
+    query.build()
+}
+
+impl FromModel<SpotArchive> for SpotArchiveView {
+    fn from_model(s: SpotArchive) -> Self {
+        Self {
+            program: s.program.into(),
+            activation_date: s.activation_date.to_string(),
+            operator: s.operator,
+            activator: s.activator,
+            reference: s.reference,
+            reference_detail: s.reference_detail,
+            association: s.association,
+            band: s.band,
+            mode: s.mode,
+            frequency: s.frequency,
+            first_spot: s.first_spot.to_rfc3339(),
+            last_spot: s.last_spot.to_rfc3339(),
+            spot_count: s.spot_count,
+        }
+    }
+}
+
+impl FromModel<ReferenceYearStat> for ReferenceYearStatView {
+    fn from_model(s: ReferenceYearStat) -> Self {
+        Self {
+            program: s.program.into(),
+            reference: s.reference,
+            year: s.year,
+            activations: s.activations,
+            activators: s.activators,
+        }
+    }
+}
+
+impl FromModel<ActivatorStat> for ActivatorStatView {
+    fn from_model(s: ActivatorStat) -> Self {
+        Self {
+            operator: s.operator,
+            activations: s.activations,
+            references: s.references,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_archive_query_year_overrides_range() {
+        let param = GetParam {
+            by_call: Some("ja1abc/p".to_string()),
+            band: Some("20M".to_string()),
+            mode: Some("usb".to_string()),
+            year: Some(2025),
+            after: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+        let query = build_archive_query(AwardProgram::SOTA, &param);
+        assert_eq!(query.operator.as_deref(), Some("JA1ABC"));
+        assert_eq!(query.band.as_deref(), Some("20m"));
+        assert_eq!(query.mode.as_deref(), Some("SSB"));
+        assert_eq!(query.months(), (Some(202501), Some(202512)));
+        assert_eq!(query.limit, Some(ARCHIVE_DEFAULT_LIMIT));
+    }
+
+    #[test]
+    fn test_build_archive_query_caps_limit() {
+        let param = GetParam {
+            limit: Some(5000),
+            ..Default::default()
+        };
+        let query = build_archive_query(AwardProgram::POTA, &param);
+        assert_eq!(query.limit, Some(ARCHIVE_MAX_LIMIT));
+        assert_eq!(query.program, Some(AwardProgram::POTA));
+    }
+}
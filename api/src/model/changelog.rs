@@ -0,0 +1,21 @@
+use domain::model::changelog::ReferenceChange;
+
+use crate::model::FromModel;
+
+pub use dto::changelog::ReferenceChangeView;
+
+/// since未指定時に返す変更履歴の期間（日）
+pub const CHANGES_DEFAULT_DAYS: i64 = 30;
+
+impl FromModel<ReferenceChange> for ReferenceChangeView {
+    fn from_model(c: ReferenceChange) -> Self {
+        Self {
+            program: c.program.into(),
+            reference: c.reference,
+            change: c.kind.as_str().to_string(),
+            old_value: c.old_value,
+            new_value: c.new_value,
+            changed_at: c.changed_at.to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use common::dxcc::DxccInfo;
+
+use crate::model::FromModel;
+
+pub use dto::dxcc::{CtyStatusView, DxccView};
+
+impl FromModel<DxccInfo> for DxccView {
+    fn from_model(d: DxccInfo) -> Self {
+        Self {
+            entity: d.entity,
+            prefix: d.prefix,
+            continent: d.continent,
+            cq_zone: d.cq_zone as i32,
+            itu_zone: d.itu_zone as i32,
+            lat: d.lat,
+            lon: d.lon,
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! アラートのiCalendarとスポットのAtomフィード
+
+use chrono::{DateTime, Duration, Utc};
+use std::fmt::Write;
+
+use domain::model::activation::{Alert, Spot};
+use domain::model::AwardProgram;
+
+use crate::model::aprslog::escape_xml;
+
+/// フィードに載せるスポットの上限
+pub const FEED_MAX_ENTRIES: usize = 100;
+
+/// 終了時刻のないアラートの長さ（時間）
+const ALERT_DEFAULT_HOURS: i64 = 1;
+
+/// iCalendarの1行の最大オクテット数（RFC 5545）
+const ICAL_LINE_OCTETS: usize = 75;
+
+fn program_id(program: &AwardProgram) -> &'static str {
+    match program {
+        AwardProgram::SOTA => "sota",
+        AwardProgram::POTA => "pota",
+        AwardProgram::WWFF => "wwff",
+    }
+}
+
+/// リファレンスの詳細ページ
+fn reference_url(program: &AwardProgram, reference: &str) -> Option<String> {
+    match program {
+        AwardProgram::SOTA => Some(format!("https://sotl.as/summits/{}", reference)),
+        AwardProgram::POTA => Some(format!("https://pota.app/#/park/{}", reference)),
+        AwardProgram::WWFF => None,
+    }
+}
+
+fn ical_time(t: &DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// TEXT値のエスケープ（RFC 5545 3.3.11）
+///
+/// 改行（CRLF・LF・単独のCR）は `\n` にし、タブ以外の制御文字は取り除く。
+fn escape_ical(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                out.push_str("\\n");
+            }
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// 75オクテットごとに折り返して1行を書く（UTF-8の文字の途中では折り返さない）
+fn write_ical_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > ICAL_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn alert_event(out: &mut String, alert: &Alert, now: &DateTime<Utc>) {
+    let end = alert
+        .end_time
+        .filter(|end| *end > alert.start_time)
+        .unwrap_or(alert.start_time + Duration::hours(ALERT_DEFAULT_HOURS));
+    let summary = format!(
+        "{} {} {}",
+        alert.activator, alert.reference, alert.reference_detail
+    );
+
+    let mut description = format!("Frequencies: {}", alert.frequencies);
+    if let Some(comment) = alert.comment.as_deref().filter(|c| !c.is_empty()) {
+        let _ = write!(description, "\n{}", comment);
+    }
+    if let Some(poster) = alert.poster.as_deref().filter(|p| !p.is_empty()) {
+        let _ = write!(description, "\nPosted by {}", poster);
+    }
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!(
+            "UID:{}-{}@sotaapp2",
+            program_id(&alert.program),
+            alert.alert_id
+        ),
+        format!("DTSTAMP:{}", ical_time(now)),
+        format!("DTSTART:{}", ical_time(&alert.start_time)),
+        format!("DTEND:{}", ical_time(&end)),
+        format!("SUMMARY:{}", escape_ical(summary.trim())),
+        format!("LOCATION:{}", escape_ical(&alert.reference)),
+        format!("DESCRIPTION:{}", escape_ical(&description)),
+        format!("CATEGORIES:{}", String::from(alert.program.clone())),
+    ];
+    if let Some(url) = reference_url(&alert.program, &alert.reference) {
+        lines.push(format!("URL:{}", url));
+    }
+    lines.push("END:VEVENT".to_string());
+
+    for line in lines {
+        write_ical_line(out, &line);
+    }
+}
+
+/// アラートをiCalendar（VEVENT、UIDはプログラムとアラートIDから作る）に変換する
+pub fn alerts_to_ical(alerts: &[Alert], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//sotaapp2//alerts//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        "X-WR-CALNAME:SOTA/POTA alerts",
+    ] {
+        write_ical_line(&mut out, line);
+    }
+    for alert in alerts {
+        alert_event(&mut out, alert, &now);
+    }
+    write_ical_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+fn atom_time(t: &DateTime<Utc>) -> String {
+    t.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// スポットをAtomフィード（新しい順）に変換する
+///
+/// `feed_key` はフィードのIDに使う（"callsign:JA1ABC" など）。
+pub fn spots_to_atom(title: &str, feed_key: &str, spots: &[Spot], now: DateTime<Utc>) -> String {
+    let updated = spots.iter().map(|s| s.spot_time).max().unwrap_or(now);
+
+    let mut out = String::new();
+    let _ = writeln!(out, r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    let _ = writeln!(out, r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    let _ = writeln!(
+        out,
+        "  <id>urn:sotaapp2:spots:{}</id>",
+        escape_xml(feed_key)
+    );
+    let _ = writeln!(out, "  <title>{}</title>", escape_xml(title));
+    let _ = writeln!(out, "  <updated>{}</updated>", atom_time(&updated));
+    let _ = writeln!(out, "  <generator>sotaapp2</generator>");
+
+    for s in spots {
+        let mode = s.canonical_mode.as_deref().unwrap_or(&s.mode);
+        let mut summary = s.reference_detail.clone();
+        if let Some(comment) = s.comment.as_deref().filter(|c| !c.is_empty()) {
+            let _ = write!(summary, " - {}", comment);
+        }
+
+        let _ = writeln!(out, "  <entry>");
+        let _ = writeln!(
+            out,
+            "    <id>urn:sotaapp2:spot:{}:{}</id>",
+            program_id(&s.program),
+            s.spot_id
+        );
+        let _ = writeln!(
+            out,
+            "    <title>{}</title>",
+            escape_xml(&format!(
+                "{} {} {} {}",
+                s.activator, s.reference, s.frequency, mode
+            ))
+        );
+        let _ = writeln!(out, "    <updated>{}</updated>", atom_time(&s.spot_time));
+        let _ = writeln!(
+            out,
+            "    <author><name>{}</name></author>",
+            escape_xml(&s.spotter)
+        );
+        let _ = writeln!(out, r#"    <category term="{}"/>"#, program_id(&s.program));
+        if let Some(url) = reference_url(&s.program, &s.reference) {
+            let _ = writeln!(out, r#"    <link href="{}"/>"#, escape_xml(&url));
+        }
+        let _ = writeln!(out, "    <summary>{}</summary>", escape_xml(summary.trim()));
+        let _ = writeln!(out, "  </entry>");
+    }
+    let _ = writeln!(out, "</feed>");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use domain::model::activation::{make_test_alert, make_test_spot};
+
+    fn alert() -> Alert {
+        Alert {
+            alert_id: 42,
+            reference_detail: "Yatsugatake, 2899m".to_string(),
+            operator: "JA1ABC".to_string(),
+            start_time: Utc.with_ymd_and_hms(2026, 10, 20, 1, 0, 0).unwrap(),
+            frequencies: "7.032-cw, 14.062-cw".to_string(),
+            comment: Some("QRV;2h".to_string()),
+            poster: None,
+            ..make_test_alert("JA1ABC/1", "JA/NN-001")
+        }
+    }
+
+    #[test]
+    fn test_alerts_to_ical() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let ics = alerts_to_ical(&[alert()], now);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:sota-42@sotaapp2\r\n"));
+        assert!(ics.contains("DTSTART:20261020T010000Z\r\n"));
+        assert!(ics.contains("DTEND:20261020T020000Z\r\n"));
+        assert!(ics.contains("SUMMARY:JA1ABC/1 JA/NN-001 Yatsugatake\\, 2899m\r\n"));
+        assert!(ics.contains("DESCRIPTION:Frequencies: 7.032-cw\\, 14.062-cw\\nQRV\\;2h\r\n"));
+        assert!(ics.contains("URL:https://sotl.as/summits/JA/NN-001\r\n"));
+    }
+
+    #[test]
+    fn test_escape_ical_control_chars() {
+        assert_eq!(escape_ical("a\r\nb\nc\rd"), "a\\nb\\nc\\nd");
+        assert_eq!(escape_ical("QRV\r"), "QRV\\n");
+        assert_eq!(escape_ical("a\u{7}b\u{1b}c\td"), "abc\td");
+        assert_eq!(escape_ical("a\\b;c,d"), "a\\\\b\\;c\\,d");
+
+        let mut out = String::new();
+        write_ical_line(
+            &mut out,
+            &format!("DESCRIPTION:{}", escape_ical("7.032\rCW")),
+        );
+        assert_eq!(out, "DESCRIPTION:7.032\\nCW\r\n");
+    }
+
+    #[test]
+    fn test_ical_line_folding() {
+        let mut out = String::new();
+        let line = format!("SUMMARY:{}", "八ヶ岳".repeat(10));
+        write_ical_line(&mut out, &line);
+
+        let lines: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| l.len() <= ICAL_LINE_OCTETS));
+        assert!(lines[1..].iter().all(|l| l.starts_with(' ')));
+        let unfolded: String = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| if i == 0 { *l } else { &l[1..] })
+            .collect();
+        assert_eq!(unfolded, line);
+    }
+
+    #[test]
+    fn test_spots_to_atom() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let spot = Spot {
+            program: AwardProgram::POTA,
+            spot_id: 7,
+            reference_detail: "Park <A&B>".to_string(),
+            spot_time: Utc.with_ymd_and_hms(2026, 10, 17, 23, 30, 0).unwrap(),
+            frequency: "7032".to_string(),
+            mode: "cw".to_string(),
+            frequency_hz: Some(7_032_000),
+            band: Some("40m".to_string()),
+            canonical_mode: Some("CW".to_string()),
+            comment: None,
+            ..make_test_spot("JA1ABC", "JP-0001")
+        };
+        let atom = spots_to_atom("spots: JA1ABC", "callsign:JA1ABC", &[spot], now);
+
+        assert!(atom.contains("<id>urn:sotaapp2:spots:callsign:JA1ABC</id>"));
+        assert!(atom.contains("<updated>2026-10-17T23:30:00Z</updated>"));
+        assert!(atom.contains("<id>urn:sotaapp2:spot:pota:7</id>"));
+        assert!(atom.contains("<title>JA1ABC JP-0001 7032 CW</title>"));
+        assert!(atom.contains("<summary>Park &lt;A&amp;B&gt;</summary>"));
+        assert!(atom.contains(r#"<link href="https://pota.app/#/park/JP-0001"/>"#));
+    }
+}
@@ -1,19 +1,11 @@
-use serde::Serialize;
-use typeshare::typeshare;
-
 use domain::model::geomag::GeomagIndex;
 
-#[derive(Debug, Serialize)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct GeomagView {
-    pub date: String,
-    pub a_index: i32,
-    pub k_index: Vec<i32>,
-}
+use crate::model::FromModel;
+
+pub use dto::geomag::GeomagView;
 
-impl From<GeomagIndex> for GeomagView {
-    fn from(gi: GeomagIndex) -> GeomagView {
+impl FromModel<GeomagIndex> for GeomagView {
+    fn from_model(gi: GeomagIndex) -> GeomagView {
         let GeomagIndex {
             date,
             a_index,
@@ -30,6 +22,7 @@ impl From<GeomagIndex> for GeomagView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::NaiveDate;
 
     fn create_test_geomag_index() -> GeomagIndex {
@@ -47,7 +40,7 @@ mod tests {
     #[test]
     fn test_geomag_view_from_index() {
         let index = create_test_geomag_index();
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         assert_eq!(view.date, "2024-06-15");
         assert_eq!(view.a_index, 12);
@@ -64,7 +57,7 @@ mod tests {
             k_index: vec![],
         };
 
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         assert_eq!(view.date, "2024-01-01");
     }
@@ -78,7 +71,7 @@ mod tests {
             k_index: vec![1.5, 2.7, 3.1, 4.9],
         };
 
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         // f32からi32への変換は切り捨て
         assert_eq!(view.k_index[0], 1);
@@ -95,7 +88,7 @@ mod tests {
             k_index: vec![],
         };
 
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         assert!(view.k_index.is_empty());
         assert_eq!(view.a_index, 0);
@@ -110,7 +103,7 @@ mod tests {
             k_index: vec![7.0, 8.0, 9.0, 9.0, 8.0, 7.0, 6.0, 5.0],
         };
 
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         assert_eq!(view.a_index, 150);
         assert_eq!(view.k_index[2], 9);
@@ -123,7 +116,7 @@ mod tests {
     #[test]
     fn test_geomag_view_json_serialization() {
         let index = create_test_geomag_index();
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -141,7 +134,7 @@ mod tests {
             k_index: vec![1.0],
         };
 
-        let view: GeomagView = index.into();
+        let view: GeomagView = index.into_dto();
         let json = serde_json::to_string(&view).unwrap();
 
         // snake_caseではなくcamelCase
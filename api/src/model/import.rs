@@ -1,75 +1,63 @@
-use serde::Serialize;
+use axum::{http::StatusCode, Json};
 
-/// Import operation result
-#[derive(Debug, Clone, Serialize, Default)]
-pub struct ImportResult {
-    /// Whether the import was successful
-    pub success: bool,
-    /// Human-readable message
-    pub message: String,
-    /// Number of records successfully imported
-    pub imported: u32,
-    /// Number of records skipped (duplicates, etc.)
-    pub skipped: u32,
-    /// List of errors encountered during import
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    pub errors: Vec<ImportError>,
-}
+use service::model::import::ImportReport;
 
-/// Individual import error
-#[derive(Debug, Clone, Serialize)]
-pub struct ImportError {
-    /// Line number where the error occurred (1-indexed)
-    pub line: u32,
-    /// Error message
-    pub message: String,
-}
+use crate::model::FromModel;
 
-impl ImportResult {
-    /// Create a successful result with counts
-    pub fn success(imported: u32, skipped: u32) -> Self {
-        Self {
-            success: true,
-            message: format!("インポート完了: {}件追加, {}件スキップ", imported, skipped),
-            imported,
-            skipped,
-            errors: vec![],
-        }
-    }
+pub use dto::import::{ImportError, ImportParam, ImportReportView, ImportResult};
 
-    /// Create a failed result with error message
-    pub fn failure(message: impl Into<String>) -> Self {
-        Self {
-            success: false,
-            message: message.into(),
-            imported: 0,
-            skipped: 0,
-            errors: vec![],
-        }
-    }
+/// Response for an import report; imports rejected by validation errors return 422
+pub fn import_response(report: ImportReport) -> (StatusCode, Json<ImportResult>) {
+    let status = if report.committed || report.dry_run {
+        StatusCode::OK
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+    (status, Json(ImportResult::from_model(report)))
+}
+
+impl FromModel<ImportReport> for ImportResult {
+    fn from_model(r: ImportReport) -> Self {
+        let errors: Vec<_> = r
+            .errors
+            .iter()
+            .map(|e| ImportError::new(e.line as u32, e.message.clone()))
+            .collect();
+        let applied = r.applied() as u32;
+        let skipped = r.skipped as u32;
 
-    /// Create a partial success result with errors
-    pub fn partial(imported: u32, skipped: u32, errors: Vec<ImportError>) -> Self {
-        Self {
-            success: true,
-            message: format!(
-                "インポート完了: {}件追加, {}件スキップ, {}件エラー",
-                imported,
+        let mut result = if r.committed {
+            Self::success(applied, skipped)
+        } else if r.dry_run {
+            Self {
+                success: errors.is_empty(),
+                message: format!(
+                    "検証完了: {}件追加, {}件更新, {}件削除, {}件スキップ, {}件エラー",
+                    r.inserts.len(),
+                    r.updates.len(),
+                    r.deletes.len(),
+                    skipped,
+                    errors.len()
+                ),
                 skipped,
+                dry_run: true,
+                ..Default::default()
+            }
+        } else {
+            Self::failure(format!(
+                "{}件のエラーがあるためインポートしませんでした",
                 errors.len()
-            ),
-            imported,
-            skipped,
-            errors,
-        }
-    }
-}
-
-impl ImportError {
-    pub fn new(line: u32, message: impl Into<String>) -> Self {
-        Self {
-            line,
-            message: message.into(),
+            ))
+        };
+        result.errors = errors;
+        if !r.committed {
+            result.report = Some(ImportReportView {
+                rows: r.rows as u32,
+                inserts: r.inserts,
+                updates: r.updates,
+                deletes: r.deletes,
+            });
         }
+        result
     }
 }
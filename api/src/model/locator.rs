@@ -1,25 +1,11 @@
 use domain::model::locator::{CenturyCode, MunicipalityCenturyCode};
-use serde::Serialize;
-use typeshare::typeshare;
 
-#[derive(Debug, Default, Serialize)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct CenturyCodeView {
-    pub muni_code: Option<i32>,
-    pub prefecture: Option<String>,
-    pub municipality: Option<String>,
-    pub jcc_code: Option<String>,
-    pub ward_code: Option<String>,
-    pub jcc_text: Option<String>,
-    pub jcg_code: Option<String>,
-    pub jcg_text: Option<String>,
-    pub hamlog_code: Option<String>,
-    pub maidenhead: String,
-}
+use crate::model::FromModel;
+
+pub use dto::locator::{CenturyCodeView, MapcodeView};
 
-impl From<MunicipalityCenturyCode> for CenturyCodeView {
-    fn from(mcc: MunicipalityCenturyCode) -> CenturyCodeView {
+impl FromModel<MunicipalityCenturyCode> for CenturyCodeView {
+    fn from_model(mcc: MunicipalityCenturyCode) -> CenturyCodeView {
         let MunicipalityCenturyCode {
             muni_code,
             prefecture,
@@ -62,15 +48,3 @@ impl From<MunicipalityCenturyCode> for CenturyCodeView {
         }
     }
 }
-
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MapcodeView {
-    pub mapcode: String,
-}
-
-impl From<String> for MapcodeView {
-    fn from(mapcode: String) -> Self {
-        Self { mapcode }
-    }
-}
@@ -1,13 +1,40 @@
 pub mod activation;
+pub mod activity;
 pub mod alerts;
 pub mod aprslog;
-pub mod auth;
-pub mod award;
+pub mod archive;
+pub mod changelog;
+pub mod dxcc;
+pub mod feed;
 pub mod geomag;
 pub mod import;
 pub mod locator;
 pub mod param;
 pub mod pota;
+pub mod relay;
 pub mod search;
+pub mod session;
+pub mod solar;
 pub mod sota;
 pub mod spots;
+pub mod watch;
+
+pub use dto::{auth, award};
+
+/// ドメインモデルからDTOへの変換
+///
+/// DTOは `dto` クレートにあり、ドメインモデルとの間に `From` を実装できないのでこちらを使う。
+pub trait FromModel<T> {
+    fn from_model(value: T) -> Self;
+}
+
+/// `FromModel` の逆向き（`Into` に相当する）
+pub trait IntoDto<U> {
+    fn into_dto(self) -> U;
+}
+
+impl<T, U: FromModel<T>> IntoDto<U> for T {
+    fn into_dto(self) -> U {
+        U::from_model(self)
+    }
+}
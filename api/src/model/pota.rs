@@ -1,14 +1,18 @@
 use chrono::{DateTime, NaiveDate, Utc};
 use domain::model::id::UserId;
-use domain::model::Maidenhead;
-use serde::{Deserialize, Serialize};
-use typeshare::typeshare;
-use utoipa::ToSchema;
+use serde::Deserialize;
 
 use common::utils::maidenhead;
 use domain::model::event::PagenatedResult;
 use domain::model::pota::{PotaLogHist, PotaLogStat, PotaLogStatEnt, PotaRefLog, PotaReference};
 
+use crate::model::FromModel;
+
+pub use dto::pota::{
+    PagenatedResponse, PotaLogHistView, PotaLogStatEntView, PotaLogStatView, PotaRefLogView,
+    PotaRefView, PotaSearchView, UpdateRefRequest,
+};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateRefRequest {
@@ -59,17 +63,8 @@ impl From<CreateRefRequest> for Vec<PotaReference> {
     }
 }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PagenatedResponse<PotaReference> {
-    pub total: i32,
-    pub limit: i32,
-    pub offset: i32,
-    pub results: Vec<PotaReference>,
-}
-
-impl From<PagenatedResult<PotaReference>> for PagenatedResponse<PotaRefView> {
-    fn from(pagenated: PagenatedResult<PotaReference>) -> Self {
+impl FromModel<PagenatedResult<PotaReference>> for PagenatedResponse<PotaRefView> {
+    fn from_model(pagenated: PagenatedResult<PotaReference>) -> Self {
         PagenatedResponse {
             total: pagenated.total as i32,
             limit: pagenated.limit,
@@ -77,60 +72,43 @@ impl From<PagenatedResult<PotaReference>> for PagenatedResponse<PotaRefView> {
             results: pagenated
                 .results
                 .into_iter()
-                .map(PotaRefView::from)
+                .map(PotaRefView::from_model)
                 .collect(),
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct UpdateRefRequest {
-    pub pota_code: String,
-    pub wwff_code: String,
-    pub park_name: String,
-    pub park_name_j: String,
-    pub park_location: String,
-    pub park_locid: String,
-    pub park_type: String,
-    pub park_inactive: bool,
-    pub park_area: i32,
-    pub longitude: f64,
-    pub latitude: f64,
-}
-
-impl From<UpdateRefRequest> for Vec<PotaReference> {
-    fn from(value: UpdateRefRequest) -> Self {
-        let UpdateRefRequest {
-            pota_code,
-            wwff_code,
-            park_name,
-            park_name_j,
-            park_location,
-            park_locid,
-            park_type,
-            park_inactive,
-            park_area,
-            longitude,
-            latitude,
-        } = value;
-        let update: DateTime<Utc> = Utc::now();
-        vec![PotaReference {
-            pota_code,
-            wwff_code,
-            park_name,
-            park_name_j,
-            park_location,
-            park_locid,
-            park_type,
-            park_inactive,
-            park_area,
-            longitude,
-            latitude,
-            maidenhead: maidenhead(longitude, latitude),
-            update,
-        }]
-    }
+/// 更新リクエストをパークにする
+pub fn build_references(req: UpdateRefRequest) -> Vec<PotaReference> {
+    let UpdateRefRequest {
+        pota_code,
+        wwff_code,
+        park_name,
+        park_name_j,
+        park_location,
+        park_locid,
+        park_type,
+        park_inactive,
+        park_area,
+        longitude,
+        latitude,
+    } = req;
+    let update: DateTime<Utc> = Utc::now();
+    vec![PotaReference {
+        pota_code,
+        wwff_code,
+        park_name,
+        park_name_j,
+        park_location,
+        park_locid,
+        park_type,
+        park_inactive,
+        park_area,
+        longitude,
+        latitude,
+        maidenhead: maidenhead(longitude, latitude),
+        update,
+    }]
 }
 
 #[derive(Debug)]
@@ -161,27 +139,8 @@ pub struct PotaHunterLog {
     pub upload: NaiveDate,
 }
 
-/// POTAリファレンスビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct PotaRefView {
-    pub pota_code: String,
-    pub wwff_code: String,
-    pub park_name: String,
-    pub park_name_j: String,
-    pub park_location: String,
-    pub park_locid: String,
-    pub park_type: String,
-    pub park_inactive: bool,
-    pub park_area: i32,
-    pub longitude: f64,
-    pub latitude: f64,
-    pub maidenhead: Maidenhead,
-}
-
-impl From<PotaReference> for PotaRefView {
-    fn from(pota: PotaReference) -> Self {
+impl FromModel<PotaReference> for PotaRefView {
+    fn from_model(pota: PotaReference) -> Self {
         PotaRefView {
             pota_code: pota.pota_code,
             wwff_code: pota.wwff_code,
@@ -195,35 +154,13 @@ impl From<PotaReference> for PotaRefView {
             longitude: pota.longitude,
             latitude: pota.latitude,
             maidenhead: pota.maidenhead,
+            sun: None,
         }
     }
 }
 
-/// POTAリファレンスログビュー
-#[derive(Debug, Serialize, Default, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct PotaRefLogView {
-    pub pota_code: String,
-    pub wwff_code: String,
-    pub park_name: String,
-    pub park_name_j: String,
-    pub park_location: String,
-    pub park_locid: String,
-    pub park_type: String,
-    pub park_inactive: bool,
-    pub park_area: i32,
-    pub longitude: f64,
-    pub latitude: f64,
-    pub maidenhead: Maidenhead,
-    pub attempts: Option<i32>,
-    pub activations: Option<i32>,
-    pub first_qso_date: Option<String>,
-    pub qsos: Option<i32>,
-}
-
-impl From<PotaRefLog> for PotaRefLogView {
-    fn from(pota: PotaRefLog) -> Self {
+impl FromModel<PotaRefLog> for PotaRefLogView {
+    fn from_model(pota: PotaRefLog) -> Self {
         PotaRefLogView {
             pota_code: pota.pota_code,
             wwff_code: pota.wwff_code,
@@ -245,27 +182,8 @@ impl From<PotaRefLog> for PotaRefLogView {
     }
 }
 
-/// POTA検索結果ビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct PotaSearchView {
-    pub pota: String,
-    pub wwff: String,
-    pub name: String,
-    pub name_j: String,
-    pub locid: Vec<String>,
-    pub area: i32,
-    pub lon: f64,
-    pub lat: f64,
-    pub atmpt: Option<i32>,
-    pub act: Option<i32>,
-    pub date: Option<String>,
-    pub qsos: Option<i32>,
-}
-
-impl From<PotaRefLog> for PotaSearchView {
-    fn from(pota: PotaRefLog) -> Self {
+impl FromModel<PotaRefLog> for PotaSearchView {
+    fn from_model(pota: PotaRefLog) -> Self {
         let locid: Vec<String> = pota
             .park_locid
             .split(',')
@@ -289,17 +207,8 @@ impl From<PotaRefLog> for PotaSearchView {
     }
 }
 
-#[derive(Debug, Serialize)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct PotaLogHistView {
-    pub log_id: String,
-    pub log_kind: String,
-    pub last_update: String,
-}
-
-impl From<PotaLogHist> for PotaLogHistView {
-    fn from(log: PotaLogHist) -> Self {
+impl FromModel<PotaLogHist> for PotaLogHistView {
+    fn from_model(log: PotaLogHist) -> Self {
         let log_kind = match log.log_kind {
             Some(kind) => kind.into(),
             None => "none".to_string(),
@@ -313,16 +222,8 @@ impl From<PotaLogHist> for PotaLogHistView {
     }
 }
 
-#[derive(Debug, Serialize)]
-#[typeshare]
-pub struct PotaLogStatEntView {
-    pub time: String,
-    pub users: i32,
-    pub logs: i32,
-}
-
-impl From<PotaLogStatEnt> for PotaLogStatEntView {
-    fn from(stat: PotaLogStatEnt) -> Self {
+impl FromModel<PotaLogStatEnt> for PotaLogStatEntView {
+    fn from_model(stat: PotaLogStatEnt) -> Self {
         PotaLogStatEntView {
             time: stat.time,
             users: stat.users as i32,
@@ -331,21 +232,8 @@ impl From<PotaLogStatEnt> for PotaLogStatEntView {
     }
 }
 
-#[derive(Debug, Serialize)]
-#[typeshare]
-pub struct PotaLogStatView {
-    pub log_uploaded: i32,
-    pub log_entries: i32,
-    pub log_expired: i32,
-    pub log_error: i32,
-    pub longest_id: String,
-    pub longest_entry: i32,
-    pub query_latency: String,
-    pub log_history: Vec<PotaLogStatEntView>,
-}
-
-impl From<PotaLogStat> for PotaLogStatView {
-    fn from(stat: PotaLogStat) -> Self {
+impl FromModel<PotaLogStat> for PotaLogStatView {
+    fn from_model(stat: PotaLogStat) -> Self {
         let query_latency = format!("{:.2}", stat.query_latency.as_secs_f32() * 1000f32);
 
         PotaLogStatView {
@@ -359,7 +247,7 @@ impl From<PotaLogStat> for PotaLogStatView {
             log_history: stat
                 .log_history
                 .into_iter()
-                .map(PotaLogStatEntView::from)
+                .map(PotaLogStatEntView::from_model)
                 .collect(),
         }
     }
@@ -368,6 +256,7 @@ impl From<PotaLogStat> for PotaLogStatView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::{NaiveDate, Utc};
     use domain::model::pota::{PotaRefLog, PotaReference};
 
@@ -417,7 +306,7 @@ mod tests {
     #[test]
     fn test_pota_ref_view_from_reference() {
         let reference = create_test_pota_reference();
-        let view: PotaRefView = reference.into();
+        let view: PotaRefView = reference.into_dto();
 
         assert_eq!(view.pota_code, "JA-0001");
         assert_eq!(view.wwff_code, "JAFF-0001");
@@ -438,7 +327,7 @@ mod tests {
         let mut reference = create_test_pota_reference();
         reference.park_inactive = true;
 
-        let view: PotaRefView = reference.into();
+        let view: PotaRefView = reference.into_dto();
 
         assert!(view.park_inactive);
     }
@@ -450,7 +339,7 @@ mod tests {
     #[test]
     fn test_pota_ref_log_view_from_ref_log() {
         let ref_log = create_test_pota_ref_log();
-        let view: PotaRefLogView = ref_log.into();
+        let view: PotaRefLogView = ref_log.into_dto();
 
         assert_eq!(view.pota_code, "JA-0001");
         assert_eq!(view.wwff_code, "JAFF-0001");
@@ -468,7 +357,7 @@ mod tests {
         ref_log.first_qso_date = None;
         ref_log.qsos = None;
 
-        let view: PotaRefLogView = ref_log.into();
+        let view: PotaRefLogView = ref_log.into_dto();
 
         assert!(view.attempts.is_none());
         assert!(view.activations.is_none());
@@ -483,7 +372,7 @@ mod tests {
     #[test]
     fn test_pota_search_view_from_ref_log() {
         let ref_log = create_test_pota_ref_log();
-        let view: PotaSearchView = ref_log.into();
+        let view: PotaSearchView = ref_log.into_dto();
 
         assert_eq!(view.pota, "JA-0001");
         assert_eq!(view.wwff, "JAFF-0001");
@@ -505,7 +394,7 @@ mod tests {
         let mut ref_log = create_test_pota_ref_log();
         ref_log.park_locid = "JP-22".to_string();
 
-        let view: PotaSearchView = ref_log.into();
+        let view: PotaSearchView = ref_log.into_dto();
 
         assert_eq!(view.locid, vec!["JP-22"]);
     }
@@ -524,7 +413,7 @@ mod tests {
             results: vec![reference],
         };
 
-        let response: PagenatedResponse<PotaRefView> = pagenated.into();
+        let response: PagenatedResponse<PotaRefView> = pagenated.into_dto();
 
         assert_eq!(response.total, 50);
         assert_eq!(response.limit, 20);
@@ -540,7 +429,7 @@ mod tests {
     #[test]
     fn test_pota_ref_view_json_serialization() {
         let reference = create_test_pota_reference();
-        let view: PotaRefView = reference.into();
+        let view: PotaRefView = reference.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -559,7 +448,7 @@ mod tests {
     #[test]
     fn test_pota_search_view_json_serialization() {
         let ref_log = create_test_pota_ref_log();
-        let view: PotaSearchView = ref_log.into();
+        let view: PotaSearchView = ref_log.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+
+use common::error::{AppError, AppResult};
+use domain::model::activation::{Alert, Spot};
+use domain::model::relay::RelayPost;
+
+use crate::model::param::parse_program;
+use crate::model::FromModel;
+
+pub use dto::relay::{AlertPostRequest, RelayPostView, SpotPostRequest};
+
+/// 投稿一覧の取得件数の既定値
+pub const POSTS_DEFAULT_LIMIT: i64 = 50;
+
+/// 投稿リクエストをスポットにする（参照先の名称・時刻・IDはサービスで決める）
+pub fn build_spot(req: SpotPostRequest) -> AppResult<Spot> {
+    Ok(Spot {
+        program: parse_program(&req.program)?,
+        spot_id: 0,
+        reference: req.reference,
+        reference_detail: String::new(),
+        operator: String::new(),
+        activator: req.activator,
+        activator_name: None,
+        spot_time: Utc::now(),
+        frequency: req.frequency.trim().to_string(),
+        mode: req.mode.trim().to_uppercase(),
+        spotter: req.spotter.unwrap_or_default(),
+        comment: req.comment.filter(|c| !c.trim().is_empty()),
+        frequency_hz: None,
+        band: None,
+        canonical_mode: None,
+        spotters: Vec::new(),
+    })
+}
+
+fn parse_time(value: &str, name: &str) -> AppResult<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| AppError::UnprocessableEntity(format!("{}はRFC3339で指定してください", name)))
+}
+
+/// 投稿リクエストをアラートにする（参照先の名称・所在地・IDはサービスで決める）
+pub fn build_alert(req: AlertPostRequest) -> AppResult<Alert> {
+    let start_time = parse_time(&req.start_time, "startTime")?;
+    let end_time = req
+        .end_time
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| parse_time(&t, "endTime"))
+        .transpose()?;
+    let poster = req
+        .poster
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| req.activator.clone());
+    Ok(Alert {
+        program: parse_program(&req.program)?,
+        alert_id: 0,
+        user_id: 0,
+        reference: req.reference,
+        reference_detail: String::new(),
+        location: String::new(),
+        operator: String::new(),
+        activator: req.activator,
+        activator_name: req.activator_name,
+        start_time,
+        end_time,
+        frequencies: req.frequencies.trim().to_string(),
+        comment: req.comment.filter(|c| !c.trim().is_empty()),
+        poster: Some(poster.trim().to_uppercase()),
+    })
+}
+
+impl FromModel<RelayPost> for RelayPostView {
+    fn from_model(p: RelayPost) -> Self {
+        Self {
+            post_id: p.post_id,
+            kind: p.kind.as_str().to_string(),
+            program: p.program.into(),
+            reference: p.reference,
+            activator: p.activator,
+            local_id: p.local_id,
+            status: p.status.as_str().to_string(),
+            upstream_id: p.upstream_id,
+            error: p.error,
+            created_at: p.created_at.to_rfc3339(),
+            relayed_at: p.relayed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::model::AwardProgram;
+
+    #[test]
+    fn test_build_spot() {
+        let req: SpotPostRequest = serde_json::from_value(serde_json::json!({
+            "program": "pota",
+            "reference": "JA-0001",
+            "activator": "JA1ABC",
+            "frequency": " 7032 ",
+            "mode": "cw",
+            "comment": " "
+        }))
+        .unwrap();
+        let s = build_spot(req).unwrap();
+        assert_eq!(s.program, AwardProgram::POTA);
+        assert_eq!(s.frequency, "7032");
+        assert_eq!(s.mode, "CW");
+        assert!(s.spotter.is_empty());
+        assert!(s.comment.is_none());
+    }
+
+    #[test]
+    fn test_build_alert() {
+        let req: AlertPostRequest = serde_json::from_value(serde_json::json!({
+            "program": "sota",
+            "reference": "JA/KN-006",
+            "activator": "JA1ABC/P",
+            "startTime": "2026-10-18T09:00:00+09:00",
+            "frequencies": "7.032-cw"
+        }))
+        .unwrap();
+        let a = build_alert(req).unwrap();
+        assert_eq!(a.program, AwardProgram::SOTA);
+        assert_eq!(a.start_time.to_rfc3339(), "2026-10-18T00:00:00+00:00");
+        assert!(a.end_time.is_none());
+        assert_eq!(a.poster.as_deref(), Some("JA1ABC/P"));
+
+        let req: AlertPostRequest = serde_json::from_value(serde_json::json!({
+            "program": "wwff",
+            "reference": "JAFF-0001",
+            "activator": "JA1ABC",
+            "startTime": "2026-10-18T09:00:00Z",
+            "frequencies": "7.032-cw"
+        }))
+        .unwrap();
+        assert!(build_alert(req).is_err());
+
+        let req: AlertPostRequest = serde_json::from_value(serde_json::json!({
+            "program": "sota",
+            "reference": "JA/KN-006",
+            "activator": "JA1ABC",
+            "startTime": "tomorrow",
+            "frequencies": "7.032-cw"
+        }))
+        .unwrap();
+        assert!(build_alert(req).is_err());
+    }
+}
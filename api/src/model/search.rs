@@ -1,30 +1,25 @@
-use serde::Serialize;
-use typeshare::typeshare;
-use utoipa::ToSchema;
-
 use super::pota::{PotaRefLogView, PotaSearchView};
 use super::sota::{SotaRefView, SotaSearchView};
+use super::FromModel;
 use domain::model::event::FindResult;
+use domain::model::route::{RouteHit, RouteReference};
 
-/// 検索結果レスポンス
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SearchResponse {
-    pub sota: Option<Vec<SotaSearchView>>,
-    pub pota: Option<Vec<PotaSearchView>>,
-}
-impl From<FindResult> for SearchResponse {
-    fn from(FindResult { sota, pota }: FindResult) -> Self {
+pub use dto::search::{
+    RouteCandidate, RouteSearchResponse, SearchBriefData, SearchBriefResponse, SearchFullResponse,
+    SearchResponse,
+};
+
+impl FromModel<FindResult> for SearchResponse {
+    fn from_model(FindResult { sota, pota }: FindResult) -> Self {
         Self {
             sota: if let Some(sota) = sota {
-                let res = sota.into_iter().map(SotaSearchView::from).collect();
+                let res = sota.into_iter().map(SotaSearchView::from_model).collect();
                 Some(res)
             } else {
                 None
             },
             pota: if let Some(pota) = pota {
-                let res = pota.into_iter().map(PotaSearchView::from).collect();
+                let res = pota.into_iter().map(PotaSearchView::from_model).collect();
                 Some(res)
             } else {
                 None
@@ -33,25 +28,17 @@ impl From<FindResult> for SearchResponse {
     }
 }
 
-/// 検索結果フルレスポンス
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SearchFullResponse {
-    pub sota: Option<Vec<SotaRefView>>,
-    pub pota: Option<Vec<PotaRefLogView>>,
-}
-impl From<FindResult> for SearchFullResponse {
-    fn from(FindResult { sota, pota }: FindResult) -> Self {
+impl FromModel<FindResult> for SearchFullResponse {
+    fn from_model(FindResult { sota, pota }: FindResult) -> Self {
         Self {
             sota: if let Some(sota) = sota {
-                let res = sota.into_iter().map(SotaRefView::from).collect();
+                let res = sota.into_iter().map(SotaRefView::from_model).collect();
                 Some(res)
             } else {
                 None
             },
             pota: if let Some(pota) = pota {
-                let res = pota.into_iter().map(PotaRefLogView::from).collect();
+                let res = pota.into_iter().map(PotaRefLogView::from_model).collect();
                 Some(res)
             } else {
                 None
@@ -60,29 +47,8 @@ impl From<FindResult> for SearchFullResponse {
     }
 }
 
-/// 検索結果簡易レスポンス
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SearchBriefResponse {
-    pub count: u32,
-    pub candidates: Vec<SearchBriefData>,
-}
-
-/// 検索結果簡易データ
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SearchBriefData {
-    pub code: String,
-    pub lon: f64,
-    pub lat: f64,
-    pub name: String,
-    pub name_j: String,
-}
-
-impl From<FindResult> for SearchBriefResponse {
-    fn from(FindResult { sota, pota }: FindResult) -> Self {
+impl FromModel<FindResult> for SearchBriefResponse {
+    fn from_model(FindResult { sota, pota }: FindResult) -> Self {
         let mut res = vec![];
 
         if let Some(sota) = sota {
@@ -120,9 +86,63 @@ impl From<FindResult> for SearchBriefResponse {
     }
 }
 
+impl FromModel<RouteHit> for RouteCandidate {
+    fn from_model(
+        RouteHit {
+            reference,
+            position,
+        }: RouteHit,
+    ) -> Self {
+        let distance_from_route = position.offset.round();
+        let along_route = position.along.round();
+        match reference {
+            RouteReference::Sota(r) => Self {
+                program: "SOTA".to_string(),
+                code: r.summit_code,
+                lon: r.longitude,
+                lat: r.latitude,
+                name: r.summit_name,
+                name_j: r.summit_name_j.unwrap_or_default(),
+                alt_m: Some(r.alt_m),
+                points: Some(r.points),
+                distance_from_route,
+                along_route,
+            },
+            RouteReference::Pota(r) => Self {
+                program: "POTA".to_string(),
+                code: match (r.pota_code.as_str(), r.wwff_code.as_str()) {
+                    ("", wwff) => wwff.to_string(),
+                    (pota, "") => pota.to_string(),
+                    (pota, wwff) => format!("{}/{}", pota, wwff),
+                },
+                lon: r.longitude,
+                lat: r.latitude,
+                name: r.park_name,
+                name_j: r.park_name_j,
+                alt_m: None,
+                points: None,
+                distance_from_route,
+                along_route,
+            },
+        }
+    }
+}
+
+impl FromModel<Vec<RouteHit>> for RouteSearchResponse {
+    fn from_model(hits: Vec<RouteHit>) -> Self {
+        let candidates: Vec<RouteCandidate> =
+            hits.into_iter().map(RouteCandidate::from_model).collect();
+        Self {
+            count: candidates.len() as u32,
+            candidates,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::NaiveDate;
     use domain::model::pota::PotaRefLog;
     use domain::model::sota::SotaReference;
@@ -185,7 +205,7 @@ mod tests {
             pota: Some(vec![create_test_pota_ref_log()]),
         };
 
-        let response: SearchResponse = result.into();
+        let response: SearchResponse = result.into_dto();
 
         assert!(response.sota.is_some());
         assert!(response.pota.is_some());
@@ -200,7 +220,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchResponse = result.into();
+        let response: SearchResponse = result.into_dto();
 
         assert!(response.sota.is_some());
         assert!(response.pota.is_none());
@@ -213,7 +233,7 @@ mod tests {
             pota: Some(vec![create_test_pota_ref_log()]),
         };
 
-        let response: SearchResponse = result.into();
+        let response: SearchResponse = result.into_dto();
 
         assert!(response.sota.is_none());
         assert!(response.pota.is_some());
@@ -226,7 +246,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchResponse = result.into();
+        let response: SearchResponse = result.into_dto();
 
         assert!(response.sota.is_none());
         assert!(response.pota.is_none());
@@ -243,7 +263,7 @@ mod tests {
             pota: Some(vec![create_test_pota_ref_log()]),
         };
 
-        let response: SearchFullResponse = result.into();
+        let response: SearchFullResponse = result.into_dto();
 
         assert!(response.sota.is_some());
         assert!(response.pota.is_some());
@@ -261,7 +281,7 @@ mod tests {
             pota: Some(vec![create_test_pota_ref_log()]),
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         assert_eq!(response.count, 2);
         assert_eq!(response.candidates.len(), 2);
@@ -274,7 +294,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         assert_eq!(response.count, 1);
         assert_eq!(response.candidates[0].code, "JA/TK-001");
@@ -291,7 +311,7 @@ mod tests {
             pota: Some(vec![create_test_pota_ref_log()]),
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         assert_eq!(response.count, 1);
         // POTA code + WWFF code の両方がある場合は "JA-0001/JAFF-0001" 形式
@@ -310,7 +330,7 @@ mod tests {
             pota: Some(vec![pota_ref]),
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         // POTA codeのみの場合
         assert_eq!(response.candidates[0].code, "JA-0001");
@@ -326,7 +346,7 @@ mod tests {
             pota: Some(vec![pota_ref]),
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         // WWFF codeのみの場合
         assert_eq!(response.candidates[0].code, "JAFF-0001");
@@ -339,7 +359,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         assert_eq!(response.count, 0);
         assert!(response.candidates.is_empty());
@@ -355,12 +375,48 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
 
         // summit_name_jがNoneの場合はデフォルトの空文字
         assert_eq!(response.candidates[0].name_j, "");
     }
 
+    // =====================================================
+    // RouteSearchResponse 変換テスト
+    // =====================================================
+
+    #[test]
+    fn test_route_search_response_from_hits() {
+        use domain::model::route::RoutePosition;
+
+        let hits = vec![
+            RouteHit {
+                reference: RouteReference::Sota(create_test_sota_reference()),
+                position: RoutePosition {
+                    offset: 120.4,
+                    along: 1500.6,
+                },
+            },
+            RouteHit {
+                reference: RouteReference::Pota(create_test_pota_ref_log()),
+                position: RoutePosition {
+                    offset: 800.0,
+                    along: 9000.0,
+                },
+            },
+        ];
+
+        let response: RouteSearchResponse = hits.into_dto();
+
+        assert_eq!(response.count, 2);
+        assert_eq!(response.candidates[0].program, "SOTA");
+        assert_eq!(response.candidates[0].points, Some(4));
+        assert_eq!(response.candidates[0].distance_from_route, 120.0);
+        assert_eq!(response.candidates[0].along_route, 1501.0);
+        assert_eq!(response.candidates[1].code, "JA-0001/JAFF-0001");
+        assert_eq!(response.candidates[1].alt_m, None);
+    }
+
     // =====================================================
     // JSON シリアライズテスト
     // =====================================================
@@ -372,7 +428,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchResponse = result.into();
+        let response: SearchResponse = result.into_dto();
         let json = serde_json::to_string(&response).unwrap();
 
         // camelCase形式で出力される
@@ -387,7 +443,7 @@ mod tests {
             pota: None,
         };
 
-        let response: SearchBriefResponse = result.into();
+        let response: SearchBriefResponse = result.into_dto();
         let json = serde_json::to_string(&response).unwrap();
 
         assert!(json.contains("\"count\":1"));
@@ -0,0 +1,156 @@
+use chrono::Utc;
+
+use common::error::{AppError, AppResult};
+use domain::model::id::UserId;
+use domain::model::session::{ActivationSession, SessionQso, SessionState, SessionStatus};
+use service::implement::fle::FleQsoRecord;
+use service::model::session::{ClientQso, QsoPostReport};
+
+use crate::model::param::parse_program;
+use crate::model::{FromModel, IntoDto};
+
+pub use dto::session::{
+    ClientQsoRequest, QsoPostRequest, QsoPostView, SessionQsoView, SessionRequest, SessionView,
+};
+
+/// 開始リクエストをセッションにする（ID・状態・時刻はサービスで決める）
+pub fn build_session(req: SessionRequest, user_id: UserId) -> AppResult<ActivationSession> {
+    Ok(ActivationSession {
+        session_id: 0,
+        user_id,
+        program: parse_program(&req.program)?,
+        reference: req.reference,
+        callsign: req.callsign,
+        operator: req.operator.unwrap_or_default(),
+        status: SessionStatus::Open,
+        opened_at: Utc::now(),
+        closed_at: None,
+        qualified_at: None,
+    })
+}
+
+/// 登録リクエストのQSOを読む（FLEのQSOレコードとして読めなければエラー）
+pub fn build_qsos(req: QsoPostRequest) -> AppResult<Vec<ClientQso>> {
+    req.qsos
+        .into_iter()
+        .map(|q| {
+            let record = serde_json::from_value::<FleQsoRecord>(q.record).map_err(|e| {
+                AppError::UnprocessableEntity(format!(
+                    "{}: QSOレコードが不正です: {}",
+                    q.client_id, e
+                ))
+            })?;
+            Ok(ClientQso {
+                client_id: q.client_id,
+                record,
+            })
+        })
+        .collect()
+}
+
+impl FromModel<SessionState> for SessionView {
+    fn from_model(state: SessionState) -> Self {
+        let SessionState {
+            session: s,
+            qsos,
+            qualification,
+            files,
+        } = state;
+        Self {
+            session_id: s.session_id,
+            program: s.program.into(),
+            reference: s.reference,
+            callsign: s.callsign,
+            operator: s.operator,
+            status: s.status.as_str().to_string(),
+            opened_at: s.opened_at.to_rfc3339(),
+            closed_at: s.closed_at.map(|t| t.to_rfc3339()),
+            qsos: qsos as u32,
+            required: qualification.required as u32,
+            qualifying: qualification.count as u32,
+            qualified: qualification.qualified,
+            qualified_at: s.qualified_at.map(|t| t.to_rfc3339()),
+            files,
+        }
+    }
+}
+
+impl FromModel<QsoPostReport> for QsoPostView {
+    fn from_model(r: QsoPostReport) -> Self {
+        Self {
+            session: r.state.into_dto(),
+            inserted: r.inserted as u32,
+            duplicates: r.duplicates as u32,
+            just_qualified: r.just_qualified,
+        }
+    }
+}
+
+impl FromModel<SessionQso> for SessionQsoView {
+    fn from_model(q: SessionQso) -> Self {
+        Self {
+            record: serde_json::from_str(&q.record).unwrap_or_default(),
+            client_id: q.client_id,
+            callsign: q.callsign,
+            band: q.band,
+            mode: q.mode,
+            qso_time: q.qso_time.to_rfc3339(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::model::AwardProgram;
+
+    #[test]
+    fn test_build_session() {
+        let req: SessionRequest = serde_json::from_value(serde_json::json!({
+            "program": "sota",
+            "reference": "JA/KN-006",
+            "callsign": "JA1ABC/P"
+        }))
+        .unwrap();
+        let s = build_session(req, UserId::from("user".to_string())).unwrap();
+        assert_eq!(s.program, AwardProgram::SOTA);
+        assert!(s.operator.is_empty());
+        assert!(s.is_open());
+
+        let req: SessionRequest = serde_json::from_value(serde_json::json!({
+            "program": "wwff",
+            "reference": "JAFF-0001",
+            "callsign": "JA1ABC"
+        }))
+        .unwrap();
+        assert!(build_session(req, UserId::from("user".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_qso_post_request() {
+        let req: QsoPostRequest = serde_json::from_value(serde_json::json!({
+            "qsos": [{
+                "clientId": "a1",
+                "record": {
+                    "mycall": "", "operator": "", "year": 2026, "month": 10, "day": 18,
+                    "hour": 1, "min": 5, "callsign": "JA1XYZ", "band": "40m", "freq": "",
+                    "mode": "CW", "rigset": 0, "rst_sent": "599", "rst_rcvd": "599",
+                    "his_num": "", "my_num": "", "mysota": "", "hissota": "",
+                    "mywwff": "", "hiswwff": "", "mypota": [], "hispota": [],
+                    "qsomsg": "", "qsormks": "", "qslmsg": ""
+                }
+            }]
+        }))
+        .unwrap();
+        let qsos = build_qsos(req).unwrap();
+        assert_eq!(qsos.len(), 1);
+        assert_eq!(qsos[0].client_id, "a1");
+        assert_eq!(qsos[0].record.callsign, "JA1XYZ");
+
+        let req: QsoPostRequest = serde_json::from_value(serde_json::json!({
+            "qsos": [{ "clientId": "a2", "record": { "callsign": 1 } }]
+        }))
+        .unwrap();
+        assert!(build_qsos(req).is_err());
+    }
+}
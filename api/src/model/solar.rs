@@ -0,0 +1,61 @@
+use chrono::{NaiveDate, Utc};
+
+use common::error::{AppError, AppResult};
+use service::implement::solar::{local_date, sun_times, SunTimes};
+
+use crate::model::{FromModel, IntoDto};
+
+pub use dto::solar::{SunParam, SunView};
+
+/// 地点の日の出・日の入りを求める
+pub fn sun_view(param: &SunParam, latitude: f64, longitude: f64) -> AppResult<SunView> {
+    let date = match param
+        .date
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|_| {
+            AppError::UnprocessableEntity(format!("dateはYYYY-MM-DDで指定してください: {}", d))
+        })?,
+        None => local_date(Utc::now(), longitude),
+    };
+    Ok(sun_times(date, latitude, longitude).into_dto())
+}
+
+impl FromModel<SunTimes> for SunView {
+    fn from_model(t: SunTimes) -> Self {
+        Self {
+            date: t.date.to_string(),
+            civil_dawn: t.civil_dawn.map(|t| t.to_rfc3339()),
+            sunrise: t.sunrise.map(|t| t.to_rfc3339()),
+            solar_noon: t.solar_noon.to_rfc3339(),
+            sunset: t.sunset.map(|t| t.to_rfc3339()),
+            civil_dusk: t.civil_dusk.map(|t| t.to_rfc3339()),
+            daylight_minutes: t.daylight_minutes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sun_view() {
+        let param = SunParam {
+            date: Some("2026-01-15".to_string()),
+        };
+        let view = sun_view(&param, 35.6895, 139.6917).unwrap();
+        assert_eq!(view.date, "2026-01-15");
+        assert!(view.sunrise.unwrap().starts_with("2026-01-14T21:5"));
+
+        let param = SunParam {
+            date: Some("2026/01/15".to_string()),
+        };
+        assert!(sun_view(&param, 35.6895, 139.6917).is_err());
+
+        let param = SunParam { date: None };
+        assert!(sun_view(&param, 35.6895, 139.6917).is_ok());
+    }
+}
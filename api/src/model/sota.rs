@@ -1,13 +1,15 @@
 use chrono::NaiveDate;
-use serde::{Deserialize, Serialize};
-use typeshare::typeshare;
-use utoipa::ToSchema;
+use serde::Deserialize;
 
 use common::utils::maidenhead;
 use domain::model::event::PagenatedResult;
 use domain::model::sota::SotaReference;
 use domain::model::Maidenhead;
 
+use crate::model::FromModel;
+
+pub use dto::sota::{PagenatedResponse, SotaRefView, SotaSearchView, UpdateRefRequest};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct CreateRefRequest {
@@ -83,108 +85,59 @@ impl From<CreateRefRequest> for Vec<SotaReference> {
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct UpdateRefRequest {
-    pub summit_code: String,
-    pub association_name: String,
-    pub region_name: String,
-    pub summit_name: String,
-    pub summit_name_j: String,
-    pub city: String,
-    pub city_j: String,
-    pub alt_m: i32,
-    pub alt_ft: i32,
-    pub grid_ref1: String,
-    pub grid_ref2: String,
-    pub longitude: f64,
-    pub latitude: f64,
-    pub points: i32,
-    pub bonus_points: i32,
-    pub valid_from: String,
-    pub valid_to: String,
-    pub activation_count: i32,
-    pub activation_date: Option<String>,
-    pub activation_call: Option<String>,
+/// 更新リクエストをサミットにする
+pub fn build_references(req: UpdateRefRequest) -> Vec<SotaReference> {
+    let UpdateRefRequest {
+        summit_code,
+        association_name,
+        region_name,
+        summit_name,
+        summit_name_j,
+        city,
+        city_j,
+        alt_m,
+        alt_ft,
+        grid_ref1,
+        grid_ref2,
+        longitude,
+        latitude,
+        points,
+        bonus_points,
+        valid_from,
+        valid_to,
+        activation_count,
+        activation_date,
+        activation_call,
+    } = req;
+    let request = SotaReference {
+        summit_code,
+        association_name,
+        region_name,
+        summit_name,
+        summit_name_j: Some(summit_name_j),
+        city: Some(city),
+        city_j: Some(city_j),
+        alt_m,
+        alt_ft,
+        grid_ref1,
+        grid_ref2,
+        longitude,
+        latitude,
+        maidenhead: maidenhead(longitude, latitude),
+        points,
+        bonus_points,
+        valid_from: NaiveDate::parse_from_str(&valid_from, "%d/%m/%Y").unwrap(),
+        valid_to: NaiveDate::parse_from_str(&valid_to, "%d/%m/%Y").unwrap(),
+        activation_count,
+        activation_date,
+        activation_call,
+    };
+    vec![request]
 }
 
-impl From<UpdateRefRequest> for Vec<SotaReference> {
-    fn from(value: UpdateRefRequest) -> Self {
-        let UpdateRefRequest {
-            summit_code,
-            association_name,
-            region_name,
-            summit_name,
-            summit_name_j,
-            city,
-            city_j,
-            alt_m,
-            alt_ft,
-            grid_ref1,
-            grid_ref2,
-            longitude,
-            latitude,
-            points,
-            bonus_points,
-            valid_from,
-            valid_to,
-            activation_count,
-            activation_date,
-            activation_call,
-        } = value;
-        let request = SotaReference {
-            summit_code,
-            association_name,
-            region_name,
-            summit_name,
-            summit_name_j: Some(summit_name_j),
-            city: Some(city),
-            city_j: Some(city_j),
-            alt_m,
-            alt_ft,
-            grid_ref1,
-            grid_ref2,
-            longitude,
-            latitude,
-            maidenhead: maidenhead(longitude, latitude),
-            points,
-            bonus_points,
-            valid_from: NaiveDate::parse_from_str(&valid_from, "%d/%m/%Y").unwrap(),
-            valid_to: NaiveDate::parse_from_str(&valid_to, "%d/%m/%Y").unwrap(),
-            activation_count,
-            activation_date,
-            activation_call,
-        };
-        vec![request]
-    }
-}
-
-/// SOTAリファレンス詳細ビュー
-#[derive(Debug, Serialize, Default, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SotaRefView {
-    pub summit_code: String,
-    pub association_name: String,
-    pub region_name: String,
-    pub summit_name: String,
-    pub summit_name_j: Option<String>,
-    pub city: Option<String>,
-    pub city_j: Option<String>,
-    pub alt_m: i32,
-    pub longitude: f64,
-    pub latitude: f64,
-    pub maidenhead: Maidenhead,
-    pub points: i32,
-    pub bonus_points: i32,
-    pub activation_count: i32,
-    pub activation_date: Option<String>,
-    pub activation_call: Option<String>,
-}
-
-impl From<SotaReference> for SotaRefView {
+impl FromModel<SotaReference> for SotaRefView {
     #[allow(unused_variables)]
-    fn from(value: SotaReference) -> Self {
+    fn from_model(value: SotaReference) -> Self {
         let SotaReference {
             summit_code,
             association_name,
@@ -223,16 +176,19 @@ impl From<SotaReference> for SotaRefView {
             maidenhead,
             points,
             bonus_points,
+            valid_from: valid_from.to_string(),
+            valid_to: valid_to.to_string(),
             activation_count,
             activation_date,
             activation_call,
+            sun: None,
         }
     }
 }
 
-impl From<(Maidenhead, SotaReference)> for SotaRefView {
+impl FromModel<(Maidenhead, SotaReference)> for SotaRefView {
     #[allow(unused_variables)]
-    fn from((maidenhead, value): (Maidenhead, SotaReference)) -> Self {
+    fn from_model((maidenhead, value): (Maidenhead, SotaReference)) -> Self {
         let SotaReference {
             summit_code,
             association_name,
@@ -271,25 +227,18 @@ impl From<(Maidenhead, SotaReference)> for SotaRefView {
             maidenhead,
             points,
             bonus_points,
+            valid_from: valid_from.to_string(),
+            valid_to: valid_to.to_string(),
             activation_count,
             activation_date,
             activation_call,
+            sun: None,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct PagenatedResponse<SOTAReference> {
-    pub total: i32,
-    pub limit: i32,
-    pub offset: i32,
-    pub results: Vec<SOTAReference>,
-}
-
-impl From<PagenatedResult<SotaReference>> for PagenatedResponse<SotaRefView> {
-    fn from(pagenated: PagenatedResult<SotaReference>) -> Self {
+impl FromModel<PagenatedResult<SotaReference>> for PagenatedResponse<SotaRefView> {
+    fn from_model(pagenated: PagenatedResult<SotaReference>) -> Self {
         PagenatedResponse {
             total: pagenated.total as i32,
             limit: pagenated.limit,
@@ -297,29 +246,14 @@ impl From<PagenatedResult<SotaReference>> for PagenatedResponse<SotaRefView> {
             results: pagenated
                 .results
                 .into_iter()
-                .map(SotaRefView::from)
+                .map(SotaRefView::from_model)
                 .collect(),
         }
     }
 }
 
-/// SOTA検索結果ビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SotaSearchView {
-    pub code: String,
-    pub name: String,
-    pub name_j: Option<String>,
-    pub alt: i32,
-    pub lon: f64,
-    pub lat: f64,
-    pub pts: i32,
-    pub count: i32,
-}
-
-impl From<SotaReference> for SotaSearchView {
-    fn from(value: SotaReference) -> Self {
+impl FromModel<SotaReference> for SotaSearchView {
+    fn from_model(value: SotaReference) -> Self {
         let SotaReference {
             summit_code,
             summit_name,
@@ -348,6 +282,7 @@ impl From<SotaReference> for SotaSearchView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::NaiveDate;
     use domain::model::sota::SotaReference;
 
@@ -384,7 +319,7 @@ mod tests {
     #[test]
     fn test_sota_ref_view_from_reference() {
         let reference = create_test_sota_reference();
-        let view: SotaRefView = reference.into();
+        let view: SotaRefView = reference.into_dto();
 
         assert_eq!(view.summit_code, "JA/TK-001");
         assert_eq!(view.association_name, "Japan");
@@ -413,7 +348,7 @@ mod tests {
         reference.activation_date = None;
         reference.activation_call = None;
 
-        let view: SotaRefView = reference.into();
+        let view: SotaRefView = reference.into_dto();
 
         assert!(view.summit_name_j.is_none());
         assert!(view.city.is_none());
@@ -429,7 +364,7 @@ mod tests {
     #[test]
     fn test_sota_search_view_from_reference() {
         let reference = create_test_sota_reference();
-        let view: SotaSearchView = reference.into();
+        let view: SotaSearchView = reference.into_dto();
 
         assert_eq!(view.code, "JA/TK-001");
         assert_eq!(view.name, "Mt. Takao");
@@ -455,7 +390,7 @@ mod tests {
             results: vec![reference],
         };
 
-        let response: PagenatedResponse<SotaRefView> = pagenated.into();
+        let response: PagenatedResponse<SotaRefView> = pagenated.into_dto();
 
         assert_eq!(response.total, 100);
         assert_eq!(response.limit, 10);
@@ -473,7 +408,7 @@ mod tests {
             results: vec![],
         };
 
-        let response: PagenatedResponse<SotaRefView> = pagenated.into();
+        let response: PagenatedResponse<SotaRefView> = pagenated.into_dto();
 
         assert_eq!(response.total, 0);
         assert!(response.results.is_empty());
@@ -486,7 +421,7 @@ mod tests {
     #[test]
     fn test_sota_ref_view_json_serialization() {
         let reference = create_test_sota_reference();
-        let view: SotaRefView = reference.into();
+        let view: SotaRefView = reference.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -503,7 +438,7 @@ mod tests {
     #[test]
     fn test_sota_search_view_json_serialization() {
         let reference = create_test_sota_reference();
-        let view: SotaSearchView = reference.into();
+        let view: SotaSearchView = reference.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
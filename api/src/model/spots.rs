@@ -1,13 +1,17 @@
 use chrono::{NaiveDateTime, TimeZone, Utc};
 use common::utils::call_to_operator;
-use serde::{Deserialize, Serialize};
-use typeshare::typeshare;
-use utoipa::ToSchema;
+use serde::Deserialize;
 
 use common::error::{AppError, AppResult};
 use domain::model::activation::{Spot, SpotLog};
+use domain::model::needed::Needed;
 use domain::model::AwardProgram;
 
+use crate::model::dxcc::DxccView;
+use crate::model::FromModel;
+
+pub use dto::spots::{NeededView, SpotView};
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SotaSpot {
@@ -45,6 +49,10 @@ impl From<SotaSpot> for AppResult<Spot> {
             mode: s.mode,
             spotter: s.callsign,
             comment: s.comments,
+            frequency_hz: None,
+            band: None,
+            canonical_mode: None,
+            spotters: Vec::new(),
         })
     }
 }
@@ -91,32 +99,31 @@ impl From<PotaSpot> for AppResult<Spot> {
             mode: s.mode,
             spotter: s.spotter,
             comment: s.comments,
+            frequency_hz: None,
+            band: None,
+            canonical_mode: None,
+            spotters: Vec::new(),
         })
     }
 }
 
-/// スポットビュー
-#[derive(Debug, Serialize, ToSchema)]
-#[typeshare]
-#[serde(rename_all = "camelCase")]
-pub struct SpotView {
-    pub program: String,
-    pub spot_id: i32,
-    pub reference: String,
-    pub reference_detail: String,
-    pub activator: String,
-    pub activator_name: Option<String>,
-    pub spot_time: String,
-    pub frequency: String,
-    pub mode: String,
-    pub spotter: String,
-    pub comment: Option<String>,
-    pub qsos: Option<i32>,
+/// 利用者のログから見た未交信フラグ
+pub fn needed_view(program: &AwardProgram, needed: Needed) -> NeededView {
+    let sota = *program == AwardProgram::SOTA;
+    NeededView {
+        new_summit: sota && needed.new_reference,
+        new_park: !sota && needed.new_reference,
+        new_band: needed.new_band,
+        new_mode: needed.new_mode,
+        priority: needed.priority(),
+    }
 }
 
-impl From<SpotLog> for SpotView {
-    fn from(s: SpotLog) -> Self {
+impl FromModel<SpotLog> for SpotView {
+    fn from_model(s: SpotLog) -> Self {
         let qsos = s.qsos;
+        let needed = s.needed.map(|n| needed_view(&s.spot.program, n));
+        let dxcc = s.dxcc.map(DxccView::from_model);
         let s = s.spot;
         Self {
             program: s.program.into(),
@@ -130,7 +137,13 @@ impl From<SpotLog> for SpotView {
             mode: s.mode,
             spotter: s.spotter,
             comment: s.comment,
+            frequency_hz: s.frequency_hz,
+            band: s.band,
+            canonical_mode: s.canonical_mode,
+            spotters: s.spotters,
             qsos,
+            needed,
+            dxcc,
         }
     }
 }
@@ -138,22 +151,21 @@ impl From<SpotLog> for SpotView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::IntoDto;
     use chrono::TimeZone;
+    use domain::model::activation::make_test_spot;
 
     fn create_test_spot(program: AwardProgram) -> Spot {
         Spot {
             program,
             spot_id: 99999,
-            reference: "JA/TK-001".to_string(),
             reference_detail: "Mt. Takao".to_string(),
             operator: "JA1ABC".to_string(),
-            activator: "JA1ABC/P".to_string(),
             activator_name: Some("Taro Yamada".to_string()),
             spot_time: Utc.with_ymd_and_hms(2024, 6, 15, 10, 30, 0).unwrap(),
             frequency: "14.285".to_string(),
-            mode: "SSB".to_string(),
-            spotter: "JA2XYZ".to_string(),
             comment: Some("Good signal".to_string()),
+            ..make_test_spot("JA1ABC/P", "JA/TK-001")
         }
     }
 
@@ -161,6 +173,8 @@ mod tests {
         SpotLog {
             spot: create_test_spot(program),
             qsos,
+            needed: None,
+            dxcc: None,
         }
     }
 
@@ -171,7 +185,7 @@ mod tests {
     #[test]
     fn test_spot_view_from_spot_log() {
         let spot_log = create_test_spot_log(AwardProgram::SOTA, Some(15));
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         assert_eq!(view.program, "SOTA");
         assert_eq!(view.spot_id, 99999);
@@ -189,7 +203,7 @@ mod tests {
     #[test]
     fn test_spot_view_from_pota_spot_log() {
         let spot_log = create_test_spot_log(AwardProgram::POTA, Some(25));
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         assert_eq!(view.program, "POTA");
         assert_eq!(view.qsos, Some(25));
@@ -198,7 +212,7 @@ mod tests {
     #[test]
     fn test_spot_view_qsos_none() {
         let spot_log = create_test_spot_log(AwardProgram::SOTA, None);
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         assert!(view.qsos.is_none());
     }
@@ -206,7 +220,7 @@ mod tests {
     #[test]
     fn test_spot_view_spot_time_format() {
         let spot_log = create_test_spot_log(AwardProgram::SOTA, None);
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         // RFC3339形式で出力される
         assert!(view.spot_time.contains("2024-06-15"));
@@ -219,7 +233,7 @@ mod tests {
         spot_log.spot.activator_name = None;
         spot_log.spot.comment = None;
 
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         assert!(view.activator_name.is_none());
         assert!(view.comment.is_none());
@@ -365,7 +379,7 @@ mod tests {
     #[test]
     fn test_spot_view_json_serialization() {
         let spot_log = create_test_spot_log(AwardProgram::SOTA, Some(20));
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -381,7 +395,7 @@ mod tests {
     #[test]
     fn test_spot_view_json_null_qsos() {
         let spot_log = create_test_spot_log(AwardProgram::POTA, None);
-        let view: SpotView = spot_log.into();
+        let view: SpotView = spot_log.into_dto();
 
         let json = serde_json::to_string(&view).unwrap();
 
@@ -0,0 +1,119 @@
+use common::error::AppResult;
+use domain::model::id::UserId;
+use domain::model::watch::{Watchlist, WebhookDelivery};
+
+use crate::model::param::parse_program;
+use crate::model::FromModel;
+
+pub use dto::watch::{WatchRequest, WatchView, WebhookDeliveryView};
+
+/// 配信記録の取得件数の既定値
+pub const DELIVERIES_DEFAULT_LIMIT: i64 = 100;
+
+fn normalize(values: Vec<String>, upper: bool) -> Vec<String> {
+    values
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            if upper {
+                v.to_ascii_uppercase()
+            } else {
+                v.to_ascii_lowercase()
+            }
+        })
+        .collect()
+}
+
+pub fn build_watch(req: WatchRequest, user_id: UserId, watch_id: i64) -> AppResult<Watchlist> {
+    let programs = req
+        .programs
+        .iter()
+        .map(|p| parse_program(p.trim()))
+        .collect::<AppResult<Vec<_>>>()?;
+    let now = chrono::Utc::now();
+    Ok(Watchlist {
+        watch_id,
+        user_id,
+        name: req.name.trim().to_string(),
+        callsigns: normalize(req.callsigns, true),
+        patterns: normalize(req.patterns, true),
+        programs,
+        bands: normalize(req.bands, false),
+        webhook_url: req.webhook_url.trim().to_string(),
+        secret: req.secret.unwrap_or_default(),
+        enabled: req.enabled.unwrap_or(true),
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+impl FromModel<Watchlist> for WatchView {
+    fn from_model(w: Watchlist) -> Self {
+        Self {
+            watch_id: w.watch_id,
+            name: w.name,
+            callsigns: w.callsigns,
+            patterns: w.patterns,
+            programs: w.programs.into_iter().map(String::from).collect(),
+            bands: w.bands,
+            webhook_url: w.webhook_url,
+            secret: w.secret,
+            enabled: w.enabled,
+            created_at: w.created_at.to_rfc3339(),
+            updated_at: w.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl FromModel<WebhookDelivery> for WebhookDeliveryView {
+    fn from_model(d: WebhookDelivery) -> Self {
+        let pending = d.status == domain::model::watch::DeliveryStatus::Pending;
+        Self {
+            delivery_id: d.delivery_id,
+            event: d.event,
+            event_key: d.event_key,
+            status: d.status.as_str().to_string(),
+            attempts: d.attempts,
+            status_code: d.status_code,
+            error: d.error,
+            created_at: d.created_at.to_rfc3339(),
+            next_attempt_at: pending.then(|| d.next_attempt_at.to_rfc3339()),
+            delivered_at: d.delivered_at.map(|t| t.to_rfc3339()),
+            payload: d.payload,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_watch() {
+        let req: WatchRequest = serde_json::from_value(serde_json::json!({
+            "name": " club ",
+            "callsigns": ["ja1abc", " "],
+            "patterns": ["ja/nn-*"],
+            "programs": ["SOTA"],
+            "bands": ["20M"],
+            "webhookUrl": "https://example.com/hook"
+        }))
+        .unwrap();
+        let w = build_watch(req, UserId::from("u".to_string()), 0).unwrap();
+        assert_eq!(w.name, "club");
+        assert_eq!(w.callsigns, vec!["JA1ABC"]);
+        assert_eq!(w.patterns, vec!["JA/NN-*"]);
+        assert_eq!(w.bands, vec!["20m"]);
+        assert!(w.enabled);
+        assert!(w.secret.is_empty());
+
+        let req: WatchRequest = serde_json::from_value(serde_json::json!({
+            "name": "x",
+            "programs": ["iota"],
+            "webhookUrl": "https://example.com/hook"
+        }))
+        .unwrap();
+        assert!(build_watch(req, UserId::from("u".to_string()), 0).is_err());
+    }
+}
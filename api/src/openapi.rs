@@ -1,10 +1,51 @@
 //! OpenAPI ドキュメント生成モジュール
 
 use common::config::OpenApiLevel;
+use common::error::ErrorResponse;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::openapi::OpenApi;
+use utoipa::Modify;
 
+use crate::handler::activation::{ActivationApi, ActivationPostApi};
+use crate::handler::admin::AdminApi;
+use crate::handler::archive::ArchiveApi;
+use crate::handler::auth::AuthApi;
+use crate::handler::award_admin::AwardAdminApi;
+use crate::handler::fle::FleApi;
 use crate::handler::health::HealthApi;
+use crate::handler::locator::{LocatorAdminApi, LocatorApi};
+use crate::handler::logconv::LogconvApi;
+use crate::handler::metrics::MetricsApi;
+use crate::handler::pota::{PotaAdminApi, PotaApi};
+use crate::handler::propagation::PropagationApi;
 use crate::handler::search::SearchApi;
+use crate::handler::session::SessionApi;
+use crate::handler::sota::{SotaAdminApi, SotaApi};
+use crate::handler::watch::WatchApi;
+use crate::handler::wspr::WsprApi;
+use crate::model::param::ValidationErrorResponse;
+
+/// 認証付きエンドポイントが参照するセキュリティスキーム名
+pub const BEARER_AUTH: &str = "bearer_auth";
+
+/// Firebase IDトークンによるBearer認証を登録
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some("Firebase IDトークン（/api/v2/auth/signinで取得）"))
+                    .build(),
+            ),
+        );
+    }
+}
 
 /// SOTAApp2 API ドキュメントを生成（レベル別）
 ///
@@ -23,8 +64,8 @@ pub fn create_api_doc(level: OpenApiLevel) -> Option<OpenApi> {
 fn create_public_api_doc() -> OpenApi {
     use utoipa::OpenApi;
 
-    let mut doc = HealthApi::openapi();
-    doc.merge(SearchApi::openapi());
+    let mut doc = public_paths();
+    add_error_schemas(&mut doc);
 
     set_api_info(&mut doc, "SOTAApp2 Public API");
     doc
@@ -34,14 +75,56 @@ fn create_public_api_doc() -> OpenApi {
 fn create_full_api_doc() -> OpenApi {
     use utoipa::OpenApi;
 
-    let mut doc = HealthApi::openapi();
-    doc.merge(SearchApi::openapi());
-    // TODO: 将来的にAdminApiを追加
+    let mut doc = public_paths();
+    doc.merge(SotaAdminApi::openapi());
+    doc.merge(PotaAdminApi::openapi());
+    doc.merge(LocatorAdminApi::openapi());
+    doc.merge(AuthApi::openapi());
+    doc.merge(AdminApi::openapi());
+    doc.merge(AwardAdminApi::openapi());
+    doc.merge(MetricsApi::openapi());
+    doc.merge(WatchApi::openapi());
+    doc.merge(ActivationPostApi::openapi());
+    doc.merge(SessionApi::openapi());
+    add_error_schemas(&mut doc);
+    SecurityAddon.modify(&mut doc);
 
     set_api_info(&mut doc, "SOTAApp2 API");
     doc
 }
 
+/// 認証不要のエンドポイント
+fn public_paths() -> OpenApi {
+    use utoipa::OpenApi;
+
+    let mut doc = HealthApi::openapi();
+    doc.merge(SearchApi::openapi());
+    doc.merge(SotaApi::openapi());
+    doc.merge(PotaApi::openapi());
+    doc.merge(ActivationApi::openapi());
+    doc.merge(ArchiveApi::openapi());
+    doc.merge(LocatorApi::openapi());
+    doc.merge(PropagationApi::openapi());
+    doc.merge(LogconvApi::openapi());
+    doc.merge(FleApi::openapi());
+    doc.merge(WsprApi::openapi());
+    doc
+}
+
+/// 共通のエラーレスポンススキーマを登録
+fn add_error_schemas(doc: &mut OpenApi) {
+    use utoipa::PartialSchema;
+
+    let components = doc.components.get_or_insert_with(Default::default);
+    components
+        .schemas
+        .insert("ErrorResponse".to_string(), ErrorResponse::schema());
+    components.schemas.insert(
+        "ValidationErrorResponse".to_string(),
+        ValidationErrorResponse::schema(),
+    );
+}
+
 /// API情報を設定
 fn set_api_info(doc: &mut OpenApi, title: &str) {
     doc.info.title = title.to_string();
@@ -101,4 +184,75 @@ mod tests {
         assert!(paths.contains_key("/api/v2/search/full"));
         assert!(paths.contains_key("/api/v2/search/brief"));
     }
+
+    #[test]
+    fn test_public_doc_contains_reference_and_activation_paths() {
+        let doc = create_api_doc(OpenApiLevel::Public).unwrap();
+        let paths = doc.paths.paths;
+
+        assert!(paths.contains_key("/api/v2/sota/summits/{summit_code}"));
+        assert!(paths.contains_key("/api/v2/pota/parks/{park_code}"));
+        assert!(paths.contains_key("/api/v2/activation/spots/sota"));
+        assert!(paths.contains_key("/api/v2/activation/stats/svg"));
+        assert!(paths.contains_key("/api/v2/activation/alerts.ics"));
+        assert!(paths.contains_key("/api/v2/activation/spots.atom"));
+        assert!(paths.contains_key("/api/v2/archive/{program}/spots"));
+        assert!(paths.contains_key("/api/v2/archive/{program}/stats/activators"));
+        assert!(paths.contains_key("/api/v2/locator/mapcode"));
+        assert!(paths.contains_key("/api/v2/search/dxcc/{callsign}"));
+        assert!(paths.contains_key("/api/v2/propagation/geomag"));
+        assert!(paths.contains_key("/api/v2/propagation/grayline"));
+        assert!(paths.contains_key("/api/v2/logconv/hamlog"));
+        assert!(paths.contains_key("/api/v2/fle/compile"));
+        assert!(paths.contains_key("/api/v2/wspr/svg"));
+    }
+
+    #[test]
+    fn test_public_doc_excludes_protected_operations() {
+        let doc = create_api_doc(OpenApiLevel::Public).unwrap();
+        let paths = doc.paths.paths;
+
+        assert!(!paths.contains_key("/api/v2/admin/jobs"));
+        assert!(!paths.contains_key("/api/v2/admin/cty"));
+        assert!(!paths.contains_key("/api/v2/watch"));
+        assert!(!paths.contains_key("/api/v2/activation/posts"));
+        assert!(!paths.contains_key("/api/v2/session"));
+        let spots = &paths["/api/v2/activation/spots"];
+        assert!(spots.get.is_some());
+        assert!(spots.post.is_none());
+        assert!(!paths.contains_key("/api/v2/sota/import"));
+        assert!(!paths.contains_key("/api/v2/locator/jcc-jcg/import"));
+        let summit = &paths["/api/v2/sota/summits/{summit_code}"];
+        assert!(summit.get.is_some());
+        assert!(summit.put.is_none());
+        assert!(summit.delete.is_none());
+    }
+
+    #[test]
+    fn test_full_doc_contains_protected_paths_with_security() {
+        let doc = create_api_doc(OpenApiLevel::All).unwrap();
+        let paths = &doc.paths.paths;
+
+        assert!(paths.contains_key("/api/v2/admin/jobs"));
+        assert!(paths.contains_key("/api/v2/admin/cty"));
+        assert!(paths.contains_key("/api/v2/admin/award/config"));
+        assert!(paths.contains_key("/api/v2/watch/{watch_id}/deliveries"));
+        assert!(paths.contains_key("/api/v2/activation/posts"));
+        assert!(paths.contains_key("/api/v2/session/{session_id}/qsos"));
+        let alerts = &paths["/api/v2/activation/alerts"];
+        assert!(alerts.get.is_some());
+        assert!(alerts.post.as_ref().expect("post alert").security.is_some());
+        assert!(paths.contains_key("/api/v2/auth/signin"));
+        assert!(paths.contains_key("/metrics"));
+
+        let summit = &paths["/api/v2/sota/summits/{summit_code}"];
+        assert!(summit.get.is_some());
+        let put = summit.put.as_ref().expect("update operation");
+        assert!(put.security.is_some());
+
+        let components = doc.components.as_ref().unwrap();
+        assert!(components.security_schemes.contains_key(BEARER_AUTH));
+        assert!(components.schemas.contains_key("ErrorResponse"));
+        assert!(components.schemas.contains_key("SpotView"));
+    }
 }
@@ -0,0 +1,218 @@
+/// APRS記録ファイルの再生テスト
+///
+/// test_data の記録を記録時刻のまま処理し、送信されるメッセージと状態の時刻を確認する。
+/// APRSログはメモリ上に保持し、その他のリポジトリはモックで置き換える。
+///
+/// 実行方法:
+///   cargo test --package api --test aprs_replay
+use adapter::aprs_replay::{position_callsigns, read_recording, AprsReplay, SentMessage};
+use api::aggregator::aprs_packet::replay_packets_with;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use common::config::AppConfig;
+use common::utils::calculate_distance;
+use domain::model::activation::{make_test_alert, make_test_spot, make_test_summit, Spot};
+use domain::model::aprslog::AprsLog;
+use domain::model::event::FindAprs;
+use domain::model::sota::SotaReference;
+use domain::repository::{
+    activation::MockActivationRepositry,
+    aprs::MockAprsLogRepository,
+    pota::MockPotaRepository,
+    sota::MockSotaRepository,
+    watch::{MockWatchRepository, MockWebhookRepository},
+};
+use service::implement::admin_periodic::AdminPeriodicServiceImpl;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+const SAMPLE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../test_data/aprs_replay_sample.jsonl"
+);
+
+fn t(hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 5, 3, hour, min, sec).unwrap()
+}
+
+fn summit() -> SotaReference {
+    SotaReference {
+        summit_code: "JA/YN-101".to_string(),
+        summit_name: "Mt. Replay".to_string(),
+        city: Some("Yamanashi".to_string()),
+        alt_m: 1400,
+        latitude: 34.9995,
+        longitude: 139.0038,
+        ..make_test_summit(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2099, 12, 31).unwrap(),
+        )
+    }
+}
+
+fn config() -> AppConfig {
+    for (key, val) in [
+        ("DATABASE_URL", "sqlite::memory:"),
+        ("FIREBASE_API_KEY", "test-key"),
+        ("APRSUSER", "JL1NIE-10"),
+        ("APRSPASSWORD", "12345"),
+    ] {
+        unsafe {
+            std::env::set_var(key, val);
+        }
+    }
+    let mut config = AppConfig::new().unwrap();
+    config.aprs_arrival_mesg_regex = Some("^JA".to_string());
+    config
+}
+
+fn sota_repo() -> MockSotaRepository {
+    let mut repo = MockSotaRepository::new();
+    repo.expect_find_reference().returning(|query| {
+        let s = summit();
+        let found = match (&query.sota_code, &query.center) {
+            (Some(code), _) => *code == s.summit_code,
+            (None, Some(c)) => calculate_distance(c.lat, c.lon, s.latitude, s.longitude) <= c.rad,
+            _ => false,
+        };
+        Ok(if found { vec![s] } else { vec![] })
+    });
+    repo.expect_find_activation_zone().returning(|_| Ok(None));
+    repo
+}
+
+/// JA1ABCのアラートと、01:20に出たスポット
+fn act_repo() -> MockActivationRepositry {
+    let mut repo = MockActivationRepositry::new();
+    repo.expect_find_alerts()
+        .returning(|query| match query.operator.as_deref() {
+            Some("JA1ABC") => Ok(vec![make_test_alert("JA1ABC", "JA/YN-101")]),
+            _ => Ok(vec![]),
+        });
+    repo.expect_find_spots().returning(|query| {
+        let spot = Spot {
+            spot_time: t(1, 20, 0),
+            frequency: "7.032".to_string(),
+            mode: "CW".to_string(),
+            ..make_test_spot("JA1ABC", "JA/YN-101")
+        };
+        let issued = query
+            .issued_after
+            .is_none_or(|after| spot.spot_time >= after);
+        Ok(if issued { vec![spot] } else { vec![] })
+    });
+    repo
+}
+
+fn copy_log(log: &AprsLog) -> AprsLog {
+    AprsLog {
+        callsign: log.callsign.clone(),
+        destination: log.destination.clone(),
+        state: log.state.clone(),
+        longitude: log.longitude,
+        latitude: log.latitude,
+        altitude: log.altitude,
+        detail: log.detail.clone(),
+    }
+}
+
+fn matches(log: &AprsLog, query: &FindAprs) -> bool {
+    let callsign = query.callsign.as_ref().is_none_or(|c| {
+        c.callsign == log.callsign.callsign
+            && c.ssid.is_none_or(|ssid| log.callsign.ssid == Some(ssid))
+    });
+    let after = query
+        .after
+        .is_none_or(|after| log.state.time() >= after.naive_utc());
+    callsign && after
+}
+
+/// 書き込んだログを新しい順に返すAPRSログ
+fn aprs_log_repo(logs: Arc<Mutex<Vec<AprsLog>>>) -> MockAprsLogRepository {
+    let mut repo = MockAprsLogRepository::new();
+    let store = logs.clone();
+    repo.expect_insert_aprs_log().returning(move |log| {
+        store.lock().unwrap().push(log);
+        Ok(())
+    });
+    repo.expect_find_aprs_log().returning(move |query| {
+        let mut found: Vec<_> = logs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|l| matches(l, query))
+            .map(copy_log)
+            .collect();
+        found.sort_by_key(|l| std::cmp::Reverse(l.state.time()));
+        Ok(found)
+    });
+    repo
+}
+
+#[tokio::test]
+async fn replay_sample_sends_messages_at_recorded_times() {
+    let packets = read_recording(Path::new(SAMPLE)).unwrap();
+    let replay = Arc::new(AprsReplay::new(packets.clone(), 0.0));
+    let logs = Arc::new(Mutex::new(Vec::new()));
+    let service = AdminPeriodicServiceImpl {
+        act_repo: Arc::new(act_repo()),
+        aprs_repo: replay.clone(),
+        aprs_log_repo: Arc::new(aprs_log_repo(logs.clone())),
+        sota_repo: Arc::new(sota_repo()),
+        pota_repo: Arc::new(MockPotaRepository::new()),
+        watch_repo: Arc::new(MockWatchRepository::new()),
+        webhook: Arc::new(MockWebhookRepository::new()),
+        config: config(),
+        buddy_callsigns: Default::default(),
+        delivering: Default::default(),
+    };
+
+    let errors = replay_packets_with(
+        &service,
+        replay.as_ref(),
+        position_callsigns(&packets),
+        packets.len(),
+    )
+    .await;
+    assert_eq!(errors, 0);
+    assert!(replay.is_finished());
+
+    // スポットは現在時刻ではなく記録時刻から3時間以内のものが入る
+    let spots = "01:20-JA1ABC-7.032 ";
+    assert_eq!(
+        replay.sent_messages(),
+        vec![
+            SentMessage {
+                time: t(1, 28, 15),
+                addressee: "JA1ABC-7".to_string(),
+                message: format!(
+                    "Welcome to JA/YN-101. Mt. Replay 1400m 10pts.\nYamanashi\n{}",
+                    spots
+                ),
+            },
+            SentMessage {
+                time: t(1, 30, 2),
+                addressee: "JA1ABC-7".to_string(),
+                message: spots.to_string(),
+            },
+        ]
+    );
+
+    // 状態の時刻は記録時刻、気象は直前のオブジェクトの状態に付加される
+    let states: Vec<_> = logs
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|l| (l.state.name(), Utc.from_utc_datetime(&l.state.time())))
+        .collect();
+    assert_eq!(
+        states,
+        vec![
+            ("approaching", t(0, 2, 11)),
+            ("approaching", t(0, 31, 40)),
+            ("climbing", t(1, 5, 2)),
+            ("on_summit", t(1, 28, 15)),
+            ("on_summit", t(1, 29, 30)),
+            ("on_summit", t(1, 35, 0)),
+        ]
+    );
+}
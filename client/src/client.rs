@@ -0,0 +1,727 @@
+use reqwest::{header, multipart, Client, Method, RequestBuilder, Response, StatusCode, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+use dto::{
+    activation::ActivationView,
+    activity::ActivityStatsView,
+    admin::SystemMetrics,
+    alerts::{AlertView, ReliabilityView},
+    aprslog::{AprsLogView, Segments, Tracks},
+    archive::{ActivatorStatView, ReferenceYearStatView, SpotArchiveView},
+    auth::AuthRequest,
+    changelog::ReferenceChangeView,
+    dxcc::{CtyStatusView, DxccView},
+    geomag::GeomagView,
+    import::{ImportParam, ImportResult},
+    jobs::JobStatus,
+    locator::{CenturyCodeView, MapcodeView},
+    param::GetParam,
+    pota::{self, PotaLogHistView, PotaRefLogView, PotaRefView},
+    relay::{AlertPostRequest, RelayPostView, SpotPostRequest},
+    search::{SearchBriefResponse, SearchFullResponse, SearchResponse},
+    session::{QsoPostRequest, QsoPostView, SessionQsoView, SessionRequest, SessionView},
+    sota::{self, SotaRefView},
+    spots::SpotView,
+    watch::{WatchRequest, WatchView, WebhookDeliveryView},
+};
+
+use crate::error::{ClientError, ClientResult};
+
+/// APIのベースパス
+const API_PREFIX: &str = "api/v2";
+
+/// 既定のタイムアウト（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// SOTAApp2 APIクライアント
+///
+/// サーバーと同じDTOでレスポンスを受け取る。認証が必要なAPIは
+/// `with_token` または `sign_in` でIDトークンを設定してから呼び出す。
+#[derive(Clone, Debug)]
+pub struct ApiClient {
+    http: Client,
+    base_url: Url,
+    token: Option<String>,
+}
+
+impl ApiClient {
+    /// `https://example.com` のようなサーバーのURLからクライアントを作成
+    pub fn new(base_url: &str) -> ClientResult<Self> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
+            .build()
+            .map_err(ClientError::RequestError)?;
+        Self::with_client(base_url, http)
+    }
+
+    /// 任意の `reqwest::Client` を使うクライアントを作成
+    pub fn with_client(base_url: &str, http: Client) -> ClientResult<Self> {
+        let base_url = Url::parse(base_url)
+            .map_err(|e| ClientError::InvalidUrl(format!("{base_url}: {e}")))?;
+        if base_url.cannot_be_a_base() {
+            return Err(ClientError::InvalidUrl(base_url.to_string()));
+        }
+        Ok(Self {
+            http,
+            base_url,
+            token: None,
+        })
+    }
+
+    /// Firebase IDトークンを設定
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// 設定中のIDトークン
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// メールアドレスとパスワードでサインインし、IDトークンを保持する
+    pub async fn sign_in(&mut self, email: &str, password: &str) -> ClientResult<()> {
+        let req = AuthRequest {
+            email: email.to_string(),
+            password: password.to_string(),
+        };
+        let res = self
+            .request(Method::POST, &["auth", "signin"])
+            .json(&req)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        let res = check(res).await?;
+        let token = res
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|h| h.to_str().ok())
+            .filter(|t| !t.is_empty())
+            .ok_or(ClientError::MissingToken)?;
+        self.token = Some(token.to_string());
+        Ok(())
+    }
+
+    /// 名前・コードでリファレンスを検索
+    pub async fn search(&self, param: &GetParam) -> ClientResult<SearchResponse> {
+        self.get(&["search"], param).await
+    }
+
+    /// 詳細情報付きでリファレンスを検索
+    pub async fn search_full(&self, param: &GetParam) -> ClientResult<SearchFullResponse> {
+        self.get(&["search", "full"], param).await
+    }
+
+    /// 地図表示用の簡易形式でリファレンスを検索
+    pub async fn search_brief(&self, param: &GetParam) -> ClientResult<SearchBriefResponse> {
+        self.get(&["search", "brief"], param).await
+    }
+
+    /// サミットを取得（日の出・日の入りはその地点の今日）
+    pub async fn sota_summit(&self, summit_code: &str) -> ClientResult<SotaRefView> {
+        self.get(&["sota", "summits", summit_code], &()).await
+    }
+
+    /// サミットを指定日（YYYY-MM-DD）の日の出・日の入り付きで取得
+    pub async fn sota_summit_on(&self, summit_code: &str, date: &str) -> ClientResult<SotaRefView> {
+        self.get(&["sota", "summits", summit_code], &[("date", date)])
+            .await
+    }
+
+    /// サミット一覧（ページング）
+    pub async fn sota_summits(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<sota::PagenatedResponse<SotaRefView>> {
+        self.get(&["sota", "summits"], param).await
+    }
+
+    /// サミットの変更履歴
+    pub async fn sota_changes(&self, param: &GetParam) -> ClientResult<Vec<ReferenceChangeView>> {
+        self.get(&["sota", "changes"], param).await
+    }
+
+    /// 公園を取得（日の出・日の入りはその地点の今日）
+    pub async fn pota_park(&self, park_code: &str) -> ClientResult<PotaRefView> {
+        self.get(&["pota", "parks", park_code], &()).await
+    }
+
+    /// 公園を指定日（YYYY-MM-DD）の日の出・日の入り付きで取得
+    pub async fn pota_park_on(&self, park_code: &str, date: &str) -> ClientResult<PotaRefView> {
+        self.get(&["pota", "parks", park_code], &[("date", date)])
+            .await
+    }
+
+    /// 公園一覧（ページング）
+    pub async fn pota_parks(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<pota::PagenatedResponse<PotaRefView>> {
+        self.get(&["pota", "parks"], param).await
+    }
+
+    /// 公園をログの交信状況付きで検索
+    pub async fn pota_parks_search(&self, param: &GetParam) -> ClientResult<Vec<PotaRefLogView>> {
+        self.get(&["pota", "parks", "search"], param).await
+    }
+
+    /// 公園の変更履歴
+    pub async fn pota_changes(&self, param: &GetParam) -> ClientResult<Vec<ReferenceChangeView>> {
+        self.get(&["pota", "changes"], param).await
+    }
+
+    /// POTAログの履歴
+    pub async fn pota_log(&self, log_id: &str) -> ClientResult<PotaLogHistView> {
+        self.get(&["pota", "log", log_id], &()).await
+    }
+
+    /// SOTA/POTAのスポット（リファレンスごとにグループ化）
+    pub async fn spots(&self, param: &GetParam) -> ClientResult<Vec<ActivationView<SpotView>>> {
+        self.get(&["activation", "spots"], param).await
+    }
+
+    /// SOTAのスポット
+    pub async fn sota_spots(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ActivationView<SpotView>>> {
+        self.get(&["activation", "spots", "sota"], param).await
+    }
+
+    /// POTAのスポット
+    pub async fn pota_spots(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ActivationView<SpotView>>> {
+        self.get(&["activation", "spots", "pota"], param).await
+    }
+
+    /// SOTA/POTAのアラート（リファレンスごとにグループ化）
+    pub async fn alerts(&self, param: &GetParam) -> ClientResult<Vec<ActivationView<AlertView>>> {
+        self.get(&["activation", "alerts"], param).await
+    }
+
+    /// SOTAのアラート
+    pub async fn sota_alerts(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ActivationView<AlertView>>> {
+        self.get(&["activation", "alerts", "sota"], param).await
+    }
+
+    /// POTAのアラート
+    pub async fn pota_alerts(
+        &self,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ActivationView<AlertView>>> {
+        self.get(&["activation", "alerts", "pota"], param).await
+    }
+
+    /// アラートのiCalendar
+    pub async fn alerts_ical(&self, param: &GetParam) -> ClientResult<String> {
+        self.get_text(&["activation", "alerts.ics"], param).await
+    }
+
+    /// 最近のスポットのAtomフィード
+    pub async fn spots_atom(&self, param: &GetParam) -> ClientResult<String> {
+        self.get_text(&["activation", "spots.atom"], param).await
+    }
+
+    /// スポットの投稿と中継（要認証）
+    pub async fn post_spot(&self, req: &SpotPostRequest) -> ClientResult<RelayPostView> {
+        self.send_json(Method::POST, &["activation", "spots"], req)
+            .await
+    }
+
+    /// アラートの投稿と中継（要認証）
+    pub async fn post_alert(&self, req: &AlertPostRequest) -> ClientResult<RelayPostView> {
+        self.send_json(Method::POST, &["activation", "alerts"], req)
+            .await
+    }
+
+    /// 自分の投稿と中継結果（要認証）
+    pub async fn posts(&self, limit: Option<i64>) -> ClientResult<Vec<RelayPostView>> {
+        self.get(&["activation", "posts"], &[("limit", limit)])
+            .await
+    }
+
+    /// APRSログ
+    pub async fn aprs_log(&self, param: &GetParam) -> ClientResult<Vec<AprsLogView>> {
+        self.get(&["activation", "aprs", "log"], param).await
+    }
+
+    /// APRSトラック
+    pub async fn aprs_track(&self, param: &GetParam) -> ClientResult<Tracks> {
+        self.get(&["activation", "aprs", "track"], param).await
+    }
+
+    /// アクティベーションごとのAPRSトラック
+    pub async fn aprs_activations(&self, param: &GetParam) -> ClientResult<Segments> {
+        self.get(&["activation", "aprs", "activations"], param)
+            .await
+    }
+
+    /// 保存済みのAPRSトラック一覧
+    pub async fn aprs_archives(&self, callsign: &str) -> ClientResult<Segments> {
+        self.get(&["activation", "aprs", "archive", callsign], &())
+            .await
+    }
+
+    /// アラートどおりにQRVしたかの実績
+    pub async fn reliability(
+        &self,
+        callsign: &str,
+        param: &GetParam,
+    ) -> ClientResult<ReliabilityView> {
+        self.get(&["activation", "reliability", callsign], param)
+            .await
+    }
+
+    /// バンド・モード・時間帯ごとのスポット数
+    pub async fn activity_stats(&self, param: &GetParam) -> ClientResult<ActivityStatsView> {
+        self.get(&["activation", "stats"], param).await
+    }
+
+    /// アーカイブ済みスポット（programは"sota"または"pota"）
+    pub async fn spot_archive(
+        &self,
+        program: &str,
+        param: &GetParam,
+    ) -> ClientResult<Vec<SpotArchiveView>> {
+        self.get(&["archive", program, "spots"], param).await
+    }
+
+    /// アーカイブ済みアラート
+    pub async fn alert_archive(
+        &self,
+        program: &str,
+        param: &GetParam,
+    ) -> ClientResult<Vec<AlertView>> {
+        self.get(&["archive", program, "alerts"], param).await
+    }
+
+    /// リファレンスごと・年ごとのアクティベーション数
+    pub async fn reference_year_stats(
+        &self,
+        program: &str,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ReferenceYearStatView>> {
+        self.get(&["archive", program, "stats", "references"], param)
+            .await
+    }
+
+    /// アクティベーターのランキング
+    pub async fn activator_stats(
+        &self,
+        program: &str,
+        param: &GetParam,
+    ) -> ClientResult<Vec<ActivatorStatView>> {
+        self.get(&["archive", program, "stats", "activators"], param)
+            .await
+    }
+
+    /// 市区町村コードからJCC/JCGコード、lon/latからグリッドロケーター
+    pub async fn century_code(&self, param: &GetParam) -> ClientResult<CenturyCodeView> {
+        self.get(&["locator", "jcc-jcg"], param).await
+    }
+
+    /// lon/latのマップコード
+    pub async fn mapcode(&self, param: &GetParam) -> ClientResult<MapcodeView> {
+        self.get(&["locator", "mapcode"], param).await
+    }
+
+    /// 最新の地磁気指数
+    pub async fn geomag(&self) -> ClientResult<GeomagView> {
+        self.get(&["propagation", "geomag"], &()).await
+    }
+
+    /// グレイライン（GeoJSON、時刻はRFC3339・省略時は現在）
+    pub async fn grayline(&self, time: Option<&str>) -> ClientResult<serde_json::Value> {
+        self.get(&["propagation", "grayline"], &[("time", time)])
+            .await
+    }
+
+    /// コールサインのDXCCエンティティ・ゾーン
+    pub async fn dxcc(&self, callsign: &str) -> ClientResult<DxccView> {
+        self.get(&["search", "dxcc", callsign], &()).await
+    }
+
+    /// SOTAサミットリストのインポート（要認証）
+    pub async fn import_sota_summits(
+        &self,
+        csv: String,
+        dry_run: bool,
+    ) -> ClientResult<ImportResult> {
+        self.import(&["sota", "import"], csv, dry_run).await
+    }
+
+    /// 国内SOTAサミットリストのインポート（要認証）
+    pub async fn import_sota_ja(&self, csv: String, dry_run: bool) -> ClientResult<ImportResult> {
+        self.import(&["sota", "import", "ja"], csv, dry_run).await
+    }
+
+    /// POTA公園リストのインポート（要認証）
+    pub async fn import_pota_parks(
+        &self,
+        csv: String,
+        dry_run: bool,
+    ) -> ClientResult<ImportResult> {
+        self.import(&["pota", "import"], csv, dry_run).await
+    }
+
+    /// 市区町村・JCC/JCGコード表のインポート（要認証）
+    pub async fn import_muni_codes(
+        &self,
+        csv: String,
+        dry_run: bool,
+    ) -> ClientResult<ImportResult> {
+        self.import(&["locator", "jcc-jcg", "import"], csv, dry_run)
+            .await
+    }
+
+    /// システムメトリクス（要認証）
+    pub async fn admin_metrics(&self) -> ClientResult<SystemMetrics> {
+        self.get(&["admin", "metrics"], &()).await
+    }
+
+    /// 定期ジョブ一覧（要認証）
+    pub async fn jobs(&self) -> ClientResult<Vec<JobStatus>> {
+        self.get(&["admin", "jobs"], &()).await
+    }
+
+    /// 定期ジョブを即時実行（要認証）
+    pub async fn run_job(&self, name: &str) -> ClientResult<JobStatus> {
+        self.post(&["admin", "jobs", name, "run"]).await
+    }
+
+    /// 定期ジョブを一時停止（要認証）
+    pub async fn pause_job(&self, name: &str) -> ClientResult<JobStatus> {
+        self.post(&["admin", "jobs", name, "pause"]).await
+    }
+
+    /// 定期ジョブを再開（要認証）
+    pub async fn resume_job(&self, name: &str) -> ClientResult<JobStatus> {
+        self.post(&["admin", "jobs", name, "resume"]).await
+    }
+
+    /// カントリーファイルの読み込み状況（要認証）
+    pub async fn cty_status(&self) -> ClientResult<CtyStatusView> {
+        self.get(&["admin", "cty"], &()).await
+    }
+
+    /// カントリーファイル（cty.dat形式）のアップロード（要認証）
+    pub async fn upload_cty(&self, text: String) -> ClientResult<CtyStatusView> {
+        let form = multipart::Form::new().part(
+            "file",
+            multipart::Part::text(text)
+                .file_name("cty.dat")
+                .mime_str("text/plain")
+                .map_err(ClientError::RequestError)?,
+        );
+        let res = self
+            .request(Method::POST, &["admin", "cty"])
+            .multipart(form)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        decode(check(res).await?).await
+    }
+
+    /// ウォッチリストの一覧（要認証）
+    pub async fn watches(&self) -> ClientResult<Vec<WatchView>> {
+        self.get(&["watch"], &()).await
+    }
+
+    /// ウォッチリストの登録（要認証）
+    pub async fn create_watch(&self, req: &WatchRequest) -> ClientResult<WatchView> {
+        self.send_json(Method::POST, &["watch"], req).await
+    }
+
+    /// ウォッチリストの更新（要認証）
+    pub async fn update_watch(&self, watch_id: i64, req: &WatchRequest) -> ClientResult<WatchView> {
+        self.send_json(Method::PUT, &["watch", &watch_id.to_string()], req)
+            .await
+    }
+
+    /// ウォッチリストの削除（要認証）
+    pub async fn delete_watch(&self, watch_id: i64) -> ClientResult<()> {
+        let res = self
+            .request(Method::DELETE, &["watch", &watch_id.to_string()])
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        check(res).await.map(|_| ())
+    }
+
+    /// Webhook配信記録（要認証）
+    pub async fn watch_deliveries(
+        &self,
+        watch_id: i64,
+        limit: Option<i64>,
+    ) -> ClientResult<Vec<WebhookDeliveryView>> {
+        let id = watch_id.to_string();
+        self.get(&["watch", &id, "deliveries"], &[("limit", limit)])
+            .await
+    }
+
+    /// セッションの一覧（要認証）
+    pub async fn sessions(&self) -> ClientResult<Vec<SessionView>> {
+        self.get(&["session"], &()).await
+    }
+
+    /// セッションの開始（要認証）
+    pub async fn open_session(&self, req: &SessionRequest) -> ClientResult<SessionView> {
+        self.send_json(Method::POST, &["session"], req).await
+    }
+
+    /// セッションと成立状況（要認証）
+    pub async fn session(&self, session_id: i64) -> ClientResult<SessionView> {
+        self.get(&["session", &session_id.to_string()], &()).await
+    }
+
+    /// QSOの登録（要認証・同じclientIdの再送は1件として扱われる）
+    pub async fn post_qsos(
+        &self,
+        session_id: i64,
+        req: &QsoPostRequest,
+    ) -> ClientResult<QsoPostView> {
+        let id = session_id.to_string();
+        self.send_json(Method::POST, &["session", &id, "qsos"], req)
+            .await
+    }
+
+    /// 登録済みのQSO（要認証）
+    pub async fn session_qsos(&self, session_id: i64) -> ClientResult<Vec<SessionQsoView>> {
+        let id = session_id.to_string();
+        self.get(&["session", &id, "qsos"], &()).await
+    }
+
+    /// セッションの終了とログファイルの作成（要認証）
+    pub async fn close_session(&self, session_id: i64) -> ClientResult<SessionView> {
+        let id = session_id.to_string();
+        self.post(&["session", &id, "close"]).await
+    }
+
+    /// ログファイルのダウンロード（要認証）
+    pub async fn session_file(&self, session_id: i64, filename: &str) -> ClientResult<Vec<u8>> {
+        let id = session_id.to_string();
+        let res = self
+            .request(Method::GET, &["session", &id, "files", filename])
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        let bytes = check(res)
+            .await?
+            .bytes()
+            .await
+            .map_err(ClientError::RequestError)?;
+        Ok(bytes.to_vec())
+    }
+
+    /// セッションの削除（要認証）
+    pub async fn delete_session(&self, session_id: i64) -> ClientResult<()> {
+        let res = self
+            .request(Method::DELETE, &["session", &session_id.to_string()])
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        check(res).await.map(|_| ())
+    }
+
+    /// `/api/v2/` 以下のURLを組み立てる（各セグメントはパーセントエンコードする）
+    pub fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("base_url is checked in with_client")
+            .pop_if_empty()
+            .extend(API_PREFIX.split('/'))
+            .extend(segments);
+        url
+    }
+
+    fn request(&self, method: Method, segments: &[&str]) -> RequestBuilder {
+        let req = self.http.request(method, self.url(segments));
+        match &self.token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+
+    async fn get<Q, T>(&self, segments: &[&str], query: &Q) -> ClientResult<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let res = self
+            .request(Method::GET, segments)
+            .query(query)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        decode(check(res).await?).await
+    }
+
+    async fn get_text<Q>(&self, segments: &[&str], query: &Q) -> ClientResult<String>
+    where
+        Q: Serialize + ?Sized,
+    {
+        let res = self
+            .request(Method::GET, segments)
+            .query(query)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        check(res)
+            .await?
+            .text()
+            .await
+            .map_err(ClientError::RequestError)
+    }
+
+    async fn post<T: DeserializeOwned>(&self, segments: &[&str]) -> ClientResult<T> {
+        let res = self
+            .request(Method::POST, segments)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        decode(check(res).await?).await
+    }
+
+    async fn send_json<B, T>(&self, method: Method, segments: &[&str], body: &B) -> ClientResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let res = self
+            .request(method, segments)
+            .json(body)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+        decode(check(res).await?).await
+    }
+
+    async fn import(
+        &self,
+        segments: &[&str],
+        csv: String,
+        dry_run: bool,
+    ) -> ClientResult<ImportResult> {
+        let form = multipart::Form::new().part(
+            "file",
+            multipart::Part::text(csv)
+                .file_name("import.csv")
+                .mime_str("text/csv")
+                .map_err(ClientError::RequestError)?,
+        );
+        let res = self
+            .request(Method::POST, segments)
+            .query(&ImportParam { dry_run })
+            .multipart(form)
+            .send()
+            .await
+            .map_err(ClientError::RequestError)?;
+
+        // 検証エラーで取り込まなかった場合も422でImportResultが返る
+        if res.status() == StatusCode::UNPROCESSABLE_ENTITY {
+            let body = res.text().await.map_err(ClientError::RequestError)?;
+            return serde_json::from_str(&body)
+                .map_err(|_| ClientError::from_response(StatusCode::UNPROCESSABLE_ENTITY, body));
+        }
+        decode(check(res).await?).await
+    }
+}
+
+/// 失敗ステータスをエラーに変換
+async fn check(res: Response) -> ClientResult<Response> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(res);
+    }
+    let body = res.text().await.unwrap_or_default();
+    Err(ClientError::from_response(status, body))
+}
+
+async fn decode<T: DeserializeOwned>(res: Response) -> ClientResult<T> {
+    let bytes = res.bytes().await.map_err(ClientError::RequestError)?;
+    serde_json::from_slice(&bytes).map_err(ClientError::JsonError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_joins_api_prefix() {
+        let client = ApiClient::new("https://sotaapp.example.com").unwrap();
+        assert_eq!(
+            client.url(&["activation", "spots"]).as_str(),
+            "https://sotaapp.example.com/api/v2/activation/spots"
+        );
+
+        let client = ApiClient::new("http://localhost:8080/").unwrap();
+        assert_eq!(
+            client.url(&["propagation", "geomag"]).as_str(),
+            "http://localhost:8080/api/v2/propagation/geomag"
+        );
+    }
+
+    #[test]
+    fn test_url_encodes_reference_codes() {
+        let client = ApiClient::new("https://sotaapp.example.com").unwrap();
+        assert_eq!(
+            client.url(&["sota", "summits", "JA/TK-001"]).as_str(),
+            "https://sotaapp.example.com/api/v2/sota/summits/JA%2FTK-001"
+        );
+    }
+
+    #[test]
+    fn test_invalid_base_url() {
+        assert!(matches!(
+            ApiClient::new("sotaapp.example.com"),
+            Err(ClientError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            ApiClient::new("mailto:jl1nie@example.com"),
+            Err(ClientError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_skips_unset_params_and_sends_token() {
+        let client = ApiClient::new("https://sotaapp.example.com")
+            .unwrap()
+            .with_token("id-token");
+        let param = GetParam {
+            pat_ref: Some("JA/TK".to_string()),
+            hours_ago: Some(6),
+            ..Default::default()
+        };
+        let req = client
+            .request(Method::GET, &["activation", "spots"])
+            .query(&param)
+            .build()
+            .unwrap();
+
+        assert_eq!(req.url().query(), Some("hours_ago=6&pat_ref=JA%2FTK"));
+        assert_eq!(
+            req.headers().get(header::AUTHORIZATION).unwrap(),
+            "Bearer id-token"
+        );
+    }
+
+    #[test]
+    fn test_error_response_is_decoded() {
+        let err = ClientError::from_response(
+            StatusCode::NOT_FOUND,
+            r#"{"success":false,"message":"サミットがありません","code":"NOT_FOUND"}"#.to_string(),
+        );
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(err.code(), Some("NOT_FOUND"));
+
+        let err = ClientError::from_response(StatusCode::UNAUTHORIZED, String::new());
+        assert!(matches!(err, ClientError::HttpError { .. }));
+        assert_eq!(err.code(), None);
+    }
+}
@@ -0,0 +1,53 @@
+use dto::error::ErrorResponse;
+use reqwest::StatusCode;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("URLが不正です: {0}")]
+    InvalidUrl(String),
+    #[error("リクエストに失敗しました。")]
+    RequestError(#[source] reqwest::Error),
+    #[error("レスポンスの変換に失敗しました。")]
+    JsonError(#[source] serde_json::Error),
+    #[error("APIエラー({status}): {}", .body.message)]
+    ApiError {
+        status: StatusCode,
+        body: ErrorResponse,
+    },
+    #[error("HTTPエラー({status}): {body}")]
+    HttpError { status: StatusCode, body: String },
+    #[error("Authorizationヘッダーがありません")]
+    MissingToken,
+}
+
+impl ClientError {
+    /// 失敗レスポンスをエラーに変換（`ErrorResponse`として読めなければ本文をそのまま保持）
+    pub(crate) fn from_response(status: StatusCode, body: String) -> Self {
+        match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(body) => ClientError::ApiError { status, body },
+            Err(_) => ClientError::HttpError { status, body },
+        }
+    }
+
+    /// APIが返したエラーコード（`NOT_FOUND`など）
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ClientError::ApiError { body, .. } => body.code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// HTTPステータス
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::ApiError { status, .. } | ClientError::HttpError { status, .. } => {
+                Some(*status)
+            }
+            ClientError::RequestError(e) => e.status(),
+            _ => None,
+        }
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
@@ -0,0 +1,12 @@
+//! SOTAApp2 API の型付き非同期クライアント
+//!
+//! サーバーと共有する `dto` クレートのDTOをそのまま使うため、レスポンス形式がずれない。
+
+pub mod client;
+pub mod error;
+
+pub use client::ApiClient;
+pub use error::{ClientError, ClientResult};
+
+pub use dto as model;
+pub use dto::param::GetParam;
@@ -3,9 +3,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
+use utoipa::ToSchema;
 
 /// テキストオーバーレイの設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TextOverlayConfig {
     /// X座標（ポイント、左端からの距離）
     pub x: f32,
@@ -37,7 +38,7 @@ impl Default for TextOverlayConfig {
 }
 
 /// 単一テンプレートの設定
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TemplateConfig {
     /// コールサインの印字設定
     pub callsign: TextOverlayConfig,
@@ -78,7 +79,7 @@ impl Default for TemplateConfig {
 }
 
 /// アワードテンプレート全体の設定
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 pub struct AwardTemplateConfig {
     /// アクティベータ賞テンプレート設定
     pub activator: TemplateConfig,
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use chrono::Duration;
+use std::collections::HashMap;
 use tokio::sync::watch;
 
 #[derive(Clone)]
@@ -10,6 +11,10 @@ pub struct AppConfig {
     pub run_migration: bool,
     pub migration_path: String,
     pub cors_origin: Option<String>,
+    /// `/metrics` を公開するアドレス（未設定なら公開しない）
+    ///
+    /// 本体のポートとは別に待ち受けるので、外部から届かないアドレスを指定する。
+    pub metrics_addr: Option<String>,
     pub firebase_api_key: String,
     pub auth_token_ttl: Duration,
     pub log_level: String,
@@ -30,15 +35,29 @@ pub struct AppConfig {
     pub spot_expire: Duration,
     pub aprs_log_expire: Duration,
     pub pota_log_expire: Duration,
+    pub alert_ontime_tolerance: Duration,
+    pub alert_activation_window: Duration,
     pub aprs_host: String,
     pub aprs_user: String,
     pub aprs_password: String,
     pub aprs_exclude_user: Option<String>,
     pub aprs_arrival_mesg_regex: Option<String>,
+    pub aprs_zone_radius: ZoneRadius,
+    pub aprs_zone_vertical_m: f64,
+    pub aprs_tracked_ssids: SsidPolicy,
+    pub aprs_record_path: Option<String>,
     pub openapi_level: OpenApiLevel,
     // アワード設定
     pub award_template_dir: String,
     pub award_config_path: String,
+    /// DXCC判定に使うカントリーファイル（cty.dat形式）
+    pub cty_dat_path: String,
+    /// 投稿したスポットの中継先（未設定なら中継しない）
+    pub relay_spot_endpoint: Option<String>,
+    /// 投稿したアラートの中継先（未設定なら中継しない）
+    pub relay_alert_endpoint: Option<String>,
+    /// 中継先に送るBearerトークン
+    pub relay_api_key: Option<String>,
     pub shutdown_tx: watch::Sender<bool>,
     pub shutdown_rx: watch::Receiver<bool>,
 }
@@ -83,6 +102,133 @@ impl std::str::FromStr for OpenApiLevel {
     }
 }
 
+/// アクティベーションゾーン未登録のサミットで使う到着判定半径（m）
+///
+/// `100,JA=80,W7W=150` のように既定値と協会ごとの値を指定する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneRadius {
+    pub default: f64,
+    pub by_association: HashMap<String, f64>,
+}
+
+impl Default for ZoneRadius {
+    fn default() -> Self {
+        Self {
+            default: 100.0,
+            by_association: HashMap::new(),
+        }
+    }
+}
+
+impl ZoneRadius {
+    /// サミットコード（例: `JA/TK-001`）の協会に対応する半径
+    pub fn for_summit(&self, summit_code: &str) -> f64 {
+        summit_code
+            .split('/')
+            .next()
+            .and_then(|assoc| self.by_association.get(assoc))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// ZoneRadiusのパースエラー
+#[derive(Debug, Clone)]
+pub struct ZoneRadiusParseError(String);
+
+impl std::fmt::Display for ZoneRadiusParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ZoneRadiusParseError {}
+
+impl std::str::FromStr for ZoneRadius {
+    type Err = ZoneRadiusParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ZoneRadiusParseError(format!("無効なAPRS_ZONE_RADIUS: {}", s));
+        let mut radius = ZoneRadius::default();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            match item.split_once('=') {
+                Some((assoc, m)) => {
+                    let m = m.trim().parse().map_err(|_| invalid())?;
+                    radius.by_association.insert(assoc.trim().to_uppercase(), m);
+                }
+                None => radius.default = item.parse().map_err(|_| invalid())?,
+            }
+        }
+        Ok(radius)
+    }
+}
+
+/// 到着判定の対象にするSSID
+///
+/// `5-9`、`0,7,9`（0はSSIDなし）、`*`（すべて）のように指定する。
+#[derive(Debug, Clone, PartialEq)]
+pub enum SsidPolicy {
+    All,
+    List(Vec<(u32, u32)>),
+}
+
+impl Default for SsidPolicy {
+    fn default() -> Self {
+        SsidPolicy::List(vec![(5, 9)])
+    }
+}
+
+impl SsidPolicy {
+    pub fn allows(&self, ssid: Option<u32>) -> bool {
+        let ssid = ssid.unwrap_or(0);
+        match self {
+            SsidPolicy::All => true,
+            SsidPolicy::List(ranges) => ranges.iter().any(|&(lo, hi)| lo <= ssid && ssid <= hi),
+        }
+    }
+}
+
+/// SsidPolicyのパースエラー
+#[derive(Debug, Clone)]
+pub struct SsidPolicyParseError(String);
+
+impl std::fmt::Display for SsidPolicyParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SsidPolicyParseError {}
+
+impl std::str::FromStr for SsidPolicy {
+    type Err = SsidPolicyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || SsidPolicyParseError(format!("無効なAPRS_TRACKED_SSIDS: {}", s));
+        let s = s.trim();
+        if s == "*" || s.eq_ignore_ascii_case("all") {
+            return Ok(SsidPolicy::All);
+        }
+        let mut ranges = Vec::new();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (lo, hi) = match item.split_once('-') {
+                Some((lo, hi)) => (lo.trim(), hi.trim()),
+                None => (item, item),
+            };
+            let lo: u32 = lo.parse().map_err(|_| invalid())?;
+            let hi: u32 = hi.parse().map_err(|_| invalid())?;
+            if lo > hi || hi > 15 {
+                return Err(invalid());
+            }
+            ranges.push((lo, hi));
+        }
+        if ranges.is_empty() {
+            return Err(invalid());
+        }
+        Ok(SsidPolicy::List(ranges))
+    }
+}
+
 /// 環境変数を取得（必須）
 fn env_required(key: &str) -> Result<String> {
     std::env::var(key).with_context(|| format!("環境変数 {} が設定されていません", key))
@@ -145,6 +291,7 @@ impl AppConfig {
             // オプションの設定
             run_migration: env_parse_or("RUN_MIGRATION", false),
             cors_origin: std::env::var("CORS_ORIGIN").ok(),
+            metrics_addr: std::env::var("METRICS_ADDR").ok(),
 
             // 認証
             auth_token_ttl: Duration::hours(env_parse_or("AUTH_TOKEN_TTL", 24)),
@@ -196,12 +343,20 @@ impl AppConfig {
             aprs_log_expire: Duration::days(env_parse_or("APRS_LOG_EXPIRE", 10)),
             pota_log_expire: Duration::days(env_parse_or("POTA_LOG_EXPIRE", 180)),
 
+            // アラートとスポットの突き合わせ
+            alert_ontime_tolerance: Duration::minutes(env_parse_or("ALERT_ONTIME_TOLERANCE", 30)),
+            alert_activation_window: Duration::hours(env_parse_or("ALERT_ACTIVATION_WINDOW", 3)),
+
             // APRS
             aprs_host: env_or("APRSHOST", "rotate.aprs2.net:14580"),
             aprs_user: env_required("APRSUSER")?,
             aprs_password: env_required("APRSPASSWORD")?,
             aprs_exclude_user: std::env::var("APRS_EXCLUDE_USER").ok(),
             aprs_arrival_mesg_regex: std::env::var("APRS_ARRIVAL_MESG_REGEX").ok(),
+            aprs_zone_radius: env_parse_or("APRS_ZONE_RADIUS", ZoneRadius::default()),
+            aprs_zone_vertical_m: env_parse_or("APRS_ZONE_VERTICAL_M", 25.0),
+            aprs_tracked_ssids: env_parse_or("APRS_TRACKED_SSIDS", SsidPolicy::default()),
+            aprs_record_path: std::env::var("APRS_RECORD_PATH").ok(),
 
             // その他
             openapi_level: env_parse_or("OPENAPI_LEVEL", OpenApiLevel::None),
@@ -210,6 +365,14 @@ impl AppConfig {
             award_template_dir: env_or("AWARD_TEMPLATE_DIR", "./data/award_templates"),
             award_config_path: env_or("AWARD_CONFIG_PATH", "./data/award_config.json"),
 
+            // DXCC
+            cty_dat_path: env_or("CTY_DAT_PATH", "./data/cty.dat"),
+
+            // 投稿の中継
+            relay_spot_endpoint: std::env::var("RELAY_SPOT_ENDPOINT").ok(),
+            relay_alert_endpoint: std::env::var("RELAY_ALERT_ENDPOINT").ok(),
+            relay_api_key: std::env::var("RELAY_API_KEY").ok(),
+
             shutdown_rx,
             shutdown_tx,
         })
@@ -268,4 +431,44 @@ mod tests {
     fn test_openapi_level_default() {
         assert_eq!(OpenApiLevel::default(), OpenApiLevel::None);
     }
+
+    #[test]
+    fn test_zone_radius_from_str() {
+        let radius = ZoneRadius::from_str("150, ja=80 ,W7W=200").unwrap();
+        assert_eq!(radius.default, 150.0);
+        assert_eq!(radius.for_summit("JA/TK-001"), 80.0);
+        assert_eq!(radius.for_summit("W7W/LC-001"), 200.0);
+        assert_eq!(radius.for_summit("HL/GN-001"), 150.0);
+    }
+
+    #[test]
+    fn test_zone_radius_default_and_invalid() {
+        let radius = ZoneRadius::from_str("JA=80").unwrap();
+        assert_eq!(radius.for_summit("VK2/HU-001"), 100.0);
+        assert!(ZoneRadius::from_str("JA=abc").is_err());
+        assert!(ZoneRadius::from_str("wide").is_err());
+    }
+
+    #[test]
+    fn test_ssid_policy_from_str() {
+        let policy = SsidPolicy::default();
+        assert!(policy.allows(Some(7)));
+        assert!(!policy.allows(Some(10)));
+        assert!(!policy.allows(None));
+
+        let policy = SsidPolicy::from_str("0, 7, 9-10").unwrap();
+        assert!(policy.allows(None));
+        assert!(policy.allows(Some(10)));
+        assert!(!policy.allows(Some(8)));
+
+        assert!(SsidPolicy::from_str("*").unwrap().allows(Some(15)));
+    }
+
+    #[test]
+    fn test_ssid_policy_invalid() {
+        assert!(SsidPolicy::from_str("").is_err());
+        assert!(SsidPolicy::from_str("9-5").is_err());
+        assert!(SsidPolicy::from_str("5-16").is_err());
+        assert!(SsidPolicy::from_str("mobile").is_err());
+    }
 }
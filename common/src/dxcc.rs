@@ -0,0 +1,357 @@
+//! cty.dat形式のカントリーファイルによるDXCCエンティティの判定
+//!
+//! cty.datは1エンティティごとに
+//! `名前: CQゾーン: ITUゾーン: 大陸: 緯度: 経度: UTC差: 代表プリフィックス:` の行と、
+//! カンマ区切りのプリフィックス一覧（`;`で終わる）からなる。経度は西経が正。
+//! プリフィックスの前の`=`はコールサイン完全一致、後ろの`(CQ)` `[ITU]` `<緯度/経度>`
+//! `{大陸}` `~UTC差~` はそのプリフィックスだけの上書き。
+//!
+//! 実行中にネットワークは使わず、管理画面からアップロードしたファイルを読み込む。
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, AppResult};
+
+/// 無視する移動運用の接尾辞
+const IGNORED_SUFFIXES: &[&str] = &["P", "M", "QRP", "A", "LH"];
+/// エンティティのない接尾辞（海上・航空機上）
+const NO_ENTITY_SUFFIXES: &[&str] = &["MM", "AM"];
+
+/// DXCCエンティティ
+#[derive(Debug, Clone, PartialEq)]
+pub struct DxccEntity {
+    pub name: String,
+    /// 代表プリフィックス（WAE等の`*`は除く）
+    pub prefix: String,
+    pub continent: String,
+    pub cq_zone: u8,
+    pub itu_zone: u8,
+    /// 中心の緯度（北緯が正）
+    pub lat: f64,
+    /// 中心の経度（東経が正）
+    pub lon: f64,
+}
+
+/// プリフィックス・コールサインごとの上書き
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Alias {
+    entity: usize,
+    cq_zone: Option<u8>,
+    itu_zone: Option<u8>,
+    lat_lon: Option<(f64, f64)>,
+    continent: Option<String>,
+}
+
+/// コールサインから求めたエンティティ・ゾーン
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DxccInfo {
+    pub entity: String,
+    pub prefix: String,
+    pub continent: String,
+    pub cq_zone: u8,
+    pub itu_zone: u8,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+/// 読み込んだカントリーファイル
+#[derive(Debug, Default)]
+pub struct CtyDat {
+    entities: Vec<DxccEntity>,
+    prefixes: HashMap<String, Alias>,
+    exact: HashMap<String, Alias>,
+    max_prefix_len: usize,
+}
+
+fn parse_error(entity: &str, message: &str) -> AppError {
+    AppError::UnprocessableEntity(format!("cty.datの{}: {}", entity, message))
+}
+
+/// `(CQ)` `[ITU]` などの括弧の中身
+fn enclosed(s: &str, open: char, close: char) -> Option<&str> {
+    let start = s.find(open)? + open.len_utf8();
+    let end = start + s[start..].find(close)?;
+    Some(&s[start..end])
+}
+
+fn parse_alias(token: &str, entity: usize) -> Option<(bool, String, Alias)> {
+    let (exact, token) = match token.strip_prefix('=') {
+        Some(t) => (true, t),
+        None => (false, token),
+    };
+    let end = token.find(['(', '[', '<', '{', '~']).unwrap_or(token.len());
+    let call = token[..end].trim().to_ascii_uppercase();
+    if call.is_empty() {
+        return None;
+    }
+
+    let rest = &token[end..];
+    let lat_lon = enclosed(rest, '<', '>').and_then(|ll| {
+        let (lat, lon) = ll.split_once('/')?;
+        Some((lat.trim().parse().ok()?, -lon.trim().parse::<f64>().ok()?))
+    });
+    let alias = Alias {
+        entity,
+        cq_zone: enclosed(rest, '(', ')').and_then(|z| z.parse().ok()),
+        itu_zone: enclosed(rest, '[', ']').and_then(|z| z.parse().ok()),
+        lat_lon,
+        continent: enclosed(rest, '{', '}').map(str::to_string),
+    };
+    Some((exact, call, alias))
+}
+
+impl CtyDat {
+    /// cty.dat形式のテキストを読み込む
+    pub fn parse(text: &str) -> AppResult<Self> {
+        let mut cty = Self::default();
+
+        for record in text.split(';') {
+            let record = record.trim();
+            if record.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = record.splitn(9, ':').map(str::trim).collect();
+            if fields.len() < 9 {
+                return Err(parse_error(fields[0], "エンティティの行の項目が足りません"));
+            }
+            let name = fields[0];
+            let number = |i: usize| -> AppResult<f64> {
+                fields[i]
+                    .parse()
+                    .map_err(|_| parse_error(name, &format!("数値ではありません: {}", fields[i])))
+            };
+
+            let entity = cty.entities.len();
+            cty.entities.push(DxccEntity {
+                name: name.to_string(),
+                prefix: fields[7].trim_start_matches('*').to_string(),
+                continent: fields[3].to_string(),
+                cq_zone: number(1)? as u8,
+                itu_zone: number(2)? as u8,
+                lat: number(4)?,
+                lon: -number(5)?,
+            });
+
+            for token in fields[8].split([',', '\n', '\r', ' ', '\t']) {
+                let Some((exact, call, alias)) = parse_alias(token.trim(), entity) else {
+                    continue;
+                };
+                if exact {
+                    cty.exact.insert(call, alias);
+                } else {
+                    cty.max_prefix_len = cty.max_prefix_len.max(call.len());
+                    cty.prefixes.insert(call, alias);
+                }
+            }
+        }
+
+        if cty.entities.is_empty() {
+            return Err(AppError::UnprocessableEntity(
+                "cty.datにエンティティがありません".to_string(),
+            ));
+        }
+        Ok(cty)
+    }
+
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn prefix_count(&self) -> usize {
+        self.prefixes.len() + self.exact.len()
+    }
+
+    fn info(&self, alias: &Alias) -> DxccInfo {
+        let e = &self.entities[alias.entity];
+        let (lat, lon) = alias.lat_lon.unwrap_or((e.lat, e.lon));
+        DxccInfo {
+            entity: e.name.clone(),
+            prefix: e.prefix.clone(),
+            continent: alias
+                .continent
+                .clone()
+                .unwrap_or_else(|| e.continent.clone()),
+            cq_zone: alias.cq_zone.unwrap_or(e.cq_zone),
+            itu_zone: alias.itu_zone.unwrap_or(e.itu_zone),
+            lat,
+            lon,
+        }
+    }
+
+    fn longest_prefix(&self, call: &str) -> Option<&Alias> {
+        let len = call.len().min(self.max_prefix_len);
+        (1..=len)
+            .rev()
+            .filter_map(|n| call.get(..n))
+            .find_map(|p| self.prefixes.get(p))
+    }
+
+    /// コールサインのエンティティ・ゾーンを求める
+    ///
+    /// `JA1ABC/3` はエリア番号を置き換えた`JA3`、`KH0/JA1ABC` は短い方の`KH0`で判定する。
+    /// `/P` `/M` などは無視し、`/MM` `/AM` はエンティティなしとする。
+    pub fn resolve(&self, callsign: &str) -> Option<DxccInfo> {
+        let call = callsign.trim().to_ascii_uppercase();
+        let mut parts: Vec<&str> = call.split('/').filter(|p| !p.is_empty()).collect();
+        if parts.iter().any(|p| NO_ENTITY_SUFFIXES.contains(p)) {
+            return None;
+        }
+        parts.retain(|p| !IGNORED_SUFFIXES.contains(p));
+
+        let stripped = parts.join("/");
+        if let Some(alias) = self.exact.get(&stripped) {
+            return Some(self.info(alias));
+        }
+
+        let prefix = match parts.as_slice() {
+            [] => return None,
+            [call] => call.to_string(),
+            [home, area, ..] if area.len() == 1 && area.chars().all(|c| c.is_ascii_digit()) => {
+                if let Some(alias) = self.exact.get(*home) {
+                    return Some(self.info(alias));
+                }
+                replace_area(home, area)
+            }
+            [first, second, ..] => {
+                if second.len() < first.len() {
+                    second.to_string()
+                } else {
+                    first.to_string()
+                }
+            }
+        };
+
+        self.longest_prefix(&prefix).map(|alias| self.info(alias))
+    }
+}
+
+/// コールサインのエリア番号を置き換えたプリフィックス（`JA1ABC` と `3` で `JA3`）
+fn replace_area(call: &str, area: &str) -> String {
+    let start = call
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| c.is_ascii_digit())
+        .map(|(i, _)| i);
+    match start {
+        Some(i) => format!("{}{}", &call[..i], area),
+        None => format!("{}{}", call, area),
+    }
+}
+
+static CTY: RwLock<Option<Arc<CtyDat>>> = RwLock::new(None);
+
+/// 読み込んだカントリーファイルを以後の判定に使う
+pub fn install(cty: CtyDat) {
+    if let Ok(mut guard) = CTY.write() {
+        *guard = Some(Arc::new(cty));
+    }
+}
+
+/// 現在のカントリーファイル（未読み込みならNone）
+pub fn current() -> Option<Arc<CtyDat>> {
+    CTY.read().ok().and_then(|guard| guard.clone())
+}
+
+/// 現在のカントリーファイルでコールサインを判定する
+pub fn lookup(callsign: &str) -> Option<DxccInfo> {
+    current()?.resolve(callsign)
+}
+
+/// ファイルからカントリーファイルを読み込んで使う（エンティティ数を返す）
+pub fn load_file(path: &Path) -> AppResult<usize> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        AppError::UnprocessableEntity(format!("{}を読み込めません: {}", path.display(), e))
+    })?;
+    let cty = CtyDat::parse(&text)?;
+    let count = cty.entity_count();
+    install(cty);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CTY: &str = r#"
+Japan:                    25:  45:  AS:   36.40:  -138.38:    -9.0:  JA:
+    7J,7K,7L,7M,7N,JA,JE,JF,JG,JH,JI,JJ,JK,JL,JM,JN,JO,JP,JQ,JR,JS,
+    =JA1XYZ/JD1;
+Mariana Islands:          27:  64:  OC:   15.18:  -145.72:   -10.0:  KH0:
+    AH0,KH0,NH0,WH0;
+United States:            05:  08:  NA:   37.53:    91.67:     5.0:  K:
+    AA,K,N,W,
+    W6(03)[06]<37.00/120.00>,
+    =W1AW/7(03)[06];
+Ogasawara:                27:  45:  AS:   27.05:  -142.20:    -9.0:  JD/o:
+    JD1,=JA1XYZ/JD1;
+European Russia:          16:  29:  EU:   53.65:   -41.37:    -4.0:  *UA:
+    R,U;
+"#;
+
+    fn cty() -> CtyDat {
+        CtyDat::parse(CTY).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let cty = cty();
+        assert_eq!(cty.entity_count(), 5);
+        assert_eq!(cty.entities[0].lon, 138.38);
+        assert_eq!(cty.entities[4].prefix, "UA");
+        assert!(CtyDat::parse("").is_err());
+        assert!(CtyDat::parse("Japan: 25: 45: AS;").is_err());
+    }
+
+    #[test]
+    fn test_resolve_prefix() {
+        let cty = cty();
+        let ja = cty.resolve("ja1abc").unwrap();
+        assert_eq!(ja.entity, "Japan");
+        assert_eq!(ja.continent, "AS");
+        assert_eq!((ja.cq_zone, ja.itu_zone), (25, 45));
+        assert_eq!((ja.lat, ja.lon), (36.40, 138.38));
+
+        assert_eq!(cty.resolve("JD1ABC").unwrap().entity, "Ogasawara");
+        assert_eq!(cty.resolve("7K4XYZ").unwrap().entity, "Japan");
+        assert_eq!(cty.resolve("XX9ABC"), None);
+    }
+
+    #[test]
+    fn test_resolve_override() {
+        let cty = cty();
+        let w6 = cty.resolve("W6ABC").unwrap();
+        assert_eq!(w6.entity, "United States");
+        assert_eq!((w6.cq_zone, w6.itu_zone), (3, 6));
+        assert_eq!((w6.lat, w6.lon), (37.0, -120.0));
+
+        let w1 = cty.resolve("W1ABC").unwrap();
+        assert_eq!(w1.cq_zone, 5);
+
+        // 完全一致の上書き（スラッシュ付きのコールサインも）
+        assert_eq!(cty.resolve("W1AW/7").unwrap().cq_zone, 3);
+        assert_eq!(cty.resolve("JA1XYZ/JD1").unwrap().entity, "Ogasawara");
+    }
+
+    #[test]
+    fn test_resolve_portable() {
+        let cty = cty();
+        assert_eq!(cty.resolve("KH0/JA1ABC").unwrap().entity, "Mariana Islands");
+        assert_eq!(cty.resolve("JA1ABC/KH0").unwrap().entity, "Mariana Islands");
+        assert_eq!(cty.resolve("JA1ABC/P").unwrap().entity, "Japan");
+        assert_eq!(cty.resolve("JA1ABC/3/P").unwrap().entity, "Japan");
+        assert_eq!(cty.resolve("W1ABC/6").unwrap().cq_zone, 3);
+        assert_eq!(cty.resolve("JA1ABC/MM"), None);
+        assert_eq!(cty.resolve(""), None);
+    }
+
+    #[test]
+    fn test_replace_area() {
+        assert_eq!(replace_area("JA1ABC", "3"), "JA3");
+        assert_eq!(replace_area("W1ABC", "6"), "W6");
+        assert_eq!(replace_area("7K4XYZ", "1"), "7K1");
+    }
+}
@@ -2,17 +2,9 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Json},
 };
-use serde::Serialize;
 use thiserror::Error;
 
-/// Error response returned as JSON
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub success: bool,
-    pub message: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub code: Option<String>,
-}
+pub use dto::error::ErrorResponse;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -0,0 +1,430 @@
+//! 定期ジョブレジストリ
+//!
+//! cron ジョブと一定間隔のループジョブを名前付きで登録し、
+//! 実行履歴の参照・即時実行・一時停止/再開を行う。
+
+use chrono::Utc;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio_cron_scheduler::{Job, JobScheduler};
+use uuid::Uuid;
+
+use crate::error::{AppError, AppResult};
+
+pub use dto::jobs::JobStatus;
+
+pub type JobFuture = Pin<Box<dyn Future<Output = AppResult<()>> + Send>>;
+pub type JobFn = Arc<dyn Fn() -> JobFuture + Send + Sync>;
+
+struct JobEntry {
+    status: JobStatus,
+    run: JobFn,
+    timeout: Option<Duration>,
+    cron_id: Option<Uuid>,
+}
+
+/// ジョブレジストリ
+#[derive(Clone)]
+pub struct JobRegistry {
+    sched: JobScheduler,
+    jobs: Arc<Mutex<BTreeMap<String, JobEntry>>>,
+    shutdown_rx: watch::Receiver<bool>,
+}
+
+impl JobRegistry {
+    pub async fn new(shutdown_rx: watch::Receiver<bool>) -> AppResult<Self> {
+        let sched = JobScheduler::new().await.map_err(AppError::CronjobError)?;
+        Ok(Self {
+            sched,
+            jobs: Arc::new(Mutex::new(BTreeMap::new())),
+            shutdown_rx,
+        })
+    }
+
+    /// cron スケジューラを開始（開始後もジョブは追加できる）
+    pub async fn start(&self) -> AppResult<()> {
+        self.sched.start().await.map_err(AppError::CronjobError)
+    }
+
+    fn register(&self, name: &str, schedule: String, timeout: Option<Duration>, run: JobFn) {
+        if let Ok(mut guard) = self.jobs.lock() {
+            guard.insert(
+                name.to_string(),
+                JobEntry {
+                    status: JobStatus::new(name, schedule),
+                    run,
+                    timeout,
+                    cron_id: None,
+                },
+            );
+        }
+    }
+
+    /// cron 式で実行するジョブを登録
+    pub async fn add_cron<F, Fut>(&self, name: &str, schedule: &str, run: F) -> AppResult<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = AppResult<()>> + Send + 'static,
+    {
+        let run: JobFn = Arc::new(move || Box::pin(run()));
+        self.register(name, schedule.to_string(), None, run);
+
+        let registry = self.clone();
+        let job_name = name.to_string();
+        let job = Job::new_async(schedule, move |_uuid, _l| {
+            let registry = registry.clone();
+            let name = job_name.clone();
+            Box::pin(async move {
+                registry.run_job(&name, false).await;
+            })
+        })
+        .map_err(AppError::CronjobError)?;
+
+        let id = self.sched.add(job).await.map_err(AppError::CronjobError)?;
+        if let Ok(mut guard) = self.jobs.lock() {
+            if let Some(entry) = guard.get_mut(name) {
+                entry.cron_id = Some(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// 一定間隔で繰り返すジョブを登録（登録直後に1回目を実行）
+    ///
+    /// シャットダウン通知を受けるとループを抜ける。
+    pub fn add_interval<F, Fut>(
+        &self,
+        name: &str,
+        interval: Duration,
+        timeout: Option<Duration>,
+        run: F,
+    ) -> JoinHandle<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = AppResult<()>> + Send + 'static,
+    {
+        let run: JobFn = Arc::new(move || Box::pin(run()));
+        self.register(name, format!("every {}s", interval.as_secs()), timeout, run);
+
+        let registry = self.clone();
+        let name = name.to_string();
+        let mut shutdown = self.shutdown_rx.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if *shutdown.borrow() {
+                    break;
+                }
+                registry.run_job(&name, false).await;
+
+                let next = Utc::now() + interval;
+                registry.update(&name, |s| s.next_run = Some(next));
+
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    _ = shutdown.changed() => {}
+                }
+            }
+            tracing::info!("Shutdown {} job", name);
+        })
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut JobStatus)) {
+        if let Ok(mut guard) = self.jobs.lock() {
+            if let Some(entry) = guard.get_mut(name) {
+                f(&mut entry.status);
+            }
+        }
+    }
+
+    /// ジョブを実行中にして、実行する関数とタイムアウトを返す
+    ///
+    /// 実行中かどうかの確認と実行中への切り替えを1回のロックで行うので、多重に起動しない。
+    /// `manual` が false の場合、一時停止中のジョブは `None` を返す。
+    fn claim(&self, name: &str, manual: bool) -> AppResult<Option<(JobFn, Option<Duration>)>> {
+        let mut guard = self
+            .jobs
+            .lock()
+            .map_err(|_| AppError::UnprocessableEntity("job registry is poisoned".to_string()))?;
+        let entry = guard
+            .get_mut(name)
+            .ok_or_else(|| AppError::EntityNotFound(format!("job {} not found", name)))?;
+        if entry.status.running {
+            return Err(AppError::UnprocessableEntity(format!(
+                "job {} is already running",
+                name
+            )));
+        }
+        if entry.status.paused && !manual {
+            return Ok(None);
+        }
+        entry.status.running = true;
+        entry.status.last_start = Some(Utc::now());
+        Ok(Some((entry.run.clone(), entry.timeout)))
+    }
+
+    /// スケジュールに従ってジョブを1回実行する
+    ///
+    /// 一時停止中・実行中のジョブは実行しない。
+    async fn run_job(&self, name: &str, manual: bool) {
+        if let Ok(Some((run, timeout))) = self.claim(name, manual) {
+            self.execute(name, run, timeout).await;
+        }
+    }
+
+    /// 実行中にしたジョブを実行して結果を記録
+    async fn execute(&self, name: &str, run: JobFn, timeout: Option<Duration>) {
+        tracing::info!("Starting job {}", name);
+        let result = match timeout {
+            Some(t) => match tokio::time::timeout(t, run()).await {
+                Ok(r) => r.map_err(|e| format!("{:?}", e)),
+                Err(_) => Err(format!("timed out after {}s", t.as_secs())),
+            },
+            None => run().await.map_err(|e| format!("{:?}", e)),
+        };
+
+        if let Err(e) = &result {
+            tracing::error!("Job {} failed: {}", name, e);
+        }
+
+        self.update(name, |s| {
+            s.running = false;
+            s.run_count += 1;
+            s.last_finish = Some(Utc::now());
+            if let Err(e) = result {
+                s.error_count += 1;
+                s.last_error = Some(e);
+            }
+        });
+    }
+
+    /// 登録済みジョブの状態一覧
+    pub async fn list(&self) -> Vec<JobStatus> {
+        let (mut statuses, cron_ids): (Vec<JobStatus>, Vec<Option<Uuid>>) = match self.jobs.lock() {
+            Ok(guard) => guard
+                .values()
+                .map(|e| (e.status.clone(), e.cron_id))
+                .unzip(),
+            Err(_) => return vec![],
+        };
+
+        let mut sched = self.sched.clone();
+        for (status, cron_id) in statuses.iter_mut().zip(cron_ids) {
+            if let Some(id) = cron_id {
+                status.next_run = sched.next_tick_for_job(id).await.ok().flatten();
+            }
+        }
+        statuses
+    }
+
+    /// 指定ジョブの状態
+    pub async fn get(&self, name: &str) -> AppResult<JobStatus> {
+        self.list()
+            .await
+            .into_iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| AppError::EntityNotFound(format!("job {} not found", name)))
+    }
+
+    /// ジョブを即時実行（完了を待たない）
+    ///
+    /// 一時停止中のジョブも実行する。
+    pub async fn trigger(&self, name: &str) -> AppResult<JobStatus> {
+        if let Some((run, timeout)) = self.claim(name, true)? {
+            let registry = self.clone();
+            let job_name = name.to_string();
+            tokio::spawn(async move {
+                registry.execute(&job_name, run, timeout).await;
+            });
+        }
+        self.get(name).await
+    }
+
+    /// ジョブを一時停止/再開
+    pub async fn set_paused(&self, name: &str, paused: bool) -> AppResult<JobStatus> {
+        let mut found = false;
+        self.update(name, |s| {
+            s.paused = paused;
+            found = true;
+        });
+        if !found {
+            return Err(AppError::EntityNotFound(format!("job {} not found", name)));
+        }
+        tracing::info!("Job {} {}", name, if paused { "paused" } else { "resumed" });
+        self.get(name).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn wait_until_idle(registry: &JobRegistry, name: &str) -> JobStatus {
+        for _ in 0..100 {
+            let status = registry.get(name).await.unwrap();
+            if !status.running && status.last_finish.is_some() {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", name);
+    }
+
+    #[tokio::test]
+    async fn test_trigger_records_run() {
+        let (_tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let c = counter.clone();
+        registry
+            .add_cron("count", "0 0 0 * * *", move || {
+                let c = c.clone();
+                async move {
+                    c.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap();
+
+        registry.trigger("count").await.unwrap();
+        let status = wait_until_idle(&registry, "count").await;
+
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(status.run_count, 1);
+        assert_eq!(status.error_count, 0);
+        assert_eq!(status.schedule, "0 0 0 * * *");
+        assert!(status.last_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_records_error() {
+        let (_tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+
+        registry
+            .add_cron("fail", "0 0 0 * * *", || async {
+                Err(AppError::EntityNotFound("boom".to_string()))
+            })
+            .await
+            .unwrap();
+
+        registry.trigger("fail").await.unwrap();
+        let status = wait_until_idle(&registry, "fail").await;
+
+        assert_eq!(status.error_count, 1);
+        assert!(status.last_error.unwrap().contains("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_paused_interval_job_is_skipped() {
+        let (tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let c = counter.clone();
+        let handle = registry.add_interval(
+            "loop",
+            Duration::from_millis(20),
+            Some(Duration::from_secs(1)),
+            move || {
+                let c = c.clone();
+                async move {
+                    c.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            },
+        );
+
+        wait_until_idle(&registry, "loop").await;
+        registry.set_paused("loop", true).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let paused_count = counter.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(counter.load(Ordering::SeqCst), paused_count);
+
+        let status = registry.set_paused("loop", false).await.unwrap();
+        assert!(!status.paused);
+        assert!(status.next_run.is_some());
+
+        tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interval_job_timeout() {
+        let (tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+
+        let handle = registry.add_interval(
+            "slow",
+            Duration::from_secs(60),
+            Some(Duration::from_millis(10)),
+            || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok(())
+            },
+        );
+
+        let status = wait_until_idle(&registry, "slow").await;
+        assert_eq!(status.error_count, 1);
+        assert!(status.last_error.unwrap().contains("timed out"));
+
+        tx.send(true).unwrap();
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_trigger_while_running() {
+        let (_tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+        let counter = Arc::new(AtomicU32::new(0));
+
+        let c = counter.clone();
+        registry
+            .add_cron("slow", "0 0 0 * * *", move || {
+                let c = c.clone();
+                async move {
+                    c.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    Ok(())
+                }
+            })
+            .await
+            .unwrap();
+
+        // 起動前に続けて呼んでも2回目は実行中として断る
+        let status = registry.trigger("slow").await.unwrap();
+        assert!(status.running);
+        assert!(matches!(
+            registry.trigger("slow").await,
+            Err(AppError::UnprocessableEntity(_))
+        ));
+
+        let status = wait_until_idle(&registry, "slow").await;
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+        assert_eq!(status.run_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_job() {
+        let (_tx, rx) = watch::channel(false);
+        let registry = JobRegistry::new(rx).await.unwrap();
+
+        assert!(matches!(
+            registry.trigger("nope").await,
+            Err(AppError::EntityNotFound(_))
+        ));
+        assert!(matches!(
+            registry.set_paused("nope", true).await,
+            Err(AppError::EntityNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,231 @@
+//! リファレンス名検索用の日本語正規化
+//!
+//! ひらがな・カタカナ・半角カナ・全角英数の表記ゆれと、かなのローマ字読みを吸収する。
+//! 漢字の読みは辞書がないため扱わない。
+
+/// 半角カナ（U+FF66〜U+FF9D）に対応する全角カタカナ
+const HALFWIDTH_KANA: &str =
+    "ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// 全角英数・記号と半角カナを正規化する
+pub fn fold_width(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\u{3000}' => out.push(' '),
+            '\u{FF01}'..='\u{FF5E}' => {
+                out.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c));
+            }
+            '\u{FF66}'..='\u{FF9D}' => {
+                let index = (c as u32 - 0xFF66) as usize;
+                out.extend(HALFWIDTH_KANA.chars().nth(index));
+            }
+            // 半角の濁点・半濁点は直前の文字に合成する
+            '\u{FF9E}' | '\u{FF9F}' => {
+                let voiced = out.pop().and_then(|prev| match (prev, c) {
+                    ('ウ', '\u{FF9E}') => Some('ヴ'),
+                    ('カ'..='ト', '\u{FF9E}') | ('ハ'..='ホ', '\u{FF9E}') => {
+                        char::from_u32(prev as u32 + 1)
+                    }
+                    ('ハ'..='ホ', '\u{FF9F}') => char::from_u32(prev as u32 + 2),
+                    _ => {
+                        out.push(prev);
+                        None
+                    }
+                });
+                out.extend(voiced);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// カタカナをひらがなにする
+pub fn to_hiragana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// ひらがなをカタカナにする
+pub fn to_katakana(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '\u{3041}'..='\u{3096}' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+fn hiragana_romaji(c: char) -> Option<&'static str> {
+    let r = match c {
+        'あ' => "a", 'い' => "i", 'う' => "u", 'え' => "e", 'お' => "o",
+        'か' => "ka", 'き' => "ki", 'く' => "ku", 'け' => "ke", 'こ' => "ko",
+        'さ' => "sa", 'し' => "shi", 'す' => "su", 'せ' => "se", 'そ' => "so",
+        'た' => "ta", 'ち' => "chi", 'つ' => "tsu", 'て' => "te", 'と' => "to",
+        'な' => "na", 'に' => "ni", 'ぬ' => "nu", 'ね' => "ne", 'の' => "no",
+        'は' => "ha", 'ひ' => "hi", 'ふ' => "fu", 'へ' => "he", 'ほ' => "ho",
+        'ま' => "ma", 'み' => "mi", 'む' => "mu", 'め' => "me", 'も' => "mo",
+        'や' => "ya", 'ゆ' => "yu", 'よ' => "yo",
+        'ら' => "ra", 'り' => "ri", 'る' => "ru", 'れ' => "re", 'ろ' => "ro",
+        'わ' => "wa", 'ゐ' => "i", 'ゑ' => "e", 'を' => "o", 'ん' => "n",
+        'が' => "ga", 'ぎ' => "gi", 'ぐ' => "gu", 'げ' => "ge", 'ご' => "go",
+        'ざ' => "za", 'じ' => "ji", 'ず' => "zu", 'ぜ' => "ze", 'ぞ' => "zo",
+        'だ' => "da", 'ぢ' => "ji", 'づ' => "zu", 'で' => "de", 'ど' => "do",
+        'ば' => "ba", 'び' => "bi", 'ぶ' => "bu", 'べ' => "be", 'ぼ' => "bo",
+        'ぱ' => "pa", 'ぴ' => "pi", 'ぷ' => "pu", 'ぺ' => "pe", 'ぽ' => "po",
+        'ゔ' => "vu",
+        'ぁ' => "a", 'ぃ' => "i", 'ぅ' => "u", 'ぇ' => "e", 'ぉ' => "o",
+        'ゃ' => "ya", 'ゅ' => "yu", 'ょ' => "yo", 'ゎ' => "wa",
+        _ => return None,
+    };
+    Some(r)
+}
+
+/// かなをヘボン式ローマ字にする（漢字などかな以外を含む場合はNone）
+pub fn to_romaji(s: &str) -> Option<String> {
+    let chars: Vec<char> = to_hiragana(s).chars().collect();
+    let mut out = String::new();
+    let mut sokuon = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            'っ' => {
+                sokuon = true;
+                continue;
+            }
+            // 長音記号は読まない（長母音は fold_romaji で畳む）
+            'ー' | ' ' => continue,
+            'ゃ' | 'ゅ' | 'ょ' | 'ぁ' | 'ぃ' | 'ぅ' | 'ぇ' | 'ぉ' if i > 0 => {
+                let small = hiragana_romaji(c)?;
+                let prev = chars[i - 1];
+                // 拗音: きゃ→kya、しゃ→sha、じゃ→ja
+                if matches!(c, 'ゃ' | 'ゅ' | 'ょ') && out.ends_with('i') {
+                    out.pop();
+                    if !matches!(prev, 'し' | 'ち' | 'じ' | 'ぢ') {
+                        out.push('y');
+                    }
+                    out.push_str(&small[1..]);
+                } else if out.ends_with(|v| "aiueo".contains(v)) {
+                    // ふぁ→fa、てぃ→ti
+                    out.pop();
+                    out.push_str(small);
+                } else {
+                    out.push_str(small);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let romaji = hiragana_romaji(c)?;
+        if sokuon {
+            out.push(if romaji.starts_with("ch") {
+                't'
+            } else {
+                romaji.chars().next()?
+            });
+            sokuon = false;
+        }
+        out.push_str(romaji);
+    }
+    Some(out)
+}
+
+/// ローマ字の表記ゆれを畳む
+///
+/// 小文字化し、空白・ハイフン・ピリオド・アポストロフィを除き、長母音（ou/oo/uu）を短くする。
+/// DBの検索インデックスも同じ規則で作っているので、変える場合はマイグレーションも合わせる。
+pub fn fold_romaji(s: &str) -> String {
+    let s: String = s
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '\''))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    s.replace("ou", "o").replace("oo", "o").replace("uu", "u")
+}
+
+/// リファレンス名検索のキー
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchKeys {
+    /// 名称・所在地と照合する表記（入力、ひらがな、カタカナ）
+    pub text: Vec<String>,
+    /// ローマ字の検索インデックスと照合する表記（fold_romaji済み）
+    pub romaji: Vec<String>,
+}
+
+impl SearchKeys {
+    pub fn new(query: &str) -> Self {
+        let normalized = fold_width(query).trim().to_string();
+
+        let mut text: Vec<String> = Vec::new();
+        for v in [
+            normalized.clone(),
+            to_hiragana(&normalized),
+            to_katakana(&normalized),
+        ] {
+            if !v.is_empty() && !text.contains(&v) {
+                text.push(v);
+            }
+        }
+
+        let romaji = if normalized.is_ascii() {
+            Some(normalized.clone())
+        } else {
+            to_romaji(&normalized)
+        };
+        let romaji = romaji
+            .map(|r| fold_romaji(&r))
+            .filter(|r| !r.is_empty())
+            .into_iter()
+            .collect();
+
+        Self { text, romaji }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.romaji.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_width_and_kana() {
+        assert_eq!(fold_width("ﾌｼﾞｻﾝ"), "フジサン");
+        assert_eq!(fold_width("ﾎﾟﾝ　ＪＡ－１"), "ポン JA-1");
+        assert_eq!(to_hiragana("フジサン"), "ふじさん");
+        assert_eq!(to_katakana("ふじさん"), "フジサン");
+    }
+
+    #[test]
+    fn test_to_romaji() {
+        assert_eq!(to_romaji("ふじさん").as_deref(), Some("fujisan"));
+        assert_eq!(to_romaji("トウキョウ").as_deref(), Some("toukyou"));
+        assert_eq!(to_romaji("ほっかいどう").as_deref(), Some("hokkaidou"));
+        assert_eq!(to_romaji("しゃちほこ").as_deref(), Some("shachihoko"));
+        assert_eq!(to_romaji("まっちゃ").as_deref(), Some("matcha"));
+        assert_eq!(to_romaji("ふぁみりー").as_deref(), Some("famiri"));
+        assert_eq!(to_romaji("富士さん"), None);
+    }
+
+    #[test]
+    fn test_search_keys() {
+        let keys = SearchKeys::new("とうきょう");
+        assert_eq!(keys.text, vec!["とうきょう", "トウキョウ"]);
+        assert_eq!(keys.romaji, vec!["tokyo"]);
+        assert_eq!(SearchKeys::new("Mt. Tokyo").romaji, vec!["mttokyo"]);
+        assert_eq!(SearchKeys::new("Kōshin").romaji, Vec::<String>::new());
+
+        let keys = SearchKeys::new("富士");
+        assert_eq!(keys.text, vec!["富士"]);
+        assert!(keys.romaji.is_empty());
+    }
+}
@@ -1,5 +1,9 @@
 pub mod award_config;
 pub mod config;
+pub mod dxcc;
 pub mod error;
 pub mod http;
+pub mod jobs;
+pub mod kana;
+pub mod metrics;
 pub mod utils;
@@ -0,0 +1,374 @@
+//! Prometheus テキスト形式のメトリクス
+//!
+//! 外部クレートに依存せず、カウンタ・ゲージ・ヒストグラムを
+//! プロセス内のグローバルレジストリで保持して `/metrics` で出力する。
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+/// HTTPリクエスト数（method, route, status）
+pub const HTTP_REQUESTS_TOTAL: &str = "sotaapp_http_requests_total";
+/// HTTPリクエスト処理時間（method, route）
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "sotaapp_http_request_duration_seconds";
+/// 取得したアラート数（source）
+pub const ALERTS_FETCHED_TOTAL: &str = "sotaapp_alerts_fetched_total";
+/// 変換に成功したアラート数（source）
+pub const ALERTS_CONVERTED_TOTAL: &str = "sotaapp_alerts_converted_total";
+/// 取得したスポット数（source）
+pub const SPOTS_FETCHED_TOTAL: &str = "sotaapp_spots_fetched_total";
+/// 変換に成功したスポット数（source）
+pub const SPOTS_CONVERTED_TOTAL: &str = "sotaapp_spots_converted_total";
+/// 受信したAPRSパケット数（type）
+pub const APRS_PACKETS_TOTAL: &str = "sotaapp_aprs_packets_total";
+/// 送信したAPRSメッセージ数（kind）
+pub const APRS_MESSAGES_SENT_TOTAL: &str = "sotaapp_aprs_messages_sent_total";
+/// aprs_log の状態遷移数（from, to）
+pub const APRS_STATE_TRANSITIONS_TOTAL: &str = "sotaapp_aprs_state_transitions_total";
+/// リポジトリのクエリ処理時間（repository, query）
+pub const REPOSITORY_QUERY_DURATION_SECONDS: &str = "sotaapp_repository_query_duration_seconds";
+/// サミット/パークリスト更新の所要時間（list）
+pub const REFERENCE_LIST_UPDATE_DURATION_SECONDS: &str =
+    "sotaapp_reference_list_update_duration_seconds";
+/// サミット/パークリスト更新で更新されたリファレンス数（list）
+pub const REFERENCE_LIST_UPDATED: &str = "sotaapp_reference_list_updated";
+/// サミット/パークリスト更新の最終成功時刻（list）
+pub const REFERENCE_LIST_LAST_SUCCESS_TIMESTAMP: &str =
+    "sotaapp_reference_list_last_success_timestamp_seconds";
+/// GeomagIndexの最終取得時刻
+pub const GEOMAG_LAST_UPDATE_TIMESTAMP: &str = "sotaapp_geomag_last_update_timestamp_seconds";
+/// GeomagIndexの対象日からの経過時間
+pub const GEOMAG_INDEX_AGE_SECONDS: &str = "sotaapp_geomag_index_age_seconds";
+/// GeomagIndexの取得失敗数
+pub const GEOMAG_UPDATE_ERRORS_TOTAL: &str = "sotaapp_geomag_update_errors_total";
+/// 起動からの経過時間
+pub const UPTIME_SECONDS: &str = "sotaapp_uptime_seconds";
+/// 常駐メモリサイズ
+pub const MEMORY_RSS_BYTES: &str = "sotaapp_memory_rss_bytes";
+/// データベース状態（1=正常）
+pub const DATABASE_UP: &str = "sotaapp_database_up";
+
+/// ヒストグラムのデフォルトバケット（秒）
+const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+const HELP: &[(&str, &str)] = &[
+    (HTTP_REQUESTS_TOTAL, "Total HTTP requests by route."),
+    (
+        HTTP_REQUEST_DURATION_SECONDS,
+        "HTTP request latency by route.",
+    ),
+    (ALERTS_FETCHED_TOTAL, "Alerts fetched from upstream."),
+    (ALERTS_CONVERTED_TOTAL, "Alerts successfully converted."),
+    (SPOTS_FETCHED_TOTAL, "Spots fetched from upstream."),
+    (SPOTS_CONVERTED_TOTAL, "Spots successfully converted."),
+    (APRS_PACKETS_TOTAL, "APRS packets received by type."),
+    (APRS_MESSAGES_SENT_TOTAL, "APRS messages sent."),
+    (APRS_STATE_TRANSITIONS_TOTAL, "aprs_log state transitions."),
+    (
+        REPOSITORY_QUERY_DURATION_SECONDS,
+        "Repository query latency.",
+    ),
+    (
+        REFERENCE_LIST_UPDATE_DURATION_SECONDS,
+        "Duration of the last summit/park list update.",
+    ),
+    (
+        REFERENCE_LIST_UPDATED,
+        "References updated by the last summit/park list update.",
+    ),
+    (
+        REFERENCE_LIST_LAST_SUCCESS_TIMESTAMP,
+        "Unix time of the last successful summit/park list update.",
+    ),
+    (
+        GEOMAG_LAST_UPDATE_TIMESTAMP,
+        "Unix time of the last successful GeomagIndex fetch.",
+    ),
+    (
+        GEOMAG_INDEX_AGE_SECONDS,
+        "Seconds since the start of the current GeomagIndex date.",
+    ),
+    (GEOMAG_UPDATE_ERRORS_TOTAL, "Failed GeomagIndex fetches."),
+    (UPTIME_SECONDS, "Seconds since the server started."),
+    (MEMORY_RSS_BYTES, "Resident set size of the process."),
+    (DATABASE_UP, "Whether the database health check passed."),
+];
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; DEFAULT_BUCKETS.len()];
+        }
+        for (i, le) in DEFAULT_BUCKETS.iter().enumerate() {
+            if value <= *le {
+                self.buckets[i] += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// メトリクスレジストリ
+#[derive(Debug, Default)]
+pub struct Registry {
+    counters: Mutex<BTreeMap<String, BTreeMap<Labels, u64>>>,
+    gauges: Mutex<BTreeMap<String, BTreeMap<Labels, f64>>>,
+    histograms: Mutex<BTreeMap<String, BTreeMap<Labels, Histogram>>>,
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+    labels
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_labels(labels: &Labels, extra: Option<(&str, &str)>) -> String {
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+        .collect();
+    if let Some((k, v)) = extra {
+        parts.push(format!("{}=\"{}\"", k, escape_label(v)));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str) {
+    if let Some((_, help)) = HELP.iter().find(|(n, _)| *n == name) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+    }
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+impl Registry {
+    /// カウンタを加算
+    pub fn add(&self, name: &str, labels: &[(&str, &str)], value: u64) {
+        if let Ok(mut guard) = self.counters.lock() {
+            *guard
+                .entry(name.to_string())
+                .or_default()
+                .entry(to_labels(labels))
+                .or_default() += value;
+        }
+    }
+
+    /// ゲージを設定
+    pub fn set(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        if let Ok(mut guard) = self.gauges.lock() {
+            guard
+                .entry(name.to_string())
+                .or_default()
+                .insert(to_labels(labels), value);
+        }
+    }
+
+    /// ヒストグラムに観測値（秒）を追加
+    pub fn observe(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        if let Ok(mut guard) = self.histograms.lock() {
+            guard
+                .entry(name.to_string())
+                .or_default()
+                .entry(to_labels(labels))
+                .or_default()
+                .observe(value);
+        }
+    }
+
+    /// Prometheus テキスト形式で出力
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        if let Ok(guard) = self.counters.lock() {
+            for (name, series) in guard.iter() {
+                write_header(&mut out, name, "counter");
+                for (labels, value) in series {
+                    let _ = writeln!(out, "{}{} {}", name, format_labels(labels, None), value);
+                }
+            }
+        }
+
+        if let Ok(guard) = self.gauges.lock() {
+            for (name, series) in guard.iter() {
+                write_header(&mut out, name, "gauge");
+                for (labels, value) in series {
+                    let _ = writeln!(out, "{}{} {}", name, format_labels(labels, None), value);
+                }
+            }
+        }
+
+        if let Ok(guard) = self.histograms.lock() {
+            for (name, series) in guard.iter() {
+                write_header(&mut out, name, "histogram");
+                for (labels, h) in series {
+                    for (le, count) in DEFAULT_BUCKETS.iter().zip(h.buckets.iter()) {
+                        let le = le.to_string();
+                        let _ = writeln!(
+                            out,
+                            "{}_bucket{} {}",
+                            name,
+                            format_labels(labels, Some(("le", &le))),
+                            count
+                        );
+                    }
+                    let _ = writeln!(
+                        out,
+                        "{}_bucket{} {}",
+                        name,
+                        format_labels(labels, Some(("le", "+Inf"))),
+                        h.count
+                    );
+                    let _ = writeln!(out, "{}_sum{} {}", name, format_labels(labels, None), h.sum);
+                    let _ = writeln!(
+                        out,
+                        "{}_count{} {}",
+                        name,
+                        format_labels(labels, None),
+                        h.count
+                    );
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// グローバルレジストリ
+pub fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::default)
+}
+
+/// カウンタを1加算
+pub fn inc_counter(name: &str, labels: &[(&str, &str)]) {
+    registry().add(name, labels, 1);
+}
+
+/// カウンタを加算
+pub fn add_counter(name: &str, labels: &[(&str, &str)], value: u64) {
+    registry().add(name, labels, value);
+}
+
+/// ゲージを設定
+pub fn set_gauge(name: &str, labels: &[(&str, &str)], value: f64) {
+    registry().set(name, labels, value);
+}
+
+/// ヒストグラムに観測値を追加
+pub fn observe(name: &str, labels: &[(&str, &str)], value: f64) {
+    registry().observe(name, labels, value);
+}
+
+/// 現在のメトリクスを Prometheus テキスト形式で出力
+pub fn render() -> String {
+    registry().render()
+}
+
+/// スコープ終了時にリポジトリのクエリ処理時間を記録するタイマー
+pub struct QueryTimer {
+    repository: &'static str,
+    query: &'static str,
+    start: Instant,
+}
+
+impl QueryTimer {
+    pub fn new(repository: &'static str, query: &'static str) -> Self {
+        Self {
+            repository,
+            query,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        observe(
+            REPOSITORY_QUERY_DURATION_SECONDS,
+            &[("repository", self.repository), ("query", self.query)],
+            self.start.elapsed().as_secs_f64(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_render() {
+        let reg = Registry::default();
+        reg.add(ALERTS_FETCHED_TOTAL, &[("source", "sota")], 3);
+        reg.add(ALERTS_FETCHED_TOTAL, &[("source", "sota")], 2);
+        reg.add(ALERTS_FETCHED_TOTAL, &[("source", "pota")], 1);
+
+        let text = reg.render();
+        assert!(text.contains("# TYPE sotaapp_alerts_fetched_total counter"));
+        assert!(text.contains("sotaapp_alerts_fetched_total{source=\"sota\"} 5"));
+        assert!(text.contains("sotaapp_alerts_fetched_total{source=\"pota\"} 1"));
+    }
+
+    #[test]
+    fn test_gauge_overwrite() {
+        let reg = Registry::default();
+        reg.set(UPTIME_SECONDS, &[], 10.0);
+        reg.set(UPTIME_SECONDS, &[], 20.0);
+
+        let text = reg.render();
+        assert!(text.contains("# TYPE sotaapp_uptime_seconds gauge"));
+        assert!(text.contains("sotaapp_uptime_seconds 20"));
+        assert!(!text.contains("sotaapp_uptime_seconds 10"));
+    }
+
+    #[test]
+    fn test_histogram_buckets_are_cumulative() {
+        let reg = Registry::default();
+        let labels = [("repository", "activation"), ("query", "find_spots")];
+        reg.observe(REPOSITORY_QUERY_DURATION_SECONDS, &labels, 0.003);
+        reg.observe(REPOSITORY_QUERY_DURATION_SECONDS, &labels, 0.2);
+        reg.observe(REPOSITORY_QUERY_DURATION_SECONDS, &labels, 30.0);
+
+        let text = reg.render();
+        let prefix = "sotaapp_repository_query_duration_seconds_bucket{repository=\"activation\",query=\"find_spots\"";
+        assert!(text.contains(&format!("{},le=\"0.005\"}} 1", prefix)));
+        assert!(text.contains(&format!("{},le=\"0.25\"}} 2", prefix)));
+        assert!(text.contains(&format!("{},le=\"10\"}} 2", prefix)));
+        assert!(text.contains(&format!("{},le=\"+Inf\"}} 3", prefix)));
+        assert!(text.contains(
+            "sotaapp_repository_query_duration_seconds_count{repository=\"activation\",query=\"find_spots\"} 3"
+        ));
+    }
+
+    #[test]
+    fn test_label_escape() {
+        let reg = Registry::default();
+        reg.add(HTTP_REQUESTS_TOTAL, &[("route", "a\"b\\c")], 1);
+        let text = reg.render();
+        assert!(text.contains(r#"route="a\"b\\c""#));
+    }
+}
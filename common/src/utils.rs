@@ -27,6 +27,42 @@ pub fn csv_reader<T: DeserializeOwned + std::fmt::Debug>(
     Ok(reflist)
 }
 
+/// CSVの行エラー（行番号は1始まり）
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRowError {
+    pub line: u64,
+    pub message: String,
+}
+
+/// 不正な行があっても中断せずに全行を読み、行番号つきの結果とエラーを返す
+pub fn csv_reader_lenient<T: DeserializeOwned>(
+    csv: &str,
+    has_headers: bool,
+    skip: usize,
+) -> (Vec<(u64, T)>, Vec<CsvRowError>) {
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(has_headers)
+        .flexible(true)
+        .from_reader(csv.as_bytes());
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for result in rdr.records().skip(skip) {
+        let parsed = result.and_then(|record| {
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            record.deserialize::<T>(None).map(|r| (line, r))
+        });
+        match parsed {
+            Ok(row) => rows.push(row),
+            Err(e) => errors.push(CsvRowError {
+                line: e.position().map(|p| p.line()).unwrap_or_default(),
+                message: e.to_string(),
+            }),
+        }
+    }
+    (rows, errors)
+}
+
 pub fn call_to_operator(callsign: &str) -> String {
     let callsign = callsign.trim_end().to_string();
     let parts: Vec<_> = callsign.split("/").collect();
@@ -53,6 +89,65 @@ pub fn calculate_distance(lat: f64, lon: f64, lat2: f64, lon2: f64) -> f64 {
     g.inverse(lat, lon, lat2, lon2)
 }
 
+/// Douglas-Peucker法で残す点のインデックスを返す（点は(緯度, 経度)、toleranceはm）
+///
+/// 始点と終点は常に残す。短い区間なので点と線分の距離は局所的な平面近似で求める。
+pub fn simplify_track(points: &[(f64, f64)], tolerance: f64) -> Vec<usize> {
+    const METERS_PER_DEGREE: f64 = 111_320.0;
+
+    if points.len() < 3 {
+        return (0..points.len()).collect();
+    }
+
+    let project = |(lat, lon): (f64, f64), lat0: f64| {
+        (
+            lon * METERS_PER_DEGREE * lat0.to_radians().cos(),
+            lat * METERS_PER_DEGREE,
+        )
+    };
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        if last <= first + 1 {
+            continue;
+        }
+        let lat0 = points[first].0;
+        let (ax, ay) = project(points[first], lat0);
+        let (bx, by) = project(points[last], lat0);
+        let (dx, dy) = (bx - ax, by - ay);
+        let len2 = dx * dx + dy * dy;
+
+        let mut farthest = (first, 0.0);
+        for (i, p) in points.iter().enumerate().take(last).skip(first + 1) {
+            let (px, py) = project(*p, lat0);
+            let t = if len2 > 0.0 {
+                (((px - ax) * dx + (py - ay) * dy) / len2).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let dist = (px - ax - t * dx).hypot(py - ay - t * dy);
+            if dist > farthest.1 {
+                farthest = (i, dist);
+            }
+        }
+
+        if farthest.1 > tolerance {
+            keep[farthest.0] = true;
+            stack.push((first, farthest.0));
+            stack.push((farthest.0, last));
+        }
+    }
+
+    keep.iter()
+        .enumerate()
+        .filter_map(|(i, k)| k.then_some(i))
+        .collect()
+}
+
 pub fn maidenhead(lon: f64, lat: f64) -> String {
     let lon = if lon >= 180.0 { lon - 360.0f64 } else { lon };
     longlat_to_grid(lon, lat, 8).unwrap_or("--------".to_string())
@@ -145,6 +240,28 @@ mod tests {
         assert!(dist > 350_000.0 && dist < 450_000.0);
     }
 
+    #[test]
+    fn test_simplify_track_straight_line() {
+        // 直線上の点は始点と終点だけ残る
+        let points: Vec<_> = (0..10).map(|i| (35.0 + i as f64 * 0.001, 139.0)).collect();
+        assert_eq!(simplify_track(&points, 1.0), vec![0, 9]);
+    }
+
+    #[test]
+    fn test_simplify_track_keeps_corner() {
+        // L字の角（約90m外れる）は許容値10mなら残し、200mなら落とす
+        let points = vec![
+            (35.0, 139.0),
+            (35.0004, 139.0),
+            (35.0008, 139.0),
+            (35.0008, 139.001),
+            (35.0008, 139.002),
+        ];
+        assert_eq!(simplify_track(&points, 10.0), vec![0, 2, 4]);
+        assert_eq!(simplify_track(&points, 200.0), vec![0, 4]);
+        assert_eq!(simplify_track(&points[..2], 10.0), vec![0, 1]);
+    }
+
     #[test]
     fn test_calculate_bounding_box() {
         let (min_lat, min_lon, max_lat, max_lon) = calculate_bounding_box(35.0, 139.0, 10000.0);
@@ -152,4 +269,16 @@ mod tests {
         assert!(min_lat < 35.0 && max_lat > 35.0);
         assert!(min_lon < 139.0 && max_lon > 139.0);
     }
+
+    #[test]
+    fn test_csv_reader_lenient() {
+        let csv = "header\nA,1\nB,x\nC,3\n";
+        let (rows, errors) = csv_reader_lenient::<(String, i32)>(csv, false, 1);
+        assert_eq!(
+            rows,
+            vec![(2, ("A".to_string(), 1)), (4, ("C".to_string(), 3))]
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+    }
 }
@@ -1,9 +1,15 @@
+#[cfg(any(test, feature = "test-util"))]
+use chrono::NaiveDate;
 use chrono::{DateTime, Utc};
+use common::dxcc::DxccInfo;
 
+use crate::model::needed::Needed;
 use crate::model::pota::PotaRefLog;
+#[cfg(any(test, feature = "test-util"))]
+use crate::model::sota::SotaReference;
 use crate::model::AwardProgram;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Alert {
     pub program: AwardProgram,
     pub alert_id: i32,
@@ -35,12 +41,24 @@ pub struct Spot {
     pub mode: String,
     pub spotter: String,
     pub comment: Option<String>,
+    /// 正規化した周波数（Hz、読めなければNone）
+    pub frequency_hz: Option<i64>,
+    /// 周波数から求めたバンド（"20m"など）
+    pub band: Option<String>,
+    /// ADIFのモード（CW/SSB/FM/MFSKなど）
+    pub canonical_mode: Option<String>,
+    /// まとめた重複スポットのスポッター（spotterを含む）
+    pub spotters: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SpotLog {
     pub spot: Spot,
     pub qsos: Option<i32>,
+    /// 利用者のログから見た未交信フラグ（ログ指定時のみ）
+    pub needed: Option<Needed>,
+    /// アクティベーターのDXCCエンティティ（カントリーファイル読み込み時のみ）
+    pub dxcc: Option<DxccInfo>,
 }
 
 impl SpotLog {
@@ -49,12 +67,214 @@ impl SpotLog {
             SpotLog {
                 spot: s,
                 qsos: r.qsos,
+                needed: None,
+                dxcc: None,
             }
         } else {
             SpotLog {
                 spot: s,
                 qsos: None,
+                needed: None,
+                dxcc: None,
             }
         }
     }
 }
+
+/// アラートに対するアクティベーション結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+#[repr(i32)]
+pub enum ActivationOutcome {
+    /// アラートの時間帯がまだ終わっておらずスポットもない
+    Pending = 0,
+    /// 予定のリファレンスで予定時刻どおりにスポットされた
+    OnTime = 1,
+    /// 予定のリファレンスで予定時刻より遅れてスポットされた
+    Late = 2,
+    /// アラートの時間帯に別のサミット/パークでスポットされた
+    DifferentReference = 3,
+    /// アラートの時間帯にスポットされなかった
+    NoShow = 4,
+}
+
+impl ActivationOutcome {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::OnTime => "on_time",
+            Self::Late => "late",
+            Self::DifferentReference => "different_reference",
+            Self::NoShow => "no_show",
+        }
+    }
+}
+
+impl From<i32> for ActivationOutcome {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::OnTime,
+            2 => Self::Late,
+            3 => Self::DifferentReference,
+            4 => Self::NoShow,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// スポットと突き合わせたアラート
+#[derive(Debug, Clone)]
+pub struct AlertLog {
+    pub alert: Alert,
+    pub outcome: ActivationOutcome,
+    pub spot: Option<Spot>,
+    /// APRSから推定したサミット到着予想時刻（未スポットのSOTAアラートのみ）
+    pub eta: Option<DateTime<Utc>>,
+    /// 利用者のログから見た未交信フラグ（ログ指定時のみ）
+    pub needed: Option<Needed>,
+    /// アクティベーターのDXCCエンティティ（カントリーファイル読み込み時のみ）
+    pub dxcc: Option<DxccInfo>,
+}
+
+/// 確定したアクティベーション結果（アラート削除後も保持する）
+#[derive(Debug, Clone)]
+pub struct AlertOutcome {
+    pub program: AwardProgram,
+    pub alert_id: i32,
+    pub operator: String,
+    pub reference: String,
+    pub start_time: DateTime<Utc>,
+    pub outcome: ActivationOutcome,
+    pub spot_reference: Option<String>,
+    pub spot_time: Option<DateTime<Utc>>,
+}
+
+impl AlertOutcome {
+    pub fn new(log: &AlertLog) -> Self {
+        Self {
+            program: log.alert.program.clone(),
+            alert_id: log.alert.alert_id,
+            operator: log.alert.operator.clone(),
+            reference: log.alert.reference.clone(),
+            start_time: log.alert.start_time,
+            outcome: log.outcome,
+            spot_reference: log.spot.as_ref().map(|s| s.reference.clone()),
+            spot_time: log.spot.as_ref().map(|s| s.spot_time),
+        }
+    }
+}
+
+/// コールサインごとのアラート履行状況
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutcomeSummary {
+    pub operator: String,
+    pub total: i64,
+    pub on_time: i64,
+    pub late: i64,
+    pub different_reference: i64,
+    pub no_show: i64,
+}
+
+impl OutcomeSummary {
+    pub fn from_outcomes(operator: &str, outcomes: &[AlertOutcome]) -> Self {
+        let mut summary = Self {
+            operator: operator.to_string(),
+            ..Default::default()
+        };
+        for o in outcomes {
+            match o.outcome {
+                ActivationOutcome::Pending => continue,
+                ActivationOutcome::OnTime => summary.on_time += 1,
+                ActivationOutcome::Late => summary.late += 1,
+                ActivationOutcome::DifferentReference => summary.different_reference += 1,
+                ActivationOutcome::NoShow => summary.no_show += 1,
+            }
+            summary.total += 1;
+        }
+        summary
+    }
+
+    /// 予定のリファレンスでQRVした割合
+    pub fn reliability(&self) -> Option<f64> {
+        if self.total == 0 {
+            None
+        } else {
+            Some((self.on_time + self.late) as f64 / self.total as f64)
+        }
+    }
+}
+
+/// テスト用Alertを生成するヘルパー（他のクレートのテストでは `test-util` フィーチャーで使う）
+#[cfg(any(test, feature = "test-util"))]
+pub fn make_test_alert(activator: &str, reference: &str) -> Alert {
+    Alert {
+        program: AwardProgram::SOTA,
+        alert_id: 1,
+        user_id: 1,
+        activator: activator.to_string(),
+        activator_name: None,
+        operator: activator.to_string(),
+        reference: reference.to_string(),
+        reference_detail: "Test Summit".to_string(),
+        location: "Tokyo".to_string(),
+        start_time: Utc::now(),
+        end_time: None,
+        frequencies: "14.280".to_string(),
+        comment: Some("Test".to_string()),
+        poster: Some(activator.to_string()),
+    }
+}
+
+/// テスト用Spotを生成するヘルパー（他のクレートのテストでは `test-util` フィーチャーで使う）
+#[cfg(any(test, feature = "test-util"))]
+pub fn make_test_spot(activator: &str, reference: &str) -> Spot {
+    Spot {
+        program: AwardProgram::SOTA,
+        spot_id: 1,
+        activator: activator.to_string(),
+        activator_name: None,
+        operator: activator.to_string(),
+        reference: reference.to_string(),
+        reference_detail: "Test Summit".to_string(),
+        spot_time: Utc::now(),
+        frequency: "14.280".to_string(),
+        mode: "SSB".to_string(),
+        spotter: "JA2XYZ".to_string(),
+        comment: Some("Test".to_string()),
+        frequency_hz: None,
+        band: None,
+        canonical_mode: None,
+        spotters: Vec::new(),
+    }
+}
+
+/// テスト用SotaReferenceを生成するヘルパー（他のクレートのテストでは `test-util` フィーチャーで使う）
+#[cfg(any(test, feature = "test-util"))]
+pub fn make_test_summit(valid_from: NaiveDate, valid_to: NaiveDate) -> SotaReference {
+    SotaReference {
+        summit_code: "JA/TK-001".to_string(),
+        association_name: "Japan".to_string(),
+        region_name: "Tokyo".to_string(),
+        summit_name: "Mt. Test".to_string(),
+        summit_name_j: None,
+        alt_m: 1000,
+        alt_ft: 3280,
+        grid_ref1: "PM95".to_string(),
+        grid_ref2: "".to_string(),
+        longitude: 139.0,
+        latitude: 35.0,
+        maidenhead: "PM95wv".to_string(),
+        points: 10,
+        bonus_points: 0,
+        valid_from,
+        valid_to,
+        activation_count: 0,
+        activation_date: None,
+        activation_call: None,
+        city: None,
+        city_j: None,
+    }
+}
@@ -0,0 +1,164 @@
+//! スポットのバンド・モード・時間帯ごとの集計
+//!
+//! 今どのバンドでSOTA/POTAのアクティベーションが聞こえているかを見るために使う。
+//! 時間帯はUTCの時（0〜23）で、地磁気指数（3時間ごとのK指数）と同じ基準にしている。
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Timelike, Utc};
+
+use crate::model::activation::Spot;
+use crate::model::AwardProgram;
+
+/// プログラム・バンド・モード・時間帯ごとのスポット数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityCell {
+    pub program: AwardProgram,
+    pub band: String,
+    pub mode: String,
+    /// UTCの時（0〜23）
+    pub hour: u32,
+    pub count: u32,
+}
+
+/// 期間内のスポットの集計
+#[derive(Debug, Clone)]
+pub struct ActivityStats {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// 集計したスポット数
+    pub total: u32,
+    /// バンドがわからず（正規化で `Spot.band` が決まらず）除いたスポット数
+    pub unknown_band: u32,
+    /// バンド（周波数の低い順）・モード・プログラム・時間帯の順に並べる
+    pub cells: Vec<ActivityCell>,
+}
+
+impl ActivityStats {
+    /// スポットの正規化したバンド（`Spot.band`）とモード（`Spot.canonical_mode`）で集計する
+    pub fn from_spots(spots: &[Spot], from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        let mut counts: BTreeMap<(String, String, i32, u32), u32> = BTreeMap::new();
+        // バンドの並び順に使うバンドごとの最低周波数
+        let mut band_hz: HashMap<String, i64> = HashMap::new();
+        let mut unknown_band = 0;
+
+        for spot in spots
+            .iter()
+            .filter(|s| s.spot_time >= from && s.spot_time <= to)
+        {
+            let Some(band) = &spot.band else {
+                unknown_band += 1;
+                continue;
+            };
+            let hz = spot.frequency_hz.unwrap_or(i64::MAX);
+            band_hz
+                .entry(band.clone())
+                .and_modify(|v| *v = (*v).min(hz))
+                .or_insert(hz);
+            let key = (
+                band.clone(),
+                spot.canonical_mode.clone().unwrap_or_default(),
+                spot.program.as_i32(),
+                spot.spot_time.hour(),
+            );
+            *counts.entry(key).or_insert(0) += 1;
+        }
+
+        let mut cells: Vec<ActivityCell> = counts
+            .into_iter()
+            .map(|((band, mode, program, hour), count)| ActivityCell {
+                program: AwardProgram::from(program),
+                band,
+                mode,
+                hour,
+                count,
+            })
+            .collect();
+        // 安定ソートなのでバンド内はモード・プログラム・時間帯の順のまま
+        cells.sort_by_key(|c| band_hz[&c.band]);
+
+        Self {
+            from,
+            to,
+            total: cells.iter().map(|c| c.count).sum(),
+            unknown_band,
+            cells,
+        }
+    }
+
+    /// スポットのあったバンド（周波数の低い順）
+    pub fn bands(&self) -> Vec<String> {
+        let mut bands: Vec<String> = Vec::new();
+        for cell in &self.cells {
+            if !bands.contains(&cell.band) {
+                bands.push(cell.band.clone());
+            }
+        }
+        bands
+    }
+
+    /// バンドと時間帯ごとのスポット数（モード・プログラムは合算）
+    pub fn band_hours(&self) -> BTreeMap<(String, u32), u32> {
+        let mut result = BTreeMap::new();
+        for cell in &self.cells {
+            *result.entry((cell.band.clone(), cell.hour)).or_insert(0) += cell.count;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::activation::make_test_spot;
+    use chrono::TimeZone;
+
+    fn spot(program: AwardProgram, time: DateTime<Utc>, hz: i64, band: &str, mode: &str) -> Spot {
+        Spot {
+            program,
+            spot_time: time,
+            frequency_hz: Some(hz),
+            band: Some(band.to_string()),
+            canonical_mode: Some(mode.to_string()),
+            ..make_test_spot("JA1ABC/P", "JA/TK-001")
+        }
+    }
+
+    #[test]
+    fn test_activity_stats_from_spots() {
+        let from = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 10, 18, 23, 59, 59).unwrap();
+        let t1 = Utc.with_ymd_and_hms(2026, 10, 18, 1, 10, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2026, 10, 18, 1, 50, 0).unwrap();
+        let t3 = Utc.with_ymd_and_hms(2026, 10, 18, 5, 0, 0).unwrap();
+        let old = Utc.with_ymd_and_hms(2026, 10, 17, 5, 0, 0).unwrap();
+        let spots = vec![
+            spot(AwardProgram::SOTA, t1, 145_500_000, "2m", "FM"),
+            spot(AwardProgram::SOTA, t2, 14_062_000, "20m", "CW"),
+            spot(AwardProgram::POTA, t2, 14_062_000, "20m", "CW"),
+            spot(AwardProgram::SOTA, t3, 14_285_000, "20m", "SSB"),
+            Spot {
+                band: None,
+                ..spot(AwardProgram::SOTA, t3, 0, "", "CW")
+            },
+            spot(AwardProgram::SOTA, old, 14_062_000, "20m", "CW"),
+        ];
+
+        let stats = ActivityStats::from_spots(&spots, from, to);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.unknown_band, 1);
+        assert_eq!(stats.bands(), vec!["20m".to_string(), "2m".to_string()]);
+
+        let band_hours = stats.band_hours();
+        assert_eq!(band_hours.get(&("20m".to_string(), 1)), Some(&2));
+        assert_eq!(band_hours.get(&("20m".to_string(), 5)), Some(&1));
+        assert_eq!(band_hours.get(&("2m".to_string(), 1)), Some(&1));
+
+        let first = &stats.cells[0];
+        assert_eq!(first.band, "20m");
+        assert_eq!(first.mode, "CW");
+        assert_eq!(first.program, AwardProgram::SOTA);
+        assert_eq!(first.hour, 1);
+        assert_eq!(first.count, 1);
+    }
+}
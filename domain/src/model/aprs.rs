@@ -0,0 +1,493 @@
+//! APRSパケット
+//!
+//! APRS-ISから受信したTNC2形式（`FROM>TO,PATH:payload`）の生パケットを解析し、
+//! メッセージ・位置・オブジェクト・アイテム・気象・テレメトリを取り出す。
+
+use aprs_message::AprsCallsign;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use common::error::AppError;
+
+/// 受信したAPRSパケット
+#[derive(Debug, Clone, PartialEq)]
+pub enum AprsPacket {
+    Message {
+        callsign: AprsCallsign,
+        addressee: String,
+        message: String,
+    },
+    Position {
+        callsign: AprsCallsign,
+        latitude: f64,
+        longitude: f64,
+        /// 高度（m、コメントの `/A=` から取得）
+        altitude: Option<f64>,
+    },
+    Object {
+        callsign: AprsCallsign,
+        object: AprsObject,
+    },
+    Weather {
+        callsign: AprsCallsign,
+        /// (緯度, 経度)。位置なし気象パケットでは None
+        position: Option<(f64, f64)>,
+        report: WeatherReport,
+    },
+    Telemetry {
+        callsign: AprsCallsign,
+        telemetry: Telemetry,
+    },
+}
+
+impl AprsPacket {
+    pub fn callsign(&self) -> &AprsCallsign {
+        match self {
+            Self::Message { callsign, .. }
+            | Self::Position { callsign, .. }
+            | Self::Object { callsign, .. }
+            | Self::Weather { callsign, .. }
+            | Self::Telemetry { callsign, .. } => callsign,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Message { .. } => "message",
+            Self::Position { .. } => "position",
+            Self::Object { object, .. } => match object.kind {
+                AprsObjectKind::Object => "object",
+                AprsObjectKind::Item => "item",
+            },
+            Self::Weather { .. } => "weather",
+            Self::Telemetry { .. } => "telemetry",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AprsObjectKind {
+    Object,
+    Item,
+}
+
+/// オブジェクト・アイテム
+#[derive(Debug, Clone, PartialEq)]
+pub struct AprsObject {
+    pub kind: AprsObjectKind,
+    pub name: String,
+    /// false なら削除（kill）
+    pub alive: bool,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub comment: String,
+}
+
+/// 気象データ（単位はAPRS仕様のまま）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeatherReport {
+    /// 風向（度）
+    pub wind_direction: Option<u16>,
+    /// 風速（mph）
+    pub wind_speed: Option<u16>,
+    /// 最大瞬間風速（mph）
+    pub wind_gust: Option<u16>,
+    /// 気温（°F）
+    pub temperature: Option<i16>,
+    /// 雨量（1/100インチ、過去1時間・過去24時間・0時から）
+    pub rain_1h: Option<u16>,
+    pub rain_24h: Option<u16>,
+    pub rain_midnight: Option<u16>,
+    /// 湿度（%）
+    pub humidity: Option<u8>,
+    /// 気圧（1/10 hPa）
+    pub pressure: Option<u32>,
+}
+
+impl WeatherReport {
+    pub fn temperature_c(&self) -> Option<f64> {
+        self.temperature.map(|f| (f as f64 - 32.0) * 5.0 / 9.0)
+    }
+
+    pub fn pressure_hpa(&self) -> Option<f64> {
+        self.pressure.map(|p| p as f64 / 10.0)
+    }
+}
+
+/// テレメトリ（`T#` パケット）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Telemetry {
+    pub sequence: String,
+    pub analog: Vec<f64>,
+    /// B1を最上位ビットとしたデジタル値
+    pub digital: Option<u8>,
+}
+
+/// `CALL-SSID` 形式のコールサインを分解する
+pub fn parse_callsign(s: &str) -> AprsCallsign {
+    let s = s.trim().trim_end_matches('*');
+    match s.split_once('-') {
+        Some((callsign, ssid)) => AprsCallsign {
+            callsign: callsign.to_uppercase(),
+            ssid: ssid.parse().ok().filter(|&ssid| ssid != 0),
+        },
+        None => AprsCallsign {
+            callsign: s.to_uppercase(),
+            ssid: None,
+        },
+    }
+}
+
+/// コールサインを `CALL-SSID` 形式にする
+pub fn format_callsign(callsign: &AprsCallsign) -> String {
+    match callsign.ssid {
+        Some(ssid) => format!("{}-{}", callsign.callsign, ssid),
+        None => callsign.callsign.clone(),
+    }
+}
+
+impl FromStr for AprsPacket {
+    type Err = AppError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| AppError::UnprocessableEntity(format!("{}: {}", reason, line));
+
+        let (header, payload) = line
+            .trim_end_matches(['\r', '\n'])
+            .split_once(':')
+            .ok_or_else(|| invalid("APRSヘッダがありません"))?;
+        let (from, _) = header
+            .split_once('>')
+            .ok_or_else(|| invalid("APRS送信元がありません"))?;
+        let callsign = parse_callsign(from);
+        let body = payload.get(1..).unwrap_or_default();
+
+        let packet = match payload.chars().next() {
+            Some(':') => parse_message(callsign, body),
+            Some('!') | Some('=') => parse_position(callsign, body),
+            Some('/') | Some('@') => body.get(7..).and_then(|b| parse_position(callsign, b)),
+            Some(';') => parse_object(callsign, body),
+            Some(')') => parse_item(callsign, body),
+            Some('_') => body.get(8..).map(|b| AprsPacket::Weather {
+                callsign,
+                position: None,
+                report: parse_weather(b),
+            }),
+            Some('T') if body.starts_with('#') => parse_telemetry(callsign, &body[1..]),
+            _ => return Err(invalid("未対応のAPRSパケット")),
+        };
+        packet.ok_or_else(|| invalid("APRSパケットを解析できません"))
+    }
+}
+
+fn parse_message(callsign: AprsCallsign, body: &str) -> Option<AprsPacket> {
+    let addressee = body.get(..9)?.trim().to_string();
+    let text = body.get(9..)?.strip_prefix(':')?;
+    // 末尾のメッセージ番号 `{nn` を取り除く
+    let message = match text.rsplit_once('{') {
+        Some((m, id)) if id.len() <= 5 && id.chars().all(|c| c.is_ascii_alphanumeric()) => m,
+        _ => text,
+    };
+    Some(AprsPacket::Message {
+        callsign,
+        addressee,
+        message: message.to_string(),
+    })
+}
+
+/// 位置（非圧縮・圧縮）を解析し、(緯度, 経度, シンボルコード, 残り) を返す
+fn parse_location(s: &str) -> Option<(f64, f64, char, &str)> {
+    let first = s.chars().next()?;
+    if first.is_ascii_digit() || first == ' ' {
+        let lat = parse_degrees(s.get(..8)?, 2)?;
+        let lon = parse_degrees(s.get(9..18)?, 3)?;
+        let code = s.get(18..19)?.chars().next()?;
+        Some((lat, lon, code, s.get(19..)?))
+    } else {
+        let lat = 90.0 - base91(s.get(1..5)?)? / 380926.0;
+        let lon = -180.0 + base91(s.get(5..9)?)? / 190463.0;
+        let code = s.get(9..10)?.chars().next()?;
+        Some((lat, lon, code, s.get(13..)?))
+    }
+}
+
+/// `DDMM.mmN` / `DDDMM.mmE` 形式（位置曖昧化の空白は0とみなす）
+fn parse_degrees(s: &str, deg_len: usize) -> Option<f64> {
+    let s = s.replace(' ', "0");
+    let deg: f64 = s.get(..deg_len)?.parse().ok()?;
+    let min: f64 = s.get(deg_len..s.len() - 1)?.parse().ok()?;
+    let value = deg + min / 60.0;
+    let (max, value) = match s.chars().last()? {
+        'N' => (90.0, value),
+        'S' => (90.0, -value),
+        'E' => (180.0, value),
+        'W' => (180.0, -value),
+        _ => return None,
+    };
+    (value.abs() <= max).then_some(value)
+}
+
+fn base91(s: &str) -> Option<f64> {
+    s.bytes().try_fold(0.0, |acc, b| {
+        (33..=124)
+            .contains(&b)
+            .then(|| acc * 91.0 + (b - 33) as f64)
+    })
+}
+
+/// コメント中の `/A=nnnnnn`（フィート）を m に変換する
+fn parse_altitude(comment: &str) -> Option<f64> {
+    let (_, rest) = comment.split_once("/A=")?;
+    let feet: i32 = rest.get(..6)?.parse().ok()?;
+    Some(feet as f64 * 0.3048)
+}
+
+fn parse_position(callsign: AprsCallsign, body: &str) -> Option<AprsPacket> {
+    let (latitude, longitude, code, rest) = parse_location(body)?;
+    if code == '_' {
+        return Some(AprsPacket::Weather {
+            callsign,
+            position: Some((latitude, longitude)),
+            report: parse_weather(rest),
+        });
+    }
+    Some(AprsPacket::Position {
+        callsign,
+        latitude,
+        longitude,
+        altitude: parse_altitude(rest),
+    })
+}
+
+fn parse_object(callsign: AprsCallsign, body: &str) -> Option<AprsPacket> {
+    let name = body.get(..9)?.trim_end().to_string();
+    let alive = match body.get(9..10)? {
+        "*" => true,
+        "_" => false,
+        _ => return None,
+    };
+    let (latitude, longitude, _, rest) = parse_location(body.get(17..)?)?;
+    Some(AprsPacket::Object {
+        callsign,
+        object: AprsObject {
+            kind: AprsObjectKind::Object,
+            name,
+            alive,
+            latitude,
+            longitude,
+            comment: rest.trim().to_string(),
+        },
+    })
+}
+
+fn parse_item(callsign: AprsCallsign, body: &str) -> Option<AprsPacket> {
+    let (pos, status) = body
+        .char_indices()
+        .take(10)
+        .find(|&(i, c)| i >= 3 && (c == '!' || c == '_'))?;
+    let (latitude, longitude, _, rest) = parse_location(body.get(pos + 1..)?)?;
+    Some(AprsPacket::Object {
+        callsign,
+        object: AprsObject {
+            kind: AprsObjectKind::Item,
+            name: body[..pos].to_string(),
+            alive: status == '!',
+            latitude,
+            longitude,
+            comment: rest.trim().to_string(),
+        },
+    })
+}
+
+/// 気象データ（`ddd/sss` に続く `g`,`t`,`r`,`p`,`P`,`h`,`b` 等）を解析する
+///
+/// 未知のフィールド以降はコメントとみなして無視する。
+fn parse_weather(s: &str) -> WeatherReport {
+    let mut report = WeatherReport::default();
+    let mut rest = s;
+
+    if rest.get(3..4) == Some("/") {
+        if let (Some(dir), Some(spd)) = (rest.get(..3), rest.get(4..7)) {
+            report.wind_direction = dir.parse().ok();
+            report.wind_speed = spd.parse().ok();
+            rest = &rest[7..];
+        }
+    }
+
+    while let Some(field) = rest.chars().next() {
+        let width = match field {
+            'c' | 's' | 'g' | 't' | 'r' | 'p' | 'P' | 'L' | 'l' => 3,
+            'h' => 2,
+            'b' => 5,
+            _ => break,
+        };
+        let Some(value) = rest.get(1..1 + width) else {
+            break;
+        };
+        rest = &rest[1 + width..];
+        let value = value.trim();
+        match field {
+            'c' => report.wind_direction = value.parse().ok(),
+            's' => report.wind_speed = value.parse().ok(),
+            'g' => report.wind_gust = value.parse().ok(),
+            't' => report.temperature = value.parse().ok(),
+            'r' => report.rain_1h = value.parse().ok(),
+            'p' => report.rain_24h = value.parse().ok(),
+            'P' => report.rain_midnight = value.parse().ok(),
+            'h' => report.humidity = value.parse().ok().map(|h: u8| if h == 0 { 100 } else { h }),
+            'b' => report.pressure = value.parse().ok(),
+            _ => {}
+        }
+    }
+    report
+}
+
+fn parse_telemetry(callsign: AprsCallsign, body: &str) -> Option<AprsPacket> {
+    let mut fields = body.split(',');
+    let sequence = fields.next()?.trim().to_string();
+    let values: Vec<&str> = fields.collect();
+    let analog = values
+        .iter()
+        .take(5)
+        .map(|v| v.trim().parse().ok())
+        .collect::<Option<Vec<f64>>>()?;
+    let digital = values
+        .get(5)
+        .and_then(|bits| bits.get(..8))
+        .and_then(|bits| u8::from_str_radix(bits, 2).ok());
+    Some(AprsPacket::Telemetry {
+        callsign,
+        telemetry: Telemetry {
+            sequence,
+            analog,
+            digital,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> AprsPacket {
+        line.parse().unwrap()
+    }
+
+    #[test]
+    fn test_position_with_altitude() {
+        match parse("JA1ABC-7>APRS,TCPIP*:!3540.00N/13945.00E>/A=004921 climbing") {
+            AprsPacket::Position {
+                callsign,
+                latitude,
+                longitude,
+                altitude,
+            } => {
+                assert_eq!(callsign.callsign, "JA1ABC");
+                assert_eq!(callsign.ssid, Some(7));
+                assert!((latitude - 35.666_667).abs() < 1e-5);
+                assert!((longitude - 139.75).abs() < 1e-5);
+                assert!((altitude.unwrap() - 1500.0).abs() < 0.1);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_compressed_timestamped_position() {
+        // APRS仕様書の圧縮位置の例（49°30'N 72°45'W）
+        match parse("JA1ABC-9>APRS:@092345z/5L!!<*e7>7P[") {
+            AprsPacket::Position {
+                latitude,
+                longitude,
+                ..
+            } => {
+                assert!((latitude - 49.5).abs() < 1e-4);
+                assert!((longitude + 72.75).abs() < 1e-4);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_object_and_item() {
+        match parse("JA1ABC-7>APRS:;JA/TK-001*111111z3540.00N/13945.00E[QRV 7.032 CW") {
+            AprsPacket::Object { object, .. } => {
+                assert_eq!(object.kind, AprsObjectKind::Object);
+                assert_eq!(object.name, "JA/TK-001");
+                assert!(object.alive);
+                assert_eq!(object.comment, "QRV 7.032 CW");
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let packet = parse("JA1ABC>APRS:)TK-001_3540.00N/13945.00E[");
+        assert_eq!(packet.kind(), "item");
+        match packet {
+            AprsPacket::Object { object, .. } => {
+                assert_eq!(object.name, "TK-001");
+                assert!(!object.alive);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_weather() {
+        match parse("JA1ABC-13>APRS:!3540.00N/13945.00E_220/004g005t041r000p000P000h85b10132wRSW") {
+            AprsPacket::Weather {
+                position, report, ..
+            } => {
+                assert!(position.is_some());
+                assert_eq!(report.wind_direction, Some(220));
+                assert_eq!(report.wind_gust, Some(5));
+                assert_eq!(report.temperature_c(), Some(5.0));
+                assert_eq!(report.humidity, Some(85));
+                assert_eq!(report.pressure_hpa(), Some(1013.2));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        match parse("JA1ABC-13>APRS:_10090556c220s004g...t-05h00") {
+            AprsPacket::Weather {
+                position, report, ..
+            } => {
+                assert!(position.is_none());
+                assert_eq!(report.wind_speed, Some(4));
+                assert_eq!(report.wind_gust, None);
+                assert_eq!(report.temperature, Some(-5));
+                assert_eq!(report.humidity, Some(100));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_telemetry() {
+        match parse("JA1ABC-7>APRS:T#005,199,000,255,073,123,01101001") {
+            AprsPacket::Telemetry { telemetry, .. } => {
+                assert_eq!(telemetry.sequence, "005");
+                assert_eq!(telemetry.analog, vec![199.0, 0.0, 255.0, 73.0, 123.0]);
+                assert_eq!(telemetry.digital, Some(0b0110_1001));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_message_and_invalid() {
+        assert_eq!(
+            parse("JA1ABC-7>APRS::JL1NIE-10:KEEP{12"),
+            AprsPacket::Message {
+                callsign: parse_callsign("JA1ABC-7"),
+                addressee: "JL1NIE-10".to_string(),
+                message: "KEEP".to_string(),
+            }
+        );
+        assert!("JA1ABC-7>APRS:`(_fn\"Oj/".parse::<AprsPacket>().is_err());
+        assert!("no header".parse::<AprsPacket>().is_err());
+        assert!("JA1ABC>APRS:!9940.00N/13945.00E>"
+            .parse::<AprsPacket>()
+            .is_err());
+    }
+}
@@ -1,5 +1,8 @@
 use aprs_message::AprsCallsign;
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, TimeDelta, Utc};
+use common::utils::simplify_track;
+
+use super::aprs::{Telemetry, WeatherReport};
 
 #[derive(Debug, Clone)]
 pub enum AprsState {
@@ -53,6 +56,31 @@ impl AprsState {
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Travelling { .. } => "travelling",
+            Self::Approaching { .. } => "approaching",
+            Self::Climbing { .. } => "climbing",
+            Self::NearSummit { .. } => "near_summit",
+            Self::OnSummit { .. } => "on_summit",
+            Self::Descending { .. } => "descending",
+        }
+    }
+
+    /// 時刻だけを置き換えた状態
+    pub fn with_time(&self, time: NaiveDateTime) -> Self {
+        let mut state = self.clone();
+        match &mut state {
+            Self::Travelling { time: t }
+            | Self::Approaching { time: t, .. }
+            | Self::Climbing { time: t, .. }
+            | Self::NearSummit { time: t, .. }
+            | Self::OnSummit { time: t, .. }
+            | Self::Descending { time: t, .. } => *t = time,
+        }
+        state
+    }
+
     pub fn message(&self) -> Option<&String> {
         match self {
             Self::Travelling { .. } => None,
@@ -65,6 +93,31 @@ impl AprsState {
     }
 }
 
+/// ログの元になったパケットの種類と内容
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum AprsLogDetail {
+    #[default]
+    Position,
+    /// サミットオブジェクト（オブジェクト名）
+    Object(String),
+    /// サミットアイテム（アイテム名）
+    Item(String),
+    Weather(WeatherReport),
+    Telemetry(Telemetry),
+}
+
+impl AprsLogDetail {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Position => "position",
+            Self::Object(_) => "object",
+            Self::Item(_) => "item",
+            Self::Weather(_) => "weather",
+            Self::Telemetry(_) => "telemetry",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct AprsLog {
     pub callsign: AprsCallsign,
@@ -72,6 +125,9 @@ pub struct AprsLog {
     pub state: AprsState,
     pub longitude: f64,
     pub latitude: f64,
+    /// 高度（m）
+    pub altitude: Option<f64>,
+    pub detail: AprsLogDetail,
 }
 
 #[derive(Debug)]
@@ -86,6 +142,124 @@ pub struct AprsTrack {
     pub spot_freq: Option<String>,
     pub spot_mode: Option<String>,
     pub spot_comment: Option<String>,
+    /// トラックに付加された最新の気象データ
+    pub weather: Option<WeatherReport>,
+    /// サミット到着予想時刻
+    pub eta: Option<DateTime<Utc>>,
+}
+
+/// 時刻つきの位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct AprsTrackPoint {
+    pub time: NaiveDateTime,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// 1回のアクティベーション分のトラック
+#[derive(Debug, Clone)]
+pub struct AprsSegment {
+    pub callsign: AprsCallsign,
+    /// 到着したサミット（未到着なら最後の目的サミット）
+    pub summit: Option<String>,
+    /// 初めて OnSummit になった時刻
+    pub arrival: Option<NaiveDateTime>,
+    /// 時刻順の位置
+    pub points: Vec<AprsTrackPoint>,
+}
+
+impl AprsSegment {
+    fn new(log: &AprsLog) -> Self {
+        Self {
+            callsign: log.callsign.clone(),
+            summit: None,
+            arrival: None,
+            points: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, log: AprsLog) {
+        if self.arrival.is_none() {
+            if let AprsState::OnSummit { time, .. } = log.state {
+                self.arrival = Some(time);
+            }
+            self.summit = log.destination;
+        }
+        self.points.push(AprsTrackPoint {
+            time: log.state.time(),
+            latitude: log.latitude,
+            longitude: log.longitude,
+        });
+    }
+
+    /// 到着後に別のサミットが目的地になったら次のアクティベーション
+    fn continues(&self, log: &AprsLog, max_gap: TimeDelta) -> bool {
+        let Some(last) = self.points.last() else {
+            return true;
+        };
+        log.callsign == self.callsign
+            && log.state.time() - last.time <= max_gap
+            && (self.arrival.is_none() || log.destination == self.summit)
+    }
+
+    /// ログをコールサインごと・アクティベーションごとのトラックに分ける
+    ///
+    /// `max_gap` より長く位置が途切れたら別のアクティベーションとみなす。
+    pub fn split(mut logs: Vec<AprsLog>, max_gap: TimeDelta) -> Vec<AprsSegment> {
+        logs.sort_by(|a, b| {
+            (&a.callsign.callsign, a.callsign.ssid, a.state.time()).cmp(&(
+                &b.callsign.callsign,
+                b.callsign.ssid,
+                b.state.time(),
+            ))
+        });
+
+        let mut segments: Vec<AprsSegment> = Vec::new();
+        for log in logs {
+            match segments.last_mut() {
+                Some(seg) if seg.continues(&log, max_gap) => seg.push(log),
+                _ => {
+                    let mut seg = AprsSegment::new(&log);
+                    seg.push(log);
+                    segments.push(seg);
+                }
+            }
+        }
+        segments
+    }
+
+    pub fn start_time(&self) -> Option<NaiveDateTime> {
+        self.points.first().map(|p| p.time)
+    }
+
+    pub fn end_time(&self) -> Option<NaiveDateTime> {
+        self.points.last().map(|p| p.time)
+    }
+
+    /// Douglas-Peucker法で点を間引く（toleranceはm）
+    pub fn simplify(&mut self, tolerance: f64) {
+        let coords: Vec<_> = self
+            .points
+            .iter()
+            .map(|p| (p.latitude, p.longitude))
+            .collect();
+        let keep = simplify_track(&coords, tolerance);
+        let mut keep = keep.into_iter().peekable();
+        let mut index = 0;
+        self.points.retain(|_| {
+            let kept = keep.next_if_eq(&index).is_some();
+            index += 1;
+            kept
+        });
+    }
+}
+
+/// 保存されたアクティベーションのトラック
+#[derive(Debug, Clone)]
+pub struct AprsTrackArchive {
+    pub archive_id: i64,
+    pub archived_at: DateTime<Utc>,
+    pub segment: AprsSegment,
 }
 
 #[cfg(test)]
@@ -210,6 +384,82 @@ mod tests {
         assert_eq!(state.message(), Some(&msg));
     }
 
+    fn log(call: &str, min: i64, destination: &str, on_summit: bool) -> AprsLog {
+        let time = make_test_time() + TimeDelta::minutes(min);
+        let state = if on_summit {
+            AprsState::OnSummit {
+                time,
+                distance: 10.0,
+                message: String::new(),
+            }
+        } else {
+            AprsState::Climbing {
+                time,
+                distance: 500.0,
+            }
+        };
+        AprsLog {
+            callsign: AprsCallsign {
+                callsign: call.to_string(),
+                ssid: Some(7),
+            },
+            destination: Some(destination.to_string()),
+            state,
+            longitude: 139.0,
+            latitude: 35.0 + min as f64 * 0.0001,
+            altitude: None,
+            detail: AprsLogDetail::Position,
+        }
+    }
+
+    #[test]
+    fn test_segment_split_by_summit_and_gap() {
+        let logs = vec![
+            // 新しい順で渡されても時刻順に並べ替える
+            log("JA1ABC", 90, "JA/TK-002", false),
+            log("JA1ABC", 40, "JA/TK-001", false),
+            log("JA1ABC", 30, "JA/TK-001", true),
+            log("JA1ABC", 10, "JA/TK-001", false),
+            // 到着前は目的サミットが変わっても同じアクティベーション
+            log("JA1ABC", 0, "JA/TK-003", false),
+            log("JA1ABC", 600, "JA/TK-002", true),
+            log("JA2XYZ", 5, "JA/TK-001", false),
+        ];
+        let segments = AprsSegment::split(logs, TimeDelta::hours(3));
+
+        assert_eq!(segments.len(), 4);
+        assert_eq!(segments[0].summit.as_deref(), Some("JA/TK-001"));
+        assert_eq!(segments[0].points.len(), 4);
+        assert_eq!(
+            segments[0].arrival,
+            Some(make_test_time() + TimeDelta::minutes(30))
+        );
+        // 到着後に別サミットへ向かった分は次のアクティベーション
+        assert_eq!(segments[1].summit.as_deref(), Some("JA/TK-002"));
+        assert!(segments[1].arrival.is_none());
+        // 3時間以上空いたら別のアクティベーション
+        assert_eq!(segments[2].points.len(), 1);
+        assert!(segments[2].arrival.is_some());
+        assert_eq!(segments[3].callsign.callsign, "JA2XYZ");
+    }
+
+    #[test]
+    fn test_segment_simplify_keeps_endpoints() {
+        let logs = (0..5)
+            .map(|i| log("JA1ABC", i, "JA/TK-001", false))
+            .collect();
+        let mut segments = AprsSegment::split(logs, TimeDelta::hours(3));
+        let seg = &mut segments[0];
+        seg.simplify(5.0);
+
+        assert_eq!(seg.points.len(), 2);
+        assert_eq!(seg.start_time(), Some(make_test_time()));
+        assert_eq!(
+            seg.end_time(),
+            Some(make_test_time() + TimeDelta::minutes(4))
+        );
+    }
+
     #[test]
     fn test_aprs_state_message_none_for_descending() {
         let state = AprsState::Descending {
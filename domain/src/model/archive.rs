@@ -0,0 +1,346 @@
+//! 期限切れのスポット・アラートのアーカイブ
+//!
+//! スポットは同じ日・同じ運用者・同じリファレンス・同じバンド/モードのものを1行にまとめて保存する。
+//! アーカイブは月（YYYYMM）で区切って保存し、検索も月の範囲で絞り込む。
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+
+use crate::model::activation::{Alert, Spot};
+use crate::model::AwardProgram;
+
+/// 日時が属する月（YYYYMM）
+pub fn archive_month(time: &DateTime<Utc>) -> i32 {
+    time.year() * 100 + time.month() as i32
+}
+
+/// リファレンスの協会・エンティティ（"JA/TK-001" → "JA"、"JP-0001" → "JP"）
+pub fn association_of(program: &AwardProgram, reference: &str) -> String {
+    let sep = match program {
+        AwardProgram::SOTA => '/',
+        AwardProgram::POTA | AwardProgram::WWFF => '-',
+    };
+    reference
+        .split_once(sep)
+        .map(|(assoc, _)| assoc)
+        .unwrap_or(reference)
+        .to_ascii_uppercase()
+}
+
+/// まとめて保存したスポット
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpotArchive {
+    pub program: AwardProgram,
+    pub month: i32,
+    pub activation_date: NaiveDate,
+    pub operator: String,
+    pub reference: String,
+    /// バンド名（周波数が読めなければ空）
+    pub band: String,
+    /// 正規化したモード（CW/SSB/FM/MFSKなど、不明なら空）
+    pub mode: String,
+    pub activator: String,
+    pub association: String,
+    pub reference_detail: String,
+    /// 最後のスポットの周波数
+    pub frequency: String,
+    pub first_spot: DateTime<Utc>,
+    pub last_spot: DateTime<Utc>,
+    pub spot_count: i32,
+}
+
+impl SpotArchive {
+    fn new(spot: &Spot) -> Self {
+        Self {
+            program: spot.program.clone(),
+            month: archive_month(&spot.spot_time),
+            activation_date: spot.spot_time.date_naive(),
+            operator: spot.operator.clone(),
+            reference: spot.reference.clone(),
+            band: spot.band.clone().unwrap_or_default(),
+            mode: spot.canonical_mode.clone().unwrap_or_default(),
+            activator: spot.activator.clone(),
+            association: association_of(&spot.program, &spot.reference),
+            reference_detail: spot.reference_detail.clone(),
+            frequency: spot.frequency.clone(),
+            first_spot: spot.spot_time,
+            last_spot: spot.spot_time,
+            spot_count: 1,
+        }
+    }
+
+    fn key(&self) -> (i32, NaiveDate, String, String, String, String) {
+        (
+            self.program.as_i32(),
+            self.activation_date,
+            self.operator.clone(),
+            self.reference.clone(),
+            self.band.clone(),
+            self.mode.clone(),
+        )
+    }
+
+    /// 同じアクティベーション・バンド・モードのスポットを1行にまとめる
+    pub fn merge(&mut self, other: &SpotArchive) {
+        if other.last_spot > self.last_spot {
+            self.last_spot = other.last_spot;
+            self.frequency = other.frequency.clone();
+            self.activator = other.activator.clone();
+            self.reference_detail = other.reference_detail.clone();
+        }
+        self.first_spot = self.first_spot.min(other.first_spot);
+        self.spot_count += other.spot_count;
+    }
+}
+
+/// スポットをアクティベーション（日・運用者・リファレンス）とバンド・モードごとにまとめる
+pub fn compact_spots(spots: &[Spot]) -> Vec<SpotArchive> {
+    let mut compacted: BTreeMap<_, SpotArchive> = BTreeMap::new();
+    for spot in spots {
+        let archived = SpotArchive::new(spot);
+        match compacted.get_mut(&archived.key()) {
+            Some(entry) => entry.merge(&archived),
+            None => {
+                compacted.insert(archived.key(), archived);
+            }
+        }
+    }
+    compacted.into_values().collect()
+}
+
+/// 保存したアラート
+#[derive(Debug, Clone)]
+pub struct AlertArchive {
+    pub month: i32,
+    pub association: String,
+    pub alert: Alert,
+}
+
+impl From<Alert> for AlertArchive {
+    fn from(alert: Alert) -> Self {
+        Self {
+            month: archive_month(&alert.start_time),
+            association: association_of(&alert.program, &alert.reference),
+            alert,
+        }
+    }
+}
+
+/// アーカイブの検索条件
+#[derive(Debug, Default, Clone)]
+pub struct FindArchive {
+    pub program: Option<AwardProgram>,
+    pub operator: Option<String>,
+    pub reference: Option<String>,
+    pub association: Option<String>,
+    pub band: Option<String>,
+    pub mode: Option<String>,
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+impl FindArchive {
+    /// 検索する月の範囲（YYYYMM、両端を含む）
+    pub fn months(&self) -> (Option<i32>, Option<i32>) {
+        (
+            self.after.as_ref().map(archive_month),
+            self.before.as_ref().map(archive_month),
+        )
+    }
+}
+
+#[derive(Default)]
+pub struct FindArchiveBuilder {
+    pub param: FindArchive,
+}
+
+impl FindArchiveBuilder {
+    pub fn program(mut self, program: AwardProgram) -> Self {
+        self.param.program = Some(program);
+        self
+    }
+
+    pub fn operator(mut self, operator: &str) -> Self {
+        self.param.operator = Some(operator.to_ascii_uppercase());
+        self
+    }
+
+    pub fn reference(mut self, reference: &str) -> Self {
+        self.param.reference = Some(reference.to_ascii_uppercase());
+        self
+    }
+
+    pub fn association(mut self, association: &str) -> Self {
+        self.param.association = Some(association.to_ascii_uppercase());
+        self
+    }
+
+    pub fn band(mut self, band: &str) -> Self {
+        self.param.band = Some(band.to_ascii_lowercase());
+        self
+    }
+
+    /// 正規化したモード（`Spot.canonical_mode` と同じ表記）
+    pub fn mode(mut self, mode: &str) -> Self {
+        self.param.mode = Some(mode.trim().to_ascii_uppercase());
+        self
+    }
+
+    pub fn after(mut self, after: DateTime<Utc>) -> Self {
+        self.param.after = Some(after);
+        self
+    }
+
+    pub fn before(mut self, before: DateTime<Utc>) -> Self {
+        self.param.before = Some(before);
+        self
+    }
+
+    /// 1年分（1月1日から12月31日まで）
+    pub fn year(self, year: i32) -> Self {
+        let start = NaiveDate::from_ymd_opt(year, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0));
+        let end = NaiveDate::from_ymd_opt(year + 1, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0));
+        match (start, end) {
+            (Some(start), Some(end)) => self
+                .after(start.and_utc())
+                .before(end.and_utc() - chrono::Duration::seconds(1)),
+            _ => self,
+        }
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.param.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.param.offset = Some(offset);
+        self
+    }
+
+    pub fn build(self) -> FindArchive {
+        self.param
+    }
+}
+
+/// リファレンスごと・年ごとのアクティベーション数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceYearStat {
+    pub program: AwardProgram,
+    pub reference: String,
+    pub year: i32,
+    /// アクティベーション数（運用者・日ごとに1回）
+    pub activations: i64,
+    /// アクティベーターの人数
+    pub activators: i64,
+}
+
+/// アクティベーターごとの集計
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivatorStat {
+    pub operator: String,
+    pub activations: i64,
+    /// 運用したリファレンスの数
+    pub references: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::activation::make_test_spot;
+    use chrono::TimeZone;
+
+    fn spot(operator: &str, reference: &str, time: DateTime<Utc>, freq: &str, band: &str) -> Spot {
+        Spot {
+            operator: operator.to_string(),
+            spot_time: time,
+            frequency: freq.to_string(),
+            band: (!band.is_empty()).then(|| band.to_string()),
+            mode: "cw".to_string(),
+            canonical_mode: Some("CW".to_string()),
+            ..make_test_spot(&format!("{}/P", operator), reference)
+        }
+    }
+
+    #[test]
+    fn test_association_of() {
+        assert_eq!(association_of(&AwardProgram::SOTA, "JA/TK-001"), "JA");
+        assert_eq!(association_of(&AwardProgram::SOTA, "w7w/kg-001"), "W7W");
+        assert_eq!(association_of(&AwardProgram::POTA, "JP-0001"), "JP");
+        assert_eq!(association_of(&AwardProgram::WWFF, "JAFF-0123"), "JAFF");
+        assert_eq!(association_of(&AwardProgram::POTA, "UNKNOWN"), "UNKNOWN");
+    }
+
+    #[test]
+    fn test_archive_month() {
+        let t = Utc.with_ymd_and_hms(2025, 12, 31, 23, 59, 59).unwrap();
+        assert_eq!(archive_month(&t), 202512);
+        let t = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(archive_month(&t), 202601);
+    }
+
+    #[test]
+    fn test_compact_spots_merges_same_activation_band_mode() {
+        let t0 = Utc.with_ymd_and_hms(2026, 5, 3, 1, 0, 0).unwrap();
+        let t1 = Utc.with_ymd_and_hms(2026, 5, 3, 1, 20, 0).unwrap();
+        let t2 = Utc.with_ymd_and_hms(2026, 5, 3, 1, 40, 0).unwrap();
+        let spots = vec![
+            spot("JA1ABC", "JA/TK-001", t1, "14.062", "20m"),
+            spot("JA1ABC", "JA/TK-001", t0, "14.060", "20m"),
+            spot("JA1ABC", "JA/TK-001", t2, "7.032", "40m"),
+            Spot {
+                canonical_mode: Some("FM".to_string()),
+                ..spot("JA1ABC", "JA/TK-001", t2, "433.500", "70cm")
+            },
+        ];
+
+        let compacted = compact_spots(&spots);
+        assert_eq!(compacted.len(), 3);
+
+        let cw20 = compacted
+            .iter()
+            .find(|s| s.band == "20m" && s.mode == "CW")
+            .unwrap();
+        assert_eq!(cw20.spot_count, 2);
+        assert_eq!(cw20.first_spot, t0);
+        assert_eq!(cw20.last_spot, t1);
+        assert_eq!(cw20.frequency, "14.062");
+        assert_eq!(cw20.month, 202605);
+        assert_eq!(cw20.association, "JA");
+        assert_eq!(cw20.activator, "JA1ABC/P");
+    }
+
+    #[test]
+    fn test_compact_spots_keeps_days_and_unknown_bands_apart() {
+        let day1 = Utc.with_ymd_and_hms(2026, 5, 3, 23, 50, 0).unwrap();
+        let day2 = Utc.with_ymd_and_hms(2026, 5, 4, 0, 10, 0).unwrap();
+        let spots = vec![
+            spot("JA1ABC", "JA/TK-001", day1, "14.062", "20m"),
+            spot("JA1ABC", "JA/TK-001", day2, "14.062", "20m"),
+            spot("JA1ABC", "JA/TK-001", day2, "QRV soon", ""),
+        ];
+
+        let compacted = compact_spots(&spots);
+        assert_eq!(compacted.len(), 3);
+        assert!(compacted.iter().any(|s| s.band.is_empty()));
+    }
+
+    #[test]
+    fn test_find_archive_year_months() {
+        let query = FindArchiveBuilder::default().year(2025).build();
+        assert_eq!(query.months(), (Some(202501), Some(202512)));
+
+        let query = FindArchiveBuilder::default()
+            .band("20M")
+            .mode("mfsk")
+            .operator("ja1abc")
+            .build();
+        assert_eq!(query.band.as_deref(), Some("20m"));
+        assert_eq!(query.mode.as_deref(), Some("MFSK"));
+        assert_eq!(query.operator.as_deref(), Some("JA1ABC"));
+        assert_eq!(query.months(), (None, None));
+    }
+}
@@ -0,0 +1,303 @@
+//! サミット/パークリスト更新時の変更履歴
+
+use chrono::{DateTime, NaiveDate, Utc};
+use common::utils::calculate_distance;
+use std::hash::{Hash, Hasher};
+
+use crate::model::pota::PotaReference;
+use crate::model::sota::SotaReference;
+use crate::model::AwardProgram;
+
+/// これ以上位置が変わったら移動として記録する（m）
+pub const MOVED_THRESHOLD_M: f64 = 10.0;
+
+/// リファレンスの変更種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+#[repr(i32)]
+pub enum ReferenceChangeKind {
+    /// 新規追加（再有効化を含む）
+    Added = 0,
+    /// 有効期限切れ・無効化
+    Retired = 1,
+    PointsChanged = 2,
+    Renamed = 3,
+    Moved = 4,
+}
+
+impl ReferenceChangeKind {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Retired => "retired",
+            Self::PointsChanged => "points_changed",
+            Self::Renamed => "renamed",
+            Self::Moved => "moved",
+        }
+    }
+}
+
+impl From<i32> for ReferenceChangeKind {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Retired,
+            2 => Self::PointsChanged,
+            3 => Self::Renamed,
+            4 => Self::Moved,
+            _ => Self::Added,
+        }
+    }
+}
+
+/// リファレンスの変更履歴
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceChange {
+    pub program: AwardProgram,
+    pub reference: String,
+    pub kind: ReferenceChangeKind,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+/// 変更履歴の比較対象フィールド
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceSnapshot {
+    pub reference: String,
+    pub name: String,
+    /// SOTAのみ
+    pub points: Option<i32>,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub retired: bool,
+}
+
+impl ReferenceSnapshot {
+    /// サミットのスナップショット
+    ///
+    /// 有効期間外かどうかは取り込み日（UTC）で判定し、同じ取り込みの中では同じ日付を使う。
+    pub fn sota(r: &SotaReference, on: NaiveDate) -> Self {
+        Self {
+            reference: r.summit_code.clone(),
+            name: r.summit_name.clone(),
+            points: Some(r.points),
+            longitude: r.longitude,
+            latitude: r.latitude,
+            retired: !r.is_valid_on(on),
+        }
+    }
+
+    /// 比較対象フィールドのハッシュ値
+    pub fn change_hash(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.reference.hash(&mut hasher);
+        self.name.hash(&mut hasher);
+        self.points.hash(&mut hasher);
+        self.longitude.to_bits().hash(&mut hasher);
+        self.latitude.to_bits().hash(&mut hasher);
+        self.retired.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn position(&self) -> String {
+        format!("{:.5},{:.5}", self.latitude, self.longitude)
+    }
+
+    /// 旧（DB）と新（リストファイル）を比較して変更履歴を作る
+    ///
+    /// 旧がなければ追加、新がなければ期限切れとする。
+    pub fn diff(
+        program: AwardProgram,
+        old: Option<&Self>,
+        new: Option<&Self>,
+        changed_at: DateTime<Utc>,
+    ) -> Vec<ReferenceChange> {
+        let change =
+            |reference: &str, kind, old_value: Option<String>, new_value| ReferenceChange {
+                program: program.clone(),
+                reference: reference.to_string(),
+                kind,
+                old_value,
+                new_value,
+                changed_at,
+            };
+
+        let (old, new) = match (old, new) {
+            (None, Some(new)) if !new.retired => {
+                return vec![change(
+                    &new.reference,
+                    ReferenceChangeKind::Added,
+                    None,
+                    Some(new.name.clone()),
+                )]
+            }
+            (Some(old), None) if !old.retired => {
+                return vec![change(
+                    &old.reference,
+                    ReferenceChangeKind::Retired,
+                    Some(old.name.clone()),
+                    None,
+                )]
+            }
+            (Some(old), Some(new)) => (old, new),
+            _ => return vec![],
+        };
+
+        let mut changes = Vec::new();
+        let r = &new.reference;
+        match (old.retired, new.retired) {
+            (false, true) => changes.push(change(
+                r,
+                ReferenceChangeKind::Retired,
+                Some(old.name.clone()),
+                None,
+            )),
+            (true, false) => changes.push(change(
+                r,
+                ReferenceChangeKind::Added,
+                None,
+                Some(new.name.clone()),
+            )),
+            _ => {}
+        }
+        if old.points != new.points {
+            changes.push(change(
+                r,
+                ReferenceChangeKind::PointsChanged,
+                old.points.map(|p| p.to_string()),
+                new.points.map(|p| p.to_string()),
+            ));
+        }
+        if old.name != new.name {
+            changes.push(change(
+                r,
+                ReferenceChangeKind::Renamed,
+                Some(old.name.clone()),
+                Some(new.name.clone()),
+            ));
+        }
+        let distance = calculate_distance(old.latitude, old.longitude, new.latitude, new.longitude);
+        if distance >= MOVED_THRESHOLD_M {
+            changes.push(change(
+                r,
+                ReferenceChangeKind::Moved,
+                Some(old.position()),
+                Some(new.position()),
+            ));
+        }
+        changes
+    }
+}
+
+impl From<&PotaReference> for ReferenceSnapshot {
+    fn from(r: &PotaReference) -> Self {
+        Self {
+            reference: r.pota_code.clone(),
+            name: r.park_name.clone(),
+            points: None,
+            longitude: r.longitude,
+            latitude: r.latitude,
+            retired: r.park_inactive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::activation::make_test_summit;
+
+    fn snapshot(name: &str, points: i32, latitude: f64) -> ReferenceSnapshot {
+        ReferenceSnapshot {
+            reference: "JA/TK-001".to_string(),
+            name: name.to_string(),
+            points: Some(points),
+            longitude: 139.0,
+            latitude,
+            retired: false,
+        }
+    }
+
+    fn summit(valid_to: NaiveDate) -> SotaReference {
+        SotaReference {
+            points: 4,
+            ..make_test_summit(NaiveDate::from_ymd_opt(2010, 1, 1).unwrap(), valid_to)
+        }
+    }
+
+    fn kinds(changes: &[ReferenceChange]) -> Vec<ReferenceChangeKind> {
+        changes.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn test_diff_added_and_retired() {
+        let now = Utc::now();
+        let s = snapshot("Mt. Test", 4, 35.0);
+
+        let changes = ReferenceSnapshot::diff(AwardProgram::SOTA, None, Some(&s), now);
+        assert_eq!(kinds(&changes), vec![ReferenceChangeKind::Added]);
+        assert_eq!(changes[0].new_value.as_deref(), Some("Mt. Test"));
+
+        let changes = ReferenceSnapshot::diff(AwardProgram::SOTA, Some(&s), None, now);
+        assert_eq!(kinds(&changes), vec![ReferenceChangeKind::Retired]);
+
+        // パークの無効化と再有効化
+        let inactive = ReferenceSnapshot {
+            retired: true,
+            points: None,
+            ..s.clone()
+        };
+        let active = ReferenceSnapshot {
+            points: None,
+            ..s.clone()
+        };
+        let changes =
+            ReferenceSnapshot::diff(AwardProgram::POTA, Some(&active), Some(&inactive), now);
+        assert_eq!(kinds(&changes), vec![ReferenceChangeKind::Retired]);
+        let changes =
+            ReferenceSnapshot::diff(AwardProgram::POTA, Some(&inactive), Some(&active), now);
+        assert_eq!(kinds(&changes), vec![ReferenceChangeKind::Added]);
+        assert!(ReferenceSnapshot::diff(AwardProgram::POTA, None, Some(&inactive), now).is_empty());
+    }
+
+    #[test]
+    fn test_sota_snapshot_retired_on_import_date() {
+        let valid_to = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let r = summit(valid_to);
+
+        assert!(!ReferenceSnapshot::sota(&r, valid_to).retired);
+        assert!(ReferenceSnapshot::sota(&r, valid_to.succ_opt().unwrap()).retired);
+        assert_ne!(
+            ReferenceSnapshot::sota(&r, valid_to).change_hash(),
+            ReferenceSnapshot::sota(&r, valid_to.succ_opt().unwrap()).change_hash()
+        );
+    }
+
+    #[test]
+    fn test_diff_points_name_position() {
+        let now = Utc::now();
+        let old = snapshot("Mt. Test", 4, 35.0);
+
+        assert!(
+            ReferenceSnapshot::diff(AwardProgram::SOTA, Some(&old), Some(&old), now).is_empty()
+        );
+
+        // 緯度0.00005度（約5m）の違いは移動としない
+        let new = snapshot("Mt. Test", 6, 35.00005);
+        let changes = ReferenceSnapshot::diff(AwardProgram::SOTA, Some(&old), Some(&new), now);
+        assert_eq!(kinds(&changes), vec![ReferenceChangeKind::PointsChanged]);
+        assert_eq!(changes[0].old_value.as_deref(), Some("4"));
+        assert_eq!(changes[0].new_value.as_deref(), Some("6"));
+
+        let new = snapshot("Mt. Renamed", 4, 35.001);
+        let changes = ReferenceSnapshot::diff(AwardProgram::SOTA, Some(&old), Some(&new), now);
+        assert_eq!(
+            kinds(&changes),
+            vec![ReferenceChangeKind::Renamed, ReferenceChangeKind::Moved]
+        );
+        assert_eq!(changes[1].new_value.as_deref(), Some("35.00100,139.00000"));
+    }
+}
@@ -1,10 +1,14 @@
 use aprs_message::AprsCallsign;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use derive_new::new;
 use std::str::FromStr;
 
-use crate::model::{id::LogId, AwardProgram};
-use crate::model::{pota::PotaRefLog, sota::SotaReference};
+use crate::model::{
+    id::{LogId, UserId},
+    locator::Prefecture,
+    AwardProgram,
+};
+use crate::model::{pota::PotaRefLog, route::RouteCorridor, sota::SotaReference};
 
 #[derive(new, Debug)]
 pub struct BoundingBox {
@@ -37,9 +41,34 @@ pub struct FindRef {
     pub log_id: Option<LogId>,
     pub limit: Option<i32>,
     pub offset: Option<i32>,
+    /// この日に有効なサミットのみ（未指定なら今日）
+    pub as_of: Option<NaiveDate>,
+    /// 有効期間外のサミットも含める
+    pub include_retired: bool,
+    /// グリッドロケーター（4桁または6桁）の前方一致
+    pub grid: Option<String>,
+    /// JCC/JCGコード（市区町村・郡の名称に解決して所在地と照合）
+    pub jcc_code: Option<String>,
+    pub jcg_code: Option<String>,
+    pub prefecture: Option<Prefecture>,
+    /// 経路から一定距離内（DBでは経路を囲む矩形で絞り込む）
+    pub route: Option<RouteCorridor>,
 }
 
 impl FindRef {
+    /// SOTAの有効期間で絞り込む日付
+    ///
+    /// as_of指定時はその日、コード指定やinclude_retiredでは絞り込まず、それ以外は今日（UTC）。
+    pub fn valid_on(&self) -> Option<NaiveDate> {
+        if self.as_of.is_some() {
+            self.as_of
+        } else if self.include_retired || self.sota_code.is_some() {
+            None
+        } else {
+            Some(Utc::now().date_naive())
+        }
+    }
+
     pub fn is_sota(&self) -> bool {
         self.program.contains(&AwardProgram::SOTA)
     }
@@ -134,6 +163,11 @@ impl FindRefBuilder {
         self
     }
 
+    pub fn route(mut self, route: RouteCorridor) -> Self {
+        self.param.route = Some(route);
+        self
+    }
+
     pub fn min_elev(mut self, elev: i32) -> Self {
         self.param.min_elev = Some(elev);
         self
@@ -159,6 +193,36 @@ impl FindRefBuilder {
         self
     }
 
+    pub fn as_of(mut self, date: NaiveDate) -> Self {
+        self.param.as_of = Some(date);
+        self
+    }
+
+    pub fn include_retired(mut self) -> Self {
+        self.param.include_retired = true;
+        self
+    }
+
+    pub fn grid(mut self, grid: String) -> Self {
+        self.param.grid = Some(grid.to_uppercase());
+        self
+    }
+
+    pub fn jcc_code(mut self, code: String) -> Self {
+        self.param.jcc_code = Some(code);
+        self
+    }
+
+    pub fn jcg_code(mut self, code: String) -> Self {
+        self.param.jcg_code = Some(code);
+        self
+    }
+
+    pub fn prefecture(mut self, prefecture: Prefecture) -> Self {
+        self.param.prefecture = Some(prefecture);
+        self
+    }
+
     pub fn build(self) -> FindRef {
         self.param
     }
@@ -195,6 +259,9 @@ pub struct FindLog {
     pub after: Option<DateTime<Utc>>,
     pub before: Option<DateTime<Utc>>,
     pub activation: bool,
+    /// 相手がサミットの交信（S2Sを含む、activationより優先）
+    pub summit_chase: bool,
+    pub user_id: Option<UserId>,
 }
 
 #[derive(Default)]
@@ -223,6 +290,16 @@ impl FindLogBuilder {
         self
     }
 
+    pub fn summit_chase(mut self) -> Self {
+        self.param.summit_chase = true;
+        self
+    }
+
+    pub fn user_id(mut self, user_id: UserId) -> Self {
+        self.param.user_id = Some(user_id);
+        self
+    }
+
     pub fn build(self) -> FindLog {
         self.param
     }
@@ -240,8 +317,12 @@ pub struct FindAct {
     pub issued_after: Option<DateTime<Utc>>,
     pub operator: Option<String>,
     pub pattern: Option<String>,
+    /// スポットのバンド（正規化済みのband列、例: 40m）
+    pub band: Option<String>,
     pub group_by: Option<GroupBy>,
     pub log_id: Option<LogId>,
+    /// 未交信フラグの判定に使うSOTAログの利用者
+    pub user_id: Option<UserId>,
     pub limit: Option<i32>,
     pub offset: Option<i32>,
 }
@@ -302,6 +383,16 @@ impl FindActBuilder {
         self
     }
 
+    pub fn band(mut self, band: &str) -> Self {
+        self.param.band = Some(band.to_ascii_lowercase());
+        self
+    }
+
+    pub fn user_id(mut self, user_id: UserId) -> Self {
+        self.param.user_id = Some(user_id);
+        self
+    }
+
     pub fn log_id(mut self, log_id: &str) -> Self {
         if let Ok(log_id) = LogId::from_str(log_id) {
             self.param.log_id = Some(log_id)
@@ -325,4 +416,5 @@ pub struct FindAprs {
     pub callsign: Option<AprsCallsign>,
     pub reference: Option<String>,
     pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
 }
@@ -19,3 +19,95 @@ pub struct MunicipalityCenturyCode {
     pub municipality: String,
     pub code: CenturyCode,
 }
+
+/// 都道府県（ISO 3166-2コード、名称、SOTAリージョン名に使われるローマ字）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prefecture {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub romaji: &'static str,
+}
+
+const fn pref(code: &'static str, name: &'static str, romaji: &'static str) -> Prefecture {
+    Prefecture { code, name, romaji }
+}
+
+pub const PREFECTURES: [Prefecture; 47] = [
+    pref("JP-01", "北海道", "Hokkaido"),
+    pref("JP-02", "青森県", "Aomori"),
+    pref("JP-03", "岩手県", "Iwate"),
+    pref("JP-04", "宮城県", "Miyagi"),
+    pref("JP-05", "秋田県", "Akita"),
+    pref("JP-06", "山形県", "Yamagata"),
+    pref("JP-07", "福島県", "Fukushima"),
+    pref("JP-08", "茨城県", "Ibaraki"),
+    pref("JP-09", "栃木県", "Tochigi"),
+    pref("JP-10", "群馬県", "Gunma"),
+    pref("JP-11", "埼玉県", "Saitama"),
+    pref("JP-12", "千葉県", "Chiba"),
+    pref("JP-13", "東京都", "Tokyo"),
+    pref("JP-14", "神奈川県", "Kanagawa"),
+    pref("JP-15", "新潟県", "Niigata"),
+    pref("JP-16", "富山県", "Toyama"),
+    pref("JP-17", "石川県", "Ishikawa"),
+    pref("JP-18", "福井県", "Fukui"),
+    pref("JP-19", "山梨県", "Yamanashi"),
+    pref("JP-20", "長野県", "Nagano"),
+    pref("JP-21", "岐阜県", "Gifu"),
+    pref("JP-22", "静岡県", "Shizuoka"),
+    pref("JP-23", "愛知県", "Aichi"),
+    pref("JP-24", "三重県", "Mie"),
+    pref("JP-25", "滋賀県", "Shiga"),
+    pref("JP-26", "京都府", "Kyoto"),
+    pref("JP-27", "大阪府", "Osaka"),
+    pref("JP-28", "兵庫県", "Hyogo"),
+    pref("JP-29", "奈良県", "Nara"),
+    pref("JP-30", "和歌山県", "Wakayama"),
+    pref("JP-31", "鳥取県", "Tottori"),
+    pref("JP-32", "島根県", "Shimane"),
+    pref("JP-33", "岡山県", "Okayama"),
+    pref("JP-34", "広島県", "Hiroshima"),
+    pref("JP-35", "山口県", "Yamaguchi"),
+    pref("JP-36", "徳島県", "Tokushima"),
+    pref("JP-37", "香川県", "Kagawa"),
+    pref("JP-38", "愛媛県", "Ehime"),
+    pref("JP-39", "高知県", "Kochi"),
+    pref("JP-40", "福岡県", "Fukuoka"),
+    pref("JP-41", "佐賀県", "Saga"),
+    pref("JP-42", "長崎県", "Nagasaki"),
+    pref("JP-43", "熊本県", "Kumamoto"),
+    pref("JP-44", "大分県", "Oita"),
+    pref("JP-45", "宮崎県", "Miyazaki"),
+    pref("JP-46", "鹿児島県", "Kagoshima"),
+    pref("JP-47", "沖縄県", "Okinawa"),
+];
+
+impl Prefecture {
+    /// コード（JP-13）、名称（東京都・東京）、ローマ字（Tokyo）から都道府県を探す
+    pub fn resolve(s: &str) -> Option<Self> {
+        let s = s.trim();
+        PREFECTURES.iter().copied().find(|p| {
+            p.code.eq_ignore_ascii_case(s)
+                || p.romaji.eq_ignore_ascii_case(s)
+                || p.name == s
+                || (p.name != "北海道" && p.name.strip_suffix(['都', '府', '県']) == Some(s))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefecture() {
+        let tokyo = Prefecture::resolve("JP-13").unwrap();
+        assert_eq!(tokyo.name, "東京都");
+        assert_eq!(Prefecture::resolve("jp-13"), Some(tokyo));
+        assert_eq!(Prefecture::resolve("東京"), Some(tokyo));
+        assert_eq!(Prefecture::resolve("tokyo"), Some(tokyo));
+        assert_eq!(Prefecture::resolve("京都").unwrap().code, "JP-26");
+        assert_eq!(Prefecture::resolve("北海道").unwrap().code, "JP-01");
+        assert_eq!(Prefecture::resolve("JP-48"), None);
+    }
+}
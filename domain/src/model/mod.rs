@@ -2,13 +2,22 @@ use serde::Serialize;
 use typeshare::typeshare;
 
 pub mod activation;
+pub mod activity;
+pub mod aprs;
 pub mod aprslog;
+pub mod archive;
+pub mod changelog;
 pub mod event;
 pub mod geomag;
 pub mod id;
 pub mod locator;
+pub mod needed;
 pub mod pota;
+pub mod relay;
+pub mod route;
+pub mod session;
 pub mod sota;
+pub mod watch;
 
 #[derive(PartialEq, Debug, sqlx::Type, Clone, Serialize)]
 #[repr(i32)]
@@ -0,0 +1,202 @@
+//! 利用者のログから見たスポット/アラートの未交信フラグ
+
+use std::collections::{HashMap, HashSet};
+
+use crate::model::activation::Spot;
+
+/// 未交信フラグ（ログから判定できない項目はNone）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Needed {
+    /// 未交信のサミット/パーク
+    pub new_reference: bool,
+    /// このリファレンスで未交信のバンド
+    pub new_band: Option<bool>,
+    /// このリファレンスで未交信のモード
+    pub new_mode: Option<bool>,
+}
+
+impl Needed {
+    /// 並べ替えの優先度（小さいほど必要度が高い）
+    pub fn priority(&self) -> u8 {
+        if self.new_reference {
+            0
+        } else if self.new_band == Some(true) {
+            1
+        } else if self.new_mode == Some(true) {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+/// バンドとモードの組（バンドに当てはまらない周波数はNone、モード不明は空文字）
+///
+/// バンドとモードはスポットの正規化（`Spot.band`、`Spot.canonical_mode`）と同じ基準にそろえておく。
+pub type Channel = (Option<String>, String);
+
+#[derive(Debug, Default)]
+struct Worked {
+    bands: HashSet<String>,
+    modes: HashSet<String>,
+}
+
+/// リファレンスごとの交信済みバンドとモード
+#[derive(Debug, Default)]
+pub struct WorkedHistory {
+    worked: HashMap<String, Worked>,
+    /// バンドとモードを記録しているか（POTAのハンターログはパークごとの交信数だけ）
+    detailed: bool,
+}
+
+impl WorkedHistory {
+    /// リファレンスごとの交信（バンドとモード）から作る
+    pub fn from_channels(qsos: impl IntoIterator<Item = (String, Channel)>) -> Self {
+        let mut worked: HashMap<String, Worked> = HashMap::new();
+        for (reference, (band, mode)) in qsos {
+            let w = worked.entry(reference.to_uppercase()).or_default();
+            w.bands.extend(band);
+            if !mode.is_empty() {
+                w.modes.insert(mode);
+            }
+        }
+        Self {
+            worked,
+            detailed: true,
+        }
+    }
+
+    /// 交信済みのリファレンスだけから作る（バンドとモードは判定しない）
+    pub fn from_references(references: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            worked: references
+                .into_iter()
+                .map(|r| (r.to_uppercase(), Worked::default()))
+                .collect(),
+            detailed: false,
+        }
+    }
+
+    pub fn needed(&self, reference: &str, channels: &[Channel]) -> Needed {
+        let worked = self.worked.get(&reference.to_uppercase());
+        let new_reference = worked.is_none();
+
+        // 複数の周波数・モードを挙げたアラートは、どれか1つでも未交信なら立てる
+        let bands: Vec<_> = channels
+            .iter()
+            .filter_map(|(band, _)| band.as_ref())
+            .collect();
+        let modes: Vec<_> = channels
+            .iter()
+            .map(|(_, mode)| mode)
+            .filter(|mode| !mode.is_empty())
+            .collect();
+        let new_band = (self.detailed && !bands.is_empty())
+            .then(|| worked.is_none_or(|w| bands.iter().any(|b| !w.bands.contains(*b))));
+        let new_mode = (self.detailed && !modes.is_empty())
+            .then(|| worked.is_none_or(|w| modes.iter().any(|m| !w.modes.contains(*m))));
+
+        Needed {
+            new_reference,
+            new_band,
+            new_mode,
+        }
+    }
+
+    /// スポットの正規化したバンドとモードで判定する
+    pub fn spot(&self, spot: &Spot) -> Needed {
+        let channel = (
+            spot.band.clone(),
+            spot.canonical_mode.clone().unwrap_or_default(),
+        );
+        self.needed(&spot.reference, &[channel])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::activation::make_test_spot;
+
+    fn channel(band: &str, mode: &str) -> Channel {
+        (Some(band.to_string()), mode.to_string())
+    }
+
+    fn worked(summit: &str, band: &str, mode: &str) -> (String, Channel) {
+        (summit.to_string(), channel(band, mode))
+    }
+
+    #[test]
+    fn test_sota_needed() {
+        let history = WorkedHistory::from_channels([
+            worked("JA/TK-001", "40m", "CW"),
+            worked("JA/TK-001", "2m", "FM"),
+        ]);
+
+        let needed = history.needed("JA/TK-002", &[channel("40m", "CW")]);
+        assert_eq!(
+            needed,
+            Needed {
+                new_reference: true,
+                new_band: Some(true),
+                new_mode: Some(true)
+            }
+        );
+        assert_eq!(needed.priority(), 0);
+
+        let needed = history.needed("ja/tk-001", &[channel("20m", "CW")]);
+        assert!(!needed.new_reference);
+        assert_eq!(needed.new_band, Some(true));
+        assert_eq!(needed.new_mode, Some(false));
+        assert_eq!(needed.priority(), 1);
+
+        let needed = history.needed("JA/TK-001", &[channel("40m", "SSB")]);
+        assert_eq!(
+            (needed.new_band, needed.new_mode),
+            (Some(false), Some(true))
+        );
+        assert_eq!(needed.priority(), 2);
+
+        let needed = history.needed("JA/TK-001", &[channel("2m", "FM")]);
+        assert_eq!(needed.priority(), 3);
+
+        // 周波数もモードも読めなければ判定しない
+        let needed = history.needed("JA/TK-001", &[(None, String::new())]);
+        assert_eq!((needed.new_band, needed.new_mode), (None, None));
+    }
+
+    #[test]
+    fn test_spot_uses_normalized_band_and_mode() {
+        let history = WorkedHistory::from_channels([worked("JA/TK-001", "20m", "MFSK")]);
+
+        // 周波数・モードの生の文字列ではなく、正規化したバンドとモードで判定する
+        let mut spot = Spot {
+            frequency: "14.080".to_string(),
+            mode: "FT4".to_string(),
+            band: Some("20m".to_string()),
+            canonical_mode: Some("MFSK".to_string()),
+            ..make_test_spot("JA1ABC/P", "JA/TK-001")
+        };
+        assert_eq!(history.spot(&spot).priority(), 3);
+
+        spot.band = None;
+        spot.canonical_mode = None;
+        let needed = history.spot(&spot);
+        assert_eq!((needed.new_band, needed.new_mode), (None, None));
+    }
+
+    #[test]
+    fn test_pota_needed() {
+        let history = WorkedHistory::from_references(vec!["JA-0001".to_string()]);
+        let needed = history.needed("JA-0001", &[channel("20m", "SSB")]);
+        assert_eq!(
+            needed,
+            Needed {
+                new_reference: false,
+                new_band: None,
+                new_mode: None
+            }
+        );
+        assert!(history.needed("JA-0002", &[]).new_reference);
+    }
+}
@@ -0,0 +1,221 @@
+use chrono::{DateTime, Utc};
+
+use crate::model::id::UserId;
+use crate::model::AwardProgram;
+
+/// 投稿の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[repr(i32)]
+pub enum PostKind {
+    Spot = 0,
+    Alert = 1,
+}
+
+impl PostKind {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostKind::Spot => "spot",
+            PostKind::Alert => "alert",
+        }
+    }
+}
+
+impl From<i32> for PostKind {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => PostKind::Alert,
+            _ => PostKind::Spot,
+        }
+    }
+}
+
+/// アップストリームへの中継状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[repr(i32)]
+pub enum RelayStatus {
+    Pending = 0,
+    Relayed = 1,
+    Failed = 2,
+    /// 中継先が設定されていない（ローカル保存のみ）
+    Disabled = 3,
+}
+
+impl RelayStatus {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelayStatus::Pending => "pending",
+            RelayStatus::Relayed => "relayed",
+            RelayStatus::Failed => "failed",
+            RelayStatus::Disabled => "disabled",
+        }
+    }
+}
+
+impl From<i32> for RelayStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => RelayStatus::Relayed,
+            2 => RelayStatus::Failed,
+            3 => RelayStatus::Disabled,
+            _ => RelayStatus::Pending,
+        }
+    }
+}
+
+/// アップストリームの応答
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelayOutcome {
+    /// 受け付けられた（アップストリーム側のIDが返ればそれを持つ）
+    Relayed { upstream_id: Option<String> },
+    /// 拒否された（HTTPステータスと応答本文）
+    Rejected { status_code: u16, message: String },
+    /// 中継先が設定されていない
+    Disabled,
+}
+
+/// 利用者が投稿したスポット・アラートと中継の記録
+#[derive(Debug, Clone)]
+pub struct RelayPost {
+    pub post_id: i64,
+    pub user_id: UserId,
+    pub kind: PostKind,
+    pub program: AwardProgram,
+    pub reference: String,
+    pub activator: String,
+    /// ローカルに保存したスポット・アラートのID
+    pub local_id: i32,
+    pub status: RelayStatus,
+    pub upstream_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub relayed_at: Option<DateTime<Utc>>,
+}
+
+impl RelayPost {
+    pub fn new(
+        user_id: UserId,
+        kind: PostKind,
+        program: AwardProgram,
+        reference: String,
+        activator: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            post_id: 0,
+            user_id,
+            kind,
+            program,
+            reference,
+            activator,
+            local_id: 0,
+            status: RelayStatus::Pending,
+            upstream_id: None,
+            error: None,
+            created_at: now,
+            relayed_at: None,
+        }
+    }
+
+    /// 投稿IDから決めるローカルのスポット・アラートID
+    ///
+    /// アップストリームから取り込むIDは正なので、負の値にして衝突を避ける。
+    pub fn local_id_for(post_id: i64) -> i32 {
+        -(((post_id - 1) % i32::MAX as i64) as i32) - 1
+    }
+
+    /// 中継の結果を記録する
+    pub fn record_outcome(&mut self, result: Result<RelayOutcome, String>, now: DateTime<Utc>) {
+        match result {
+            Ok(RelayOutcome::Relayed { upstream_id }) => {
+                self.status = RelayStatus::Relayed;
+                self.upstream_id = upstream_id;
+                self.error = None;
+                self.relayed_at = Some(now);
+            }
+            Ok(RelayOutcome::Rejected {
+                status_code,
+                message,
+            }) => {
+                self.status = RelayStatus::Failed;
+                self.error = Some(format!("HTTP {}: {}", status_code, message));
+            }
+            Ok(RelayOutcome::Disabled) => {
+                self.status = RelayStatus::Disabled;
+                self.error = None;
+            }
+            Err(e) => {
+                self.status = RelayStatus::Failed;
+                self.error = Some(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post() -> RelayPost {
+        RelayPost::new(
+            UserId::from("user".to_string()),
+            PostKind::Spot,
+            AwardProgram::SOTA,
+            "JA/KN-006".to_string(),
+            "JA1ABC".to_string(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_local_id_is_negative() {
+        assert_eq!(RelayPost::local_id_for(1), -1);
+        assert_eq!(RelayPost::local_id_for(42), -42);
+        assert_eq!(RelayPost::local_id_for(i32::MAX as i64), -i32::MAX);
+        assert!(RelayPost::local_id_for(i32::MAX as i64 + 5) < 0);
+    }
+
+    #[test]
+    fn test_record_outcome() {
+        let now = Utc::now();
+
+        let mut p = post();
+        p.record_outcome(
+            Ok(RelayOutcome::Relayed {
+                upstream_id: Some("123".to_string()),
+            }),
+            now,
+        );
+        assert_eq!(p.status, RelayStatus::Relayed);
+        assert_eq!(p.upstream_id.as_deref(), Some("123"));
+        assert_eq!(p.relayed_at, Some(now));
+
+        let mut p = post();
+        p.record_outcome(
+            Ok(RelayOutcome::Rejected {
+                status_code: 400,
+                message: "bad reference".to_string(),
+            }),
+            now,
+        );
+        assert_eq!(p.status, RelayStatus::Failed);
+        assert_eq!(p.error.as_deref(), Some("HTTP 400: bad reference"));
+        assert!(p.relayed_at.is_none());
+
+        let mut p = post();
+        p.record_outcome(Ok(RelayOutcome::Disabled), now);
+        assert_eq!(p.status, RelayStatus::Disabled);
+
+        let mut p = post();
+        p.record_outcome(Err("timeout".to_string()), now);
+        assert_eq!(p.status, RelayStatus::Failed);
+        assert_eq!(p.error.as_deref(), Some("timeout"));
+    }
+}
@@ -0,0 +1,316 @@
+//! ルート沿いのリファレンス検索
+//!
+//! GPX/GeoJSONの経路から一定距離内の山岳・公園を探し、始点からの経路沿い距離を付ける。
+//! DBでは経路を囲む矩形で粗く絞り込み、距離の判定はここで行う。
+
+use common::error::{AppError, AppResult};
+use common::utils::simplify_track;
+use serde_json::Value;
+
+use crate::model::event::BoundingBox;
+use crate::model::pota::PotaRefLog;
+use crate::model::sota::SotaReference;
+
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+/// 絞り込み用の矩形の最大数（SQLのOR条件の数）
+pub const MAX_ROUTE_BOXES: usize = 32;
+
+/// 経路と検索幅
+#[derive(Debug, Clone)]
+pub struct RouteCorridor {
+    /// 経路の点（経度, 緯度）
+    pub points: Vec<(f64, f64)>,
+    /// 経路からの距離（m）
+    pub width: f64,
+}
+
+/// 経路に対するリファレンスの位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoutePosition {
+    /// 経路からの距離（m）
+    pub offset: f64,
+    /// 始点から最寄り位置までの経路沿い距離（m）
+    pub along: f64,
+}
+
+#[derive(Debug)]
+pub enum RouteReference {
+    Sota(SotaReference),
+    Pota(PotaRefLog),
+}
+
+/// ルート検索の結果（経路沿い距離の順）
+#[derive(Debug)]
+pub struct RouteHit {
+    pub reference: RouteReference,
+    pub position: RoutePosition,
+}
+
+impl RouteCorridor {
+    pub fn new(points: Vec<(f64, f64)>, width: f64) -> AppResult<Self> {
+        if points.len() < 2 {
+            return Err(AppError::UnprocessableEntity(
+                "route must have at least 2 points".to_string(),
+            ));
+        }
+        Ok(Self { points, width })
+    }
+
+    /// GPX（trkpt、なければrtept）またはGeoJSON（LineString/MultiLineString）を読む
+    pub fn parse(body: &str, width: f64) -> AppResult<Self> {
+        let body = body.trim_start_matches('\u{FEFF}').trim_start();
+        let points = if body.starts_with('{') {
+            let json: Value = serde_json::from_str(body).map_err(AppError::JsonError)?;
+            let mut points = Vec::new();
+            collect_geojson(&json, &mut points);
+            points
+        } else if body.starts_with('<') {
+            let track = parse_gpx(body, "trkpt");
+            if track.is_empty() {
+                parse_gpx(body, "rtept")
+            } else {
+                track
+            }
+        } else {
+            return Err(AppError::UnprocessableEntity(
+                "route must be GPX or GeoJSON".to_string(),
+            ));
+        };
+        Self::new(points, width)
+    }
+
+    /// 経路を幅だけ広げて覆う矩形
+    ///
+    /// 点の多い経路は間引いてから区間をまとめ、矩形の数をMAX_ROUTE_BOXES以下にする。
+    /// 間引きの誤差の分だけ余分に広げるので、幅内の点を取りこぼさない。
+    pub fn bounding_boxes(&self) -> Vec<BoundingBox> {
+        let tolerance = self.width / 2.0;
+        let latlon: Vec<_> = self.points.iter().map(|&(lon, lat)| (lat, lon)).collect();
+        let kept: Vec<(f64, f64)> = simplify_track(&latlon, tolerance)
+            .into_iter()
+            .map(|i| self.points[i])
+            .collect();
+
+        let segments = kept.len() - 1;
+        let chunk = segments.div_ceil(MAX_ROUTE_BOXES);
+        let margin = (self.width + tolerance) / METERS_PER_DEGREE;
+
+        (0..segments)
+            .step_by(chunk)
+            .map(|start| {
+                let points = &kept[start..=(start + chunk).min(segments)];
+                let fold = |f: fn(f64, f64) -> f64, init: f64, pick: fn(&(f64, f64)) -> f64| {
+                    points.iter().map(pick).fold(init, f)
+                };
+                let min_lon = fold(f64::min, f64::MAX, |p| p.0);
+                let max_lon = fold(f64::max, f64::MIN, |p| p.0);
+                let min_lat = fold(f64::min, f64::MAX, |p| p.1) - margin;
+                let max_lat = fold(f64::max, f64::MIN, |p| p.1) + margin;
+                let cos = min_lat
+                    .abs()
+                    .max(max_lat.abs())
+                    .min(89.0)
+                    .to_radians()
+                    .cos();
+                BoundingBox::new(
+                    min_lon - margin / cos,
+                    min_lat,
+                    max_lon + margin / cos,
+                    max_lat,
+                )
+            })
+            .collect()
+    }
+
+    /// 経路から幅以内ならその位置を返す
+    ///
+    /// 区間ごとに局所的な平面近似で点と線分の距離を求め、最も近い区間を採る。
+    pub fn locate(&self, lon: f64, lat: f64) -> Option<RoutePosition> {
+        let mut best: Option<RoutePosition> = None;
+        let mut travelled = 0.0;
+
+        for w in self.points.windows(2) {
+            let (lon0, lat0) = w[0];
+            let scale = lat0.to_radians().cos() * METERS_PER_DEGREE;
+            let project = |(x, y): (f64, f64)| ((x - lon0) * scale, (y - lat0) * METERS_PER_DEGREE);
+
+            let (bx, by) = project(w[1]);
+            let (px, py) = project((lon, lat));
+            let len2 = bx * bx + by * by;
+            let t = if len2 > 0.0 {
+                ((px * bx + py * by) / len2).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let offset = (px - t * bx).hypot(py - t * by);
+            let length = len2.sqrt();
+
+            if offset <= self.width && best.is_none_or(|b| offset < b.offset) {
+                best = Some(RoutePosition {
+                    offset,
+                    along: travelled + t * length,
+                });
+            }
+            travelled += length;
+        }
+        best
+    }
+
+    /// 経路から幅以内のリファレンスを経路沿い距離の順に並べる
+    pub fn hits(&self, sota: Vec<SotaReference>, pota: Vec<PotaRefLog>) -> Vec<RouteHit> {
+        let sota = sota.into_iter().filter_map(|r| {
+            let position = self.locate(r.longitude, r.latitude)?;
+            Some(RouteHit {
+                reference: RouteReference::Sota(r),
+                position,
+            })
+        });
+        let pota = pota.into_iter().filter_map(|r| {
+            let position = self.locate(r.longitude, r.latitude)?;
+            Some(RouteHit {
+                reference: RouteReference::Pota(r),
+                position,
+            })
+        });
+        let mut hits: Vec<_> = sota.chain(pota).collect();
+        hits.sort_by(|a, b| a.position.along.total_cmp(&b.position.along));
+        hits
+    }
+
+    /// PostGIS用のWKT
+    pub fn to_wkt(&self) -> String {
+        let points: Vec<_> = self
+            .points
+            .iter()
+            .map(|(lon, lat)| format!("{} {}", lon, lat))
+            .collect();
+        format!("SRID=4326;LINESTRING({})", points.join(","))
+    }
+}
+
+/// GPXから指定した要素の緯度経度を順に取り出す
+fn parse_gpx(body: &str, element: &str) -> Vec<(f64, f64)> {
+    let attribute = |tag: &str, name: &str| -> Option<f64> {
+        tag.split_whitespace().find_map(|attr| {
+            attr.strip_prefix(name)?
+                .strip_prefix('=')?
+                .trim_matches(|c| c == '"' || c == '\'' || c == '/')
+                .parse()
+                .ok()
+        })
+    };
+
+    body.split('<')
+        .filter_map(|tag| {
+            let tag = &tag[..tag.find('>').unwrap_or(tag.len())];
+            let name = tag.split_whitespace().next()?;
+            // 名前空間の接頭辞（gpx:trkpt）も受け付ける
+            if name.rsplit(':').next() != Some(element) {
+                return None;
+            }
+            Some((attribute(tag, "lon")?, attribute(tag, "lat")?))
+        })
+        .collect()
+}
+
+fn collect_geojson(json: &Value, points: &mut Vec<(f64, f64)>) {
+    let position = |c: &Value| Some((c.get(0)?.as_f64()?, c.get(1)?.as_f64()?));
+    let line = |coordinates: &Value, points: &mut Vec<(f64, f64)>| {
+        if let Some(coordinates) = coordinates.as_array() {
+            points.extend(coordinates.iter().filter_map(position));
+        }
+    };
+
+    match json.get("type").and_then(Value::as_str) {
+        Some("FeatureCollection") => json["features"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .for_each(|f| collect_geojson(f, points)),
+        Some("Feature") => collect_geojson(&json["geometry"], points),
+        Some("GeometryCollection") => json["geometries"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .for_each(|g| collect_geojson(g, points)),
+        Some("LineString") => line(&json["coordinates"], points),
+        Some("MultiLineString") => json["coordinates"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .for_each(|l| line(l, points)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpx_and_geojson() {
+        let gpx = r#"<?xml version="1.0"?>
+<gpx version="1.1"><rte><rtept lat="35.0" lon="139.0"/></rte>
+<trk><trkseg>
+  <trkpt lat="35.6251" lon="139.2438"><ele>599</ele></trkpt>
+  <trkpt lon='139.2500' lat='35.6300'></trkpt>
+</trkseg></trk></gpx>"#;
+        let route = RouteCorridor::parse(gpx, 1000.0).unwrap();
+        assert_eq!(route.points, vec![(139.2438, 35.6251), (139.25, 35.63)]);
+
+        let geojson = r#"{"type":"FeatureCollection","features":[
+            {"type":"Feature","properties":{},"geometry":
+              {"type":"LineString","coordinates":[[139.0,35.0,100],[139.1,35.1]]}}]}"#;
+        let route = RouteCorridor::parse(geojson, 1000.0).unwrap();
+        assert_eq!(route.points, vec![(139.0, 35.0), (139.1, 35.1)]);
+
+        assert!(RouteCorridor::parse(r#"{"type":"Point","coordinates":[139,35]}"#, 1.0).is_err());
+        assert!(RouteCorridor::parse("139,35", 1.0).is_err());
+    }
+
+    #[test]
+    fn test_locate_along_route() {
+        // 経度0.01度ごとに東へ進む経路（緯度35度で約912m間隔）
+        let points: Vec<_> = (0..=10).map(|i| (139.0 + i as f64 * 0.01, 35.0)).collect();
+        let route = RouteCorridor::new(points, 1000.0).unwrap();
+
+        // 5区間目の中ほどから北へ約556m
+        let pos = route.locate(139.055, 35.005).unwrap();
+        assert!((pos.offset - 556.6).abs() < 1.0);
+        assert!((pos.along - 5.5 * 912.0).abs() < 5.0);
+
+        // 始点の手前は始点までの距離、幅の外はNone
+        let pos = route.locate(138.995, 35.0).unwrap();
+        assert_eq!(pos.along, 0.0);
+        assert!(route.locate(139.05, 35.02).is_none());
+    }
+
+    #[test]
+    fn test_bounding_boxes_cover_corridor() {
+        // 折れ曲がる長い経路
+        let points: Vec<_> = (0..500)
+            .map(|i| {
+                let x = i as f64 * 0.002;
+                (139.0 + x, 35.0 + (x * 20.0).sin() * 0.01)
+            })
+            .collect();
+        let route = RouteCorridor::new(points.clone(), 500.0).unwrap();
+        let boxes = route.bounding_boxes();
+        assert!(!boxes.is_empty() && boxes.len() <= MAX_ROUTE_BOXES);
+
+        let inside = |lon: f64, lat: f64| {
+            boxes.iter().any(|b| {
+                (b.min_lon..=b.max_lon).contains(&lon) && (b.min_lat..=b.max_lat).contains(&lat)
+            })
+        };
+        for &(lon, lat) in points.iter().step_by(7) {
+            for (dlon, dlat) in [(0.0, 0.0045), (0.0, -0.0045), (0.0054, 0.0)] {
+                let (lon, lat) = (lon + dlon, lat + dlat);
+                if route.locate(lon, lat).is_some() {
+                    assert!(inside(lon, lat), "{} {} is not covered", lon, lat);
+                }
+            }
+        }
+    }
+}
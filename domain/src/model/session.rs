@@ -0,0 +1,203 @@
+use chrono::{DateTime, Utc};
+use common::utils::call_to_operator;
+use std::collections::{HashMap, HashSet};
+
+use crate::model::id::UserId;
+use crate::model::AwardProgram;
+
+/// アクティベーションセッションの状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[repr(i32)]
+pub enum SessionStatus {
+    Open = 0,
+    Closed = 1,
+}
+
+impl SessionStatus {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SessionStatus::Open => "open",
+            SessionStatus::Closed => "closed",
+        }
+    }
+}
+
+impl From<i32> for SessionStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => SessionStatus::Closed,
+            _ => SessionStatus::Open,
+        }
+    }
+}
+
+/// 1回のアクティベーション（開始から終了まで）
+#[derive(Debug, Clone)]
+pub struct ActivationSession {
+    pub session_id: i64,
+    pub user_id: UserId,
+    pub program: AwardProgram,
+    pub reference: String,
+    /// 運用コールサイン
+    pub callsign: String,
+    /// 運用者（空ならコールサインから求める）
+    pub operator: String,
+    pub status: SessionStatus,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    /// 成立条件を満たした時刻
+    pub qualified_at: Option<DateTime<Utc>>,
+}
+
+impl ActivationSession {
+    pub fn is_open(&self) -> bool {
+        self.status == SessionStatus::Open
+    }
+}
+
+/// セッション中に記録したQSO
+#[derive(Debug, Clone)]
+pub struct SessionQso {
+    pub session_id: i64,
+    /// クライアントが生成したID（再送しても1件として扱う）
+    pub client_id: String,
+    pub callsign: String,
+    pub band: String,
+    pub mode: String,
+    pub qso_time: DateTime<Utc>,
+    /// QSOレコード（JSON）
+    pub record: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// セッション終了時に作るログファイル
+#[derive(Debug, Clone)]
+pub struct SessionFile {
+    pub session_id: i64,
+    pub filename: String,
+    pub content: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// アクティベーション成立に必要なQSO数
+pub fn required_qsos(program: &AwardProgram) -> usize {
+    match program {
+        AwardProgram::SOTA => 4,
+        AwardProgram::POTA => 10,
+        AwardProgram::WWFF => 44,
+    }
+}
+
+/// 成立状況
+#[derive(Debug, Clone, PartialEq)]
+pub struct Qualification {
+    pub required: usize,
+    /// 成立条件に数えるQSO数
+    pub count: usize,
+    pub qualified: bool,
+}
+
+impl Qualification {
+    /// QSOから成立状況を求める
+    ///
+    /// SOTAは異なる局（運用者）の数、POTA/WWFFはUTCの同じ日の
+    /// 局・バンド・モードの組み合わせの数（日ごとの最大）で数える。
+    pub fn evaluate(program: &AwardProgram, qsos: &[SessionQso]) -> Self {
+        let count = match program {
+            AwardProgram::SOTA => qsos
+                .iter()
+                .map(|q| call_to_operator(&q.callsign))
+                .collect::<HashSet<_>>()
+                .len(),
+            _ => {
+                let mut by_day: HashMap<_, HashSet<_>> = HashMap::new();
+                for q in qsos {
+                    by_day.entry(q.qso_time.date_naive()).or_default().insert((
+                        call_to_operator(&q.callsign),
+                        q.band.to_lowercase(),
+                        q.mode.to_uppercase(),
+                    ));
+                }
+                by_day.values().map(HashSet::len).max().unwrap_or(0)
+            }
+        };
+        let required = required_qsos(program);
+        Self {
+            required,
+            count,
+            qualified: count >= required,
+        }
+    }
+}
+
+/// セッションと成立状況・ログファイル名
+#[derive(Debug, Clone)]
+pub struct SessionState {
+    pub session: ActivationSession,
+    pub qsos: usize,
+    pub qualification: Qualification,
+    pub files: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn qso(callsign: &str, band: &str, mode: &str, time: DateTime<Utc>) -> SessionQso {
+        SessionQso {
+            session_id: 1,
+            client_id: format!("{}-{}-{}", callsign, band, time.timestamp()),
+            callsign: callsign.to_string(),
+            band: band.to_string(),
+            mode: mode.to_string(),
+            qso_time: time,
+            record: "{}".to_string(),
+            created_at: time,
+        }
+    }
+
+    #[test]
+    fn test_sota_counts_distinct_stations() {
+        let t = Utc.with_ymd_and_hms(2026, 10, 18, 1, 0, 0).unwrap();
+        let qsos = vec![
+            qso("JA1AAA", "40m", "CW", t),
+            qso("JA1AAA/P", "20m", "CW", t),
+            qso("JA1BBB", "40m", "CW", t),
+            qso("JA1CCC", "40m", "SSB", t),
+        ];
+        let q = Qualification::evaluate(&AwardProgram::SOTA, &qsos);
+        assert_eq!(q.count, 3);
+        assert!(!q.qualified);
+
+        let mut qsos = qsos;
+        qsos.push(qso("JA1DDD", "2m", "FM", t));
+        let q = Qualification::evaluate(&AwardProgram::SOTA, &qsos);
+        assert_eq!(q.count, 4);
+        assert!(q.qualified);
+    }
+
+    #[test]
+    fn test_pota_counts_per_utc_day() {
+        let t = Utc.with_ymd_and_hms(2026, 10, 18, 23, 0, 0).unwrap();
+        let mut qsos: Vec<SessionQso> = (0..6)
+            .map(|i| qso(&format!("JA1A{}", i), "40m", "CW", t))
+            .collect();
+        // 同じ局・バンド・モードは1件
+        qsos.push(qso("JA1A0", "40m", "CW", t));
+        // バンドが違えば別に数える
+        qsos.push(qso("JA1A0", "20m", "CW", t));
+        // 日付が変わった分は別の日に数える
+        qsos.extend(
+            (0..5).map(|i| qso(&format!("JA1B{}", i), "40m", "CW", t + Duration::hours(2))),
+        );
+        let q = Qualification::evaluate(&AwardProgram::POTA, &qsos);
+        assert_eq!(q.required, 10);
+        assert_eq!(q.count, 7);
+        assert!(!q.qualified);
+    }
+}
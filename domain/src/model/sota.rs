@@ -1,4 +1,5 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{json, Value};
 
 use super::{id::UserId, Maidenhead};
 
@@ -37,6 +38,83 @@ pub struct SotaReference {
     pub activation_call: Option<String>,
 }
 
+impl SotaReference {
+    /// 指定日に有効期間内か
+    pub fn is_valid_on(&self, date: NaiveDate) -> bool {
+        self.valid_from <= date && date <= self.valid_to
+    }
+}
+
+/// サミットのアクティベーションゾーン
+///
+/// サミットリストの再取り込みで消えないよう `sota_references` とは別に保持する。
+/// 各ポリゴンは外周リングのみを (経度, 緯度) で持つ（穴は扱わない）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivationZone {
+    pub summit_code: String,
+    pub polygons: Vec<Vec<(f64, f64)>>,
+}
+
+impl ActivationZone {
+    /// GeoJSONのPolygon/MultiPolygonジオメトリから生成
+    pub fn from_geometry(summit_code: &str, geometry: &Value) -> Option<Self> {
+        let ring = |v: &Value| -> Option<Vec<(f64, f64)>> {
+            let ring: Vec<(f64, f64)> = v
+                .as_array()?
+                .iter()
+                .map(|p| Some((p.get(0)?.as_f64()?, p.get(1)?.as_f64()?)))
+                .collect::<Option<_>>()?;
+            (ring.len() >= 3).then_some(ring)
+        };
+        let outer = |v: &Value| v.as_array().and_then(|rings| rings.first()).and_then(ring);
+
+        let coords = geometry.get("coordinates")?;
+        let polygons: Vec<_> = match geometry.get("type")?.as_str()? {
+            "Polygon" => vec![outer(coords)?],
+            "MultiPolygon" => coords
+                .as_array()?
+                .iter()
+                .map(outer)
+                .collect::<Option<_>>()?,
+            _ => return None,
+        };
+
+        (!polygons.is_empty()).then(|| Self {
+            summit_code: summit_code.to_string(),
+            polygons,
+        })
+    }
+
+    /// GeoJSONのMultiPolygonジオメトリ
+    pub fn geometry(&self) -> Value {
+        let coordinates: Vec<Vec<Vec<[f64; 2]>>> = self
+            .polygons
+            .iter()
+            .map(|p| vec![p.iter().map(|&(lon, lat)| [lon, lat]).collect()])
+            .collect();
+        json!({ "type": "MultiPolygon", "coordinates": coordinates })
+    }
+
+    /// 点がいずれかのポリゴンの内側にあるか（ray casting）
+    pub fn contains(&self, longitude: f64, latitude: f64) -> bool {
+        self.polygons.iter().any(|ring| {
+            let mut inside = false;
+            let mut j = ring.len() - 1;
+            for i in 0..ring.len() {
+                let (xi, yi) = ring[i];
+                let (xj, yj) = ring[j];
+                if (yi > latitude) != (yj > latitude)
+                    && longitude < (xj - xi) * (latitude - yi) / (yj - yi) + xi
+                {
+                    inside = !inside;
+                }
+                j = i;
+            }
+            inside
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SotaLog {
     pub user_id: UserId,
@@ -51,3 +129,43 @@ pub struct SotaLog {
     pub comment: Option<String>,
     pub update: DateTime<Utc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Value {
+        json!({
+            "type": "Polygon",
+            "coordinates": [[[139.0, 35.0], [139.01, 35.0], [139.01, 35.01], [139.0, 35.01], [139.0, 35.0]]]
+        })
+    }
+
+    #[test]
+    fn test_zone_from_polygon() {
+        let zone = ActivationZone::from_geometry("JA/TK-001", &square()).unwrap();
+        assert_eq!(zone.polygons.len(), 1);
+        assert!(zone.contains(139.005, 35.005));
+        assert!(!zone.contains(139.02, 35.005));
+        assert!(!zone.contains(139.005, 34.999));
+    }
+
+    #[test]
+    fn test_zone_from_multipolygon_roundtrip() {
+        let zone = ActivationZone::from_geometry("JA/TK-001", &square()).unwrap();
+        let geometry = zone.geometry();
+        assert_eq!(geometry["type"], "MultiPolygon");
+
+        let again = ActivationZone::from_geometry("JA/TK-001", &geometry).unwrap();
+        assert_eq!(zone, again);
+    }
+
+    #[test]
+    fn test_zone_rejects_invalid_geometry() {
+        let point = json!({ "type": "Point", "coordinates": [139.0, 35.0] });
+        assert!(ActivationZone::from_geometry("JA/TK-001", &point).is_none());
+
+        let line = json!({ "type": "Polygon", "coordinates": [[[139.0, 35.0], [139.1, 35.0]]] });
+        assert!(ActivationZone::from_geometry("JA/TK-001", &line).is_none());
+    }
+}
@@ -0,0 +1,376 @@
+//! ウォッチリストとWebhook通知
+//!
+//! ウォッチリストはコールサイン・リファレンスパターン・プログラム・バンドの条件を持ち、
+//! 取り込んだスポット/アラートが条件に合えば登録されたURLへ署名つきのJSONを送る。
+//! 条件は種類ごとにAND、同じ種類の中はORで判定し、空の条件は「すべて」とみなす。
+
+use chrono::{DateTime, Duration, Utc};
+use common::utils::call_to_operator;
+
+use crate::model::activation::{Alert, Spot};
+use crate::model::id::UserId;
+use crate::model::AwardProgram;
+
+/// 配信をあきらめるまでの送信回数
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 5;
+
+/// 再送間隔の初期値（分）。失敗するたびに倍にする
+const RETRY_INITIAL_MINUTES: i64 = 1;
+
+/// 再送間隔の上限（分）
+const RETRY_MAX_MINUTES: i64 = 60;
+
+/// ウォッチリスト
+#[derive(Debug, Clone)]
+pub struct Watchlist {
+    pub watch_id: i64,
+    pub user_id: UserId,
+    pub name: String,
+    /// 運用者のコールサイン（"JA1ABC"、"JA1*" のようなワイルドカードも可）
+    pub callsigns: Vec<String>,
+    /// リファレンスのパターン（"JA/NN-*" など。`*` と `?` が使える）
+    pub patterns: Vec<String>,
+    pub programs: Vec<AwardProgram>,
+    /// バンド名（"20m"など）
+    pub bands: Vec<String>,
+    pub webhook_url: String,
+    /// 署名用の共有シークレット
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Watchlist {
+    /// 条件が1つ以上あるか（条件なしのウォッチはすべてのスポットに合うため登録させない）
+    pub fn has_criteria(&self) -> bool {
+        !(self.callsigns.is_empty()
+            && self.patterns.is_empty()
+            && self.programs.is_empty()
+            && self.bands.is_empty())
+    }
+
+    /// スポットのバンドは正規化したバンド（`Spot.band`）で判定する
+    pub fn matches_spot(&self, spot: &Spot) -> bool {
+        self.matches(
+            &spot.program,
+            &spot.activator,
+            &spot.reference,
+            spot.band.as_slice(),
+        )
+    }
+
+    /// アラートは予定周波数（"7.032-cw, 14.062-ssb" など）から求めたバンドのいずれかで判定する
+    pub fn matches_alert(&self, alert: &Alert, bands: &[String]) -> bool {
+        self.matches(&alert.program, &alert.activator, &alert.reference, bands)
+    }
+
+    fn matches(
+        &self,
+        program: &AwardProgram,
+        activator: &str,
+        reference: &str,
+        bands: &[String],
+    ) -> bool {
+        if !self.enabled || !self.has_criteria() {
+            return false;
+        }
+        if !self.programs.is_empty() && !self.programs.contains(program) {
+            return false;
+        }
+        if !self.callsigns.is_empty() {
+            let operator = call_to_operator(activator);
+            if !self
+                .callsigns
+                .iter()
+                .any(|c| glob_match(c, &operator) || glob_match(c, activator))
+            {
+                return false;
+            }
+        }
+        if !self.patterns.is_empty() && !self.patterns.iter().any(|p| glob_match(p, reference)) {
+            return false;
+        }
+        if !self.bands.is_empty()
+            && !bands
+                .iter()
+                .any(|b| self.bands.iter().any(|w| w.eq_ignore_ascii_case(b)))
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// 署名用のシークレットを作る（利用者が指定しなかったとき）
+pub fn generate_secret() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// `*`（任意の文字列）と `?`（任意の1文字）のワイルドカード照合（大文字小文字は区別しない）
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim().to_ascii_uppercase().chars().collect();
+    let text: Vec<char> = text.trim().to_ascii_uppercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// 配信の状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[repr(i32)]
+pub enum DeliveryStatus {
+    /// 未送信または再送待ち
+    Pending = 0,
+    /// 2xxが返った
+    Delivered = 1,
+    /// 再送回数を使い切った
+    Failed = 2,
+}
+
+impl DeliveryStatus {
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl From<i32> for DeliveryStatus {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Delivered,
+            2 => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// Webhookの配信記録
+///
+/// 同じウォッチ・同じイベント（`event_key`）の配信は1件だけ作るため、
+/// 定期取得で同じスポットを何度取り込んでも通知は1回になる。
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    pub delivery_id: i64,
+    pub watch_id: i64,
+    /// イベントの識別子（"spot:SOTA:12345" など）
+    pub event_key: String,
+    /// イベントの種類（"spot" / "alert"）
+    pub event: String,
+    /// 送信するJSON
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    /// 最後に返ったHTTPステータス
+    pub status_code: Option<i32>,
+    /// 最後の失敗理由
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    pub fn new(
+        watch_id: i64,
+        event_key: String,
+        event: &str,
+        payload: String,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            delivery_id: 0,
+            watch_id,
+            event_key,
+            event: event.to_string(),
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            status_code: None,
+            error: None,
+            created_at: now,
+            next_attempt_at: now,
+            delivered_at: None,
+        }
+    }
+
+    /// 送信結果を記録する（失敗なら再送時刻を決め、回数を使い切ったら失敗にする）
+    pub fn record_attempt(&mut self, result: Result<u16, String>, now: DateTime<Utc>) {
+        self.attempts += 1;
+        let error = match result {
+            Ok(code) if (200..300).contains(&code) => {
+                self.status = DeliveryStatus::Delivered;
+                self.status_code = Some(code as i32);
+                self.error = None;
+                self.delivered_at = Some(now);
+                return;
+            }
+            Ok(code) => {
+                self.status_code = Some(code as i32);
+                format!("HTTP {}", code)
+            }
+            Err(e) => e,
+        };
+        self.error = Some(error);
+        if self.attempts >= WEBHOOK_MAX_ATTEMPTS {
+            self.status = DeliveryStatus::Failed;
+        } else {
+            self.next_attempt_at = now + retry_backoff(self.attempts);
+        }
+    }
+}
+
+/// `attempts` 回失敗したあとの再送間隔（1分から倍々で増やし、60分で頭打ち）
+pub fn retry_backoff(attempts: i32) -> Duration {
+    let exp = attempts.clamp(1, 16) as u32 - 1;
+    Duration::minutes((RETRY_INITIAL_MINUTES << exp).min(RETRY_MAX_MINUTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::activation::{make_test_alert, make_test_spot};
+
+    fn watch() -> Watchlist {
+        Watchlist {
+            watch_id: 1,
+            user_id: UserId::from("user".to_string()),
+            name: "test".to_string(),
+            callsigns: vec![],
+            patterns: vec![],
+            programs: vec![],
+            bands: vec![],
+            webhook_url: "https://example.com/hook".to_string(),
+            secret: "secret".to_string(),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn spot(program: AwardProgram, activator: &str, reference: &str, band: &str) -> Spot {
+        Spot {
+            program,
+            operator: call_to_operator(activator),
+            band: Some(band.to_string()),
+            ..make_test_spot(activator, reference)
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("JA/NN-*", "JA/NN-001"));
+        assert!(glob_match("ja/nn-*", "JA/NN-001"));
+        assert!(glob_match("JA/??-001", "JA/TK-001"));
+        assert!(glob_match("*-0001", "JP-0001"));
+        assert!(glob_match("JA1ABC", "JA1ABC"));
+        assert!(!glob_match("JA/NN-*", "JA/TK-001"));
+        assert!(!glob_match("JA1ABC", "JA1ABCD"));
+        assert!(!glob_match("JA/??-001", "JA/T-001"));
+    }
+
+    #[test]
+    fn test_empty_watch_never_matches() {
+        let w = watch();
+        assert!(!w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/NN-001", "40m")));
+    }
+
+    #[test]
+    fn test_match_spot_criteria_are_anded() {
+        let mut w = watch();
+        w.patterns = vec!["JA/NN-*".to_string(), "JA/TK-*".to_string()];
+        w.bands = vec!["40m".to_string()];
+
+        assert!(w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/NN-001", "40m")));
+        assert!(w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/TK-001", "40m")));
+        assert!(!w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/NN-001", "20m")));
+        assert!(!w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/KN-001", "40m")));
+
+        // 周波数の文字列ではなく正規化したバンドで判定する
+        let mut unknown = spot(AwardProgram::SOTA, "JA1ABC", "JA/NN-001", "40m");
+        unknown.frequency = "7.032".to_string();
+        unknown.band = None;
+        assert!(!w.matches_spot(&unknown));
+
+        w.enabled = false;
+        assert!(!w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC", "JA/NN-001", "40m")));
+    }
+
+    #[test]
+    fn test_match_spot_by_operator() {
+        let mut w = watch();
+        w.callsigns = vec!["JA1ABC".to_string()];
+        w.programs = vec![AwardProgram::POTA];
+
+        assert!(w.matches_spot(&spot(AwardProgram::POTA, "JA1ABC/1", "JP-0001", "40m")));
+        assert!(!w.matches_spot(&spot(AwardProgram::SOTA, "JA1ABC/1", "JA/NN-001", "40m")));
+        assert!(!w.matches_spot(&spot(AwardProgram::POTA, "JA1XYZ", "JP-0001", "40m")));
+    }
+
+    #[test]
+    fn test_match_alert_by_any_band() {
+        let mut w = watch();
+        w.bands = vec!["20m".to_string()];
+        let alert = make_test_alert("JA1ABC", "JA/NN-001");
+        let bands = vec!["40m".to_string(), "20m".to_string()];
+        assert!(w.matches_alert(&alert, &bands));
+
+        w.bands = vec!["2m".to_string()];
+        assert!(!w.matches_alert(&alert, &bands));
+    }
+
+    #[test]
+    fn test_record_attempt_backoff() {
+        let now = Utc::now();
+        let mut d = WebhookDelivery::new(1, "spot:SOTA:1".to_string(), "spot", "{}".into(), now);
+
+        d.record_attempt(Ok(500), now);
+        assert_eq!(d.status, DeliveryStatus::Pending);
+        assert_eq!(d.status_code, Some(500));
+        assert_eq!(d.next_attempt_at, now + Duration::minutes(1));
+
+        d.record_attempt(Err("timeout".to_string()), now);
+        assert_eq!(d.next_attempt_at, now + Duration::minutes(2));
+        assert_eq!(d.error.as_deref(), Some("timeout"));
+
+        d.record_attempt(Ok(204), now);
+        assert_eq!(d.status, DeliveryStatus::Delivered);
+        assert_eq!(d.delivered_at, Some(now));
+        assert_eq!(d.error, None);
+    }
+
+    #[test]
+    fn test_record_attempt_gives_up() {
+        let now = Utc::now();
+        let mut d = WebhookDelivery::new(1, "spot:SOTA:1".to_string(), "spot", "{}".into(), now);
+        for _ in 0..WEBHOOK_MAX_ATTEMPTS {
+            d.record_attempt(Ok(404), now);
+        }
+        assert_eq!(d.status, DeliveryStatus::Failed);
+        assert_eq!(d.attempts, WEBHOOK_MAX_ATTEMPTS);
+        assert_eq!(retry_backoff(10), Duration::minutes(60));
+    }
+}
@@ -1,13 +1,16 @@
 use async_trait::async_trait;
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
-use crate::model::activation::{Alert, Spot};
+use crate::model::activation::{Alert, AlertOutcome, Spot};
+use crate::model::archive::{
+    ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
 use crate::model::event::{DeleteAct, FindAct};
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait ActivationRepositry: Send + Sync + Interface {
     async fn update_alerts(&self, alerts: Vec<Alert>) -> AppResult<()>;
@@ -16,52 +19,23 @@ pub trait ActivationRepositry: Send + Sync + Interface {
     async fn update_spots(&self, spots: Vec<Spot>) -> AppResult<()>;
     async fn find_spots(&self, query: &FindAct) -> AppResult<Vec<Spot>>;
     async fn delete_spots(&self, query: DeleteAct) -> AppResult<()>;
+    async fn update_outcomes(&self, outcomes: Vec<AlertOutcome>) -> AppResult<()>;
+    async fn find_outcomes(&self, query: &FindAct) -> AppResult<Vec<AlertOutcome>>;
+    /// 期限切れのアラートをアーカイブへ移す（移した件数を返す）
+    async fn archive_alerts(&self, query: DeleteAct) -> AppResult<u64>;
+    /// 期限切れのスポットをまとめてアーカイブへ移す（移したスポットの件数を返す）
+    async fn archive_spots(&self, query: DeleteAct) -> AppResult<u64>;
+    async fn find_alert_archive(&self, query: &FindArchive) -> AppResult<Vec<AlertArchive>>;
+    async fn find_spot_archive(&self, query: &FindArchive) -> AppResult<Vec<SpotArchive>>;
+    async fn reference_year_stats(&self, query: &FindArchive) -> AppResult<Vec<ReferenceYearStat>>;
+    async fn activator_stats(&self, query: &FindArchive) -> AppResult<Vec<ActivatorStat>>;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::activation::{make_test_alert, make_test_spot};
     use crate::model::event::FindActBuilder;
-    use crate::model::AwardProgram;
-    use chrono::Utc;
-
-    /// テスト用Alertを生成するヘルパー
-    fn make_test_alert(activator: &str, reference: &str) -> Alert {
-        Alert {
-            program: AwardProgram::SOTA,
-            alert_id: 1,
-            user_id: 1,
-            activator: activator.to_string(),
-            activator_name: None,
-            operator: activator.to_string(),
-            reference: reference.to_string(),
-            reference_detail: "Test Summit".to_string(),
-            location: "Tokyo".to_string(),
-            start_time: Utc::now(),
-            end_time: None,
-            frequencies: "14.280".to_string(),
-            comment: Some("Test".to_string()),
-            poster: Some(activator.to_string()),
-        }
-    }
-
-    /// テスト用Spotを生成するヘルパー
-    fn make_test_spot(activator: &str, reference: &str) -> Spot {
-        Spot {
-            program: AwardProgram::SOTA,
-            spot_id: 1,
-            activator: activator.to_string(),
-            activator_name: None,
-            operator: activator.to_string(),
-            reference: reference.to_string(),
-            reference_detail: "Test Summit".to_string(),
-            spot_time: Utc::now(),
-            frequency: "14.280".to_string(),
-            mode: "SSB".to_string(),
-            spotter: "JA2XYZ".to_string(),
-            comment: Some("Test".to_string()),
-        }
-    }
 
     #[tokio::test]
     async fn test_mock_find_alerts_empty() {
@@ -139,4 +113,49 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_mock_update_and_find_outcomes() {
+        use crate::model::activation::{ActivationOutcome, AlertLog};
+
+        let mut mock = MockActivationRepositry::new();
+
+        mock.expect_update_outcomes()
+            .times(1)
+            .returning(|outcomes| {
+                assert_eq!(outcomes[0].outcome, ActivationOutcome::NoShow);
+                Ok(())
+            });
+        mock.expect_find_outcomes().returning(|_| {
+            let log = AlertLog {
+                alert: make_test_alert("JA1ABC", "JA/TK-001"),
+                outcome: ActivationOutcome::OnTime,
+                spot: Some(make_test_spot("JA1ABC", "JA/TK-001")),
+                eta: None,
+                needed: None,
+                dxcc: None,
+            };
+            Ok(vec![AlertOutcome::new(&log)])
+        });
+
+        let log = AlertLog {
+            alert: make_test_alert("JA1ABC", "JA/TK-001"),
+            outcome: ActivationOutcome::NoShow,
+            spot: None,
+            eta: None,
+            needed: None,
+            dxcc: None,
+        };
+        assert!(mock
+            .update_outcomes(vec![AlertOutcome::new(&log)])
+            .await
+            .is_ok());
+
+        let query = FindActBuilder::default().operator("JA1ABC").build();
+        let result = mock.find_outcomes(&query).await.unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].outcome, ActivationOutcome::OnTime);
+        assert_eq!(result[0].spot_reference.as_deref(), Some("JA/TK-001"));
+    }
 }
@@ -1,27 +1,33 @@
-use aprs_message::{AprsCallsign, AprsData};
+use aprs_message::AprsCallsign;
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
-use crate::model::aprslog::AprsLog;
+use crate::model::aprs::AprsPacket;
+use crate::model::aprslog::{AprsLog, AprsSegment, AprsTrackArchive};
 use crate::model::event::FindAprs;
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait AprsRepositry: Send + Sync + Interface {
     async fn write_message(&self, addressee: &AprsCallsign, message: &str) -> AppResult<()>;
     async fn set_buddy_list(&self, buddy: Vec<String>) -> AppResult<()>;
     async fn set_filter(&self, filter: String) -> AppResult<()>;
-    async fn get_aprs_packet(&self) -> AppResult<AprsData>;
+    /// 次のパケットと受信時刻（記録の再生では記録時刻）
+    async fn get_aprs_packet(&self) -> AppResult<(DateTime<Utc>, AprsPacket)>;
 }
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait AprsLogRepository: Send + Sync + Interface {
     async fn find_aprs_log(&self, query: &FindAprs) -> AppResult<Vec<AprsLog>>;
     async fn insert_aprs_log(&self, aprs_log: AprsLog) -> AppResult<()>;
     async fn delete_aprs_log(&self, before: &NaiveDateTime) -> AppResult<()>;
+    /// トラックを保存する（同じ開始時刻のトラックは上書き）
+    async fn archive_track(&self, segment: AprsSegment) -> AppResult<i64>;
+    async fn find_track_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchive>>;
+    async fn find_track_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchive>>;
 }
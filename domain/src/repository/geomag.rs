@@ -1,12 +1,12 @@
 use async_trait::async_trait;
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
 use crate::model::geomag::GeomagIndex;
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait GeoMagRepositry: Send + Sync + Interface {
     async fn get_geomag(&self) -> AppResult<Option<GeomagIndex>>;
@@ -1,10 +1,10 @@
 use async_trait::async_trait;
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait HealthCheckRepositry: Send + Sync + Interface {
     async fn check_database(&self) -> AppResult<bool>;
@@ -1,12 +1,12 @@
 use async_trait::async_trait;
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
 use crate::model::locator::MunicipalityCenturyCode;
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait LocatorRepositry: Send + Sync + Interface {
     async fn upload_muni_century_list(&self, table: Vec<MunicipalityCenturyCode>) -> AppResult<()>;
@@ -14,5 +14,6 @@ pub trait LocatorRepositry: Send + Sync + Interface {
         &self,
         muni_code: i32,
     ) -> AppResult<MunicipalityCenturyCode>;
+    async fn find_all_muni_codes(&self) -> AppResult<Vec<i32>>;
     async fn find_mapcode(&self, lon: f64, lat: f64) -> AppResult<String>;
 }
@@ -1,11 +1,11 @@
 use async_trait::async_trait;
 use chrono::Duration;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use serde_json::Value;
 use shaku::Interface;
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait KvsRepositry: Send + Sync + Interface {
     async fn set(&self, key: String, value: Value, expire: Option<Duration>);
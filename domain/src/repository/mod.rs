@@ -6,4 +6,7 @@ pub mod locator;
 pub mod mapcode;
 pub mod minikvs;
 pub mod pota;
+pub mod relay;
+pub mod session;
 pub mod sota;
+pub mod watch;
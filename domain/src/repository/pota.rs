@@ -1,16 +1,18 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
+use crate::model::changelog::ReferenceChange;
 use crate::model::event::{DeleteLog, DeleteRef, FindRef, PagenatedResult};
 use crate::model::id::LogId;
 use crate::model::pota::{
     ParkCode, PotaActLog, PotaHuntLog, PotaLogHist, PotaLogStat, PotaRefLog, PotaReference,
 };
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait PotaRepository: Send + Sync + Interface {
     async fn count_reference(&self, query: &FindRef) -> AppResult<i64>;
@@ -25,6 +27,9 @@ pub trait PotaRepository: Send + Sync + Interface {
     async fn update_reference(&self, refernces: Vec<PotaReference>) -> AppResult<()>;
     async fn delete_reference(&self, query: DeleteRef<ParkCode>) -> AppResult<()>;
 
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()>;
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>>;
+
     async fn upload_activator_log(&self, logs: Vec<PotaActLog>) -> AppResult<()>;
     async fn upload_hunter_log(&self, logs: Vec<PotaHuntLog>) -> AppResult<()>;
     async fn delete_log(&self, query: DeleteLog) -> AppResult<()>;
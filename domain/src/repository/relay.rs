@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+use common::error::AppResult;
+#[cfg(any(test, feature = "test-util"))]
+use mockall::automock;
+use shaku::Interface;
+
+use crate::model::activation::{Alert, Spot};
+use crate::model::id::UserId;
+use crate::model::relay::{RelayOutcome, RelayPost};
+
+#[cfg_attr(any(test, feature = "test-util"), automock)]
+#[async_trait]
+pub trait RelayPostRepository: Send + Sync + Interface {
+    async fn insert_post(&self, post: RelayPost) -> AppResult<i64>;
+    async fn update_post(&self, post: RelayPost) -> AppResult<()>;
+    async fn find_post(&self, post_id: i64) -> AppResult<Option<RelayPost>>;
+    /// 利用者の投稿（新しい順）
+    async fn find_posts(&self, user_id: &UserId, limit: i64) -> AppResult<Vec<RelayPost>>;
+}
+
+/// 投稿をSOTAwatch/POTAなどのアップストリームへ中継する
+#[cfg_attr(any(test, feature = "test-util"), automock)]
+#[async_trait]
+pub trait UpstreamRelay: Send + Sync + Interface {
+    async fn relay_spot(&self, spot: &Spot) -> AppResult<RelayOutcome>;
+    async fn relay_alert(&self, alert: &Alert) -> AppResult<RelayOutcome>;
+}
@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use common::error::AppResult;
+#[cfg(any(test, feature = "test-util"))]
+use mockall::automock;
+use shaku::Interface;
+
+use crate::model::id::UserId;
+use crate::model::session::{ActivationSession, SessionFile, SessionQso};
+
+#[cfg_attr(any(test, feature = "test-util"), automock)]
+#[async_trait]
+pub trait SessionRepository: Send + Sync + Interface {
+    async fn create_session(&self, session: ActivationSession) -> AppResult<i64>;
+    async fn update_session(&self, session: ActivationSession) -> AppResult<()>;
+    /// セッションとQSO・ログファイルを削除する
+    async fn delete_session(&self, session_id: i64) -> AppResult<()>;
+    async fn find_session(&self, session_id: i64) -> AppResult<Option<ActivationSession>>;
+    /// 利用者のセッション（新しい順）
+    async fn find_sessions(&self, user_id: &UserId) -> AppResult<Vec<ActivationSession>>;
+    /// QSOを登録する（同じクライアントIDが登録済みならfalse）
+    async fn insert_qso(&self, qso: SessionQso) -> AppResult<bool>;
+    /// セッションのQSO（交信時刻順）
+    async fn find_qsos(&self, session_id: i64) -> AppResult<Vec<SessionQso>>;
+    /// ログファイルを置き換える
+    async fn replace_files(&self, session_id: i64, files: Vec<SessionFile>) -> AppResult<()>;
+    async fn find_file_names(&self, session_id: i64) -> AppResult<Vec<String>>;
+    async fn find_file(&self, session_id: i64, filename: &str) -> AppResult<Option<SessionFile>>;
+}
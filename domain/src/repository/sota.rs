@@ -1,13 +1,15 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use common::error::AppResult;
-#[cfg(test)]
+#[cfg(any(test, feature = "test-util"))]
 use mockall::automock;
 use shaku::Interface;
 
+use crate::model::changelog::ReferenceChange;
 use crate::model::event::{DeleteLog, DeleteRef, FindLog, FindRef, PagenatedResult};
-use crate::model::sota::{SotaLog, SotaReference, SummitCode};
+use crate::model::sota::{ActivationZone, SotaLog, SotaReference, SummitCode};
 
-#[cfg_attr(test, automock)]
+#[cfg_attr(any(test, feature = "test-util"), automock)]
 #[async_trait]
 pub trait SotaRepository: Send + Sync + Interface {
     async fn count_reference(&self, query: &FindRef) -> AppResult<i64>;
@@ -21,8 +23,16 @@ pub trait SotaRepository: Send + Sync + Interface {
     ) -> AppResult<PagenatedResult<SotaReference>>;
     async fn update_reference(&self, references: Vec<SotaReference>) -> AppResult<()>;
     async fn upsert_reference(&self, references: Vec<SotaReference>) -> AppResult<()>;
+    /// 全サミットを1トランザクションで置き換える
+    async fn replace_reference(&self, references: Vec<SotaReference>) -> AppResult<()>;
     async fn delete_reference(&self, query: DeleteRef<SummitCode>) -> AppResult<()>;
 
+    async fn upsert_activation_zones(&self, zones: Vec<ActivationZone>) -> AppResult<()>;
+    async fn find_activation_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZone>>;
+
+    async fn record_changes(&self, changes: Vec<ReferenceChange>) -> AppResult<()>;
+    async fn find_changes(&self, since: DateTime<Utc>) -> AppResult<Vec<ReferenceChange>>;
+
     async fn upload_log(&self, logs: Vec<SotaLog>) -> AppResult<()>;
     async fn find_log(&self, query: &FindLog) -> AppResult<Vec<SotaLog>>;
     async fn delete_log(&self, query: DeleteLog) -> AppResult<()>;
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use common::error::AppResult;
+#[cfg(any(test, feature = "test-util"))]
+use mockall::automock;
+use shaku::Interface;
+
+use crate::model::id::UserId;
+use crate::model::watch::{Watchlist, WebhookDelivery};
+
+#[cfg_attr(any(test, feature = "test-util"), automock)]
+#[async_trait]
+pub trait WatchRepository: Send + Sync + Interface {
+    async fn create_watch(&self, watch: Watchlist) -> AppResult<i64>;
+    async fn update_watch(&self, watch: Watchlist) -> AppResult<()>;
+    /// ウォッチと配信記録を削除する
+    async fn delete_watch(&self, watch_id: i64) -> AppResult<()>;
+    async fn find_watch(&self, watch_id: i64) -> AppResult<Option<Watchlist>>;
+    async fn find_watches(&self, user_id: &UserId) -> AppResult<Vec<Watchlist>>;
+    async fn find_enabled_watches(&self) -> AppResult<Vec<Watchlist>>;
+    /// 配信を登録する（同じウォッチ・同じイベントが登録済みならfalse）
+    async fn insert_delivery(&self, delivery: WebhookDelivery) -> AppResult<bool>;
+    /// 再送時刻を過ぎた未送信の配信
+    async fn find_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>>;
+    async fn update_delivery(&self, delivery: WebhookDelivery) -> AppResult<()>;
+    /// ウォッチの配信記録（新しい順）
+    async fn find_deliveries(&self, watch_id: i64, limit: i64) -> AppResult<Vec<WebhookDelivery>>;
+    async fn delete_deliveries(&self, before: DateTime<Utc>) -> AppResult<u64>;
+}
+
+#[cfg_attr(any(test, feature = "test-util"), automock)]
+#[async_trait]
+pub trait WebhookRepository: Send + Sync + Interface {
+    /// 宛先URLに送れるか確かめる（httpsで、内部ネットワークのアドレスに解決されないこと）
+    async fn check_url(&self, url: &str) -> AppResult<()>;
+    /// JSONを署名つきでPOSTし、返ったHTTPステータスを返す
+    ///
+    /// 送信時にも宛先を確かめ直し、リダイレクトはたどらない。
+    async fn post(&self, url: &str, secret: &str, payload: &str) -> AppResult<u16>;
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// アクティベーションビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ActivationView<T> {
+    pub key: Option<String>,
+    pub values: Vec<T>,
+}
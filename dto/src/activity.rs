@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// プログラム・バンド・モード・時間帯ごとのスポット数
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCellView {
+    pub program: String,
+    pub band: String,
+    pub mode: String,
+    /// UTCの時（0〜23）
+    pub hour: u32,
+    pub count: u32,
+}
+
+/// スポット集計ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityStatsView {
+    pub from: String,
+    pub to: String,
+    pub total: u32,
+    /// 周波数からバンドがわからず除いたスポット数
+    pub unknown_band: u32,
+    pub cells: Vec<ActivityCellView>,
+    /// 地磁気K指数（geomag=true指定時のみ、3時間ごと）
+    pub k_index: Option<Vec<i32>>,
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// システムメトリクス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SystemMetrics {
+    /// アップタイム（秒）
+    pub uptime_secs: u64,
+    /// メモリ使用量（バイト）
+    pub memory_used_bytes: Option<u64>,
+    /// メモリ使用量（MB）
+    pub memory_used_mb: Option<f64>,
+    /// データベース状態
+    pub db_status: String,
+}
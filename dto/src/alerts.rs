@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+use crate::dxcc::DxccView;
+use crate::spots::{NeededView, SpotView};
+
+/// アラートビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct AlertView {
+    pub program: String,
+    pub alert_id: i32,
+    pub user_id: i32,
+    pub reference: String,
+    pub reference_detail: String,
+    pub location: String,
+    pub activator: String,
+    pub operator: String,
+    pub activator_name: Option<String>,
+    pub start_time: String,
+    pub end_time: Option<String>,
+    pub frequencies: String,
+    pub comment: Option<String>,
+    pub poster: Option<String>,
+    /// アクティベーション結果（pending/on_time/late/different_reference/no_show）
+    pub outcome: Option<String>,
+    /// アラートに対応するスポット
+    pub spot: Option<SpotView>,
+    /// APRSから推定したサミット到着予想時刻
+    pub eta: Option<String>,
+    /// 利用者のログから見た未交信フラグ（user_id/log_id指定時のみ）
+    pub needed: Option<NeededView>,
+    /// アクティベーターのDXCCエンティティ
+    pub dxcc: Option<DxccView>,
+}
+
+/// 確定したアクティベーション結果ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct AlertOutcomeView {
+    pub program: String,
+    pub alert_id: i32,
+    pub reference: String,
+    pub start_time: String,
+    pub outcome: String,
+    pub spot_reference: Option<String>,
+    pub spot_time: Option<String>,
+}
+
+/// コールサインごとのアラート履行状況ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct ReliabilityView {
+    pub operator: String,
+    pub total: i64,
+    pub on_time: i64,
+    pub late: i64,
+    pub different_reference: i64,
+    pub no_show: i64,
+    /// 予定のリファレンスでQRVした割合（0.0〜1.0）
+    pub reliability: Option<f64>,
+    pub recent: Vec<AlertOutcomeView>,
+}
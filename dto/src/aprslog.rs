@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct AprsLogView {
+    pub time: String,
+    pub callsign: String,
+    pub ssid: i32,
+    pub destination: String,
+    pub state: String,
+    pub distance: f64,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub packet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Tracks {
+    pub tracks: Vec<Track>,
+}
+
+/// APRSトラック（GeoJSON Feature、座標は[緯度, 経度]）
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Track {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub geometry: Geometry,
+    pub properties: Properties,
+}
+
+/// GeoJSON LineString
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Geometry {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub coordinates: Vec<Vec<f64>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[schema(as = TrackProperties)]
+pub struct Properties {
+    pub callsign: String,
+    pub ssid: Option<String>,
+    pub lastseen: String,
+    pub distance: i32,
+    pub summit: String,
+    pub spot_summit: Option<String>,
+    pub spot_time: Option<String>,
+    pub spot_freq: Option<String>,
+    pub spot_mode: Option<String>,
+    pub spot_comment: Option<String>,
+    /// 直近の気象データ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<WeatherView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta: Option<String>,
+}
+
+/// 気象データ（単位はAPRS仕様のまま）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct WeatherView {
+    /// 風向（度）
+    pub wind_direction: Option<u16>,
+    /// 風速（mph）
+    pub wind_speed: Option<u16>,
+    /// 最大瞬間風速（mph）
+    pub wind_gust: Option<u16>,
+    /// 気温（°F）
+    pub temperature: Option<i16>,
+    /// 雨量（1/100インチ、過去1時間・過去24時間・0時から）
+    pub rain_1h: Option<u16>,
+    pub rain_24h: Option<u16>,
+    pub rain_midnight: Option<u16>,
+    /// 湿度（%）
+    pub humidity: Option<u8>,
+    /// 気圧（1/10 hPa）
+    pub pressure: Option<u32>,
+}
+
+/// アクティベーションごとのトラック（GeoJSON FeatureCollection、座標は[経度, 緯度]）
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Segments {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub features: Vec<Segment>,
+}
+
+impl Segments {
+    pub fn new(features: Vec<Segment>) -> Self {
+        Self {
+            type_: "FeatureCollection".to_string(),
+            features,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct Segment {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub geometry: Geometry,
+    pub properties: SegmentProperties,
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SegmentProperties {
+    pub callsign: String,
+    pub ssid: Option<String>,
+    pub summit: Option<String>,
+    pub arrival: Option<String>,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    /// 各点の時刻（座標と同じ順）
+    pub times: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<String>,
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// アーカイブ済みスポットビュー（アクティベーション・バンド・モードごとに1件）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SpotArchiveView {
+    pub program: String,
+    pub activation_date: String,
+    pub operator: String,
+    pub activator: String,
+    pub reference: String,
+    pub reference_detail: String,
+    pub association: String,
+    pub band: String,
+    pub mode: String,
+    /// 最後のスポットの周波数
+    pub frequency: String,
+    pub first_spot: String,
+    pub last_spot: String,
+    pub spot_count: i32,
+}
+
+/// リファレンスごと・年ごとのアクティベーション数ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceYearStatView {
+    pub program: String,
+    pub reference: String,
+    pub year: i32,
+    /// アクティベーション数（運用者・日ごとに1回）
+    pub activations: i64,
+    pub activators: i64,
+}
+
+/// アクティベーターごとの集計ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct ActivatorStatView {
+    pub operator: String,
+    pub activations: i64,
+    /// 運用したリファレンスの数
+    pub references: i64,
+}
@@ -1,12 +1,14 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize)]
+/// サインインリクエスト
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct AuthRequest {
     pub email: String,
     pub password: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub struct AuthResponse {
     pub message: String,
 }
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
+use utoipa::ToSchema;
 
 /// ログ種別
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "snake_case")]
 pub enum LogType {
@@ -15,7 +16,7 @@ pub enum LogType {
 }
 
 /// 判定モード
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "snake_case")]
 pub enum JudgmentMode {
@@ -27,7 +28,7 @@ pub enum JudgmentMode {
 }
 
 /// SOTA日本支部設立10周年記念アワード判定結果
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "camelCase")]
 pub struct AwardJudgmentResult {
@@ -48,7 +49,7 @@ pub struct AwardJudgmentResult {
 
 /// アクティベータ賞判定結果
 /// 条件: 10座の異なる山岳で、それぞれ10局以上の異なる局と交信
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "camelCase")]
 pub struct ActivatorAwardResult {
@@ -58,10 +59,14 @@ pub struct ActivatorAwardResult {
     pub qualified_summits: u32,
     /// 各山岳の詳細
     pub summits: Vec<SummitActivation>,
+    /// S2S交信相手の山岳数
+    pub s2s_summits: u32,
+    /// S2S交信の得点合計（交信日時点の得点）
+    pub s2s_points: i32,
 }
 
 /// 山岳ごとのアクティベーション結果
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "camelCase")]
 pub struct SummitActivation {
@@ -71,11 +76,14 @@ pub struct SummitActivation {
     pub unique_stations: u32,
     /// 10局以上で達成
     pub qualified: bool,
+    /// アクティベーション日時点の得点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<i32>,
 }
 
 /// チェイサー賞判定結果
 /// 条件: 1つの山岳から10人以上の異なるアクティベータと交信
-#[derive(Debug, Serialize, Default)]
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "camelCase")]
 pub struct ChaserAwardResult {
@@ -86,7 +94,7 @@ pub struct ChaserAwardResult {
 }
 
 /// 山岳ごとのチェイス結果
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 #[typeshare]
 #[serde(rename_all = "camelCase")]
 pub struct SummitChase {
@@ -96,4 +104,7 @@ pub struct SummitChase {
     pub unique_activators: u32,
     /// アクティベータ一覧
     pub activators: Vec<String>,
+    /// 最初の交信日時点の得点
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points: Option<i32>,
 }
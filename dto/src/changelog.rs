@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// サミット/パークの変更履歴ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct ReferenceChangeView {
+    pub program: String,
+    pub reference: String,
+    /// added / retired / points_changed / renamed / moved
+    pub change: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_at: String,
+}
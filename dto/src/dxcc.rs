@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// DXCCエンティティ・ゾーンビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct DxccView {
+    pub entity: String,
+    pub prefix: String,
+    pub continent: String,
+    pub cq_zone: i32,
+    pub itu_zone: i32,
+    /// エンティティ中心の緯度
+    pub lat: f64,
+    /// エンティティ中心の経度（東経が正）
+    pub lon: f64,
+}
+
+/// カントリーファイルの読み込み状況
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CtyStatusView {
+    pub loaded: bool,
+    pub entities: usize,
+    pub prefixes: usize,
+    pub path: String,
+}
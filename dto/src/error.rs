@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Error response returned as JSON
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    pub success: bool,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct GeomagView {
+    pub date: String,
+    pub a_index: i32,
+    pub k_index: Vec<i32>,
+}
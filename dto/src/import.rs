@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Import query parameters
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportParam {
+    /// Validate the file and report changes without writing to the database
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Import operation result
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+pub struct ImportResult {
+    /// Whether the import was successful
+    pub success: bool,
+    /// Human-readable message
+    pub message: String,
+    /// Number of records successfully imported
+    pub imported: u32,
+    /// Number of records skipped (duplicates, etc.)
+    pub skipped: u32,
+    /// List of errors encountered during import
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ImportError>,
+    /// Whether this was a dry run (nothing written)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// Detailed validation report (dry run or rejected import)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<ImportReportView>,
+}
+
+/// References an import would insert, update or delete
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+pub struct ImportReportView {
+    /// Number of data rows read, including rows with errors
+    pub rows: u32,
+    pub inserts: Vec<String>,
+    pub updates: Vec<String>,
+    pub deletes: Vec<String>,
+}
+
+/// Individual import error
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportError {
+    /// Line number where the error occurred (1-indexed)
+    pub line: u32,
+    /// Error message
+    pub message: String,
+}
+
+impl ImportResult {
+    /// Create a successful result with counts
+    pub fn success(imported: u32, skipped: u32) -> Self {
+        Self {
+            success: true,
+            message: format!("インポート完了: {}件追加, {}件スキップ", imported, skipped),
+            imported,
+            skipped,
+            errors: vec![],
+            ..Default::default()
+        }
+    }
+
+    /// Create a failed result with error message
+    pub fn failure(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            message: message.into(),
+            imported: 0,
+            skipped: 0,
+            errors: vec![],
+            ..Default::default()
+        }
+    }
+
+    /// Create a partial success result with errors
+    pub fn partial(imported: u32, skipped: u32, errors: Vec<ImportError>) -> Self {
+        Self {
+            success: true,
+            message: format!(
+                "インポート完了: {}件追加, {}件スキップ, {}件エラー",
+                imported,
+                skipped,
+                errors.len()
+            ),
+            imported,
+            skipped,
+            errors,
+            ..Default::default()
+        }
+    }
+}
+
+impl ImportError {
+    pub fn new(line: u32, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
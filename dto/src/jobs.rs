@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// ジョブの状態
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct JobStatus {
+    pub name: String,
+    /// cron 式、または `every 120s` 形式の実行間隔
+    pub schedule: String,
+    pub paused: bool,
+    pub running: bool,
+    pub run_count: u64,
+    pub error_count: u64,
+    pub last_start: Option<DateTime<Utc>>,
+    pub last_finish: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+impl JobStatus {
+    /// 登録直後（未実行）の状態
+    pub fn new(name: &str, schedule: String) -> Self {
+        Self {
+            name: name.to_string(),
+            schedule,
+            paused: false,
+            running: false,
+            run_count: 0,
+            error_count: 0,
+            last_start: None,
+            last_finish: None,
+            last_error: None,
+            next_run: None,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//! APIのリクエスト・レスポンス型（DTO）
+//!
+//! サーバー（`api`）とクライアント（`client`）で共有する。クライアントから使えるよう
+//! serde・utoipaなどの軽い依存だけにし、ドメインモデルとの変換は `api` に置く。
+
+pub mod activation;
+pub mod activity;
+pub mod admin;
+pub mod alerts;
+pub mod aprslog;
+pub mod archive;
+pub mod auth;
+pub mod award;
+pub mod changelog;
+pub mod dxcc;
+pub mod error;
+pub mod geomag;
+pub mod import;
+pub mod jobs;
+pub mod locator;
+pub mod param;
+pub mod pota;
+pub mod relay;
+pub mod search;
+pub mod session;
+pub mod solar;
+pub mod sota;
+pub mod spots;
+pub mod watch;
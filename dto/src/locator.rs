@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct CenturyCodeView {
+    pub muni_code: Option<i32>,
+    pub prefecture: Option<String>,
+    pub municipality: Option<String>,
+    pub jcc_code: Option<String>,
+    pub ward_code: Option<String>,
+    pub jcc_text: Option<String>,
+    pub jcg_code: Option<String>,
+    pub jcg_text: Option<String>,
+    pub hamlog_code: Option<String>,
+    pub maidenhead: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MapcodeView {
+    pub mapcode: String,
+}
+
+impl From<String> for MapcodeView {
+    fn from(mapcode: String) -> Self {
+        Self { mapcode }
+    }
+}
@@ -0,0 +1,417 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+/// バリデーションエラーレスポンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub success: bool,
+    pub message: String,
+    pub code: String,
+}
+
+// バリデーション制約値は#[validate]属性内で直接指定
+// 定数として定義するとvalidatorマクロが対応しないため
+
+/// APIクエリパラメータ
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Validate, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetParam {
+    #[validate(range(min = -180.0, max = 180.0, message = "経度は-180〜180の範囲で指定してください"))]
+    pub lon: Option<f64>,
+    #[validate(range(min = -90.0, max = 90.0, message = "緯度は-90〜90の範囲で指定してください"))]
+    pub lat: Option<f64>,
+    #[validate(range(
+        min = 0.0,
+        max = 500.0,
+        message = "距離は0〜500kmの範囲で指定してください"
+    ))]
+    pub dist: Option<f64>,
+    #[validate(range(min = -180.0, max = 180.0, message = "経度は-180〜180の範囲で指定してください"))]
+    pub min_lon: Option<f64>,
+    #[validate(range(min = -90.0, max = 90.0, message = "緯度は-90〜90の範囲で指定してください"))]
+    pub min_lat: Option<f64>,
+    #[validate(range(min = -180.0, max = 180.0, message = "経度は-180〜180の範囲で指定してください"))]
+    pub max_lon: Option<f64>,
+    #[validate(range(min = -90.0, max = 90.0, message = "緯度は-90〜90の範囲で指定してください"))]
+    pub max_lat: Option<f64>,
+    #[validate(range(
+        min = 0,
+        max = 9000,
+        message = "標高は0〜9000mの範囲で指定してください"
+    ))]
+    pub min_elev: Option<i32>,
+    #[validate(range(
+        min = 0,
+        max = 100000000,
+        message = "面積は0〜100000000の範囲で指定してください"
+    ))]
+    pub min_area: Option<i32>,
+    #[validate(range(
+        min = 1,
+        max = 10000,
+        message = "max_countは1〜10000の範囲で指定してください"
+    ))]
+    pub max_count: Option<u32>,
+    #[validate(length(max = 20, message = "pota_codeは20文字以内で指定してください"))]
+    pub pota_code: Option<String>,
+    #[validate(length(max = 20, message = "sota_codeは20文字以内で指定してください"))]
+    pub sota_code: Option<String>,
+    #[validate(length(max = 20, message = "wwff_codeは20文字以内で指定してください"))]
+    pub wwff_code: Option<String>,
+    #[validate(length(max = 100, message = "user_idは100文字以内で指定してください"))]
+    pub user_id: Option<String>,
+    #[validate(length(max = 50, message = "log_idは50文字以内で指定してください"))]
+    pub log_id: Option<String>,
+    #[validate(length(max = 100, message = "nameは100文字以内で指定してください"))]
+    pub name: Option<String>,
+    /// グリッドロケーター（4桁または6桁、前方一致）
+    #[validate(custom(
+        function = "validate_grid",
+        message = "gridは4桁または6桁のグリッドロケーターで指定してください"
+    ))]
+    pub grid: Option<String>,
+    /// JCCコード
+    #[validate(length(max = 10, message = "jccは10文字以内で指定してください"))]
+    pub jcc: Option<String>,
+    /// JCGコード
+    #[validate(length(max = 10, message = "jcgは10文字以内で指定してください"))]
+    pub jcg: Option<String>,
+    /// 都道府県（名称、ローマ字、またはJP-13のようなコード）
+    #[validate(length(max = 20, message = "prefectureは20文字以内で指定してください"))]
+    pub prefecture: Option<String>,
+    #[validate(range(
+        min = 0,
+        max = 8760,
+        message = "hours_agoは0〜8760（1年）の範囲で指定してください"
+    ))]
+    pub hours_ago: Option<i64>,
+    #[validate(range(
+        min = 1,
+        max = 10000,
+        message = "limitは1〜10000の範囲で指定してください"
+    ))]
+    pub limit: Option<i32>,
+    #[validate(range(
+        min = 0,
+        max = 1000000,
+        message = "offsetは0〜1000000の範囲で指定してください"
+    ))]
+    pub offset: Option<i32>,
+    #[validate(range(
+        min = 1000,
+        max = 999999,
+        message = "muni_codeは1000〜999999の範囲で指定してください"
+    ))]
+    pub muni_code: Option<i32>,
+    #[validate(length(max = 20, message = "by_callは20文字以内で指定してください"))]
+    pub by_call: Option<String>,
+    #[validate(length(max = 20, message = "by_refは20文字以内で指定してください"))]
+    pub by_ref: Option<String>,
+    #[validate(length(max = 50, message = "pat_refは50文字以内で指定してください"))]
+    pub pat_ref: Option<String>,
+    /// スポット/アラートの並び順（neededで未交信のものを先に）
+    #[validate(length(max = 20, message = "sortは20文字以内で指定してください"))]
+    pub sort: Option<String>,
+    /// スポット・アーカイブ検索: バンド（20m、2mなど）
+    #[validate(length(max = 10, message = "bandは10文字以内で指定してください"))]
+    pub band: Option<String>,
+    /// アーカイブ検索: モード（CW/SSB/FM/MFSKなど、スポットの `canonicalMode` と同じ表記）
+    #[validate(length(max = 20, message = "modeは20文字以内で指定してください"))]
+    pub mode: Option<String>,
+    /// アーカイブ検索: 協会・エンティティ（JA、W7W、JPなど）
+    #[validate(length(max = 16, message = "associationは16文字以内で指定してください"))]
+    pub association: Option<String>,
+    /// スポット集計: プログラム（sota/pota、未指定なら両方）
+    #[validate(length(max = 10, message = "programは10文字以内で指定してください"))]
+    pub program: Option<String>,
+    /// スポット集計: 地磁気指数（K指数）を重ねる
+    pub geomag: Option<bool>,
+    /// アーカイブ検索: 年（after/beforeより優先）
+    #[validate(range(
+        min = 2000,
+        max = 2100,
+        message = "yearは2000〜2100の範囲で指定してください"
+    ))]
+    pub year: Option<i32>,
+    /// この時刻より後（RFC3339、hours_agoより優先）
+    pub after: Option<DateTime<Utc>>,
+    /// この時刻より前（RFC3339）
+    pub before: Option<DateTime<Utc>>,
+    /// 変更履歴: この時刻以降（RFC3339）
+    pub since: Option<DateTime<Utc>>,
+    /// この日に有効期間内だったサミットを検索（YYYY-MM-DD、期間外のサミットを含む）
+    pub as_of: Option<NaiveDate>,
+    #[validate(range(
+        min = 0.0,
+        max = 1000.0,
+        message = "toleranceは0〜1000mの範囲で指定してください"
+    ))]
+    pub tolerance: Option<f64>,
+    /// ルート検索: 経路からの距離（km、既定1km）
+    #[validate(range(
+        min = 0.01,
+        max = 50.0,
+        message = "widthは0.01〜50kmの範囲で指定してください"
+    ))]
+    pub width: Option<f64>,
+}
+
+/// 4桁（PM95）または6桁（PM95wv）のグリッドロケーター
+fn validate_grid(grid: &str) -> Result<(), ValidationError> {
+    let c: Vec<char> = grid.chars().collect();
+    let field =
+        |c: &char| c.to_ascii_uppercase().is_ascii_uppercase() && c.to_ascii_uppercase() <= 'R';
+    let subsquare =
+        |c: &char| c.to_ascii_uppercase().is_ascii_uppercase() && c.to_ascii_uppercase() <= 'X';
+    let valid = matches!(c.len(), 4 | 6)
+        && c[..2].iter().all(field)
+        && c[2..4].iter().all(char::is_ascii_digit)
+        && c[4..].iter().all(subsquare);
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("grid"))
+    }
+}
+
+impl GetParam {
+    pub fn to_key(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_default()
+    }
+
+    /// バリデーションを実行し、エラーがあればメッセージを返す
+    pub fn validated(self) -> Result<Self, String> {
+        self.validate().map_err(|e| validation_message(&e))?;
+        Ok(self)
+    }
+}
+
+/// バリデーションエラーのメッセージ（フィールドごとのメッセージをカンマ区切りでつなぐ）
+pub fn validation_message(e: &ValidationErrors) -> String {
+    let messages: Vec<String> = e
+        .field_errors()
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |err| {
+                err.message
+                    .as_ref()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| format!("{}: 無効な値です", field))
+            })
+        })
+        .collect();
+    messages.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =====================================================
+    // GetParam バリデーションテスト
+    // =====================================================
+
+    /// 正常な座標値のバリデーション
+    #[test]
+    fn test_valid_coordinates() {
+        let param = GetParam {
+            lon: Some(139.7),
+            lat: Some(35.6),
+            dist: Some(10.0),
+            ..Default::default()
+        };
+        assert!(param.validated().is_ok());
+    }
+
+    /// 経度の範囲外値でエラー
+    #[test]
+    fn test_invalid_longitude_too_high() {
+        let param = GetParam {
+            lon: Some(181.0),
+            ..Default::default()
+        };
+        let result = param.validated();
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("経度"));
+    }
+
+    #[test]
+    fn test_invalid_longitude_too_low() {
+        let param = GetParam {
+            lon: Some(-181.0),
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    /// 緯度の範囲外値でエラー
+    #[test]
+    fn test_invalid_latitude_too_high() {
+        let param = GetParam {
+            lat: Some(91.0),
+            ..Default::default()
+        };
+        let result = param.validated();
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("緯度"));
+    }
+
+    #[test]
+    fn test_invalid_latitude_too_low() {
+        let param = GetParam {
+            lat: Some(-91.0),
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    /// 距離の範囲外値でエラー
+    #[test]
+    fn test_invalid_distance_too_high() {
+        let param = GetParam {
+            dist: Some(501.0),
+            ..Default::default()
+        };
+        let result = param.validated();
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("距離"));
+    }
+
+    #[test]
+    fn test_invalid_distance_negative() {
+        let param = GetParam {
+            dist: Some(-1.0),
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    /// hours_agoの範囲テスト
+    #[test]
+    fn test_valid_hours_ago() {
+        let param = GetParam {
+            hours_ago: Some(24),
+            ..Default::default()
+        };
+        assert!(param.validated().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_hours_ago_too_high() {
+        let param = GetParam {
+            hours_ago: Some(8761), // > 8760 (1年)
+            ..Default::default()
+        };
+        let result = param.validated();
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("hours_ago"));
+    }
+
+    /// limitの範囲テスト
+    #[test]
+    fn test_valid_limit() {
+        let param = GetParam {
+            limit: Some(100),
+            ..Default::default()
+        };
+        assert!(param.validated().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_limit_zero() {
+        let param = GetParam {
+            limit: Some(0),
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    #[test]
+    fn test_invalid_limit_too_high() {
+        let param = GetParam {
+            limit: Some(10001),
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    /// 文字列長のバリデーション
+    #[test]
+    fn test_valid_string_length() {
+        let param = GetParam {
+            pota_code: Some("JA-0001".to_string()),
+            sota_code: Some("JA/TK-001".to_string()),
+            ..Default::default()
+        };
+        assert!(param.validated().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_string_too_long() {
+        let param = GetParam {
+            pota_code: Some("A".repeat(21)), // > 20文字
+            ..Default::default()
+        };
+        assert!(param.validated().is_err());
+    }
+
+    /// 空のパラメータは有効
+    #[test]
+    fn test_empty_params_valid() {
+        let param = GetParam::default();
+        assert!(param.validated().is_ok());
+    }
+
+    // =====================================================
+    // GetParam::to_key テスト
+    // =====================================================
+
+    #[test]
+    fn test_to_key_deterministic() {
+        let param = GetParam {
+            lon: Some(139.7),
+            lat: Some(35.6),
+            ..Default::default()
+        };
+        let key1 = param.to_key();
+        let key2 = param.to_key();
+        assert_eq!(key1, key2);
+    }
+
+    #[test]
+    fn test_to_key_different_params() {
+        let param1 = GetParam {
+            lon: Some(139.7),
+            ..Default::default()
+        };
+        let param2 = GetParam {
+            lon: Some(140.0),
+            ..Default::default()
+        };
+        assert_ne!(param1.to_key(), param2.to_key());
+    }
+
+    #[test]
+    fn test_grid_validation() {
+        for grid in ["PM95", "pm95wv", "QM06aa"] {
+            let param = GetParam {
+                grid: Some(grid.to_string()),
+                ..Default::default()
+            };
+            assert!(param.validated().is_ok(), "{}", grid);
+        }
+        for grid in ["PM9", "PM95w", "ZZ95", "PM9A", "PM95ZZ"] {
+            let param = GetParam {
+                grid: Some(grid.to_string()),
+                ..Default::default()
+            };
+            assert!(param.validated().is_err(), "{}", grid);
+        }
+    }
+}
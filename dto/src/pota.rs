@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+use crate::solar::SunView;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PagenatedResponse<PotaReference> {
+    pub total: i32,
+    pub limit: i32,
+    pub offset: i32,
+    pub results: Vec<PotaReference>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[schema(as = PotaUpdateRefRequest)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRefRequest {
+    pub pota_code: String,
+    pub wwff_code: String,
+    pub park_name: String,
+    pub park_name_j: String,
+    pub park_location: String,
+    pub park_locid: String,
+    pub park_type: String,
+    pub park_inactive: bool,
+    pub park_area: i32,
+    pub longitude: f64,
+    pub latitude: f64,
+}
+
+/// POTAリファレンスビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PotaRefView {
+    pub pota_code: String,
+    pub wwff_code: String,
+    pub park_name: String,
+    pub park_name_j: String,
+    pub park_location: String,
+    pub park_locid: String,
+    pub park_type: String,
+    pub park_inactive: bool,
+    pub park_area: i32,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub maidenhead: String,
+    /// 日の出・日の入り（パークの詳細のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun: Option<SunView>,
+}
+
+/// POTAリファレンスログビュー
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PotaRefLogView {
+    pub pota_code: String,
+    pub wwff_code: String,
+    pub park_name: String,
+    pub park_name_j: String,
+    pub park_location: String,
+    pub park_locid: String,
+    pub park_type: String,
+    pub park_inactive: bool,
+    pub park_area: i32,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub maidenhead: String,
+    pub attempts: Option<i32>,
+    pub activations: Option<i32>,
+    pub first_qso_date: Option<String>,
+    pub qsos: Option<i32>,
+}
+
+/// POTA検索結果ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PotaSearchView {
+    pub pota: String,
+    pub wwff: String,
+    pub name: String,
+    pub name_j: String,
+    pub locid: Vec<String>,
+    pub area: i32,
+    pub lon: f64,
+    pub lat: f64,
+    pub atmpt: Option<i32>,
+    pub act: Option<i32>,
+    pub date: Option<String>,
+    pub qsos: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PotaLogHistView {
+    pub log_id: String,
+    pub log_kind: String,
+    pub last_update: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct PotaLogStatEntView {
+    pub time: String,
+    pub users: i32,
+    pub logs: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+pub struct PotaLogStatView {
+    pub log_uploaded: i32,
+    pub log_entries: i32,
+    pub log_expired: i32,
+    pub log_error: i32,
+    pub longest_id: String,
+    pub longest_entry: i32,
+    pub query_latency: String,
+    pub log_history: Vec<PotaLogStatEntView>,
+}
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// スポットの投稿リクエスト
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPostRequest {
+    /// プログラム（"sota" / "pota"）
+    pub program: String,
+    /// サミットコード・パークコード
+    pub reference: String,
+    pub activator: String,
+    /// 周波数（SOTAはMHz、POTAはkHz）
+    pub frequency: String,
+    pub mode: String,
+    /// 省略時はアクティベーター自身（セルフスポット）
+    pub spotter: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// アラートの投稿リクエスト
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AlertPostRequest {
+    /// プログラム（"sota" / "pota"）
+    pub program: String,
+    /// サミットコード・パークコード
+    pub reference: String,
+    pub activator: String,
+    pub activator_name: Option<String>,
+    /// 開始予定時刻（RFC3339）
+    pub start_time: String,
+    /// 終了予定時刻（RFC3339）
+    pub end_time: Option<String>,
+    /// 予定の周波数・モード（"7.032-cw, 10.123-cw" など）
+    pub frequencies: String,
+    pub comment: Option<String>,
+    /// 投稿者のコールサイン（省略時はアクティベーター）
+    pub poster: Option<String>,
+}
+
+/// 投稿と中継結果のビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct RelayPostView {
+    pub post_id: i64,
+    /// "spot" / "alert"
+    pub kind: String,
+    pub program: String,
+    pub reference: String,
+    pub activator: String,
+    /// ローカルに保存したスポット・アラートのID（負の値）
+    pub local_id: i32,
+    /// "pending" / "relayed" / "failed" / "disabled"
+    pub status: String,
+    pub upstream_id: Option<String>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub relayed_at: Option<String>,
+}
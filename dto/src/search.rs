@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+use crate::pota::{PotaRefLogView, PotaSearchView};
+use crate::sota::{SotaRefView, SotaSearchView};
+
+/// 検索結果レスポンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    pub sota: Option<Vec<SotaSearchView>>,
+    pub pota: Option<Vec<PotaSearchView>>,
+}
+
+/// 検索結果フルレスポンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFullResponse {
+    pub sota: Option<Vec<SotaRefView>>,
+    pub pota: Option<Vec<PotaRefLogView>>,
+}
+
+/// 検索結果簡易レスポンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SearchBriefResponse {
+    pub count: u32,
+    pub candidates: Vec<SearchBriefData>,
+}
+
+/// 検索結果簡易データ
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SearchBriefData {
+    pub code: String,
+    pub lon: f64,
+    pub lat: f64,
+    pub name: String,
+    pub name_j: String,
+}
+
+/// ルート検索結果レスポンス（経路沿い距離の順）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct RouteSearchResponse {
+    pub count: u32,
+    pub candidates: Vec<RouteCandidate>,
+}
+
+/// ルート沿いのリファレンス
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct RouteCandidate {
+    /// "SOTA" または "POTA"
+    pub program: String,
+    pub code: String,
+    pub lon: f64,
+    pub lat: f64,
+    pub name: String,
+    pub name_j: String,
+    /// 標高（SOTAのみ）
+    pub alt_m: Option<i32>,
+    /// 得点（SOTAのみ）
+    pub points: Option<i32>,
+    /// 経路からの距離（m）
+    pub distance_from_route: f64,
+    /// 始点から最寄り位置までの経路沿い距離（m）
+    pub along_route: f64,
+}
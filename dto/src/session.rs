@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// セッションの開始リクエスト
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRequest {
+    /// プログラム（"sota" / "pota"）
+    pub program: String,
+    /// サミットコード・パークコード
+    pub reference: String,
+    /// 運用コールサイン
+    pub callsign: String,
+    /// 運用者（省略時はコールサインから求める）
+    pub operator: Option<String>,
+}
+
+/// 登録するQSO
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientQsoRequest {
+    /// クライアントが生成したID（同じIDの再送は1件として扱う）
+    pub client_id: String,
+    /// FLEのQSOレコード（時刻はUTC、自局の情報は省略時にセッションの値を使う）
+    #[schema(value_type = Object)]
+    pub record: serde_json::Value,
+}
+
+/// QSOの登録リクエスト
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QsoPostRequest {
+    pub qsos: Vec<ClientQsoRequest>,
+}
+
+/// セッションのビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SessionView {
+    pub session_id: i64,
+    pub program: String,
+    pub reference: String,
+    pub callsign: String,
+    pub operator: String,
+    /// "open" / "closed"
+    pub status: String,
+    pub opened_at: String,
+    pub closed_at: Option<String>,
+    /// 登録したQSO数
+    pub qsos: u32,
+    /// 成立に必要なQSO数
+    pub required: u32,
+    /// 成立条件に数えるQSO数
+    pub qualifying: u32,
+    pub qualified: bool,
+    pub qualified_at: Option<String>,
+    /// ダウンロードできるログファイル
+    pub files: Vec<String>,
+}
+
+/// QSO登録結果のビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct QsoPostView {
+    pub session: SessionView,
+    pub inserted: u32,
+    /// 登録済みだった（再送の）件数
+    pub duplicates: u32,
+    /// この登録で成立条件を満たしたか
+    pub just_qualified: bool,
+}
+
+/// 登録済みQSOのビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionQsoView {
+    pub client_id: String,
+    pub callsign: String,
+    pub band: String,
+    pub mode: String,
+    pub qso_time: String,
+    #[schema(value_type = Object)]
+    pub record: serde_json::Value,
+}
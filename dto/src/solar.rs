@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+
+/// 日の出・日の入りの日付パラメータ
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SunParam {
+    /// 日付（YYYY-MM-DD、省略時はその地点の今日）
+    pub date: Option<String>,
+}
+
+/// 日の出・日の入り・市民薄明・南中（時刻はRFC3339、極夜・白夜で起きないものはnull）
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SunView {
+    pub date: String,
+    pub civil_dawn: Option<String>,
+    pub sunrise: Option<String>,
+    pub solar_noon: String,
+    pub sunset: Option<String>,
+    pub civil_dusk: Option<String>,
+    /// 日の出から日の入りまでの分数
+    pub daylight_minutes: Option<i64>,
+}
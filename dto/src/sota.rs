@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+use crate::solar::SunView;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[schema(as = SotaUpdateRefRequest)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRefRequest {
+    pub summit_code: String,
+    pub association_name: String,
+    pub region_name: String,
+    pub summit_name: String,
+    pub summit_name_j: String,
+    pub city: String,
+    pub city_j: String,
+    pub alt_m: i32,
+    pub alt_ft: i32,
+    pub grid_ref1: String,
+    pub grid_ref2: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub points: i32,
+    pub bonus_points: i32,
+    pub valid_from: String,
+    pub valid_to: String,
+    pub activation_count: i32,
+    pub activation_date: Option<String>,
+    pub activation_call: Option<String>,
+}
+
+/// SOTAリファレンス詳細ビュー
+#[derive(Debug, Serialize, Deserialize, Default, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SotaRefView {
+    pub summit_code: String,
+    pub association_name: String,
+    pub region_name: String,
+    pub summit_name: String,
+    pub summit_name_j: Option<String>,
+    pub city: Option<String>,
+    pub city_j: Option<String>,
+    pub alt_m: i32,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub maidenhead: String,
+    pub points: i32,
+    pub bonus_points: i32,
+    /// 有効期間（YYYY-MM-DD）
+    pub valid_from: String,
+    pub valid_to: String,
+    pub activation_count: i32,
+    pub activation_date: Option<String>,
+    pub activation_call: Option<String>,
+    /// 日の出・日の入り（サミットの詳細のみ）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun: Option<SunView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct PagenatedResponse<SOTAReference> {
+    pub total: i32,
+    pub limit: i32,
+    pub offset: i32,
+    pub results: Vec<SOTAReference>,
+}
+
+/// SOTA検索結果ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SotaSearchView {
+    pub code: String,
+    pub name: String,
+    pub name_j: Option<String>,
+    pub alt: i32,
+    pub lon: f64,
+    pub lat: f64,
+    pub pts: i32,
+    pub count: i32,
+}
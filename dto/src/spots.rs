@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+use crate::dxcc::DxccView;
+
+/// スポットビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct SpotView {
+    pub program: String,
+    pub spot_id: i32,
+    pub reference: String,
+    pub reference_detail: String,
+    pub activator: String,
+    pub activator_name: Option<String>,
+    pub spot_time: String,
+    pub frequency: String,
+    pub mode: String,
+    pub spotter: String,
+    pub comment: Option<String>,
+    /// 周波数（Hz）
+    pub frequency_hz: Option<i64>,
+    pub band: Option<String>,
+    /// ADIFのモード（USB/LSBはSSBにまとめる）
+    pub canonical_mode: Option<String>,
+    /// 重複スポットをまとめたときのスポッター全員
+    pub spotters: Vec<String>,
+    pub qsos: Option<i32>,
+    /// 利用者のログから見た未交信フラグ（user_id/log_id指定時のみ）
+    pub needed: Option<NeededView>,
+    /// アクティベーターのDXCCエンティティ
+    pub dxcc: Option<DxccView>,
+}
+
+/// 未交信フラグ
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct NeededView {
+    /// 未交信のサミット
+    pub new_summit: bool,
+    /// 未交信のパーク
+    pub new_park: bool,
+    /// このリファレンスで未交信のバンド（判定できなければnull）
+    pub new_band: Option<bool>,
+    /// このリファレンスで未交信のモード（判定できなければnull）
+    pub new_mode: Option<bool>,
+    /// 並べ替えの優先度（0: 新リファレンス、1: 新バンド、2: 新モード、3: 交信済み）
+    pub priority: u8,
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+
+/// ウォッチリストの登録・更新リクエスト
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchRequest {
+    pub name: String,
+    /// 運用者のコールサイン（"JA1*" のようなワイルドカードも可）
+    #[serde(default)]
+    pub callsigns: Vec<String>,
+    /// リファレンスのパターン（"JA/NN-*" など）
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// プログラム（"sota" / "pota"）
+    #[serde(default)]
+    pub programs: Vec<String>,
+    /// バンド（"20m" など）
+    #[serde(default)]
+    pub bands: Vec<String>,
+    pub webhook_url: String,
+    /// 署名用のシークレット（省略時は登録時に作る・更新時は変えない）
+    pub secret: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+/// ウォッチリストビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct WatchView {
+    pub watch_id: i64,
+    pub name: String,
+    pub callsigns: Vec<String>,
+    pub patterns: Vec<String>,
+    pub programs: Vec<String>,
+    pub bands: Vec<String>,
+    pub webhook_url: String,
+    /// 署名用のシークレット（X-SotaApp-Signatureの検証に使う）
+    pub secret: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Webhook配信記録ビュー
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[typeshare]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryView {
+    pub delivery_id: i64,
+    pub event: String,
+    pub event_key: String,
+    /// "pending" / "delivered" / "failed"
+    pub status: String,
+    pub attempts: i32,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub next_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+    /// 送信したJSON
+    pub payload: String,
+}
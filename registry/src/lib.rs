@@ -1,23 +1,27 @@
 use axum::extract::FromRef;
 use common::config::AppConfig;
+use common::jobs::JobRegistry;
 use shaku::module;
 use std::sync::Arc;
 
-use aprs_message::AprsIS;
-
 use adapter::{
-    aprs::{AprsRepositryImpl, AprsRepositryImplParameters},
+    aprs::{AprsClient, AprsRepositryImpl, AprsRepositryImplParameters},
     database::connect::ConnectionPool,
     geomag::{GeoMag, GeoMagRepositryImpl, GeoMagRepositryImplParameters},
     minikvs::{MiniKvs, MiniKvsRepositryImpl, MiniKvsRepositryImplParameters},
+    relay::{HttpRelayImpl, HttpRelayImplParameters},
+    webhook::{WebhookRepositoryImpl, WebhookRepositoryImplParameters},
 };
 
 use service::implement::{
     admin_periodic::{AdminPeriodicServiceImpl, AdminPeriodicServiceImplParameters},
     admin_service::{AdminServiceImpl, AdminServiceImplParameters},
     pota_log_service::{PotaLogServiceImpl, PotaLogServiceImplParameters},
+    relay_service::RelayServiceImpl,
+    session_service::SessionServiceImpl,
     sota_log_service::SotaLogServiceImpl,
-    user_service::UserServiceImpl,
+    user_service::{UserServiceImpl, UserServiceImplParameters},
+    watch_service::WatchServiceImpl,
 };
 
 #[cfg(not(feature = "sqlite"))]
@@ -27,7 +31,10 @@ use adapter::database::implement::postgis::{
     healthcheck::{HealthCheckRepositryImpl, HealthCheckRepositryImplParameters},
     locator::{LocatorRepositryImpl, LocatorRepositryImplParameters},
     pota_reference::{POTARepositoryImpl, POTARepositoryImplParameters},
+    relay::{RelayPostRepositoryImpl, RelayPostRepositoryImplParameters},
+    session::{SessionRepositoryImpl, SessionRepositoryImplParameters},
     sota_reference::{SOTARepositoryImpl, SOTARepositoryImplParameters},
+    watch::{WatchRepositoryImpl, WatchRepositoryImplParameters},
 };
 
 #[cfg(feature = "sqlite")]
@@ -37,7 +44,10 @@ use adapter::database::implement::sqlite::{
     healthcheck::{HealthCheckRepositryImpl, HealthCheckRepositryImplParameters},
     locator::{LocatorRepositryImpl, LocatorRepositryImplParameters},
     pota_reference::{PotaRepositoryImpl, PotaRepositoryImplParameters},
+    relay::{RelayPostRepositoryImpl, RelayPostRepositoryImplParameters},
+    session::{SessionRepositoryImpl, SessionRepositoryImplParameters},
     sota_reference::{SotaRepositoryImpl, SotaRepositoryImplParameters},
+    watch::{WatchRepositoryImpl, WatchRepositoryImplParameters},
 };
 
 module! {
@@ -46,7 +56,10 @@ module! {
         SotaRepositoryImpl,PotaRepositoryImpl,
         LocatorRepositryImpl,GeoMagRepositryImpl,AprsRepositryImpl,AprsLogRepositoryImpl,
         MiniKvsRepositryImpl,
-        HealthCheckRepositryImpl],
+        HealthCheckRepositryImpl,
+        WatchServiceImpl,WatchRepositoryImpl,WebhookRepositoryImpl,
+        RelayServiceImpl,RelayPostRepositoryImpl,HttpRelayImpl,
+        SessionServiceImpl,SessionRepositoryImpl],
         providers = [],
     }
 }
@@ -55,11 +68,10 @@ impl AppRegistry {
     pub fn new(
         config: &AppConfig,
         pool: ConnectionPool,
-        aprs: AprsIS,
+        aprs: AprsClient,
         geomag: GeoMag,
         kvs: Arc<MiniKvs>,
     ) -> Self {
-        let aprs = Arc::new(aprs);
         AppRegistry::builder()
             .with_component_parameters::<SotaRepositoryImpl>(SotaRepositoryImplParameters {
                 pool: pool.clone(),
@@ -81,11 +93,15 @@ impl AppRegistry {
             .with_component_parameters::<PotaLogServiceImpl>(PotaLogServiceImplParameters {
                 config: config.clone(),
             })
+            .with_component_parameters::<UserServiceImpl>(UserServiceImplParameters {
+                config: config.clone(),
+            })
             .with_component_parameters::<AdminServiceImpl>(AdminServiceImplParameters {})
             .with_component_parameters::<AdminPeriodicServiceImpl>(
                 AdminPeriodicServiceImplParameters {
                     config: config.clone(),
                     buddy_callsigns: Default::default(),
+                    delivering: Default::default(),
                 },
             )
             .with_component_parameters::<GeoMagRepositryImpl>(GeoMagRepositryImplParameters {
@@ -98,6 +114,19 @@ impl AppRegistry {
             .with_component_parameters::<HealthCheckRepositryImpl>(
                 HealthCheckRepositryImplParameters { pool: pool.clone() },
             )
+            .with_component_parameters::<WatchRepositoryImpl>(WatchRepositoryImplParameters {
+                pool: pool.clone(),
+            })
+            .with_component_parameters::<WebhookRepositoryImpl>(WebhookRepositoryImplParameters {})
+            .with_component_parameters::<RelayPostRepositoryImpl>(
+                RelayPostRepositoryImplParameters { pool: pool.clone() },
+            )
+            .with_component_parameters::<HttpRelayImpl>(HttpRelayImplParameters {
+                config: config.clone(),
+            })
+            .with_component_parameters::<SessionRepositoryImpl>(SessionRepositoryImplParameters {
+                pool: pool.clone(),
+            })
             .build()
     }
 }
@@ -106,13 +135,15 @@ impl AppRegistry {
 pub struct AppState {
     module: Arc<AppRegistry>,
     pub config: AppConfig,
+    pub jobs: JobRegistry,
 }
 
 impl AppState {
-    pub fn new(module: AppRegistry, config: AppConfig) -> Self {
+    pub fn new(module: AppRegistry, config: AppConfig, jobs: JobRegistry) -> Self {
         Self {
             module: Arc::new(module),
             config,
+            jobs,
         }
     }
 }
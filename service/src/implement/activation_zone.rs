@@ -0,0 +1,131 @@
+//! APRS位置によるサミット到着判定
+//!
+//! アクティベーションゾーン（ポリゴン）が登録されていればその内外で、
+//! なければ協会ごとの半径でサミット到着を判定する。APRS高度がある場合は
+//! サミット標高からの高度差も条件に加える。
+
+use common::utils::calculate_distance;
+use domain::model::sota::{ActivationZone, SotaReference};
+
+/// サミットまでの距離がこれより遠ければ Approaching
+pub const APPROACHING_DISTANCE: f64 = 1000.0;
+/// サミットまでの距離がこれより遠ければ Climbing
+pub const CLIMBING_DISTANCE: f64 = 300.0;
+
+/// 位置から判定した到着状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneState {
+    Approaching,
+    Climbing,
+    NearSummit,
+    OnSummit,
+}
+
+/// 到着判定パラメータ
+#[derive(Debug, Clone, Copy)]
+pub struct ZoneParam {
+    /// ゾーン未登録時の到着判定半径（m）
+    pub radius: f64,
+    /// サミット標高からの許容高度差（m）
+    pub vertical_m: f64,
+}
+
+/// 位置がアクティベーションゾーン内か判定し、到着状態とサミットまでの距離（m）を返す
+pub fn classify_position(
+    summit: &SotaReference,
+    zone: Option<&ActivationZone>,
+    param: &ZoneParam,
+    latitude: f64,
+    longitude: f64,
+    altitude: Option<f64>,
+) -> (ZoneState, f64) {
+    let distance =
+        calculate_distance(latitude, longitude, summit.latitude, summit.longitude).floor();
+
+    let in_area = match zone {
+        Some(zone) => zone.contains(longitude, latitude),
+        None => distance <= param.radius,
+    };
+    let in_altitude = altitude.is_none_or(|alt| alt >= summit.alt_m as f64 - param.vertical_m);
+
+    let state = if in_area && in_altitude {
+        ZoneState::OnSummit
+    } else if distance > APPROACHING_DISTANCE {
+        ZoneState::Approaching
+    } else if distance > CLIMBING_DISTANCE {
+        ZoneState::Climbing
+    } else {
+        ZoneState::NearSummit
+    };
+    (state, distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use domain::model::activation::make_test_summit;
+
+    fn summit() -> SotaReference {
+        make_test_summit(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2099, 12, 31).unwrap(),
+        )
+    }
+
+    fn param() -> ZoneParam {
+        ZoneParam {
+            radius: 100.0,
+            vertical_m: 25.0,
+        }
+    }
+
+    #[test]
+    fn test_fallback_radius_thresholds() {
+        let s = summit();
+        // 緯度0.001度 ≒ 111m
+        let cases = [
+            (35.0005, ZoneState::OnSummit),
+            (35.002, ZoneState::NearSummit),
+            (35.005, ZoneState::Climbing),
+            (35.02, ZoneState::Approaching),
+        ];
+        for (lat, expected) in cases {
+            let (state, _) = classify_position(&s, None, &param(), lat, 139.0, None);
+            assert_eq!(state, expected, "lat {}", lat);
+        }
+    }
+
+    #[test]
+    fn test_altitude_below_zone() {
+        let s = summit();
+        let (state, _) = classify_position(&s, None, &param(), 35.0, 139.0, Some(960.0));
+        assert_eq!(state, ZoneState::NearSummit);
+
+        let (state, _) = classify_position(&s, None, &param(), 35.0, 139.0, Some(980.0));
+        assert_eq!(state, ZoneState::OnSummit);
+    }
+
+    #[test]
+    fn test_polygon_overrides_radius() {
+        let s = summit();
+        // 東西に細長いゾーン（サミット中心から東へ約500m）
+        let zone = ActivationZone {
+            summit_code: s.summit_code.clone(),
+            polygons: vec![vec![
+                (138.9995, 34.9995),
+                (139.0055, 34.9995),
+                (139.0055, 35.0005),
+                (138.9995, 35.0005),
+            ]],
+        };
+
+        let (state, distance) = classify_position(&s, Some(&zone), &param(), 35.0, 139.005, None);
+        assert_eq!(state, ZoneState::OnSummit);
+        assert!(distance > 400.0);
+
+        // 中心から100m以内でもゾーン外なら到着としない
+        let (state, _) = classify_position(&s, Some(&zone), &param(), 35.0008, 139.0, None);
+        assert_eq!(state, ZoneState::NearSummit);
+    }
+}
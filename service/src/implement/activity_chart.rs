@@ -0,0 +1,211 @@
+//! スポット集計のSVGヒートマップ生成
+//!
+//! 横軸をUTCの時、縦軸をバンドとして、スポット数を色の濃さで表す。
+//! 地磁気指数があれば、同じ時間軸でK指数の棒グラフを下に重ねる。
+
+use plotters::prelude::*;
+
+use domain::model::activity::ActivityStats;
+use domain::model::geomag::GeomagIndex;
+
+/// ヒートマップ1行の高さ（ピクセル）
+const ROW_HEIGHT: u32 = 28;
+/// K指数グラフの高さ（ピクセル）
+const GEOMAG_HEIGHT: u32 = 140;
+
+/// スポット数をヒートマップの色に変換（薄い黄色から濃い赤へ）
+fn heat_color(count: u32, max: u32) -> RGBColor {
+    let ratio = if max == 0 {
+        0.0
+    } else {
+        count as f64 / max as f64
+    };
+    let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * ratio) as u8;
+    RGBColor(mix(255, 180), mix(240, 0), mix(190, 0))
+}
+
+/// K指数の色（4以上は擾乱、5以上は磁気嵐）
+fn k_color(k: f32) -> RGBColor {
+    if k >= 5.0 {
+        RGBColor(220, 50, 50)
+    } else if k >= 4.0 {
+        RGBColor(240, 160, 40)
+    } else {
+        RGBColor(80, 170, 90)
+    }
+}
+
+/// スポット集計からSVGヒートマップを生成
+pub fn generate_activity_svg(
+    stats: &ActivityStats,
+    geomag: Option<&GeomagIndex>,
+    width: u32,
+) -> Result<String, String> {
+    let bands = stats.bands();
+    let band_hours = stats.band_hours();
+    let max = band_hours.values().copied().max().unwrap_or(0);
+    let rows = (bands.len() as u32).max(1);
+
+    let width = width.max(400);
+    let heatmap_height = rows * ROW_HEIGHT + 90;
+    let height = heatmap_height + geomag.map_or(0, |_| GEOMAG_HEIGHT);
+
+    let title = format!(
+        "Spots by band/hour (UTC) {} - {}  total {}",
+        stats.from.format("%Y-%m-%d %H:%M"),
+        stats.to.format("%Y-%m-%d %H:%M"),
+        stats.total
+    );
+
+    let mut svg_buffer = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg_buffer, (width, height)).into_drawing_area();
+        root.fill(&WHITE).map_err(|e| e.to_string())?;
+        let (upper, lower) = root.split_vertically(heatmap_height);
+
+        let mut chart = ChartBuilder::on(&upper)
+            .caption(&title, ("sans-serif", 16).into_font())
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(60)
+            .build_cartesian_2d(
+                (0u32..24u32).into_segmented(),
+                (0u32..rows).into_segmented(),
+            )
+            .map_err(|e| e.to_string())?;
+
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .x_labels(24)
+            .y_labels(rows as usize)
+            .x_label_formatter(&|x: &SegmentValue<u32>| match x {
+                SegmentValue::CenterOf(h) => format!("{:02}", h),
+                _ => String::new(),
+            })
+            .y_label_formatter(&|y: &SegmentValue<u32>| match y {
+                SegmentValue::CenterOf(i) => bands.get(*i as usize).cloned().unwrap_or_default(),
+                _ => String::new(),
+            })
+            .draw()
+            .map_err(|e| e.to_string())?;
+
+        let cells: Vec<(u32, u32, u32)> = bands
+            .iter()
+            .enumerate()
+            .flat_map(|(row, band)| (0..24u32).map(move |hour| (hour, row as u32, band)))
+            .map(|(hour, row, band)| {
+                let count = band_hours.get(&(band.clone(), hour)).copied().unwrap_or(0);
+                (hour, row, count)
+            })
+            .collect();
+
+        chart
+            .draw_series(cells.iter().map(|&(hour, row, count)| {
+                Rectangle::new(
+                    [
+                        (SegmentValue::Exact(hour), SegmentValue::Exact(row)),
+                        (SegmentValue::Exact(hour + 1), SegmentValue::Exact(row + 1)),
+                    ],
+                    heat_color(count, max).filled(),
+                )
+            }))
+            .map_err(|e| e.to_string())?;
+
+        chart
+            .draw_series(cells.iter().filter(|c| c.2 > 0).map(|&(hour, row, count)| {
+                Text::new(
+                    count.to_string(),
+                    (SegmentValue::CenterOf(hour), SegmentValue::CenterOf(row)),
+                    ("sans-serif", 11).into_font(),
+                )
+            }))
+            .map_err(|e| e.to_string())?;
+
+        if let Some(geomag) = geomag {
+            let mut kchart = ChartBuilder::on(&lower)
+                .caption(
+                    format!("K index {}", geomag.date),
+                    ("sans-serif", 14).into_font(),
+                )
+                .margin(10)
+                .x_label_area_size(20)
+                .y_label_area_size(60)
+                .build_cartesian_2d(0u32..24u32, 0f32..9f32)
+                .map_err(|e| e.to_string())?;
+
+            kchart
+                .configure_mesh()
+                .disable_x_mesh()
+                .x_labels(9)
+                .y_labels(4)
+                .draw()
+                .map_err(|e| e.to_string())?;
+
+            // K指数は3時間ごとの8個
+            kchart
+                .draw_series(geomag.k_index.iter().take(8).enumerate().map(|(i, k)| {
+                    let x = i as u32 * 3;
+                    Rectangle::new([(x, 0.0), (x + 3, *k)], k_color(*k).filled())
+                }))
+                .map_err(|e| e.to_string())?;
+        }
+
+        root.present().map_err(|e| e.to_string())?;
+    }
+
+    Ok(svg_buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use domain::model::activation::{make_test_spot, Spot};
+
+    fn stats() -> ActivityStats {
+        let from = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 10, 18, 23, 0, 0).unwrap();
+        let spot = Spot {
+            spot_time: Utc.with_ymd_and_hms(2026, 10, 18, 2, 0, 0).unwrap(),
+            frequency_hz: Some(7_032_000),
+            band: Some("40m".to_string()),
+            canonical_mode: Some("CW".to_string()),
+            ..make_test_spot("JA1ABC/P", "JA/TK-001")
+        };
+        ActivityStats::from_spots(&[spot], from, to)
+    }
+
+    #[test]
+    fn test_heat_color() {
+        let rgb = |c: RGBColor| (c.0, c.1, c.2);
+        assert_eq!(rgb(heat_color(0, 10)), (255, 240, 190));
+        assert_eq!(rgb(heat_color(10, 10)), (180, 0, 0));
+        assert_eq!(rgb(heat_color(0, 0)), (255, 240, 190));
+    }
+
+    #[test]
+    fn test_generate_activity_svg() {
+        let svg = generate_activity_svg(&stats(), None, 800).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("40m"));
+    }
+
+    #[test]
+    fn test_generate_activity_svg_with_geomag() {
+        let geomag = GeomagIndex {
+            date: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            a_index: 12,
+            k_index: vec![2.0, 3.0, 5.0, 4.0, 2.0, 1.0, 1.0, 2.0],
+        };
+        let svg = generate_activity_svg(&stats(), Some(&geomag), 800).unwrap();
+        assert!(svg.contains("K index 2026-10-18"));
+    }
+
+    #[test]
+    fn test_generate_activity_svg_empty() {
+        let from = Utc.with_ymd_and_hms(2026, 10, 18, 0, 0, 0).unwrap();
+        let empty = ActivityStats::from_spots(&[], from, from);
+        assert!(generate_activity_svg(&empty, None, 100).is_ok());
+    }
+}
@@ -1,4 +1,3 @@
-use aprs_message::AprsData;
 use async_trait::async_trait;
 use chrono::{DateTime, TimeDelta, Utc};
 use csv::ReaderBuilder;
@@ -7,18 +6,33 @@ use shaku::Component;
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
+use common::metrics::{self, APRS_PACKETS_TOTAL};
 use common::{config::AppConfig, error::AppError, error::AppResult};
-use domain::model::event::{DeleteRef, FindRefBuilder};
+use domain::model::activation::AlertOutcome;
+use domain::model::aprs::AprsPacket;
+use domain::model::aprslog::AprsLogDetail;
+use domain::model::changelog::{ReferenceChange, ReferenceSnapshot};
+use domain::model::event::{FindActBuilder, FindRefBuilder};
 use domain::model::pota::PotaReference;
-use domain::model::sota::{SotaReference, SummitCode};
-use domain::model::{activation::Alert, activation::Spot, event::DeleteAct};
+use domain::model::sota::SotaReference;
+use domain::model::watch::{Watchlist, WebhookDelivery};
+use domain::model::{activation::Alert, activation::Spot, event::DeleteAct, AwardProgram};
+use domain::repository::watch::{WatchRepository, WebhookRepository};
 use domain::repository::{
     activation::ActivationRepositry, aprs::AprsRepositry, pota::PotaRepository,
     sota::SotaRepository,
 };
 
+use crate::implement::alert_outcome::{correlate_alerts, OutcomeWindow};
+use crate::implement::spot_normalize::{
+    merge_duplicate_spots, normalize_spot, SPOT_MERGE_WINDOW_MINUTES,
+};
+use crate::implement::watch_notify::{
+    alert_deliveries, deliver_due, enqueue_deliveries, spot_deliveries, DELIVERY_RETENTION_DAYS,
+};
 use crate::model::pota::POTAAllCSVFile;
 use crate::model::sota::SOTASummitCSV;
 use crate::services::AdminPeriodicService;
@@ -36,17 +50,19 @@ pub struct AdminPeriodicServiceImpl {
     pub sota_repo: Arc<dyn SotaRepository>,
     #[shaku(inject)]
     pub pota_repo: Arc<dyn PotaRepository>,
+    #[shaku(inject)]
+    pub watch_repo: Arc<dyn WatchRepository>,
+    #[shaku(inject)]
+    pub webhook: Arc<dyn WebhookRepository>,
 
     pub config: AppConfig,
     /// APRSバディリスト（コールサイン、SSIDなし）
     /// r/+t/ フィルターで受信後にアプリ側でフィルタリングするために保持する
     #[shaku(default)]
     pub buddy_callsigns: Mutex<HashSet<String>>,
-}
-
-fn is_valid_summit(r: &SotaReference) -> bool {
-    let today = chrono::Local::now().date_naive();
-    today <= r.valid_to && today >= r.valid_from
+    /// Webhookを送信中か（前回の送信が終わるまで次を始めない）
+    #[shaku(default)]
+    pub delivering: Arc<AtomicBool>,
 }
 
 /// SotaReferenceの比較対象フィールドからハッシュ値を計算
@@ -83,6 +99,66 @@ fn compute_park_hash(r: &PotaReference) -> u64 {
     hasher.finish()
 }
 
+impl AdminPeriodicServiceImpl {
+    /// 時間帯が終わったアラートの結果を確定して保存する（アラート削除後も残す）
+    async fn record_alert_outcomes(
+        &self,
+        after: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let query = FindActBuilder::default().issued_after(after).build();
+        let alerts = self.act_repo.find_alerts(&query).await?;
+
+        let param = OutcomeWindow::from_config(&self.config);
+        let outcomes: Vec<AlertOutcome> =
+            correlate_alerts(self.act_repo.as_ref(), alerts, &param, now)
+                .await?
+                .iter()
+                .filter(|log| param.is_closed(&log.alert, now))
+                .map(AlertOutcome::new)
+                .collect();
+
+        if !outcomes.is_empty() {
+            tracing::debug!("record {} alert outcomes", outcomes.len());
+            self.act_repo.update_outcomes(outcomes).await?;
+        }
+        Ok(())
+    }
+
+    /// 有効なウォッチリスト（取得できなければ通知しない）
+    async fn enabled_watches(&self) -> Vec<Watchlist> {
+        self.watch_repo
+            .find_enabled_watches()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("find watchlists failed: {:?}", e);
+                vec![]
+            })
+    }
+
+    /// 配信を登録して送信を始める（取り込みは通知の失敗で止めない）
+    async fn notify_watchers(&self, deliveries: Vec<WebhookDelivery>) {
+        match enqueue_deliveries(self.watch_repo.as_ref(), deliveries).await {
+            Ok(n) if n > 0 => tracing::debug!("enqueue {} webhook deliveries", n),
+            Ok(_) => {}
+            Err(e) => tracing::warn!("enqueue webhook deliveries failed: {:?}", e),
+        }
+
+        if self.delivering.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let watch_repo = self.watch_repo.clone();
+        let webhook = self.webhook.clone();
+        let delivering = self.delivering.clone();
+        tokio::spawn(async move {
+            if let Err(e) = deliver_due(watch_repo.as_ref(), webhook.as_ref(), Utc::now()).await {
+                tracing::warn!("webhook delivery failed: {:?}", e);
+            }
+            delivering.store(false, Ordering::Release);
+        });
+    }
+}
+
 #[async_trait]
 impl AdminPeriodicService for AdminPeriodicServiceImpl {
     async fn update_alerts(&self, alerts: Vec<Alert>) -> AppResult<()> {
@@ -114,74 +190,133 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
             tracing::warn!("APRS set_buddy_list skipped: {:?}", e);
         }
 
+        let deliveries = alert_deliveries(&self.enabled_watches().await, &alerts, now);
         self.act_repo.update_alerts(alerts).await?;
+        self.notify_watchers(deliveries).await;
 
         let expire = now - self.config.alert_expire;
-        self.act_repo
-            .delete_alerts(DeleteAct { before: expire })
+        self.record_alert_outcomes(expire, now).await?;
+
+        // 期限切れのアラートは削除せずアーカイブへ移す
+        let archived = self
+            .act_repo
+            .archive_alerts(DeleteAct { before: expire })
             .await?;
+        if archived > 0 {
+            tracing::debug!("archive {} alerts", archived);
+        }
 
         let expire = now - self.config.aprs_log_expire;
         self.aprs_log_repo
             .delete_aprs_log(&expire.naive_utc())
             .await?;
 
+        let expire = now - TimeDelta::days(DELIVERY_RETENTION_DAYS);
+        self.watch_repo.delete_deliveries(expire).await?;
+
         Ok(())
     }
 
-    async fn update_spots(&self, spots: Vec<Spot>) -> AppResult<()> {
+    async fn update_spots(&self, mut spots: Vec<Spot>) -> AppResult<()> {
+        spots.iter_mut().for_each(normalize_spot);
+        let spots = merge_duplicate_spots(spots, TimeDelta::minutes(SPOT_MERGE_WINDOW_MINUTES));
+        let now: DateTime<Utc> = Utc::now();
+        let deliveries = spot_deliveries(&self.enabled_watches().await, &spots, now);
         self.act_repo.update_spots(spots).await?;
+        self.notify_watchers(deliveries).await;
 
-        let expire: DateTime<Utc> = Utc::now() - self.config.alert_expire;
-        self.act_repo
-            .delete_spots(DeleteAct { before: expire })
+        let expire: DateTime<Utc> = now - self.config.alert_expire;
+        let archived = self
+            .act_repo
+            .archive_spots(DeleteAct { before: expire })
             .await?;
+        if archived > 0 {
+            tracing::debug!("archive {} spots", archived);
+        }
         Ok(())
     }
 
-    async fn aprs_packet_received(&self, packet: AprsData) -> AppResult<()> {
-        match packet {
-            AprsData::AprsMessage {
+    async fn aprs_packet_received(&self, packet: AprsPacket, now: DateTime<Utc>) -> AppResult<()> {
+        metrics::inc_counter(APRS_PACKETS_TOTAL, &[("type", packet.kind())]);
+
+        if let AprsPacket::Message {
+            callsign,
+            addressee,
+            message,
+        } = packet
+        {
+            tracing::info!(
+                "APRS message from = {:?} to = {:} message = {:}",
                 callsign,
                 addressee,
-                message,
-            } => {
-                tracing::info!(
-                    "APRS message from = {:?} to = {:} message = {:}",
-                    callsign,
-                    addressee,
-                    message
-                );
-                return self.process_message(&callsign, message).await;
-            }
-            AprsData::AprsPosition {
+                message
+            );
+            return self.process_message(&callsign, message, now).await;
+        }
+
+        // バディリストに含まれるコールサインのみ処理（SSID除いた基本コールで照合）
+        let in_buddy = self
+            .buddy_callsigns
+            .lock()
+            .map(|guard| guard.contains(&packet.callsign().callsign))
+            .unwrap_or(false);
+        if !in_buddy {
+            return Ok(());
+        }
+
+        match packet {
+            AprsPacket::Position {
                 callsign,
                 latitude,
                 longitude,
+                altitude,
             } => {
-                // バディリストに含まれるコールサインのみ処理（SSID除いた基本コールで照合）
-                let in_buddy = self
-                    .buddy_callsigns
-                    .lock()
-                    .map(|guard| guard.contains(&callsign.callsign))
-                    .unwrap_or(false);
-                if !in_buddy {
-                    return Ok(());
-                }
-                if let Some(ssid) = callsign.ssid {
-                    if [5, 6, 7, 8, 9].contains(&ssid) {
-                        return self.process_position(callsign, latitude, longitude).await;
-                    }
+                if self.config.aprs_tracked_ssids.allows(callsign.ssid) {
+                    return self
+                        .process_position(callsign, latitude, longitude, altitude, now)
+                        .await;
                 }
             }
+            AprsPacket::Object { callsign, object } => {
+                return self.process_object(callsign, object, now).await;
+            }
+            AprsPacket::Weather {
+                callsign,
+                position,
+                report,
+            } => {
+                return self
+                    .attach_to_track(callsign, position, AprsLogDetail::Weather(report), now)
+                    .await;
+            }
+            AprsPacket::Telemetry {
+                callsign,
+                telemetry,
+            } => {
+                return self
+                    .attach_to_track(callsign, None, AprsLogDetail::Telemetry(telemetry), now)
+                    .await;
+            }
+            AprsPacket::Message { .. } => {}
         };
         Ok(())
     }
 
+    fn set_buddy_callsigns(&self, callsigns: HashSet<String>) {
+        if let Ok(mut guard) = self.buddy_callsigns.lock() {
+            *guard = callsigns;
+        }
+    }
+
     async fn update_summit_list_from_file(&self, path: &Path) -> AppResult<usize> {
+        let now = Utc::now();
+        // 有効期間外の判定はすべてのパスで取り込み日にそろえる
+        let today = now.date_naive();
+
         // Pass 1: ファイルを読んで軽量データを構築
-        let mut valid_hashes: HashMap<String, u64> = HashMap::new();
-        let mut invalid_codes: HashSet<String> = HashSet::new();
+        // 有効期間外のサミットも過去ログの参照用に有効期間付きで保持する
+        let mut summit_hashes: HashMap<String, u64> = HashMap::new();
+        let mut change_hashes: HashMap<String, u64> = HashMap::new();
 
         let mut rdr = ReaderBuilder::new()
             .has_headers(false)
@@ -194,23 +329,21 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
             let row: SOTASummitCSV = record.deserialize(None).map_err(AppError::CSVReadError)?;
             let summit = SotaReference::from(row);
 
-            if is_valid_summit(&summit) {
-                let hash = compute_summit_hash(&summit);
-                valid_hashes.insert(summit.summit_code.clone(), hash);
-            } else {
-                invalid_codes.insert(summit.summit_code.clone());
-            }
+            let hash = compute_summit_hash(&summit);
+            summit_hashes.insert(summit.summit_code.clone(), hash);
+            let change_hash = ReferenceSnapshot::sota(&summit, today).change_hash();
+            change_hashes.insert(summit.summit_code.clone(), change_hash);
         }
 
-        tracing::info!(
-            "Pass 1: {} valid summits, {} invalid summits",
-            valid_hashes.len(),
-            invalid_codes.len()
-        );
+        tracing::info!("Pass 1: {} summits", summit_hashes.len());
 
-        // Pass 2: DBと比較して更新/削除対象を特定
-        let mut to_update: HashSet<String> = valid_hashes.keys().cloned().collect();
-        let mut to_delete: Vec<SummitCode> = Vec::new();
+        // Pass 2: DBと比較して更新対象を特定
+        let mut to_update: HashSet<String> = summit_hashes.keys().cloned().collect();
+        // 変更履歴用: DBにないサミットと、比較対象フィールドが変わったサミットの旧値
+        let mut added: HashSet<String> = summit_hashes.keys().cloned().collect();
+        let mut snapshots: HashMap<String, ReferenceSnapshot> = HashMap::new();
+        let mut changes: Vec<ReferenceChange> = Vec::new();
+        let mut db_count = 0;
 
         let limit = 5000;
         let mut offset = 0;
@@ -218,6 +351,7 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
         loop {
             let query = FindRefBuilder::new()
                 .sota()
+                .include_retired()
                 .limit(limit)
                 .offset(offset)
                 .build();
@@ -228,29 +362,22 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
             }
 
             for r in result {
-                if let Some(&new_hash) = valid_hashes.get(&r.summit_code) {
+                db_count += 1;
+                let snapshot = ReferenceSnapshot::sota(&r, today);
+                if let Some(&new_hash) = summit_hashes.get(&r.summit_code) {
+                    added.remove(&r.summit_code);
                     if compute_summit_hash(&r) == new_hash {
                         to_update.remove(&r.summit_code);
                     }
-                } else if invalid_codes.contains(&r.summit_code) {
-                    to_delete.push(SummitCode::new(r.summit_code.clone()));
+                    if change_hashes.get(&r.summit_code) != Some(&snapshot.change_hash()) {
+                        snapshots.insert(r.summit_code.clone(), snapshot);
+                    }
                 }
             }
             offset += limit;
         }
 
-        tracing::info!(
-            "Pass 2: {} to update, {} to delete",
-            to_update.len(),
-            to_delete.len()
-        );
-
-        // 削除実行
-        for code in to_delete {
-            self.sota_repo
-                .delete_reference(DeleteRef::Delete(code))
-                .await?;
-        }
+        tracing::info!("Pass 2: {} to update", to_update.len());
 
         // Pass 3: ファイルを再度読んで更新対象だけ収集
         let mut updates: Vec<SotaReference> = Vec::new();
@@ -266,6 +393,17 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
             let row: SOTASummitCSV = record.deserialize(None).map_err(AppError::CSVReadError)?;
             let summit = SotaReference::from(row);
 
+            // 初回取り込み（DBが空）のときは追加を記録しない
+            let old = snapshots.get(&summit.summit_code);
+            if old.is_some() || (db_count > 0 && added.contains(&summit.summit_code)) {
+                changes.extend(ReferenceSnapshot::diff(
+                    AwardProgram::SOTA,
+                    old,
+                    Some(&ReferenceSnapshot::sota(&summit, today)),
+                    now,
+                ));
+            }
+
             if to_update.contains(&summit.summit_code) {
                 updates.push(summit);
             }
@@ -275,12 +413,18 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
         tracing::info!("Pass 3: upserting {} summits", count);
         self.sota_repo.upsert_reference(updates).await?;
 
+        tracing::info!("{} summit changes", changes.len());
+        self.sota_repo.record_changes(changes).await?;
+
         Ok(count)
     }
 
     async fn update_pota_park_list_from_file(&self, path: &Path) -> AppResult<usize> {
+        let now = Utc::now();
+
         // Pass 1: ファイルを読んで軽量データを構築
         let mut park_hashes: HashMap<String, u64> = HashMap::new();
+        let mut change_hashes: HashMap<String, u64> = HashMap::new();
 
         let mut rdr = ReaderBuilder::new()
             .has_headers(false)
@@ -300,6 +444,8 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
                 Ok(park) => {
                     let hash = compute_park_hash(&park);
                     park_hashes.insert(park.pota_code.clone(), hash);
+                    let change_hash = ReferenceSnapshot::from(&park).change_hash();
+                    change_hashes.insert(park.pota_code.clone(), change_hash);
                 }
                 Err(_) => continue,
             }
@@ -309,6 +455,10 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
 
         // Pass 2: DBと比較して更新対象を特定
         let mut to_update: HashSet<String> = park_hashes.keys().cloned().collect();
+        // 変更履歴用: DBにないパークと、比較対象フィールドが変わったパークの旧値
+        let mut added: HashSet<String> = park_hashes.keys().cloned().collect();
+        let mut snapshots: HashMap<String, ReferenceSnapshot> = HashMap::new();
+        let mut db_count = 0;
 
         let limit = 5000;
         let mut offset = 0;
@@ -342,10 +492,16 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
                     update: chrono::Utc::now(),
                 };
 
+                db_count += 1;
                 if let Some(&new_hash) = park_hashes.get(&r.pota_code) {
+                    added.remove(&r.pota_code);
                     if compute_park_hash(&park_ref) == new_hash {
                         to_update.remove(&r.pota_code);
                     }
+                    let snapshot = ReferenceSnapshot::from(&park_ref);
+                    if change_hashes.get(&r.pota_code) != Some(&snapshot.change_hash()) {
+                        snapshots.insert(r.pota_code.clone(), snapshot);
+                    }
                 }
             }
             offset += limit;
@@ -355,6 +511,7 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
 
         // Pass 3: ファイルを再度読んで更新対象だけ収集
         let mut updates: Vec<PotaReference> = Vec::new();
+        let mut changes: Vec<ReferenceChange> = Vec::new();
 
         let mut rdr = ReaderBuilder::new()
             .has_headers(false)
@@ -371,6 +528,17 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
             }
 
             if let Ok(park) = PotaReference::try_from(row) {
+                // 初回取り込み（DBが空）のときは追加を記録しない
+                let old = snapshots.get(&park.pota_code);
+                if old.is_some() || (db_count > 0 && added.contains(&park.pota_code)) {
+                    changes.extend(ReferenceSnapshot::diff(
+                        AwardProgram::POTA,
+                        old,
+                        Some(&ReferenceSnapshot::from(&park)),
+                        now,
+                    ));
+                }
+
                 if to_update.contains(&park.pota_code) {
                     updates.push(park);
                 }
@@ -381,6 +549,9 @@ impl AdminPeriodicService for AdminPeriodicServiceImpl {
         tracing::info!("Pass 3: upserting {} parks", count);
         self.pota_repo.create_reference(updates).await?;
 
+        tracing::info!("{} park changes", changes.len());
+        self.pota_repo.record_changes(changes).await?;
+
         Ok(count)
     }
 }
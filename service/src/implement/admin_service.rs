@@ -1,12 +1,12 @@
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 
 use shaku::Component;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use common::error::AppResult;
-use common::utils::csv_reader;
+use common::utils::{csv_reader, csv_reader_lenient, CsvRowError};
 
 use domain::model::event::{DeleteRef, FindRef, FindRefBuilder, PagenatedResult};
 use domain::model::locator::MunicipalityCenturyCode;
@@ -17,10 +17,11 @@ use domain::repository::{
     sota::SotaRepository,
 };
 
+use crate::model::import::ImportReport;
 use crate::model::locator::{MuniCSVFile, UploadMuniCSV};
 use crate::model::pota::{POTAAllCSVFile, POTACSVFile, UploadPOTAReference};
 use crate::model::sota::{SOTASumitOptCSV, SOTASummitCSV};
-use crate::model::sota::{UploadSOTASummit, UploadSOTASummitOpt};
+use crate::model::sota::{UploadActivationZones, UploadSOTASummit, UploadSOTASummitOpt};
 
 use crate::services::AdminService;
 
@@ -38,8 +39,52 @@ pub struct AdminServiceImpl {
 }
 
 fn is_valid_summit(r: &SotaReference) -> bool {
-    let today = Local::now().date_naive();
-    today <= r.valid_to && today >= r.valid_from
+    r.is_valid_on(Utc::now().date_naive())
+}
+
+impl AdminServiceImpl {
+    /// DBに登録済みのサミットコード
+    async fn existing_sota_codes(&self) -> AppResult<HashSet<String>> {
+        let mut codes = HashSet::new();
+        let limit = 5000;
+        let mut offset = 0;
+        loop {
+            let query = FindRefBuilder::new()
+                .sota()
+                .include_retired()
+                .limit(limit)
+                .offset(offset)
+                .build();
+            let result = self.sota_repo.find_reference(&query).await?;
+            if result.is_empty() {
+                break;
+            }
+            codes.extend(result.into_iter().map(|r| r.summit_code));
+            offset += limit;
+        }
+        Ok(codes)
+    }
+
+    /// DBに登録済みのパークコード
+    async fn existing_pota_codes(&self) -> AppResult<HashSet<String>> {
+        let mut codes = HashSet::new();
+        let limit = 5000;
+        let mut offset = 0;
+        loop {
+            let query = FindRefBuilder::new()
+                .pota()
+                .limit(limit)
+                .offset(offset)
+                .build();
+            let result = self.pota_repo.find_reference(&query).await?;
+            if result.is_empty() {
+                break;
+            }
+            codes.extend(result.into_iter().map(|r| r.pota_code));
+            offset += limit;
+        }
+        Ok(codes)
+    }
 }
 
 #[async_trait]
@@ -47,23 +92,33 @@ impl AdminService for AdminServiceImpl {
     async fn import_summit_list(
         &self,
         UploadSOTASummit { data }: UploadSOTASummit,
-    ) -> AppResult<usize> {
-        let csv: Vec<SOTASummitCSV> = csv_reader(data, false, 2)?;
-        let req: Vec<_> = csv
+        dry_run: bool,
+    ) -> AppResult<ImportReport> {
+        let (rows, errors) = csv_reader_lenient::<SOTASummitCSV>(&data, false, 2);
+        let mut report = ImportReport::new(dry_run, rows.len() + errors.len(), errors);
+        report.check_duplicates(rows.iter().map(|(line, r)| (*line, r.summit_code.as_str())));
+
+        // 有効期間外のサミットも過去ログの参照用に取り込む
+        let req: Vec<_> = rows
             .into_iter()
-            .map(SotaReference::from)
-            .filter(is_valid_summit)
+            .map(|(_, r)| SotaReference::from(r))
             .collect();
 
-        let count = req.len();
-        tracing::info!("import {} references.", count);
-        self.sota_repo
-            .delete_reference(DeleteRef::DeleteAll)
-            .await?;
-
-        self.sota_repo.create_reference(req).await?;
-
-        Ok(count)
+        let mut existing = self.existing_sota_codes().await?;
+        for r in &req {
+            let exists = existing.remove(&r.summit_code);
+            report.classify(r.summit_code.clone(), exists);
+        }
+        report.deletes = existing.into_iter().collect();
+        report.deletes.sort();
+
+        if report.should_commit() {
+            let retired = req.iter().filter(|r| !is_valid_summit(r)).count();
+            tracing::info!("import {} references ({} retired).", req.len(), retired);
+            self.sota_repo.replace_reference(req).await?;
+            report.committed = true;
+        }
+        Ok(report)
     }
 
     async fn update_summit_list(
@@ -93,7 +148,6 @@ impl AdminService for AdminServiceImpl {
         let mut new_hash: HashMap<_, _> = csv
             .into_iter()
             .map(SotaReference::from)
-            .filter(is_valid_summit)
             .map(|r| (r.summit_code.clone(), r))
             .collect();
 
@@ -103,6 +157,7 @@ impl AdminService for AdminServiceImpl {
         loop {
             let query = FindRefBuilder::new()
                 .sota()
+                .include_retired()
                 .limit(limit)
                 .offset(offset)
                 .build();
@@ -135,12 +190,15 @@ impl AdminService for AdminServiceImpl {
     async fn import_summit_opt_list(
         &self,
         UploadSOTASummitOpt { data }: UploadSOTASummitOpt,
-    ) -> AppResult<usize> {
-        let csv: Vec<SOTASumitOptCSV> = csv_reader(data, false, 1)?;
+        dry_run: bool,
+    ) -> AppResult<ImportReport> {
+        let (rows, errors) = csv_reader_lenient::<SOTASumitOptCSV>(&data, false, 1);
+        let mut report = ImportReport::new(dry_run, rows.len() + errors.len(), errors);
+        report.check_duplicates(rows.iter().map(|(line, r)| (*line, r.summit_code.as_str())));
 
-        let ja_hash: HashMap<_, _> = csv
+        let ja_hash: HashMap<_, _> = rows
             .into_iter()
-            .map(|r| (r.summit_code.clone(), r))
+            .map(|(_, r)| (r.summit_code.clone(), r))
             .collect();
 
         let associations: HashSet<String> = ja_hash
@@ -148,31 +206,51 @@ impl AdminService for AdminServiceImpl {
             .map(|s| s.split("/").next().unwrap_or("").to_owned() + "/")
             .collect();
 
-        let mut total_count = 0;
+        let mut newref = Vec::new();
         for assoc in associations {
-            let query = FindRefBuilder::new().sota().name(assoc).build();
+            let query = FindRefBuilder::new()
+                .sota()
+                .include_retired()
+                .name(assoc)
+                .build();
             let result = self.sota_repo.find_reference(&query).await?;
-            let newref: Vec<_> = result
-                .into_iter()
-                .filter(|r| ja_hash.contains_key(&r.summit_code))
-                .filter_map(|mut r| {
-                    // filterで確認済みなのでget()は成功するはずだが、安全のためfilter_mapを使用
-                    let ja = ja_hash.get(&r.summit_code)?;
-                    r.summit_name = ja.summit_name.clone();
-                    r.summit_name_j = Some(ja.summit_name_j.clone());
-                    r.city = Some(ja.city.clone());
-                    r.city_j = Some(ja.city_j.clone());
-                    r.longitude = ja.longitude;
-                    r.latitude = ja.latitude;
-                    r.alt_m = ja.alt_m;
-                    Some(r)
-                })
-                .collect();
-            total_count += newref.len();
+            newref.extend(
+                result
+                    .into_iter()
+                    .filter(|r| ja_hash.contains_key(&r.summit_code))
+                    .filter_map(|mut r| {
+                        // filterで確認済みなのでget()は成功するはずだが、安全のためfilter_mapを使用
+                        let ja = ja_hash.get(&r.summit_code)?;
+                        r.summit_name = ja.summit_name.clone();
+                        r.summit_name_j = Some(ja.summit_name_j.clone());
+                        r.city = Some(ja.city.clone());
+                        r.city_j = Some(ja.city_j.clone());
+                        r.longitude = ja.longitude;
+                        r.latitude = ja.latitude;
+                        r.alt_m = ja.alt_m;
+                        Some(r)
+                    }),
+            );
+        }
+        for r in &newref {
+            report.classify(r.summit_code.clone(), true);
+        }
+        // DBにないサミットは更新しない
+        report.skipped = ja_hash.len() - newref.len();
+
+        if report.should_commit() {
             self.sota_repo.update_reference(newref).await?;
+            report.committed = true;
         }
+        Ok(report)
+    }
 
-        Ok(total_count)
+    async fn import_activation_zones(&self, event: UploadActivationZones) -> AppResult<usize> {
+        let zones = event.parse()?;
+        let count = zones.len();
+        tracing::info!("import {} activation zones.", count);
+        self.sota_repo.upsert_activation_zones(zones).await?;
+        Ok(count)
     }
 
     async fn import_pota_park_list(
@@ -196,30 +274,79 @@ impl AdminService for AdminServiceImpl {
     async fn import_pota_park_list_ja(
         &self,
         UploadPOTAReference { data }: UploadPOTAReference,
-    ) -> AppResult<usize> {
-        let requests: Vec<POTACSVFile> = csv_reader(data, false, 1)?;
-        let newref: Vec<_> = requests.into_iter().map(PotaReference::from).collect();
+        dry_run: bool,
+    ) -> AppResult<ImportReport> {
+        let (rows, mut errors) = csv_reader_lenient::<POTACSVFile>(&data, false, 1);
+        let total = rows.len() + errors.len();
+        let rows: Vec<_> = rows
+            .into_iter()
+            .filter_map(|(line, r)| match r.pota_code.as_deref() {
+                Some(code) if !code.trim().is_empty() => Some((line, r)),
+                _ => {
+                    errors.push(CsvRowError {
+                        line,
+                        message: "pota_code is empty".to_string(),
+                    });
+                    None
+                }
+            })
+            .collect();
+        let mut report = ImportReport::new(dry_run, total, errors);
+        report.check_duplicates(
+            rows.iter()
+                .map(|(line, r)| (*line, r.pota_code.as_deref().unwrap_or_default())),
+        );
 
-        let count = newref.len();
-        tracing::info!("update {} JA parks.", count);
-        self.pota_repo.create_reference(newref).await?;
+        let newref: Vec<_> = rows
+            .into_iter()
+            .map(|(_, r)| PotaReference::from(r))
+            .collect();
 
-        Ok(count)
+        let existing = self.existing_pota_codes().await?;
+        for r in &newref {
+            report.classify(r.pota_code.clone(), existing.contains(&r.pota_code));
+        }
+
+        if report.should_commit() {
+            tracing::info!("update {} JA parks.", newref.len());
+            self.pota_repo.create_reference(newref).await?;
+            report.committed = true;
+        }
+        Ok(report)
     }
 
     async fn import_muni_century_list(
         &self,
         UploadMuniCSV { data }: UploadMuniCSV,
-    ) -> AppResult<usize> {
-        let requests: Vec<MuniCSVFile> = csv_reader(data, false, 1)?;
-        let newtable: Vec<_> = requests
+        dry_run: bool,
+    ) -> AppResult<ImportReport> {
+        let (rows, errors) = csv_reader_lenient::<MuniCSVFile>(&data, false, 1);
+        let mut report = ImportReport::new(dry_run, rows.len() + errors.len(), errors);
+        let codes: Vec<_> = rows
+            .iter()
+            .map(|(line, r)| (*line, r.muni_code.to_string()))
+            .collect();
+        report.check_duplicates(codes.iter().map(|(line, c)| (*line, c.as_str())));
+
+        let existing: HashSet<i32> = self
+            .loc_repo
+            .find_all_muni_codes()
+            .await?
             .into_iter()
-            .map(MunicipalityCenturyCode::from)
             .collect();
-        let count = newtable.len();
-        self.loc_repo.upload_muni_century_list(newtable).await?;
+        for (_, r) in &rows {
+            report.classify(r.muni_code.to_string(), existing.contains(&r.muni_code));
+        }
 
-        Ok(count)
+        if report.should_commit() {
+            let newtable: Vec<_> = rows
+                .into_iter()
+                .map(|(_, r)| MunicipalityCenturyCode::from(r))
+                .collect();
+            self.loc_repo.upload_muni_century_list(newtable).await?;
+            report.committed = true;
+        }
+        Ok(report)
     }
 
     async fn show_sota_reference(&self, event: FindRef) -> AppResult<SotaReference> {
@@ -271,39 +398,11 @@ impl AdminService for AdminServiceImpl {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
-    use domain::model::sota::SotaReference;
-
-    /// is_valid_summit関数のテスト用ヘルパー
-    fn make_test_summit(valid_from: NaiveDate, valid_to: NaiveDate) -> SotaReference {
-        SotaReference {
-            summit_code: "JA/TK-001".to_string(),
-            association_name: "Japan".to_string(),
-            region_name: "Tokyo".to_string(),
-            summit_name: "Mt. Test".to_string(),
-            summit_name_j: None,
-            alt_m: 1000,
-            alt_ft: 3280,
-            grid_ref1: "PM95".to_string(),
-            grid_ref2: "".to_string(),
-            longitude: 139.0,
-            latitude: 35.0,
-            maidenhead: "PM95wv".to_string(),
-            points: 10,
-            bonus_points: 0,
-            valid_from,
-            valid_to,
-            activation_count: 0,
-            activation_date: None,
-            activation_call: None,
-            city: None,
-            city_j: None,
-        }
-    }
+    use domain::model::activation::make_test_summit;
 
     #[test]
     fn test_is_valid_summit_currently_valid() {
-        let today = Local::now().date_naive();
+        let today = Utc::now().date_naive();
         let valid_from = today - chrono::Duration::days(30);
         let valid_to = today + chrono::Duration::days(30);
         let summit = make_test_summit(valid_from, valid_to);
@@ -313,7 +412,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_summit_today_is_start_date() {
-        let today = Local::now().date_naive();
+        let today = Utc::now().date_naive();
         let valid_to = today + chrono::Duration::days(30);
         let summit = make_test_summit(today, valid_to);
 
@@ -322,7 +421,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_summit_today_is_end_date() {
-        let today = Local::now().date_naive();
+        let today = Utc::now().date_naive();
         let valid_from = today - chrono::Duration::days(30);
         let summit = make_test_summit(valid_from, today);
 
@@ -331,7 +430,7 @@ mod tests {
 
     #[test]
     fn test_is_valid_summit_expired() {
-        let today = Local::now().date_naive();
+        let today = Utc::now().date_naive();
         let valid_from = today - chrono::Duration::days(60);
         let valid_to = today - chrono::Duration::days(30);
         let summit = make_test_summit(valid_from, valid_to);
@@ -341,11 +440,66 @@ mod tests {
 
     #[test]
     fn test_is_valid_summit_not_yet_valid() {
-        let today = Local::now().date_naive();
+        let today = Utc::now().date_naive();
         let valid_from = today + chrono::Duration::days(30);
         let valid_to = today + chrono::Duration::days(60);
         let summit = make_test_summit(valid_from, valid_to);
 
         assert!(!is_valid_summit(&summit));
     }
+
+    #[test]
+    fn test_parse_activation_zones() {
+        let upload = UploadActivationZones {
+            data: r#"{
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "properties": { "summit_code": "ja/tk-001" },
+                        "geometry": {
+                            "type": "Polygon",
+                            "coordinates": [[[139.0, 35.0], [139.01, 35.0], [139.01, 35.01], [139.0, 35.0]]]
+                        }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": { "summitCode": "JA/TK-002", "name": "Mt. Test" },
+                        "geometry": {
+                            "type": "MultiPolygon",
+                            "coordinates": [[[[139.1, 35.1], [139.2, 35.1], [139.2, 35.2], [139.1, 35.1]]]]
+                        }
+                    }
+                ]
+            }"#
+            .to_string(),
+        };
+
+        let zones = upload.parse().unwrap();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].summit_code, "JA/TK-001");
+        assert!(zones[1].contains(139.15, 35.12));
+    }
+
+    #[test]
+    fn test_parse_activation_zones_invalid_geometry() {
+        let upload = UploadActivationZones {
+            data: r#"{
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "properties": { "summit_code": "JA/TK-001" },
+                        "geometry": { "type": "Point", "coordinates": [139.0, 35.0] }
+                    }
+                ]
+            }"#
+            .to_string(),
+        };
+
+        assert!(matches!(
+            upload.parse(),
+            Err(common::error::AppError::UnprocessableEntity(_))
+        ));
+    }
 }
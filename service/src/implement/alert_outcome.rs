@@ -0,0 +1,225 @@
+//! アラートとスポットの突き合わせ
+//!
+//! アラートの時間帯に同じオペレータのスポットがあるかどうかで
+//! アクティベーション結果（定刻/遅延/別リファレンス/不在）を判定する。
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use common::config::AppConfig;
+use common::error::AppResult;
+use domain::model::activation::{ActivationOutcome, Alert, AlertLog, Spot};
+use domain::model::event::FindAct;
+use domain::repository::activation::ActivationRepositry;
+
+/// 判定パラメータ
+#[derive(Debug, Clone, Copy)]
+pub struct OutcomeWindow {
+    /// 予定時刻からの許容誤差（前後）
+    pub tolerance: TimeDelta,
+    /// 終了時刻のないアラートの有効時間
+    pub window: TimeDelta,
+}
+
+impl OutcomeWindow {
+    pub fn new(tolerance: TimeDelta, window: TimeDelta) -> Self {
+        Self { tolerance, window }
+    }
+
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(
+            config.alert_ontime_tolerance,
+            config.alert_activation_window,
+        )
+    }
+
+    /// アラートに対応するスポットを探す時間帯
+    pub fn range(&self, alert: &Alert) -> (DateTime<Utc>, DateTime<Utc>) {
+        let start = alert.start_time - self.tolerance;
+        let end = match alert.end_time {
+            Some(end) if end > alert.start_time => end,
+            _ => alert.start_time + self.window,
+        };
+        (start, end)
+    }
+
+    /// 時間帯が終わっており結果が確定しているか
+    pub fn is_closed(&self, alert: &Alert, now: DateTime<Utc>) -> bool {
+        let (_, end) = self.range(alert);
+        now > end
+    }
+}
+
+/// アラートをスポットと突き合わせて結果を判定する
+///
+/// `spots` はプログラム・時刻を問わず渡してよい（ここで絞り込む）。
+pub fn correlate_alert(
+    alert: Alert,
+    spots: &[Spot],
+    param: &OutcomeWindow,
+    now: DateTime<Utc>,
+) -> AlertLog {
+    let (start, end) = param.range(&alert);
+
+    let candidates: Vec<&Spot> = spots
+        .iter()
+        .filter(|s| {
+            s.program == alert.program
+                && s.operator.eq_ignore_ascii_case(&alert.operator)
+                && s.spot_time >= start
+                && s.spot_time <= end
+        })
+        .collect();
+
+    let same_ref = candidates
+        .iter()
+        .filter(|s| s.reference.eq_ignore_ascii_case(&alert.reference))
+        .min_by_key(|s| s.spot_time);
+
+    let (outcome, spot) = if let Some(s) = same_ref {
+        if s.spot_time <= alert.start_time + param.tolerance {
+            (ActivationOutcome::OnTime, Some((*s).clone()))
+        } else {
+            (ActivationOutcome::Late, Some((*s).clone()))
+        }
+    } else if let Some(s) = candidates.iter().max_by_key(|s| s.spot_time) {
+        (ActivationOutcome::DifferentReference, Some((*s).clone()))
+    } else if now > end {
+        (ActivationOutcome::NoShow, None)
+    } else {
+        (ActivationOutcome::Pending, None)
+    };
+
+    AlertLog {
+        alert,
+        outcome,
+        spot,
+        eta: None,
+        needed: None,
+        dxcc: None,
+    }
+}
+
+/// アラート群の時間帯に含まれるスポットを取得してまとめて突き合わせる
+pub async fn correlate_alerts(
+    act_repo: &dyn ActivationRepositry,
+    alerts: Vec<Alert>,
+    param: &OutcomeWindow,
+    now: DateTime<Utc>,
+) -> AppResult<Vec<AlertLog>> {
+    let Some(earliest) = alerts.iter().map(|a| param.range(a).0).min() else {
+        return Ok(Vec::new());
+    };
+
+    let query = FindAct {
+        issued_after: Some(earliest),
+        ..Default::default()
+    };
+    let spots = act_repo.find_spots(&query).await?;
+
+    Ok(alerts
+        .into_iter()
+        .map(|a| correlate_alert(a, &spots, param, now))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use domain::model::activation::{make_test_alert, make_test_spot};
+    use domain::model::AwardProgram;
+
+    fn t(h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 3, h, m, 0).unwrap()
+    }
+
+    fn alert(reference: &str, start: DateTime<Utc>) -> Alert {
+        Alert {
+            operator: "JA1ABC".to_string(),
+            start_time: start,
+            ..make_test_alert("JA1ABC/P", reference)
+        }
+    }
+
+    fn spot(operator: &str, reference: &str, time: DateTime<Utc>) -> Spot {
+        Spot {
+            operator: operator.to_string(),
+            spot_time: time,
+            ..make_test_spot(&format!("{}/P", operator), reference)
+        }
+    }
+
+    fn param() -> OutcomeWindow {
+        OutcomeWindow::new(TimeDelta::minutes(30), TimeDelta::hours(3))
+    }
+
+    #[test]
+    fn test_on_time() {
+        let spots = vec![spot("JA1ABC", "JA/TK-001", t(1, 10))];
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &spots, &param(), t(1, 15));
+        assert_eq!(log.outcome, ActivationOutcome::OnTime);
+        assert_eq!(log.spot.unwrap().spot_time, t(1, 10));
+    }
+
+    #[test]
+    fn test_early_spot_is_on_time() {
+        let spots = vec![spot("ja1abc", "JA/TK-001", t(0, 40))];
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &spots, &param(), t(1, 0));
+        assert_eq!(log.outcome, ActivationOutcome::OnTime);
+    }
+
+    #[test]
+    fn test_late_uses_earliest_spot() {
+        let spots = vec![
+            spot("JA1ABC", "JA/TK-001", t(3, 0)),
+            spot("JA1ABC", "JA/TK-001", t(2, 0)),
+        ];
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &spots, &param(), t(3, 0));
+        assert_eq!(log.outcome, ActivationOutcome::Late);
+        assert_eq!(log.spot.unwrap().spot_time, t(2, 0));
+    }
+
+    #[test]
+    fn test_different_reference() {
+        let spots = vec![spot("JA1ABC", "JA/TK-002", t(1, 5))];
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &spots, &param(), t(1, 10));
+        assert_eq!(log.outcome, ActivationOutcome::DifferentReference);
+        assert_eq!(log.spot.unwrap().reference, "JA/TK-002");
+    }
+
+    #[test]
+    fn test_no_show_after_window() {
+        let spots = vec![
+            spot("JA1XYZ", "JA/TK-001", t(1, 5)),
+            spot("JA1ABC", "JA/TK-001", t(5, 0)),
+        ];
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &spots, &param(), t(4, 1));
+        assert_eq!(log.outcome, ActivationOutcome::NoShow);
+        assert!(log.spot.is_none());
+    }
+
+    #[test]
+    fn test_pending_within_window() {
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &[], &param(), t(2, 0));
+        assert_eq!(log.outcome, ActivationOutcome::Pending);
+    }
+
+    #[test]
+    fn test_range_uses_end_time() {
+        let mut a = alert("JA-0001", t(1, 0));
+        a.end_time = Some(t(6, 0));
+        let (start, end) = param().range(&a);
+        assert_eq!(start, t(0, 30));
+        assert_eq!(end, t(6, 0));
+        assert!(!param().is_closed(&a, t(5, 0)));
+        assert!(param().is_closed(&a, t(6, 1)));
+    }
+
+    #[test]
+    fn test_program_mismatch_ignored() {
+        let mut s = spot("JA1ABC", "JA/TK-001", t(1, 0));
+        s.program = AwardProgram::POTA;
+        let log = correlate_alert(alert("JA/TK-001", t(1, 0)), &[s], &param(), t(1, 10));
+        assert_eq!(log.outcome, ActivationOutcome::Pending);
+    }
+}
@@ -1,21 +1,37 @@
 use aprs_message::AprsCallsign;
-use chrono::{Duration, TimeZone, Utc};
+use chrono::{DateTime, Duration, TimeZone, Utc};
 use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::OnceLock;
 
+use super::activation_zone::{classify_position, ZoneParam, ZoneState, CLIMBING_DISTANCE};
 use super::admin_periodic::AdminPeriodicServiceImpl;
+use super::eta::{estimate_eta, predict_eta, summit_altitude};
 use super::user_service::UserServiceImpl;
 
 use common::error::AppResult;
-use common::utils::calculate_distance;
+use common::metrics::{self, APRS_MESSAGES_SENT_TOTAL, APRS_STATE_TRANSITIONS_TOTAL};
+use common::utils::{calculate_distance, simplify_track};
 use domain::model::{
     activation::Spot,
-    aprslog::{AprsLog, AprsState, AprsTrack},
+    aprs::{parse_callsign, AprsObject, AprsObjectKind},
+    aprslog::{AprsLog, AprsLogDetail, AprsSegment, AprsState, AprsTrack},
     event::{FindActBuilder, FindAprs, FindRefBuilder},
+    sota::SotaReference,
 };
 
+/// 位置がこれ以上途切れたら別のアクティベーションとみなす（時間）
+pub const ACTIVATION_GAP_HOURS: i64 = 3;
+
+/// オブジェクト名・コメント中のサミットコード
+fn summit_code_regex() -> &'static Regex {
+    static SUMMIT_CODE: OnceLock<Regex> = OnceLock::new();
+    SUMMIT_CODE.get_or_init(|| {
+        Regex::new(r"\b[A-Z0-9]{1,4}/[A-Z]{2}-[0-9]{3}\b").expect("Invalid summit code regex")
+    })
+}
+
 /// キャッシュされた正規表現パターン
 fn get_cached_regex(pattern: &str) -> Option<&'static Regex> {
     static JA_PATTERN: OnceLock<Regex> = OnceLock::new();
@@ -31,8 +47,9 @@ fn get_cached_regex(pattern: &str) -> Option<&'static Regex> {
 }
 
 impl AdminPeriodicServiceImpl {
-    async fn last_three_spots_messasge(&self, pat: &str) -> AppResult<String> {
-        let after = Utc::now() - Duration::hours(3);
+    /// `now` までの3時間に出たスポットのうち、新しいもの3件
+    async fn last_three_spots_messasge(&self, pat: &str, now: DateTime<Utc>) -> AppResult<String> {
+        let after = now - Duration::hours(3);
         let query = FindActBuilder::default().sota().issued_after(after).build();
         let mut spots = self.act_repo.find_spots(&query).await?;
 
@@ -63,7 +80,7 @@ impl AdminPeriodicServiceImpl {
         }
 
         let mut spots: Vec<_> = latest.into_values().collect();
-        spots.sort_by(|a, b| b.spot_time.cmp(&a.spot_time));
+        spots.sort_by_key(|b| std::cmp::Reverse(b.spot_time));
         spots.truncate(3);
 
         let mut message = String::new();
@@ -85,12 +102,36 @@ impl AdminPeriodicServiceImpl {
         Ok(message)
     }
 
-    pub async fn process_message(&self, from: &AprsCallsign, message: String) -> AppResult<()> {
+    pub async fn process_message(
+        &self,
+        from: &AprsCallsign,
+        message: String,
+        now: DateTime<Utc>,
+    ) -> AppResult<()> {
         let message = message.to_uppercase();
         let commands: Vec<_> = message.split_ascii_whitespace().collect();
 
         if commands.is_empty() || commands.len() > 2 {
             self.aprs_repo.write_message(from, "?").await?;
+            metrics::inc_counter(APRS_MESSAGES_SENT_TOTAL, &[("kind", "reply")]);
+            return Ok(());
+        }
+
+        if commands[0] == "KEEP" {
+            let reply = self.archive_latest_track(from).await?;
+            self.aprs_repo.write_message(from, &reply).await?;
+            metrics::inc_counter(APRS_MESSAGES_SENT_TOTAL, &[("kind", "reply")]);
+            return Ok(());
+        }
+
+        if commands[0] == "ETA" {
+            let callsign = commands
+                .get(1)
+                .map(|c| parse_callsign(c).callsign)
+                .unwrap_or_else(|| from.callsign.clone());
+            let reply = self.eta_message(&callsign, now).await?;
+            self.aprs_repo.write_message(from, &reply).await?;
+            metrics::inc_counter(APRS_MESSAGES_SENT_TOTAL, &[("kind", "reply")]);
             return Ok(());
         }
 
@@ -100,13 +141,76 @@ impl AdminPeriodicServiceImpl {
             _ => r"^JA.*",
         };
 
-        let message = self.last_three_spots_messasge(pat).await?;
+        let message = self.last_three_spots_messasge(pat, now).await?;
 
         self.aprs_repo.write_message(from, &message).await?;
+        metrics::inc_counter(APRS_MESSAGES_SENT_TOTAL, &[("kind", "reply")]);
 
         Ok(())
     }
 
+    /// 到着予想の返信メッセージ
+    async fn eta_message(&self, callsign: &str, now: DateTime<Utc>) -> AppResult<String> {
+        let eta = predict_eta(
+            self.aprs_log_repo.as_ref(),
+            self.sota_repo.as_ref(),
+            callsign,
+            now,
+        )
+        .await?;
+        Ok(match eta {
+            Some(eta) if eta.arrived => format!(
+                "{} on {} since {}Z.",
+                callsign,
+                eta.summit,
+                eta.arrival.format("%H:%M")
+            ),
+            Some(eta) => format!(
+                "{} {} ETA {}Z {}m.",
+                callsign,
+                eta.summit,
+                eta.arrival.format("%H:%M"),
+                eta.distance
+            ),
+            None => format!("No ETA for {}.", callsign),
+        })
+    }
+
+    /// 直近のアクティベーションのトラックを保存し、返信メッセージを返す
+    ///
+    /// SSIDの異なる端末から送っても同じコールサインのトラックを対象にする。
+    async fn archive_latest_track(&self, from: &AprsCallsign) -> AppResult<String> {
+        let query = FindAprs {
+            callsign: Some(AprsCallsign {
+                callsign: from.callsign.clone(),
+                ssid: None,
+            }),
+            ..Default::default()
+        };
+        let aprslog = self.aprs_log_repo.find_aprs_log(&query).await?;
+        let latest = AprsSegment::split(aprslog, Duration::hours(ACTIVATION_GAP_HOURS))
+            .into_iter()
+            .max_by_key(|s| s.end_time());
+
+        let Some(segment) = latest else {
+            return Ok("No track found.".to_string());
+        };
+        let summit = segment.summit.clone().unwrap_or_default();
+        let points = segment.points.len();
+        let archive_id = self.aprs_log_repo.archive_track(segment).await?;
+        tracing::info!(
+            "APRS track archived {} {} ({} points) id={}",
+            from.callsign,
+            summit,
+            points,
+            archive_id
+        );
+        Ok(format!(
+            "Track saved: {} {}pts #{}",
+            summit, points, archive_id
+        ))
+    }
+
     async fn send_message(
         &self,
         from: &AprsCallsign,
@@ -122,6 +226,7 @@ impl AdminPeriodicServiceImpl {
         );
         if mesg_enabled {
             self.aprs_repo.write_message(from, message).await?;
+            metrics::inc_counter(APRS_MESSAGES_SENT_TOTAL, &[("kind", "arrival")]);
         }
         Ok(())
     }
@@ -131,6 +236,8 @@ impl AdminPeriodicServiceImpl {
         from: AprsCallsign,
         latitude: f64,
         longitude: f64,
+        altitude: Option<f64>,
+        now: DateTime<Utc>,
     ) -> AppResult<()> {
         let query = FindActBuilder::default()
             .sota()
@@ -149,7 +256,7 @@ impl AdminPeriodicServiceImpl {
 
         let dest = self.sota_repo.find_reference(&query).await?;
 
-        let time = Utc::now().naive_utc();
+        let time = now.naive_utc();
 
         if dest.is_empty() {
             /*
@@ -185,55 +292,69 @@ impl AdminPeriodicServiceImpl {
                 .as_ref()
                 .is_some_and(|s| s.contains(&from.callsign));
 
-        let (destlat, destlon) = (summit.latitude, summit.longitude);
-
         let query = FindAprs {
             callsign: Some(from.clone()),
             ..Default::default()
         };
         let aprslog = self.aprs_log_repo.find_aprs_log(&query).await?;
 
-        let distance = calculate_distance(latitude, longitude, destlat, destlon).floor();
+        let zone = self
+            .sota_repo
+            .find_activation_zone(&summit.summit_code)
+            .await?;
+        let param = ZoneParam {
+            radius: self.config.aprs_zone_radius.for_summit(&destination),
+            vertical_m: self.config.aprs_zone_vertical_m,
+        };
+        let (zone_state, distance) = classify_position(
+            &summit,
+            zone.as_ref(),
+            &param,
+            latitude,
+            longitude,
+            altitude,
+        );
 
-        let new_state = if distance > 1000.0 {
-            AprsState::Approaching { time, distance }
-        } else if distance > 300.0 {
-            AprsState::Climbing { time, distance }
-        } else if distance > 100.0 {
-            let message = format!(
-                "Approaching {}. {}m remaining.",
-                summit.summit_code, distance
-            );
-            AprsState::NearSummit {
-                time,
-                distance,
-                message,
+        let new_state = match zone_state {
+            ZoneState::Approaching => AprsState::Approaching { time, distance },
+            ZoneState::Climbing => AprsState::Climbing { time, distance },
+            ZoneState::NearSummit => {
+                let message = format!(
+                    "Approaching {}. {}m remaining.",
+                    summit.summit_code, distance
+                );
+                AprsState::NearSummit {
+                    time,
+                    distance,
+                    message,
+                }
             }
-        } else {
-            let message = if destination.starts_with("JA") {
-                format!(
-                    "Welcome to {}. {} {}m {}pts.\n{}\n{}",
-                    summit.summit_code,
-                    summit.summit_name,
-                    summit.alt_m,
-                    summit.points,
-                    summit.city.unwrap_or_default(),
-                    self.last_three_spots_messasge("^JA.*").await?
-                )
-            } else {
-                format!(
-                    "Welcome to {}. {} {}m {}pts.\n{}",
-                    summit.summit_code,
-                    summit.summit_name,
-                    summit.alt_m,
-                    summit.points,
-                    self.last_three_spots_messasge(".*").await?
-                )
-            };
-            AprsState::OnSummit {
-                time,
-                distance,
-                message,
+            ZoneState::OnSummit => {
+                let message = if destination.starts_with("JA") {
+                    format!(
+                        "Welcome to {}. {} {}m {}pts.\n{}\n{}",
+                        summit.summit_code,
+                        summit.summit_name,
+                        summit.alt_m,
+                        summit.points,
+                        summit.city.unwrap_or_default(),
+                        self.last_three_spots_messasge("^JA.*", now).await?
+                    )
+                } else {
+                    format!(
+                        "Welcome to {}. {} {}m {}pts.\n{}",
+                        summit.summit_code,
+                        summit.summit_name,
+                        summit.alt_m,
+                        summit.points,
+                        self.last_three_spots_messasge(".*", now).await?
+                    )
+                };
+                AprsState::OnSummit {
+                    time,
+                    distance,
+                    message,
+                }
             }
         };
 
@@ -278,62 +399,210 @@ impl AdminPeriodicServiceImpl {
             }
         };
 
+        let prev_state = aprslog.first().map(|l| l.state.name()).unwrap_or("none");
+        if prev_state != state.name() {
+            metrics::inc_counter(
+                APRS_STATE_TRANSITIONS_TOTAL,
+                &[("from", prev_state), ("to", state.name())],
+            );
+        }
+
         let log = AprsLog {
             callsign: from,
             destination: Some(destination),
             state,
             longitude,
             latitude,
+            altitude,
+            detail: AprsLogDetail::Position,
         };
 
         self.aprs_log_repo.insert_aprs_log(log).await?;
 
         Ok(())
     }
-}
 
-impl UserServiceImpl {
-    pub async fn generate_track(&self, aprslog: Vec<AprsLog>) -> AppResult<Vec<AprsTrack>> {
-        let mut track: HashMap<AprsCallsign, Vec<(f64, f64)>> = HashMap::new();
-        let mut lastlog: HashMap<AprsCallsign, AprsLog> = HashMap::new();
+    /// オブジェクト・アイテムの対象サミット
+    ///
+    /// 名前かコメントにサミットコードがあればそのサミット、なければ
+    /// オブジェクト位置から到着判定半径内の最寄りサミット。
+    async fn find_object_summit(&self, object: &AprsObject) -> AppResult<Option<SotaReference>> {
+        let text = format!("{} {}", object.name, object.comment).to_uppercase();
+        if let Some(code) = summit_code_regex().find(&text) {
+            let query = FindRefBuilder::default()
+                .sota()
+                .sota_code(code.as_str().to_string())
+                .build();
+            return Ok(self
+                .sota_repo
+                .find_reference(&query)
+                .await?
+                .into_iter()
+                .next());
+        }
 
-        for l in aprslog {
-            let callsign = l.callsign.clone();
+        let query = FindRefBuilder::default()
+            .center(object.longitude, object.latitude, CLIMBING_DISTANCE)
+            .build();
+        let summit = self
+            .sota_repo
+            .find_reference(&query)
+            .await?
+            .into_iter()
+            .next();
+        Ok(summit.filter(|s| {
+            let distance =
+                calculate_distance(object.latitude, object.longitude, s.latitude, s.longitude);
+            distance <= self.config.aprs_zone_radius.for_summit(&s.summit_code)
+        }))
+    }
+
+    /// サミットオブジェクト・アイテムをアクティベーションの告知として記録する
+    ///
+    /// オブジェクトがあれば OnSummit、削除されたら Descending とする。
+    pub async fn process_object(
+        &self,
+        from: AprsCallsign,
+        object: AprsObject,
+        now: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let Some(summit) = self.find_object_summit(&object).await? else {
+            tracing::debug!("APRS object {} is not a summit object", object.name);
+            return Ok(());
+        };
+
+        let time = now.naive_utc();
+        let distance = calculate_distance(
+            object.latitude,
+            object.longitude,
+            summit.latitude,
+            summit.longitude,
+        )
+        .floor();
+        let state = if object.alive {
+            AprsState::OnSummit {
+                time,
+                distance,
+                message: object.comment.clone(),
+            }
+        } else {
+            AprsState::Descending { time, distance }
+        };
+        tracing::info!(
+            "APRS summit object {} {} by {}: {}",
+            object.name,
+            summit.summit_code,
+            from.callsign,
+            state.name()
+        );
 
-            track
-                .entry(callsign.clone())
-                .or_default()
-                .push((l.latitude, l.longitude));
+        let detail = match object.kind {
+            AprsObjectKind::Object => AprsLogDetail::Object(object.name),
+            AprsObjectKind::Item => AprsLogDetail::Item(object.name),
+        };
+        let log = AprsLog {
+            callsign: from,
+            destination: Some(summit.summit_code),
+            state,
+            longitude: object.longitude,
+            latitude: object.latitude,
+            altitude: None,
+            detail,
+        };
+        self.aprs_log_repo.insert_aprs_log(log).await
+    }
+
+    /// 気象・テレメトリを同じコールサインの直近のトラックに付加する
+    ///
+    /// 送信元のSSIDに関わらず、直近のログと同じ端末・サミット・状態で記録する。
+    pub async fn attach_to_track(
+        &self,
+        from: AprsCallsign,
+        position: Option<(f64, f64)>,
+        detail: AprsLogDetail,
+        now: DateTime<Utc>,
+    ) -> AppResult<()> {
+        let query = FindAprs {
+            callsign: Some(AprsCallsign {
+                callsign: from.callsign.clone(),
+                ssid: None,
+            }),
+            after: Some(now - Duration::hours(ACTIVATION_GAP_HOURS)),
+            ..Default::default()
+        };
+        let latest = self
+            .aprs_log_repo
+            .find_aprs_log(&query)
+            .await?
+            .into_iter()
+            .max_by_key(|l| l.state.time());
+
+        let Some(latest) = latest.filter(|l| l.destination.is_some()) else {
+            tracing::debug!("APRS {} from {:?} has no track", detail.name(), from);
+            return Ok(());
+        };
+        let (latitude, longitude) = position.unwrap_or((latest.latitude, latest.longitude));
+        let log = AprsLog {
+            callsign: latest.callsign,
+            destination: latest.destination,
+            state: latest.state.with_time(now.naive_utc()),
+            longitude,
+            latitude,
+            altitude: latest.altitude,
+            detail,
+        };
+        self.aprs_log_repo.insert_aprs_log(log).await
+    }
+}
 
-            lastlog.entry(callsign).or_insert(l);
+impl UserServiceImpl {
+    pub async fn generate_track(
+        &self,
+        aprslog: Vec<AprsLog>,
+        tolerance: Option<f64>,
+    ) -> AppResult<Vec<AprsTrack>> {
+        // 新しい順のログを端末ごとにまとめる
+        let mut logs: HashMap<AprsCallsign, Vec<AprsLog>> = HashMap::new();
+        for l in aprslog {
+            logs.entry(l.callsign.clone()).or_default().push(l);
         }
 
         let mut result = Vec::new();
 
-        for callsign in track.keys() {
+        for (callsign, logs) in logs {
+            let Some(log) = logs.first() else {
+                continue;
+            };
+            let lastseen = Utc.from_utc_datetime(&log.state.time());
+
+            // スポットは現在時刻ではなく最後の位置の時刻を基準に探す
             let query = FindActBuilder::default()
                 .sota()
                 .operator(&callsign.callsign)
-                .issued_after(Utc::now() - Duration::hours(8))
+                .issued_after(lastseen - Duration::hours(8))
                 .build();
             let spot = self.act_repo.find_spots(&query).await?;
 
-            let Some(log) = lastlog.get(callsign) else {
-                continue;
-            };
-            let lastseen = Utc.from_utc_datetime(&log.state.time());
+            let weather = logs.iter().find_map(|l| match &l.detail {
+                AprsLogDetail::Weather(report) => Some(report.clone()),
+                _ => None,
+            });
+            let summit_alt = summit_altitude(self.sota_repo.as_ref(), &logs).await?;
+            let eta = estimate_eta(&logs, summit_alt).map(|e| e.arrival);
 
-            let Some(coords) = track.get(callsign) else {
-                continue;
-            };
-            let mut coordinates: Vec<_> = coords.to_vec();
+            let mut coordinates: Vec<_> = logs.iter().map(|l| (l.latitude, l.longitude)).collect();
             coordinates.reverse();
+            if let Some(tolerance) = tolerance {
+                coordinates = simplify_track(&coordinates, tolerance)
+                    .into_iter()
+                    .map(|i| coordinates[i])
+                    .collect();
+            }
 
-            let callsign_cloned = callsign.clone();
             let aprstrack = if let Some(spot) = spot.first() {
                 let reference = spot.reference.clone();
                 AprsTrack {
-                    callsign: callsign_cloned,
+                    callsign: callsign.clone(),
                     coordinates,
                     summit: Some(reference.clone()),
                     distance: Some(log.state.distance()),
@@ -343,10 +612,12 @@ impl UserServiceImpl {
                     spot_freq: Some(spot.frequency.clone()),
                     spot_mode: Some(spot.mode.clone()),
                     spot_comment: spot.comment.clone(),
+                    weather,
+                    eta,
                 }
             } else {
                 AprsTrack {
-                    callsign: callsign_cloned,
+                    callsign: callsign.clone(),
                     coordinates,
                     summit: log.destination.clone(),
                     distance: Some(log.state.distance()),
@@ -356,6 +627,8 @@ impl UserServiceImpl {
                     spot_freq: None,
                     spot_mode: None,
                     spot_comment: None,
+                    weather,
+                    eta,
                 }
             };
             result.push(aprstrack);
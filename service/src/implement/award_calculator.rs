@@ -33,6 +33,19 @@ pub fn judge_award_with_mode(
     period: &AwardPeriod,
     mode: JudgmentMode,
     log_type: LogType,
+) -> AwardResult {
+    judge_award_with_points(logs, period, mode, log_type, &|_, _| None)
+}
+
+/// In-memoryでアワード判定を行う（山岳の得点を交信日時点で解決）
+///
+/// `points` は山岳コードとUTC日付からその日の得点を返す。
+pub fn judge_award_with_points(
+    logs: Vec<SotaLogEntry>,
+    period: &AwardPeriod,
+    mode: JudgmentMode,
+    log_type: LogType,
+    points: &dyn Fn(&str, NaiveDate) -> Option<i32>,
 ) -> AwardResult {
     // 最初のログエントリからコールサインを取得
     let callsign = logs.first().map(|l| l.operator()).unwrap_or_default();
@@ -42,8 +55,13 @@ pub fn judge_award_with_mode(
     // アクティベータ: 山岳コード -> UTC日付 -> 交信した局のセット
     let mut activator_map: HashMap<String, BTreeMap<NaiveDate, HashSet<String>>> = HashMap::new();
 
+    // アクティベータのS2S: (相手の山岳コード, UTC日付)
+    let mut s2s_set: HashSet<(String, NaiveDate)> = HashSet::new();
+
     // チェイサー: 山岳コード -> アクティベータのセット
     let mut chaser_map: HashMap<String, HashSet<String>> = HashMap::new();
+    // チェイサー: 山岳コード -> 最初の交信日
+    let mut chase_first: HashMap<String, NaiveDate> = HashMap::new();
 
     for log in logs {
         // 日時をパース
@@ -72,6 +90,12 @@ pub fn judge_award_with_mode(
                     .entry(utc_date)
                     .or_default()
                     .insert(his_operator);
+
+                if let Some(his_summit_code) =
+                    log.his_summit_code.as_ref().filter(|_| log.is_chase())
+                {
+                    s2s_set.insert((his_summit_code.to_uppercase(), utc_date));
+                }
             }
         }
 
@@ -82,7 +106,12 @@ pub fn judge_award_with_mode(
             };
             let his_summit_code = his_summit_code.to_uppercase();
             let his_operator = log.his_operator().to_uppercase();
+            let utc_date = datetime.date_naive();
 
+            chase_first
+                .entry(his_summit_code.clone())
+                .and_modify(|d| *d = (*d).min(utc_date))
+                .or_insert(utc_date);
             chaser_map
                 .entry(his_summit_code)
                 .or_default()
@@ -94,17 +123,30 @@ pub fn judge_award_with_mode(
     let activator = if log_type == LogType::Activator {
         let mut summits: Vec<SummitActivationResult> = activator_map
             .into_iter()
-            .map(|(summit_code, date_map)| evaluate_summit_activation(&summit_code, date_map, mode))
+            .map(|(summit_code, date_map)| {
+                evaluate_summit_activation(&summit_code, date_map, mode, points)
+            })
             .collect();
 
         // ユニーク局数で降順ソート
-        summits.sort_by(|a, b| b.unique_stations.cmp(&a.unique_stations));
+        summits.sort_by_key(|b| std::cmp::Reverse(b.unique_stations));
 
         let qualified_summits = summits.iter().filter(|s| s.qualified).count() as u32;
+        let s2s_summits = s2s_set
+            .iter()
+            .map(|(code, _)| code)
+            .collect::<HashSet<_>>()
+            .len() as u32;
+        let s2s_points = s2s_set
+            .iter()
+            .filter_map(|(code, date)| points(code, *date))
+            .sum();
         Some(ActivatorResult {
             achieved: qualified_summits >= 10,
             qualified_summits,
             summits,
+            s2s_summits,
+            s2s_points,
         })
     } else {
         None
@@ -119,10 +161,14 @@ pub fn judge_award_with_mode(
                 if unique_activators >= 10 {
                     let mut activator_list: Vec<String> = activators.into_iter().collect();
                     activator_list.sort();
+                    let points = chase_first
+                        .get(&summit_code)
+                        .and_then(|date| points(&summit_code, *date));
                     Some(SummitChaseResult {
                         summit_code,
                         unique_activators,
                         activators: activator_list,
+                        points,
                     })
                 } else {
                     None
@@ -131,7 +177,7 @@ pub fn judge_award_with_mode(
             .collect();
 
         // ユニークアクティベータ数で降順ソート
-        qualified_chase_summits.sort_by(|a, b| b.unique_activators.cmp(&a.unique_activators));
+        qualified_chase_summits.sort_by_key(|b| std::cmp::Reverse(b.unique_activators));
 
         Some(ChaserResult {
             // チェイサー賞: 1つの山から10人以上のアクティベータと交信で達成
@@ -161,6 +207,7 @@ fn evaluate_summit_activation(
     summit_code: &str,
     date_map: BTreeMap<NaiveDate, HashSet<String>>,
     mode: JudgmentMode,
+    points: &dyn Fn(&str, NaiveDate) -> Option<i32>,
 ) -> SummitActivationResult {
     let dates: Vec<_> = date_map.keys().cloned().collect();
 
@@ -183,6 +230,7 @@ fn evaluate_summit_activation(
             summit_code: summit_code.to_string(),
             unique_stations: all_stations.len() as u32,
             qualified: false,
+            points: dates.first().and_then(|d| points(summit_code, *d)),
         };
     };
 
@@ -230,6 +278,7 @@ fn evaluate_summit_activation(
         summit_code: summit_code.to_string(),
         unique_stations,
         qualified,
+        points: points(summit_code, act_date),
     }
 }
 
@@ -705,4 +754,73 @@ mod tests {
 
         assert_eq!(result.mode, JudgmentMode::Strict);
     }
+
+    #[test]
+    fn test_points_resolved_as_of_qso_date() {
+        // JA/TK-002は8/1に4点から6点に変更、JA/TK-003は7/15で有効期限切れ
+        let points = |code: &str, date: NaiveDate| {
+            let changed = NaiveDate::from_ymd_opt(2025, 8, 1).unwrap();
+            let retired = NaiveDate::from_ymd_opt(2025, 7, 15).unwrap();
+            match code {
+                "JA/TK-001" => Some(2),
+                "JA/TK-002" if date < changed => Some(4),
+                "JA/TK-002" => Some(6),
+                "JA/TK-003" if date <= retired => Some(1),
+                _ => None,
+            }
+        };
+
+        let logs = vec![
+            make_log(
+                Some("JA/TK-001"),
+                "JH1AAA/P",
+                Some("JA/TK-002"),
+                "01/07/2025",
+            ),
+            make_log(
+                Some("JA/TK-001"),
+                "JH1AAA/P",
+                Some("JA/TK-002"),
+                "02/08/2025",
+            ),
+            make_log(
+                Some("JA/TK-001"),
+                "JH2BBB/P",
+                Some("ja/tk-003"),
+                "01/07/2025",
+            ),
+            make_log(Some("JA/TK-001"), "JH3CCC", None, "01/07/2025"),
+        ];
+        let result = judge_award_with_points(
+            logs,
+            &test_period(),
+            JudgmentMode::Strict,
+            LogType::Activator,
+            &points,
+        );
+        let activator = result.activator.unwrap();
+        assert_eq!(activator.summits[0].points, Some(2));
+        assert_eq!(activator.s2s_summits, 2);
+        assert_eq!(activator.s2s_points, 4 + 6 + 1);
+
+        let logs: Vec<_> = (0..10)
+            .map(|i| {
+                make_log(
+                    None,
+                    &format!("JH{}AAA/P", i),
+                    Some("JA/TK-002"),
+                    &format!("{:02}/07/2025", i + 1),
+                )
+            })
+            .collect();
+        let result = judge_award_with_points(
+            logs,
+            &test_period(),
+            JudgmentMode::Strict,
+            LogType::Chaser,
+            &points,
+        );
+        let chaser = result.chaser.unwrap();
+        assert_eq!(chaser.qualified_summits[0].points, Some(4));
+    }
 }
@@ -0,0 +1,303 @@
+//! サミット到着予想時刻
+//!
+//! 直近のAPRSログからサミットへの接近速度（水平距離）と、高度があれば登高速度を求め、
+//! 残りの距離・標高差から到着時刻を推定する。両方求まる場合は遅い方を採る。
+
+use chrono::{DateTime, TimeDelta, Utc};
+use std::collections::HashMap;
+
+use aprs_message::AprsCallsign;
+use common::error::AppResult;
+use domain::model::activation::{ActivationOutcome, AlertLog};
+use domain::model::aprslog::{AprsLog, AprsState};
+use domain::model::event::{FindAprs, FindRefBuilder};
+use domain::model::AwardProgram;
+use domain::repository::{aprs::AprsLogRepository, sota::SotaRepository};
+
+use super::aprs_service::ACTIVATION_GAP_HOURS;
+
+/// 速度を求める直近の期間（分）
+pub const ETA_WINDOW_MINUTES: i64 = 45;
+/// 速度を求めるのに必要な最短の期間（分）
+const ETA_MIN_SPAN_MINUTES: i64 = 3;
+/// これより先の到着予想は出さない（時間）
+const ETA_MAX_HOURS: i64 = 12;
+/// 接近しているとみなす最低速度（m/秒）
+const MIN_HORIZONTAL_RATE: f64 = 0.05;
+const MIN_VERTICAL_RATE: f64 = 0.01;
+
+/// 到着予想
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eta {
+    pub callsign: AprsCallsign,
+    pub summit: String,
+    /// サミットまでの残り距離（m）
+    pub distance: f64,
+    /// 到着予想時刻（到着済みなら到着時刻）
+    pub arrival: DateTime<Utc>,
+    pub arrived: bool,
+}
+
+/// 1人のアクティベータのログから最新の目的サミットへの到着時刻を推定する
+///
+/// 最新ログと同じ端末・同じ目的サミットのログだけを使う。下山中や
+/// 接近が止まっている場合は None。
+pub fn estimate_eta(logs: &[AprsLog], summit_alt: Option<f64>) -> Option<Eta> {
+    let latest = logs.iter().max_by_key(|l| l.state.time())?;
+    let summit = latest.destination.clone()?;
+    let last_time = latest.state.time();
+
+    let mut recent: Vec<&AprsLog> = logs
+        .iter()
+        .filter(|l| {
+            l.callsign == latest.callsign
+                && l.destination == latest.destination
+                && l.state.time() >= last_time - TimeDelta::minutes(ETA_WINDOW_MINUTES)
+        })
+        .collect();
+    recent.sort_by_key(|l| l.state.time());
+
+    let eta = |arrival: chrono::NaiveDateTime, arrived| Eta {
+        callsign: latest.callsign.clone(),
+        summit: summit.clone(),
+        distance: latest.state.distance(),
+        arrival: arrival.and_utc(),
+        arrived,
+    };
+
+    match latest.state {
+        AprsState::OnSummit { .. } => {
+            let arrival = recent
+                .iter()
+                .rev()
+                .take_while(|l| matches!(l.state, AprsState::OnSummit { .. }))
+                .last()
+                .map(|l| l.state.time())
+                .unwrap_or(last_time);
+            return Some(eta(arrival, true));
+        }
+        AprsState::Descending { .. } | AprsState::Travelling { .. } => return None,
+        _ => {}
+    }
+
+    let first = recent.first()?;
+    let span = last_time - first.state.time();
+    if span < TimeDelta::minutes(ETA_MIN_SPAN_MINUTES) {
+        return None;
+    }
+    let secs = span.num_seconds() as f64;
+
+    let distance = latest.state.distance();
+    let horizontal_rate = (first.state.distance() - distance) / secs;
+    let horizontal = (horizontal_rate > MIN_HORIZONTAL_RATE).then(|| distance / horizontal_rate);
+
+    let vertical = match (summit_alt, first.altitude, latest.altitude) {
+        (Some(summit_alt), Some(from), Some(to)) => {
+            let remaining = summit_alt - to;
+            let rate = (to - from) / secs;
+            if remaining <= 0.0 {
+                Some(0.0)
+            } else {
+                (rate > MIN_VERTICAL_RATE).then(|| remaining / rate)
+            }
+        }
+        _ => None,
+    };
+
+    let remaining = match (horizontal, vertical) {
+        (Some(h), Some(v)) => h.max(v),
+        (Some(t), None) | (None, Some(t)) => t,
+        (None, None) => return None,
+    };
+    if remaining > (ETA_MAX_HOURS * 3600) as f64 {
+        return None;
+    }
+    Some(eta(
+        last_time + TimeDelta::seconds(remaining.round() as i64),
+        false,
+    ))
+}
+
+/// ログをSSIDを除いたコールサインごとにまとめる
+pub fn group_by_operator(logs: Vec<AprsLog>) -> HashMap<String, Vec<AprsLog>> {
+    let mut result: HashMap<String, Vec<AprsLog>> = HashMap::new();
+    for l in logs {
+        result
+            .entry(l.callsign.callsign.clone())
+            .or_default()
+            .push(l);
+    }
+    result
+}
+
+/// サミット標高（高度つきのログがある場合のみ参照する）
+pub async fn summit_altitude(
+    sota_repo: &dyn SotaRepository,
+    logs: &[AprsLog],
+) -> AppResult<Option<f64>> {
+    let Some(latest) = logs.iter().max_by_key(|l| l.state.time()) else {
+        return Ok(None);
+    };
+    let Some(code) = latest.destination.clone() else {
+        return Ok(None);
+    };
+    if logs.iter().all(|l| l.altitude.is_none()) {
+        return Ok(None);
+    }
+    let query = FindRefBuilder::default().sota().sota_code(code).build();
+    let summit = sota_repo.find_reference(&query).await?;
+    Ok(summit.first().map(|s| s.alt_m as f64))
+}
+
+/// コールサイン（SSIDなし）の直近のログから到着時刻を推定する
+pub async fn predict_eta(
+    aprs_log_repo: &dyn AprsLogRepository,
+    sota_repo: &dyn SotaRepository,
+    callsign: &str,
+    now: DateTime<Utc>,
+) -> AppResult<Option<Eta>> {
+    let query = FindAprs {
+        callsign: Some(AprsCallsign {
+            callsign: callsign.to_uppercase(),
+            ssid: None,
+        }),
+        after: Some(now - TimeDelta::hours(ACTIVATION_GAP_HOURS)),
+        ..Default::default()
+    };
+    let logs = aprs_log_repo.find_aprs_log(&query).await?;
+    let summit_alt = summit_altitude(sota_repo, &logs).await?;
+    Ok(estimate_eta(&logs, summit_alt))
+}
+
+/// 未スポットのSOTAアラートに到着予想時刻（到着済みなら到着時刻）を付ける
+///
+/// アクティベータが別のサミットへ向かっている場合は付けない。
+pub async fn attach_alert_eta(
+    aprs_log_repo: &dyn AprsLogRepository,
+    sota_repo: &dyn SotaRepository,
+    logs: &mut [AlertLog],
+    now: DateTime<Utc>,
+) -> AppResult<()> {
+    let horizon = now + TimeDelta::hours(ACTIVATION_GAP_HOURS);
+    let is_target = |l: &AlertLog| {
+        l.outcome == ActivationOutcome::Pending
+            && l.alert.program == AwardProgram::SOTA
+            && l.alert.start_time <= horizon
+    };
+    if !logs.iter().any(is_target) {
+        return Ok(());
+    }
+
+    let query = FindAprs {
+        after: Some(now - TimeDelta::hours(ACTIVATION_GAP_HOURS)),
+        ..Default::default()
+    };
+    let by_operator = group_by_operator(aprs_log_repo.find_aprs_log(&query).await?);
+
+    let mut etas: HashMap<String, Option<Eta>> = HashMap::new();
+    for log in logs.iter_mut().filter(|l| is_target(l)) {
+        let operator = log.alert.operator.to_uppercase();
+        if !etas.contains_key(&operator) {
+            let eta = match by_operator.get(&operator) {
+                Some(aprslog) => {
+                    let summit_alt = summit_altitude(sota_repo, aprslog).await?;
+                    estimate_eta(aprslog, summit_alt)
+                }
+                None => None,
+            };
+            etas.insert(operator.clone(), eta);
+        }
+        if let Some(Some(eta)) = etas.get(&operator) {
+            if eta.summit.eq_ignore_ascii_case(&log.alert.reference) {
+                log.eta = Some(eta.arrival);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+    use domain::model::aprslog::AprsLogDetail;
+
+    fn t(h: u32, m: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 5, 3)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    }
+
+    fn log(time: NaiveDateTime, distance: f64, altitude: Option<f64>) -> AprsLog {
+        let state = if distance <= 50.0 {
+            AprsState::OnSummit {
+                time,
+                distance,
+                message: String::new(),
+            }
+        } else {
+            AprsState::Climbing { time, distance }
+        };
+        AprsLog {
+            callsign: AprsCallsign {
+                callsign: "JA1ABC".to_string(),
+                ssid: Some(7),
+            },
+            destination: Some("JA/TK-001".to_string()),
+            state,
+            longitude: 139.0,
+            latitude: 35.0,
+            altitude,
+            detail: AprsLogDetail::Position,
+        }
+    }
+
+    #[test]
+    fn test_eta_from_horizontal_rate() {
+        // 20分で600m接近、残り900m → 30分後
+        let logs = vec![
+            log(t(9, 20), 900.0, None),
+            log(t(9, 0), 1500.0, None),
+            log(t(9, 10), 1200.0, None),
+        ];
+        let eta = estimate_eta(&logs, Some(1500.0)).unwrap();
+        assert_eq!(eta.summit, "JA/TK-001");
+        assert_eq!(eta.arrival, t(9, 50).and_utc());
+        assert_eq!(eta.distance, 900.0);
+        assert!(!eta.arrived);
+    }
+
+    #[test]
+    fn test_eta_uses_slower_vertical_rate() {
+        // 水平では20分後だが、登高 5m/分で残り150m → 30分後
+        let logs = vec![
+            log(t(9, 0), 1000.0, Some(1250.0)),
+            log(t(9, 20), 500.0, Some(1350.0)),
+        ];
+        let eta = estimate_eta(&logs, Some(1500.0)).unwrap();
+        assert_eq!(eta.arrival, t(9, 50).and_utc());
+
+        // 標高が分からなければ水平のみ
+        let eta = estimate_eta(&logs, None).unwrap();
+        assert_eq!(eta.arrival, t(9, 40).and_utc());
+    }
+
+    #[test]
+    fn test_eta_arrived_and_stalled() {
+        let logs = vec![
+            log(t(9, 0), 400.0, None),
+            log(t(9, 10), 40.0, None),
+            log(t(9, 20), 30.0, None),
+        ];
+        let eta = estimate_eta(&logs, None).unwrap();
+        assert!(eta.arrived);
+        assert_eq!(eta.arrival, t(9, 10).and_utc());
+
+        // 離れている・期間が短い場合は予想しない
+        let logs = vec![log(t(9, 0), 800.0, None), log(t(9, 20), 900.0, None)];
+        assert!(estimate_eta(&logs, None).is_none());
+        let logs = vec![log(t(9, 0), 900.0, None), log(t(9, 1), 800.0, None)];
+        assert!(estimate_eta(&logs, None).is_none());
+    }
+}
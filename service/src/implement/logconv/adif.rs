@@ -3,6 +3,7 @@
 //! Parses ADIF 3.x format files into QSO records
 
 use super::types::{freq_to_band, mode_to_adif_mode, QsoRecord};
+use common::dxcc;
 use std::collections::HashMap;
 
 /// Parse a single ADIF record into a HashMap of fields
@@ -181,6 +182,8 @@ pub fn decode_adif(record: &str) -> Result<QsoRecord, String> {
         .cloned()
         .unwrap_or_default();
 
+    let dxcc = dxcc::lookup(&call);
+
     Ok(QsoRecord {
         has_error: false,
         error_message: String::new(),
@@ -217,6 +220,7 @@ pub fn decode_adif(record: &str) -> Result<QsoRecord, String> {
         qth: his_sig,
         remarks1: my_sig,
         remarks2: fields.get("COMMENT").cloned().unwrap_or_default(),
+        dxcc,
     })
 }
 
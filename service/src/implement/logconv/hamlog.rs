@@ -7,6 +7,7 @@ use super::types::{
     QsoRecord,
 };
 use chrono::{FixedOffset, Offset, TimeZone, Utc};
+use common::dxcc;
 use regex::Regex;
 
 /// Decode HAMLOG CSV row into QsoRecord
@@ -164,6 +165,7 @@ pub fn decode_hamlog(cols: &[String]) -> Result<QsoRecord, String> {
     record.qth = cols[11].clone();
     record.remarks1 = cols[12].clone();
     record.remarks2 = cols.get(13).cloned().unwrap_or_default();
+    record.dxcc = dxcc::lookup(&record.callsign);
 
     Ok(record)
 }
@@ -265,6 +267,7 @@ pub fn decode_hamlog_ios(cols: &[String]) -> Result<QsoRecord, String> {
     record.qsl = cols[14].clone();
     record.qsl_sent = cols[15].parse().unwrap_or(0);
     record.qsl_rcvd = cols[16].parse().unwrap_or(0);
+    record.dxcc = dxcc::lookup(&record.callsign);
 
     Ok(record)
 }
@@ -1,5 +1,6 @@
 //! Common types for log conversion
 
+use common::dxcc::DxccInfo;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -340,6 +341,9 @@ pub struct QsoRecord {
     pub qth: String,
     pub remarks1: String,
     pub remarks2: String,
+
+    /// DXCC entity and zones of the worked station (when a country file is loaded)
+    pub dxcc: Option<DxccInfo>,
 }
 
 /// Reference information extracted from remarks
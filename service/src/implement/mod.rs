@@ -1,11 +1,23 @@
+pub mod activation_zone;
+pub mod activity_chart;
 pub mod admin_periodic;
 pub mod admin_service;
+pub mod alert_outcome;
 pub mod aprs_service;
 pub mod award_calculator;
 pub mod award_pdf;
+pub mod eta;
 pub mod fle;
 pub mod logconv;
+pub mod needed;
 pub mod pota_log_service;
+pub mod relay_service;
+pub mod session_service;
+pub mod solar;
 pub mod sota_log_service;
+pub mod spot_normalize;
+pub mod summit_points;
 pub mod user_service;
+pub mod watch_notify;
+pub mod watch_service;
 pub mod wspr_service;
@@ -0,0 +1,103 @@
+//! スポット/アラートへの未交信フラグ付け
+//!
+//! SOTAは利用者のSOTAログのサミット交信（S2Sを含む）、POTAはハンターログのパークごとの交信数を使う。
+
+use std::collections::HashMap;
+
+use crate::implement::spot_normalize::{alert_channels, canonical_mode, sota_log_band};
+use common::error::AppResult;
+use domain::model::activation::{AlertLog, Spot, SpotLog};
+use domain::model::event::{FindAct, FindLogBuilder, FindRefBuilder};
+use domain::model::needed::WorkedHistory;
+use domain::model::AwardProgram;
+use domain::repository::{pota::PotaRepository, sota::SotaRepository};
+
+#[derive(Debug, Default)]
+pub struct NeededJudge {
+    sota: Option<WorkedHistory>,
+    pota: Option<WorkedHistory>,
+    /// パークごとのハンターログの交信数
+    qsos: HashMap<String, Option<i32>>,
+}
+
+impl NeededJudge {
+    /// event.user_idのSOTAログと、event.log_idのハンターログ（parksに挙げたパーク）を読み込む
+    pub async fn load(
+        sota_repo: &dyn SotaRepository,
+        pota_repo: &dyn PotaRepository,
+        event: &FindAct,
+        parks: impl IntoIterator<Item = String>,
+    ) -> AppResult<Self> {
+        let mut judge = Self::default();
+
+        if let Some(user_id) = &event.user_id {
+            let query = FindLogBuilder::default()
+                .summit_chase()
+                .user_id(user_id.clone())
+                .build();
+            let logs = sota_repo.find_log(&query).await?;
+            // SOTAログのバンド表記とモードをスポットと同じ基準にそろえる
+            let chases = logs.into_iter().filter_map(|log| {
+                let channel = (
+                    sota_log_band(&log.frequency),
+                    canonical_mode(&log.mode).unwrap_or_default(),
+                );
+                log.his_summit_code.map(|summit| (summit, channel))
+            });
+            judge.sota = Some(WorkedHistory::from_channels(chases));
+        }
+
+        if let Some(log_id) = event.log_id {
+            for code in parks {
+                if judge.qsos.contains_key(&code) {
+                    continue;
+                }
+                let query = FindRefBuilder::default()
+                    .pota()
+                    .pota_code(code.clone())
+                    .log_id(log_id)
+                    .build();
+                let qsos = pota_repo
+                    .find_reference(&query)
+                    .await?
+                    .first()
+                    .and_then(|p| p.qsos);
+                judge.qsos.insert(code, qsos);
+            }
+            let worked = judge
+                .qsos
+                .iter()
+                .filter(|(_, qsos)| qsos.unwrap_or(0) > 0)
+                .map(|(code, _)| code.clone());
+            judge.pota = Some(WorkedHistory::from_references(worked));
+        }
+
+        Ok(judge)
+    }
+
+    fn history(&self, program: &AwardProgram) -> Option<&WorkedHistory> {
+        match program {
+            AwardProgram::SOTA => self.sota.as_ref(),
+            AwardProgram::POTA => self.pota.as_ref(),
+            AwardProgram::WWFF => None,
+        }
+    }
+
+    pub fn spot_log(&self, spot: Spot) -> SpotLog {
+        let mut log = SpotLog::new(spot, None);
+        if log.spot.program == AwardProgram::POTA {
+            log.qsos = self.qsos.get(&log.spot.reference).copied().flatten();
+        }
+        log.needed = self.history(&log.spot.program).map(|h| h.spot(&log.spot));
+        log
+    }
+
+    /// アラートは予定周波数のいずれかが未交信なら立てる
+    pub fn attach(&self, log: &mut AlertLog) {
+        let alert = &log.alert;
+        let khz = alert.program != AwardProgram::SOTA;
+        log.needed = self
+            .history(&alert.program)
+            .map(|h| h.needed(&alert.reference, &alert_channels(&alert.frequencies, khz)));
+    }
+}
@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shaku::Component;
+use std::sync::Arc;
+
+use crate::implement::spot_normalize::normalize_spot;
+use crate::implement::watch_notify::{alert_deliveries, enqueue_deliveries, spot_deliveries};
+use crate::services::RelayService;
+use common::error::{AppError, AppResult};
+use common::utils::call_to_operator;
+use domain::model::activation::{Alert, Spot};
+use domain::model::event::FindRefBuilder;
+use domain::model::id::UserId;
+use domain::model::relay::{PostKind, RelayPost};
+use domain::model::watch::{Watchlist, WebhookDelivery};
+use domain::model::AwardProgram;
+use domain::repository::{
+    activation::ActivationRepositry,
+    pota::PotaRepository,
+    relay::{RelayPostRepository, UpstreamRelay},
+    sota::SotaRepository,
+    watch::WatchRepository,
+};
+
+/// アラートの開始時刻として受け付ける過去の範囲（時間）
+const ALERT_PAST_HOURS: i64 = 24;
+
+#[derive(Component)]
+#[shaku(interface = RelayService)]
+pub struct RelayServiceImpl {
+    #[shaku(inject)]
+    act_repo: Arc<dyn ActivationRepositry>,
+    #[shaku(inject)]
+    sota_repo: Arc<dyn SotaRepository>,
+    #[shaku(inject)]
+    pota_repo: Arc<dyn PotaRepository>,
+    #[shaku(inject)]
+    relay_repo: Arc<dyn RelayPostRepository>,
+    #[shaku(inject)]
+    upstream: Arc<dyn UpstreamRelay>,
+    #[shaku(inject)]
+    watch_repo: Arc<dyn WatchRepository>,
+}
+
+/// 登録済みリファレンスの名称と所在地
+struct ReferenceInfo {
+    code: String,
+    detail: String,
+    location: String,
+}
+
+fn required(value: &str, name: &str) -> AppResult<()> {
+    if value.trim().is_empty() {
+        return Err(AppError::UnprocessableEntity(format!(
+            "{}を指定してください",
+            name
+        )));
+    }
+    Ok(())
+}
+
+fn validate_spot(spot: &Spot) -> AppResult<()> {
+    required(&spot.activator, "activator")?;
+    required(&spot.mode, "mode")?;
+    if spot.frequency_hz.is_none() {
+        return Err(AppError::UnprocessableEntity(format!(
+            "frequencyを読めません: {}",
+            spot.frequency
+        )));
+    }
+    Ok(())
+}
+
+fn validate_alert(alert: &Alert, now: DateTime<Utc>) -> AppResult<()> {
+    required(&alert.activator, "activator")?;
+    required(&alert.frequencies, "frequencies")?;
+    if alert.start_time < now - chrono::Duration::hours(ALERT_PAST_HOURS) {
+        return Err(AppError::UnprocessableEntity(
+            "startTimeが古すぎます".to_string(),
+        ));
+    }
+    if alert.end_time.is_some_and(|end| end < alert.start_time) {
+        return Err(AppError::UnprocessableEntity(
+            "endTimeはstartTimeより後にしてください".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl RelayServiceImpl {
+    /// 参照先がローカルのリファレンス一覧にあるか確かめる
+    async fn resolve_reference(
+        &self,
+        program: &AwardProgram,
+        reference: &str,
+    ) -> AppResult<ReferenceInfo> {
+        let code = reference.trim().to_uppercase();
+        required(&code, "reference")?;
+        let info = match program {
+            AwardProgram::SOTA => {
+                let query = FindRefBuilder::default()
+                    .sota()
+                    .sota_code(code.clone())
+                    .build();
+                self.sota_repo
+                    .find_reference(&query)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .map(|s| ReferenceInfo {
+                        location: s.summit_code.split('/').next().unwrap_or("").to_string(),
+                        detail: s.summit_name,
+                        code: s.summit_code,
+                    })
+            }
+            AwardProgram::POTA => {
+                let query = FindRefBuilder::default()
+                    .pota()
+                    .pota_code(code.clone())
+                    .build();
+                self.pota_repo
+                    .find_reference(&query)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .map(|p| ReferenceInfo {
+                        location: p.park_locid,
+                        detail: p.park_name,
+                        code: p.pota_code,
+                    })
+            }
+            AwardProgram::WWFF => None,
+        };
+        info.ok_or_else(|| {
+            AppError::UnprocessableEntity(format!("登録されていないリファレンスです: {}", code))
+        })
+    }
+
+    /// 投稿を記録してローカルIDを割り当てる
+    async fn record_post(
+        &self,
+        user_id: UserId,
+        kind: PostKind,
+        program: AwardProgram,
+        reference: &str,
+        activator: &str,
+        now: DateTime<Utc>,
+    ) -> AppResult<RelayPost> {
+        let mut post = RelayPost::new(
+            user_id,
+            kind,
+            program,
+            reference.to_string(),
+            activator.to_string(),
+            now,
+        );
+        post.post_id = self.relay_repo.insert_post(post.clone()).await?;
+        post.local_id = RelayPost::local_id_for(post.post_id);
+        Ok(post)
+    }
+
+    async fn enabled_watches(&self) -> Vec<Watchlist> {
+        self.watch_repo
+            .find_enabled_watches()
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("find watchlists failed: {:?}", e);
+                vec![]
+            })
+    }
+
+    /// ウォッチリストの配信を登録する（送信は次の定期更新で行う）
+    async fn enqueue_watchers(&self, deliveries: Vec<WebhookDelivery>) {
+        if let Err(e) = enqueue_deliveries(self.watch_repo.as_ref(), deliveries).await {
+            tracing::warn!("enqueue webhook deliveries failed: {:?}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl RelayService for RelayServiceImpl {
+    async fn post_spot(&self, user_id: UserId, mut spot: Spot) -> AppResult<RelayPost> {
+        let now = Utc::now();
+        let info = self
+            .resolve_reference(&spot.program, &spot.reference)
+            .await?;
+        spot.reference = info.code;
+        spot.reference_detail = info.detail;
+        spot.activator = spot.activator.trim().to_uppercase();
+        spot.operator = call_to_operator(&spot.activator);
+        if spot.spotter.trim().is_empty() {
+            spot.spotter = spot.activator.clone();
+        }
+        spot.spotter = spot.spotter.trim().to_uppercase();
+        spot.spot_time = now;
+        normalize_spot(&mut spot);
+        validate_spot(&spot)?;
+
+        let mut post = self
+            .record_post(
+                user_id,
+                PostKind::Spot,
+                spot.program.clone(),
+                &spot.reference,
+                &spot.activator,
+                now,
+            )
+            .await?;
+        spot.spot_id = post.local_id;
+
+        let watches = self.enabled_watches().await;
+        self.act_repo.update_spots(vec![spot.clone()]).await?;
+        self.enqueue_watchers(spot_deliveries(&watches, std::slice::from_ref(&spot), now))
+            .await;
+
+        let result = self
+            .upstream
+            .relay_spot(&spot)
+            .await
+            .map_err(|e| e.to_string());
+        post.record_outcome(result, Utc::now());
+        self.relay_repo.update_post(post.clone()).await?;
+        Ok(post)
+    }
+
+    async fn post_alert(&self, user_id: UserId, mut alert: Alert) -> AppResult<RelayPost> {
+        let now = Utc::now();
+        let info = self
+            .resolve_reference(&alert.program, &alert.reference)
+            .await?;
+        alert.reference = info.code;
+        alert.reference_detail = info.detail;
+        alert.location = info.location;
+        alert.activator = alert.activator.trim().to_uppercase();
+        alert.operator = call_to_operator(&alert.activator);
+        validate_alert(&alert, now)?;
+
+        let mut post = self
+            .record_post(
+                user_id,
+                PostKind::Alert,
+                alert.program.clone(),
+                &alert.reference,
+                &alert.activator,
+                now,
+            )
+            .await?;
+        alert.alert_id = post.local_id;
+
+        let watches = self.enabled_watches().await;
+        self.act_repo.update_alerts(vec![alert.clone()]).await?;
+        self.enqueue_watchers(alert_deliveries(
+            &watches,
+            std::slice::from_ref(&alert),
+            now,
+        ))
+        .await;
+
+        let result = self
+            .upstream
+            .relay_alert(&alert)
+            .await
+            .map_err(|e| e.to_string());
+        post.record_outcome(result, Utc::now());
+        self.relay_repo.update_post(post.clone()).await?;
+        Ok(post)
+    }
+
+    async fn find_posts(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RelayPost>> {
+        self.relay_repo.find_posts(&user_id, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use domain::model::activation::{make_test_alert, make_test_spot};
+
+    fn spot(frequency: &str, mode: &str) -> Spot {
+        let mut s = Spot {
+            spot_id: 0,
+            frequency: frequency.to_string(),
+            mode: mode.to_string(),
+            spotter: "JA1ABC".to_string(),
+            ..make_test_spot("JA1ABC", "JA/KN-006")
+        };
+        normalize_spot(&mut s);
+        s
+    }
+
+    fn alert(start_time: DateTime<Utc>, end_time: Option<DateTime<Utc>>) -> Alert {
+        Alert {
+            program: AwardProgram::POTA,
+            alert_id: 0,
+            start_time,
+            end_time,
+            frequencies: "7.032 CW".to_string(),
+            ..make_test_alert("JA1ABC", "JA-0001")
+        }
+    }
+
+    #[test]
+    fn test_validate_spot() {
+        assert!(validate_spot(&spot("14.062", "CW")).is_ok());
+        assert!(validate_spot(&spot("abc", "CW")).is_err());
+        assert!(validate_spot(&spot("14.062", " ")).is_err());
+    }
+
+    #[test]
+    fn test_validate_alert() {
+        let now = Utc::now();
+        assert!(validate_alert(&alert(now + Duration::hours(2), None), now).is_ok());
+        assert!(validate_alert(&alert(now - Duration::hours(48), None), now).is_err());
+        assert!(validate_alert(&alert(now, Some(now - Duration::hours(1))), now).is_err());
+    }
+}
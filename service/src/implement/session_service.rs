@@ -0,0 +1,584 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use shaku::Component;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::implement::fle::{generate_fle_output, FleCompileResult, FleQsoRecord};
+use crate::implement::logconv::{
+    band_to_freq, convert_to_adif, convert_to_sota_activator, freq_to_band, get_ref,
+    mode_to_adif_mode, mode_to_sota_mode, ConversionOptions, QsoRecord,
+};
+use crate::model::session::{ClientQso, QsoPostReport};
+use crate::services::SessionService;
+use common::error::{AppError, AppResult};
+use common::utils::call_to_operator;
+use domain::model::id::UserId;
+use domain::model::session::{
+    ActivationSession, Qualification, SessionFile, SessionQso, SessionState, SessionStatus,
+};
+use domain::model::AwardProgram;
+use domain::repository::session::SessionRepository;
+
+/// 1回に登録できるQSOの件数
+const QSO_POST_MAX: usize = 500;
+
+#[derive(Component)]
+#[shaku(interface = SessionService)]
+pub struct SessionServiceImpl {
+    #[shaku(inject)]
+    session_repo: Arc<dyn SessionRepository>,
+}
+
+fn validate_session(session: &ActivationSession) -> AppResult<()> {
+    if session.callsign.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "callsignを指定してください".to_string(),
+        ));
+    }
+    let refs = get_ref(&session.reference);
+    let valid = match session.program {
+        AwardProgram::SOTA => refs.sota == session.reference,
+        AwardProgram::POTA => refs.pota == vec![session.reference.clone()],
+        AwardProgram::WWFF => false,
+    };
+    if !valid {
+        return Err(AppError::UnprocessableEntity(format!(
+            "リファレンスが正しくありません: {}",
+            session.reference
+        )));
+    }
+    Ok(())
+}
+
+/// QSOの交信日時（UTC）
+fn qso_time(record: &FleQsoRecord) -> AppResult<DateTime<Utc>> {
+    NaiveDate::from_ymd_opt(record.year as i32, record.month as u32, record.day as u32)
+        .and_then(|d| d.and_hms_opt(record.hour as u32, record.min as u32, 0))
+        .map(|t| t.and_utc())
+        .ok_or_else(|| {
+            AppError::UnprocessableEntity(format!(
+                "交信日時が正しくありません: {}",
+                record.callsign
+            ))
+        })
+}
+
+/// QSOのバンド（波長）。バンドがなければ周波数から求める
+fn qso_band(record: &FleQsoRecord) -> Option<String> {
+    if !record.band.is_empty() {
+        return Some(record.band.to_lowercase());
+    }
+    freq_to_band(&record.freq)
+        .ok()
+        .map(|(_, _, wlen)| wlen.to_lowercase())
+}
+
+/// 自局の情報が空ならセッションの値で埋める
+fn fill_record(session: &ActivationSession, record: &mut FleQsoRecord) {
+    if record.mycall.is_empty() {
+        record.mycall = session.callsign.clone();
+    }
+    if record.operator.is_empty() {
+        record.operator = session.operator.clone();
+    }
+    match session.program {
+        AwardProgram::SOTA if record.mysota.is_empty() => {
+            record.mysota = session.reference.clone();
+        }
+        AwardProgram::POTA if record.mypota.is_empty() => {
+            record.mypota = vec![session.reference.clone()];
+        }
+        _ => {}
+    }
+}
+
+fn to_session_qso(
+    session: &ActivationSession,
+    qso: ClientQso,
+    now: DateTime<Utc>,
+) -> AppResult<SessionQso> {
+    let ClientQso {
+        client_id,
+        mut record,
+    } = qso;
+    let client_id = client_id.trim().to_string();
+    if client_id.is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "clientIdを指定してください".to_string(),
+        ));
+    }
+    record.callsign = record.callsign.trim().to_uppercase();
+    if record.callsign.is_empty() {
+        return Err(AppError::UnprocessableEntity(format!(
+            "コールサインがありません: {}",
+            client_id
+        )));
+    }
+    let band = qso_band(&record).ok_or_else(|| {
+        AppError::UnprocessableEntity(format!("バンドまたは周波数がありません: {}", client_id))
+    })?;
+    let qso_time = qso_time(&record)?;
+    fill_record(session, &mut record);
+    let json =
+        serde_json::to_string(&record).map_err(|e| AppError::UnprocessableEntity(e.to_string()))?;
+    Ok(SessionQso {
+        session_id: session.session_id,
+        client_id,
+        callsign: record.callsign,
+        band,
+        mode: record.mode.to_uppercase(),
+        qso_time,
+        record: json,
+        created_at: now,
+    })
+}
+
+fn parse_record(qso: &SessionQso) -> AppResult<FleQsoRecord> {
+    serde_json::from_str(&qso.record).map_err(|e| {
+        AppError::UnprocessableEntity(format!("QSOを読めません: {}: {}", qso.client_id, e))
+    })
+}
+
+/// FLEのQSOをログ変換用のQSOレコードにする（相手局のリファレンスは備考1に入れる）
+fn to_qso_record(record: &FleQsoRecord) -> QsoRecord {
+    let mut qso = QsoRecord {
+        callsign: record.callsign.clone(),
+        year: record.year as i32,
+        month: record.month as u32,
+        day: record.day as u32,
+        hour: record.hour as u32,
+        minute: record.min as u32,
+        timezone: "UTC".to_string(),
+        rst_sent: record.rst_sent.clone(),
+        rst_rcvd: record.rst_rcvd.clone(),
+        freq: record.freq.clone(),
+        mode_sota: mode_to_sota_mode(&record.mode).to_string(),
+        remarks2: record.qsormks.clone(),
+        ..Default::default()
+    };
+    (qso.mode, qso.sub_mode) = mode_to_adif_mode(&record.mode);
+
+    match freq_to_band(&record.freq) {
+        Ok((band, band_sota, wlen)) if !record.freq.is_empty() => {
+            qso.band = band.to_string();
+            qso.band_sota = band_sota.to_string();
+            qso.band_wlen = wlen.to_string();
+        }
+        _ => match (
+            band_to_freq(&record.band, false),
+            band_to_freq(&record.band, true),
+        ) {
+            (Some(band), Some(band_sota)) => {
+                qso.band = band.to_string();
+                qso.band_sota = band_sota.to_string();
+                qso.band_wlen = record.band.to_lowercase();
+            }
+            _ => {
+                qso.band_error = record.band.clone();
+                qso.error_message = format!("Unknown band: {}", record.band);
+            }
+        },
+    }
+
+    let mut his_refs = Vec::new();
+    if !record.hissota.is_empty() {
+        his_refs.push(record.hissota.clone());
+    }
+    his_refs.extend(record.hispota.iter().cloned());
+    if !record.hiswwff.is_empty() {
+        his_refs.push(record.hiswwff.clone());
+    }
+    qso.remarks1 = his_refs.join(" ");
+    qso
+}
+
+fn compile_result(session: &ActivationSession, records: Vec<FleQsoRecord>) -> FleCompileResult {
+    let is_sota = session.program == AwardProgram::SOTA;
+    let is_pota = session.program == AwardProgram::POTA;
+    FleCompileResult {
+        log_type: if is_sota { "SOTA" } else { "WWFF" }.to_string(),
+        mycall: session.callsign.clone(),
+        operator: session.operator.clone(),
+        mysota: if is_sota {
+            session.reference.clone()
+        } else {
+            String::new()
+        },
+        mypota: if is_pota {
+            vec![session.reference.clone()]
+        } else {
+            Vec::new()
+        },
+        records,
+        has_sota: is_sota,
+        has_pota: is_pota,
+        ..Default::default()
+    }
+}
+
+/// セッションのQSOからログファイルを作る
+///
+/// FLEの一括出力（ZIP）と、SOTAはアクティベーターCSV、POTAはパークごとのADIFを作る。
+fn build_log_files(
+    session: &ActivationSession,
+    qsos: &[SessionQso],
+    now: DateTime<Utc>,
+) -> AppResult<Vec<SessionFile>> {
+    let mut records = qsos
+        .iter()
+        .map(parse_record)
+        .collect::<AppResult<Vec<_>>>()?;
+    for r in records.iter_mut() {
+        fill_record(session, r);
+    }
+    let Some(first) = records.first() else {
+        return Err(AppError::UnprocessableEntity(
+            "QSOが登録されていません".to_string(),
+        ));
+    };
+    let log_name = format!(
+        "{:04}{:02}{:02}@{}",
+        first.year,
+        first.month,
+        first.day,
+        session.reference.replace('/', "-")
+    );
+
+    let qso_records: Vec<QsoRecord> = records.iter().map(to_qso_record).collect();
+    let options = ConversionOptions {
+        my_qth: "user_defined".to_string(),
+        his_qth: "rmks1".to_string(),
+        summit: session.reference.clone(),
+        park: vec![session.reference.clone()],
+        sota_activator: session.callsign.clone(),
+        pota_activator: session.callsign.clone(),
+        pota_operator: session.operator.clone(),
+        ..Default::default()
+    };
+    let converted = match session.program {
+        AwardProgram::SOTA => convert_to_sota_activator(&qso_records, &session.callsign, &options),
+        _ => convert_to_adif(&qso_records, &options).files,
+    };
+
+    let zip = generate_fle_output(&compile_result(session, records))
+        .map_err(AppError::UnprocessableEntity)?;
+
+    let mut files = vec![SessionFile {
+        session_id: session.session_id,
+        filename: format!("fle-{}.zip", log_name),
+        content: zip,
+        created_at: now,
+    }];
+    files.extend(
+        converted
+            .into_iter()
+            .map(|(filename, content)| SessionFile {
+                session_id: session.session_id,
+                filename,
+                content: content.into_bytes(),
+                created_at: now,
+            }),
+    );
+    Ok(files)
+}
+
+impl SessionServiceImpl {
+    async fn find_own_session(
+        &self,
+        user_id: &UserId,
+        session_id: i64,
+    ) -> AppResult<ActivationSession> {
+        self.session_repo
+            .find_session(session_id)
+            .await?
+            .filter(|s| &s.user_id == user_id)
+            .ok_or_else(|| AppError::EntityNotFound(format!("session {}", session_id)))
+    }
+
+    async fn state(&self, session: ActivationSession) -> AppResult<SessionState> {
+        let qsos = self.session_repo.find_qsos(session.session_id).await?;
+        let files = self
+            .session_repo
+            .find_file_names(session.session_id)
+            .await?;
+        Ok(SessionState {
+            qualification: Qualification::evaluate(&session.program, &qsos),
+            qsos: qsos.len(),
+            session,
+            files,
+        })
+    }
+}
+
+#[async_trait]
+impl SessionService for SessionServiceImpl {
+    async fn open_session(&self, mut session: ActivationSession) -> AppResult<SessionState> {
+        session.reference = session.reference.trim().to_uppercase();
+        session.callsign = session.callsign.trim().to_uppercase();
+        session.operator = session.operator.trim().to_uppercase();
+        if session.operator.is_empty() {
+            session.operator = call_to_operator(&session.callsign);
+        }
+        validate_session(&session)?;
+        session.status = SessionStatus::Open;
+        session.opened_at = Utc::now();
+        session.closed_at = None;
+        session.qualified_at = None;
+        session.session_id = self.session_repo.create_session(session.clone()).await?;
+        self.state(session).await
+    }
+
+    async fn find_sessions(&self, user_id: UserId) -> AppResult<Vec<SessionState>> {
+        let sessions = self.session_repo.find_sessions(&user_id).await?;
+        let mut states = Vec::with_capacity(sessions.len());
+        for s in sessions {
+            states.push(self.state(s).await?);
+        }
+        Ok(states)
+    }
+
+    async fn find_session(&self, user_id: UserId, session_id: i64) -> AppResult<SessionState> {
+        let session = self.find_own_session(&user_id, session_id).await?;
+        self.state(session).await
+    }
+
+    async fn add_qsos(
+        &self,
+        user_id: UserId,
+        session_id: i64,
+        qsos: Vec<ClientQso>,
+    ) -> AppResult<QsoPostReport> {
+        if qsos.len() > QSO_POST_MAX {
+            return Err(AppError::UnprocessableEntity(format!(
+                "1回に登録できるQSOは{}件までです",
+                QSO_POST_MAX
+            )));
+        }
+        let mut session = self.find_own_session(&user_id, session_id).await?;
+        let now = Utc::now();
+        let qsos = qsos
+            .into_iter()
+            .map(|q| to_session_qso(&session, q, now))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        // 終了後は再送（登録済みのQSO）だけを受け付ける
+        if !session.is_open() {
+            let stored: HashSet<String> = self
+                .session_repo
+                .find_qsos(session_id)
+                .await?
+                .into_iter()
+                .map(|q| q.client_id)
+                .collect();
+            if qsos.iter().any(|q| !stored.contains(&q.client_id)) {
+                return Err(AppError::UnprocessableEntity(format!(
+                    "session {} は終了しています",
+                    session_id
+                )));
+            }
+            let duplicates = qsos.len();
+            return Ok(QsoPostReport {
+                state: self.state(session).await?,
+                inserted: 0,
+                duplicates,
+                just_qualified: false,
+            });
+        }
+
+        let mut inserted = 0;
+        let mut duplicates = 0;
+        for q in qsos {
+            if self.session_repo.insert_qso(q).await? {
+                inserted += 1;
+            } else {
+                duplicates += 1;
+            }
+        }
+
+        let mut just_qualified = false;
+        let qsos = self.session_repo.find_qsos(session_id).await?;
+        if session.qualified_at.is_none()
+            && Qualification::evaluate(&session.program, &qsos).qualified
+        {
+            session.qualified_at = Some(now);
+            self.session_repo.update_session(session.clone()).await?;
+            just_qualified = true;
+        }
+        Ok(QsoPostReport {
+            state: self.state(session).await?,
+            inserted,
+            duplicates,
+            just_qualified,
+        })
+    }
+
+    async fn find_qsos(&self, user_id: UserId, session_id: i64) -> AppResult<Vec<SessionQso>> {
+        self.find_own_session(&user_id, session_id).await?;
+        self.session_repo.find_qsos(session_id).await
+    }
+
+    async fn close_session(&self, user_id: UserId, session_id: i64) -> AppResult<SessionState> {
+        let mut session = self.find_own_session(&user_id, session_id).await?;
+        let now = Utc::now();
+        let qsos = self.session_repo.find_qsos(session_id).await?;
+        let files = build_log_files(&session, &qsos, now)?;
+        self.session_repo.replace_files(session_id, files).await?;
+        if session.is_open() {
+            session.status = SessionStatus::Closed;
+            session.closed_at = Some(now);
+            self.session_repo.update_session(session.clone()).await?;
+        }
+        self.state(session).await
+    }
+
+    async fn find_file(
+        &self,
+        user_id: UserId,
+        session_id: i64,
+        filename: String,
+    ) -> AppResult<SessionFile> {
+        self.find_own_session(&user_id, session_id).await?;
+        self.session_repo
+            .find_file(session_id, &filename)
+            .await?
+            .ok_or_else(|| AppError::EntityNotFound(format!("file {}", filename)))
+    }
+
+    async fn delete_session(&self, user_id: UserId, session_id: i64) -> AppResult<()> {
+        self.find_own_session(&user_id, session_id).await?;
+        self.session_repo.delete_session(session_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(program: AwardProgram, reference: &str) -> ActivationSession {
+        ActivationSession {
+            session_id: 1,
+            user_id: UserId::from("user".to_string()),
+            program,
+            reference: reference.to_string(),
+            callsign: "JA1ABC/P".to_string(),
+            operator: "JA1ABC".to_string(),
+            status: SessionStatus::Open,
+            opened_at: Utc::now(),
+            closed_at: None,
+            qualified_at: None,
+        }
+    }
+
+    fn record(callsign: &str, band: &str, freq: &str, min: u8) -> FleQsoRecord {
+        FleQsoRecord {
+            mycall: String::new(),
+            operator: String::new(),
+            year: 2026,
+            month: 10,
+            day: 18,
+            hour: 1,
+            min,
+            callsign: callsign.to_string(),
+            band: band.to_string(),
+            freq: freq.to_string(),
+            mode: "CW".to_string(),
+            rigset: 0,
+            rst_sent: "599".to_string(),
+            rst_rcvd: "599".to_string(),
+            his_num: String::new(),
+            my_num: String::new(),
+            mysota: String::new(),
+            hissota: String::new(),
+            mywwff: String::new(),
+            hiswwff: String::new(),
+            mypota: Vec::new(),
+            hispota: Vec::new(),
+            qsomsg: String::new(),
+            qsormks: String::new(),
+            qslmsg: String::new(),
+        }
+    }
+
+    fn client_qso(id: &str, record: FleQsoRecord) -> ClientQso {
+        ClientQso {
+            client_id: id.to_string(),
+            record,
+        }
+    }
+
+    #[test]
+    fn test_validate_session() {
+        assert!(validate_session(&session(AwardProgram::SOTA, "JA/KN-006")).is_ok());
+        assert!(validate_session(&session(AwardProgram::POTA, "JA-0001")).is_ok());
+        assert!(validate_session(&session(AwardProgram::SOTA, "JA-0001")).is_err());
+        assert!(validate_session(&session(AwardProgram::WWFF, "JAFF-0001")).is_err());
+    }
+
+    #[test]
+    fn test_to_session_qso() {
+        let s = session(AwardProgram::SOTA, "JA/KN-006");
+        let now = Utc::now();
+        let q = to_session_qso(
+            &s,
+            client_qso(" a1 ", record("ja1xyz", "", "7.032", 5)),
+            now,
+        )
+        .unwrap();
+        assert_eq!(q.client_id, "a1");
+        assert_eq!(q.callsign, "JA1XYZ");
+        assert_eq!(q.band, "40m");
+        assert_eq!(q.qso_time.to_rfc3339(), "2026-10-18T01:05:00+00:00");
+        let r = parse_record(&q).unwrap();
+        assert_eq!(r.mycall, "JA1ABC/P");
+        assert_eq!(r.mysota, "JA/KN-006");
+
+        assert!(to_session_qso(&s, client_qso("", record("JA1XYZ", "40m", "", 0)), now).is_err());
+        assert!(to_session_qso(&s, client_qso("a2", record("JA1XYZ", "", "", 0)), now).is_err());
+        let mut bad = record("JA1XYZ", "40m", "", 0);
+        bad.month = 13;
+        assert!(to_session_qso(&s, client_qso("a3", bad), now).is_err());
+    }
+
+    #[test]
+    fn test_build_log_files() {
+        let now = Utc::now();
+        let s = session(AwardProgram::SOTA, "JA/KN-006");
+        let mut s2s = record("JA1BBB/P", "40m", "", 2);
+        s2s.hissota = "JA/TK-001".to_string();
+        let qsos: Vec<SessionQso> = [record("JA1AAA", "40m", "", 1), s2s]
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| to_session_qso(&s, client_qso(&i.to_string(), r), now).unwrap())
+            .collect();
+        let files = build_log_files(&s, &qsos, now).unwrap();
+        let names: HashSet<_> = files.iter().map(|f| f.filename.as_str()).collect();
+        assert!(names.contains("fle-20261018@JA-KN-006.zip"));
+        assert!(names.contains("sota20261018.csv"));
+        assert!(names.contains("sota-s2s-20261018.csv"));
+        let csv = files
+            .iter()
+            .find(|f| f.filename == "sota20261018.csv")
+            .unwrap();
+        let csv = String::from_utf8_lossy(&csv.content);
+        assert!(csv.contains("V2,JA1ABC/P,JA/KN-006,18/10/2026,01:02"));
+        assert!(csv.contains("JA1BBB/P,JA/TK-001"));
+
+        let s = session(AwardProgram::POTA, "JA-0001");
+        let qsos =
+            vec![
+                to_session_qso(&s, client_qso("1", record("JA1AAA", "", "14.062", 1)), now)
+                    .unwrap(),
+            ];
+        let files = build_log_files(&s, &qsos, now).unwrap();
+        let adif = files
+            .iter()
+            .find(|f| f.filename == "JA1ABC-P@JA-0001-20261018.adi")
+            .unwrap();
+        let adif = String::from_utf8_lossy(&adif.content);
+        assert!(adif.contains("<MY_SIG_INFO:7>JA-0001"));
+        assert!(adif.contains("<BAND:3>20m"));
+
+        assert!(build_log_files(&s, &[], now).is_err());
+    }
+}
@@ -0,0 +1,278 @@
+//! 太陽の位置と日の出・日の入り・薄明・グレイライン
+//!
+//! NOAAの太陽計算式（Solar Calculator）による。精度は中緯度で1分程度。
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde_json::{json, Value};
+
+/// 日の出・日の入りの太陽の天頂距離（大気差と視半径を含む）
+const ZENITH_SUNRISE: f64 = 90.833;
+
+/// 市民薄明の太陽の天頂距離
+const ZENITH_CIVIL: f64 = 96.0;
+
+/// ターミネーターを描く経度の間隔（度）
+const TERMINATOR_STEP_DEG: usize = 2;
+
+/// ある日・ある地点の太陽の出入り（極夜・白夜で起きないものはNone）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SunTimes {
+    pub date: NaiveDate,
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub sunrise: Option<DateTime<Utc>>,
+    pub solar_noon: DateTime<Utc>,
+    pub sunset: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+}
+
+impl SunTimes {
+    /// 日の出から日の入りまでの分数
+    pub fn daylight_minutes(&self) -> Option<i64> {
+        Some((self.sunset? - self.sunrise?).num_minutes())
+    }
+}
+
+/// 太陽の赤緯（度）と均時差（分）
+fn solar_position(time: DateTime<Utc>) -> (f64, f64) {
+    let jd = time.timestamp() as f64 / 86400.0 + 2440587.5;
+    let jc = (jd - 2451545.0) / 36525.0;
+
+    let mean_long = (280.46646 + jc * (36000.76983 + jc * 0.0003032)).rem_euclid(360.0);
+    let mean_anom = 357.52911 + jc * (35999.05029 - 0.0001537 * jc);
+    let ecc = 0.016708634 - jc * (0.000042037 + 0.0000001267 * jc);
+    let m = mean_anom.to_radians();
+    let center = m.sin() * (1.914602 - jc * (0.004817 + 0.000014 * jc))
+        + (2.0 * m).sin() * (0.019993 - 0.000101 * jc)
+        + (3.0 * m).sin() * 0.000289;
+    let omega = (125.04 - 1934.136 * jc).to_radians();
+    let app_long = mean_long + center - 0.00569 - 0.00478 * omega.sin();
+    let mean_obliq =
+        23.0 + (26.0 + (21.448 - jc * (46.815 + jc * (0.00059 - jc * 0.001813))) / 60.0) / 60.0;
+    let obliq = (mean_obliq + 0.00256 * omega.cos()).to_radians();
+
+    let decl = (obliq.sin() * app_long.to_radians().sin())
+        .asin()
+        .to_degrees();
+
+    let y = (obliq / 2.0).tan().powi(2);
+    let l = mean_long.to_radians();
+    let eq_time = 4.0
+        * (y * (2.0 * l).sin() - 2.0 * ecc * m.sin() + 4.0 * ecc * y * m.sin() * (2.0 * l).cos()
+            - 0.5 * y * y * (4.0 * l).sin()
+            - 1.25 * ecc * ecc * (2.0 * m).sin())
+        .to_degrees();
+    (decl, eq_time)
+}
+
+fn at_minutes(date: NaiveDate, minutes: f64) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap().and_utc()
+        + Duration::seconds((minutes * 60.0).round() as i64)
+}
+
+/// 南中時刻（UTC 0時からの分）
+fn noon_minutes(date: NaiveDate, lon: f64) -> f64 {
+    let mut minutes = 720.0 - 4.0 * lon;
+    for _ in 0..2 {
+        let (_, eq_time) = solar_position(at_minutes(date, minutes));
+        minutes = 720.0 - 4.0 * lon - eq_time;
+    }
+    minutes
+}
+
+/// 太陽が天頂距離 `zenith` を通る時刻（`rising` なら午前側）
+fn crossing(
+    date: NaiveDate,
+    lat: f64,
+    lon: f64,
+    zenith: f64,
+    rising: bool,
+) -> Option<DateTime<Utc>> {
+    let sign = if rising { -1.0 } else { 1.0 };
+    let mut minutes = noon_minutes(date, lon);
+    let mut hour_angle = 90.0;
+    for _ in 0..3 {
+        let (decl, eq_time) = solar_position(at_minutes(date, minutes + sign * 4.0 * hour_angle));
+        let (lat_r, decl_r) = (lat.to_radians(), decl.to_radians());
+        let cos_ha =
+            zenith.to_radians().cos() / (lat_r.cos() * decl_r.cos()) - lat_r.tan() * decl_r.tan();
+        if !(-1.0..=1.0).contains(&cos_ha) {
+            return None;
+        }
+        hour_angle = cos_ha.acos().to_degrees();
+        minutes = 720.0 - 4.0 * lon - eq_time;
+    }
+    Some(at_minutes(date, minutes + sign * 4.0 * hour_angle))
+}
+
+/// 地点の日付（経度から求めた地方時の日付）の日の出・日の入りと市民薄明
+pub fn sun_times(date: NaiveDate, lat: f64, lon: f64) -> SunTimes {
+    SunTimes {
+        date,
+        civil_dawn: crossing(date, lat, lon, ZENITH_CIVIL, true),
+        sunrise: crossing(date, lat, lon, ZENITH_SUNRISE, true),
+        solar_noon: at_minutes(date, noon_minutes(date, lon)),
+        sunset: crossing(date, lat, lon, ZENITH_SUNRISE, false),
+        civil_dusk: crossing(date, lat, lon, ZENITH_CIVIL, false),
+    }
+}
+
+/// 経度から求めた地方時の今日の日付
+pub fn local_date(now: DateTime<Utc>, lon: f64) -> NaiveDate {
+    (now + Duration::minutes((lon * 4.0) as i64)).date_naive()
+}
+
+fn normalize_lon(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// 太陽が天頂にある地点（緯度・経度）
+pub fn subsolar_point(time: DateTime<Utc>) -> (f64, f64) {
+    let (decl, eq_time) = solar_position(time);
+    let minutes = time.timestamp().rem_euclid(86400) as f64 / 60.0;
+    (decl, normalize_lon((720.0 - minutes - eq_time) / 4.0))
+}
+
+/// 昼夜の境界（ターミネーター）の経度・緯度の列（西から東へ）
+pub fn terminator(time: DateTime<Utc>) -> Vec<(f64, f64)> {
+    let (decl, sub_lon) = subsolar_point(time);
+    // 春分・秋分で赤緯が0になるとtanが0になるので避ける
+    let decl = if decl.abs() < 1e-6 { 1e-6 } else { decl };
+    let tan_decl = decl.to_radians().tan();
+    (0..=360)
+        .step_by(TERMINATOR_STEP_DEG)
+        .map(|i| {
+            let lon = i as f64 - 180.0;
+            let lat = (-(lon - sub_lon).to_radians().cos() / tan_decl)
+                .atan()
+                .to_degrees();
+            (lon, lat)
+        })
+        .collect()
+}
+
+fn round4(v: f64) -> f64 {
+    (v * 10000.0).round() / 10000.0
+}
+
+/// グレイラインをGeoJSONのFeatureCollectionにする
+///
+/// ターミネーター（LineString）、夜側の領域（Polygon）、太陽直下点（Point）を返す。
+pub fn grayline_geojson(time: DateTime<Utc>) -> Value {
+    let line: Vec<[f64; 2]> = terminator(time)
+        .into_iter()
+        .map(|(lon, lat)| [round4(lon), round4(lat)])
+        .collect();
+    let (sub_lat, sub_lon) = subsolar_point(time);
+
+    // 太陽が北半球にあれば南極側が夜
+    let night_pole = if sub_lat >= 0.0 { -90.0 } else { 90.0 };
+    let mut ring = line.clone();
+    ring.push([180.0, night_pole]);
+    ring.push([-180.0, night_pole]);
+    ring.push(line[0]);
+
+    let time = time.to_rfc3339();
+    json!({
+        "type": "FeatureCollection",
+        "features": [
+            {
+                "type": "Feature",
+                "properties": {"name": "terminator", "time": time},
+                "geometry": {"type": "LineString", "coordinates": line},
+            },
+            {
+                "type": "Feature",
+                "properties": {"name": "night", "time": time},
+                "geometry": {"type": "Polygon", "coordinates": [ring]},
+            },
+            {
+                "type": "Feature",
+                "properties": {"name": "subsolar", "time": time},
+                "geometry": {"type": "Point", "coordinates": [round4(sub_lon), round4(sub_lat)]},
+            },
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn assert_near(actual: Option<DateTime<Utc>>, expected: DateTime<Utc>) {
+        let actual = actual.expect("time");
+        let diff = (actual - expected).num_seconds().abs();
+        assert!(diff <= 120, "{} vs {}", actual, expected);
+    }
+
+    #[test]
+    fn test_sun_times_tokyo() {
+        // 東京 2026-01-15（国立天文台: 日の出 6:51 / 南中 11:50 / 日の入り 16:50 JST）
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let t = sun_times(date, 35.6895, 139.6917);
+        assert_near(
+            t.sunrise,
+            Utc.with_ymd_and_hms(2026, 1, 14, 21, 51, 0).unwrap(),
+        );
+        assert_near(
+            Some(t.solar_noon),
+            Utc.with_ymd_and_hms(2026, 1, 15, 2, 50, 0).unwrap(),
+        );
+        assert_near(
+            t.sunset,
+            Utc.with_ymd_and_hms(2026, 1, 15, 7, 50, 0).unwrap(),
+        );
+        assert!(t.civil_dawn.unwrap() < t.sunrise.unwrap());
+        assert!(t.civil_dusk.unwrap() > t.sunset.unwrap());
+        let minutes = t.daylight_minutes().unwrap();
+        assert!((595..=605).contains(&minutes), "{}", minutes);
+    }
+
+    #[test]
+    fn test_sun_times_polar() {
+        let date = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+        let t = sun_times(date, 78.0, 15.0);
+        assert!(t.sunrise.is_none());
+        assert!(t.sunset.is_none());
+        assert!(t.daylight_minutes().is_none());
+    }
+
+    #[test]
+    fn test_local_date() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 20, 0, 0).unwrap();
+        assert_eq!(
+            local_date(now, 139.0),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+        );
+        assert_eq!(
+            local_date(now, -120.0),
+            NaiveDate::from_ymd_opt(2026, 10, 18).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_terminator() {
+        // 6月の正午UTC: 太陽直下点は北回帰線付近・経度0付近
+        let time = Utc.with_ymd_and_hms(2026, 6, 21, 12, 0, 0).unwrap();
+        let (lat, lon) = subsolar_point(time);
+        assert!((lat - 23.4).abs() < 0.2, "{}", lat);
+        assert!(lon.abs() < 1.0, "{}", lon);
+
+        let line = terminator(time);
+        assert_eq!(line.len(), 181);
+        // 太陽直下点から90度離れた経度ではほぼ赤道上
+        let (_, lat_at_90) = line.iter().find(|(l, _)| *l == 90.0).unwrap();
+        assert!(lat_at_90.abs() < 2.0, "{}", lat_at_90);
+        // 真夜中側は北極圏の南、真昼側は南極圏の北で昼夜が分かれる
+        let (_, lat_at_180) = line.last().unwrap();
+        assert!((lat_at_180 - 66.6).abs() < 1.0, "{}", lat_at_180);
+
+        let geojson = grayline_geojson(time);
+        assert_eq!(geojson["features"].as_array().unwrap().len(), 3);
+        let ring = geojson["features"][1]["geometry"]["coordinates"][0]
+            .as_array()
+            .unwrap();
+        assert_eq!(ring.first(), ring.last());
+        assert_eq!(ring[ring.len() - 2][1], -90.0);
+    }
+}
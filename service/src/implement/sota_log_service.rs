@@ -1,9 +1,11 @@
 use async_trait::async_trait;
 use chrono::Utc;
 use shaku::Component;
+use std::collections::HashSet;
 use std::sync::Arc;
 
-use super::award_calculator::{detect_log_type, judge_award_with_mode};
+use super::award_calculator::{detect_log_type, judge_award_with_points};
+use super::summit_points::SummitPointTable;
 use crate::model::award::{AwardPeriod, AwardResult, JudgmentMode, SotaLogEntry};
 use crate::model::sota::{SOTALogCSV, UploadSOTALog};
 use crate::services::SotaLogService;
@@ -50,7 +52,7 @@ impl SotaLogService for SotaLogServiceImpl {
         Ok(())
     }
 
-    fn judge_10th_anniversary_award(
+    async fn judge_10th_anniversary_award(
         &self,
         csv_data: &str,
         mode: JudgmentMode,
@@ -72,8 +74,20 @@ impl SotaLogService for SotaLogServiceImpl {
         // アワード期間を設定
         let period = AwardPeriod::default();
 
+        // ログに現れる山岳の交信日時点の得点（期限切れの山岳を含む）
+        let codes: HashSet<String> = logs
+            .iter()
+            .flat_map(|l| [l.my_summit_code.as_ref(), l.his_summit_code.as_ref()])
+            .flatten()
+            .filter(|code| !code.is_empty())
+            .map(|code| code.to_uppercase())
+            .collect();
+        let table = SummitPointTable::load(self.sota_repo.as_ref(), codes, period.start).await?;
+
         // in-memoryで判定（ログ種別とモード指定）
-        let result = judge_award_with_mode(logs, &period, mode, log_type);
+        let result = judge_award_with_points(logs, &period, mode, log_type, &|code, date| {
+            table.points_on(code, date)
+        });
 
         tracing::info!(
             "Award judgment complete: {} QSOs in period, log_type={:?}",
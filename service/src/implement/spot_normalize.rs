@@ -0,0 +1,274 @@
+//! スポットの正規化と重複スポットのまとめ
+//!
+//! 上流のスポットは周波数の単位（SOTAはMHz、POTAはkHz）やモードの表記がばらばらなので、
+//! Hzの周波数・バンド・ADIFのモードをそろえる。同じアクティベーターを同じ周波数で
+//! 別のソース（RBNや手動のスポットなど）が続けて上げたものは1件にまとめる。
+
+use chrono::{DateTime, Duration, Utc};
+
+use domain::model::activation::Spot;
+use domain::model::needed::Channel;
+use domain::model::AwardProgram;
+
+use crate::implement::logconv::types::{freq_to_band, mode_to_adif_mode, FREQ_TABLE};
+
+/// 重複とみなす時間（最後のスポットからの分）
+pub const SPOT_MERGE_WINDOW_MINUTES: i64 = 10;
+/// 重複とみなす周波数の差（Hz）
+const SPOT_MERGE_TOLERANCE_HZ: i64 = 1_000;
+
+/// 周波数をHzで読む
+///
+/// 単位（kHz/MHz/GHz）がなければ `khz` がtrueならkHz、falseならMHzとみなす。
+/// "freq1/freq2" の形式は最初の周波数を使う。
+pub fn frequency_hz(freq: &str, khz: bool) -> Option<i64> {
+    let freq = freq.split('/').next()?.trim().to_ascii_lowercase();
+    let (number, scale) = if let Some(v) = freq.strip_suffix("ghz") {
+        (v, 1e9)
+    } else if let Some(v) = freq.strip_suffix("mhz") {
+        (v, 1e6)
+    } else if let Some(v) = freq.strip_suffix("khz") {
+        (v, 1e3)
+    } else if khz {
+        (freq.as_str(), 1e3)
+    } else {
+        (freq.as_str(), 1e6)
+    };
+    let hz = number.trim().parse::<f64>().ok()? * scale;
+    (hz.is_finite() && hz > 0.0).then(|| hz.round() as i64)
+}
+
+/// 周波数（Hz）からバンド名（"20m"など）を求める
+///
+/// バンドの範囲はログ変換と同じ `freq_to_band` の表による。
+pub fn hz_to_band(hz: i64) -> Option<String> {
+    freq_to_band(&(hz as f64 / 1_000_000.0).to_string())
+        .ok()
+        .map(|(_, _, wavelength)| wavelength)
+        .filter(|wavelength| !wavelength.is_empty())
+        .map(str::to_string)
+}
+
+/// 周波数の文字列からバンド名を求める（単位がなければ `khz` でkHzかMHzかを決める）
+pub fn frequency_band(freq: &str, khz: bool) -> Option<String> {
+    frequency_hz(freq, khz).and_then(hz_to_band)
+}
+
+/// SOTAログのバンド表記（"14MHz"、"5.6GHz"など）からバンド名を求める
+pub fn sota_log_band(band: &str) -> Option<String> {
+    let band = band.trim();
+    FREQ_TABLE
+        .iter()
+        .find(|b| !b.wavelength.is_empty() && b.band_sota.eq_ignore_ascii_case(band))
+        .map(|b| b.wavelength.to_string())
+        .or_else(|| frequency_band(band, false))
+}
+
+/// モードをADIFのモード（`mode_to_adif_mode`）にそろえる。USB/LSBはSSBにする
+pub fn canonical_mode(mode: &str) -> Option<String> {
+    let (mode, _) = mode_to_adif_mode(mode.trim());
+    match mode.as_str() {
+        "" => None,
+        "USB" | "LSB" => Some("SSB".to_string()),
+        _ => Some(mode),
+    }
+}
+
+/// アラートの周波数欄（"14.062-cw, 7.032-cw" など）をバンドとモードの組にする
+pub fn alert_channels(frequencies: &str, khz: bool) -> Vec<Channel> {
+    frequencies
+        .split([',', ';', ' ', '/'])
+        .filter(|s| !s.is_empty())
+        .map(|item| {
+            let (freq, mode) = item.split_once('-').unwrap_or((item, ""));
+            (
+                frequency_band(freq, khz),
+                canonical_mode(mode).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+/// スポットの周波数・バンド・モードを正規化する
+pub fn normalize_spot(spot: &mut Spot) {
+    let khz = spot.program != AwardProgram::SOTA;
+    spot.frequency_hz = frequency_hz(&spot.frequency, khz);
+    spot.band = spot.frequency_hz.and_then(hz_to_band);
+    spot.canonical_mode = canonical_mode(&spot.mode);
+
+    if spot.spotters.is_empty() {
+        spot.spotters.push(spot.spotter.clone());
+    }
+}
+
+fn is_duplicate(a: &Spot, b: &Spot) -> bool {
+    let same_freq = match (a.frequency_hz, b.frequency_hz) {
+        (Some(x), Some(y)) => (x - y).abs() <= SPOT_MERGE_TOLERANCE_HZ,
+        _ => a.frequency.trim() == b.frequency.trim(),
+    };
+    a.program == b.program
+        && a.operator == b.operator
+        && a.reference == b.reference
+        && a.canonical_mode == b.canonical_mode
+        && same_freq
+}
+
+/// 重複スポットを最初のスポットにまとめる
+///
+/// spot_idは最初のスポットのもの（上流の一覧に残っている間は同じ行を更新する）、
+/// 時刻・周波数・コメントは最後のスポットのものを使い、スポッターをすべて残す。
+pub fn merge_duplicate_spots(mut spots: Vec<Spot>, window: Duration) -> Vec<Spot> {
+    spots.sort_by_key(|s| (s.spot_time, s.spot_id));
+
+    let mut merged: Vec<(Spot, DateTime<Utc>)> = Vec::new();
+    for spot in spots {
+        let found = merged
+            .iter_mut()
+            .rev()
+            .find(|(m, last)| spot.spot_time - *last <= window && is_duplicate(m, &spot));
+        match found {
+            Some((m, last)) => {
+                *last = spot.spot_time;
+                for spotter in spot.spotters.iter().chain(std::iter::once(&spot.spotter)) {
+                    if !m.spotters.contains(spotter) {
+                        m.spotters.push(spotter.clone());
+                    }
+                }
+                m.spot_time = spot.spot_time;
+                m.frequency = spot.frequency;
+                m.frequency_hz = spot.frequency_hz;
+                if spot.comment.is_some() {
+                    m.comment = spot.comment;
+                }
+            }
+            None => {
+                let last = spot.spot_time;
+                merged.push((spot, last));
+            }
+        }
+    }
+    merged.into_iter().map(|(m, _)| m).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use domain::model::activation::make_test_spot;
+
+    fn spot(id: i32, program: AwardProgram, minute: u32, freq: &str, mode: &str) -> Spot {
+        Spot {
+            program,
+            spot_id: id,
+            operator: "JA1ABC".to_string(),
+            spot_time: Utc.with_ymd_and_hms(2026, 10, 18, 1, minute, 0).unwrap(),
+            frequency: freq.to_string(),
+            mode: mode.to_string(),
+            spotter: format!("SPOTTER{}", id),
+            comment: None,
+            ..make_test_spot("JA1ABC/P", "JA-0001")
+        }
+    }
+
+    fn normalized(mut s: Spot) -> Spot {
+        normalize_spot(&mut s);
+        s
+    }
+
+    #[test]
+    fn test_normalize_spot() {
+        let s = normalized(spot(1, AwardProgram::POTA, 0, "7032.5", "cw"));
+        assert_eq!(s.frequency_hz, Some(7_032_500));
+        assert_eq!(s.band.as_deref(), Some("40m"));
+        assert_eq!(s.canonical_mode.as_deref(), Some("CW"));
+        assert_eq!(s.spotters, vec!["SPOTTER1".to_string()]);
+
+        let s = normalized(spot(2, AwardProgram::SOTA, 0, "7.032/14.062", "usb"));
+        assert_eq!(s.frequency_hz, Some(7_032_000));
+        assert_eq!(s.canonical_mode.as_deref(), Some("SSB"));
+
+        let s = normalized(spot(3, AwardProgram::SOTA, 0, "14.074", "FT4"));
+        assert_eq!(s.band.as_deref(), Some("20m"));
+        assert_eq!(s.canonical_mode.as_deref(), Some("MFSK"));
+
+        // バンドの範囲は freq_to_band の表による
+        let s = normalized(spot(4, AwardProgram::SOTA, 0, "7.250", "SSB"));
+        assert_eq!(s.frequency_hz, Some(7_250_000));
+        assert_eq!(s.band, None);
+
+        let s = normalized(spot(5, AwardProgram::SOTA, 0, "QRT", ""));
+        assert_eq!(s.frequency_hz, None);
+        assert_eq!(s.band, None);
+        assert_eq!(s.canonical_mode, None);
+    }
+
+    #[test]
+    fn test_frequency_hz() {
+        assert_eq!(frequency_hz("14.062", false), Some(14_062_000));
+        assert_eq!(frequency_hz("7032.5", true), Some(7_032_500));
+        assert_eq!(frequency_hz("7.032/14.062", false), Some(7_032_000));
+        assert_eq!(frequency_hz("433MHz", true), Some(433_000_000));
+        assert_eq!(frequency_hz("5.6GHz", false), Some(5_600_000_000));
+        assert_eq!(frequency_hz("QRT", false), None);
+        assert_eq!(frequency_hz("0", false), None);
+    }
+
+    #[test]
+    fn test_log_band_and_alert_channels() {
+        assert_eq!(sota_log_band("14MHz").as_deref(), Some("20m"));
+        assert_eq!(sota_log_band("5.6GHz").as_deref(), Some("6cm"));
+        assert_eq!(sota_log_band("433MHz").as_deref(), Some("70cm"));
+        assert_eq!(sota_log_band("QRP"), None);
+        assert_eq!(frequency_band("27.040", false), None);
+
+        assert_eq!(canonical_mode("ft4").as_deref(), Some("MFSK"));
+        assert_eq!(canonical_mode("lsb").as_deref(), Some("SSB"));
+        assert_eq!(canonical_mode(" "), None);
+
+        assert_eq!(
+            alert_channels("14.062-cw, 7.032-USB", false),
+            vec![
+                (Some("20m".to_string()), "CW".to_string()),
+                (Some("40m".to_string()), "SSB".to_string())
+            ]
+        );
+        assert_eq!(
+            alert_channels("7144", true),
+            vec![(Some("40m".to_string()), String::new())]
+        );
+    }
+
+    #[test]
+    fn test_merge_duplicate_spots() {
+        let window = Duration::minutes(SPOT_MERGE_WINDOW_MINUTES);
+        let spots = vec![
+            normalized(spot(3, AwardProgram::POTA, 8, "14062.5", "CW")),
+            normalized(spot(1, AwardProgram::POTA, 0, "14062", "CW")),
+            normalized(spot(2, AwardProgram::POTA, 4, "14062.1", "cw")),
+            // 別の周波数
+            normalized(spot(4, AwardProgram::POTA, 5, "7032", "CW")),
+            // 最後のスポットから10分を超えた
+            normalized(spot(5, AwardProgram::POTA, 30, "14062", "CW")),
+        ];
+
+        let merged = merge_duplicate_spots(spots, window);
+        assert_eq!(merged.len(), 3);
+
+        let first = &merged[0];
+        assert_eq!(first.spot_id, 1);
+        assert_eq!(
+            first.spotters,
+            vec![
+                "SPOTTER1".to_string(),
+                "SPOTTER2".to_string(),
+                "SPOTTER3".to_string()
+            ]
+        );
+        assert_eq!(first.frequency, "14062.5");
+        assert_eq!(first.spot_time.format("%M").to_string(), "08");
+
+        assert_eq!(merged[1].spot_id, 4);
+        assert_eq!(merged[2].spot_id, 5);
+        assert_eq!(merged[2].spotters, vec!["SPOTTER5".to_string()]);
+    }
+}
@@ -0,0 +1,136 @@
+//! 交信日時点のサミット得点
+//!
+//! サミットリストの更新で得点が変わったり有効期限が切れたりしても、
+//! 過去のログは交信日時点の得点で評価する。
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+use common::error::AppResult;
+use domain::model::changelog::{ReferenceChange, ReferenceChangeKind};
+use domain::model::event::FindRefBuilder;
+use domain::model::sota::SotaReference;
+use domain::repository::sota::SotaRepository;
+
+/// サミットコード -> 有効期間と得点の履歴
+#[derive(Debug, Default)]
+pub struct SummitPointTable {
+    summits: HashMap<String, SotaReference>,
+    /// 得点変更（変更日, 変更前の得点）を日付順に保持
+    changes: HashMap<String, Vec<(NaiveDate, Option<i32>)>>,
+}
+
+impl SummitPointTable {
+    pub fn new(summits: Vec<SotaReference>, changes: Vec<ReferenceChange>) -> Self {
+        let summits: HashMap<_, _> = summits
+            .into_iter()
+            .map(|s| (s.summit_code.clone(), s))
+            .collect();
+
+        let mut table: HashMap<String, Vec<(NaiveDate, Option<i32>)>> = HashMap::new();
+        for c in changes {
+            if c.kind != ReferenceChangeKind::PointsChanged || !summits.contains_key(&c.reference) {
+                continue;
+            }
+            let old = c.old_value.and_then(|v| v.parse().ok());
+            table
+                .entry(c.reference)
+                .or_default()
+                .push((c.changed_at.date_naive(), old));
+        }
+        for v in table.values_mut() {
+            v.sort_by_key(|(date, _)| *date);
+        }
+
+        Self {
+            summits,
+            changes: table,
+        }
+    }
+
+    /// ログに現れるサミットと、`since` 以降の得点変更を読み込む
+    ///
+    /// コード指定の検索は有効期間で絞り込まないので、期限切れのサミットも得られる。
+    pub async fn load(
+        sota_repo: &dyn SotaRepository,
+        codes: impl IntoIterator<Item = String>,
+        since: DateTime<Utc>,
+    ) -> AppResult<Self> {
+        let mut summits = Vec::new();
+        for code in codes {
+            let query = FindRefBuilder::default().sota().sota_code(code).build();
+            summits.extend(sota_repo.find_reference(&query).await?);
+        }
+        let changes = sota_repo.find_changes(since).await?;
+        Ok(Self::new(summits, changes))
+    }
+
+    /// 指定日の得点（有効期間外や未登録のサミットはNone）
+    ///
+    /// その日より後に得点が変わっていれば、最初の変更前の得点を返す。
+    pub fn points_on(&self, code: &str, date: NaiveDate) -> Option<i32> {
+        let summit = self.summits.get(code)?;
+        if !summit.is_valid_on(date) {
+            return None;
+        }
+        let later = self
+            .changes
+            .get(code)
+            .and_then(|v| v.iter().find(|(changed, _)| *changed > date));
+        match later {
+            Some((_, old)) => *old,
+            None => Some(summit.points),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use domain::model::activation::make_test_summit;
+    use domain::model::AwardProgram;
+
+    fn summit(code: &str, points: i32, valid_to: NaiveDate) -> SotaReference {
+        SotaReference {
+            summit_code: code.to_string(),
+            points,
+            ..make_test_summit(NaiveDate::from_ymd_opt(2010, 1, 1).unwrap(), valid_to)
+        }
+    }
+
+    fn points_changed(code: &str, old: i32, new: i32, date: NaiveDate) -> ReferenceChange {
+        ReferenceChange {
+            program: AwardProgram::SOTA,
+            reference: code.to_string(),
+            kind: ReferenceChangeKind::PointsChanged,
+            old_value: Some(old.to_string()),
+            new_value: Some(new.to_string()),
+            changed_at: Utc.from_utc_datetime(&date.and_hms_opt(3, 0, 0).unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_points_on() {
+        let ymd = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let table = SummitPointTable::new(
+            vec![
+                summit("JA/TK-001", 8, ymd(2099, 12, 31)),
+                summit("JA/TK-002", 2, ymd(2020, 3, 31)),
+            ],
+            vec![
+                points_changed("JA/TK-001", 6, 8, ymd(2024, 1, 1)),
+                points_changed("JA/TK-001", 4, 6, ymd(2022, 1, 1)),
+            ],
+        );
+
+        assert_eq!(table.points_on("JA/TK-001", ymd(2021, 6, 1)), Some(4));
+        assert_eq!(table.points_on("JA/TK-001", ymd(2022, 1, 1)), Some(6));
+        assert_eq!(table.points_on("JA/TK-001", ymd(2024, 6, 1)), Some(8));
+
+        // 期限切れのサミットは有効期間内の交信だけ得点になる
+        assert_eq!(table.points_on("JA/TK-002", ymd(2020, 3, 31)), Some(2));
+        assert_eq!(table.points_on("JA/TK-002", ymd(2020, 4, 1)), None);
+        assert_eq!(table.points_on("JA/TK-999", ymd(2020, 4, 1)), None);
+    }
+}
@@ -1,17 +1,31 @@
 use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
 use domain::model::AwardProgram;
 use regex::Regex;
 use shaku::Component;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::implement::alert_outcome::{correlate_alerts, OutcomeWindow};
+use crate::implement::aprs_service::ACTIVATION_GAP_HOURS;
+use crate::implement::eta::attach_alert_eta;
+use crate::implement::needed::NeededJudge;
 use crate::services::UserService;
-use common::error::AppResult;
-use domain::model::activation::{Alert, Spot, SpotLog};
-use domain::model::aprslog::{AprsLog, AprsTrack};
-use domain::model::event::{FindAct, FindAprs, FindRef, FindRefBuilder, FindResult, GroupBy};
+use common::config::AppConfig;
+use common::dxcc;
+use common::error::{AppError, AppResult};
+use domain::model::activation::{Alert, AlertLog, AlertOutcome, Spot, SpotLog};
+use domain::model::activity::ActivityStats;
+use domain::model::aprslog::{AprsLog, AprsSegment, AprsTrack, AprsTrackArchive};
+use domain::model::archive::{
+    ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
+use domain::model::changelog::ReferenceChange;
+use domain::model::event::{FindAct, FindAprs, FindRef, FindResult, GroupBy};
 use domain::model::geomag::GeomagIndex;
 use domain::model::locator::MunicipalityCenturyCode;
+use domain::model::route::RouteHit;
+use domain::model::sota::ActivationZone;
 use domain::repository::{
     activation::ActivationRepositry, aprs::AprsLogRepository, geomag::GeoMagRepositry,
     locator::LocatorRepositry, pota::PotaRepository, sota::SotaRepository,
@@ -21,7 +35,7 @@ use domain::repository::{
 #[shaku(interface = UserService)]
 pub struct UserServiceImpl {
     #[shaku(inject)]
-    sota_repo: Arc<dyn SotaRepository>,
+    pub sota_repo: Arc<dyn SotaRepository>,
     #[shaku(inject)]
     pota_repo: Arc<dyn PotaRepository>,
     #[shaku(inject)]
@@ -32,6 +46,8 @@ pub struct UserServiceImpl {
     pub aprs_log_repo: Arc<dyn AprsLogRepository>,
     #[shaku(inject)]
     geomag_repo: Arc<dyn GeoMagRepositry>,
+
+    config: AppConfig,
 }
 
 fn get_alert_group(event: &FindAct, r: &Alert) -> GroupBy {
@@ -94,7 +110,26 @@ impl UserService for UserServiceImpl {
         Ok(result)
     }
 
-    async fn find_alerts(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<Alert>>> {
+    async fn find_references_along_route(&self, mut event: FindRef) -> AppResult<Vec<RouteHit>> {
+        let Some(route) = event.route.clone() else {
+            return Err(AppError::UnprocessableEntity(
+                "route is required".to_string(),
+            ));
+        };
+
+        // DBは経路を囲む矩形で絞り込むだけなので、件数の制限は距離で絞ってから行う
+        let limit = event.limit.take().map_or(usize::MAX, |v| v.max(0) as usize);
+        let offset = event.offset.take().map_or(0, |v| v.max(0) as usize);
+
+        let result = self.find_references(event).await?;
+        let hits = route.hits(
+            result.sota.unwrap_or_default(),
+            result.pota.unwrap_or_default(),
+        );
+        Ok(hits.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn find_alerts(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<AlertLog>>> {
         let mut result = HashMap::new();
         if event.group_by.is_some() {
             let mut alerts = self.act_repo.find_alerts(&event).await?;
@@ -104,16 +139,46 @@ impl UserService for UserServiceImpl {
                     alerts.retain(|r| pat.is_match(&r.location));
                 }
             }
-            for alert in alerts {
+            let param = OutcomeWindow::from_config(&self.config);
+            let now = Utc::now();
+            let mut logs = correlate_alerts(self.act_repo.as_ref(), alerts, &param, now).await?;
+            attach_alert_eta(
+                self.aprs_log_repo.as_ref(),
+                self.sota_repo.as_ref(),
+                &mut logs,
+                now,
+            )
+            .await?;
+
+            let parks: Vec<_> = logs
+                .iter()
+                .filter(|l| l.alert.program == AwardProgram::POTA)
+                .map(|l| l.alert.reference.clone())
+                .collect();
+            let judge = NeededJudge::load(
+                self.sota_repo.as_ref(),
+                self.pota_repo.as_ref(),
+                &event,
+                parks,
+            )
+            .await?;
+
+            for mut log in logs {
+                judge.attach(&mut log);
+                log.dxcc = dxcc::lookup(&log.alert.activator);
                 result
-                    .entry(get_alert_group(&event, &alert))
+                    .entry(get_alert_group(&event, &log.alert))
                     .or_insert(Vec::new())
-                    .push(alert);
+                    .push(log);
             }
         }
         Ok(result)
     }
 
+    async fn find_alert_outcomes(&self, event: FindAct) -> AppResult<Vec<AlertOutcome>> {
+        Ok(self.act_repo.find_outcomes(&event).await?)
+    }
+
     async fn find_spots(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<SpotLog>>> {
         let mut result = HashMap::new();
         if event.group_by.is_some() {
@@ -125,58 +190,48 @@ impl UserService for UserServiceImpl {
                     spots.retain(|r| pat.is_match(&r.reference));
                 }
             }
-            let mut pota_hash: HashMap<String, SpotLog> = HashMap::new();
+
+            let parks: Vec<_> = spots
+                .iter()
+                .filter(|s| s.program == AwardProgram::POTA)
+                .map(|s| s.reference.clone())
+                .collect();
+            let judge = NeededJudge::load(
+                self.sota_repo.as_ref(),
+                self.pota_repo.as_ref(),
+                &event,
+                parks,
+            )
+            .await?;
 
             for spot in spots {
-                let mut spotlog = SpotLog::new(spot.clone(), None);
-
-                match get_spot_group(&event, &spot) {
-                    GroupBy::Callsign(_) => {
-                        result
-                            .entry(get_spot_group(&event, &spot))
-                            .or_insert(Vec::new())
-                            .push(spotlog);
-                    }
-                    GroupBy::Reference(code) => {
-                        if let Some(log_id) = &event.log_id {
-                            if spot.program == AwardProgram::POTA {
-                                let code = code.unwrap_or_default();
-                                if let Some(v) = pota_hash.get(&code) {
-                                    spotlog.qsos = v.qsos;
-                                } else {
-                                    let builder = FindRefBuilder::default();
-                                    let query = builder
-                                        .pota()
-                                        .pota_code(code.clone())
-                                        .log_id(*log_id)
-                                        .build();
-
-                                    let parks = self.find_references(query).await?;
-                                    if let FindResult { pota: Some(p), .. } = parks {
-                                        if let Some(pota) = p.first() {
-                                            spotlog.qsos = pota.qsos;
-                                            pota_hash.insert(code, spotlog.clone());
-                                        }
-                                    }
-                                }
-                            }
-                            result
-                                .entry(get_spot_group(&event, &spot))
-                                .or_insert(Vec::new())
-                                .push(spotlog);
-                        } else {
-                            result
-                                .entry(get_spot_group(&event, &spot))
-                                .or_insert(Vec::new())
-                                .push(spotlog);
-                        }
-                    }
-                }
+                let mut log = judge.spot_log(spot);
+                log.dxcc = dxcc::lookup(&log.spot.activator);
+                result
+                    .entry(get_spot_group(&event, &log.spot))
+                    .or_insert(Vec::new())
+                    .push(log);
             }
         }
         Ok(result)
     }
 
+    async fn activity_stats(&self, event: FindAct) -> AppResult<ActivityStats> {
+        let mut spots = self.act_repo.find_spots(&event).await?;
+
+        if let Some(loc_regex) = &event.pattern {
+            if let Ok(pat) = Regex::new(loc_regex) {
+                spots.retain(|r| pat.is_match(&r.reference));
+            }
+        }
+
+        let to = Utc::now();
+        let from = event
+            .issued_after
+            .unwrap_or_else(|| spots.iter().map(|s| s.spot_time).min().unwrap_or(to));
+        Ok(ActivityStats::from_spots(&spots, from, to))
+    }
+
     async fn find_century_code(&self, muni_code: i32) -> AppResult<MunicipalityCenturyCode> {
         let result = self
             .locator_repo
@@ -193,61 +248,77 @@ impl UserService for UserServiceImpl {
         Ok(self.geomag_repo.get_geomag().await?)
     }
 
+    async fn find_activation_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZone>> {
+        Ok(self.sota_repo.find_activation_zone(summit_code).await?)
+    }
+
+    async fn find_reference_changes(
+        &self,
+        program: AwardProgram,
+        since: DateTime<Utc>,
+    ) -> AppResult<Vec<ReferenceChange>> {
+        match program {
+            AwardProgram::SOTA => self.sota_repo.find_changes(since).await,
+            _ => self.pota_repo.find_changes(since).await,
+        }
+    }
+
+    async fn find_spot_archive(&self, query: FindArchive) -> AppResult<Vec<SpotArchive>> {
+        self.act_repo.find_spot_archive(&query).await
+    }
+
+    async fn find_alert_archive(&self, query: FindArchive) -> AppResult<Vec<AlertArchive>> {
+        self.act_repo.find_alert_archive(&query).await
+    }
+
+    async fn reference_year_stats(&self, query: FindArchive) -> AppResult<Vec<ReferenceYearStat>> {
+        self.act_repo.reference_year_stats(&query).await
+    }
+
+    async fn activator_stats(&self, query: FindArchive) -> AppResult<Vec<ActivatorStat>> {
+        self.act_repo.activator_stats(&query).await
+    }
+
     async fn find_aprs_log(&self, event: FindAprs) -> AppResult<Vec<AprsLog>> {
         Ok(self.aprs_log_repo.find_aprs_log(&event).await?)
     }
 
-    async fn get_aprs_track(&self, event: FindAprs) -> AppResult<Vec<AprsTrack>> {
+    async fn get_aprs_track(
+        &self,
+        event: FindAprs,
+        tolerance: Option<f64>,
+    ) -> AppResult<Vec<AprsTrack>> {
+        let aprslog = self.find_aprs_log(event).await?;
+        self.generate_track(aprslog, tolerance).await
+    }
+
+    async fn get_aprs_segments(
+        &self,
+        event: FindAprs,
+        tolerance: Option<f64>,
+    ) -> AppResult<Vec<AprsSegment>> {
         let aprslog = self.find_aprs_log(event).await?;
-        self.generate_track(aprslog).await
+        let mut segments = AprsSegment::split(aprslog, TimeDelta::hours(ACTIVATION_GAP_HOURS));
+        if let Some(tolerance) = tolerance {
+            segments.iter_mut().for_each(|s| s.simplify(tolerance));
+        }
+        Ok(segments)
+    }
+
+    async fn find_track_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchive>> {
+        Ok(self.aprs_log_repo.find_track_archives(callsign).await?)
+    }
+
+    async fn find_track_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchive>> {
+        Ok(self.aprs_log_repo.find_track_archive(archive_id).await?)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
-    use domain::model::activation::Spot;
+    use domain::model::activation::{make_test_alert, make_test_spot};
     use domain::model::event::{FindActBuilder, GroupBy};
-    use domain::model::AwardProgram;
-
-    /// テスト用Alertを生成するヘルパー
-    fn make_test_alert(activator: &str, reference: &str) -> Alert {
-        Alert {
-            program: AwardProgram::SOTA,
-            alert_id: 1,
-            user_id: 1,
-            activator: activator.to_string(),
-            activator_name: None,
-            operator: activator.to_string(),
-            reference: reference.to_string(),
-            reference_detail: "Test Summit".to_string(),
-            location: "Tokyo".to_string(),
-            start_time: Utc::now(),
-            end_time: None,
-            frequencies: "14.280".to_string(),
-            comment: Some("Test".to_string()),
-            poster: Some(activator.to_string()),
-        }
-    }
-
-    /// テスト用Spotを生成するヘルパー
-    fn make_test_spot(activator: &str, reference: &str) -> Spot {
-        Spot {
-            program: AwardProgram::SOTA,
-            spot_id: 1,
-            activator: activator.to_string(),
-            activator_name: None,
-            operator: activator.to_string(),
-            reference: reference.to_string(),
-            reference_detail: "Test Summit".to_string(),
-            spot_time: Utc::now(),
-            frequency: "14.280".to_string(),
-            mode: "SSB".to_string(),
-            spotter: "JA2XYZ".to_string(),
-            comment: Some("Test".to_string()),
-        }
-    }
 
     // ==================== ヘルパー関数テスト ====================
 
@@ -0,0 +1,248 @@
+//! ウォッチリストに合うスポット/アラートのWebhook配信
+//!
+//! 取り込んだスポット/アラートをウォッチリストと照合して配信を登録し、
+//! 登録済みの配信を送る。失敗した配信は `WebhookDelivery::record_attempt` が決めた
+//! 時刻に次の取り込みで再送する。
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::implement::spot_normalize::alert_channels;
+use common::error::AppResult;
+use domain::model::activation::{Alert, Spot};
+use domain::model::watch::{Watchlist, WebhookDelivery};
+use domain::model::AwardProgram;
+use domain::repository::watch::{WatchRepository, WebhookRepository};
+
+/// 1回に送る配信の上限
+pub const DELIVERY_BATCH_SIZE: i64 = 50;
+/// 配信記録の保存日数
+pub const DELIVERY_RETENTION_DAYS: i64 = 30;
+
+fn program_name(program: &AwardProgram) -> String {
+    String::from(program.clone())
+}
+
+pub fn spot_event_key(spot: &Spot) -> String {
+    format!("spot:{}:{}", program_name(&spot.program), spot.spot_id)
+}
+
+pub fn alert_event_key(alert: &Alert) -> String {
+    format!("alert:{}:{}", program_name(&alert.program), alert.alert_id)
+}
+
+fn spot_payload(watch: &Watchlist, spot: &Spot) -> String {
+    json!({
+        "event": "spot",
+        "watchId": watch.watch_id,
+        "watchName": watch.name,
+        "spot": {
+            "program": program_name(&spot.program),
+            "spotId": spot.spot_id,
+            "reference": spot.reference,
+            "referenceDetail": spot.reference_detail,
+            "activator": spot.activator,
+            "operator": spot.operator,
+            "spotTime": spot.spot_time,
+            "frequency": spot.frequency,
+            "frequencyHz": spot.frequency_hz,
+            "band": spot.band,
+            "mode": spot.canonical_mode.as_deref().unwrap_or(&spot.mode),
+            "spotter": spot.spotter,
+            "comment": spot.comment,
+        }
+    })
+    .to_string()
+}
+
+fn alert_payload(watch: &Watchlist, alert: &Alert) -> String {
+    json!({
+        "event": "alert",
+        "watchId": watch.watch_id,
+        "watchName": watch.name,
+        "alert": {
+            "program": program_name(&alert.program),
+            "alertId": alert.alert_id,
+            "reference": alert.reference,
+            "referenceDetail": alert.reference_detail,
+            "location": alert.location,
+            "activator": alert.activator,
+            "operator": alert.operator,
+            "startTime": alert.start_time,
+            "endTime": alert.end_time,
+            "frequencies": alert.frequencies,
+            "comment": alert.comment,
+            "poster": alert.poster,
+        }
+    })
+    .to_string()
+}
+
+/// ウォッチリストに合うスポットの配信を作る
+pub fn spot_deliveries(
+    watches: &[Watchlist],
+    spots: &[Spot],
+    now: DateTime<Utc>,
+) -> Vec<WebhookDelivery> {
+    watches
+        .iter()
+        .flat_map(|w| {
+            spots.iter().filter(|s| w.matches_spot(s)).map(move |s| {
+                WebhookDelivery::new(
+                    w.watch_id,
+                    spot_event_key(s),
+                    "spot",
+                    spot_payload(w, s),
+                    now,
+                )
+            })
+        })
+        .collect()
+}
+
+/// アラートの予定周波数から求めたバンド（スポットの正規化と同じ基準）
+fn alert_bands(alert: &Alert) -> Vec<String> {
+    let khz = alert.program != AwardProgram::SOTA;
+    alert_channels(&alert.frequencies, khz)
+        .into_iter()
+        .filter_map(|(band, _)| band)
+        .collect()
+}
+
+/// ウォッチリストに合うアラートの配信を作る
+pub fn alert_deliveries(
+    watches: &[Watchlist],
+    alerts: &[Alert],
+    now: DateTime<Utc>,
+) -> Vec<WebhookDelivery> {
+    let alerts: Vec<_> = alerts.iter().map(|a| (a, alert_bands(a))).collect();
+    watches
+        .iter()
+        .flat_map(|w| {
+            alerts
+                .iter()
+                .filter(|(a, bands)| w.matches_alert(a, bands))
+                .map(move |(a, _)| {
+                    WebhookDelivery::new(
+                        w.watch_id,
+                        alert_event_key(a),
+                        "alert",
+                        alert_payload(w, a),
+                        now,
+                    )
+                })
+        })
+        .collect()
+}
+
+/// 配信を登録する（通知済みのイベントは登録しない）。登録した件数を返す
+pub async fn enqueue_deliveries(
+    watch_repo: &dyn WatchRepository,
+    deliveries: Vec<WebhookDelivery>,
+) -> AppResult<usize> {
+    let mut count = 0;
+    for d in deliveries {
+        if watch_repo.insert_delivery(d).await? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// 送信時刻になった配信を送り、結果を記録する。送った件数を返す
+pub async fn deliver_due(
+    watch_repo: &dyn WatchRepository,
+    webhook: &dyn WebhookRepository,
+    now: DateTime<Utc>,
+) -> AppResult<usize> {
+    let deliveries = watch_repo
+        .find_due_deliveries(now, DELIVERY_BATCH_SIZE)
+        .await?;
+    let count = deliveries.len();
+
+    for mut d in deliveries {
+        let Some(watch) = watch_repo.find_watch(d.watch_id).await? else {
+            continue;
+        };
+        let result = webhook
+            .post(&watch.webhook_url, &watch.secret, &d.payload)
+            .await
+            .map_err(|e| e.to_string());
+        d.record_attempt(result, Utc::now());
+        tracing::debug!(
+            "webhook delivery {} to watch {}: {} ({} attempts)",
+            d.delivery_id,
+            d.watch_id,
+            d.status.as_str(),
+            d.attempts
+        );
+        watch_repo.update_delivery(d).await?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::model::activation::make_test_spot;
+    use domain::model::id::UserId;
+
+    fn watch(watch_id: i64, patterns: &[&str]) -> Watchlist {
+        Watchlist {
+            watch_id,
+            user_id: UserId::from("user".to_string()),
+            name: format!("watch{}", watch_id),
+            callsigns: vec![],
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            programs: vec![],
+            bands: vec![],
+            webhook_url: "https://example.com/hook".to_string(),
+            secret: "secret".to_string(),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn spot(spot_id: i32, reference: &str) -> Spot {
+        Spot {
+            spot_id,
+            operator: "JA1ABC".to_string(),
+            frequency: "7.032".to_string(),
+            mode: "cw".to_string(),
+            frequency_hz: Some(7_032_000),
+            band: Some("40m".to_string()),
+            canonical_mode: Some("CW".to_string()),
+            spotters: vec!["JA2XYZ".to_string()],
+            ..make_test_spot("JA1ABC/1", reference)
+        }
+    }
+
+    #[test]
+    fn test_spot_deliveries() {
+        let now = Utc::now();
+        let watches = vec![watch(1, &["JA/NN-*"]), watch(2, &["JA/TK-*", "JA/NN-001"])];
+        let spots = vec![spot(10, "JA/NN-001"), spot(11, "JA/NN-002")];
+
+        let deliveries = spot_deliveries(&watches, &spots, now);
+        let keys: Vec<_> = deliveries
+            .iter()
+            .map(|d| (d.watch_id, d.event_key.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (1, "spot:SOTA:10"),
+                (1, "spot:SOTA:11"),
+                (2, "spot:SOTA:10")
+            ]
+        );
+
+        let payload: serde_json::Value = serde_json::from_str(&deliveries[0].payload).unwrap();
+        assert_eq!(payload["event"], "spot");
+        assert_eq!(payload["watchId"], 1);
+        assert_eq!(payload["spot"]["reference"], "JA/NN-001");
+        assert_eq!(payload["spot"]["mode"], "CW");
+        assert_eq!(payload["spot"]["band"], "40m");
+    }
+}
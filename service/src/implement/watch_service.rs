@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use shaku::Component;
+use std::sync::Arc;
+
+use crate::services::WatchService;
+use common::error::{AppError, AppResult};
+use domain::model::id::UserId;
+use domain::model::watch::{generate_secret, Watchlist, WebhookDelivery};
+use domain::repository::watch::{WatchRepository, WebhookRepository};
+
+/// 条件1種類あたりの上限
+const MAX_CRITERIA: usize = 50;
+
+#[derive(Component)]
+#[shaku(interface = WatchService)]
+pub struct WatchServiceImpl {
+    #[shaku(inject)]
+    watch_repo: Arc<dyn WatchRepository>,
+    #[shaku(inject)]
+    webhook_repo: Arc<dyn WebhookRepository>,
+}
+
+fn validate_watch(watch: &Watchlist) -> AppResult<()> {
+    if watch.name.trim().is_empty() {
+        return Err(AppError::UnprocessableEntity(
+            "nameを指定してください".to_string(),
+        ));
+    }
+    if !watch
+        .webhook_url
+        .to_ascii_lowercase()
+        .starts_with("https://")
+    {
+        return Err(AppError::UnprocessableEntity(
+            "webhookUrlはhttpsのURLで指定してください".to_string(),
+        ));
+    }
+    if !watch.has_criteria() {
+        return Err(AppError::UnprocessableEntity(
+            "callsigns/patterns/programs/bandsのいずれかを指定してください".to_string(),
+        ));
+    }
+    let lists = [&watch.callsigns, &watch.patterns, &watch.bands];
+    if lists.iter().any(|l| l.len() > MAX_CRITERIA)
+        || lists.iter().flat_map(|l| l.iter()).any(|v| v.contains(','))
+    {
+        return Err(AppError::UnprocessableEntity(format!(
+            "条件は{}件以内で、カンマを含まない値で指定してください",
+            MAX_CRITERIA
+        )));
+    }
+    Ok(())
+}
+
+impl WatchServiceImpl {
+    /// 利用者のウォッチを取得する（他の利用者のウォッチは見つからない扱い）
+    async fn find_own_watch(&self, user_id: &UserId, watch_id: i64) -> AppResult<Watchlist> {
+        self.watch_repo
+            .find_watch(watch_id)
+            .await?
+            .filter(|w| &w.user_id == user_id)
+            .ok_or_else(|| AppError::EntityNotFound(format!("watch {}", watch_id)))
+    }
+}
+
+#[async_trait]
+impl WatchService for WatchServiceImpl {
+    async fn create_watch(&self, mut watch: Watchlist) -> AppResult<Watchlist> {
+        validate_watch(&watch)?;
+        self.webhook_repo.check_url(&watch.webhook_url).await?;
+        if watch.secret.is_empty() {
+            watch.secret = generate_secret();
+        }
+        let now = Utc::now();
+        watch.created_at = now;
+        watch.updated_at = now;
+        watch.watch_id = self.watch_repo.create_watch(watch.clone()).await?;
+        Ok(watch)
+    }
+
+    async fn update_watch(&self, mut watch: Watchlist) -> AppResult<Watchlist> {
+        validate_watch(&watch)?;
+        self.webhook_repo.check_url(&watch.webhook_url).await?;
+        let current = self.find_own_watch(&watch.user_id, watch.watch_id).await?;
+        if watch.secret.is_empty() {
+            watch.secret = current.secret;
+        }
+        watch.created_at = current.created_at;
+        watch.updated_at = Utc::now();
+        self.watch_repo.update_watch(watch.clone()).await?;
+        Ok(watch)
+    }
+
+    async fn delete_watch(&self, user_id: UserId, watch_id: i64) -> AppResult<()> {
+        self.find_own_watch(&user_id, watch_id).await?;
+        self.watch_repo.delete_watch(watch_id).await
+    }
+
+    async fn find_watches(&self, user_id: UserId) -> AppResult<Vec<Watchlist>> {
+        self.watch_repo.find_watches(&user_id).await
+    }
+
+    async fn find_deliveries(
+        &self,
+        user_id: UserId,
+        watch_id: i64,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>> {
+        self.find_own_watch(&user_id, watch_id).await?;
+        self.watch_repo.find_deliveries(watch_id, limit).await
+    }
+}
@@ -57,6 +57,10 @@ pub struct ActivatorResult {
     pub achieved: bool,
     pub qualified_summits: u32,
     pub summits: Vec<SummitActivationResult>,
+    /// S2S交信相手の山岳数（ユニーク）
+    pub s2s_summits: u32,
+    /// S2S交信の得点合計（交信日時点の得点、山岳・UTC日ごとに1回）
+    pub s2s_points: i32,
 }
 
 /// 山岳ごとのアクティベーション結果
@@ -65,6 +69,8 @@ pub struct SummitActivationResult {
     pub summit_code: String,
     pub unique_stations: u32,
     pub qualified: bool,
+    /// アクティベーション日時点の得点（不明ならNone）
+    pub points: Option<i32>,
 }
 
 /// チェイサー賞結果
@@ -80,6 +86,8 @@ pub struct SummitChaseResult {
     pub summit_code: String,
     pub unique_activators: u32,
     pub activators: Vec<String>,
+    /// 最初の交信日時点の得点（不明ならNone）
+    pub points: Option<i32>,
 }
 
 /// アワード期間
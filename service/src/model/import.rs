@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use common::utils::CsvRowError;
+
+/// リファレンス取り込みの検証結果
+///
+/// dry-runでは書き込まずにこの結果だけを返す。エラーが1件でもあれば書き込まない。
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// 書き込みを行ったか
+    pub committed: bool,
+    /// 読み込んだデータ行数（エラー行を含む）
+    pub rows: usize,
+    /// 取り込み対象外の行数（DBに存在しないなど）
+    pub skipped: usize,
+    pub errors: Vec<CsvRowError>,
+    pub inserts: Vec<String>,
+    pub updates: Vec<String>,
+    pub deletes: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn new(dry_run: bool, rows: usize, errors: Vec<CsvRowError>) -> Self {
+        Self {
+            dry_run,
+            rows,
+            errors,
+            ..Default::default()
+        }
+    }
+
+    /// 書き込んでよいか（dry-runでなくエラーもない）
+    pub fn should_commit(&self) -> bool {
+        !self.dry_run && self.errors.is_empty()
+    }
+
+    /// 書き込み件数
+    pub fn applied(&self) -> usize {
+        self.inserts.len() + self.updates.len() + self.deletes.len()
+    }
+
+    /// 既存コードかどうかで追加/更新に振り分ける
+    pub fn classify(&mut self, code: String, exists: bool) {
+        if exists {
+            self.updates.push(code);
+        } else {
+            self.inserts.push(code);
+        }
+    }
+
+    /// ファイル内で重複しているコードをエラーに加える
+    pub fn check_duplicates<'a>(&mut self, codes: impl IntoIterator<Item = (u64, &'a str)>) {
+        let mut seen: HashMap<&str, u64> = HashMap::new();
+        for (line, code) in codes {
+            if let Some(first) = seen.get(code) {
+                self.errors.push(CsvRowError {
+                    line,
+                    message: format!("duplicate reference {} (first at line {})", code, first),
+                });
+            } else {
+                seen.insert(code, line);
+            }
+        }
+        self.errors.sort_by_key(|e| e.line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_duplicates_block_commit() {
+        let mut report = ImportReport::new(false, 3, vec![]);
+        report.check_duplicates(vec![(2, "JA/TK-001"), (3, "JA/TK-002"), (4, "JA/TK-001")]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 4);
+        assert!(!report.should_commit());
+
+        let report = ImportReport::new(true, 3, vec![]);
+        assert!(!report.should_commit());
+        let report = ImportReport::new(false, 3, vec![]);
+        assert!(report.should_commit());
+    }
+}
@@ -1,4 +1,6 @@
 pub mod award;
+pub mod import;
 pub mod locator;
 pub mod pota;
+pub mod session;
 pub mod sota;
@@ -0,0 +1,22 @@
+use domain::model::session::SessionState;
+
+use crate::implement::fle::FleQsoRecord;
+
+/// クライアントが送るQSO（IDはクライアントが生成する）
+#[derive(Debug, Clone)]
+pub struct ClientQso {
+    pub client_id: String,
+    pub record: FleQsoRecord,
+}
+
+/// QSO登録の結果
+#[derive(Debug, Clone)]
+pub struct QsoPostReport {
+    pub state: SessionState,
+    /// 新しく登録した件数
+    pub inserted: usize,
+    /// 登録済みだった件数（再送）
+    pub duplicates: usize,
+    /// この登録で成立条件を満たしたか
+    pub just_qualified: bool,
+}
@@ -1,10 +1,11 @@
 use chrono::{NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
+use common::error::{AppError, AppResult};
 use common::utils::{call_to_operator, maidenhead, parse_date_flexible};
 use domain::model::{
     id::UserId,
-    sota::{SotaLog, SotaReference},
+    sota::{ActivationZone, SotaLog, SotaReference},
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -159,6 +160,50 @@ pub struct UploadSOTASummit {
     pub data: String,
 }
 
+/// アクティベーションゾーンのGeoJSON（FeatureCollection）
+pub struct UploadActivationZones {
+    pub data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZoneFeatureCollection {
+    features: Vec<ZoneFeature>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZoneFeature {
+    properties: ZoneProperties,
+    geometry: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ZoneProperties {
+    #[serde(alias = "summitCode", alias = "SummitCode")]
+    summit_code: String,
+}
+
+impl UploadActivationZones {
+    /// 各Featureの `summit_code` プロパティとPolygon/MultiPolygonジオメトリを読み込む
+    pub fn parse(&self) -> AppResult<Vec<ActivationZone>> {
+        let collection: ZoneFeatureCollection =
+            serde_json::from_str(&self.data).map_err(AppError::JsonError)?;
+
+        collection
+            .features
+            .into_iter()
+            .map(|f| {
+                let code = f.properties.summit_code.trim().to_uppercase();
+                ActivationZone::from_geometry(&code, &f.geometry).ok_or_else(|| {
+                    AppError::UnprocessableEntity(format!(
+                        "{} のジオメトリはPolygonまたはMultiPolygonで指定してください",
+                        code
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
 pub struct UploadSOTASummitOpt {
     pub data: String,
 }
@@ -1,16 +1,25 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use shaku::Interface;
-use std::collections::HashMap;
-
-use aprs_message::AprsData;
+use std::collections::{HashMap, HashSet};
 
 use crate::model::award::{AwardResult, JudgmentMode};
+use crate::model::import::ImportReport;
 use crate::model::locator::UploadMuniCSV;
 use crate::model::pota::{UploadPOTALog, UploadPOTAReference};
-use crate::model::sota::{UploadSOTALog, UploadSOTASummit, UploadSOTASummitOpt};
+use crate::model::session::{ClientQso, QsoPostReport};
+use crate::model::sota::{
+    UploadActivationZones, UploadSOTALog, UploadSOTASummit, UploadSOTASummitOpt,
+};
 use common::error::AppResult;
-use domain::model::activation::{Alert, Spot, SpotLog};
-use domain::model::aprslog::{AprsLog, AprsTrack};
+use domain::model::activation::{Alert, AlertLog, AlertOutcome, Spot, SpotLog};
+use domain::model::activity::ActivityStats;
+use domain::model::aprs::AprsPacket;
+use domain::model::aprslog::{AprsLog, AprsSegment, AprsTrack, AprsTrackArchive};
+use domain::model::archive::{
+    ActivatorStat, AlertArchive, FindArchive, ReferenceYearStat, SpotArchive,
+};
+use domain::model::changelog::ReferenceChange;
 use domain::model::event::{
     DeleteRef, FindAct, FindAprs, FindRef, FindResult, GroupBy, PagenatedResult,
 };
@@ -18,7 +27,12 @@ use domain::model::geomag::GeomagIndex;
 use domain::model::id::{LogId, UserId};
 use domain::model::locator::MunicipalityCenturyCode;
 use domain::model::pota::{ParkCode, PotaLogHist, PotaReference};
-use domain::model::sota::{SotaReference, SummitCode};
+use domain::model::relay::RelayPost;
+use domain::model::route::RouteHit;
+use domain::model::session::{ActivationSession, SessionFile, SessionQso, SessionState};
+use domain::model::sota::{ActivationZone, SotaReference, SummitCode};
+use domain::model::watch::{Watchlist, WebhookDelivery};
+use domain::model::AwardProgram;
 use std::path::Path;
 
 /// SOTAログ管理サービス
@@ -27,8 +41,10 @@ pub trait SotaLogService: Send + Sync + Interface {
     async fn upload_sota_log(&self, user_id: UserId, event: UploadSOTALog) -> AppResult<()>;
     async fn delete_sota_log(&self, user_id: UserId) -> AppResult<()>;
 
-    /// SOTA日本支部設立10周年記念アワード判定（in-memory、DBに保存しない）
-    fn judge_10th_anniversary_award(
+    /// SOTA日本支部設立10周年記念アワード判定（DBに保存しない）
+    ///
+    /// 山岳の得点は交信日時点のものを使う。
+    async fn judge_10th_anniversary_award(
         &self,
         csv_data: &str,
         mode: JudgmentMode,
@@ -48,26 +64,75 @@ pub trait PotaLogService: Send + Sync + Interface {
 pub trait UserService: Send + Sync + Interface {
     async fn count_references(&self, event: &FindRef) -> AppResult<i64>;
     async fn find_references(&self, event: FindRef) -> AppResult<FindResult>;
+    /// 経路沿いのリファレンス（event.routeが必要）
+    async fn find_references_along_route(&self, event: FindRef) -> AppResult<Vec<RouteHit>>;
 
-    async fn find_alerts(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<Alert>>>;
+    async fn find_alerts(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<AlertLog>>>;
     async fn find_spots(&self, event: FindAct) -> AppResult<HashMap<GroupBy, Vec<SpotLog>>>;
+    /// issued_after以降のスポットをバンド・モード・時間帯ごとに集計（patternでリファレンスを絞り込み）
+    async fn activity_stats(&self, event: FindAct) -> AppResult<ActivityStats>;
+    /// 確定済みのアクティベーション結果（アラート履行状況）
+    async fn find_alert_outcomes(&self, event: FindAct) -> AppResult<Vec<AlertOutcome>>;
 
     async fn find_century_code(&self, muni_code: i32) -> AppResult<MunicipalityCenturyCode>;
     async fn find_mapcode(&self, lon: f64, lat: f64) -> AppResult<String>;
     async fn find_aprs_log(&self, event: FindAprs) -> AppResult<Vec<AprsLog>>;
-    async fn get_aprs_track(&self, event: FindAprs) -> AppResult<Vec<AprsTrack>>;
+    /// コールサインごとのトラック（tolerance[m]を指定するとDouglas-Peucker法で間引く）
+    async fn get_aprs_track(
+        &self,
+        event: FindAprs,
+        tolerance: Option<f64>,
+    ) -> AppResult<Vec<AprsTrack>>;
+    /// アクティベーションごとのトラック
+    async fn get_aprs_segments(
+        &self,
+        event: FindAprs,
+        tolerance: Option<f64>,
+    ) -> AppResult<Vec<AprsSegment>>;
+    async fn find_track_archives(&self, callsign: &str) -> AppResult<Vec<AprsTrackArchive>>;
+    async fn find_track_archive(&self, archive_id: i64) -> AppResult<Option<AprsTrackArchive>>;
     async fn get_geomagnetic(&self) -> AppResult<Option<GeomagIndex>>;
+    async fn find_activation_zone(&self, summit_code: &str) -> AppResult<Option<ActivationZone>>;
+    /// サミット/パークリスト更新による変更履歴
+    async fn find_reference_changes(
+        &self,
+        program: AwardProgram,
+        since: DateTime<Utc>,
+    ) -> AppResult<Vec<ReferenceChange>>;
+    /// アーカイブ済みのスポット・アラートの検索と集計
+    async fn find_spot_archive(&self, query: FindArchive) -> AppResult<Vec<SpotArchive>>;
+    async fn find_alert_archive(&self, query: FindArchive) -> AppResult<Vec<AlertArchive>>;
+    async fn reference_year_stats(&self, query: FindArchive) -> AppResult<Vec<ReferenceYearStat>>;
+    async fn activator_stats(&self, query: FindArchive) -> AppResult<Vec<ActivatorStat>>;
 }
 
 /// 管理者向けAPIサービス（外部公開用）
 #[async_trait]
 pub trait AdminService: Send + Sync + Interface {
-    async fn import_summit_list(&self, event: UploadSOTASummit) -> AppResult<usize>;
+    /// dry_runなら検証結果だけを返し、エラーがなければ1トランザクションで書き込む
+    async fn import_summit_list(
+        &self,
+        event: UploadSOTASummit,
+        dry_run: bool,
+    ) -> AppResult<ImportReport>;
     async fn update_summit_list(&self, event: UploadSOTASummit) -> AppResult<usize>;
-    async fn import_summit_opt_list(&self, event: UploadSOTASummitOpt) -> AppResult<usize>;
+    async fn import_summit_opt_list(
+        &self,
+        event: UploadSOTASummitOpt,
+        dry_run: bool,
+    ) -> AppResult<ImportReport>;
+    async fn import_activation_zones(&self, event: UploadActivationZones) -> AppResult<usize>;
     async fn import_pota_park_list(&self, event: UploadPOTAReference) -> AppResult<usize>;
-    async fn import_pota_park_list_ja(&self, event: UploadPOTAReference) -> AppResult<usize>;
-    async fn import_muni_century_list(&self, event: UploadMuniCSV) -> AppResult<usize>;
+    async fn import_pota_park_list_ja(
+        &self,
+        event: UploadPOTAReference,
+        dry_run: bool,
+    ) -> AppResult<ImportReport>;
+    async fn import_muni_century_list(
+        &self,
+        event: UploadMuniCSV,
+        dry_run: bool,
+    ) -> AppResult<ImportReport>;
     async fn show_sota_reference(&self, query: FindRef) -> AppResult<SotaReference>;
     async fn show_all_sota_references(
         &self,
@@ -91,10 +156,67 @@ pub trait AdminService: Send + Sync + Interface {
 pub trait AdminPeriodicService: Send + Sync + Interface {
     async fn update_alerts(&self, alerts: Vec<Alert>) -> AppResult<()>;
     async fn update_spots(&self, spots: Vec<Spot>) -> AppResult<()>;
-    async fn aprs_packet_received(&self, packet: AprsData) -> AppResult<()>;
+    /// 受信したAPRSパケットを処理する（`now` は受信時刻、記録の再生では記録時刻）
+    async fn aprs_packet_received(&self, packet: AprsPacket, now: DateTime<Utc>) -> AppResult<()>;
+    /// 位置パケットを処理するコールサイン（SSIDなし）を置き換える（記録再生用）
+    fn set_buddy_callsigns(&self, callsigns: HashSet<String>);
 
     /// メモリ効率の良いサミットリスト更新（ファイルから2回読み込み）
     async fn update_summit_list_from_file(&self, path: &Path) -> AppResult<usize>;
     /// メモリ効率の良いパークリスト更新（ファイルから2回読み込み）
     async fn update_pota_park_list_from_file(&self, path: &Path) -> AppResult<usize>;
 }
+
+/// ウォッチリストとWebhook配信の管理（利用者ごと）
+#[async_trait]
+pub trait WatchService: Send + Sync + Interface {
+    /// ウォッチを登録する（シークレットが空なら作る）
+    async fn create_watch(&self, watch: Watchlist) -> AppResult<Watchlist>;
+    async fn update_watch(&self, watch: Watchlist) -> AppResult<Watchlist>;
+    async fn delete_watch(&self, user_id: UserId, watch_id: i64) -> AppResult<()>;
+    async fn find_watches(&self, user_id: UserId) -> AppResult<Vec<Watchlist>>;
+    /// ウォッチの配信記録（新しい順）
+    async fn find_deliveries(
+        &self,
+        user_id: UserId,
+        watch_id: i64,
+        limit: i64,
+    ) -> AppResult<Vec<WebhookDelivery>>;
+}
+
+/// スポット・アラートの投稿とアップストリームへの中継（利用者ごと）
+#[async_trait]
+pub trait RelayService: Send + Sync + Interface {
+    /// 参照先を確かめてスポットをローカルに保存し、アップストリームへ中継する
+    async fn post_spot(&self, user_id: UserId, spot: Spot) -> AppResult<RelayPost>;
+    /// 参照先を確かめてアラートをローカルに保存し、アップストリームへ中継する
+    async fn post_alert(&self, user_id: UserId, alert: Alert) -> AppResult<RelayPost>;
+    /// 利用者の投稿と中継結果（新しい順）
+    async fn find_posts(&self, user_id: UserId, limit: i64) -> AppResult<Vec<RelayPost>>;
+}
+
+/// アクティベーションセッション（QSOを逐次記録し、終了時にログファイルを作る）
+#[async_trait]
+pub trait SessionService: Send + Sync + Interface {
+    async fn open_session(&self, session: ActivationSession) -> AppResult<SessionState>;
+    /// 利用者のセッション（新しい順）
+    async fn find_sessions(&self, user_id: UserId) -> AppResult<Vec<SessionState>>;
+    async fn find_session(&self, user_id: UserId, session_id: i64) -> AppResult<SessionState>;
+    /// QSOを登録する（登録済みのクライアントIDは読み飛ばす）
+    async fn add_qsos(
+        &self,
+        user_id: UserId,
+        session_id: i64,
+        qsos: Vec<ClientQso>,
+    ) -> AppResult<QsoPostReport>;
+    async fn find_qsos(&self, user_id: UserId, session_id: i64) -> AppResult<Vec<SessionQso>>;
+    /// セッションを終了してログファイルを作る
+    async fn close_session(&self, user_id: UserId, session_id: i64) -> AppResult<SessionState>;
+    async fn find_file(
+        &self,
+        user_id: UserId,
+        session_id: i64,
+        filename: String,
+    ) -> AppResult<SessionFile>;
+    async fn delete_session(&self, user_id: UserId, session_id: i64) -> AppResult<()>;
+}
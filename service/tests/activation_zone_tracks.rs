@@ -0,0 +1,159 @@
+/// 模擬APRSトラックによるアクティベーションゾーン判定テスト
+///
+/// 固定半径では誤判定していた2つのケースを、地形を模して手で作った位置列で確認する。
+/// （実際の記録ではない。記録の再生は api の aprs_replay テストを参照）
+///   - 山頂が広く平坦なサミット（山頂標識から離れた場所で運用）
+///   - 山頂直下まで車道があるサミット（車道通過時に到着扱いしない）
+///
+/// 実行方法:
+///   cargo test --package service --test activation_zone_tracks
+use chrono::NaiveDate;
+use domain::model::activation::make_test_summit;
+use domain::model::sota::{ActivationZone, SotaReference};
+use serde_json::json;
+use service::implement::activation_zone::{classify_position, ZoneParam, ZoneState};
+
+/// 模擬トラックの点（時刻, 緯度, 経度, 高度）
+type TrackPoint = (&'static str, f64, f64, Option<f64>);
+
+fn summit(code: &str, latitude: f64, longitude: f64, alt_m: i32) -> SotaReference {
+    SotaReference {
+        summit_code: code.to_string(),
+        alt_m,
+        alt_ft: (alt_m as f64 * 3.28084) as i32,
+        longitude,
+        latitude,
+        ..make_test_summit(
+            NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2099, 12, 31).unwrap(),
+        )
+    }
+}
+
+fn classify_track(
+    summit: &SotaReference,
+    zone: Option<&ActivationZone>,
+    track: &[TrackPoint],
+) -> Vec<(&'static str, ZoneState)> {
+    let param = ZoneParam {
+        radius: 100.0,
+        vertical_m: 25.0,
+    };
+    track
+        .iter()
+        .map(|&(time, lat, lon, alt)| {
+            let (state, _) = classify_position(summit, zone, &param, lat, lon, alt);
+            (time, state)
+        })
+        .collect()
+}
+
+fn first_arrival(states: &[(&'static str, ZoneState)]) -> Option<&'static str> {
+    states
+        .iter()
+        .find(|(_, s)| *s == ZoneState::OnSummit)
+        .map(|(t, _)| *t)
+}
+
+/// 平坦な山頂（模擬）: 三角点から東へ約350mの台地上で運用
+const PLATEAU_TRACK: &[TrackPoint] = &[
+    ("08:02:11", 34.9820, 138.9950, None),
+    ("08:31:40", 34.9900, 138.9990, None),
+    ("09:05:02", 34.9955, 139.0020, None),
+    ("09:20:37", 34.9980, 139.0035, None),
+    ("09:28:15", 34.9995, 139.0038, None),
+    ("09:41:50", 34.9998, 139.0039, None),
+    ("10:12:03", 34.9997, 139.0039, None),
+];
+
+fn plateau_zone() -> ActivationZone {
+    let geojson = json!({
+        "type": "Polygon",
+        "coordinates": [[
+            [138.9985, 34.9985],
+            [139.0050, 34.9985],
+            [139.0050, 35.0012],
+            [138.9985, 35.0012],
+            [138.9985, 34.9985]
+        ]]
+    });
+    ActivationZone::from_geometry("JA/YN-101", &geojson).unwrap()
+}
+
+#[test]
+fn plateau_summit_fixed_radius_never_arrives() {
+    let summit = summit("JA/YN-101", 35.0, 139.0, 1800);
+    let states = classify_track(&summit, None, PLATEAU_TRACK);
+
+    assert_eq!(first_arrival(&states), None);
+    assert_eq!(states.last().unwrap().1, ZoneState::Climbing);
+}
+
+#[test]
+fn plateau_summit_zone_detects_arrival() {
+    let summit = summit("JA/YN-101", 35.0, 139.0, 1800);
+    let zone = plateau_zone();
+    let states = classify_track(&summit, Some(&zone), PLATEAU_TRACK);
+
+    assert_eq!(states[0].1, ZoneState::Approaching);
+    assert_eq!(first_arrival(&states), Some("09:28:15"));
+    assert!(states[4..].iter().all(|(_, s)| *s == ZoneState::OnSummit));
+}
+
+/// 山頂直下の車道（模擬）: 山頂の西約80m・標高差40mを車で通過した後、徒歩で登頂
+const ROAD_TRACK: &[TrackPoint] = &[
+    ("13:00:05", 35.1900, 138.4890, Some(1180.0)),
+    ("13:06:40", 35.1970, 138.4930, Some(1410.0)),
+    ("13:09:12", 35.1998, 138.4991, Some(1462.0)),
+    ("13:10:01", 35.2003, 138.4991, Some(1461.0)),
+    ("13:11:30", 35.2010, 138.4988, Some(1458.0)),
+    ("13:25:44", 35.2002, 138.4995, Some(1478.0)),
+    ("13:31:09", 35.2000, 138.5000, Some(1501.0)),
+];
+
+#[test]
+fn road_summit_fixed_radius_false_arrival() {
+    let summit = summit("JA/SO-201", 35.2, 138.5, 1502);
+    let no_alt: Vec<TrackPoint> = ROAD_TRACK
+        .iter()
+        .map(|&(t, lat, lon, _)| (t, lat, lon, None))
+        .collect();
+    let states = classify_track(&summit, None, &no_alt);
+
+    // 高度なし・固定半径では車道通過時に到着と誤判定する
+    assert_eq!(first_arrival(&states), Some("13:09:12"));
+}
+
+#[test]
+fn road_summit_altitude_rejects_road() {
+    let summit = summit("JA/SO-201", 35.2, 138.5, 1502);
+    let states = classify_track(&summit, None, ROAD_TRACK);
+
+    assert_eq!(states[2].1, ZoneState::NearSummit);
+    assert_eq!(states[3].1, ZoneState::NearSummit);
+    assert_eq!(first_arrival(&states), Some("13:25:44"));
+}
+
+#[test]
+fn road_summit_zone_excludes_road() {
+    let summit = summit("JA/SO-201", 35.2, 138.5, 1502);
+    // 車道（西側）を含まない山頂部のポリゴン
+    let geojson = json!({
+        "type": "MultiPolygon",
+        "coordinates": [[[
+            [138.4994, 35.1995],
+            [138.5006, 35.1995],
+            [138.5006, 35.2005],
+            [138.4994, 35.2005],
+            [138.4994, 35.1995]
+        ]]]
+    });
+    let zone = ActivationZone::from_geometry("JA/SO-201", &geojson).unwrap();
+    let no_alt: Vec<TrackPoint> = ROAD_TRACK
+        .iter()
+        .map(|&(t, lat, lon, _)| (t, lat, lon, None))
+        .collect();
+    let states = classify_track(&summit, Some(&zone), &no_alt);
+
+    assert_eq!(first_arrival(&states), Some("13:25:44"));
+}
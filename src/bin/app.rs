@@ -3,9 +3,14 @@ use axum::{http::HeaderValue, Router};
 use chrono::Local;
 use clap::{Parser, Subcommand};
 use common::config::AppConfig;
+use common::dxcc;
+use common::jobs::JobRegistry;
 use firebase_auth_sdk::FireAuth;
 use std::{
+    collections::HashSet,
+    io::Write,
     net::{IpAddr, SocketAddr},
+    path::Path,
     sync::Arc,
 };
 use tokio::{net::TcpListener, sync::watch};
@@ -16,14 +21,16 @@ use tower_http::{
 use tracing_subscriber::EnvFilter;
 
 use adapter::{
-    aprs::connect_aprsis_with,
+    aprs::{connect_aprsis_with, AprsClient},
+    aprs_replay::{position_callsigns, read_recording, AprsReplay},
     database::connect::{
         backup_database, connect_database_with, optimize_database, reset_database, restore_database,
     },
-    geomag::connect_geomag_with,
+    geomag::{connect_geomag_with, GeoMag},
     minikvs::MiniKvs,
 };
-use api::handler::{admin, v2};
+use api::aggregator::aprs_packet::replay_packets;
+use api::handler::{admin, metrics, v2};
 use registry::{AppRegistry, AppState};
 use utoipa_swagger_ui::SwaggerUi;
 
@@ -45,6 +52,34 @@ enum Commands {
         #[command(subcommand)]
         action: DbCommands,
     },
+
+    /// APRS debugging commands
+    Aprs {
+        #[command(subcommand)]
+        action: AprsCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum AprsCommands {
+    /// Replay a recorded packet file (APRS_RECORD_PATH) through the APRS pipeline
+    Replay {
+        /// Recorded packet file (JSON Lines)
+        #[arg(short, long)]
+        input: String,
+
+        /// Playback speed (1 = original timing, 60 = 60x faster, 0 = no wait)
+        #[arg(short, long, default_value_t = 0.0)]
+        speed: f64,
+
+        /// Callsigns to track, comma separated (default: all position senders in the recording)
+        #[arg(short, long, value_delimiter = ',')]
+        buddy: Vec<String>,
+
+        /// Write outbound messages as JSON Lines to this file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -83,6 +118,7 @@ async fn main() -> Result<()> {
 
     match cli.command {
         Some(Commands::Db { action }) => handle_db_command(action).await,
+        Some(Commands::Aprs { action }) => handle_aprs_command(action).await,
         Some(Commands::Serve) | None => bootstrap().await,
     }
 }
@@ -148,6 +184,68 @@ async fn handle_db_command(action: DbCommands) -> Result<()> {
     Ok(())
 }
 
+async fn handle_aprs_command(action: AprsCommands) -> Result<()> {
+    let config = AppConfig::new()?;
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(&config.log_level))
+        .init();
+
+    match action {
+        AprsCommands::Replay {
+            input,
+            speed,
+            buddy,
+            output,
+        } => {
+            let packets = read_recording(Path::new(&input))?;
+            let count = packets.len();
+            let buddy: HashSet<String> = if buddy.is_empty() {
+                position_callsigns(&packets)
+            } else {
+                buddy.into_iter().map(|c| c.to_uppercase()).collect()
+            };
+            println!(
+                "Replaying {} packets from {} ({} callsigns)",
+                count,
+                input,
+                buddy.len()
+            );
+
+            // 状態はDATABASE_URLのDBに書き込まれる。本番DBに対して実行しないこと。
+            let pool = connect_database_with(&config).await?;
+            let replay = Arc::new(AprsReplay::new(packets, speed));
+            let minikvs = Arc::new(MiniKvs::new(config.auth_token_ttl));
+            let module = AppRegistry::new(
+                &config,
+                pool,
+                AprsClient::Replay(replay.clone()),
+                GeoMag::detached(),
+                minikvs,
+            );
+            let registry = Arc::new(module);
+
+            let errors = replay_packets(&registry, buddy, count).await;
+
+            let mut out: Box<dyn Write> = match &output {
+                Some(path) => Box::new(std::fs::File::create(path)?),
+                None => Box::new(std::io::stdout()),
+            };
+            let sent = replay.sent_messages();
+            for message in &sent {
+                writeln!(out, "{}", serde_json::to_string(message)?)?;
+            }
+            println!(
+                "Replay completed: {} packets, {} errors, {} messages sent",
+                count,
+                errors,
+                sent.len()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 async fn bootstrap() -> Result<()> {
     // 起動時刻を記録（admin metricsで使用）
     admin::init_start_time();
@@ -163,6 +261,12 @@ async fn bootstrap() -> Result<()> {
 
     let pool = connect_database_with(&config).await?;
 
+    // DXCC判定用のカントリーファイル（未登録なら判定しない）
+    match dxcc::load_file(Path::new(&config.cty_dat_path)) {
+        Ok(n) => tracing::info!("cty.dat loaded: {} entities", n),
+        Err(e) => tracing::info!("cty.dat not loaded: {}", e),
+    }
+
     // サーバー起動後5分待ってからDB最適化（起動直後のDBロック競合を避けるため）
     let pool_for_optimize = pool.clone();
     tokio::spawn(async move {
@@ -172,11 +276,14 @@ async fn bootstrap() -> Result<()> {
         }
     });
 
+    let jobs = JobRegistry::new(config.shutdown_rx.clone()).await?;
+    jobs.start().await?;
+
     let aprs = connect_aprsis_with(&config).await?;
-    let geomag = connect_geomag_with(&config).await?;
+    let geomag = connect_geomag_with(&config, &jobs).await?;
     let minikvs = Arc::new(MiniKvs::new(config.auth_token_ttl));
     let module = AppRegistry::new(&config, pool, aprs, geomag, minikvs);
-    let app_state = AppState::new(module, config.clone());
+    let app_state = AppState::new(module, config.clone(), jobs);
     let job_state = app_state.clone();
 
     let firebase = FireAuth::new(config.firebase_api_key.clone());
@@ -214,6 +321,7 @@ async fn bootstrap() -> Result<()> {
         tracing::info!("OpenAPI disabled");
     }
 
+    let metrics_app = metrics::build_metrics_routers().with_state(app_state.clone());
     let app = app
         .with_state(app_state)
         .layer(cors)
@@ -228,12 +336,24 @@ async fn bootstrap() -> Result<()> {
             .await
             .map_err(Error::from)
     };
+    // メトリクスは本体とは別のアドレスでだけ公開する
+    let metrics_server = async {
+        let Some(metrics_addr) = &config.metrics_addr else {
+            return Ok::<(), Error>(());
+        };
+        let listener = TcpListener::bind(metrics_addr).await?;
+        tracing::info!("Metrics listening on {}", metrics_addr);
+        axum::serve(listener, metrics_app)
+            .with_graceful_shutdown(shudown_signal(config.shutdown_rx.clone()))
+            .await
+            .map_err(Error::from)
+    };
     let job_monitor = async { api::aggregator::builder::build(&config, &job_state).await };
 
     tracing::info!("DATABASE_URL = {}", config.database);
     tracing::info!("Listening on {}", addr);
 
-    let _res = tokio::join!(job_monitor, http);
+    let _res = tokio::join!(job_monitor, http, metrics_server);
 
     Ok(())
 }